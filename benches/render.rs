@@ -0,0 +1,67 @@
+//! Benchmarks for drawing one frame of the list views with a large config,
+//! the work done on every key press and resize.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+use nirikiri::model::{
+    AppearanceViewModel, BindingAction, BindingProperties, Keybinding, KeybindingChange, KeybindingsViewModel,
+    Modifiers, SelectableList,
+};
+use nirikiri::view::{AppearanceListWidget, KeybindingDetailWidget, KeybindingsListWidget};
+
+/// A keybindings view of 2300 spawn binds with a few staged changes
+fn large_view_model() -> KeybindingsViewModel {
+    let bindings: Vec<Keybinding> = (0..2300)
+        .map(|i| {
+            let (modifiers, key) = Modifiers::parse(&format!("Mod+Shift+Ctrl+Alt+F{i}"));
+            Keybinding {
+                modifiers,
+                key,
+                properties: BindingProperties::default(),
+                action: BindingAction::Spawn(vec![format!("app-{i}"), "--new-window".to_string()]),
+                kdl_index: Some(i),
+                source: None,
+            }
+        })
+        .collect();
+    let mut modified = bindings[1200].clone();
+    modified.action = BindingAction::Simple("close-window".to_string());
+    KeybindingsViewModel {
+        pending_changes: [KeybindingChange::Delete(10), KeybindingChange::Modify { index: 1200, new: modified }]
+            .into_iter()
+            .collect(),
+        bindings,
+        list: SelectableList { selected_index: 1150, scroll_offset: 1130, ..Default::default() },
+        ..Default::default()
+    }
+}
+
+fn draw_keybindings(view_model: &KeybindingsViewModel, buffer: &mut Buffer) {
+    let area = buffer.area;
+    let (list, detail) = (Rect { width: area.width / 2, ..area }, Rect { x: area.width / 2, ..area });
+    let filtered = view_model.filtered_bindings();
+    KeybindingsListWidget::new(view_model, true).filtered(&filtered).render(list, buffer);
+    let selected = filtered.get(view_model.list.selected_index).copied();
+    KeybindingDetailWidget::with_status(selected.map(|eb| eb.binding), selected.map(|eb| eb.status))
+        .render(detail, buffer);
+}
+
+fn bench_render(c: &mut Criterion) {
+    let area = Rect::new(0, 0, 200, 60);
+    let mut buffer = Buffer::empty(area);
+
+    let mut view_model = large_view_model();
+    c.bench_function("draw_keybindings", |b| b.iter(|| draw_keybindings(&view_model, &mut buffer)));
+
+    view_model.set_search("app-11".to_string());
+    view_model.list.selected_index = 5;
+    c.bench_function("draw_keybindings_search", |b| b.iter(|| draw_keybindings(&view_model, &mut buffer)));
+
+    let appearance = AppearanceViewModel::default();
+    c.bench_function("draw_appearance", |b| {
+        b.iter(|| AppearanceListWidget::new(&appearance, true).render(area, &mut buffer))
+    });
+}
+
+criterion_group!(benches, bench_render);
+criterion_main!(benches);
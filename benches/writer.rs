@@ -0,0 +1,120 @@
+//! Benchmarks for preparing a save on a large config: applying staged changes
+//! to the parsed document and serializing it.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use kdl::KdlDocument;
+use nirikiri::config::kdl_format::ensure_v1_new;
+use nirikiri::config::{
+    apply_appearance, apply_keybindings, apply_output_config, parse_appearance, parse_keybindings,
+};
+use nirikiri::model::{BindingAction, ConfigDocument, KeybindingChange, Modifiers, OutputChange, Position};
+
+/// Build a config of roughly 3000 lines: many outputs, a layout block and a large binds block
+fn large_config() -> String {
+    let mut source = String::new();
+    for i in 0..100 {
+        source.push_str(&format!(
+            "// Monitor {i}\noutput \"DP-{i}\" {{\n    mode \"2560x1440@144\"\n    scale 1.0\n    position x={} y=0\n}}\n\n",
+            i * 2560
+        ));
+    }
+    source.push_str(
+        "layout {\n    gaps 16\n    center-focused-column \"never\"\n    focus-ring {\n        width 4\n        active-color \"#7fc8ff\"\n        inactive-color \"#505050\"\n    }\n    border {\n        off\n    }\n}\n\n",
+    );
+    source.push_str("binds {\n");
+    for i in 0..2300 {
+        source.push_str(&format!(
+            "    Mod+Shift+Ctrl+Alt+F{i} hotkey-overlay-title=\"Run {i}\" {{ spawn \"app-{i}\"; }}\n"
+        ));
+    }
+    source.push_str("}\n");
+    source
+}
+
+fn parse(source: &str) -> ConfigDocument {
+    ConfigDocument {
+        doc: KdlDocument::parse_v1(source).unwrap(),
+        path: PathBuf::from("/tmp/nirikiri-bench.kdl"),
+        source: source.to_string(),
+    }
+}
+
+/// Everything `save` does to the document before rendering it
+fn prepare(config: &mut ConfigDocument) {
+    ensure_v1_new(&mut config.doc);
+}
+
+fn bench_writers(c: &mut Criterion) {
+    let source = large_config();
+    let base = parse(&source);
+
+    let bindings = parse_keybindings(&base);
+    let mut modified = bindings[1200].clone();
+    modified.action = BindingAction::Simple("close-window".to_string());
+    let (modifiers, key) = Modifiers::parse("Mod+Shift+Q");
+    let added = nirikiri::model::Keybinding {
+        modifiers,
+        key,
+        action: BindingAction::Simple("quit".to_string()),
+        kdl_index: None,
+        source: None,
+        ..modified.clone()
+    };
+    let changes = vec![
+        KeybindingChange::Modify { index: 1200, new: modified },
+        KeybindingChange::Add(added),
+    ];
+    c.bench_function("apply_keybindings", |b| {
+        b.iter_batched_ref(
+            || parse(&source),
+            |config| {
+                apply_keybindings(config, &changes).unwrap();
+                prepare(config)
+            },
+            BatchSize::LargeInput,
+        )
+    });
+
+    let original = parse_appearance(&base);
+    let mut settings = original.clone();
+    settings.gaps = 24;
+    settings.focus_ring.width = 2;
+    c.bench_function("apply_appearance", |b| {
+        b.iter_batched_ref(
+            || parse(&source),
+            |config| {
+                apply_appearance(config, &original, &settings);
+                prepare(config)
+            },
+            BatchSize::LargeInput,
+        )
+    });
+
+    let change = OutputChange { position: Some(Position::new(0, 1440)), ..Default::default() };
+    let changes = HashMap::from([("DP-50".to_string(), change)]);
+    c.bench_function("apply_output_config", |b| {
+        b.iter_batched_ref(
+            || parse(&source),
+            |config| {
+                apply_output_config(config, &changes).unwrap();
+                prepare(config)
+            },
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+fn bench_save(c: &mut Criterion) {
+    let source = large_config();
+    c.bench_function("prepare_unchanged", |b| {
+        b.iter_batched_ref(|| parse(&source), prepare, BatchSize::LargeInput)
+    });
+    let config = parse(&source);
+    c.bench_function("render", |b| b.iter(|| config.doc.to_string()));
+}
+
+criterion_group!(benches, bench_writers, bench_save);
+criterion_main!(benches);
@@ -1,240 +1,1997 @@
 use anyhow::Result;
-use crossterm::event::{self, Event, KeyCode, KeyModifiers};
+use crossterm::event::{
+    self, Event, KeyCode, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
 use ratatui::{
-    layout::{Constraint, Direction, Layout},
+    layout::{Constraint, Direction, Layout, Rect},
     Frame,
 };
-use std::time::Duration;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
 
+use crate::binding_tags::{BindingTagEditor, BindingTags};
 use crate::category::Category;
 use crate::config::{
-    get_configured_positions, load_config, parse_appearance, parse_keybindings, write_appearance,
-    write_keybindings, write_positions,
+    appearance_field_kdl, apply_appearance, commit_config, commit_message, copy_system_config, default_config_path, discover_config, ensure_included, fragment_path, includes_fragment,
+    get_config_path, get_configured_positions, keybinding_kdl, load_config, load_default_appearance, load_default_binds,
+    load_editable_config, locate_appearance_fields, locate_general_fields, owned_blocks, parse_general, parse_appearance, parse_bind_nodes, set_config_path,
+    parse_keybindings, unmodeled_parts, workspace_spawn_kdl, write_appearance, write_general, write_keybindings, write_merged_outputs, write_output_config,
+    write_template,
 };
-use crate::ipc::NiriClient;
-use crate::message::Message;
+use crate::diagnostics::Diagnostics;
+use crate::error::{AppError, ErrorLog};
+use crate::hooks::{run_hooks, HookEvent, HookPayload};
+use crate::i18n;
+use crate::ipc::{connect_backend, watch_outputs, CompositorBackend, NiriClient};
+use crate::message::{AppearanceMessage, GeneralMessage, KeybindingsMessage, Message, OutputMessage};
+use crate::output_labels::{OutputLabelEditor, OutputLabels};
+use crate::output_profiles::{fingerprint, Fingerprint, OutputProfile, OutputProfiles, ProfilePicker};
+use crate::model::keys;
+use crate::model::nested_preview::preview_files;
 use crate::model::{
-    AppearanceEditMode, AppearanceField, AppearanceListItem, AppearanceViewModel, ColorEditField,
-    ConfigDocument, EditField, EditMode, FieldValue, KeybindingChange, KeybindingsViewModel,
-    OutputViewModel,
+    AppearanceEditMode, AppearanceListItem, AppearanceSection, AppearanceViewModel, BindOverlay, BindingFlag, BindingStatus,
+    ColorEditField, OutputPane, OutputTransform, ConflictChoice, ConfigDiscovery,
+    ConfigDocument, DryRun, EditField, NestedPreview, EditMode, FieldValue, GeneralViewModel, HealthReport, Keybinding, KeybindingsViewModel, Modifiers,
+    OutputChange, OutputColorEditor, OutputPositionEditor, OutputViewModel, QuickOpen, position_moves, SourceLocation, TemplateGallery, TemplatePrompt, WorkspaceSpawnWizard, Tutorial, BACKGROUND_LOAD_BYTES,
 };
+use crate::color_history::ColorHistory;
+use crate::recent_configs::RecentConfigs;
+use crate::settings::Settings;
+use crate::ui_state::UiState;
 use crate::update::update_output;
 use crate::view::{
-    AppearanceDetailWidget, AppearanceEditWidget, AppearanceListWidget, KeybindingDetailWidget,
-    KeybindingEditWidget, KeybindingsListWidget, OutputInfoWidget, OutputListWidget,
-    StatusBarWidget, TabBarWidget,
+    AppearanceDetailWidget, AppearanceEditWidget, AppearanceListWidget, AppearanceSectionEditWidget, GeneralDetailWidget,
+    GeneralEditWidget, GeneralListWidget,
+    BindOverlayWidget, BindingConflictWidget, BindingGeneratorWidget, BindingTagsWidget, BindingTemplateWidget,
+    ColorMode, ConfigSourceWidget, DebugOverlayWidget, DisabledOutputWarningWidget, DryRunWidget, ErrorLogWidget, HealthReportWidget, KeybindingDetailWidget,
+    KeybindingEditWidget, KeybindingsListWidget, LockoutWarningWidget, ModeBrowserWidget, OutputInfoWidget,
+    OutputColorsWidget, OutputLabelWidget, OutputProfilesWidget, OutputListWidget, OutputPositionWidget,
+    QuickOpenWidget, SaveConflictWidget, StatusBarWidget, TabBarWidget, TemplateGalleryWidget, TutorialWidget,
+    WorkspaceSpawnWidget,
 };
 use crate::widgets::{CanvasViewport, MonitorCanvasWidget};
 
+/// How long a preview stays on the live session without being kept
+const PREVIEW_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// How long output moves settle before live preview sends them
+const LIVE_PREVIEW_DELAY: Duration = Duration::from_millis(150);
+/// How long an identified output stays upside down
+const IDENTIFY_DURATION: Duration = Duration::from_millis(1500);
+
+/// Output changes previewed on the live session, undone unless kept before
+/// the deadline
+pub struct ActivePreview {
+    pub deadline: Instant,
+    /// What to send back to niri to undo the preview
+    pub revert: HashMap<String, OutputChange>,
+}
+
+/// An output shown upside down for a moment, so it can be told apart from
+/// the others on the desk
+pub struct IdentifyFlash {
+    pub output: String,
+    /// The transform to put back
+    pub transform: OutputTransform,
+    pub until: Instant,
+}
+
+/// A config file being parsed on a background thread
+pub struct ConfigLoad {
+    pub receiver: Receiver<Result<ConfigDocument>>,
+    pub size: u64,
+    pub scope: ReloadScope,
+}
+
+/// Which categories a reload re-reads and discards staged changes for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReloadScope {
+    Category(Category),
+    All,
+}
+
+impl ReloadScope {
+    pub fn includes(&self, category: Category) -> bool {
+        match self {
+            ReloadScope::Category(c) => *c == category,
+            ReloadScope::All => true,
+        }
+    }
+}
+
 /// Main application state
 pub struct App {
     pub current_category: Category,
     pub view_model: OutputViewModel,
     pub keybindings_view_model: KeybindingsViewModel,
     pub appearance_view_model: AppearanceViewModel,
+    pub general_view_model: GeneralViewModel,
     pub config: Option<ConfigDocument>,
     pub viewport: CanvasViewport,
-    pub error: Option<String>,
+    /// Where the canvas was last drawn, for mouse input; None while hidden
+    pub canvas_area: Option<Rect>,
+    pub error: Option<AppError>,
+    /// Recent errors, browsable in the error log dialog
+    pub error_log: ErrorLog,
+    /// Informational message shown in the status bar (not an error)
+    pub notice: Option<String>,
     pub should_quit: bool,
+    /// nirikiri's own settings
+    pub settings: Settings,
+    /// Colors recently entered in the color editor
+    pub color_history: ColorHistory,
+    /// Pane sizes, saved whenever they change
+    pub ui_state: UiState,
+    pub recent_configs: RecentConfigs,
+    /// Friendly names and notes for outputs
+    pub output_labels: OutputLabels,
+    /// Named output arrangements to switch between
+    pub output_profiles: OutputProfiles,
+    /// The monitors last checked against the profiles, so each set is
+    /// suggested or applied once rather than on every refresh
+    pub profile_fingerprint: Option<Fingerprint>,
+    /// Contrast and NO_COLOR handling for the whole frame
+    pub color_mode: ColorMode,
+    /// niri IPC socket all requests go to (None if none could be found)
+    pub socket_path: Option<PathBuf>,
+    /// Reload that was refused because it would drop staged changes;
+    /// repeating the same reload confirms it
+    pub pending_reload: Option<ReloadScope>,
+    /// Output block merge waiting for M to be pressed again
+    pub pending_merge: bool,
+    /// Category whose save was stopped because the file changed on disk
+    pub save_conflict: Option<Category>,
+    /// Template chooser for adding sections the config lacks
+    pub template_gallery: Option<TemplateGallery>,
+    pub quick_open: Option<QuickOpen>,
+    /// Dialog showing which config is edited and where else it was looked for
+    pub config_source: Option<ConfigDiscovery>,
+    /// Wizard adding the config pieces that open an app on a named workspace
+    pub workspace_spawn: Option<WorkspaceSpawnWizard>,
+    pub label_editor: Option<OutputLabelEditor>,
+    pub profile_picker: Option<ProfilePicker>,
+    pub color_editor: Option<OutputColorEditor>,
+    pub position_editor: Option<OutputPositionEditor>,
+    /// Nested niri showing the staged appearance changes; dropping it
+    /// stops niri and removes its temporary config
+    pub nested_preview: Option<NestedPreview>,
+    /// Pending background parse of a large config file
+    pub config_load: Option<ConfigLoad>,
+    /// Set when the user asked to open the config in an external editor;
+    /// the main loop suspends the TUI, runs the editor and clears it
+    pub editor_request: Option<SourceLocation>,
+    /// Startup checks, shown before the main UI until dismissed
+    pub health_report: Option<HealthReport>,
+    /// Guided tour shown over the normal UI
+    pub tutorial: Option<Tutorial>,
+    /// Output preview counting down to its automatic revert
+    pub preview: Option<ActivePreview>,
+    pub identify: Option<IdentifyFlash>,
+    /// Signals from the event stream thread that outputs were plugged in or
+    /// out; None when niri couldn't be watched
+    pub output_events: Option<Receiver<()>>,
+    /// When live preview sends the staged positions, pushed back by each move
+    pub live_preview_due: Option<Instant>,
+    /// Timings for the debug overlay; None while it is hidden
+    pub diagnostics: Option<Diagnostics>,
 }
 
 impl App {
-    pub fn new() -> Result<Self> {
+    /// `accessible` is the --accessible flag; the settings file can turn it on too
+    pub fn new(socket_path: Option<PathBuf>, accessible: bool) -> Result<Self> {
         let mut app = Self {
             current_category: Category::default(),
             view_model: OutputViewModel::default(),
             keybindings_view_model: KeybindingsViewModel::default(),
             appearance_view_model: AppearanceViewModel::default(),
+            general_view_model: GeneralViewModel::default(),
             config: None,
             viewport: CanvasViewport::default(),
+            canvas_area: None,
             error: None,
+            error_log: ErrorLog::default(),
+            notice: None,
             should_quit: false,
+            editor_request: None,
+            health_report: None,
+            tutorial: None,
+            preview: None,
+            output_events: None,
+            live_preview_due: None,
+            identify: None,
+            diagnostics: None,
+            config_load: None,
+            template_gallery: None,
+            quick_open: None,
+            config_source: None,
+            workspace_spawn: None,
+            label_editor: None,
+            profile_picker: None,
+            color_editor: None,
+            position_editor: None,
+            nested_preview: None,
+            save_conflict: None,
+            pending_reload: None,
+            pending_merge: false,
+            socket_path,
+            settings: Settings::default(),
+            color_history: ColorHistory::default(),
+            ui_state: UiState::default(),
+            recent_configs: RecentConfigs::default(),
+            output_labels: OutputLabels::default(),
+            output_profiles: OutputProfiles::default(),
+            profile_fingerprint: None,
+            color_mode: ColorMode::Normal,
         };
 
-        // Initialize
-        app.load_outputs()?;
-        app.load_config();
+        match Settings::load() {
+            Ok(settings) => app.settings = settings,
+            Err(e) => app.set_error(AppError::parse("Failed to load nirikiri settings", &e)),
+        }
+        if let Some(language) = i18n::detect_language(app.settings.language.as_deref()) {
+            match i18n::load_catalog(&language) {
+                Ok(Some(catalog)) => i18n::install(catalog),
+                Ok(None) => {}
+                Err(e) => app.set_error(AppError::parse("Failed to load translations", &e)),
+            }
+        }
+        let no_color = std::env::var("NO_COLOR").ok();
+        app.color_mode = ColorMode::detect(accessible || app.settings.accessible, no_color.as_deref());
+        match ColorHistory::load() {
+            Ok(history) => app.color_history = history,
+            Err(e) => app.set_error(AppError::parse("Failed to load color history", &e)),
+        }
+        match UiState::load() {
+            Ok(state) => app.ui_state = state,
+            Err(e) => app.set_error(AppError::parse("Failed to load UI state", &e)),
+        }
+        match BindingTags::load() {
+            Ok(tags) => app.keybindings_view_model.tags = tags,
+            Err(e) => app.set_error(AppError::parse("Failed to load binding tags", &e)),
+        }
+        match OutputLabels::load() {
+            Ok(labels) => app.output_labels = labels,
+            Err(e) => app.set_error(AppError::parse("Failed to load output labels", &e)),
+        }
+        match OutputProfiles::load() {
+            Ok(profiles) => app.output_profiles = profiles,
+            Err(e) => app.set_error(AppError::parse("Failed to load output profiles", &e)),
+        }
+        match RecentConfigs::load() {
+            Ok(recent) => app.recent_configs = recent,
+            Err(e) => app.set_error(AppError::parse("Failed to load recent configs", &e)),
+        }
+        // Remember configs opened with --config
+        if let (Ok(path), Ok(live)) = (get_config_path(), default_config_path()) {
+            if path != live {
+                app.record_recent_config(&path);
+            }
+        }
+
+        // Initialize; failures are reported by the health check rather than
+        // keeping nirikiri from starting
+        let outputs = app.load_outputs();
+        if let Err(e) = &outputs {
+            app.set_error(AppError::ipc("Failed to query outputs", e));
+        }
+        app.load_config(ReloadScope::All);
+        app.run_health_check(outputs.as_ref().err());
+        app.check_config_source();
+        // Without niri there is nothing to watch; the health check says why
+        app.output_events = watch_outputs(app.socket_path.as_deref()).ok();
 
         Ok(app)
     }
 
+    /// Check niri and the config file, keeping the report if anything is wrong
+    fn run_health_check(&mut self, niri_error: Option<&anyhow::Error>) {
+        let mut report = HealthReport::default();
+        report.check_niri(niri_error.map_or(Ok(()), Err));
+        let loaded = match (&self.config, &self.config_load) {
+            (Some(config), _) => Some(Ok(config)),
+            (None, Some(_)) => None,
+            (None, None) => Some(Err(self
+                .error
+                .as_ref()
+                .map_or_else(|| "not loaded".to_string(), |e| e.to_string()))),
+        };
+        let path = get_config_path().ok();
+        report.check_config(path.as_deref(), loaded);
+        if self.settings.managed_fragment {
+            if let (Some(path), Ok(main)) = (&path, load_config()) {
+                report.check_fragment(&fragment_path(path), includes_fragment(&main));
+            }
+        }
+        self.health_report = report.has_problems().then_some(report);
+    }
+
+    /// Offer to copy the config when niri only found a system one we can't write
+    fn check_config_source(&mut self) {
+        if let Ok(discovery) = discover_config() {
+            if discovery.needs_copy() {
+                self.set_error(AppError::validation(format!(
+                    "Only the read-only system config {} was found; press c to copy it to your config directory",
+                    discovery.path().display()
+                )));
+                self.config_source = Some(discovery);
+            }
+        }
+    }
+
+    /// Copy the system config to the user path and edit the copy
+    fn copy_system_config(&mut self) {
+        let Some(discovery) = &self.config_source else {
+            return;
+        };
+        if !discovery.needs_copy() {
+            return;
+        }
+        match copy_system_config(discovery) {
+            Ok(path) => {
+                self.reload(ReloadScope::All);
+                self.config_source = discover_config().ok();
+                self.notice = Some(format!("Copied to {}; niri reads the copy from now on", path.display()));
+            }
+            Err(e) => self.set_error(AppError::write("Failed to copy the system config", &e)),
+        }
+    }
+
+    fn connect(&self) -> Result<Box<dyn CompositorBackend>> {
+        connect_backend(self.socket_path.as_deref())
+    }
+
+    /// Connect and send one request, timing the round trip for the debug overlay
+    fn ipc<T>(&mut self, what: &str, request: impl FnOnce(&mut dyn CompositorBackend) -> Result<T>) -> Result<T> {
+        let start = Instant::now();
+        let result = self.connect().and_then(|mut client| request(client.as_mut()));
+        self.record_ipc(what, start);
+        result
+    }
+
+    fn record_ipc(&mut self, what: &str, start: Instant) {
+        if let Some(diagnostics) = &mut self.diagnostics {
+            diagnostics.record_ipc(what, start.elapsed());
+        }
+    }
+
     fn load_outputs(&mut self) -> Result<()> {
-        let mut client = NiriClient::connect()?;
-        self.view_model.outputs = client.get_outputs()?;
+        self.view_model.outputs = self.ipc("get outputs", |client| client.get_outputs())?;
         Ok(())
     }
 
-    fn load_config(&mut self) {
-        match load_config() {
-            Ok(config) => {
-                // Mark outputs that have config entries
-                let positions = get_configured_positions(&config);
-                for (name, _) in &positions {
-                    if let Some(output) = self.view_model.outputs.iter_mut().find(|o| &o.name == name)
-                    {
-                        output.configured = true;
-                    }
+    fn load_config(&mut self, scope: ReloadScope) {
+        let size = get_config_path()
+            .ok()
+            .and_then(|path| std::fs::metadata(path).ok())
+            .map_or(0, |m| m.len());
+
+        // Parse large configs on a worker thread so the UI keeps redrawing
+        if size > BACKGROUND_LOAD_BYTES {
+            let (sender, receiver) = mpsc::channel();
+            let managed = self.settings.managed_fragment;
+            std::thread::spawn(move || {
+                let _ = sender.send(load_editable_config(managed));
+            });
+            self.config = None;
+            self.config_load = Some(ConfigLoad { receiver, size, scope });
+            return;
+        }
+
+        self.config_load = None;
+        let result = load_editable_config(self.settings.managed_fragment);
+        self.finish_config_load(result, scope);
+    }
+
+    /// Pick up the result of a background config load, if it has finished
+    pub fn poll_config_load(&mut self) {
+        let Some(load) = &self.config_load else {
+            return;
+        };
+        let scope = load.scope;
+        let result = match load.receiver.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => Err(anyhow::anyhow!("config loader thread exited")),
+        };
+        self.config_load = None;
+        self.finish_config_load(result, scope);
+    }
+
+    /// Re-read the outputs after a hotplug reported by the event stream
+    pub fn poll_output_events(&mut self) {
+        let Some(events) = &self.output_events else {
+            return;
+        };
+        let mut changed = false;
+        loop {
+            match events.try_recv() {
+                Ok(()) => changed = true,
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    // niri went away or restarted; r still refreshes by hand
+                    self.output_events = None;
+                    break;
                 }
+            }
+        }
+        if changed {
+            self.update(OutputMessage::RefreshOutputs.into());
+            if self.error.is_none() {
+                self.notice = Some("Outputs changed; list refreshed".to_string());
+                self.check_output_profiles();
+            }
+        }
+    }
+
+    /// Show the output of a dry run once its command has finished
+    pub fn poll_dry_run(&mut self) {
+        if let Some(dry_run) = &mut self.keybindings_view_model.dry_run {
+            dry_run.poll();
+        }
+    }
+
+    /// Follow the staged appearance changes in the nested niri, and notice
+    /// when it exits (e.g. its window was closed)
+    pub fn poll_nested_preview(&mut self) {
+        let Some(preview) = &mut self.nested_preview else {
+            return;
+        };
+        if let Some(exit) = preview.poll_exit() {
+            self.nested_preview = None;
+            self.notice = Some(format!("Nested niri exited ({exit})"));
+            return;
+        }
+        if preview.settings == self.appearance_view_model.settings {
+            return;
+        }
+        let settings = self.appearance_view_model.settings.clone();
+        let result = self.nested_preview_files().and_then(|files| match &mut self.nested_preview {
+            Some(preview) => preview.update(&files, settings),
+            None => Ok(()),
+        });
+        if let Err(e) = result {
+            self.nested_preview = None;
+            self.set_error(AppError::write("Failed to update the nested niri config; closed it", &e));
+        }
+    }
 
+    /// Start a nested niri on the config with the staged appearance
+    /// changes, or close the running one
+    fn toggle_nested_preview(&mut self) {
+        if self.nested_preview.take().is_some() {
+            self.notice = Some("Nested niri closed".to_string());
+            return;
+        }
+        let settings = self.appearance_view_model.settings.clone();
+        match self.nested_preview_files().and_then(|files| NestedPreview::launch(&files, settings)) {
+            Ok(preview) => {
+                self.notice = Some(format!(
+                    "Nested niri started on {}; staged appearance changes show there",
+                    preview.config_path().display()
+                ));
+                self.nested_preview = Some(preview);
+            }
+            Err(e) => self.set_error(AppError::ipc("Failed to start a nested niri", &e)),
+        }
+    }
+
+    /// The edited config with the staged appearance changes applied in
+    /// memory, as files for the nested niri
+    fn nested_preview_files(&self) -> Result<Vec<(&'static str, String)>> {
+        let Some(config) = &self.config else {
+            anyhow::bail!("No config loaded");
+        };
+        let mut edited =
+            ConfigDocument { doc: config.doc.clone(), path: config.path.clone(), source: config.source.clone() };
+        let view_model = &self.appearance_view_model;
+        apply_appearance(&mut edited, &view_model.original_settings, &view_model.settings);
+        let main = if self.settings.managed_fragment { Some(load_config()?) } else { None };
+        Ok(preview_files(&edited, main.as_ref()))
+    }
+
+    /// Load niri's default layout values for the running version, once
+    fn load_appearance_defaults(&mut self) {
+        if self.appearance_view_model.defaults_release.is_some() {
+            return;
+        }
+        // Unknown versions fall back to the newest snapshot
+        let version = NiriClient::connect(self.socket_path.as_deref())
+            .and_then(|mut c| c.version())
+            .ok();
+        match load_default_appearance(version.as_deref()) {
+            Ok((release, defaults)) => {
+                self.appearance_view_model.defaults = defaults;
+                self.appearance_view_model.defaults_release = Some(release);
+            }
+            Err(e) => self.set_error(AppError::parse("Failed to load niri's default layout", &e)),
+        }
+    }
+
+    /// Install a freshly loaded config, re-deriving state only for the
+    /// categories in scope so other categories keep their staged changes
+    fn finish_config_load(&mut self, result: Result<ConfigDocument>, scope: ReloadScope) {
+        match result {
+            Ok(config) => {
                 // Load keybindings
-                self.keybindings_view_model.bindings = parse_keybindings(&config);
+                if scope.includes(Category::Keybindings) {
+                    self.keybindings_view_model.bindings = parse_keybindings(&config);
+                }
 
                 // Load appearance settings
-                let appearance_settings = parse_appearance(&config);
-                self.appearance_view_model = AppearanceViewModel::new(appearance_settings);
+                if scope.includes(Category::Appearance) {
+                    let appearance_settings = parse_appearance(&config);
+                    let mut view_model = AppearanceViewModel::new(appearance_settings);
+                    view_model.defaults = std::mem::take(&mut self.appearance_view_model.defaults);
+                    view_model.defaults_release = self.appearance_view_model.defaults_release.take();
+                    self.appearance_view_model = view_model;
+                }
+                self.appearance_view_model.field_sources = locate_appearance_fields(&config);
+
+                if scope.includes(Category::General) {
+                    self.general_view_model = GeneralViewModel::new(parse_general(&config));
+                }
+                self.general_view_model.field_sources = locate_general_fields(&config);
 
                 self.config = Some(config);
+                if scope.includes(Category::Outputs) {
+                    self.sync_outputs_with_config();
+                    self.check_output_profiles();
+                }
+            }
+            Err(e) => {
+                self.set_error(AppError::parse("Failed to load config", &e));
+            }
+        }
+    }
+
+    /// Show an error in the status bar and record it in the error log
+    fn set_error(&mut self, error: AppError) {
+        self.error_log.push(error.clone());
+        self.error = Some(error);
+    }
+
+    /// Mark outputs that have config entries, then stage a position for any
+    /// new output that would otherwise overlap another screen
+    fn sync_outputs_with_config(&mut self) {
+        if let Some(config) = &self.config {
+            let positions = get_configured_positions(config);
+            for output in &mut self.view_model.outputs {
+                output.configured = positions.iter().any(|(name, _)| name == &output.name);
+                output.source = config.output_location(&output.name);
+                output.duplicate_sources = config.output_locations(&output.name).into_iter().skip(1).collect();
+                output.background_color = config.get_output_string(&output.name, "background-color");
+                output.backdrop_color = config.get_output_string(&output.name, "backdrop-color");
+                output.focus_at_startup = config.has_output_flag(&output.name, "focus-at-startup");
+            }
+        }
+
+        let staged = self.view_model.stage_new_output_positions();
+        if !staged.is_empty() {
+            self.notice = Some(format!(
+                "New output {} placed to the right; press s to save",
+                staged.join(", ")
+            ));
+        }
+
+        let duplicates = self.config.as_ref().map(|c| c.duplicate_outputs()).unwrap_or_default();
+        if !duplicates.is_empty() {
+            self.set_error(AppError::validation(format!(
+                "{} defined in more than one output block; only the first is used. Press M in Outputs to merge",
+                duplicates.join(", ")
+            )));
+        }
+    }
+
+    /// Fold duplicate output blocks into the first block of each output,
+    /// asking for M again first since it rewrites the config straight away
+    fn merge_output_blocks(&mut self) {
+        let Some(config) = &self.config else {
+            self.set_error(AppError::validation("No config loaded"));
+            return;
+        };
+        let names = config.duplicate_outputs();
+        if names.is_empty() {
+            self.pending_merge = false;
+            self.notice = Some("No output is defined in more than one block".to_string());
+            return;
+        }
+        if !self.pending_merge {
+            self.pending_merge = true;
+            self.notice = Some(format!(
+                "Merge the output blocks of {} into the first one and save? Press M again to confirm",
+                names.join(", ")
+            ));
+            return;
+        }
+        self.pending_merge = false;
+        self.notice = None;
+
+        match config.changed_on_disk() {
+            Ok(false) => {}
+            Ok(true) => {
+                self.set_error(AppError::validation("Config changed on disk; reload (r) before merging output blocks"));
+                return;
+            }
+            Err(e) => {
+                self.set_error(AppError::write("Failed to check config before saving", &e));
+                return;
+            }
+        }
+        let summary: Vec<String> = names.iter().map(|name| format!("merge output blocks of {name}")).collect();
+        if !self.run_hooks(HookEvent::PreSave, Some(Category::Outputs), &summary) {
+            return;
+        }
+        let Some(config) = &mut self.config else {
+            return;
+        };
+        match write_merged_outputs(config) {
+            Ok(names) if names.is_empty() => {
+                self.notice = Some("No output is defined in more than one block".to_string());
+            }
+            Ok(names) => {
+                for output in &mut self.view_model.outputs {
+                    output.source = config.output_location(&output.name);
+                    output.duplicate_sources.clear();
+                }
+                self.error = None;
+                self.notice = Some(format!("Merged the output blocks of {}", names.join(", ")));
+                self.finish_save(Category::Outputs, &summary);
+                if let Err(e) = self.ipc("reload config", |client| client.reload_config()) {
+                    self.set_error(AppError::ipc("Saved, but failed to reload niri config", &e));
+                }
+            }
+            Err(e) => {
+                self.set_error(AppError::write("Failed to merge output blocks", &e));
+            }
+        }
+    }
+
+    /// Save the outputs as they would be with the staged changes, under the
+    /// name typed in the profile dialog
+    fn save_output_profile(&mut self) {
+        let Some(name) = self.profile_picker.as_ref().and_then(|p| p.naming.as_ref()).map(|f| f.value.trim().to_string())
+        else {
+            return;
+        };
+        if name.is_empty() {
+            self.set_error(AppError::validation("Give the profile a name"));
+            return;
+        }
+        let profile = OutputProfile::capture(&name, &self.view_model.outputs, &self.view_model.pending_changes);
+        let count = profile.outputs.len();
+        self.output_profiles.set(profile);
+        if let Err(e) = self.output_profiles.save() {
+            self.set_error(AppError::write("Failed to save output profiles", &e));
+            return;
+        }
+        if let Some(picker) = &mut self.profile_picker {
+            picker.naming = None;
+            if let Some(index) = self.output_profiles.profiles.iter().position(|p| p.name == name) {
+                picker.list.selected_index = index;
+            }
+        }
+        self.notice = Some(format!("Saved profile {name} with {count} output(s)"));
+    }
+
+    /// Replace the staged output changes with the ones that bring the
+    /// outputs to `profile`, and save them in one write
+    fn apply_output_profile(&mut self, profile: &OutputProfile) {
+        if self.preview.is_some() {
+            self.set_error(AppError::validation("Keep or revert the preview before applying a profile"));
+            return;
+        }
+        let (changes, notes) = profile.changes(&self.view_model.outputs);
+        let skipped = if notes.is_empty() { String::new() } else { format!(" (skipped: {})", notes.join("; ")) };
+        if changes.is_empty() {
+            self.notice = Some(format!("The outputs already match profile {}{skipped}", profile.name));
+            return;
+        }
+        if let Err(e) = self.view_model.stage_changes(changes) {
+            self.set_error(AppError::validation(format!("Profile {} not applied: {e}", profile.name)));
+            return;
+        }
+        self.save_category(Category::Outputs);
+        // The save may still wait on a prompt, which leaves the changes staged
+        if !self.view_model.has_pending_changes() {
+            self.notice = Some(format!("Applied profile {}{skipped}", profile.name));
+        }
+    }
+
+    /// Suggest the saved profile made for the monitors now connected, or
+    /// apply it if it is set to apply on its own. Staged output changes are
+    /// never replaced without asking.
+    fn check_output_profiles(&mut self) {
+        let connected = fingerprint(&self.view_model.outputs);
+        if self.profile_fingerprint.as_ref() == Some(&connected) {
+            return;
+        }
+        self.profile_fingerprint = Some(connected);
+        let Some(profile) = self.output_profiles.matching(&self.view_model.outputs).cloned() else {
+            return;
+        };
+        if profile.changes(&self.view_model.outputs).0.is_empty() {
+            return;
+        }
+        if profile.auto_apply && !self.view_model.has_pending_changes() && self.preview.is_none() {
+            self.apply_output_profile(&profile);
+        } else {
+            self.notice = Some(format!("Profile {} matches the connected monitors; O applies it", profile.name));
+        }
+    }
+
+    /// Write the positions, modes, scales and transforms niri reports for
+    /// every enabled output into the config in one go, so a layout arranged
+    /// with `niri msg` survives a restart. Staged changes stay staged.
+    fn persist_live_layout(&mut self) {
+        if self.preview.is_some() {
+            self.set_error(AppError::validation("Keep or revert the preview before saving the live layout"));
+            return;
+        }
+        let outputs = match self.ipc("get outputs", |client| client.get_outputs()) {
+            Ok(outputs) => outputs,
+            Err(e) => {
+                self.set_error(AppError::ipc("Failed to read outputs from niri", &e));
+                return;
+            }
+        };
+        let Some(config) = &self.config else {
+            self.set_error(AppError::validation("No config loaded"));
+            return;
+        };
+        match config.changed_on_disk() {
+            Ok(false) => {}
+            Ok(true) => {
+                self.set_error(AppError::validation("Config changed on disk; reload (r) before saving the live layout"));
+                return;
             }
             Err(e) => {
-                self.error = Some(format!("Failed to load config: {e}"));
+                self.set_error(AppError::write("Failed to check config before saving", &e));
+                return;
+            }
+        }
+        let changes: HashMap<String, OutputChange> = outputs
+            .iter()
+            .filter(|output| output.enabled)
+            .map(|output| (output.name.clone(), output.live_layout()))
+            .collect();
+        let mut names: Vec<&String> = changes.keys().collect();
+        names.sort();
+        let summary: Vec<String> = names.iter().map(|name| format!("save live layout of {name}")).collect();
+        if !self.run_hooks(HookEvent::PreSave, Some(Category::Outputs), &summary) {
+            return;
+        }
+        let Some(config) = &mut self.config else {
+            return;
+        };
+        if let Err(e) = write_output_config(config, &changes) {
+            self.set_error(AppError::write("Failed to save the live layout", &e));
+            return;
+        }
+
+        self.view_model.outputs = outputs;
+        self.view_model.list.clamp(self.view_model.outputs.len());
+        self.sync_outputs_with_config();
+        self.error = None;
+        self.notice = Some(format!("Saved the live layout of {} output(s)", summary.len()));
+        self.finish_save(Category::Outputs, &summary);
+    }
+
+    /// Number of staged changes a reload of this scope would discard
+    fn staged_change_count(&self, scope: ReloadScope) -> usize {
+        let mut count = 0;
+        if scope.includes(Category::Outputs) {
+            count += self.view_model.change_summary().len();
+        }
+        if scope.includes(Category::Keybindings) {
+            count += self.keybindings_view_model.pending_changes.len();
+        }
+        if scope.includes(Category::Appearance) {
+            count += self.appearance_view_model.pending_changes.len();
+        }
+        if scope.includes(Category::General) {
+            count += self.general_view_model.change_summary().len();
+        }
+        count
+    }
+
+    /// Reload, asking for the same key again first if staged changes would be lost
+    fn request_reload(&mut self, scope: ReloadScope) {
+        let staged = self.staged_change_count(scope);
+        if staged > 0 && self.pending_reload != Some(scope) {
+            let (what, key) = match scope {
+                ReloadScope::Category(category) => (category.name(), "r"),
+                ReloadScope::All => ("all categories", "R"),
+            };
+            self.pending_reload = Some(scope);
+            self.notice = Some(format!(
+                "Reload drops {staged} staged change(s) in {what}; press {key} again to confirm"
+            ));
+            return;
+        }
+        self.notice = None;
+        self.reload(scope);
+    }
+
+    /// Discard staged changes in scope and re-read niri state and the config
+    fn reload(&mut self, scope: ReloadScope) {
+        if scope.includes(Category::Outputs) {
+            self.view_model.clear_pending_changes();
+            if let Err(e) = self.load_outputs() {
+                self.set_error(AppError::ipc("Failed to reload outputs", &e));
+                return;
+            }
+        }
+        if scope.includes(Category::Keybindings) {
+            self.keybindings_view_model.pending_changes.clear();
+        }
+        if scope.includes(Category::Appearance) {
+            self.appearance_view_model.reset_changes();
+        }
+        if scope.includes(Category::General) {
+            self.general_view_model.reset_changes();
+        }
+        self.load_config(scope);
+        let category = match scope {
+            ReloadScope::Category(category) => Some(category),
+            ReloadScope::All => None,
+        };
+        self.run_hooks(HookEvent::PostReload, category, &[]);
+    }
+
+    /// Run the settings' hooks for an event. Returns false, with the error
+    /// shown, when one failed.
+    fn run_hooks(&mut self, event: HookEvent, category: Option<Category>, changes: &[String]) -> bool {
+        if self.settings.hooks.is_empty() {
+            return true;
+        }
+        let path = self.config.as_ref().map(|c| c.path.clone()).or_else(|| get_config_path().ok());
+        let payload = HookPayload {
+            event,
+            category: category.map(|c| c.name()),
+            config: path.as_deref(),
+            changes,
+        };
+        match run_hooks(&self.settings.hooks, &payload) {
+            Ok(()) => true,
+            Err(e) => {
+                let context = match event {
+                    HookEvent::PreSave => "Save cancelled by hook",
+                    _ => "Hook failed",
+                };
+                self.set_error(AppError::write(context, &e));
+                false
             }
         }
     }
 
     /// Process a message and update state
     pub fn update(&mut self, message: Message) {
+        // Any other action cancels a reload waiting for confirmation
+        if !matches!(message, Message::Reload | Message::ReloadAll)
+            && self.pending_reload.take().is_some()
+        {
+            self.notice = None;
+        }
+        if !matches!(message, Message::Output(OutputMessage::MergeOutputBlocks)) && self.pending_merge {
+            self.pending_merge = false;
+            self.notice = None;
+        }
+
+        // The tour moves on once the user has done what the step asks
+        if let Some(tutorial) = &mut self.tutorial {
+            if tutorial.observe(&message) && tutorial.is_finished() {
+                self.tutorial = None;
+                self.notice = Some("Tour complete; press F12 to take it again".to_string());
+            }
+        }
+
         match message {
             Message::Quit => {
-                self.should_quit = true;
+                self.should_quit = Category::all().iter().all(|&category| self.auto_save(category));
+                // Only once quitting is certain, so a failed auto-save leaves
+                // the preview running to match what is staged. A preview that
+                // was saved is kept and has nothing left to undo.
+                if self.should_quit {
+                    self.restore_outputs();
+                }
             }
             Message::SwitchCategory(category) => {
+                if category != self.current_category && !self.auto_save(self.current_category) {
+                    return;
+                }
                 self.current_category = category;
                 self.error = None;
+                self.notice = None;
+                if category == Category::Appearance {
+                    self.load_appearance_defaults();
+                }
+            }
+            Message::ToggleDebugOverlay => {
+                self.diagnostics = match self.diagnostics {
+                    Some(_) => None,
+                    None => Some(Diagnostics::default()),
+                };
+            }
+            Message::ResizePane(steps) => {
+                if self.ui_state.resize_pane(self.current_category, steps) {
+                    if let Err(e) = self.ui_state.save() {
+                        self.set_error(AppError::write("Failed to save UI state", &e));
+                    }
+                }
+            }
+            Message::Save => {
+                self.notice = None;
+                self.save_config();
+            }
+            Message::OverwriteConflict => {
+                if let Some(category) = self.save_conflict.take() {
+                    self.write_category(category);
+                }
+            }
+            Message::ReloadAndReapply => {
+                if let Some(category) = self.save_conflict.take() {
+                    self.reload_and_reapply(category);
+                }
+            }
+            Message::StartTutorial => {
+                self.tutorial = Some(Tutorial::default());
+            }
+            Message::SkipTutorialStep => {
+                if let Some(tutorial) = &mut self.tutorial {
+                    tutorial.skip();
+                    if tutorial.is_finished() {
+                        self.tutorial = None;
+                    }
+                }
+            }
+            Message::EndTutorial => {
+                self.tutorial = None;
+            }
+            Message::DismissHealthReport => {
+                self.health_report = None;
+            }
+            Message::CancelSaveConflict => {
+                self.save_conflict = None;
+            }
+            Message::Reload => {
+                self.request_reload(ReloadScope::Category(self.current_category));
+            }
+            Message::ReloadAll => {
+                self.request_reload(ReloadScope::All);
+            }
+            Message::OpenInEditor => {
+                match self.selected_source_location() {
+                    Some(loc) => self.editor_request = Some(loc),
+                    None => self.set_error(AppError::validation("No config loaded")),
+                }
+            }
+            Message::Error(e) => {
+                self.set_error(e);
+            }
+            Message::ClearError => {
+                self.error = None;
+            }
+            Message::OpenTemplateGallery => match &self.config {
+                Some(config) => {
+                    let gallery = TemplateGallery::for_config(config);
+                    if gallery.templates.is_empty() {
+                        self.set_error(AppError::validation(
+                            "Every templated section is already in the config",
+                        ));
+                    } else {
+                        self.template_gallery = Some(gallery);
+                        self.error = None;
+                    }
+                }
+                None => self.set_error(AppError::validation("No config loaded")),
+            },
+            Message::CancelTemplateGallery => {
+                self.template_gallery = None;
+            }
+            Message::OpenQuickOpen => {
+                let live = default_config_path().ok();
+                let current = get_config_path().ok();
+                self.quick_open = Some(QuickOpen::new(live.as_deref(), &self.recent_configs.paths, current.as_deref()));
+            }
+            Message::CancelQuickOpen => {
+                self.quick_open = None;
+            }
+            Message::OpenConfigSource => match discover_config() {
+                Ok(discovery) => self.config_source = Some(discovery),
+                Err(e) => self.set_error(AppError::parse("Failed to find the config", &e)),
+            },
+            Message::CloseConfigSource => {
+                self.config_source = None;
+            }
+            Message::CopySystemConfig => {
+                self.copy_system_config();
+            }
+            Message::ConfirmQuickOpen => {
+                self.open_selected_config();
+            }
+            Message::InsertTemplate => {
+                self.insert_template();
+            }
+            Message::OpenWorkspaceSpawn => {
+                // From the outputs view, start with the workspace pinned to the selected monitor
+                let output = match self.current_category {
+                    Category::Outputs => self.view_model.selected_output().map(|o| o.name.as_str()),
+                    _ => None,
+                };
+                self.workspace_spawn = Some(WorkspaceSpawnWizard::new(output));
+            }
+            Message::CancelWorkspaceSpawn => {
+                self.workspace_spawn = None;
+            }
+            Message::ConfirmWorkspaceSpawn => {
+                self.stage_workspace_spawn();
+            }
+            Message::OpenErrorLog => {
+                self.error_log.list.reset();
+                self.error_log.open = true;
+            }
+            Message::CloseErrorLog => {
+                self.error_log.open = false;
+            }
+            // Keybindings and outputs search
+            Message::StartSearch => match self.current_category {
+                Category::Outputs => self.view_model.search_mode = true,
+                _ => self.keybindings_view_model.search_mode = true,
+            },
+            Message::UpdateSearch(query) => match self.current_category {
+                Category::Outputs => self.view_model.set_search(query),
+                _ => self.keybindings_view_model.set_search(query),
+            },
+            Message::EndSearch => match self.current_category {
+                Category::Outputs => self.view_model.search_mode = false,
+                _ => self.keybindings_view_model.search_mode = false,
+            },
+            Message::ClearSearch => match self.current_category {
+                Category::Outputs => self.view_model.clear_search(),
+                _ => self.keybindings_view_model.clear_search(),
+            },
+            Message::Output(message) => self.update_outputs(message),
+            Message::Keybindings(message) => self.update_keybindings(message),
+            Message::Appearance(message) => self.update_appearance(message),
+            Message::General(message) => self.update_general(message),
+        }
+    }
+
+    /// Process a message for the outputs category
+    fn update_outputs(&mut self, message: OutputMessage) {
+        // Live preview waits for a pause in the moves before sending them
+        let moves = matches!(
+            message,
+            OutputMessage::MoveOutput { .. }
+                | OutputMessage::SnapLeft { .. }
+                | OutputMessage::SnapRight { .. }
+                | OutputMessage::SnapAbove { .. }
+                | OutputMessage::SnapBelow { .. }
+                | OutputMessage::MoveToOrigin
+                | OutputMessage::SetPosition { .. }
+                | OutputMessage::CanvasDrag { .. }
+        );
+        if moves && self.settings.live_preview {
+            self.live_preview_due = Some(Instant::now() + LIVE_PREVIEW_DELAY);
+            // The countdown starts over with each move, so the layout
+            // doesn't snap back in the middle of a drag
+            if let Some(preview) = &mut self.preview {
+                preview.deadline = Instant::now() + LIVE_PREVIEW_DELAY + PREVIEW_TIMEOUT;
+            }
+        }
+
+        match message {
+            OutputMessage::PanCanvas { dx, dy } => {
+                self.viewport.pan(dx, dy);
+            }
+            OutputMessage::CanvasPress { column, row } => {
+                let Some(area) = self.canvas_area else {
+                    return;
+                };
+                let grabbed = MonitorCanvasWidget::new(&self.view_model, &self.viewport, true).grab(area, column, row);
+                if let Some((idx, drag)) = grabbed {
+                    self.view_model.list.selected_index = idx;
+                    self.viewport.drag = Some(drag);
+                }
             }
-            Message::PanCanvas { .. } => {
-                // Panning removed - view auto-fits all monitors
+            OutputMessage::CanvasDrag { column, row } => {
+                let Some(area) = self.canvas_area else {
+                    return;
+                };
+                let canvas = MonitorCanvasWidget::new(&self.view_model, &self.viewport, true);
+                if let (Some(position), Some(drag)) = (canvas.drag_position(area, column, row), &self.viewport.drag) {
+                    let name = drag.output.clone();
+                    self.view_model.apply_pending_change(&name, position);
+                }
             }
-            Message::ZoomIn => {
+            OutputMessage::CanvasRelease => {
+                self.viewport.drag = None;
+            }
+            OutputMessage::ZoomIn => {
                 self.viewport.zoom_in();
             }
-            Message::ZoomOut => {
+            OutputMessage::ZoomOut => {
                 self.viewport.zoom_out();
             }
-            Message::ResetView => {
+            OutputMessage::ResetView => {
                 self.viewport.reset();
             }
-            Message::Save => {
-                self.save_config();
+            OutputMessage::ZoomToSelected => {
+                self.viewport.toggle_focus_selected();
             }
-            Message::Reload => {
-                self.view_model.clear_pending_changes();
-                self.keybindings_view_model.pending_changes.clear();
-                self.appearance_view_model.reset_changes();
-                if let Err(e) = self.load_outputs() {
-                    self.error = Some(format!("Failed to reload: {e}"));
-                } else {
-                    self.load_config();
+            OutputMessage::ToggleColumnPreview => {
+                self.viewport.toggle_column_preview();
+            }
+            OutputMessage::ChangePreviewColumns(delta) => {
+                self.viewport.change_preview_columns(delta);
+            }
+            OutputMessage::ToggleOutputEnabled => {
+                if let Some(name) = self.view_model.selected_output().map(|o| o.name.clone()) {
+                    if let Err(e) = self.view_model.toggle_enabled(&name) {
+                        self.set_error(AppError::validation(e));
+                    }
                 }
             }
-            Message::PreviewChanges => {
+            OutputMessage::PreviewChanges => {
                 self.preview_changes();
             }
-            Message::RevertPreview => {
+            OutputMessage::RevertPreview => {
+                self.revert_preview();
                 self.view_model.clear_pending_changes();
+                self.notice = None;
             }
-            Message::Error(e) => {
-                self.error = Some(e);
+            OutputMessage::ToggleLivePreview => {
+                self.settings.live_preview = !self.settings.live_preview;
+                self.live_preview_due = None;
+                self.notice = Some(if self.settings.live_preview {
+                    "Live preview on: moves are sent to niri as you make them".to_string()
+                } else {
+                    "Live preview off".to_string()
+                });
             }
-            Message::ClearError => {
-                self.error = None;
+            OutputMessage::KeepPreview => {
+                if self.preview.take().is_some() {
+                    self.notice = Some("Preview kept; press s to save it".to_string());
+                }
             }
-            Message::RefreshOutputs => {
+            OutputMessage::MergeOutputBlocks => {
+                self.merge_output_blocks();
+            }
+            OutputMessage::PersistLiveLayout => {
+                self.persist_live_layout();
+            }
+            OutputMessage::IdentifyOutput => {
+                self.identify_output();
+            }
+            OutputMessage::OpenOutputProfiles => {
+                self.profile_picker = Some(ProfilePicker::new(&self.output_profiles, &self.view_model.outputs));
+            }
+            OutputMessage::CloseOutputProfiles => {
+                self.profile_picker = None;
+            }
+            OutputMessage::SaveOutputProfile => {
+                self.save_output_profile();
+            }
+            OutputMessage::ApplyOutputProfile => {
+                let selected = self.profile_picker.as_ref().and_then(|p| p.selected(&self.output_profiles)).cloned();
+                if let Some(profile) = selected {
+                    self.profile_picker = None;
+                    self.apply_output_profile(&profile);
+                }
+            }
+            OutputMessage::DeleteOutputProfile => {
+                let Some(picker) = &mut self.profile_picker else {
+                    return;
+                };
+                let Some(name) = picker.selected(&self.output_profiles).map(|p| p.name.clone()) else {
+                    return;
+                };
+                self.output_profiles.remove(&name);
+                picker.list.clamp(self.output_profiles.profiles.len());
+                match self.output_profiles.save() {
+                    Ok(()) => self.notice = Some(format!("Deleted profile {name}")),
+                    Err(e) => self.set_error(AppError::write("Failed to save output profiles", &e)),
+                }
+            }
+            OutputMessage::ToggleProfileAutoApply => {
+                let Some(picker) = &self.profile_picker else {
+                    return;
+                };
+                let Some(name) = picker.selected(&self.output_profiles).map(|p| p.name.clone()) else {
+                    return;
+                };
+                let Some(profile) = self.output_profiles.get_mut(&name) else {
+                    return;
+                };
+                profile.auto_apply = !profile.auto_apply;
+                let notice = if profile.auto_apply {
+                    format!("Profile {} applies whenever its monitors are plugged in", profile.name)
+                } else {
+                    format!("Profile {} is only suggested when its monitors are plugged in", profile.name)
+                };
+                match self.output_profiles.save() {
+                    Ok(()) => self.notice = Some(notice),
+                    Err(e) => self.set_error(AppError::write("Failed to save output profiles", &e)),
+                }
+            }
+            OutputMessage::OpenOutputLabel => {
+                if let Some(output) = self.view_model.selected_output() {
+                    self.label_editor = Some(OutputLabelEditor::new(&output.name, self.output_labels.get(&output.name)));
+                }
+            }
+            OutputMessage::CancelOutputLabel => {
+                self.label_editor = None;
+            }
+            OutputMessage::ConfirmOutputLabel => {
+                let Some(editor) = self.label_editor.take() else {
+                    return;
+                };
+                if self.output_labels.set(&editor.output, editor.label(), editor.note()) {
+                    if let Err(e) = self.output_labels.save() {
+                        self.set_error(AppError::write("Failed to save output labels", &e));
+                    }
+                }
+            }
+            OutputMessage::OpenOutputColors => {
+                if let Some(output) = self.view_model.selected_output() {
+                    let mut staged = output.clone();
+                    if let Some(change) = self.view_model.pending_changes.get(&output.name) {
+                        staged.apply_change(change);
+                    }
+                    self.color_editor = Some(OutputColorEditor::new(&staged));
+                }
+            }
+            OutputMessage::CancelOutputColors => {
+                self.color_editor = None;
+            }
+            OutputMessage::ConfirmOutputColors => {
+                if let Some(editor) = self.color_editor.take() {
+                    self.view_model.stage_colors(&editor.output, editor.background(), editor.backdrop());
+                }
+            }
+            OutputMessage::OpenOutputPosition => {
+                if let Some(output) = self.view_model.selected_output() {
+                    let position = self.view_model.get_display_position(&output.name).unwrap_or(output.position);
+                    self.position_editor = Some(OutputPositionEditor::new(&output.name, position));
+                }
+            }
+            OutputMessage::CancelOutputPosition => {
+                self.position_editor = None;
+            }
+            OutputMessage::ConfirmOutputPosition => {
+                if let Some(editor) = &mut self.position_editor {
+                    match editor.position() {
+                        Ok(position) => {
+                            self.position_editor = None;
+                            self.update_outputs(OutputMessage::SetPosition { x: position.x, y: position.y });
+                        }
+                        Err(e) => editor.error = Some(e),
+                    }
+                }
+            }
+            OutputMessage::RefreshOutputs => {
+                let selected = self.view_model.selected_output().map(|o| o.name.clone());
                 if let Err(e) = self.load_outputs() {
-                    self.error = Some(format!("Failed to refresh: {e}"));
+                    self.set_error(AppError::ipc("Failed to refresh outputs", &e));
+                } else {
+                    self.sync_outputs_with_config();
+                    // Stay on the same monitor when others come or go
+                    let outputs = &self.view_model.outputs;
+                    self.view_model.list.selected_index = selected
+                        .and_then(|name| outputs.iter().position(|o| o.name == name))
+                        .unwrap_or(self.view_model.list.selected_index.min(outputs.len().saturating_sub(1)));
+                }
+            }
+            OutputMessage::EnableAndSave => {
+                if let Some(names) = self.view_model.disabled_warning.take() {
+                    self.view_model.stage_enabled(&names);
+                    self.notice = Some(format!("Turning on {}; saving", names.join(", ")));
+                    self.save_checking_disk(Category::Outputs);
                 }
             }
+            OutputMessage::SaveDisabledPositions => {
+                if let Some(names) = self.view_model.disabled_warning.take() {
+                    self.save_checking_disk(Category::Outputs);
+                    if self.error.is_none() && self.save_conflict.is_none() {
+                        self.notice = Some(format!(
+                            "{} stays off; the saved position applies once it is turned on",
+                            names.join(", ")
+                        ));
+                    }
+                }
+            }
+            OutputMessage::CancelDisabledWarning => {
+                self.view_model.disabled_warning = None;
+            }
+            // Report what the whole layout operations moved, since they can
+            // shift every monitor at once
+            message @ (OutputMessage::Normalize | OutputMessage::Arrange(_)) => {
+                let before = self.view_model.display_positions();
+                update_output(&mut self.view_model, &message);
+                let moves = self.view_model.moves_since(&before);
+                let operation = if matches!(message, OutputMessage::Normalize) { "Normalize" } else { "Arrange" };
+                self.notice = Some(if moves.is_empty() {
+                    format!("{operation}: outputs already in place")
+                } else {
+                    format!("{operation} staged {}", moves.join("  "))
+                });
+            }
+            // Position, snapping and mode browsing only touch the view model
+            message => {
+                update_output(&mut self.view_model, &message);
+            }
+        }
+    }
+
+    /// Process a message for the keybindings category
+    fn update_keybindings(&mut self, message: KeybindingsMessage) {
+        match message {
+            KeybindingsMessage::AddFallbackBinds => {
+                if let Some(actions) = self.keybindings_view_model.lockout_warning.take() {
+                    let staged = self.keybindings_view_model.stage_fallback_binds(&actions);
+                    if self.keybindings_view_model.lost_essential_actions().is_empty() {
+                        self.notice = Some(format!("Added fallback {}; saving", staged.join(", ")));
+                        self.save_checking_disk(Category::Keybindings);
+                    } else {
+                        self.set_error(AppError::validation(
+                            "Every fallback combo is taken; bind quit and close-window yourself before saving",
+                        ));
+                    }
+                }
+            }
+            KeybindingsMessage::SaveWithoutFallback => {
+                if self.keybindings_view_model.lockout_warning.take().is_some() {
+                    self.save_checking_disk(Category::Keybindings);
+                }
+            }
+            KeybindingsMessage::CancelLockoutWarning => {
+                self.keybindings_view_model.lockout_warning = None;
+            }
             // Keybindings navigation
-            Message::SelectNextKeybinding => {
+            KeybindingsMessage::SelectNextKeybinding => {
                 self.keybindings_view_model.select_next();
             }
-            Message::SelectPrevKeybinding => {
+            KeybindingsMessage::SelectPrevKeybinding => {
                 self.keybindings_view_model.select_prev();
             }
-            Message::SelectKeybinding(idx) => {
-                let count = self.keybindings_view_model.visible_count();
-                if idx < count {
-                    self.keybindings_view_model.selected_index = idx;
-                }
-            }
-            // Keybindings search
-            Message::StartSearch => {
-                self.keybindings_view_model.search_mode = true;
+            KeybindingsMessage::PageDownKeybindings => {
+                self.keybindings_view_model.page_down();
             }
-            Message::UpdateSearch(query) => {
-                self.keybindings_view_model.set_search(query);
+            KeybindingsMessage::PageUpKeybindings => {
+                self.keybindings_view_model.page_up();
             }
-            Message::ClearSearch => {
-                self.keybindings_view_model.clear_search();
+            KeybindingsMessage::SelectKeybinding(idx) => {
+                let count = self.keybindings_view_model.visible_count();
+                self.keybindings_view_model.list.select(idx, count);
             }
             // Keybindings editing
-            Message::StartEdit => {
+            KeybindingsMessage::StartEdit => {
                 self.start_edit_keybinding();
             }
-            Message::CancelEdit => {
+            KeybindingsMessage::CancelEdit => {
                 self.keybindings_view_model.edit_mode = None;
                 self.error = None;
             }
-            Message::ConfirmEdit => {
+            KeybindingsMessage::ConfirmEdit => {
                 self.confirm_edit_keybinding();
             }
-            Message::AddKeybinding => {
-                self.keybindings_view_model.edit_mode = Some(EditMode::new_binding());
-                self.error = None;
+            KeybindingsMessage::AddKeybinding => {
+                self.keybindings_view_model.edit_mode = Some(EditMode::new_binding());
+                self.error = None;
+            }
+            KeybindingsMessage::DeleteKeybinding => {
+                self.delete_selected_keybinding();
+            }
+            KeybindingsMessage::ToggleBindingFlag(flag) => {
+                match self.keybindings_view_model.toggle_selected_flag(flag) {
+                    Ok(binding) => {
+                        self.notice = Some(format!(
+                            "{}: {} (staged)",
+                            binding.combo(),
+                            flag.describe(&binding.properties)
+                        ));
+                    }
+                    Err(message) => self.set_error(AppError::validation(message)),
+                }
+            }
+            KeybindingsMessage::FixRepeatLint => {
+                match self.keybindings_view_model.fix_selected_repeat() {
+                    Ok(binding) => self.notice = Some(format!("{}: repeat off (staged)", binding.combo())),
+                    Err(message) => self.set_error(AppError::validation(message)),
+                }
+            }
+            KeybindingsMessage::ToggleRawKeyNames => {
+                self.keybindings_view_model.toggle_raw_key_names();
+            }
+            KeybindingsMessage::ToggleCategoryColumn => {
+                let columns = &mut self.keybindings_view_model.columns;
+                columns.category = !columns.category;
+            }
+            KeybindingsMessage::ToggleFlagsColumn => {
+                let columns = &mut self.keybindings_view_model.columns;
+                columns.flags = !columns.flags;
+            }
+            KeybindingsMessage::AdjustComboWidth(delta) => {
+                self.keybindings_view_model.columns.adjust_combo_width(delta);
+            }
+            KeybindingsMessage::ToggleDefaultBinds => {
+                if self.keybindings_view_model.toggle_defaults() {
+                    // Unknown versions fall back to the newest snapshot
+                    let version = NiriClient::connect(self.socket_path.as_deref())
+                        .and_then(|mut c| c.version())
+                        .ok();
+                    match load_default_binds(version.as_deref()) {
+                        Ok((release, defaults)) => {
+                            self.keybindings_view_model.defaults = defaults;
+                            self.keybindings_view_model.defaults_release = Some(release);
+                        }
+                        Err(e) => {
+                            self.keybindings_view_model.show_defaults = false;
+                            self.set_error(AppError::parse("Failed to load niri default binds", &e));
+                        }
+                    }
+                }
+            }
+            KeybindingsMessage::ToggleMissingFilter => {
+                let path_var = std::env::var_os("PATH");
+                let count = self.keybindings_view_model.toggle_missing_filter(path_var.as_deref());
+                self.notice = self.keybindings_view_model.missing_only.then(|| match count {
+                    0 => "Every spawned program is installed".to_string(),
+                    n => format!("{n} bind(s) spawn a program that isn't installed; M shows all again"),
+                });
+            }
+            KeybindingsMessage::OverrideDefaultBind => {
+                self.override_default_binding();
+            }
+            KeybindingsMessage::OpenDryRun => {
+                // The edit dialog's action as typed, otherwise the selected binding
+                let dry_run = match &self.keybindings_view_model.edit_mode {
+                    Some(edit) => Some(DryRun::new(edit.key_combo.clone(), edit.dry_run_argv())),
+                    None => self.keybindings_view_model.selected_binding().map(|binding| {
+                        let argv = binding
+                            .action
+                            .argv()
+                            .ok_or_else(|| "Only spawn and spawn-sh bindings run a command".to_string());
+                        DryRun::new(binding.combo(), argv)
+                    }),
+                };
+                self.keybindings_view_model.dry_run = dry_run;
+            }
+            KeybindingsMessage::RunDryRun => {
+                if let Some(dry_run) = &mut self.keybindings_view_model.dry_run {
+                    dry_run.start();
+                }
+            }
+            KeybindingsMessage::CloseDryRun => {
+                self.keybindings_view_model.dry_run = None;
+            }
+            KeybindingsMessage::OpenBindingGenerator => {
+                self.keybindings_view_model.generator = Some(Default::default());
+                self.error = None;
+            }
+            KeybindingsMessage::CancelBindingGenerator => {
+                self.keybindings_view_model.generator = None;
+            }
+            KeybindingsMessage::ConfirmBindingGenerator => {
+                if let Some(generator) = self.keybindings_view_model.generator.take() {
+                    let (staged, skipped) = self.keybindings_view_model.stage_generated(&generator);
+                    let clashes = self.keybindings_view_model.start_conflict_resolution("Generated", skipped);
+                    if staged == 0 && clashes == 0 {
+                        self.set_error(AppError::validation("All generated combos already exist"));
+                    }
+                }
+            }
+            KeybindingsMessage::OpenBindingTemplate => {
+                match TemplatePrompt::new(self.settings.binding_templates.clone()) {
+                    Some(prompt) => {
+                        self.keybindings_view_model.template_prompt = Some(prompt);
+                        self.error = None;
+                    }
+                    None => self.set_error(AppError::validation(
+                        "No binding templates; add binding-template blocks to ~/.config/nirikiri/settings.kdl",
+                    )),
+                }
+            }
+            KeybindingsMessage::CancelBindingTemplate => {
+                self.keybindings_view_model.template_prompt = None;
+            }
+            KeybindingsMessage::ResolveConflict(choice) => match self.keybindings_view_model.resolve_conflict(choice) {
+                Ok(summary) => {
+                    self.error = None;
+                    if summary.is_some() {
+                        self.notice = summary;
+                    }
+                }
+                Err(e) => self.set_error(AppError::validation(e)),
+            },
+            KeybindingsMessage::KeepRemainingConflicts => {
+                self.notice = self.keybindings_view_model.keep_remaining_conflicts();
+            }
+            KeybindingsMessage::OpenBindingTags => {
+                let vm = &self.keybindings_view_model;
+                if let Some(combo) = vm.selected_binding().map(|b| b.combo()) {
+                    let editor = BindingTagEditor::new(combo.clone(), vm.tags.tags(&combo));
+                    self.keybindings_view_model.tag_editor = Some(editor);
+                }
+            }
+            KeybindingsMessage::CancelBindingTags => {
+                self.keybindings_view_model.tag_editor = None;
+            }
+            KeybindingsMessage::ConfirmBindingTags => {
+                let vm = &mut self.keybindings_view_model;
+                let Some(editor) = vm.tag_editor.take() else {
+                    return;
+                };
+                if vm.tags.set(&editor.combo, &editor.field.value) {
+                    if let Err(e) = vm.tags.save() {
+                        self.set_error(AppError::write("Failed to save binding tags", &e));
+                    }
+                }
+            }
+            KeybindingsMessage::ConfirmBindingTemplate => {
+                self.stage_binding_template();
+            }
+            KeybindingsMessage::OpenBindOverlay => {
+                let overlay = BindOverlay::new(&self.keybindings_view_model.effective_bindings());
+                if overlay.entries.is_empty() {
+                    self.notice = Some("No binds in the config to rebind".to_string());
+                } else {
+                    self.keybindings_view_model.bind_overlay = Some(overlay);
+                }
+            }
+            KeybindingsMessage::CloseBindOverlay => {
+                self.keybindings_view_model.bind_overlay = None;
+            }
+            KeybindingsMessage::StartRebind => {
+                if let Some(overlay) = &mut self.keybindings_view_model.bind_overlay {
+                    overlay.start_capture();
+                }
+            }
+            KeybindingsMessage::CancelRebind => {
+                if let Some(overlay) = &mut self.keybindings_view_model.bind_overlay {
+                    overlay.capture = None;
+                }
+            }
+            KeybindingsMessage::RebindTo(combo) => {
+                self.rebind_from_overlay(&combo);
+            }
+        }
+    }
+
+    /// Stage the bind selected in the overlay on `combo`; a combo that is
+    /// taken keeps the capture open so another can be pressed
+    fn rebind_from_overlay(&mut self, combo: &str) {
+        let vm = &mut self.keybindings_view_model;
+        let Some(entry) = vm.bind_overlay.as_ref().and_then(|o| o.selected()).cloned() else {
+            return;
+        };
+        let result = vm.rebind(entry.original_index, &entry.binding, combo);
+        match result {
+            Ok(new) => {
+                if let Some(mut overlay) = vm.bind_overlay.take() {
+                    overlay.capture = None;
+                    overlay.refresh(&vm.effective_bindings());
+                    vm.bind_overlay = Some(overlay);
+                }
+                self.notice = Some(format!(
+                    "{} → {}: {} (staged)",
+                    entry.binding.combo(),
+                    new.combo(),
+                    new.action.short_description()
+                ));
+            }
+            Err(message) => {
+                if let Some(capture) = vm.bind_overlay.as_mut().and_then(|o| o.capture.as_mut()) {
+                    capture.rejected = Some(message);
+                }
+            }
+        }
+    }
+
+    /// Process a message for the general category
+    fn update_general(&mut self, message: GeneralMessage) {
+        let view_model = &mut self.general_view_model;
+        match message {
+            GeneralMessage::SelectNextGeneralSetting => view_model.select_next(),
+            GeneralMessage::SelectPrevGeneralSetting => view_model.select_prev(),
+            GeneralMessage::ToggleGeneralFlag => view_model.toggle_selected(),
+            GeneralMessage::StartGeneralEdit => view_model.start_edit(),
+            GeneralMessage::CancelGeneralEdit => view_model.edit = None,
+            GeneralMessage::ConfirmGeneralEdit => view_model.confirm_edit(),
+            GeneralMessage::RevertGeneralChanges => view_model.reset_changes(),
+        }
+    }
+
+    /// Process a message for the appearance category
+    fn update_appearance(&mut self, message: AppearanceMessage) {
+        match message {
+            AppearanceMessage::SaveAppearanceSection => {
+                if let Some(section) = self.appearance_view_model.selected_section() {
+                    self.save_appearance_section(section);
+                }
+            }
+            AppearanceMessage::RevertAppearanceSection => {
+                if let Some(section) = self.appearance_view_model.selected_section() {
+                    self.appearance_view_model.revert_section(section);
+                }
+            }
+            AppearanceMessage::RevertAppearanceField => {
+                if let Some(AppearanceListItem::Field(field)) = self.appearance_view_model.selected_item() {
+                    self.appearance_view_model.revert_field(field);
+                }
             }
-            Message::DeleteKeybinding => {
-                self.delete_selected_keybinding();
+            AppearanceMessage::ResetAppearanceToDefault => {
+                self.load_appearance_defaults();
+                if let Some(AppearanceListItem::Field(field)) = self.appearance_view_model.selected_item() {
+                    self.appearance_view_model.reset_to_default(field);
+                }
             }
             // Appearance navigation
-            Message::SelectNextAppearanceSetting => {
+            AppearanceMessage::SelectNextAppearanceSetting => {
                 self.appearance_view_model.select_next();
             }
-            Message::SelectPrevAppearanceSetting => {
+            AppearanceMessage::SelectPrevAppearanceSetting => {
                 self.appearance_view_model.select_prev();
             }
-            Message::ToggleSection => {
+            AppearanceMessage::PageDownAppearance => {
+                self.appearance_view_model.page_down();
+            }
+            AppearanceMessage::PageUpAppearance => {
+                self.appearance_view_model.page_up();
+            }
+            AppearanceMessage::ToggleNestedPreview => {
+                self.toggle_nested_preview();
+            }
+            AppearanceMessage::ToggleSection => {
                 self.appearance_view_model.toggle_selected_section();
             }
             // Appearance editing
-            Message::StartAppearanceEdit => {
+            AppearanceMessage::StartAppearanceEdit => {
                 self.start_appearance_edit();
             }
-            Message::CancelAppearanceEdit => {
+            AppearanceMessage::CancelAppearanceEdit => {
                 self.appearance_view_model.edit_mode = None;
                 self.error = None;
             }
-            Message::ConfirmAppearanceEdit => {
+            AppearanceMessage::ConfirmAppearanceEdit => {
                 self.confirm_appearance_edit();
             }
-            Message::ToggleAppearanceBool => {
+            AppearanceMessage::StartSectionEdit => {
+                self.appearance_view_model.start_section_edit();
+                self.error = None;
+            }
+            AppearanceMessage::CancelSectionEdit => {
+                self.appearance_view_model.section_edit = None;
+                self.error = None;
+            }
+            AppearanceMessage::ConfirmSectionEdit => {
+                let section = self.appearance_view_model.section_edit.as_ref().map(|e| e.section);
+                let staged: Vec<FieldValue> = match &self.appearance_view_model.section_edit {
+                    Some(edit) => edit.changed_values().unwrap_or_default().into_iter().map(|(_, v)| v).collect(),
+                    None => Vec::new(),
+                };
+                match self.appearance_view_model.confirm_section_edit() {
+                    Ok(count) => {
+                        self.error = None;
+                        self.remember_colors(&staged);
+                        if let Some(section) = section {
+                            self.notice = Some(match count {
+                                0 => format!("{}: nothing changed", section.name()),
+                                1 => format!("{}: staged 1 change", section.name()),
+                                n => format!("{}: staged {n} changes", section.name()),
+                            });
+                        }
+                    }
+                    Err(message) => self.set_error(AppError::validation(message)),
+                }
+            }
+            AppearanceMessage::ToggleAppearanceBool => {
                 self.toggle_appearance_bool();
             }
-            Message::IncrementValue => {
+            AppearanceMessage::IncrementValue => {
                 self.adjust_appearance_value(1);
             }
-            Message::DecrementValue => {
+            AppearanceMessage::DecrementValue => {
                 self.adjust_appearance_value(-1);
             }
-            Message::CycleEnumForward => {
+            AppearanceMessage::CycleEnumForward => {
                 self.cycle_appearance_enum(true);
             }
-            Message::CycleEnumBackward => {
+            AppearanceMessage::CycleEnumBackward => {
                 self.cycle_appearance_enum(false);
             }
-            Message::UpdateAppearanceValue(_) => {
+            AppearanceMessage::UpdateAppearanceValue(_) => {
                 // Handled in edit mode input
             }
-            // Output-related messages
-            msg => {
-                update_output(&mut self.view_model, &msg);
+        }
+    }
+
+    /// Get the config location of whatever is selected in the current category,
+    /// falling back to the enclosing block or the top of the file
+    fn selected_source_location(&self) -> Option<SourceLocation> {
+        let config = self.config.as_ref()?;
+        let block_location = |name: &str| {
+            config
+                .doc
+                .nodes()
+                .iter()
+                .find(|n| n.name().value() == name)
+                .and_then(|n| config.locate(n))
+        };
+
+        let location = match self.current_category {
+            Category::Outputs => self
+                .view_model
+                .selected_output()
+                .and_then(|o| o.source.clone()),
+            Category::Keybindings => self
+                .keybindings_view_model
+                .selected_effective_binding()
+                .and_then(|eb| eb.binding.source.clone())
+                .or_else(|| block_location("binds")),
+            Category::Appearance => match self.appearance_view_model.selected_item() {
+                Some(AppearanceListItem::Field(field)) => {
+                    self.appearance_view_model.field_sources.get(&field).cloned()
+                }
+                _ => None,
+            }
+            .or_else(|| block_location("layout")),
+            Category::General => self.general_view_model.selected_field().and_then(|field| {
+                self.general_view_model.field_sources.get(&field).cloned().or_else(|| block_location(field.block()))
+            }),
+        };
+
+        Some(location.unwrap_or_else(|| SourceLocation {
+            path: config.path.clone(),
+            line: 1,
+        }))
+    }
+
+    /// Called by the main loop after the external editor exits
+    pub fn editor_finished(&mut self, result: Result<bool>) {
+        match result {
+            // Re-read everything so indices and spans match the edited file
+            Ok(true) => self.reload(ReloadScope::All),
+            Ok(false) => {}
+            Err(e) => self.set_error(AppError::write("Failed to run editor", &e)),
+        }
+    }
+
+    /// Stage the binds of the filled-in template; the dialog stays open
+    /// when a value is missing or the result doesn't parse
+    fn stage_binding_template(&mut self) {
+        let Some(prompt) = &self.keybindings_view_model.template_prompt else {
+            return;
+        };
+        let name = prompt.template().name.clone();
+        let bindings = match prompt.render().map_err(anyhow::Error::msg).and_then(|kdl| parse_bind_nodes(&kdl)) {
+            Ok(bindings) => bindings,
+            Err(e) => {
+                self.set_error(AppError::parse(format!("Template {name} doesn't give valid binds"), &e));
+                return;
+            }
+        };
+        self.keybindings_view_model.template_prompt = None;
+        let count = bindings.len();
+        let skipped = self.keybindings_view_model.stage_new_bindings(bindings);
+        let staged = count - skipped.len();
+        let clashes = self.keybindings_view_model.start_conflict_resolution(&name, skipped);
+        self.notice = Some(if clashes == 0 {
+            format!("Staged {staged} bind(s) from {name}")
+        } else {
+            format!("Staged {staged} bind(s) from {name}; {clashes} clash(es) to resolve")
+        });
+    }
+
+    /// Switch to the config chosen in the quick-open dialog, reloading
+    /// everything from it
+    fn open_selected_config(&mut self) {
+        let Some(entry) = self.quick_open.take().and_then(|q| q.selected().cloned()) else {
+            return;
+        };
+        if entry.current {
+            return;
+        }
+        if !entry.exists {
+            self.set_error(AppError::validation(format!("{} no longer exists", entry.path.display())));
+            return;
+        }
+        let staged = self.staged_change_count(ReloadScope::All);
+        if staged > 0 {
+            self.set_error(AppError::validation(format!(
+                "{staged} staged change(s) would be lost; save or discard them before switching configs"
+            )));
+            return;
+        }
+
+        set_config_path((!entry.live).then(|| entry.path.clone()));
+        if !entry.live {
+            self.record_recent_config(&entry.path);
+        }
+        self.reload(ReloadScope::All);
+        self.notice = Some(format!("Editing {}", entry.path.display()));
+    }
+
+    /// Put `path` at the top of the quick-open list
+    fn record_recent_config(&mut self, path: &std::path::Path) {
+        if self.recent_configs.record(path) {
+            if let Err(e) = self.recent_configs.save() {
+                self.set_error(AppError::write("Failed to save recent configs", &e));
+            }
+        }
+    }
+
+    /// Append the chosen template to the config and open it in the editor
+    fn insert_template(&mut self) {
+        let Some(template) = self.template_gallery.take().and_then(|g| g.selected()) else {
+            return;
+        };
+        let Some(config) = &mut self.config else {
+            self.set_error(AppError::validation("No config loaded"));
+            return;
+        };
+        match write_template(config, template) {
+            Ok(location) => {
+                self.error = None;
+                self.editor_request = Some(location);
+            }
+            Err(e) => {
+                self.set_error(AppError::write(format!("Failed to add {}", template.node), &e));
             }
         }
     }
 
+    /// Stage the wizard's workspace, window rule and startup entry in
+    /// General, to be written together on its next save; the wizard stays
+    /// open when something is missing
+    fn stage_workspace_spawn(&mut self) {
+        let Some(wizard) = &self.workspace_spawn else {
+            return;
+        };
+        let spawn = match wizard.plan() {
+            Ok(spawn) => spawn,
+            Err(e) => {
+                self.set_error(AppError::validation(e));
+                return;
+            }
+        };
+        self.workspace_spawn = None;
+        self.error = None;
+        self.notice = Some(format!(
+            "Staged {} on workspace {}; save General (F4) to write it",
+            spawn.command[0], spawn.workspace
+        ));
+        self.general_view_model.stage_workspace_spawn(spawn);
+    }
+
     fn save_config(&mut self) {
-        match self.current_category {
+        self.save_category(self.current_category);
+    }
+
+    /// With auto-save on, save the category's staged changes before leaving
+    /// it. Returns false, showing the category, when they are still staged
+    /// because the save failed or waits on the user (a conflict or lockout
+    /// warning).
+    fn auto_save(&mut self, category: Category) -> bool {
+        let scope = ReloadScope::Category(category);
+        if !self.settings.auto_save || self.staged_change_count(scope) == 0 {
+            return true;
+        }
+        self.save_category(category);
+        if self.staged_change_count(scope) > 0 {
+            self.current_category = category;
+            return false;
+        }
+        true
+    }
+
+    fn save_category(&mut self, category: Category) {
+        // Losing the last quit or close-window bind can trap the user in the session
+        if category == Category::Keybindings {
+            let lost = self.keybindings_view_model.lost_essential_actions();
+            if !lost.is_empty() {
+                self.keybindings_view_model.lockout_warning = Some(lost);
+                return;
+            }
+        }
+        // A position for an output that's off is saved but shows nowhere
+        if category == Category::Outputs {
+            let disabled = self.view_model.positions_on_disabled();
+            if !disabled.is_empty() {
+                self.view_model.disabled_warning = Some(disabled);
+                return;
+            }
+        }
+        self.save_checking_disk(category);
+    }
+
+    fn save_checking_disk(&mut self, category: Category) {
+        // Don't clobber edits another program made since the config was loaded
+        if self.staged_change_count(ReloadScope::Category(category)) > 0 {
+            if let Some(config) = &self.config {
+                match config.changed_on_disk() {
+                    Ok(true) => {
+                        self.save_conflict = Some(category);
+                        return;
+                    }
+                    Ok(false) => {}
+                    Err(e) => {
+                        self.set_error(AppError::write("Failed to check config before saving", &e));
+                        return;
+                    }
+                }
+            }
+        }
+        self.write_category(category);
+    }
+
+    fn write_category(&mut self, category: Category) {
+        match category {
             Category::Outputs => self.save_output_config(),
             Category::Keybindings => self.save_keybindings_config(),
-            Category::Appearance => self.save_appearance_config(),
+            Category::Appearance => self.save_appearance_config(None),
+            Category::General => self.save_general_config(),
+        }
+    }
+
+    /// Re-read the config that changed on disk and stage the category's
+    /// edits again on top of it, reporting the ones that no longer apply.
+    /// The user reviews the result and saves again.
+    fn reload_and_reapply(&mut self, category: Category) {
+        let old_bindings = self.keybindings_view_model.bindings.clone();
+        let output_changes = self.view_model.change_summary().len();
+        let outputs = std::mem::take(&mut self.view_model.pending_changes);
+        let bindings = std::mem::take(&mut self.keybindings_view_model.pending_changes);
+        let appearance = self.appearance_view_model.pending_changes.clone();
+        let general = self.general_view_model.clone();
+
+        // Load synchronously so the edits land on the new document
+        self.config_load = None;
+        self.finish_config_load(
+            load_editable_config(self.settings.managed_fragment),
+            ReloadScope::Category(category),
+        );
+        if self.config.is_none() {
+            self.view_model.pending_changes = outputs;
+            self.keybindings_view_model.pending_changes = bindings;
+            return;
+        }
+
+        let (staged, skipped) = match category {
+            Category::Outputs => {
+                self.keybindings_view_model.pending_changes = bindings;
+                (output_changes, self.view_model.rebase_changes(outputs))
+            }
+            Category::Keybindings => {
+                self.view_model.pending_changes = outputs;
+                let staged = bindings.len();
+                (staged, self.keybindings_view_model.rebase_changes(&old_bindings, bindings))
+            }
+            Category::Appearance => {
+                self.view_model.pending_changes = outputs;
+                self.keybindings_view_model.pending_changes = bindings;
+                let staged = appearance.len();
+                (staged, self.appearance_view_model.rebase_changes(appearance))
+            }
+            Category::General => {
+                self.view_model.pending_changes = outputs;
+                self.keybindings_view_model.pending_changes = bindings;
+                self.general_view_model.rebase_changes(&general);
+                (self.general_view_model.change_summary().len(), Vec::new())
+            }
+        };
+
+        let reapplied = staged - skipped.len();
+        if skipped.is_empty() {
+            self.error = None;
+            self.notice = Some(format!("Reloaded; re-applied {reapplied} change(s). Save again to write"));
+        } else {
+            self.notice = Some(format!(
+                "Reloaded; re-applied {reapplied} of {staged} change(s). Save again to write"
+            ));
+            self.set_error(AppError::validation(format!("Not re-applied: {}", skipped.join("; "))));
         }
     }
 
@@ -243,27 +2000,36 @@ impl App {
             return;
         }
 
+        let summary = self.view_model.change_summary();
+        if !self.run_hooks(HookEvent::PreSave, Some(Category::Outputs), &summary) {
+            return;
+        }
         if let Some(config) = &mut self.config {
-            match write_positions(config, &self.view_model.pending_changes) {
+            match write_output_config(config, &self.view_model.pending_changes) {
                 Ok(()) => {
                     // Apply pending changes to outputs
-                    for (name, pos) in &self.view_model.pending_changes {
+                    for (name, change) in &self.view_model.pending_changes {
                         if let Some(output) =
                             self.view_model.outputs.iter_mut().find(|o| &o.name == name)
                         {
-                            output.position = *pos;
-                            output.configured = true;
+                            output.apply_change(change);
                         }
                     }
+                    for output in &mut self.view_model.outputs {
+                        output.source = config.output_location(&output.name);
+                    }
                     self.view_model.clear_pending_changes();
+                    // Saving keeps what was previewed
+                    self.preview = None;
                     self.error = None;
+                    self.finish_save(Category::Outputs, &summary);
                 }
                 Err(e) => {
-                    self.error = Some(format!("Failed to save: {e}"));
+                    self.set_error(AppError::write("Failed to save outputs", &e));
                 }
             }
         } else {
-            self.error = Some("No config loaded".to_string());
+            self.set_error(AppError::validation("No config loaded"));
         }
     }
 
@@ -272,52 +2038,181 @@ impl App {
             return;
         }
 
+        let summary = self.keybindings_view_model.change_summary();
+        if !self.run_hooks(HookEvent::PreSave, Some(Category::Keybindings), &summary) {
+            return;
+        }
         if let Some(config) = &mut self.config {
-            match write_keybindings(config, &self.keybindings_view_model.pending_changes) {
+            match write_keybindings(config, &self.keybindings_view_model.pending_changes.to_changes()) {
                 Ok(()) => {
                     // Reload keybindings from saved config
                     self.keybindings_view_model.bindings = parse_keybindings(config);
                     self.keybindings_view_model.pending_changes.clear();
-                    self.keybindings_view_model.selected_index = 0;
+                    self.keybindings_view_model.list.selected_index = 0;
                     self.error = None;
+                    self.finish_save(Category::Keybindings, &summary);
 
                     // Tell niri to reload its config so keybindings take effect
-                    if let Err(e) = NiriClient::connect().and_then(|mut c| c.reload_config()) {
-                        self.error = Some(format!("Saved, but failed to reload niri config: {e}"));
+                    if let Err(e) = self.ipc("reload config", |client| client.reload_config()) {
+                        self.set_error(AppError::ipc("Saved, but failed to reload niri config", &e));
                     }
                 }
                 Err(e) => {
-                    self.error = Some(format!("Failed to save keybindings: {e}"));
+                    self.set_error(AppError::write("Failed to save keybindings", &e));
                 }
             }
         } else {
-            self.error = Some("No config loaded".to_string());
+            self.set_error(AppError::validation("No config loaded"));
+        }
+    }
+
+    /// Save one section's staged changes. The conflict dialog would offer to
+    /// write every staged change, so an external edit is reported instead.
+    fn save_appearance_section(&mut self, section: AppearanceSection) {
+        if let Some(config) = &self.config {
+            match config.changed_on_disk() {
+                Ok(true) => {
+                    self.set_error(AppError::validation(
+                        "Config changed on disk; save all changes (s) to resolve it first",
+                    ));
+                    return;
+                }
+                Ok(false) => {}
+                Err(e) => {
+                    self.set_error(AppError::write("Failed to check config before saving", &e));
+                    return;
+                }
+            }
         }
+        self.save_appearance_config(Some(section));
     }
 
-    fn save_appearance_config(&mut self) {
-        if !self.appearance_view_model.has_pending_changes() {
+    /// Save staged appearance changes, or only those in `section`
+    fn save_appearance_config(&mut self, section: Option<AppearanceSection>) {
+        let view_model = &self.appearance_view_model;
+        let has_changes = match section {
+            Some(section) => view_model.has_section_changes(section),
+            None => view_model.has_pending_changes(),
+        };
+        if !has_changes {
             return;
         }
 
+        // The writer only touches fields that differ from the saved settings,
+        // so leaving other sections at their saved values keeps their blocks as is
+        let (summary, settings) = match section {
+            Some(section) => (
+                view_model.section_change_summary(section),
+                view_model.settings_with_section(section),
+            ),
+            None => (view_model.change_summary(), view_model.settings.clone()),
+        };
+        if !self.run_hooks(HookEvent::PreSave, Some(Category::Appearance), &summary) {
+            return;
+        }
         if let Some(config) = &mut self.config {
-            match write_appearance(config, &self.appearance_view_model.settings) {
+            match write_appearance(config, &self.appearance_view_model.original_settings, &settings) {
                 Ok(()) => {
                     // Apply pending changes
-                    self.appearance_view_model.apply_changes();
+                    match section {
+                        Some(section) => self.appearance_view_model.apply_section(section),
+                        None => self.appearance_view_model.apply_changes(),
+                    }
+                    self.appearance_view_model.field_sources = locate_appearance_fields(config);
                     self.error = None;
+                    self.finish_save(Category::Appearance, &summary);
 
                     // Tell niri to reload its config so appearance changes take effect
-                    if let Err(e) = NiriClient::connect().and_then(|mut c| c.reload_config()) {
-                        self.error = Some(format!("Saved, but failed to reload niri config: {e}"));
+                    if let Err(e) = self.ipc("reload config", |client| client.reload_config()) {
+                        self.set_error(AppError::ipc("Saved, but failed to reload niri config", &e));
+                    }
+                }
+                Err(e) => {
+                    self.set_error(AppError::write("Failed to save appearance", &e));
+                }
+            }
+        } else {
+            self.set_error(AppError::validation("No config loaded"));
+        }
+    }
+
+    fn save_general_config(&mut self) {
+        if !self.general_view_model.has_pending_changes() {
+            return;
+        }
+
+        let summary = self.general_view_model.change_summary();
+        if !self.run_hooks(HookEvent::PreSave, Some(Category::General), &summary) {
+            return;
+        }
+        if let Some(config) = &mut self.config {
+            let view_model = &mut self.general_view_model;
+            let spawns = &view_model.workspace_spawns;
+            match write_general(config, &view_model.original_settings, &view_model.settings, spawns) {
+                Ok(()) => {
+                    view_model.apply_changes();
+                    view_model.field_sources = locate_general_fields(config);
+                    self.error = None;
+                    self.finish_save(Category::General, &summary);
+
+                    // Tell niri to reload its config so the changes take effect
+                    if let Err(e) = self.ipc("reload config", |client| client.reload_config()) {
+                        self.set_error(AppError::ipc("Saved, but failed to reload niri config", &e));
                     }
                 }
                 Err(e) => {
-                    self.error = Some(format!("Failed to save appearance: {e}"));
+                    self.set_error(AppError::write("Failed to save general settings", &e));
                 }
             }
         } else {
-            self.error = Some("No config loaded".to_string());
+            self.set_error(AppError::validation("No config loaded"));
+        }
+    }
+
+    /// After a save: make sure the niri config includes the fragment in
+    /// managed mode, commit when enabled in settings, then run the
+    /// post-save hooks
+    fn finish_save(&mut self, category: Category, summary: &[String]) {
+        self.commit_save(summary);
+        self.run_hooks(HookEvent::PostSave, Some(category), summary);
+    }
+
+    fn commit_save(&mut self, summary: &[String]) {
+        let mut include_added = None;
+        if self.settings.managed_fragment {
+            match load_config().and_then(|mut main| Ok(ensure_included(&mut main)?.then_some(main.path))) {
+                Ok(added) => include_added = added,
+                Err(e) => self.set_error(AppError::write("Saved, but failed to include nirikiri.kdl", &e)),
+            }
+        }
+        let Some(config) = &self.config else {
+            return;
+        };
+        if let Some(main_path) = &include_added {
+            self.notice = Some(format!(
+                "Added include \"{}\" to {}; it now owns {}",
+                config.path.file_name().unwrap_or_default().to_string_lossy(),
+                main_path.display(),
+                owned_blocks(config).join(", ")
+            ));
+        }
+        if !self.settings.git_commit {
+            return;
+        }
+        let message = commit_message(summary);
+        if let Some(main_path) = include_added {
+            if let Err(e) = commit_config(&main_path, "nirikiri: include nirikiri.kdl") {
+                self.set_error(AppError::write("Saved, but git commit failed", &e));
+                return;
+            }
+        }
+        let Some(config) = &self.config else {
+            return;
+        };
+        match commit_config(&config.path, &message) {
+            Ok(true) => self.notice = Some(format!("Committed: {message}")),
+            Ok(false) => {}
+            Err(e) => self.set_error(AppError::write("Saved, but git commit failed", &e)),
         }
     }
 
@@ -337,8 +2232,12 @@ impl App {
             if field.is_color() {
                 let current_value = self.appearance_view_model.get_field_value(field);
                 if let FieldValue::Color(color) = current_value {
+                    let theme = self.appearance_view_model.settings.colors_except(field);
+                    let palette = self.color_history.palette(theme);
                     self.appearance_view_model.edit_mode =
-                        Some(AppearanceEditMode::new_color(field, &color));
+                        Some(AppearanceEditMode::new_color(field, &color)
+                            .with_palette(palette)
+                            .with_inactive_factor(self.settings.inactive_color_factor));
                     self.error = None;
                 }
                 return;
@@ -365,62 +2264,41 @@ impl App {
             None => return,
         };
 
-        let field = edit_mode.field;
+        let value = match edit_mode.parsed_value() {
+            Ok(value) => value,
+            Err(message) => {
+                self.set_error(AppError::validation(message));
+                return;
+            }
+        };
 
-        // Handle color editing with ColorEditState
-        if let Some(ref color_state) = edit_mode.color_state {
-            match color_state.to_color_value() {
-                Some(color) => {
-                    self.appearance_view_model
-                        .set_field_value(field, FieldValue::Color(color));
-                    self.appearance_view_model.edit_mode = None;
-                    self.error = None;
-                }
-                None => {
-                    self.error = Some("Color value cannot be empty".to_string());
-                }
+        self.remember_colors(std::slice::from_ref(&value));
+        self.appearance_view_model.set_field_value(edit_mode.field, value);
+        if edit_mode.suggest_inactive {
+            if let Some((inactive, color)) = edit_mode.inactive_suggestion() {
+                self.notice = Some(format!("Also staged {} {color}", inactive.qualified_name()));
+                self.appearance_view_model.set_field_value(inactive, FieldValue::Color(color));
             }
-            return;
         }
+        self.appearance_view_model.edit_mode = None;
+        self.error = None;
+    }
 
-        // Handle simple value editing
-        let value_str = edit_mode.value.trim();
-
-        // Parse the value based on field type
-        let value = if field.is_integer() {
-            match value_str.parse::<i32>() {
-                Ok(n) => FieldValue::Integer(n),
-                Err(_) => {
-                    self.error = Some("Invalid integer value".to_string());
-                    return;
+    /// Add the colors among `values` to the color history and save it
+    fn remember_colors(&mut self, values: &[FieldValue]) {
+        let mut changed = false;
+        for value in values {
+            if let FieldValue::Color(color) = value {
+                for c in color.colors() {
+                    changed |= self.color_history.record(c);
                 }
             }
-        } else if matches!(
-            field,
-            AppearanceField::StrutsLeft
-                | AppearanceField::StrutsRight
-                | AppearanceField::StrutsTop
-                | AppearanceField::StrutsBottom
-        ) {
-            // Optional integer for struts
-            if value_str.is_empty() {
-                FieldValue::OptionalInteger(None)
-            } else {
-                match value_str.parse::<i32>() {
-                    Ok(n) => FieldValue::OptionalInteger(Some(n)),
-                    Err(_) => {
-                        self.error = Some("Invalid integer value".to_string());
-                        return;
-                    }
-                }
+        }
+        if changed {
+            if let Err(e) = self.color_history.save() {
+                self.set_error(AppError::write("Failed to save color history", &e));
             }
-        } else {
-            FieldValue::String(value_str.to_string())
-        };
-
-        self.appearance_view_model.set_field_value(field, value);
-        self.appearance_view_model.edit_mode = None;
-        self.error = None;
+        }
     }
 
     fn toggle_appearance_bool(&mut self) {
@@ -448,36 +2326,70 @@ impl App {
     }
 
     fn delete_selected_keybinding(&mut self) {
-        let filtered = self.keybindings_view_model.filtered_bindings();
-        if let Some(eb) = filtered.get(self.keybindings_view_model.selected_index) {
+        let selected = self
+            .keybindings_view_model
+            .selected_effective_binding()
+            .map(|eb| (eb.status, eb.original_index, eb.binding.clone()));
+        if let Some((status, original_index, binding)) = selected {
+            if status == BindingStatus::Default {
+                self.set_error(AppError::validation("niri defaults are not in the config"));
+                return;
+            }
             // Only delete if it has an original index (not a new binding)
-            if let Some(original_index) = eb.original_index {
-                self.keybindings_view_model
-                    .pending_changes
-                    .push(KeybindingChange::Delete(original_index));
+            if let Some(original_index) = original_index {
+                self.keybindings_view_model.pending_changes.delete(original_index);
             } else {
-                // Remove the Add entry from pending_changes for new bindings
-                self.keybindings_view_model.pending_changes.retain(|c| {
-                    !matches!(c, KeybindingChange::Add(b) if b.combo() == eb.binding.combo())
-                });
+                // Unstage new bindings instead
+                self.keybindings_view_model.pending_changes.remove_added(&binding);
             }
 
             // Update selection if needed
             let count = self.keybindings_view_model.visible_count();
-            if self.keybindings_view_model.selected_index >= count.saturating_sub(1) {
-                self.keybindings_view_model.selected_index =
+            if self.keybindings_view_model.list.selected_index >= count.saturating_sub(1) {
+                self.keybindings_view_model.list.selected_index =
                     count.saturating_sub(2);
             }
         }
     }
 
+    /// Open the add dialog pre-filled with the selected niri default, so
+    /// confirming copies it into the config
+    fn override_default_binding(&mut self) {
+        match self.keybindings_view_model.selected_effective_binding() {
+            Some(eb) if eb.status == BindingStatus::Default => {
+                let mut edit_mode = EditMode::from_binding(0, eb.binding);
+                edit_mode.is_new = true;
+                self.keybindings_view_model.edit_mode = Some(edit_mode);
+                self.error = None;
+            }
+            Some(_) => self.set_error(AppError::validation("Only niri defaults can be overridden")),
+            None => {}
+        }
+    }
+
     fn start_edit_keybinding(&mut self) {
-        let filtered = self.keybindings_view_model.filtered_bindings();
-        if let Some(eb) = filtered.get(self.keybindings_view_model.selected_index) {
-            let original_index = eb.original_index.unwrap_or(0);
-            self.keybindings_view_model.edit_mode =
-                Some(EditMode::from_binding(original_index, &eb.binding));
-            self.error = None;
+        let Some(eb) = self.keybindings_view_model.selected_effective_binding() else {
+            return;
+        };
+        if eb.status == BindingStatus::Default {
+            self.override_default_binding();
+            return;
+        }
+        let edit_mode = EditMode::from_binding(eb.original_index.unwrap_or(0), eb.binding);
+        let unmodeled = self.unmodeled_binding_parts(eb.binding);
+        let combo = eb.binding.combo();
+        self.keybindings_view_model.edit_mode = Some(edit_mode);
+        self.error = None;
+        if !unmodeled.is_empty() {
+            self.notice = Some(format!("{combo} also has {}; these are kept as is", unmodeled.join(", ")));
+        }
+    }
+
+    /// Parts of a config binding nirikiri doesn't edit, kept as is on save
+    fn unmodeled_binding_parts(&self, binding: &Keybinding) -> Vec<String> {
+        match (&self.config, binding.kdl_index) {
+            (Some(config), Some(index)) => unmodeled_parts(config, index),
+            _ => Vec::new(),
         }
     }
 
@@ -487,51 +2399,259 @@ impl App {
             None => return,
         };
 
+        if let Err(message) = edit_mode.validate() {
+            self.set_error(AppError::validation(message));
+            return;
+        }
+
         // Validate and convert to keybinding
-        let new_binding = match edit_mode.to_keybinding() {
+        let mut new_binding = match edit_mode.to_keybinding() {
             Some(kb) => kb,
             None => {
-                self.error = Some("Invalid keybinding: key combo and action are required".to_string());
+                self.set_error(AppError::validation(
+                    "Invalid keybinding: key combo and action are required",
+                ));
                 return;
             }
         };
 
+        let editing = (!edit_mode.is_new).then_some(edit_mode.original_index);
+        if let Some(existing) = self
+            .keybindings_view_model
+            .conflicting_binding(&new_binding, editing)
+        {
+            self.set_error(AppError::validation(format!(
+                "{} is already bound to {}",
+                existing.combo(),
+                existing.action.short_description()
+            )));
+            return;
+        }
+
         // Add the change
         if edit_mode.is_new {
-            self.keybindings_view_model
-                .pending_changes
-                .push(KeybindingChange::Add(new_binding));
+            self.keybindings_view_model.pending_changes.add(new_binding);
         } else {
+            // Keep the original location so write errors can point at it
+            new_binding.source = self
+                .keybindings_view_model
+                .bindings
+                .get(edit_mode.original_index)
+                .and_then(|b| b.source.clone());
             self.keybindings_view_model
                 .pending_changes
-                .push(KeybindingChange::Modify {
-                    index: edit_mode.original_index,
-                    new: new_binding,
-                });
+                .modify(edit_mode.original_index, new_binding);
+        }
+
+        // Exit edit mode
+        self.keybindings_view_model.edit_mode = None;
+        self.error = None;
+    }
+
+    /// Apply the staged output changes to the live session. Unless kept
+    /// with Enter, they are undone after `PREVIEW_TIMEOUT`.
+    fn preview_changes(&mut self) {
+        if !self.view_model.has_pending_changes() {
+            return;
+        }
+        if self.view_model.all_off() {
+            self.set_error(AppError::validation("The staged changes turn every output off; turn one back on first"));
+            return;
+        }
+        let changes = self.view_model.pending_changes.clone();
+        self.start_preview(changes);
+    }
+
+    /// Send just the staged positions, once moves have paused. They go
+    /// through the same countdown as a full preview, which starts over with
+    /// every move.
+    pub fn poll_live_preview(&mut self) {
+        if self.live_preview_due.is_none_or(|due| Instant::now() < due) {
+            return;
+        }
+        self.live_preview_due = None;
+        let positions: HashMap<String, OutputChange> = self
+            .view_model
+            .pending_changes
+            .iter()
+            .filter(|(_, change)| change.position.is_some())
+            .map(|(name, change)| (name.clone(), OutputChange { position: change.position, ..Default::default() }))
+            .collect();
+        if positions.is_empty() {
+            // Moved back to where niri had them: put back just the
+            // positions, leaving the rest of a running preview alone
+            self.revert_preview_positions();
+            return;
+        }
+        self.start_preview(positions);
+    }
+
+    /// Undo the positions of a running preview, ending it if that was all
+    /// it changed
+    fn revert_preview_positions(&mut self) {
+        let Some(preview) = &mut self.preview else {
+            return;
+        };
+        let positions: HashMap<String, OutputChange> = preview
+            .revert
+            .iter_mut()
+            .filter_map(|(name, change)| {
+                let position = change.position.take()?;
+                Some((name.clone(), OutputChange { position: Some(position), ..Default::default() }))
+            })
+            .collect();
+        if preview.revert.values().all(|change| *change == OutputChange::default()) {
+            self.preview = None;
+        }
+        if !positions.is_empty() {
+            self.send_output_changes(&positions);
+        }
+    }
+
+    /// Send `changes`, remembering how to undo them on top of any preview
+    /// already running
+    fn start_preview(&mut self, changes: HashMap<String, OutputChange>) {
+        // Values from before the first preview, so previewing again and
+        // then reverting still gets back to the start
+        let revert = match self.preview.take() {
+            Some(previous) => self.view_model.revert_changes_over(previous.revert),
+            None => self.view_model.revert_changes(),
+        };
+        // Whatever got through is undone too if sending the rest fails
+        self.preview = Some(ActivePreview { deadline: Instant::now() + PREVIEW_TIMEOUT, revert });
+        self.send_output_changes(&changes);
+    }
+
+    /// Put back what niri only shows for a moment, a preview or an
+    /// identify flash, so quitting never leaves it on screen
+    pub fn restore_outputs(&mut self) {
+        self.end_identify();
+        self.revert_preview();
+    }
+
+    /// Undo a preview by sending back the values niri had before it
+    fn revert_preview(&mut self) {
+        if let Some(preview) = self.preview.take() {
+            self.send_output_changes(&preview.revert);
+        }
+    }
+
+    /// Turn the selected output's picture upside down until
+    /// `poll_identify` puts it back
+    fn identify_output(&mut self) {
+        if self.preview.is_some() {
+            self.set_error(AppError::validation("Keep or revert the preview before identifying an output"));
+            return;
+        }
+        self.end_identify();
+        let Some(output) = self.view_model.selected_output() else {
+            return;
+        };
+        if !output.enabled {
+            let message = format!("{} is off; turn it on to see which monitor it is", output.name);
+            self.set_error(AppError::validation(message));
+            return;
+        }
+        let (name, transform) = (output.name.clone(), output.transform);
+        let flipped = transform.upside_down();
+        if let Err(e) = self.ipc("identify", |client| client.preview_transform(&name, flipped)) {
+            self.set_error(AppError::ipc("Failed to identify output", &e));
+            return;
         }
+        self.notice = Some(format!("{name} is the monitor showing upside down"));
+        self.identify = Some(IdentifyFlash { output: name, transform, until: Instant::now() + IDENTIFY_DURATION });
+    }
 
-        // Exit edit mode
-        self.keybindings_view_model.edit_mode = None;
-        self.error = None;
+    /// Put the identified output's picture back once its time is up
+    pub fn poll_identify(&mut self) {
+        if self.identify.as_ref().is_some_and(|flash| Instant::now() >= flash.until) {
+            self.end_identify();
+        }
     }
 
-    fn preview_changes(&mut self) {
-        if !self.view_model.has_pending_changes() {
+    fn end_identify(&mut self) {
+        let Some(flash) = self.identify.take() else {
             return;
+        };
+        if let Err(e) = self.ipc("identify", |client| client.preview_transform(&flash.output, flash.transform)) {
+            self.set_error(AppError::ipc("Failed to turn the identified output back", &e));
+        }
+    }
+
+    /// Undo the preview once its time is up
+    pub fn poll_preview_timeout(&mut self) {
+        if self.preview.as_ref().is_some_and(|p| Instant::now() >= p.deadline) {
+            self.revert_preview();
+            self.notice = Some("Preview not kept; outputs reverted (changes are still staged)".to_string());
         }
+    }
+
+    /// Send output changes over IPC, returning false if any output failed
+    fn send_output_changes(&mut self, changes: &HashMap<String, OutputChange>) -> bool {
+        let start = Instant::now();
+        let sent = self.send_output_changes_untimed(changes);
+        self.record_ipc("preview", start);
+        sent
+    }
 
-        let mut client = match NiriClient::connect() {
+    fn send_output_changes_untimed(&mut self, changes: &HashMap<String, OutputChange>) -> bool {
+        let mut client = match self.connect() {
             Ok(c) => c,
             Err(e) => {
-                self.error = Some(format!("Failed to connect: {e}"));
-                return;
+                self.set_error(AppError::ipc("Failed to connect to niri", &e));
+                return false;
             }
         };
 
-        for (name, pos) in &self.view_model.pending_changes {
-            if let Err(e) = client.preview_position(name, *pos) {
-                self.error = Some(format!("Preview failed for {name}: {e}"));
-                return;
+        // Power, modes, scales and transforms first: they change the logical
+        // sizes positions are laid out with. An output that fails is
+        // reported and left out of the moves; the others carry on.
+        let mut failed: Vec<(String, anyhow::Error)> = Vec::new();
+        for (name, change) in changes {
+            let resized = change
+                .enabled
+                .map_or(Ok(()), |enabled| client.preview_enabled(name, enabled))
+                .and_then(|()| change.mode.as_ref().map_or(Ok(()), |mode| client.preview_mode(name, mode)))
+                .and_then(|()| change.scale.map_or(Ok(()), |scale| client.preview_scale(name, scale)))
+                .and_then(|()| change.transform.map_or(Ok(()), |t| client.preview_transform(name, t)))
+                .and_then(|()| change.vrr.map_or(Ok(()), |vrr| client.preview_vrr(name, vrr)));
+            if let Err(e) = resized {
+                failed.push((name.clone(), e));
+            }
+        }
+
+        // niri has no call that moves several outputs at once, so order the
+        // moves against the layout it has now, with the new sizes
+        let current = client.get_outputs().unwrap_or_else(|_| self.view_model.outputs.clone());
+        let positions: HashMap<String, OutputChange> = changes
+            .iter()
+            .filter(|(name, _)| !failed.iter().any(|(f, _)| f == *name))
+            .map(|(name, change)| (name.clone(), change.clone()))
+            .collect();
+        let mut moved = Vec::new();
+        for (name, position) in position_moves(&current, &positions) {
+            if failed.iter().any(|(f, _)| *f == name) {
+                continue;
+            }
+            match client.preview_position(&name, position) {
+                Ok(()) => moved.push(name),
+                Err(e) => failed.push((name, e)),
+            }
+        }
+
+        match failed.as_slice() {
+            [] => true,
+            [(name, e)] => {
+                self.set_error(AppError::ipc(format!("Preview failed for {name}"), e));
+                false
+            }
+            _ => {
+                moved.sort();
+                moved.dedup();
+                let outputs: Vec<String> = failed.iter().map(|(name, e)| format!("{name} ({e})")).collect();
+                let sent = if moved.is_empty() { String::new() } else { format!("; moved {}", moved.join(", ")) };
+                self.set_error(AppError::validation(format!("Preview failed for {}{sent}", outputs.join(", "))));
+                false
             }
         }
     }
@@ -539,17 +2659,227 @@ impl App {
     /// Handle keyboard input and return a message
     pub fn handle_input(&mut self) -> Result<Option<Message>> {
         if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
+            let event = event::read()?;
+            if let Event::Mouse(mouse) = event {
+                return Ok(self.handle_mouse(mouse));
+            }
+            if let Event::Key(key) = event {
                 // Handle F-keys for category switching (global)
                 if let Some(category) = Category::from_function_key(key.code) {
                     return Ok(Some(Message::SwitchCategory(category)));
                 }
 
+                // F12 starts or ends the guided tour, F11 skips a step
+                match key.code {
+                    KeyCode::F(10) => return Ok(Some(Message::ToggleDebugOverlay)),
+                    KeyCode::F(12) if self.tutorial.is_some() => return Ok(Some(Message::EndTutorial)),
+                    KeyCode::F(12) => return Ok(Some(Message::StartTutorial)),
+                    KeyCode::F(11) if self.tutorial.is_some() => return Ok(Some(Message::SkipTutorialStep)),
+                    _ => {}
+                }
+
+                // The error log dialog captures input while open
+                if self.error_log.open {
+                    return Ok(self.handle_error_log_input(key.code));
+                }
+
+                if self.health_report.is_some() {
+                    return Ok(match key.code {
+                        KeyCode::Enter | KeyCode::Esc => Some(Message::DismissHealthReport),
+                        KeyCode::Char('q') => Some(Message::Quit),
+                        _ => None,
+                    });
+                }
+
+                if self.keybindings_view_model.lockout_warning.is_some() {
+                    return Ok(match key.code {
+                        KeyCode::Char('a') => Some(KeybindingsMessage::AddFallbackBinds.into()),
+                        KeyCode::Char('s') => Some(KeybindingsMessage::SaveWithoutFallback.into()),
+                        KeyCode::Esc | KeyCode::Char('q') => Some(KeybindingsMessage::CancelLockoutWarning.into()),
+                        _ => None,
+                    });
+                }
+
+                if self.view_model.disabled_warning.is_some() {
+                    return Ok(match key.code {
+                        KeyCode::Char('e') => Some(OutputMessage::EnableAndSave.into()),
+                        KeyCode::Char('s') => Some(OutputMessage::SaveDisabledPositions.into()),
+                        KeyCode::Esc | KeyCode::Char('q') => Some(OutputMessage::CancelDisabledWarning.into()),
+                        _ => None,
+                    });
+                }
+
+                if self.save_conflict.is_some() {
+                    return Ok(match key.code {
+                        KeyCode::Char('o') => Some(Message::OverwriteConflict),
+                        KeyCode::Char('r') => Some(Message::ReloadAndReapply),
+                        KeyCode::Esc | KeyCode::Char('q') => Some(Message::CancelSaveConflict),
+                        _ => None,
+                    });
+                }
+
+                if let Some(ref mut gallery) = self.template_gallery {
+                    return Ok(match key.code {
+                        KeyCode::Esc => Some(Message::CancelTemplateGallery),
+                        KeyCode::Enter => Some(Message::InsertTemplate),
+                        KeyCode::Char('j') | KeyCode::Down => {
+                            gallery.select_next();
+                            None
+                        }
+                        KeyCode::Char('k') | KeyCode::Up => {
+                            gallery.select_prev();
+                            None
+                        }
+                        _ => None,
+                    });
+                }
+
+                if let Some(ref mut wizard) = self.workspace_spawn {
+                    match key.code {
+                        KeyCode::Esc => return Ok(Some(Message::CancelWorkspaceSpawn)),
+                        KeyCode::Enter => return Ok(Some(Message::ConfirmWorkspaceSpawn)),
+                        KeyCode::Tab | KeyCode::Down => wizard.focus_next(),
+                        KeyCode::BackTab | KeyCode::Up => wizard.focus_prev(),
+                        KeyCode::Left => wizard.cursor_left(),
+                        KeyCode::Right => wizard.cursor_right(),
+                        KeyCode::Backspace => wizard.delete_char(),
+                        KeyCode::Char(c) => wizard.insert_char(c),
+                        _ => {}
+                    }
+                    return Ok(None);
+                }
+
+                if let Some(ref mut editor) = self.label_editor {
+                    match key.code {
+                        KeyCode::Esc => return Ok(Some(OutputMessage::CancelOutputLabel.into())),
+                        KeyCode::Enter => return Ok(Some(OutputMessage::ConfirmOutputLabel.into())),
+                        KeyCode::Tab | KeyCode::BackTab | KeyCode::Down | KeyCode::Up => editor.focus_next(),
+                        KeyCode::Left => editor.field_mut().cursor_left(),
+                        KeyCode::Right => editor.field_mut().cursor_right(),
+                        KeyCode::Backspace => editor.field_mut().delete_char(),
+                        KeyCode::Char(c) => editor.field_mut().insert_char(c),
+                        _ => {}
+                    }
+                    return Ok(None);
+                }
+
+                if let Some(ref mut picker) = self.profile_picker {
+                    if let Some(ref mut field) = picker.naming {
+                        match key.code {
+                            KeyCode::Esc => {
+                                picker.naming = None;
+                                if self.output_profiles.profiles.is_empty() {
+                                    return Ok(Some(OutputMessage::CloseOutputProfiles.into()));
+                                }
+                            }
+                            KeyCode::Enter => return Ok(Some(OutputMessage::SaveOutputProfile.into())),
+                            KeyCode::Left => field.cursor_left(),
+                            KeyCode::Right => field.cursor_right(),
+                            KeyCode::Backspace => field.delete_char(),
+                            KeyCode::Char(c) => field.insert_char(c),
+                            _ => {}
+                        }
+                        return Ok(None);
+                    }
+                    let count = self.output_profiles.profiles.len();
+                    match key.code {
+                        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('O') => {
+                            return Ok(Some(OutputMessage::CloseOutputProfiles.into()))
+                        }
+                        KeyCode::Enter => return Ok(Some(OutputMessage::ApplyOutputProfile.into())),
+                        KeyCode::Char('d') => return Ok(Some(OutputMessage::DeleteOutputProfile.into())),
+                        KeyCode::Char('a') => return Ok(Some(OutputMessage::ToggleProfileAutoApply.into())),
+                        KeyCode::Char('s') => {
+                            let name = picker.selected(&self.output_profiles).map(|p| p.name.clone());
+                            picker.start_naming(name.as_deref());
+                        }
+                        KeyCode::Char('n') => picker.start_naming(None),
+                        KeyCode::Char('j') | KeyCode::Down => picker.list.select_next(count),
+                        KeyCode::Char('k') | KeyCode::Up => picker.list.select_prev(count),
+                        _ => {}
+                    }
+                    return Ok(None);
+                }
+
+                if let Some(ref mut editor) = self.position_editor {
+                    match key.code {
+                        KeyCode::Esc => return Ok(Some(OutputMessage::CancelOutputPosition.into())),
+                        KeyCode::Enter => return Ok(Some(OutputMessage::ConfirmOutputPosition.into())),
+                        KeyCode::Tab | KeyCode::BackTab | KeyCode::Down | KeyCode::Up => editor.focus_next(),
+                        KeyCode::Left => editor.field_mut().cursor_left(),
+                        KeyCode::Right => editor.field_mut().cursor_right(),
+                        KeyCode::Backspace => editor.field_mut().delete_char(),
+                        KeyCode::Char(c) => editor.field_mut().insert_char(c),
+                        _ => {}
+                    }
+                    return Ok(None);
+                }
+
+                if let Some(ref mut editor) = self.color_editor {
+                    match key.code {
+                        KeyCode::Esc => return Ok(Some(OutputMessage::CancelOutputColors.into())),
+                        KeyCode::Enter => return Ok(Some(OutputMessage::ConfirmOutputColors.into())),
+                        KeyCode::Tab | KeyCode::BackTab | KeyCode::Down | KeyCode::Up => editor.focus_next(),
+                        KeyCode::Left => editor.field_mut().cursor_left(),
+                        KeyCode::Right => editor.field_mut().cursor_right(),
+                        KeyCode::Backspace => editor.field_mut().delete_char(),
+                        KeyCode::Char(c) => editor.field_mut().insert_char(c),
+                        _ => {}
+                    }
+                    return Ok(None);
+                }
+
+                if let Some(ref discovery) = self.config_source {
+                    return Ok(match key.code {
+                        KeyCode::Char('c') if discovery.needs_copy() => Some(Message::CopySystemConfig),
+                        KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') | KeyCode::Char('C') => {
+                            Some(Message::CloseConfigSource)
+                        }
+                        _ => None,
+                    });
+                }
+
+                if let Some(ref mut quick_open) = self.quick_open {
+                    return Ok(match key.code {
+                        KeyCode::Esc => Some(Message::CancelQuickOpen),
+                        KeyCode::Enter => Some(Message::ConfirmQuickOpen),
+                        KeyCode::Char('j') | KeyCode::Down => {
+                            quick_open.select_next();
+                            None
+                        }
+                        KeyCode::Char('k') | KeyCode::Up => {
+                            quick_open.select_prev();
+                            None
+                        }
+                        _ => None,
+                    });
+                }
+
+                // Ctrl+O opens quick open from every category, unless the
+                // category has a dialog or text field open
+                if key.code == KeyCode::Char('o')
+                    && key.modifiers == KeyModifiers::CONTROL
+                    && !self.category_captures_keys()
+                {
+                    return Ok(Some(Message::OpenQuickOpen));
+                }
+
+                // Alt+Left/Right resize the left pane in every category,
+                // unless a text field there takes the arrows
+                if key.modifiers.contains(KeyModifiers::ALT) && !self.category_captures_keys() {
+                    match key.code {
+                        KeyCode::Left => return Ok(Some(Message::ResizePane(-1))),
+                        KeyCode::Right => return Ok(Some(Message::ResizePane(1))),
+                        _ => {}
+                    }
+                }
+
                 // Handle category-specific input
                 let msg = match self.current_category {
                     Category::Outputs => self.handle_outputs_input(key.code, key.modifiers),
                     Category::Keybindings => self.handle_keybindings_input(key.code, key.modifiers),
                     Category::Appearance => self.handle_appearance_input(key.code, key.modifiers),
+                    Category::General => self.handle_general_input(key.code, key.modifiers),
                 };
                 return Ok(msg);
             }
@@ -557,52 +2887,337 @@ impl App {
         Ok(None)
     }
 
+    /// Whether the current category has a dialog or text field open that
+    /// its own handler takes every key for
+    fn category_captures_keys(&self) -> bool {
+        match self.current_category {
+            Category::Outputs => self.view_model.mode_browser.is_some() || self.view_model.search_mode,
+            Category::Keybindings => {
+                let view_model = &self.keybindings_view_model;
+                view_model.dry_run.is_some()
+                    || view_model.edit_mode.is_some()
+                    || view_model.conflict_resolver.is_some()
+                    || view_model.bind_overlay.is_some()
+                    || view_model.tag_editor.is_some()
+                    || view_model.template_prompt.is_some()
+                    || view_model.generator.is_some()
+                    || view_model.search_mode
+            }
+            Category::Appearance => {
+                self.appearance_view_model.edit_mode.is_some() || self.appearance_view_model.section_edit.is_some()
+            }
+            Category::General => self.general_view_model.edit.is_some(),
+        }
+    }
+
+    /// Mouse input drags monitors on the outputs canvas; it's ignored while
+    /// a dialog is open
+    fn handle_mouse(&self, mouse: MouseEvent) -> Option<Message> {
+        let dialog_open = self.error_log.open
+            || self.health_report.is_some()
+            || self.template_gallery.is_some()
+            || self.quick_open.is_some()
+            || self.config_source.is_some()
+            || self.workspace_spawn.is_some()
+            || self.label_editor.is_some()
+            || self.profile_picker.is_some()
+            || self.color_editor.is_some()
+            || self.position_editor.is_some()
+            || self.view_model.disabled_warning.is_some()
+            || self.save_conflict.is_some()
+            || self.tutorial.is_some();
+        if self.current_category != Category::Outputs || dialog_open {
+            return None;
+        }
+        let (column, row) = (mouse.column, mouse.row);
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => Some(OutputMessage::CanvasPress { column, row }.into()),
+            MouseEventKind::Drag(MouseButton::Left) if self.viewport.drag.is_some() => {
+                Some(OutputMessage::CanvasDrag { column, row }.into())
+            }
+            MouseEventKind::Up(MouseButton::Left) if self.viewport.drag.is_some() => Some(OutputMessage::CanvasRelease.into()),
+            _ => None,
+        }
+    }
+
+    fn handle_error_log_input(&mut self, code: KeyCode) -> Option<Message> {
+        match code {
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') | KeyCode::Char('!') => {
+                Some(Message::CloseErrorLog)
+            }
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.error_log.select_next();
+                None
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.error_log.select_prev();
+                None
+            }
+            _ => None,
+        }
+    }
+
     fn handle_outputs_input(&self, code: KeyCode, modifiers: KeyModifiers) -> Option<Message> {
+        // Mode browser captures navigation while open
+        if self.view_model.mode_browser.is_some() {
+            return match code {
+                KeyCode::Esc | KeyCode::Char('m') => Some(OutputMessage::CloseModeBrowser.into()),
+                KeyCode::Char('j') | KeyCode::Down => Some(OutputMessage::SelectNextMode.into()),
+                KeyCode::Char('k') | KeyCode::Up => Some(OutputMessage::SelectPrevMode.into()),
+                KeyCode::Char('f') => Some(OutputMessage::CycleRefreshFilter.into()),
+                KeyCode::Enter => Some(OutputMessage::StageSelectedMode.into()),
+                KeyCode::Tab => Some(OutputMessage::SelectNextOutput.into()),
+                KeyCode::BackTab => Some(OutputMessage::SelectPrevOutput.into()),
+                _ => None,
+            };
+        }
+
+        // Typing a search filters the list; Enter keeps the filter
+        if self.view_model.search_mode {
+            let mut query = self.view_model.search_query.clone();
+            return match code {
+                KeyCode::Esc => Some(Message::ClearSearch),
+                KeyCode::Enter => Some(Message::EndSearch),
+                KeyCode::Backspace => {
+                    query.pop();
+                    Some(Message::UpdateSearch(query))
+                }
+                KeyCode::Char(c) => {
+                    query.push(c);
+                    Some(Message::UpdateSearch(query))
+                }
+                _ => None,
+            };
+        }
+
+        let list_focused = self.view_model.focused_pane == OutputPane::List;
         match (code, modifiers) {
             // Quit
             (KeyCode::Char('q'), _) => Some(Message::Quit),
             (KeyCode::Char('c'), KeyModifiers::CONTROL) => Some(Message::Quit),
+            (KeyCode::Char('C'), _) => Some(Message::OpenConfigSource),
+            (KeyCode::Char('!'), _) => Some(Message::OpenErrorLog),
+            (KeyCode::Char('t'), _) => Some(Message::OpenTemplateGallery),
+            (KeyCode::Char('W'), _) => Some(Message::OpenWorkspaceSpawn),
 
-            // Tab cycles between monitors
-            (KeyCode::Tab, _) => Some(Message::SelectNextOutput),
-            (KeyCode::BackTab, _) => Some(Message::SelectPrevOutput),
+            // Filter by connector, make or model
+            (KeyCode::Char('/'), _) => Some(Message::StartSearch),
+            (KeyCode::Esc, _) if !self.view_model.search_query.is_empty() => Some(Message::ClearSearch),
 
-            // Snap positioning with Shift+HJKL (uppercase)
-            (KeyCode::Char('H'), _) => Some(Message::SnapLeft),
-            (KeyCode::Char('L'), _) => Some(Message::SnapRight),
-            (KeyCode::Char('K'), _) => Some(Message::SnapAbove),
-            (KeyCode::Char('J'), _) => Some(Message::SnapBelow),
+            // Tab cycles between monitors
+            (KeyCode::Tab, _) => Some(OutputMessage::SelectNextOutput.into()),
+            (KeyCode::BackTab, _) => Some(OutputMessage::SelectPrevOutput.into()),
+
+            // Snap positioning with Shift+HJKL (uppercase); holding Alt locks
+            // the other axis so e.g. a left snap keeps the current Y
+            (KeyCode::Char('H'), m) => Some(OutputMessage::SnapLeft { axis_lock: m.contains(KeyModifiers::ALT) }.into()),
+            (KeyCode::Char('L'), m) => Some(OutputMessage::SnapRight { axis_lock: m.contains(KeyModifiers::ALT) }.into()),
+            (KeyCode::Char('K'), m) => Some(OutputMessage::SnapAbove { axis_lock: m.contains(KeyModifiers::ALT) }.into()),
+            (KeyCode::Char('J'), m) => Some(OutputMessage::SnapBelow { axis_lock: m.contains(KeyModifiers::ALT) }.into()),
+            // a cycles top/center/bottom for left and right snaps, A cycles
+            // left/center/right for snaps above and below
+            (KeyCode::Char('a'), _) => Some(OutputMessage::CycleSnapAlignment { stacked: false }.into()),
+            (KeyCode::Char('A'), _) => Some(OutputMessage::CycleSnapAlignment { stacked: true }.into()),
+
+            // Move the selected monitor to the origin
+            (KeyCode::Char('o'), _) => Some(OutputMessage::MoveToOrigin.into()),
+
+            // With the list focused, j/k and the arrows pick an output
+            (KeyCode::Char('j') | KeyCode::Down, KeyModifiers::NONE) if list_focused => {
+                Some(OutputMessage::SelectNextOutput.into())
+            }
+            (KeyCode::Char('k') | KeyCode::Up, KeyModifiers::NONE) if list_focused => {
+                Some(OutputMessage::SelectPrevOutput.into())
+            }
+            (KeyCode::Char('h') | KeyCode::Char('l') | KeyCode::Left | KeyCode::Right, KeyModifiers::NONE)
+                if list_focused =>
+            {
+                None
+            }
+            (KeyCode::Char('F'), _) => Some(OutputMessage::ToggleOutputPane.into()),
 
             // hjkl for movement
-            (KeyCode::Char('h'), _) => Some(Message::MoveOutput { dx: -10, dy: 0 }),
-            (KeyCode::Char('j'), _) => Some(Message::MoveOutput { dx: 0, dy: 10 }),
-            (KeyCode::Char('k'), _) => Some(Message::MoveOutput { dx: 0, dy: -10 }),
-            (KeyCode::Char('l'), _) => Some(Message::MoveOutput { dx: 10, dy: 0 }),
+            (KeyCode::Char('h'), _) => Some(OutputMessage::MoveOutput { dx: -10, dy: 0 }.into()),
+            (KeyCode::Char('j'), _) => Some(OutputMessage::MoveOutput { dx: 0, dy: 10 }.into()),
+            (KeyCode::Char('k'), _) => Some(OutputMessage::MoveOutput { dx: 0, dy: -10 }.into()),
+            (KeyCode::Char('l'), _) => Some(OutputMessage::MoveOutput { dx: 10, dy: 0 }.into()),
 
             // Zoom (for large multi-monitor setups)
-            (KeyCode::Char('+') | KeyCode::Char('='), _) => Some(Message::ZoomIn),
-            (KeyCode::Char('-'), _) => Some(Message::ZoomOut),
-            (KeyCode::Char('0'), _) => Some(Message::ResetView),
+            (KeyCode::Char('+') | KeyCode::Char('='), _) => Some(OutputMessage::ZoomIn.into()),
+            (KeyCode::Char('-'), _) => Some(OutputMessage::ZoomOut.into()),
+            (KeyCode::Char('0'), _) => Some(OutputMessage::ResetView.into()),
+            (KeyCode::Char('z'), _) => Some(OutputMessage::ZoomToSelected.into()),
+
+            // Pan layouts that don't fit the canvas
+            (KeyCode::Left, KeyModifiers::CONTROL) => Some(OutputMessage::PanCanvas { dx: -1, dy: 0 }.into()),
+            (KeyCode::Right, KeyModifiers::CONTROL) => Some(OutputMessage::PanCanvas { dx: 1, dy: 0 }.into()),
+            (KeyCode::Up, KeyModifiers::CONTROL) => Some(OutputMessage::PanCanvas { dx: 0, dy: -1 }.into()),
+            (KeyCode::Down, KeyModifiers::CONTROL) => Some(OutputMessage::PanCanvas { dx: 0, dy: 1 }.into()),
+
+            // Arrows move the selected monitor like hjkl; Shift takes big
+            // steps and Ctrl+Shift single pixels
+            (KeyCode::Left | KeyCode::Right | KeyCode::Up | KeyCode::Down, m) => {
+                let step = if m.contains(KeyModifiers::CONTROL | KeyModifiers::SHIFT) {
+                    1
+                } else if m.contains(KeyModifiers::SHIFT) {
+                    100
+                } else {
+                    10
+                };
+                let (dx, dy) = match code {
+                    KeyCode::Left => (-step, 0),
+                    KeyCode::Right => (step, 0),
+                    KeyCode::Up => (0, -step),
+                    _ => (0, step),
+                };
+                Some(OutputMessage::MoveOutput { dx, dy }.into())
+            }
+
+            // Column layout preview of the selected monitor
+            (KeyCode::Char('c'), _) => Some(OutputMessage::ToggleColumnPreview.into()),
+            (KeyCode::Char('['), _) => Some(OutputMessage::ChangePreviewColumns(-1).into()),
+            (KeyCode::Char(']'), _) => Some(OutputMessage::ChangePreviewColumns(1).into()),
+
+            // Mode list for the selected output
+            (KeyCode::Char('m'), _) => Some(OutputMessage::OpenModeBrowser.into()),
+
+            // Scale in common fractional steps
+            (KeyCode::Char('>'), _) => Some(OutputMessage::StepScale(1).into()),
+            (KeyCode::Char('<'), _) => Some(OutputMessage::StepScale(-1).into()),
+            (KeyCode::Char('D'), _) => Some(OutputMessage::StageSuggestedScale.into()),
+
+            // Transform, variable refresh rate and power
+            (KeyCode::Char('T'), _) => Some(OutputMessage::CycleTransform.into()),
+            (KeyCode::Char('v'), _) => Some(OutputMessage::ToggleVrr.into()),
+            (KeyCode::Char('d'), _) => Some(OutputMessage::ToggleOutputEnabled.into()),
+            (KeyCode::Char('f'), _) => Some(OutputMessage::ToggleFocusAtStartup.into()),
 
             // Normalize layout to origin
-            (KeyCode::Char('n'), _) => Some(Message::Normalize),
+            (KeyCode::Char('n'), _) => Some(OutputMessage::Normalize.into()),
+            (KeyCode::Char('i'), _) => Some(OutputMessage::IdentifyOutput.into()),
+
+            // Friendly name and note
+            (KeyCode::Char('N'), _) => Some(OutputMessage::OpenOutputLabel.into()),
+            (KeyCode::Char('b'), _) => Some(OutputMessage::OpenOutputColors.into()),
+            (KeyCode::Char('O'), _) => Some(OutputMessage::OpenOutputProfiles.into()),
+            (KeyCode::Char('g'), _) => Some(OutputMessage::OpenOutputPosition.into()),
+
+            // Fold duplicate output blocks together
+            (KeyCode::Char('M'), _) => Some(OutputMessage::MergeOutputBlocks.into()),
+
+            // Write what niri reports right now, e.g. after `niri msg output`
+            (KeyCode::Char('S'), _) => Some(OutputMessage::PersistLiveLayout.into()),
 
             // Actions
             (KeyCode::Char('s'), _) => Some(Message::Save),
             (KeyCode::Char('r'), _) => Some(Message::Reload),
-            (KeyCode::Char('p'), _) => Some(Message::PreviewChanges),
-            (KeyCode::Esc, _) => Some(Message::RevertPreview),
+            (KeyCode::Char('R'), _) => Some(Message::ReloadAll),
+            (KeyCode::Char('p'), _) => Some(OutputMessage::PreviewChanges.into()),
+            (KeyCode::Char('P'), _) => Some(OutputMessage::ToggleLivePreview.into()),
+            (KeyCode::Enter, _) if self.preview.is_some() => Some(OutputMessage::KeepPreview.into()),
+            (KeyCode::Char('e'), _) => Some(Message::OpenInEditor),
+            (KeyCode::Esc, _) => Some(OutputMessage::RevertPreview.into()),
 
             _ => None,
         }
     }
 
     fn handle_keybindings_input(&mut self, code: KeyCode, modifiers: KeyModifiers) -> Option<Message> {
+        // The dry-run dialog sits on top of the edit dialog
+        if self.keybindings_view_model.dry_run.is_some() {
+            return match code {
+                KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('x') => Some(KeybindingsMessage::CloseDryRun.into()),
+                KeyCode::Enter | KeyCode::Char('r') => Some(KeybindingsMessage::RunDryRun.into()),
+                _ => None,
+            };
+        }
+
         // Handle edit mode input
         if self.keybindings_view_model.edit_mode.is_some() {
             return self.handle_edit_mode_input(code, modifiers);
         }
 
+        if self.keybindings_view_model.conflict_resolver.is_some() {
+            return match code {
+                KeyCode::Char('k') => Some(KeybindingsMessage::ResolveConflict(ConflictChoice::Keep).into()),
+                KeyCode::Char('r') => Some(KeybindingsMessage::ResolveConflict(ConflictChoice::Replace).into()),
+                KeyCode::Char('b') => Some(KeybindingsMessage::ResolveConflict(ConflictChoice::Rebind).into()),
+                KeyCode::Esc => Some(KeybindingsMessage::KeepRemainingConflicts.into()),
+                _ => None,
+            };
+        }
+
+        if let Some(ref mut overlay) = self.keybindings_view_model.bind_overlay {
+            if let Some(ref capture) = overlay.capture {
+                return match code {
+                    KeyCode::Esc => Some(KeybindingsMessage::CancelRebind.into()),
+                    KeyCode::Tab => {
+                        overlay.toggle_capture_mod();
+                        None
+                    }
+                    _ => captured_combo(code, modifiers, capture.mod_key).map(|c| KeybindingsMessage::RebindTo(c).into()),
+                };
+            }
+            match code {
+                KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('?') => return Some(KeybindingsMessage::CloseBindOverlay.into()),
+                KeyCode::Enter => return Some(KeybindingsMessage::StartRebind.into()),
+                KeyCode::Char('j') | KeyCode::Down => overlay.select_next(),
+                KeyCode::Char('k') | KeyCode::Up => overlay.select_prev(),
+                KeyCode::PageDown => overlay.list.page_down(overlay.entries.len()),
+                KeyCode::PageUp => overlay.list.page_up(),
+                _ => {}
+            }
+            return None;
+        }
+
+        if let Some(ref mut editor) = self.keybindings_view_model.tag_editor {
+            match code {
+                KeyCode::Esc => return Some(KeybindingsMessage::CancelBindingTags.into()),
+                KeyCode::Enter => return Some(KeybindingsMessage::ConfirmBindingTags.into()),
+                KeyCode::Left => editor.field.cursor_left(),
+                KeyCode::Right => editor.field.cursor_right(),
+                KeyCode::Backspace => editor.field.delete_char(),
+                KeyCode::Char(c) => editor.field.insert_char(c),
+                _ => {}
+            }
+            return None;
+        }
+
+        // Handle binding template dialog input
+        if let Some(ref mut prompt) = self.keybindings_view_model.template_prompt {
+            match code {
+                KeyCode::Esc => return Some(KeybindingsMessage::CancelBindingTemplate.into()),
+                KeyCode::Enter => return Some(KeybindingsMessage::ConfirmBindingTemplate.into()),
+                KeyCode::Tab | KeyCode::Down => prompt.focus_next(),
+                KeyCode::BackTab | KeyCode::Up => prompt.focus_prev(),
+                KeyCode::Left if prompt.focused == 0 => prompt.cycle_template(false),
+                KeyCode::Right if prompt.focused == 0 => prompt.cycle_template(true),
+                KeyCode::Left => prompt.cursor_left(),
+                KeyCode::Right => prompt.cursor_right(),
+                KeyCode::Backspace => prompt.delete_char(),
+                KeyCode::Char(c) => prompt.insert_char(c),
+                _ => {}
+            }
+            return None;
+        }
+
+        // Handle generator dialog input
+        if let Some(ref mut generator) = self.keybindings_view_model.generator {
+            return match code {
+                KeyCode::Esc => Some(KeybindingsMessage::CancelBindingGenerator.into()),
+                KeyCode::Enter => Some(KeybindingsMessage::ConfirmBindingGenerator.into()),
+                KeyCode::Left | KeyCode::Char('h') => {
+                    generator.prev_modifier();
+                    None
+                }
+                KeyCode::Right | KeyCode::Char('l') | KeyCode::Char(' ') => {
+                    generator.next_modifier();
+                    None
+                }
+                _ => None,
+            };
+        }
+
         // Handle search mode input
         if self.keybindings_view_model.search_mode {
             match code {
@@ -631,10 +3246,16 @@ impl App {
             // Quit
             (KeyCode::Char('q'), _) => Some(Message::Quit),
             (KeyCode::Char('c'), KeyModifiers::CONTROL) => Some(Message::Quit),
+            (KeyCode::Char('C'), _) => Some(Message::OpenConfigSource),
+            (KeyCode::Char('!'), _) => Some(Message::OpenErrorLog),
+            (KeyCode::Char('t'), _) => Some(Message::OpenTemplateGallery),
+            (KeyCode::Char('W'), _) => Some(Message::OpenWorkspaceSpawn),
 
             // Navigation
-            (KeyCode::Char('j'), _) | (KeyCode::Down, _) => Some(Message::SelectNextKeybinding),
-            (KeyCode::Char('k'), _) | (KeyCode::Up, _) => Some(Message::SelectPrevKeybinding),
+            (KeyCode::Char('j'), _) | (KeyCode::Down, _) => Some(KeybindingsMessage::SelectNextKeybinding.into()),
+            (KeyCode::Char('k'), _) | (KeyCode::Up, _) => Some(KeybindingsMessage::SelectPrevKeybinding.into()),
+            (KeyCode::PageDown, _) => Some(KeybindingsMessage::PageDownKeybindings.into()),
+            (KeyCode::PageUp, _) => Some(KeybindingsMessage::PageUpKeybindings.into()),
 
             // Search
             (KeyCode::Char('/'), _) => Some(Message::StartSearch),
@@ -647,25 +3268,44 @@ impl App {
             }
 
             // Actions
-            (KeyCode::Enter, _) => Some(Message::StartEdit),
-            (KeyCode::Char('a'), _) => Some(Message::AddKeybinding),
-            (KeyCode::Char('d'), _) => Some(Message::DeleteKeybinding),
+            (KeyCode::Enter, _) => Some(KeybindingsMessage::StartEdit.into()),
+            (KeyCode::Char('a'), _) => Some(KeybindingsMessage::AddKeybinding.into()),
+            (KeyCode::Char('d'), _) => Some(KeybindingsMessage::DeleteKeybinding.into()),
+            (KeyCode::Char('p'), _) => Some(KeybindingsMessage::ToggleBindingFlag(BindingFlag::Repeat).into()),
+            (KeyCode::Char('l'), _) => Some(KeybindingsMessage::ToggleBindingFlag(BindingFlag::AllowWhenLocked).into()),
+            (KeyCode::Char('P'), _) => Some(KeybindingsMessage::FixRepeatLint.into()),
+            (KeyCode::Char('g'), _) => Some(KeybindingsMessage::OpenBindingGenerator.into()),
+            (KeyCode::Char('T'), _) => Some(KeybindingsMessage::OpenBindingTemplate.into()),
+            (KeyCode::Char('x'), _) => Some(KeybindingsMessage::OpenDryRun.into()),
+            (KeyCode::Char('#'), _) => Some(KeybindingsMessage::OpenBindingTags.into()),
+            (KeyCode::Char('?'), _) => Some(KeybindingsMessage::OpenBindOverlay.into()),
+            (KeyCode::Char('D'), _) => Some(KeybindingsMessage::ToggleDefaultBinds.into()),
+            (KeyCode::Char('M'), _) => Some(KeybindingsMessage::ToggleMissingFilter.into()),
+            (KeyCode::Char('n'), _) => Some(KeybindingsMessage::ToggleRawKeyNames.into()),
+            (KeyCode::Char('c'), _) => Some(KeybindingsMessage::ToggleCategoryColumn.into()),
+            (KeyCode::Char('f'), _) => Some(KeybindingsMessage::ToggleFlagsColumn.into()),
+            (KeyCode::Char('<'), _) => Some(KeybindingsMessage::AdjustComboWidth(-2).into()),
+            (KeyCode::Char('>'), _) => Some(KeybindingsMessage::AdjustComboWidth(2).into()),
+            (KeyCode::Char('o'), _) => Some(KeybindingsMessage::OverrideDefaultBind.into()),
             (KeyCode::Char('s'), _) => Some(Message::Save),
             (KeyCode::Char('r'), _) => Some(Message::Reload),
+            (KeyCode::Char('R'), _) => Some(Message::ReloadAll),
+            (KeyCode::Char('e'), _) => Some(Message::OpenInEditor),
 
             _ => None,
         }
     }
 
-    fn handle_edit_mode_input(&mut self, code: KeyCode, _modifiers: KeyModifiers) -> Option<Message> {
+    fn handle_edit_mode_input(&mut self, code: KeyCode, modifiers: KeyModifiers) -> Option<Message> {
         let edit_mode = match &mut self.keybindings_view_model.edit_mode {
             Some(em) => em,
             None => return None,
         };
 
         match code {
-            KeyCode::Esc => Some(Message::CancelEdit),
-            KeyCode::Enter => Some(Message::ConfirmEdit),
+            KeyCode::Esc => Some(KeybindingsMessage::CancelEdit.into()),
+            KeyCode::Enter => Some(KeybindingsMessage::ConfirmEdit.into()),
+            KeyCode::Char('t') if modifiers.contains(KeyModifiers::CONTROL) => Some(KeybindingsMessage::OpenDryRun.into()),
             KeyCode::Tab => {
                 edit_mode.focused_field = edit_mode.focused_field.next();
                 None
@@ -686,7 +3326,7 @@ impl App {
             // Left/Right arrows for cursor movement in text fields, or action type cycling
             KeyCode::Left => {
                 match edit_mode.focused_field {
-                    EditField::KeyCombo | EditField::ActionValue => {
+                    EditField::KeyCombo | EditField::ActionValue | EditField::Cooldown => {
                         edit_mode.cursor_left();
                     }
                     EditField::ActionType => {
@@ -698,7 +3338,7 @@ impl App {
             }
             KeyCode::Right => {
                 match edit_mode.focused_field {
-                    EditField::KeyCombo | EditField::ActionValue => {
+                    EditField::KeyCombo | EditField::ActionValue | EditField::Cooldown => {
                         edit_mode.cursor_right();
                     }
                     EditField::ActionType => {
@@ -729,8 +3369,8 @@ impl App {
                     EditField::AllowWhenLocked => {
                         edit_mode.toggle_allow_when_locked();
                     }
-                    EditField::KeyCombo => {
-                        // Don't add space to key combo
+                    EditField::KeyCombo | EditField::Cooldown => {
+                        // Don't add spaces to key combos or durations
                     }
                     EditField::ActionType => {
                         // Space also cycles action type forward
@@ -755,44 +3395,54 @@ impl App {
         if self.appearance_view_model.edit_mode.is_some() {
             return self.handle_appearance_edit_mode_input(code, modifiers);
         }
+        if self.appearance_view_model.section_edit.is_some() {
+            return self.handle_section_edit_input(code);
+        }
 
         match (code, modifiers) {
             // Quit
             (KeyCode::Char('q'), _) => Some(Message::Quit),
             (KeyCode::Char('c'), KeyModifiers::CONTROL) => Some(Message::Quit),
+            (KeyCode::Char('C'), _) => Some(Message::OpenConfigSource),
+            (KeyCode::Char('!'), _) => Some(Message::OpenErrorLog),
+            (KeyCode::Char('t'), _) => Some(Message::OpenTemplateGallery),
+            (KeyCode::Char('W'), _) => Some(Message::OpenWorkspaceSpawn),
 
             // Navigation
-            (KeyCode::Char('j'), _) | (KeyCode::Down, _) => Some(Message::SelectNextAppearanceSetting),
-            (KeyCode::Char('k'), _) | (KeyCode::Up, _) => Some(Message::SelectPrevAppearanceSetting),
+            (KeyCode::Char('j'), _) | (KeyCode::Down, _) => Some(AppearanceMessage::SelectNextAppearanceSetting.into()),
+            (KeyCode::Char('k'), _) | (KeyCode::Up, _) => Some(AppearanceMessage::SelectPrevAppearanceSetting.into()),
+            (KeyCode::PageDown, _) => Some(AppearanceMessage::PageDownAppearance.into()),
+            (KeyCode::PageUp, _) => Some(AppearanceMessage::PageUpAppearance.into()),
 
             // Expand/Collapse sections
-            (KeyCode::Tab, _) => Some(Message::ToggleSection),
+            (KeyCode::Tab, _) => Some(AppearanceMessage::ToggleSection.into()),
 
             // Edit/Toggle
-            (KeyCode::Enter, _) => Some(Message::StartAppearanceEdit),
+            (KeyCode::Enter, _) => Some(AppearanceMessage::StartAppearanceEdit.into()),
+            (KeyCode::Char('E'), _) => Some(AppearanceMessage::StartSectionEdit.into()),
             (KeyCode::Char(' '), _) => {
                 // Space toggles booleans or cycles enums
                 if let Some(AppearanceListItem::Field(field)) = self.appearance_view_model.selected_item() {
                     if field.is_boolean() {
-                        return Some(Message::ToggleAppearanceBool);
+                        return Some(AppearanceMessage::ToggleAppearanceBool.into());
                     } else if field.is_enum() {
-                        return Some(Message::CycleEnumForward);
+                        return Some(AppearanceMessage::CycleEnumForward.into());
                     }
                 } else if let Some(AppearanceListItem::SectionHeader(_)) = self.appearance_view_model.selected_item() {
-                    return Some(Message::ToggleSection);
+                    return Some(AppearanceMessage::ToggleSection.into());
                 }
                 None
             }
 
             // Increment/Decrement
-            (KeyCode::Char('+') | KeyCode::Char('='), _) => Some(Message::IncrementValue),
-            (KeyCode::Char('-'), _) => Some(Message::DecrementValue),
+            (KeyCode::Char('+') | KeyCode::Char('='), _) => Some(AppearanceMessage::IncrementValue.into()),
+            (KeyCode::Char('-'), _) => Some(AppearanceMessage::DecrementValue.into()),
 
             // Cycle enum with arrows when on enum field
             (KeyCode::Left, _) => {
                 if let Some(AppearanceListItem::Field(field)) = self.appearance_view_model.selected_item() {
                     if field.is_enum() {
-                        return Some(Message::CycleEnumBackward);
+                        return Some(AppearanceMessage::CycleEnumBackward.into());
                     }
                 }
                 None
@@ -800,15 +3450,22 @@ impl App {
             (KeyCode::Right, _) => {
                 if let Some(AppearanceListItem::Field(field)) = self.appearance_view_model.selected_item() {
                     if field.is_enum() {
-                        return Some(Message::CycleEnumForward);
+                        return Some(AppearanceMessage::CycleEnumForward.into());
                     }
                 }
                 None
             }
 
             // Actions
+            (KeyCode::Char('x'), _) => Some(AppearanceMessage::ResetAppearanceToDefault.into()),
+            (KeyCode::Char('S'), _) => Some(AppearanceMessage::SaveAppearanceSection.into()),
+            (KeyCode::Char('u'), _) => Some(AppearanceMessage::RevertAppearanceSection.into()),
+            (KeyCode::Char('U'), _) => Some(AppearanceMessage::RevertAppearanceField.into()),
+            (KeyCode::Char('N'), _) => Some(AppearanceMessage::ToggleNestedPreview.into()),
             (KeyCode::Char('s'), _) => Some(Message::Save),
             (KeyCode::Char('r'), _) => Some(Message::Reload),
+            (KeyCode::Char('R'), _) => Some(Message::ReloadAll),
+            (KeyCode::Char('e'), _) => Some(Message::OpenInEditor),
             (KeyCode::Esc, _) => {
                 // Reset changes on Esc
                 self.appearance_view_model.reset_changes();
@@ -819,22 +3476,108 @@ impl App {
         }
     }
 
+    fn handle_general_input(&mut self, code: KeyCode, modifiers: KeyModifiers) -> Option<Message> {
+        if let Some(field) = &mut self.general_view_model.edit {
+            match code {
+                KeyCode::Esc => return Some(GeneralMessage::CancelGeneralEdit.into()),
+                KeyCode::Enter => return Some(GeneralMessage::ConfirmGeneralEdit.into()),
+                KeyCode::Left => field.cursor_left(),
+                KeyCode::Right => field.cursor_right(),
+                KeyCode::Backspace => field.delete_char(),
+                KeyCode::Char(c) => field.insert_char(c),
+                _ => {}
+            }
+            return None;
+        }
+
+        match (code, modifiers) {
+            (KeyCode::Char('q'), _) => Some(Message::Quit),
+            (KeyCode::Char('c'), KeyModifiers::CONTROL) => Some(Message::Quit),
+            (KeyCode::Char('C'), _) => Some(Message::OpenConfigSource),
+            (KeyCode::Char('!'), _) => Some(Message::OpenErrorLog),
+            (KeyCode::Char('t'), _) => Some(Message::OpenTemplateGallery),
+            (KeyCode::Char('W'), _) => Some(Message::OpenWorkspaceSpawn),
+            (KeyCode::Char('j'), _) | (KeyCode::Down, _) => Some(GeneralMessage::SelectNextGeneralSetting.into()),
+            (KeyCode::Char('k'), _) | (KeyCode::Up, _) => Some(GeneralMessage::SelectPrevGeneralSetting.into()),
+            (KeyCode::Char(' '), _) => Some(GeneralMessage::ToggleGeneralFlag.into()),
+            (KeyCode::Enter, _) => match self.general_view_model.selected_field() {
+                Some(field) if field.is_boolean() => Some(GeneralMessage::ToggleGeneralFlag.into()),
+                _ => Some(GeneralMessage::StartGeneralEdit.into()),
+            },
+            (KeyCode::Char('s'), _) => Some(Message::Save),
+            (KeyCode::Char('r'), _) => Some(Message::Reload),
+            (KeyCode::Char('R'), _) => Some(Message::ReloadAll),
+            (KeyCode::Char('e'), _) => Some(Message::OpenInEditor),
+            (KeyCode::Esc, _) => Some(GeneralMessage::RevertGeneralChanges.into()),
+            _ => None,
+        }
+    }
+
+    fn handle_section_edit_input(&mut self, code: KeyCode) -> Option<Message> {
+        let edit = self.appearance_view_model.section_edit.as_mut()?;
+        match code {
+            KeyCode::Esc => return Some(AppearanceMessage::CancelSectionEdit.into()),
+            KeyCode::Enter => return Some(AppearanceMessage::ConfirmSectionEdit.into()),
+            KeyCode::Tab | KeyCode::Down => edit.focus_next(),
+            KeyCode::BackTab | KeyCode::Up => edit.focus_prev(),
+            _ => {}
+        }
+        // Typed rows take characters and cursor keys; the others cycle
+        if let Some(text) = edit.focused_text() {
+            match code {
+                KeyCode::Left => text.cursor_left(),
+                KeyCode::Right => text.cursor_right(),
+                KeyCode::Home => text.cursor_home(),
+                KeyCode::End => text.cursor_end(),
+                KeyCode::Backspace => text.delete_char(),
+                KeyCode::Char(' ') => {}
+                KeyCode::Char(c) => text.insert_char(c),
+                _ => {}
+            }
+        } else {
+            match code {
+                KeyCode::Char(' ') | KeyCode::Right => edit.cycle(true),
+                KeyCode::Left => edit.cycle(false),
+                _ => {}
+            }
+        }
+        None
+    }
+
     fn handle_appearance_edit_mode_input(
         &mut self,
         code: KeyCode,
-        _modifiers: KeyModifiers,
+        modifiers: KeyModifiers,
     ) -> Option<Message> {
         let edit_mode = match &mut self.appearance_view_model.edit_mode {
             Some(em) => em,
             None => return None,
         };
 
+        // Alt shortcuts only exist in the color editor; elsewhere Alt+key
+        // falls through like any other key
+        if let (Some(cs), true) = (&mut edit_mode.color_state, modifiers.contains(KeyModifiers::ALT)) {
+            match code {
+                // Alt+i stages a dimmed copy as the inactive color too
+                KeyCode::Char('i') if edit_mode.field.inactive_counterpart().is_some() => {
+                    edit_mode.suggest_inactive = !edit_mode.suggest_inactive;
+                    return None;
+                }
+                // Alt+1..9 picks from the palette
+                KeyCode::Char(c @ '1'..='9') => {
+                    cs.pick(c as usize - '1' as usize);
+                    return None;
+                }
+                _ => {}
+            }
+        }
+
         // Check if we're in color editing mode
         let has_color_state = edit_mode.color_state.is_some();
 
         match code {
-            KeyCode::Esc => Some(Message::CancelAppearanceEdit),
-            KeyCode::Enter => Some(Message::ConfirmAppearanceEdit),
+            KeyCode::Esc => Some(AppearanceMessage::CancelAppearanceEdit.into()),
+            KeyCode::Enter => Some(AppearanceMessage::ConfirmAppearanceEdit.into()),
             KeyCode::Tab => {
                 if let Some(ref mut cs) = edit_mode.color_state {
                     cs.focused_field = cs.focused_field.next_for_mode(cs.is_gradient);
@@ -940,8 +3683,11 @@ impl App {
             ])
             .split(size);
 
-        // Tab bar
-        let tab_bar = TabBarWidget::new(self.current_category);
+        // Tab bar; during the tour, the tab the next step needs stands out
+        let mut tab_bar = TabBarWidget::new(self.current_category);
+        if let Some(category) = self.tutorial.as_ref().and_then(|t| t.pending_switch(self.current_category)) {
+            tab_bar = tab_bar.highlight(category);
+        }
         frame.render_widget(tab_bar, main_layout[0]);
 
         // Draw category-specific content
@@ -949,6 +3695,7 @@ impl App {
             Category::Outputs => self.draw_outputs(frame, main_layout[1]),
             Category::Keybindings => self.draw_keybindings(frame, main_layout[1]),
             Category::Appearance => self.draw_appearance(frame, main_layout[1]),
+            Category::General => self.draw_general(frame, main_layout[1]),
         }
 
         // Status bar with category-specific keybinds
@@ -956,21 +3703,128 @@ impl App {
             Category::Outputs => self.view_model.has_pending_changes(),
             Category::Keybindings => self.keybindings_view_model.has_pending_changes(),
             Category::Appearance => self.appearance_view_model.has_pending_changes(),
+            Category::General => self.general_view_model.has_pending_changes(),
         };
-        let status = StatusBarWidget::new(
+        let mut status = StatusBarWidget::new(
             has_changes,
-            self.error.clone(),
+            self.error.as_ref().map(|e| e.to_string()),
             self.current_category.keybinds(),
         );
+        if let Some(load) = &self.config_load {
+            status = status.with_notice(format!(
+                "Loading config ({:.1} MiB)…",
+                load.size as f64 / (1024.0 * 1024.0)
+            ));
+        } else if let Some(notice) = &self.notice {
+            status = status.with_notice(notice.clone());
+        }
+        if let Some(path) = &self.socket_path {
+            status = status.with_socket(path.display().to_string());
+        }
+        if self.settings.auto_save {
+            status = status.auto_save();
+        }
+        if self.settings.live_preview && self.current_category == Category::Outputs {
+            status = status.live_preview();
+        }
+        // Staged positions niri would rearrange, before they are saved
+        if let Some(preview) = &self.preview {
+            let left = preview.deadline.saturating_duration_since(Instant::now()).as_secs() + 1;
+            status = status.with_warning(format!("Reverting preview in {left}s; Enter: keep, Esc: revert"));
+        } else if self.current_category == Category::Outputs && self.view_model.has_pending_changes() {
+            let issues = self.view_model.layout_issues(Some(&self.appearance_view_model.settings));
+            if let Some(first) = issues.first() {
+                let more = match issues.len() {
+                    1 => String::new(),
+                    n => format!(" (+{} more)", n - 1),
+                };
+                status = status.with_warning(format!("{}{more}", first.message()));
+            }
+        }
+        if let Some(step) = self.tutorial.as_ref().and_then(|t| t.current()) {
+            if step.category == self.current_category {
+                status = status.highlight(step.key);
+            }
+        }
         frame.render_widget(status, main_layout[2]);
+
+        if let Some(ref tutorial) = self.tutorial {
+            frame.render_widget(TutorialWidget::new(tutorial, self.current_category), main_layout[1]);
+        }
+
+        if let Some(ref gallery) = self.template_gallery {
+            frame.render_widget(TemplateGalleryWidget::new(gallery), size);
+        }
+
+        if let Some(ref quick_open) = self.quick_open {
+            frame.render_widget(QuickOpenWidget::new(quick_open), size);
+        }
+
+        if let Some(ref discovery) = self.config_source {
+            frame.render_widget(ConfigSourceWidget::new(discovery), size);
+        }
+
+        if let Some(ref wizard) = self.workspace_spawn {
+            let preview = match (wizard.plan(), &self.config) {
+                (Ok(spawn), Some(config)) => Ok(workspace_spawn_kdl(config, &spawn)),
+                (Ok(_), None) => Err("No config loaded".to_string()),
+                (Err(e), _) => Err(e),
+            };
+            frame.render_widget(WorkspaceSpawnWidget::new(wizard, preview), size);
+        }
+
+        if let Some(ref editor) = self.label_editor {
+            frame.render_widget(OutputLabelWidget::new(editor), size);
+        }
+
+        if let Some(ref picker) = self.profile_picker {
+            frame.render_widget(OutputProfilesWidget::new(picker, &self.output_profiles), size);
+        }
+
+        if let Some(ref editor) = self.color_editor {
+            frame.render_widget(OutputColorsWidget::new(editor), size);
+        }
+
+        if let Some(ref editor) = self.position_editor {
+            frame.render_widget(OutputPositionWidget::new(editor), size);
+        }
+
+        if let Some(ref actions) = self.keybindings_view_model.lockout_warning {
+            frame.render_widget(LockoutWarningWidget::new(actions), size);
+        }
+
+        if let Some(ref outputs) = self.view_model.disabled_warning {
+            frame.render_widget(DisabledOutputWarningWidget::new(outputs), size);
+        }
+
+        if let (Some(category), Some(config)) = (self.save_conflict, &self.config) {
+            let path = config.path.display().to_string();
+            let staged = self.staged_change_count(ReloadScope::Category(category));
+            frame.render_widget(SaveConflictWidget::new(category, &path, staged), size);
+        }
+
+        if let Some(ref report) = self.health_report {
+            frame.render_widget(HealthReportWidget::new(report), size);
+        }
+
+        // Error log renders on top of everything
+        if self.error_log.open {
+            frame.render_widget(ErrorLogWidget::new(&self.error_log), size);
+        }
+
+        if let Some(ref diagnostics) = self.diagnostics {
+            frame.render_widget(DebugOverlayWidget::new(diagnostics), size);
+        }
+
+        self.color_mode.apply(frame.buffer_mut());
     }
 
-    fn draw_outputs(&self, frame: &mut Frame, area: ratatui::layout::Rect) {
+    fn draw_outputs(&mut self, frame: &mut Frame, area: ratatui::layout::Rect) {
         // Body layout: left panel (list + info) and right panel (canvas)
         let body_layout = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([
-                Constraint::Length(25), // Left panel
+                Constraint::Length(self.ui_state.outputs_panel), // Left panel
                 Constraint::Min(30),    // Canvas
             ])
             .split(area);
@@ -980,53 +3834,136 @@ impl App {
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Min(5),     // Output list
-                Constraint::Length(10), // Info panel
+                Constraint::Length(15), // Info panel
             ])
             .split(body_layout[0]);
 
         // Render widgets
-        let output_list = OutputListWidget::new(&self.view_model, true);
+        let canvas_focused = self.view_model.focused_pane == OutputPane::Canvas;
+        let output_list = OutputListWidget::new(&self.view_model, !canvas_focused)
+            .density(self.settings.density)
+            .labels(&self.output_labels);
         frame.render_widget(output_list, left_layout[0]);
 
-        let output_info = OutputInfoWidget::new(&self.view_model);
+        let output_info = OutputInfoWidget::new(&self.view_model).labels(&self.output_labels);
         frame.render_widget(output_info, left_layout[1]);
 
-        let canvas = MonitorCanvasWidget::new(&self.view_model, &self.viewport, true);
-        frame.render_widget(canvas, body_layout[1]);
+        // The mode browser takes the canvas area while open
+        if let (Some(browser), Some(output)) = (
+            &self.view_model.mode_browser,
+            self.view_model.selected_output(),
+        ) {
+            let staged = self.view_model.pending_mode(&output.name);
+            frame.render_widget(ModeBrowserWidget::new(browser, output).staged(staged), body_layout[1]);
+            self.canvas_area = None;
+        } else {
+            self.canvas_area = Some(body_layout[1]);
+            let canvas = MonitorCanvasWidget::new(&self.view_model, &self.viewport, canvas_focused)
+                .appearance(&self.appearance_view_model.settings)
+                .labels(&self.output_labels)
+                .identifying(self.identify.as_ref().map(|flash| flash.output.as_str()));
+            frame.render_widget(canvas, body_layout[1]);
+        }
     }
 
     fn draw_keybindings(&mut self, frame: &mut Frame, area: ratatui::layout::Rect) {
-        // Calculate visible height for scroll
-        let inner_height = area.height.saturating_sub(2) as usize;
-        self.keybindings_view_model.update_scroll(inner_height);
+        // Calculate visible height for scroll (borders and the table header)
+        let inner_height = area.height.saturating_sub(3) as usize;
+        self.keybindings_view_model.update_scroll(inner_height / self.settings.density.binding_rows());
 
         // Body layout: list and detail panel
         let body_layout = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([
-                Constraint::Percentage(55), // Keybindings list
-                Constraint::Percentage(45), // Detail panel
+                Constraint::Percentage(self.ui_state.keybindings_split), // Keybindings list
+                Constraint::Percentage(100 - self.ui_state.keybindings_split), // Detail panel
             ])
             .split(area);
 
-        // Keybindings list
-        let list = KeybindingsListWidget::new(&self.keybindings_view_model, true);
+        // Keybindings list; filtering is the costly part, so it's done once per frame
+        let filtered = self.keybindings_view_model.filtered_bindings();
+        let list = KeybindingsListWidget::new(&self.keybindings_view_model, true)
+            .filtered(&filtered)
+            .density(self.settings.density);
         frame.render_widget(list, body_layout[0]);
 
         // Detail panel with status
-        let selected_eb = self.keybindings_view_model.selected_effective_binding();
+        let selected_eb = filtered.get(self.keybindings_view_model.list.selected_index).copied();
+        let unmodeled = selected_eb
+            .as_ref()
+            .map(|eb| self.unmodeled_binding_parts(eb.binding))
+            .unwrap_or_default();
         let (binding, status) = match selected_eb {
             Some(eb) => (Some(eb.binding), Some(eb.status)),
             None => (None, None),
         };
-        let detail = KeybindingDetailWidget::with_status(binding, status);
+        let tags = binding.map(|b| self.keybindings_view_model.tags.tags(&b.combo())).unwrap_or_default();
+        let detail = KeybindingDetailWidget::with_status(binding, status)
+            .raw_key_names(self.keybindings_view_model.raw_key_names)
+            .tags(tags)
+            .unmodeled(unmodeled);
         frame.render_widget(detail, body_layout[1]);
 
         // Edit dialog (renders on top if edit mode is active)
         if let Some(ref edit_mode) = self.keybindings_view_model.edit_mode {
-            let edit_widget = KeybindingEditWidget::new(edit_mode);
+            let preview = edit_mode
+                .validate()
+                .ok()
+                .and_then(|_| edit_mode.to_keybinding())
+                .map(|binding| keybinding_kdl(&binding));
+            let edit_widget = KeybindingEditWidget::new(edit_mode).with_preview(preview);
             frame.render_widget(edit_widget, area);
         }
+
+        // Generator dialog
+        if let Some(ref generator) = self.keybindings_view_model.generator {
+            let existing: Vec<_> = self
+                .keybindings_view_model
+                .effective_bindings()
+                .into_iter()
+                .map(|eb| eb.binding.clone())
+                .collect();
+            let generator_widget = BindingGeneratorWidget::new(generator, &existing);
+            frame.render_widget(generator_widget, area);
+        }
+
+        if let Some(ref dry_run) = self.keybindings_view_model.dry_run {
+            frame.render_widget(DryRunWidget::new(dry_run), area);
+        }
+
+        if let Some(ref prompt) = self.keybindings_view_model.template_prompt {
+            frame.render_widget(BindingTemplateWidget::new(prompt), area);
+        }
+
+        if let Some(ref editor) = self.keybindings_view_model.tag_editor {
+            frame.render_widget(BindingTagsWidget::new(editor), area);
+        }
+
+        if let Some(ref resolver) = self.keybindings_view_model.conflict_resolver {
+            frame.render_widget(BindingConflictWidget::new(resolver), area);
+        }
+
+        if let Some(ref overlay) = self.keybindings_view_model.bind_overlay {
+            frame.render_widget(BindOverlayWidget::new(overlay, self.keybindings_view_model.raw_key_names), area);
+        }
+    }
+
+    fn draw_general(&mut self, frame: &mut Frame, area: ratatui::layout::Rect) {
+        let body_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(self.ui_state.general_split),
+                Constraint::Percentage(100 - self.ui_state.general_split),
+            ])
+            .split(area);
+
+        let list = GeneralListWidget::new(&self.general_view_model, true).density(self.settings.density);
+        frame.render_widget(list, body_layout[0]);
+        frame.render_widget(GeneralDetailWidget::new(&self.general_view_model), body_layout[1]);
+
+        if let Some(field) = &self.general_view_model.edit {
+            frame.render_widget(GeneralEditWidget::new(field), area);
+        }
     }
 
     fn draw_appearance(&mut self, frame: &mut Frame, area: ratatui::layout::Rect) {
@@ -1038,13 +3975,13 @@ impl App {
         let body_layout = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([
-                Constraint::Percentage(55), // Appearance list
-                Constraint::Percentage(45), // Detail panel
+                Constraint::Percentage(self.ui_state.appearance_split), // Appearance list
+                Constraint::Percentage(100 - self.ui_state.appearance_split), // Detail panel
             ])
             .split(area);
 
         // Appearance list
-        let list = AppearanceListWidget::new(&self.appearance_view_model, true);
+        let list = AppearanceListWidget::new(&self.appearance_view_model, true).density(self.settings.density);
         frame.render_widget(list, body_layout[0]);
 
         // Detail panel
@@ -1053,8 +3990,54 @@ impl App {
 
         // Edit dialog (renders on top if edit mode is active)
         if let Some(ref edit_mode) = self.appearance_view_model.edit_mode {
-            let edit_widget = AppearanceEditWidget::new(edit_mode);
+            let preview = edit_mode.parsed_value().ok().map(|value| {
+                let mut settings = self.appearance_view_model.settings.clone();
+                settings.set(edit_mode.field, &value);
+                appearance_field_kdl(&settings, edit_mode.field)
+            });
+            let edit_widget = AppearanceEditWidget::new(edit_mode).with_preview(preview);
             frame.render_widget(edit_widget, area);
+        } else if let Some(ref section_edit) = self.appearance_view_model.section_edit {
+            frame.render_widget(AppearanceSectionEditWidget::new(section_edit), area);
         }
     }
 }
+
+/// The niri combo for a key pressed while rebinding, or None for keys that
+/// can't be bound (bare modifiers, keys the terminal doesn't name). Mod is
+/// added from the capture's toggle, or when the terminal reports Super.
+fn captured_combo(code: KeyCode, modifiers: KeyModifiers, mod_key: bool) -> Option<String> {
+    let mut shift = modifiers.contains(KeyModifiers::SHIFT);
+    let key = match code {
+        KeyCode::Char(c) => {
+            let (key, shifted) = keys::char_key(c)?;
+            shift |= shifted;
+            key
+        }
+        KeyCode::F(n) => format!("F{n}"),
+        KeyCode::Enter => "Return".to_string(),
+        KeyCode::Backspace => "BackSpace".to_string(),
+        KeyCode::Delete => "Delete".to_string(),
+        KeyCode::Insert => "Insert".to_string(),
+        KeyCode::Home => "Home".to_string(),
+        KeyCode::End => "End".to_string(),
+        KeyCode::PageUp => "Page_Up".to_string(),
+        KeyCode::PageDown => "Page_Down".to_string(),
+        KeyCode::Left => "Left".to_string(),
+        KeyCode::Right => "Right".to_string(),
+        KeyCode::Up => "Up".to_string(),
+        KeyCode::Down => "Down".to_string(),
+        KeyCode::PrintScreen => "Print".to_string(),
+        KeyCode::Pause => "Pause".to_string(),
+        KeyCode::Menu => "Menu".to_string(),
+        _ => return None,
+    };
+    let mods = Modifiers {
+        mod_key: mod_key || modifiers.contains(KeyModifiers::SUPER),
+        ctrl: modifiers.contains(KeyModifiers::CONTROL),
+        shift,
+        alt: modifiers.contains(KeyModifiers::ALT),
+    };
+    let mods = mods.to_string();
+    Some(if mods.is_empty() { key } else { format!("{mods}+{key}") })
+}
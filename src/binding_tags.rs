@@ -0,0 +1,113 @@
+use anyhow::Result;
+use kdl::KdlDocument;
+use std::collections::BTreeMap;
+
+use crate::config::kdl_value::string;
+use crate::model::TemplateField;
+use crate::state_file;
+
+const FILE: &str = "binding-tags.kdl";
+
+/// User-defined tags on keybindings by key combo, kept across sessions in
+/// ~/.local/state/nirikiri/binding-tags.kdl so the niri config stays as is
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BindingTags {
+    pub binds: BTreeMap<String, Vec<String>>,
+}
+
+impl BindingTags {
+    /// Load the tags, starting empty when the file doesn't exist
+    pub fn load() -> Result<Self> {
+        Ok(state_file::load(FILE, "binding tags", Self::parse)?.unwrap_or_default())
+    }
+
+    /// Parse `bind "Mod+T" "work" "experimental"` nodes; anything else is ignored
+    pub fn parse(content: &str) -> Result<Self> {
+        let doc = KdlDocument::parse_v1(content)?;
+        let mut tags = Self::default();
+        for node in doc.nodes().iter().filter(|n| n.name().value() == "bind") {
+            let mut args = node.entries().iter().filter(|e| e.name().is_none()).filter_map(|e| e.value().as_string());
+            if let Some(combo) = args.next() {
+                tags.set(combo, &args.collect::<Vec<_>>().join(" "));
+            }
+        }
+        Ok(tags)
+    }
+
+    pub fn to_kdl(&self) -> String {
+        state_file::to_kdl(self.binds.iter().map(|(combo, tags)| {
+            state_file::node("bind", std::iter::once(combo).chain(tags).map(|s| string(s.as_str())))
+        }))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        state_file::save(FILE, "binding tags", &self.to_kdl())
+    }
+
+    /// Tags on the binding with this combo
+    pub fn tags(&self, combo: &str) -> &[String] {
+        self.binds.get(combo).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Whether the binding carries every one of `tags` (lowercase)
+    pub fn has_all(&self, combo: &str, tags: &[&str]) -> bool {
+        let own = self.tags(combo);
+        tags.iter().all(|tag| own.iter().any(|t| t == tag))
+    }
+
+    /// Replace the binding's tags with those in `text`, separated by spaces
+    /// or commas; no tags forgets the binding. Returns false if nothing changed.
+    pub fn set(&mut self, combo: &str, text: &str) -> bool {
+        let tags = parse_tags(text);
+        if tags.is_empty() {
+            return self.binds.remove(combo).is_some();
+        }
+        self.binds.insert(combo.to_string(), tags.clone()) != Some(tags)
+    }
+}
+
+/// Lowercase tags in `text`, without a leading # and duplicates
+pub fn parse_tags(text: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in text.split([' ', ',']).map(|t| t.trim_start_matches('#').to_lowercase()) {
+        if !tag.is_empty() && !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+/// State for the dialog that edits a binding's tags
+#[derive(Debug, Clone)]
+pub struct BindingTagEditor {
+    pub combo: String,
+    pub field: TemplateField,
+}
+
+impl BindingTagEditor {
+    pub fn new(combo: String, tags: &[String]) -> Self {
+        Self { combo, field: TemplateField::new("Tags", tags.join(" ")) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_binding_tags() {
+        let mut tags = BindingTags::parse("bind \"Mod+T\" \"work\" \"Work\" \"#experimental\"\nbind \"Mod+Q\"\nother 1").unwrap();
+        assert_eq!(tags.tags("Mod+T"), ["work", "experimental"]);
+        assert!(tags.tags("Mod+Q").is_empty());
+        assert!(tags.has_all("Mod+T", &["work", "experimental"]));
+        assert!(!tags.has_all("Mod+T", &["work", "games"]));
+
+        assert!(tags.set("Mod+Q", "games, #work"));
+        assert!(!tags.set("Mod+Q", "games work"));
+        assert_eq!(BindingTags::parse(&tags.to_kdl()).unwrap(), tags);
+
+        assert!(tags.set("Mod+T", " , "));
+        assert!(tags.tags("Mod+T").is_empty());
+        assert_eq!(BindingTagEditor::new("Mod+Q".to_string(), tags.tags("Mod+Q")).field.value, "games work");
+    }
+}
@@ -7,6 +7,7 @@ pub enum Category {
     Outputs,     // F1
     Keybindings, // F2
     Appearance,  // F3
+    General,     // F4
 }
 
 impl Category {
@@ -16,13 +17,14 @@ impl Category {
             KeyCode::F(1) => Some(Category::Outputs),
             KeyCode::F(2) => Some(Category::Keybindings),
             KeyCode::F(3) => Some(Category::Appearance),
+            KeyCode::F(4) => Some(Category::General),
             _ => None,
         }
     }
 
     /// Get all categories in display order
     pub fn all() -> &'static [Category] {
-        &[Category::Outputs, Category::Keybindings, Category::Appearance]
+        &[Category::Outputs, Category::Keybindings, Category::Appearance, Category::General]
     }
 
     /// Get the display name for this category
@@ -31,6 +33,7 @@ impl Category {
             Category::Outputs => "Outputs",
             Category::Keybindings => "Keybindings",
             Category::Appearance => "Appearance",
+            Category::General => "General",
         }
     }
 
@@ -40,6 +43,7 @@ impl Category {
             Category::Outputs => 1,
             Category::Keybindings => 2,
             Category::Appearance => 3,
+            Category::General => 4,
         }
     }
 
@@ -49,10 +53,41 @@ impl Category {
             Category::Outputs => &[
                 ("q", "Quit"),
                 ("Tab", "Select"),
-                ("hjkl", "Move"),
+                ("/", "Search"),
+                ("hjkl/←↑↓→", "Move"),
+                ("S-←↑↓→", "Move 100"),
+                ("F", "Focus pane"),
+                ("g", "Go to x,y"),
                 ("HJKL", "Snap"),
+                ("Alt+HJKL", "Axis snap"),
+                ("a/A", "Snap align"),
+                ("o", "To 0,0"),
+                ("m", "Modes"),
+                ("</>", "Scale"),
+                ("D", "DPI scale"),
+                ("T", "Transform"),
+                ("v", "VRR"),
+                ("d", "Off/On"),
+                ("f", "Startup focus"),
+                ("z", "Zoom"),
+                ("^←↑↓→", "Pan"),
+                ("c", "Columns"),
                 ("n", "Normalize"),
+                ("N", "Label"),
+                ("b", "Colors"),
+                ("O", "Profiles"),
+                ("i", "Identify"),
+                ("M", "Merge blocks"),
+                ("S", "Save live layout"),
+                ("P", "Live preview"),
                 ("s", "Save"),
+                ("r/R", "Reload"),
+                ("e", "Editor"),
+                ("A-←/→", "Resize"),
+                ("t", "Templates"),
+                ("W", "Workspace app"),
+                ("^O", "Open config"),
+                ("C", "Config source"),
             ],
             Category::Keybindings => &[
                 ("q", "Quit"),
@@ -61,16 +96,62 @@ impl Category {
                 ("Enter", "Edit"),
                 ("a", "Add"),
                 ("d", "Delete"),
+                ("p/l", "Repeat/Locked"),
+                ("P", "Fix repeat"),
+                ("g", "Generate"),
+                ("T", "Bind template"),
+                ("x", "Dry run"),
+                ("#", "Tags"),
+                ("?", "All binds"),
+                ("D", "Defaults"),
+                ("M", "Missing apps"),
+                ("n", "Key names"),
+                ("c/f", "Columns"),
+                ("</>", "Width"),
                 ("s", "Save"),
+                ("r/R", "Reload"),
+                ("e", "Editor"),
+                ("A-←/→", "Resize"),
+                ("t", "Templates"),
+                ("W", "Workspace app"),
+                ("^O", "Open config"),
+                ("C", "Config source"),
             ],
             Category::Appearance => &[
                 ("q", "Quit"),
                 ("j/k", "Navigate"),
                 ("Tab", "Expand/Collapse"),
                 ("Enter", "Edit"),
+                ("E", "Edit section"),
                 ("Space", "Toggle"),
                 ("+/-", "Adjust"),
+                ("x", "Default"),
+                ("S/u", "Save/revert section"),
+                ("U", "Revert field"),
+                ("N", "Nested preview"),
+                ("s", "Save"),
+                ("r/R", "Reload"),
+                ("e", "Editor"),
+                ("A-←/→", "Resize"),
+                ("t", "Templates"),
+                ("W", "Workspace app"),
+                ("^O", "Open config"),
+                ("C", "Config source"),
+            ],
+            Category::General => &[
+                ("q", "Quit"),
+                ("j/k", "Navigate"),
+                ("Space", "Toggle"),
+                ("Enter", "Edit"),
                 ("s", "Save"),
+                ("r/R", "Reload"),
+                ("Esc", "Revert"),
+                ("e", "Editor"),
+                ("A-←/→", "Resize"),
+                ("t", "Templates"),
+                ("W", "Workspace app"),
+                ("^O", "Open config"),
+                ("C", "Config source"),
             ],
         }
     }
@@ -0,0 +1,243 @@
+use anyhow::{anyhow, bail, Result};
+use std::path::PathBuf;
+
+use crate::model::Arrangement;
+
+pub const USAGE: &str = "\
+Usage: nirikiri [OPTIONS]
+       nirikiri [OPTIONS] outputs <ACTIONS>
+       nirikiri [OPTIONS] kanshi export [<PROFILE>]
+       nirikiri [OPTIONS] kanshi import <FILE>
+
+Options:
+  --socket <PATH>  niri IPC socket to connect to (overrides $NIRIKIRI_SOCKET and $NIRI_SOCKET)
+  --config <PATH>  Edit this config instead of ~/.config/niri/config.kdl
+  --accessible     Higher contrast; combine with NO_COLOR=1 for no colors at all
+  --debug          Start with the frame time and IPC latency overlay (F10) shown
+  --porcelain      Print the result of a one-shot command as JSON, for scripts
+  -h, --help       Print this help
+
+Output actions (run without the TUI, save the config and exit):
+  --arrange <row|column>  Line up all enabled outputs from 0,0
+  --normalize             Shift outputs so the top-left one is at 0,0
+  --apply-profile <NAME>  Apply a saved output profile
+
+kanshi:
+  export [<PROFILE>]  Print the current layout, or a saved profile, as a kanshi profile
+  import <FILE>       Save the profiles of a kanshi config as output profiles
+";
+
+/// Parsed command line arguments
+#[derive(Debug, Default, PartialEq)]
+pub struct Cli {
+    pub socket: Option<PathBuf>,
+    pub config: Option<PathBuf>,
+    pub help: bool,
+    pub accessible: bool,
+    /// Show the debug overlay from the start
+    pub debug: bool,
+    /// Report one-shot command results as JSON instead of text
+    pub porcelain: bool,
+    /// One-shot command to run instead of the TUI
+    pub command: Option<Command>,
+}
+
+/// Commands that run headlessly and exit
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Outputs(OutputsCommand),
+    /// Print the current layout, or the named saved profile, for kanshi
+    KanshiExport(Option<String>),
+    KanshiImport(PathBuf),
+}
+
+/// Output layout operations, applied in the order arrange, normalize
+#[derive(Debug, Default, PartialEq)]
+pub struct OutputsCommand {
+    pub arrange: Option<Arrangement>,
+    pub normalize: bool,
+    pub profile: Option<String>,
+}
+
+/// Arguments that couldn't be parsed
+#[derive(Debug)]
+pub struct ParseError {
+    pub error: anyhow::Error,
+    /// `--porcelain` was given as a flag, so the error should be JSON too
+    pub porcelain: bool,
+}
+
+impl Cli {
+    /// Parse arguments, excluding the program name
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, ParseError> {
+        let mut cli = Cli::default();
+        let mut args = args.into_iter();
+        match cli.parse_from(&mut args) {
+            Ok(()) => Ok(cli),
+            Err(error) => Err(ParseError { error, porcelain: cli.porcelain || porcelain_flag(args) }),
+        }
+    }
+
+    fn parse_from(&mut self, args: &mut impl Iterator<Item = String>) -> Result<()> {
+        let cli = self;
+        while let Some(arg) = args.next() {
+            // Options taking a value accept both `--flag value` and `--flag=value`
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => (flag.to_string(), Some(value.to_string())),
+                _ => (arg, None),
+            };
+            let mut value = |what: &str| {
+                inline
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| anyhow!("{flag} requires {what}"))
+            };
+            match flag.as_str() {
+                "-h" | "--help" => cli.help = true,
+                "--accessible" => cli.accessible = true,
+                "--debug" => cli.debug = true,
+                "--porcelain" => cli.porcelain = true,
+                "--socket" => cli.socket = Some(PathBuf::from(value("a path")?)),
+                "--config" => cli.config = Some(PathBuf::from(value("a path")?)),
+                "outputs" if cli.command.is_none() => {
+                    cli.command = Some(Command::Outputs(OutputsCommand::default()));
+                }
+                "kanshi" if cli.command.is_none() => {
+                    let action = value("export or import")?;
+                    cli.command = Some(match action.as_str() {
+                        "export" => Command::KanshiExport(None),
+                        "import" => Command::KanshiImport(PathBuf::from(value("a kanshi config file")?)),
+                        _ => bail!("kanshi takes export or import, not {action:?}\n\n{USAGE}"),
+                    });
+                }
+                name if !name.starts_with('-') && cli.command == Some(Command::KanshiExport(None)) => {
+                    cli.command = Some(Command::KanshiExport(Some(name.to_string())));
+                }
+                "--arrange" | "--normalize" | "--apply-profile" => {
+                    let Some(Command::Outputs(outputs)) = &mut cli.command else {
+                        bail!("{flag} is an action of the outputs command\n\n{USAGE}");
+                    };
+                    match flag.as_str() {
+                        "--arrange" => {
+                            let name = value("row or column")?;
+                            let arrangement = Arrangement::parse(&name)
+                                .ok_or_else(|| anyhow!("--arrange takes row or column, not {name:?}"))?;
+                            outputs.arrange = Some(arrangement);
+                        }
+                        "--normalize" => outputs.normalize = true,
+                        _ => outputs.profile = Some(value("a profile name")?),
+                    }
+                }
+                _ => bail!("Unknown argument: {flag}\n\n{USAGE}"),
+            }
+        }
+        if cli.command == Some(Command::Outputs(OutputsCommand::default())) {
+            bail!("outputs needs --arrange, --normalize or --apply-profile\n\n{USAGE}");
+        }
+        // The TUI has no result to print
+        if cli.porcelain && cli.command.is_none() && !cli.help {
+            bail!("--porcelain needs the outputs or kanshi command\n\n{USAGE}");
+        }
+        Ok(())
+    }
+}
+
+/// Whether the arguments left after an error include `--porcelain` as a
+/// flag rather than as the value of an option
+fn porcelain_flag(mut args: impl Iterator<Item = String>) -> bool {
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--porcelain" => return true,
+            "--socket" | "--config" => {
+                args.next();
+            }
+            _ => {}
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Cli> {
+        Cli::parse(args.iter().map(|a| a.to_string())).map_err(|e| e.error)
+    }
+
+    fn porcelain_error(args: &[&str]) -> bool {
+        Cli::parse(args.iter().map(|a| a.to_string())).unwrap_err().porcelain
+    }
+
+    #[test]
+    fn test_parse_socket() {
+        assert_eq!(parse(&[]).unwrap(), Cli::default());
+        assert_eq!(
+            parse(&["--socket", "/tmp/niri.sock"]).unwrap().socket,
+            Some(PathBuf::from("/tmp/niri.sock"))
+        );
+        assert_eq!(
+            parse(&["--socket=/tmp/nested.sock"]).unwrap().socket,
+            Some(PathBuf::from("/tmp/nested.sock"))
+        );
+        assert!(parse(&["--accessible"]).unwrap().accessible);
+        assert!(parse(&["--debug"]).unwrap().debug);
+        assert!(parse(&["outputs", "--normalize", "--porcelain"]).unwrap().porcelain);
+        assert!(parse(&["--porcelain"]).is_err());
+        assert!(parse(&["--porcelain", "--help"]).unwrap().help);
+        assert_eq!(parse(&["--config=test.kdl"]).unwrap().config, Some(PathBuf::from("test.kdl")));
+        assert!(parse(&["--socket"]).is_err());
+        assert!(parse(&["--bogus"]).is_err());
+    }
+
+    #[test]
+    fn test_porcelain_errors() {
+        assert!(porcelain_error(&["--porcelain"]));
+        assert!(porcelain_error(&["--porcelain", "--bogus"]));
+        assert!(porcelain_error(&["--bogus", "--porcelain"]));
+        // The value of another option isn't the flag
+        assert!(!porcelain_error(&["--config", "--porcelain", "--bogus"]));
+        assert!(!porcelain_error(&["--bogus", "--config", "--porcelain"]));
+        assert!(!porcelain_error(&["--bogus"]));
+    }
+
+    #[test]
+    fn test_parse_outputs_command() {
+        let cli = parse(&["outputs", "--arrange", "row", "--normalize", "--socket=/tmp/niri.sock"]).unwrap();
+        assert_eq!(cli.socket, Some(PathBuf::from("/tmp/niri.sock")));
+        assert_eq!(
+            cli.command,
+            Some(Command::Outputs(OutputsCommand {
+                arrange: Some(Arrangement::Row),
+                normalize: true,
+                profile: None,
+            }))
+        );
+        let cli = parse(&["outputs", "--apply-profile=work"]).unwrap();
+        assert_eq!(
+            cli.command,
+            Some(Command::Outputs(OutputsCommand { profile: Some("work".to_string()), ..Default::default() }))
+        );
+
+        assert!(parse(&["outputs"]).is_err());
+        assert!(parse(&["outputs", "--arrange", "diagonal"]).is_err());
+        assert!(parse(&["--normalize"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_kanshi_command() {
+        assert_eq!(parse(&["kanshi", "export"]).unwrap().command, Some(Command::KanshiExport(None)));
+        assert_eq!(
+            parse(&["kanshi", "export", "home", "--socket=/tmp/niri.sock"]).unwrap().command,
+            Some(Command::KanshiExport(Some("home".to_string())))
+        );
+        assert_eq!(
+            parse(&["--porcelain", "kanshi", "import", "kanshi.conf"]).unwrap().command,
+            Some(Command::KanshiImport(PathBuf::from("kanshi.conf")))
+        );
+        assert!(parse(&["kanshi"]).is_err());
+        assert!(parse(&["kanshi", "sync"]).is_err());
+        assert!(parse(&["kanshi", "import"]).is_err());
+        assert!(parse(&["kanshi", "export", "home", "work"]).is_err());
+    }
+}
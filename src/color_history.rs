@@ -0,0 +1,99 @@
+use anyhow::Result;
+use kdl::KdlDocument;
+
+use crate::config::kdl_value::color;
+use crate::state_file;
+
+const FILE: &str = "colors.kdl";
+
+/// How many colors are remembered
+const MAX_COLORS: usize = 16;
+
+/// How many colors the editor offers; they are picked with Alt+1 to Alt+9
+pub const PALETTE_SIZE: usize = 9;
+
+/// Colors recently entered in the color editor, most recent first, kept
+/// across sessions in ~/.local/state/nirikiri/colors.kdl
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ColorHistory {
+    pub colors: Vec<String>,
+}
+
+impl ColorHistory {
+    /// Load the history, starting empty when the file doesn't exist
+    pub fn load() -> Result<Self> {
+        Ok(state_file::load(FILE, "color history", Self::parse)?.unwrap_or_default())
+    }
+
+    /// Parse `color "#rrggbb"` nodes; anything else is ignored
+    pub fn parse(content: &str) -> Result<Self> {
+        let doc = KdlDocument::parse_v1(content)?;
+        let colors = doc
+            .nodes()
+            .iter()
+            .filter(|n| n.name().value() == "color")
+            .filter_map(|n| n.get(0)?.as_string().map(str::to_string))
+            .take(MAX_COLORS)
+            .collect();
+        Ok(Self { colors })
+    }
+
+    pub fn to_kdl(&self) -> String {
+        state_file::to_kdl(self.colors.iter().map(|c| state_file::node("color", [color(c)])))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        state_file::save(FILE, "color history", &self.to_kdl())
+    }
+
+    /// Move `color` to the front, returning false if it already was there
+    pub fn record(&mut self, color: &str) -> bool {
+        let color = color.trim();
+        if color.is_empty() || self.colors.first().is_some_and(|c| c.eq_ignore_ascii_case(color)) {
+            return false;
+        }
+        self.colors.retain(|c| !c.eq_ignore_ascii_case(color));
+        self.colors.insert(0, color.to_string());
+        self.colors.truncate(MAX_COLORS);
+        true
+    }
+
+    /// Quick-pick colors for the editor: `theme` colors first so matching
+    /// another field is always on hand, then recent ones
+    pub fn palette(&self, theme: Vec<String>) -> Vec<String> {
+        let mut palette = theme;
+        for color in &self.colors {
+            if !palette.iter().any(|c| c.eq_ignore_ascii_case(color)) {
+                palette.push(color.clone());
+            }
+        }
+        palette.truncate(PALETTE_SIZE);
+        palette
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_history() {
+        let mut history = ColorHistory::parse("color \"#ff0000\"\ncolor \"#00ff00\"\nother 1").unwrap();
+        assert_eq!(history.colors, vec!["#ff0000", "#00ff00"]);
+
+        assert!(history.record("#00FF00"));
+        assert!(!history.record("#00ff00"));
+        assert_eq!(history.colors, vec!["#00FF00", "#ff0000"]);
+        assert_eq!(ColorHistory::parse(&history.to_kdl()).unwrap(), history);
+
+        for i in 0..20 {
+            history.record(&format!("#{i:06x}"));
+        }
+        assert_eq!(history.colors.len(), MAX_COLORS);
+        assert_eq!(history.colors[0], "#000013");
+
+        let palette = history.palette(vec!["#000013".to_string(), "#abcdef".to_string()]);
+        assert_eq!(palette.len(), PALETTE_SIZE);
+        assert_eq!(&palette[..3], ["#000013", "#abcdef", "#000012"]);
+    }
+}
@@ -1,6 +1,10 @@
+use std::collections::HashMap;
+
+use crate::config::kdl_value::{as_bool, as_float, as_int, node_color};
 use crate::model::{
-    AppearanceSettings, BorderSettings, CenterFocusedColumn, ColorValue, FocusRingSettings,
-    ShadowSettings, StrutsSettings, ConfigDocument,
+    AppearanceField, AppearanceSettings, BorderSettings, CenterFocusedColumn, ColorValue,
+    FocusRingSettings, ShadowSettings, SourceLocation, StrutsSettings, ConfigDocument,
+    OverviewSettings,
 };
 
 /// Parse appearance settings from the layout block in the config
@@ -14,10 +18,93 @@ pub fn parse_appearance(config: &ConfigDocument) -> AppearanceSettings {
             break;
         }
     }
+    if let Some(node) = config.doc.nodes().iter().find(|n| n.name().value() == "overview") {
+        settings.overview = parse_overview(node);
+    }
 
     settings
 }
 
+/// Find where each appearance field is set in the layout block
+pub fn locate_appearance_fields(config: &ConfigDocument) -> HashMap<AppearanceField, SourceLocation> {
+    let mut locations = HashMap::new();
+
+    // Top-level blocks edited with the layout
+    for block in config.doc.nodes() {
+        let section = block.name().value();
+        for node in block.children().map(|c| c.nodes()).unwrap_or_default() {
+            let field = match (section, node.name().value()) {
+                ("overview", "zoom") => AppearanceField::OverviewZoom,
+                ("overview", "backdrop-color") => AppearanceField::OverviewBackdropColor,
+                ("overview", "workspace-shadow") => {
+                    locate_workspace_shadow(config, node, &mut locations);
+                    continue;
+                }
+                _ => continue,
+            };
+            if let Some(loc) = config.locate(node) {
+                locations.insert(field, loc);
+            }
+        }
+    }
+
+    let Some(layout) = config.doc.nodes().iter().find(|n| n.name().value() == "layout") else {
+        return locations;
+    };
+    let Some(children) = layout.children() else {
+        return locations;
+    };
+
+    for child in children.nodes() {
+        let section = child.name().value();
+        let fields = match section {
+            "gaps" => vec![AppearanceField::Gaps],
+            "center-focused-column" => vec![AppearanceField::CenterFocusedColumn],
+            _ => Vec::new(),
+        };
+        for field in fields {
+            if let Some(loc) = config.locate(child) {
+                locations.insert(field, loc);
+            }
+        }
+
+        let Some(block) = child.children() else {
+            continue;
+        };
+        for node in block.nodes() {
+            let fields: &[AppearanceField] = match (section, node.name().value()) {
+                ("focus-ring", "off") => &[AppearanceField::FocusRingOff],
+                ("focus-ring", "width") => &[AppearanceField::FocusRingWidth],
+                ("focus-ring", "active-color" | "active-gradient") => &[AppearanceField::FocusRingActiveColor],
+                ("focus-ring", "inactive-color" | "inactive-gradient") => &[AppearanceField::FocusRingInactiveColor],
+                ("border", "off" | "on") => &[AppearanceField::BorderOff],
+                ("border", "width") => &[AppearanceField::BorderWidth],
+                ("border", "active-color" | "active-gradient") => &[AppearanceField::BorderActiveColor],
+                ("border", "inactive-color" | "inactive-gradient") => &[AppearanceField::BorderInactiveColor],
+                ("border", "urgent-color" | "urgent-gradient") => &[AppearanceField::BorderUrgentColor],
+                ("shadow", "on") => &[AppearanceField::ShadowOn],
+                ("shadow", "draw-behind-window") => &[AppearanceField::ShadowDrawBehindWindow],
+                ("shadow", "softness") => &[AppearanceField::ShadowSoftness],
+                ("shadow", "spread") => &[AppearanceField::ShadowSpread],
+                ("shadow", "offset") => &[AppearanceField::ShadowOffsetX, AppearanceField::ShadowOffsetY],
+                ("shadow", "color") => &[AppearanceField::ShadowColor],
+                ("struts", "left") => &[AppearanceField::StrutsLeft],
+                ("struts", "right") => &[AppearanceField::StrutsRight],
+                ("struts", "top") => &[AppearanceField::StrutsTop],
+                ("struts", "bottom") => &[AppearanceField::StrutsBottom],
+                _ => &[],
+            };
+            for field in fields {
+                if let Some(loc) = config.locate(node) {
+                    locations.insert(*field, loc);
+                }
+            }
+        }
+    }
+
+    locations
+}
+
 fn parse_layout_block(node: &kdl::KdlNode, settings: &mut AppearanceSettings) {
     // Parse direct children of layout
     if let Some(children) = node.children() {
@@ -25,8 +112,8 @@ fn parse_layout_block(node: &kdl::KdlNode, settings: &mut AppearanceSettings) {
             let name = child.name().value();
             match name {
                 "gaps" => {
-                    if let Some(val) = child.get(0).and_then(|v| v.as_integer()) {
-                        settings.gaps = val as i32;
+                    if let Some(val) = child.get(0).and_then(as_int) {
+                        settings.gaps = val;
                     }
                 }
                 "center-focused-column" => {
@@ -65,8 +152,8 @@ fn parse_focus_ring(node: &kdl::KdlNode) -> FocusRingSettings {
                     settings.off = true;
                 }
                 "width" => {
-                    if let Some(val) = child.get(0).and_then(|v| v.as_integer()) {
-                        settings.width = val as i32;
+                    if let Some(val) = child.get(0).and_then(as_int) {
+                        settings.width = val;
                     }
                 }
                 "active-color" => {
@@ -115,8 +202,8 @@ fn parse_border(node: &kdl::KdlNode) -> BorderSettings {
                     settings.off = false;
                 }
                 "width" => {
-                    if let Some(val) = child.get(0).and_then(|v| v.as_integer()) {
-                        settings.width = val as i32;
+                    if let Some(val) = child.get(0).and_then(as_int) {
+                        settings.width = val;
                     }
                 }
                 "active-color" => {
@@ -171,7 +258,7 @@ fn parse_shadow(node: &kdl::KdlNode) -> ShadowSettings {
                     settings.on = true;
                 }
                 "draw-behind-window" => {
-                    if let Some(val) = child.get(0).and_then(|v| v.as_bool()) {
+                    if let Some(val) = child.get(0).and_then(as_bool) {
                         settings.draw_behind_window = val;
                     } else {
                         // If present without value, it means true
@@ -179,22 +266,22 @@ fn parse_shadow(node: &kdl::KdlNode) -> ShadowSettings {
                     }
                 }
                 "softness" => {
-                    if let Some(val) = child.get(0).and_then(|v| v.as_integer()) {
-                        settings.softness = val as i32;
+                    if let Some(val) = child.get(0).and_then(as_int) {
+                        settings.softness = val;
                     }
                 }
                 "spread" => {
-                    if let Some(val) = child.get(0).and_then(|v| v.as_integer()) {
-                        settings.spread = val as i32;
+                    if let Some(val) = child.get(0).and_then(as_int) {
+                        settings.spread = val;
                     }
                 }
                 "offset" => {
                     // offset x=0 y=5
-                    if let Some(x) = child.get("x").and_then(|v| v.as_integer()) {
-                        settings.offset_x = x as i32;
+                    if let Some(x) = child.get("x").and_then(as_int) {
+                        settings.offset_x = x;
                     }
-                    if let Some(y) = child.get("y").and_then(|v| v.as_integer()) {
-                        settings.offset_y = y as i32;
+                    if let Some(y) = child.get("y").and_then(as_int) {
+                        settings.offset_y = y;
                     }
                 }
                 "color" => {
@@ -216,7 +303,7 @@ fn parse_struts(node: &kdl::KdlNode) -> StrutsSettings {
     if let Some(children) = node.children() {
         for child in children.nodes() {
             let name = child.name().value();
-            let value = child.get(0).and_then(|v| v.as_integer()).map(|v| v as i32);
+            let value = child.get(0).and_then(as_int);
 
             match name {
                 "left" => settings.left = value,
@@ -231,20 +318,85 @@ fn parse_struts(node: &kdl::KdlNode) -> StrutsSettings {
     settings
 }
 
+fn parse_overview(node: &kdl::KdlNode) -> OverviewSettings {
+    let mut settings = OverviewSettings::default();
+
+    for child in node.children().map(|c| c.nodes()).unwrap_or_default() {
+        match child.name().value() {
+            "zoom" => {
+                // A fraction of the screen; an integer 1 is valid KDL for it too
+                if let Some(zoom) = child.get(0).and_then(as_float) {
+                    settings.zoom_percent = (zoom * 100.0).round() as i32;
+                }
+            }
+            "backdrop-color" => {
+                if let Some(color) = parse_color_value(child) {
+                    settings.backdrop_color = color;
+                }
+            }
+            "workspace-shadow" => {
+                for shadow in child.children().map(|c| c.nodes()).unwrap_or_default() {
+                    let integer = || shadow.get(0).and_then(as_int);
+                    match shadow.name().value() {
+                        "off" => settings.workspace_shadow_off = true,
+                        "softness" => settings.shadow_softness = integer().unwrap_or(settings.shadow_softness),
+                        "spread" => settings.shadow_spread = integer().unwrap_or(settings.shadow_spread),
+                        "offset" => {
+                            if let Some(x) = shadow.get("x").and_then(as_int) {
+                                settings.shadow_offset_x = x;
+                            }
+                            if let Some(y) = shadow.get("y").and_then(as_int) {
+                                settings.shadow_offset_y = y;
+                            }
+                        }
+                        "color" => {
+                            if let Some(color) = parse_color_value(shadow) {
+                                settings.shadow_color = color;
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    settings
+}
+
+fn locate_workspace_shadow(
+    config: &ConfigDocument,
+    block: &kdl::KdlNode,
+    locations: &mut HashMap<AppearanceField, SourceLocation>,
+) {
+    for node in block.children().map(|c| c.nodes()).unwrap_or_default() {
+        let fields: &[AppearanceField] = match node.name().value() {
+            "off" => &[AppearanceField::OverviewShadowOff],
+            "softness" => &[AppearanceField::OverviewShadowSoftness],
+            "spread" => &[AppearanceField::OverviewShadowSpread],
+            "offset" => &[AppearanceField::OverviewShadowOffsetX, AppearanceField::OverviewShadowOffsetY],
+            "color" => &[AppearanceField::OverviewShadowColor],
+            _ => continue,
+        };
+        if let Some(loc) = config.locate(node) {
+            for &field in fields {
+                locations.insert(field, loc.clone());
+            }
+        }
+    }
+}
+
 /// Parse a color value from a node (either solid color string or gradient)
 fn parse_color_value(node: &kdl::KdlNode) -> Option<ColorValue> {
-    // First positional argument is the color string
-    if let Some(color) = node.get(0).and_then(|v| v.as_string()) {
-        return Some(ColorValue::Solid(color.to_string()));
-    }
-    None
+    node_color(node).map(ColorValue::Solid)
 }
 
 /// Parse a gradient from named parameters
 fn parse_gradient(node: &kdl::KdlNode) -> Option<ColorValue> {
     let from = node.get("from").and_then(|v| v.as_string())?.to_string();
     let to = node.get("to").and_then(|v| v.as_string())?.to_string();
-    let angle = node.get("angle").and_then(|v| v.as_integer()).map(|v| v as i32);
+    let angle = node.get("angle").and_then(as_int);
     let relative_to = node.get("relative-to").and_then(|v| v.as_string()).map(|s| s.to_string());
     let color_space = node.get("in").and_then(|v| v.as_string()).map(|s| s.to_string());
 
@@ -266,6 +418,7 @@ mod tests {
         ConfigDocument {
             doc: content.parse().unwrap(),
             path: std::path::PathBuf::new(),
+            source: content.to_string(),
         }
     }
 
@@ -347,6 +500,42 @@ mod tests {
         assert_eq!(settings.struts.bottom, None);
     }
 
+    #[test]
+    fn test_parse_overview() {
+        let content = "overview {\n    zoom 0.25\n    backdrop-color \"#777777\"\n    workspace-shadow {\n        off\n        offset x=0 y=20\n    }\n}\n";
+        let config = ConfigDocument {
+            doc: kdl::KdlDocument::parse_v1(content).unwrap(),
+            path: std::path::PathBuf::from("/home/user/.config/niri/config.kdl"),
+            source: content.to_string(),
+        };
+        let settings = parse_appearance(&config);
+        assert_eq!(settings.overview.zoom_percent, 25);
+        assert_eq!(settings.overview.backdrop_color, ColorValue::Solid("#777777".to_string()));
+        assert!(settings.overview.workspace_shadow_off);
+        assert_eq!(settings.overview.shadow_offset_y, 20);
+        assert_eq!(settings.overview.shadow_softness, 40);
+
+        let locations = locate_appearance_fields(&config);
+        assert_eq!(locations[&AppearanceField::OverviewZoom].line, 2);
+        assert_eq!(locations[&AppearanceField::OverviewShadowOffsetX].line, 6);
+        assert!(!locations.contains_key(&AppearanceField::OverviewShadowColor));
+    }
+
+    #[test]
+    fn test_locate_appearance_fields() {
+        let content = "layout {\n    gaps 16\n    shadow {\n        offset x=0 y=5\n    }\n}\n";
+        let config = ConfigDocument {
+            doc: kdl::KdlDocument::parse_v1(content).unwrap(),
+            path: std::path::PathBuf::from("/home/user/.config/niri/config.kdl"),
+            source: content.to_string(),
+        };
+        let locations = locate_appearance_fields(&config);
+        assert_eq!(locations[&AppearanceField::Gaps].line, 2);
+        assert_eq!(locations[&AppearanceField::ShadowOffsetY].line, 4);
+        assert_eq!(locations[&AppearanceField::ShadowOffsetX].to_string(), "config.kdl:4");
+        assert!(!locations.contains_key(&AppearanceField::BorderWidth));
+    }
+
     #[test]
     fn test_parse_border_gradient() {
         let config = parse_test_config(r##"
@@ -1,10 +1,41 @@
 use anyhow::Result;
 use kdl::{KdlDocument, KdlEntry, KdlNode, KdlValue};
 
-use crate::model::{AppearanceSettings, ColorValue, ConfigDocument};
+use super::kdl_format::format_node;
+use super::kdl_value::{self, boolean, float, int, string};
+use crate::model::{
+    AppearanceField, AppearanceSection, AppearanceSettings, BorderSettings, ColorValue, ConfigDocument,
+    FocusRingSettings, OverviewSettings, ShadowSettings, StrutsSettings,
+};
+
+/// Write appearance settings to the config document, touching only the
+/// nodes whose value differs from `original` (the settings as loaded)
+pub fn write_appearance(
+    config: &mut ConfigDocument,
+    original: &AppearanceSettings,
+    settings: &AppearanceSettings,
+) -> Result<()> {
+    apply_appearance(config, original, settings);
+    config.save()
+}
+
+/// Update the layout block (and the top-level blocks edited with it) in
+/// memory without saving
+pub fn apply_appearance(config: &mut ConfigDocument, original: &AppearanceSettings, settings: &AppearanceSettings) {
+    if original.overview != settings.overview {
+        update_overview(&mut config.doc, Some(&original.overview), &settings.overview);
+    }
+
+    // Everything else lives in layout; leave it alone (or missing) when
+    // nothing there changed
+    let layout_unchanged = AppearanceSettings {
+        overview: settings.overview.clone(),
+        ..original.clone()
+    } == *settings;
+    if layout_unchanged {
+        return;
+    }
 
-/// Write appearance settings to the config document
-pub fn write_appearance(config: &mut ConfigDocument, settings: &AppearanceSettings) -> Result<()> {
     // Find or create the layout block
     let layout_idx = config
         .doc
@@ -12,262 +43,442 @@ pub fn write_appearance(config: &mut ConfigDocument, settings: &AppearanceSettin
         .iter()
         .position(|n| n.name().value() == "layout");
 
-    let layout_node = if let Some(idx) = layout_idx {
-        config.doc.nodes_mut().get_mut(idx).unwrap()
+    let (layout_node, mut created) = if let Some(idx) = layout_idx {
+        (config.doc.nodes_mut().get_mut(idx).unwrap(), false)
     } else {
         // Create a new layout block
         let mut layout = KdlNode::new("layout");
         layout.set_children(KdlDocument::new());
         config.doc.nodes_mut().push(layout);
-        config.doc.nodes_mut().last_mut().unwrap()
+        (config.doc.nodes_mut().last_mut().unwrap(), true)
     };
 
     // Ensure children exist
     if layout_node.children().is_none() {
         layout_node.set_children(KdlDocument::new());
+        created = true;
     }
 
     let children = layout_node.children_mut().as_mut().unwrap();
+    // Compare against the loaded settings even for a new block, so values the
+    // config left to niri's defaults stay implicit
+    let old = Some(original);
 
-    // Update or add gaps
-    update_or_add_simple_value(children, "gaps", KdlValue::Integer(settings.gaps as i128));
+    if changed(old.map(|o| &o.gaps), &settings.gaps) {
+        update_or_add_simple_value(children, "gaps", int(settings.gaps), 1);
+    }
 
-    // Update or add center-focused-column
-    update_or_add_simple_value(
-        children,
-        "center-focused-column",
-        KdlValue::String(settings.center_focused_column.as_str().to_string()),
-    );
+    if changed(old.map(|o| &o.center_focused_column), &settings.center_focused_column) {
+        update_or_add_simple_value(
+            children,
+            "center-focused-column",
+            string(settings.center_focused_column.as_str()),
+            1,
+        );
+    }
 
-    // Update focus-ring block
-    update_focus_ring(children, &settings.focus_ring);
+    if changed(old.map(|o| &o.focus_ring), &settings.focus_ring) {
+        update_focus_ring(children, old.map(|o| &o.focus_ring), &settings.focus_ring);
+    }
 
-    // Update border block
-    update_border(children, &settings.border);
+    if changed(old.map(|o| &o.border), &settings.border) {
+        update_border(children, old.map(|o| &o.border), &settings.border);
+    }
 
-    // Update shadow block
-    update_shadow(children, &settings.shadow);
+    if changed(old.map(|o| &o.shadow), &settings.shadow) {
+        update_shadow(children, old.map(|o| &o.shadow), &settings.shadow);
+    }
 
-    // Update struts block
-    update_struts(children, &settings.struts);
+    if changed(old.map(|o| &o.struts), &settings.struts) {
+        update_struts(children, old.map(|o| &o.struts), &settings.struts);
+    }
 
-    // Autoformat
-    children.autoformat();
-    layout_node.autoformat();
+    if created {
+        layout_node.autoformat();
+    }
+}
 
-    config.save()
+/// Whether a value needs writing; with no previous value everything does
+pub(super) fn changed<T: PartialEq>(old: Option<&T>, new: &T) -> bool {
+    old != Some(new)
 }
 
-fn update_or_add_simple_value(children: &mut KdlDocument, name: &str, value: KdlValue) {
+/// Render the node a field is written as, inside its section block, using the
+/// same logic as `write_appearance` so toggle and property forms match the file
+pub fn appearance_field_kdl(settings: &AppearanceSettings, field: AppearanceField) -> String {
+    let mut layout = KdlDocument::new();
+    let section = match field.section() {
+        AppearanceSection::General => {
+            match field {
+                AppearanceField::Gaps => {
+                    update_or_add_simple_value(&mut layout, "gaps", int(settings.gaps), 0)
+                }
+                _ => update_or_add_simple_value(
+                    &mut layout,
+                    "center-focused-column",
+                    string(settings.center_focused_column.as_str()),
+                    0,
+                ),
+            }
+            None
+        }
+        AppearanceSection::FocusRing => {
+            update_focus_ring(&mut layout, None, &settings.focus_ring);
+            Some("focus-ring")
+        }
+        AppearanceSection::Border => {
+            update_border(&mut layout, None, &settings.border);
+            Some("border")
+        }
+        AppearanceSection::Shadow => {
+            update_shadow(&mut layout, None, &settings.shadow);
+            Some("shadow")
+        }
+        AppearanceSection::Struts => {
+            update_struts(&mut layout, None, &settings.struts);
+            Some("struts")
+        }
+        AppearanceSection::Overview => {
+            update_overview(&mut layout, None, &settings.overview);
+            Some("overview")
+        }
+    };
+
+    // Node names the field can be written as
+    let names: Vec<String> = match field {
+        AppearanceField::BorderOff => vec!["off".to_string(), "on".to_string()],
+        AppearanceField::ShadowOffsetX | AppearanceField::ShadowOffsetY => vec!["offset".to_string()],
+        // The workspace shadow fields sit one block further in
+        AppearanceField::OverviewShadowOff
+        | AppearanceField::OverviewShadowSoftness
+        | AppearanceField::OverviewShadowSpread
+        | AppearanceField::OverviewShadowOffsetX
+        | AppearanceField::OverviewShadowOffsetY
+        | AppearanceField::OverviewShadowColor => vec!["workspace-shadow".to_string()],
+        _ if field.is_color() => vec![field.name().to_string(), color_name_to_gradient_name(field.name())],
+        _ => vec![field.name().to_string()],
+    };
+
+    let source = match section {
+        Some(name) => layout
+            .nodes()
+            .iter()
+            .find(|n| n.name().value() == name)
+            .and_then(|n| n.children())
+            .cloned()
+            .unwrap_or_default(),
+        None => layout,
+    };
+    let mut nodes = KdlDocument::new();
+    for node in source.nodes() {
+        if names.iter().any(|name| node.name().value() == name) {
+            nodes.nodes_mut().push(node.clone());
+        }
+    }
+
+    if nodes.nodes().is_empty() {
+        return match section {
+            Some(name) => format!("{name} {{\n    // no {} node\n}}", field.name()),
+            None => format!("// no {} node", field.name()),
+        };
+    }
+
+    let mut doc = match section {
+        Some(name) => {
+            let mut node = KdlNode::new(name);
+            node.set_children(nodes);
+            let mut doc = KdlDocument::new();
+            doc.nodes_mut().push(node);
+            doc
+        }
+        None => nodes,
+    };
+    doc.autoformat();
+    doc.ensure_v1();
+    doc.to_string().trim().to_string()
+}
+
+pub(super) fn update_or_add_simple_value(children: &mut KdlDocument, name: &str, value: KdlValue, depth: usize) {
     // Try to find existing node
     if let Some(node) = children.nodes_mut().iter_mut().find(|n| n.name().value() == name) {
-        // Update existing
+        // Leave the node alone if it already holds this value
+        if node.entries().len() == 1 && node.entries()[0].name().is_none() && node.entries()[0].value() == &value {
+            return;
+        }
         node.entries_mut().clear();
         node.push(KdlEntry::new(value));
     } else {
         // Add new
         let mut node = KdlNode::new(name);
         node.push(KdlEntry::new(value));
-        children.nodes_mut().push(node);
+        push_node(children, node, depth);
     }
 }
 
-fn update_focus_ring(parent: &mut KdlDocument, settings: &crate::model::FocusRingSettings) {
-    // Find or create focus-ring block
-    let focus_ring_idx = parent
-        .nodes()
-        .iter()
-        .position(|n| n.name().value() == "focus-ring");
+/// Append a node formatted for its depth, leaving siblings untouched
+fn push_node(children: &mut KdlDocument, mut node: KdlNode, depth: usize) {
+    format_node(&mut node, depth);
+    children.nodes_mut().push(node);
+}
 
-    let focus_ring = if let Some(idx) = focus_ring_idx {
-        parent.nodes_mut().get_mut(idx).unwrap()
-    } else {
-        let mut node = KdlNode::new("focus-ring");
-        node.set_children(KdlDocument::new());
-        parent.nodes_mut().push(node);
-        parent.nodes_mut().last_mut().unwrap()
+/// Run `update` on a child block's contents, creating the block at the end if
+/// missing. Only a newly created block is formatted as a whole.
+fn update_block(parent: &mut KdlDocument, name: &str, update: impl FnOnce(&mut KdlDocument)) {
+    let (idx, mut created) = match parent.nodes().iter().position(|n| n.name().value() == name) {
+        Some(idx) => (idx, false),
+        None => {
+            parent.nodes_mut().push(KdlNode::new(name));
+            (parent.nodes().len() - 1, true)
+        }
     };
-
-    if focus_ring.children().is_none() {
-        focus_ring.set_children(KdlDocument::new());
+    let block = &mut parent.nodes_mut()[idx];
+    if block.children().is_none() {
+        block.set_children(KdlDocument::new());
+        created = true;
     }
+    update(block.children_mut().as_mut().unwrap());
+    if created {
+        format_node(block, 1);
+    }
+}
 
-    let children = focus_ring.children_mut().as_mut().unwrap();
-
-    // Handle off state
-    update_toggle_node(children, "off", settings.off);
-
-    // Update width
-    update_or_add_simple_value(children, "width", KdlValue::Integer(settings.width as i128));
+fn update_focus_ring(parent: &mut KdlDocument, old: Option<&FocusRingSettings>, settings: &FocusRingSettings) {
+    update_block(parent, "focus-ring", |children| {
+        // Handle off state
+        if changed(old.map(|o| &o.off), &settings.off) {
+            update_toggle_node(children, "off", settings.off);
+        }
 
-    // Update colors
-    update_color(children, "active-color", &settings.active_color);
-    update_color(children, "inactive-color", &settings.inactive_color);
+        if changed(old.map(|o| &o.width), &settings.width) {
+            update_or_add_simple_value(children, "width", int(settings.width), 2);
+        }
 
-    // Only write legacy gradient fields if the main color is solid
-    // (otherwise update_color already wrote the gradient)
-    if matches!(settings.active_color, crate::model::ColorValue::Solid(_)) {
-        if let Some(ref gradient) = settings.active_gradient {
-            update_gradient_node(children, "active-gradient", gradient);
-        } else {
-            remove_node(children, "active-gradient");
+        // Update colors
+        if changed(old.map(|o| &o.active_color), &settings.active_color) {
+            update_color(children, "active-color", &settings.active_color);
+        }
+        if changed(old.map(|o| &o.inactive_color), &settings.inactive_color) {
+            update_color(children, "inactive-color", &settings.inactive_color);
         }
-    }
 
-    if matches!(settings.inactive_color, crate::model::ColorValue::Solid(_)) {
-        if let Some(ref gradient) = settings.inactive_gradient {
-            update_gradient_node(children, "inactive-gradient", gradient);
-        } else {
-            remove_node(children, "inactive-gradient");
+        // Only write legacy gradient fields if the main color is solid
+        // (otherwise update_color already wrote the gradient)
+        if matches!(settings.active_color, ColorValue::Solid(_))
+            && changed(old.map(|o| &o.active_gradient), &settings.active_gradient)
+        {
+            if let Some(ref gradient) = settings.active_gradient {
+                update_gradient_node(children, "active-gradient", gradient);
+            } else {
+                remove_node(children, "active-gradient");
+            }
         }
-    }
 
-    children.autoformat();
-    focus_ring.autoformat();
+        if matches!(settings.inactive_color, ColorValue::Solid(_))
+            && changed(old.map(|o| &o.inactive_gradient), &settings.inactive_gradient)
+        {
+            if let Some(ref gradient) = settings.inactive_gradient {
+                update_gradient_node(children, "inactive-gradient", gradient);
+            } else {
+                remove_node(children, "inactive-gradient");
+            }
+        }
+    });
 }
 
-fn update_border(parent: &mut KdlDocument, settings: &crate::model::BorderSettings) {
-    let border_idx = parent
-        .nodes()
-        .iter()
-        .position(|n| n.name().value() == "border");
+fn update_border(parent: &mut KdlDocument, old: Option<&BorderSettings>, settings: &BorderSettings) {
+    update_block(parent, "border", |children| {
+        // Handle off/on state - remove the other when setting one
+        if changed(old.map(|o| &o.off), &settings.off) {
+            if settings.off {
+                update_toggle_node(children, "off", true);
+                remove_node(children, "on");
+            } else {
+                update_toggle_node(children, "on", true);
+                remove_node(children, "off");
+            }
+        }
 
-    let border = if let Some(idx) = border_idx {
-        parent.nodes_mut().get_mut(idx).unwrap()
-    } else {
-        let mut node = KdlNode::new("border");
-        node.set_children(KdlDocument::new());
-        parent.nodes_mut().push(node);
-        parent.nodes_mut().last_mut().unwrap()
-    };
+        if changed(old.map(|o| &o.width), &settings.width) {
+            update_or_add_simple_value(children, "width", int(settings.width), 2);
+        }
+        if changed(old.map(|o| &o.active_color), &settings.active_color) {
+            update_color(children, "active-color", &settings.active_color);
+        }
+        if changed(old.map(|o| &o.inactive_color), &settings.inactive_color) {
+            update_color(children, "inactive-color", &settings.inactive_color);
+        }
 
-    if border.children().is_none() {
-        border.set_children(KdlDocument::new());
-    }
+        if changed(old.map(|o| &o.urgent_color), &settings.urgent_color) {
+            if let Some(ref color) = settings.urgent_color {
+                update_color(children, "urgent-color", color);
+            } else {
+                remove_node(children, "urgent-color");
+                remove_node(children, "urgent-gradient");
+            }
+        }
 
-    let children = border.children_mut().as_mut().unwrap();
+        // Only write legacy gradient fields if the main color is solid
+        // (otherwise update_color already wrote the gradient)
+        if matches!(settings.active_color, ColorValue::Solid(_))
+            && changed(old.map(|o| &o.active_gradient), &settings.active_gradient)
+        {
+            if let Some(ref gradient) = settings.active_gradient {
+                update_gradient_node(children, "active-gradient", gradient);
+            } else {
+                remove_node(children, "active-gradient");
+            }
+        }
 
-    // Handle off/on state - remove the other when setting one
-    if settings.off {
-        update_toggle_node(children, "off", true);
-        remove_node(children, "on");
-    } else {
-        update_toggle_node(children, "on", true);
-        remove_node(children, "off");
-    }
+        if matches!(settings.inactive_color, ColorValue::Solid(_))
+            && changed(old.map(|o| &o.inactive_gradient), &settings.inactive_gradient)
+        {
+            if let Some(ref gradient) = settings.inactive_gradient {
+                update_gradient_node(children, "inactive-gradient", gradient);
+            } else {
+                remove_node(children, "inactive-gradient");
+            }
+        }
+    });
+}
 
-    update_or_add_simple_value(children, "width", KdlValue::Integer(settings.width as i128));
-    update_color(children, "active-color", &settings.active_color);
-    update_color(children, "inactive-color", &settings.inactive_color);
+fn update_shadow(parent: &mut KdlDocument, old: Option<&ShadowSettings>, settings: &ShadowSettings) {
+    update_block(parent, "shadow", |children| {
+        // Handle on state
+        if changed(old.map(|o| &o.on), &settings.on) {
+            update_toggle_node(children, "on", settings.on);
+        }
 
-    if let Some(ref color) = settings.urgent_color {
-        update_color(children, "urgent-color", color);
-    } else {
-        remove_node(children, "urgent-color");
-        remove_node(children, "urgent-gradient");
-    }
+        // Handle draw-behind-window
+        if changed(old.map(|o| &o.draw_behind_window), &settings.draw_behind_window) {
+            if settings.draw_behind_window {
+                update_or_add_simple_value(children, "draw-behind-window", boolean(true), 2);
+            } else {
+                remove_node(children, "draw-behind-window");
+            }
+        }
 
-    // Only write legacy gradient fields if the main color is solid
-    // (otherwise update_color already wrote the gradient)
-    if matches!(settings.active_color, crate::model::ColorValue::Solid(_)) {
-        if let Some(ref gradient) = settings.active_gradient {
-            update_gradient_node(children, "active-gradient", gradient);
-        } else {
-            remove_node(children, "active-gradient");
+        if changed(old.map(|o| &o.softness), &settings.softness) {
+            update_or_add_simple_value(children, "softness", int(settings.softness), 2);
+        }
+        if changed(old.map(|o| &o.spread), &settings.spread) {
+            update_or_add_simple_value(children, "spread", int(settings.spread), 2);
         }
-    }
 
-    if matches!(settings.inactive_color, crate::model::ColorValue::Solid(_)) {
-        if let Some(ref gradient) = settings.inactive_gradient {
-            update_gradient_node(children, "inactive-gradient", gradient);
-        } else {
-            remove_node(children, "inactive-gradient");
+        // Update offset
+        if changed(old.map(|o| (o.offset_x, o.offset_y)).as_ref(), &(settings.offset_x, settings.offset_y)) {
+            update_offset(children, settings.offset_x, settings.offset_y);
         }
-    }
 
-    children.autoformat();
-    border.autoformat();
+        if changed(old.map(|o| &o.color), &settings.color) {
+            update_color(children, "color", &settings.color);
+        }
+    });
 }
 
-fn update_shadow(parent: &mut KdlDocument, settings: &crate::model::ShadowSettings) {
-    let shadow_idx = parent
-        .nodes()
-        .iter()
-        .position(|n| n.name().value() == "shadow");
+fn update_struts(parent: &mut KdlDocument, old: Option<&StrutsSettings>, settings: &StrutsSettings) {
+    update_block(parent, "struts", |children| {
+        if changed(old.map(|o| &o.left), &settings.left) {
+            update_optional_value(children, "left", settings.left);
+        }
+        if changed(old.map(|o| &o.right), &settings.right) {
+            update_optional_value(children, "right", settings.right);
+        }
+        if changed(old.map(|o| &o.top), &settings.top) {
+            update_optional_value(children, "top", settings.top);
+        }
+        if changed(old.map(|o| &o.bottom), &settings.bottom) {
+            update_optional_value(children, "bottom", settings.bottom);
+        }
+    });
+}
 
-    let shadow = if let Some(idx) = shadow_idx {
-        parent.nodes_mut().get_mut(idx).unwrap()
-    } else {
-        let mut node = KdlNode::new("shadow");
-        node.set_children(KdlDocument::new());
-        parent.nodes_mut().push(node);
-        parent.nodes_mut().last_mut().unwrap()
+/// Like `update_block` for a block at the top of the config, which is
+/// dropped again once it holds nothing (unless a comment is attached to it)
+pub(super) fn update_top_level_block(doc: &mut KdlDocument, name: &str, update: impl FnOnce(&mut KdlDocument)) {
+    let (idx, mut created) = match doc.nodes().iter().position(|n| n.name().value() == name) {
+        Some(idx) => (idx, false),
+        None => {
+            doc.nodes_mut().push(KdlNode::new(name));
+            (doc.nodes().len() - 1, true)
+        }
     };
-
-    if shadow.children().is_none() {
-        shadow.set_children(KdlDocument::new());
+    let block = &mut doc.nodes_mut()[idx];
+    if block.children().is_none() {
+        block.set_children(KdlDocument::new());
+        created = true;
     }
-
-    let children = shadow.children_mut().as_mut().unwrap();
-
-    // Handle on state
-    update_toggle_node(children, "on", settings.on);
-
-    // Handle draw-behind-window
-    if settings.draw_behind_window {
-        update_or_add_simple_value(children, "draw-behind-window", KdlValue::Bool(true));
-    } else {
-        remove_node(children, "draw-behind-window");
+    let commented = block.format().is_some_and(|f| f.leading.contains("//"));
+    let children = block.children_mut().as_mut().unwrap();
+    let was_empty = children.nodes().is_empty();
+    update(children);
+
+    if children.nodes().is_empty() && !commented {
+        doc.nodes_mut().remove(idx);
+    } else if created || was_empty {
+        // Also lays out a kept `{}` that gets contents again
+        format_node(block, 0);
     }
-
-    update_or_add_simple_value(children, "softness", KdlValue::Integer(settings.softness as i128));
-    update_or_add_simple_value(children, "spread", KdlValue::Integer(settings.spread as i128));
-
-    // Update offset
-    update_offset(children, settings.offset_x, settings.offset_y);
-
-    update_color(children, "color", &settings.color);
-
-    children.autoformat();
-    shadow.autoformat();
 }
 
-fn update_struts(parent: &mut KdlDocument, settings: &crate::model::StrutsSettings) {
-    let struts_idx = parent
-        .nodes()
-        .iter()
-        .position(|n| n.name().value() == "struts");
-
-    let struts = if let Some(idx) = struts_idx {
-        parent.nodes_mut().get_mut(idx).unwrap()
-    } else {
-        let mut node = KdlNode::new("struts");
-        node.set_children(KdlDocument::new());
-        parent.nodes_mut().push(node);
-        parent.nodes_mut().last_mut().unwrap()
-    };
-
-    if struts.children().is_none() {
-        struts.set_children(KdlDocument::new());
+/// Add or remove a bare flag node such as `off` in a top-level block
+pub(super) fn set_top_level_flag(children: &mut KdlDocument, name: &str, enabled: bool) {
+    let exists = children.nodes().iter().any(|n| n.name().value() == name);
+    if enabled && !exists {
+        push_node(children, KdlNode::new(name), 1);
+    } else if !enabled {
+        remove_node(children, name);
     }
+}
 
-    let children = struts.children_mut().as_mut().unwrap();
-
-    update_optional_value(children, "left", settings.left);
-    update_optional_value(children, "right", settings.right);
-    update_optional_value(children, "top", settings.top);
-    update_optional_value(children, "bottom", settings.bottom);
+fn update_overview(doc: &mut KdlDocument, old: Option<&OverviewSettings>, settings: &OverviewSettings) {
+    update_top_level_block(doc, "overview", |children| {
+        if changed(old.map(|o| &o.zoom_percent), &settings.zoom_percent) {
+            let zoom = float(settings.zoom_percent as f64 / 100.0);
+            update_or_add_simple_value(children, "zoom", zoom, 1);
+        }
+        if changed(old.map(|o| &o.backdrop_color), &settings.backdrop_color) {
+            // niri only takes a solid backdrop
+            let color = match &settings.backdrop_color {
+                ColorValue::Solid(color) => color,
+                ColorValue::Gradient { from, .. } => from,
+            };
+            update_or_add_simple_value(children, "backdrop-color", kdl_value::color(color), 1);
+        }
 
-    children.autoformat();
-    struts.autoformat();
+        let shadow = |o: &OverviewSettings| {
+            (o.workspace_shadow_off, o.shadow_softness, o.shadow_spread, o.shadow_offset_x, o.shadow_offset_y, o.shadow_color.clone())
+        };
+        if !changed(old.map(shadow).as_ref(), &shadow(settings)) {
+            return;
+        }
+        update_block(children, "workspace-shadow", |children| {
+            if changed(old.map(|o| &o.workspace_shadow_off), &settings.workspace_shadow_off) {
+                update_toggle_node(children, "off", settings.workspace_shadow_off);
+            }
+            if changed(old.map(|o| &o.shadow_softness), &settings.shadow_softness) {
+                update_or_add_simple_value(children, "softness", int(settings.shadow_softness), 2);
+            }
+            if changed(old.map(|o| &o.shadow_spread), &settings.shadow_spread) {
+                update_or_add_simple_value(children, "spread", int(settings.shadow_spread), 2);
+            }
+            if changed(
+                old.map(|o| (o.shadow_offset_x, o.shadow_offset_y)).as_ref(),
+                &(settings.shadow_offset_x, settings.shadow_offset_y),
+            ) {
+                update_offset(children, settings.shadow_offset_x, settings.shadow_offset_y);
+            }
+            if changed(old.map(|o| &o.shadow_color), &settings.shadow_color) {
+                update_color(children, "color", &settings.shadow_color);
+            }
+        });
+    });
 }
 
 fn update_toggle_node(children: &mut KdlDocument, name: &str, enabled: bool) {
     let exists = children.nodes().iter().any(|n| n.name().value() == name);
 
     if enabled && !exists {
-        let node = KdlNode::new(name);
-        children.nodes_mut().push(node);
+        push_node(children, KdlNode::new(name), 2);
     } else if !enabled && exists {
         remove_node(children, name);
     }
@@ -276,8 +487,8 @@ fn update_toggle_node(children: &mut KdlDocument, name: &str, enabled: bool) {
 /// Convert a color node name to its gradient counterpart
 /// e.g., "active-color" -> "active-gradient", "color" -> "gradient"
 fn color_name_to_gradient_name(color_name: &str) -> String {
-    if color_name.ends_with("-color") {
-        format!("{}-gradient", &color_name[..color_name.len() - 6])
+    if let Some(prefix) = color_name.strip_suffix("-color") {
+        format!("{prefix}-gradient")
     } else if color_name == "color" {
         "gradient".to_string()
     } else {
@@ -293,7 +504,7 @@ fn update_color(children: &mut KdlDocument, name: &str, color: &ColorValue) {
             // Remove any existing gradient node for this color
             remove_node(children, &gradient_name);
             // Write the solid color
-            update_or_add_simple_value(children, name, KdlValue::String(c.clone()));
+            update_or_add_simple_value(children, name, kdl_value::color(c), 2);
         }
         ColorValue::Gradient { .. } => {
             // Remove any existing solid color node
@@ -313,48 +524,65 @@ fn update_gradient_node(children: &mut KdlDocument, gradient_name: &str, gradien
         color_space,
     } = gradient
     {
-        // Remove existing gradient node
-        remove_node(children, gradient_name);
-
         // Create new gradient node
         let mut node = KdlNode::new(gradient_name);
-        node.push(KdlEntry::new_prop("from", KdlValue::String(from.clone())));
-        node.push(KdlEntry::new_prop("to", KdlValue::String(to.clone())));
+        node.push(KdlEntry::new_prop("from", string(from.clone())));
+        node.push(KdlEntry::new_prop("to", string(to.clone())));
 
         if let Some(a) = angle {
-            node.push(KdlEntry::new_prop("angle", KdlValue::Integer(*a as i128)));
+            node.push(KdlEntry::new_prop("angle", int(*a)));
         }
         if let Some(r) = relative_to {
-            node.push(KdlEntry::new_prop("relative-to", KdlValue::String(r.clone())));
+            node.push(KdlEntry::new_prop("relative-to", string(r.clone())));
         }
         if let Some(c) = color_space {
-            node.push(KdlEntry::new_prop("in", KdlValue::String(c.clone())));
+            node.push(KdlEntry::new_prop("in", string(c.clone())));
         }
 
-        children.nodes_mut().push(node);
+        replace_node(children, gradient_name, node);
     }
 }
 
 fn update_offset(children: &mut KdlDocument, x: i32, y: i32) {
-    // Remove existing
-    remove_node(children, "offset");
-
-    // Create new offset node
     let mut node = KdlNode::new("offset");
-    node.push(KdlEntry::new_prop("x", KdlValue::Integer(x as i128)));
-    node.push(KdlEntry::new_prop("y", KdlValue::Integer(y as i128)));
-    children.nodes_mut().push(node);
+    node.push(KdlEntry::new_prop("x", int(x)));
+    node.push(KdlEntry::new_prop("y", int(y)));
+    replace_node(children, "offset", node);
+}
+
+/// Replace a node in place (keeping its leading comments), or append it
+fn replace_node(children: &mut KdlDocument, name: &str, mut node: KdlNode) {
+    match children.nodes().iter().position(|n| n.name().value() == name) {
+        Some(idx) => {
+            if let Some(format) = children.nodes()[idx].format() {
+                node.set_format(format.clone());
+            }
+            format_node(&mut node, 2);
+            children.nodes_mut()[idx] = node;
+            // Drop any duplicates further down
+            let mut seen = false;
+            children.nodes_mut().retain(|n| {
+                if n.name().value() != name {
+                    return true;
+                }
+                let keep = !seen;
+                seen = true;
+                keep
+            });
+        }
+        None => push_node(children, node, 2),
+    }
 }
 
 fn update_optional_value(children: &mut KdlDocument, name: &str, value: Option<i32>) {
     if let Some(v) = value {
-        update_or_add_simple_value(children, name, KdlValue::Integer(v as i128));
+        update_or_add_simple_value(children, name, int(v), 2);
     } else {
         remove_node(children, name);
     }
 }
 
-fn remove_node(children: &mut KdlDocument, name: &str) {
+pub(super) fn remove_node(children: &mut KdlDocument, name: &str) {
     children.nodes_mut().retain(|n| n.name().value() != name);
 }
 
@@ -368,6 +596,7 @@ mod tests {
         ConfigDocument {
             doc: content.parse().unwrap(),
             path: std::path::PathBuf::from("/tmp/test.kdl"),
+            source: content.to_string(),
         }
     }
 
@@ -388,4 +617,114 @@ mod tests {
         assert_eq!(CenterFocusedColumn::Always.as_str(), "always");
         assert_eq!(CenterFocusedColumn::OnOverflow.as_str(), "on-overflow");
     }
+
+    #[test]
+    fn test_appearance_field_kdl() {
+        let mut settings = AppearanceSettings {
+            gaps: 24,
+            ..Default::default()
+        };
+        assert_eq!(appearance_field_kdl(&settings, AppearanceField::Gaps), "gaps 24");
+
+        settings.shadow.offset_y = 8;
+        assert_eq!(
+            appearance_field_kdl(&settings, AppearanceField::ShadowOffsetY),
+            "shadow {\n    offset x=0 y=8\n}"
+        );
+
+        settings.border.off = false;
+        assert_eq!(
+            appearance_field_kdl(&settings, AppearanceField::BorderOff),
+            "border {\n    on\n}"
+        );
+
+        settings.focus_ring.active_color = ColorValue::Gradient {
+            from: "#80c8ff".to_string(),
+            to: "#bbddff".to_string(),
+            angle: Some(45),
+            relative_to: None,
+            color_space: None,
+        };
+        assert_eq!(
+            appearance_field_kdl(&settings, AppearanceField::FocusRingActiveColor),
+            "focus-ring {\n    active-gradient from=\"#80c8ff\" to=\"#bbddff\" angle=45\n}"
+        );
+        assert_eq!(
+            appearance_field_kdl(&settings, AppearanceField::StrutsLeft),
+            "struts {\n    // no left node\n}"
+        );
+    }
+
+    #[test]
+    fn test_apply_appearance_touches_only_changed_nodes() {
+        let source = "layout {\n    // spacing\n    gaps  16\n    focus-ring {\n        width 4\n    }\n}\n";
+        let mut config = ConfigDocument {
+            doc: KdlDocument::parse_v1(source).unwrap(),
+            path: std::path::PathBuf::from("/tmp/test.kdl"),
+            source: source.to_string(),
+        };
+        let original = parse_appearance(&config);
+        let mut settings = original.clone();
+        settings.focus_ring.width = 2;
+        settings.struts.left = Some(8);
+
+        apply_appearance(&mut config, &original, &settings);
+        crate::config::kdl_format::ensure_v1_new(&mut config.doc);
+        assert_eq!(
+            config.doc.to_string(),
+            "layout {\n    // spacing\n    gaps  16\n    focus-ring {\n        width 2\n    }\n    struts {\n        left 8\n    }\n}\n"
+        );
+
+        // Nothing changed: the document is left alone
+        let before = config.doc.to_string();
+        apply_appearance(&mut config, &settings, &settings);
+        assert_eq!(config.doc.to_string(), before);
+    }
+
+    #[test]
+    fn test_apply_appearance_keeps_defaults_implicit() {
+        let source = "input {\n    keyboard {}\n}\n";
+        let mut config = ConfigDocument {
+            doc: KdlDocument::parse_v1(source).unwrap(),
+            path: std::path::PathBuf::from("/tmp/test.kdl"),
+            source: source.to_string(),
+        };
+        let original = parse_appearance(&config);
+        let mut settings = original.clone();
+        settings.gaps = 24;
+        settings.shadow.softness = 10;
+
+        apply_appearance(&mut config, &original, &settings);
+        crate::config::kdl_format::ensure_v1_new(&mut config.doc);
+        assert_eq!(
+            config.doc.to_string(),
+            "input {\n    keyboard {}\n}\nlayout {\n    gaps 24\n    shadow {\n        softness 10\n    }\n}\n"
+        );
+    }
+    #[test]
+    fn test_apply_overview() {
+        let source = "overview {\n    zoom 0.5\n}\n";
+        let mut config = ConfigDocument {
+            doc: KdlDocument::parse_v1(source).unwrap(),
+            path: std::path::PathBuf::from("/tmp/test.kdl"),
+            source: source.to_string(),
+        };
+        let original = parse_appearance(&config);
+        let mut settings = original.clone();
+        settings.overview.zoom_percent = 35;
+        settings.overview.shadow_color = ColorValue::Solid("#00000080".to_string());
+
+        apply_appearance(&mut config, &original, &settings);
+        crate::config::kdl_format::ensure_v1_new(&mut config.doc);
+        assert_eq!(
+            config.doc.to_string(),
+            "overview {\n    zoom 0.35\n    workspace-shadow {\n        color \"#00000080\"\n    }\n}\n"
+        );
+        assert_eq!(
+            appearance_field_kdl(&settings, AppearanceField::OverviewZoom),
+            "overview {\n    zoom 0.35\n}"
+        );
+        assert!(appearance_field_kdl(&settings, AppearanceField::OverviewShadowColor)
+            .contains("workspace-shadow {\n        softness 40"));
+    }
 }
@@ -0,0 +1,86 @@
+use anyhow::{Context, Result};
+use kdl::KdlDocument;
+use std::path::PathBuf;
+
+use super::keybindings_parser::parse_keybindings;
+use crate::model::{ConfigDocument, Keybinding};
+
+/// Bundled snapshots of niri's default binds, oldest first, keyed by the
+/// niri release they were taken from
+const SNAPSHOTS: &[(&str, &str)] = &[("25.11", include_str!("default_binds/25.11.kdl"))];
+
+/// Pick the newest snapshot not newer than the running niri version.
+/// Unknown or unparsable versions get the newest snapshot.
+pub(super) fn snapshot_for_version(
+    snapshots: &[(&'static str, &'static str)],
+    version: Option<&str>,
+) -> (&'static str, &'static str) {
+    let newest = snapshots[snapshots.len() - 1];
+    let Some(running) = version.and_then(parse_release) else {
+        return newest;
+    };
+    snapshots
+        .iter()
+        .rev()
+        .find(|(release, _)| parse_release(release).is_some_and(|r| r <= running))
+        .copied()
+        .unwrap_or(snapshots[0])
+}
+
+/// Parse the leading "YY.MM" of a niri version string (e.g., "25.11 (b35bcae)")
+fn parse_release(version: &str) -> Option<(u32, u32)> {
+    let release = version.split_whitespace().next()?;
+    let mut parts = release.split(['.', '-']);
+    let year = parts.next()?.parse().ok()?;
+    let month = parts.next()?.parse().ok()?;
+    Some((year, month))
+}
+
+/// Load niri's default binds for the given niri version.
+/// Returns the snapshot release used and the bindings, which carry no
+/// config index or source location since they are not in the user's file.
+pub fn load_default_binds(version: Option<&str>) -> Result<(String, Vec<Keybinding>)> {
+    let (release, kdl) = snapshot_for_version(SNAPSHOTS, version);
+    let doc = KdlDocument::parse_v1(kdl)
+        .with_context(|| format!("Invalid bundled default binds for niri {release}"))?;
+    let snapshot = ConfigDocument {
+        doc,
+        path: PathBuf::new(),
+        source: kdl.to_string(),
+    };
+
+    let bindings = parse_keybindings(&snapshot)
+        .into_iter()
+        .map(|mut binding| {
+            binding.kdl_index = None;
+            binding.source = None;
+            binding
+        })
+        .collect();
+    Ok((release.to_string(), bindings))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_selection() {
+        assert_eq!(parse_release("25.11 (b35bcae)"), Some((25, 11)));
+        assert_eq!(parse_release("unknown"), None);
+        assert_eq!(snapshot_for_version(SNAPSHOTS, Some("26.04")).0, "25.11");
+        assert_eq!(snapshot_for_version(SNAPSHOTS, None).0, "25.11");
+        // Older than every snapshot falls back to the oldest one
+        assert_eq!(snapshot_for_version(SNAPSHOTS, Some("0.1.10")).0, SNAPSHOTS[0].0);
+    }
+
+    #[test]
+    fn test_load_default_binds() {
+        let (release, bindings) = load_default_binds(Some("25.11")).unwrap();
+        assert_eq!(release, "25.11");
+        assert!(bindings.len() > 100);
+        let close = bindings.iter().find(|b| b.combo() == "Mod+Q").unwrap();
+        assert_eq!(close.action.short_description(), "close-window");
+        assert!(bindings.iter().all(|b| b.kdl_index.is_none()));
+    }
+}
@@ -0,0 +1,42 @@
+use anyhow::{Context, Result};
+use kdl::KdlDocument;
+use std::path::PathBuf;
+
+use super::appearance_parser::parse_appearance;
+use super::default_binds::snapshot_for_version;
+use crate::model::{AppearanceSettings, ConfigDocument};
+
+/// Bundled snapshots of the layout values niri falls back to when the config
+/// leaves them out, oldest first. niri's IPC doesn't expose the loaded
+/// config, so these are taken from each release's config defaults.
+const SNAPSHOTS: &[(&str, &str)] = &[("25.11", include_str!("default_layout/25.11.kdl"))];
+
+/// Load niri's default layout settings for the given niri version.
+/// Returns the snapshot release used and the settings.
+pub fn load_default_appearance(version: Option<&str>) -> Result<(String, AppearanceSettings)> {
+    let (release, kdl) = snapshot_for_version(SNAPSHOTS, version);
+    let doc = KdlDocument::parse_v1(kdl)
+        .with_context(|| format!("Invalid bundled default layout for niri {release}"))?;
+    let snapshot = ConfigDocument {
+        doc,
+        path: PathBuf::new(),
+        source: kdl.to_string(),
+    };
+    Ok((release.to_string(), parse_appearance(&snapshot)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_default_appearance() {
+        let (release, defaults) = load_default_appearance(Some("25.11 (b35bcae)")).unwrap();
+        assert_eq!(release, "25.11");
+        assert_eq!(defaults.gaps, 16);
+        assert!(defaults.border.off);
+        assert!(!defaults.shadow.on);
+        // The fallback used before the snapshot loads should match it
+        assert_eq!(defaults, AppearanceSettings::default());
+    }
+}
@@ -0,0 +1,157 @@
+//! Managed mode: nirikiri keeps the blocks it edits in a nirikiri.kdl
+//! fragment next to the config, and the config includes it. The
+//! hand-written config is only touched once, to add the include.
+//!
+//! A block belongs to nirikiri once the fragment has it. Blocks the fragment
+//! doesn't have yet are copied from the config when it is loaded, and the
+//! copy takes over when it is first saved.
+
+use anyhow::Result;
+use kdl::{KdlDocument, KdlEntry, KdlNode};
+use std::path::{Path, PathBuf};
+
+use super::kdl_format::format_node;
+use super::kdl_value::string;
+use super::parser::load_config;
+use crate::model::ConfigDocument;
+
+/// File name of the fragment, next to config.kdl
+pub const FRAGMENT_FILE: &str = "nirikiri.kdl";
+
+/// Top-level blocks nirikiri edits and so may own
+const MANAGED_BLOCKS: &[&str] = &["layout", "binds", "output", "xwayland-satellite", "clipboard", "overview"];
+
+const HEADER: &str = "// Managed by nirikiri and included from config.kdl.\n\
+                      // Blocks here take precedence over the same blocks in config.kdl.\n";
+
+/// Path of the fragment for the config at `config_path`
+pub fn fragment_path(config_path: &Path) -> PathBuf {
+    config_path.with_file_name(FRAGMENT_FILE)
+}
+
+/// Load the document edits go to: the niri config, or its fragment in
+/// managed mode
+pub fn load_editable_config(managed: bool) -> Result<ConfigDocument> {
+    let config = load_config()?;
+    if managed {
+        load_fragment(&config)
+    } else {
+        Ok(config)
+    }
+}
+
+/// Load the fragment for `config`, adopting the managed blocks it doesn't
+/// own yet
+pub fn load_fragment(config: &ConfigDocument) -> Result<ConfigDocument> {
+    let path = fragment_path(&config.path);
+    let mut fragment = if path.exists() {
+        ConfigDocument::load(path)?
+    } else {
+        ConfigDocument { doc: KdlDocument::parse_v1(HEADER)?, path, source: String::new() }
+    };
+    if !adopt_blocks(&mut fragment.doc, &config.doc).is_empty() {
+        // Re-parse so the copies' spans point past the end of the fragment's
+        // source instead of into config.kdl
+        fragment.doc = KdlDocument::parse_v1(&fragment.doc.to_string())?;
+    }
+    Ok(fragment)
+}
+
+/// Copy managed blocks from `config` that `fragment` doesn't have.
+/// Returns the keys of the copied blocks.
+fn adopt_blocks(fragment: &mut KdlDocument, config: &KdlDocument) -> Vec<String> {
+    let owned: Vec<String> = fragment.nodes().iter().map(block_key).collect();
+    let mut adopted = Vec::new();
+    for node in config.nodes() {
+        let key = block_key(node);
+        if MANAGED_BLOCKS.contains(&node.name().value()) && !owned.contains(&key) && !adopted.contains(&key) {
+            fragment.nodes_mut().push(node.clone());
+            adopted.push(key);
+        }
+    }
+    adopted
+}
+
+/// Name of a top-level block, with the output name for outputs
+fn block_key(node: &KdlNode) -> String {
+    match node.get(0).and_then(|v| v.as_string()) {
+        Some(name) if node.name().value() == "output" => format!("output \"{name}\""),
+        _ => node.name().value().to_string(),
+    }
+}
+
+/// Blocks the fragment owns, in file order
+pub fn owned_blocks(fragment: &ConfigDocument) -> Vec<String> {
+    fragment
+        .doc
+        .nodes()
+        .iter()
+        .filter(|n| MANAGED_BLOCKS.contains(&n.name().value()))
+        .map(block_key)
+        .collect()
+}
+
+/// Whether the config has an `include` of the fragment
+pub fn includes_fragment(config: &ConfigDocument) -> bool {
+    config.doc.nodes().iter().any(|n| {
+        n.name().value() == "include"
+            && n.get(0)
+                .and_then(|v| v.as_string())
+                .is_some_and(|file| Path::new(file).file_name() == Some(FRAGMENT_FILE.as_ref()))
+    })
+}
+
+/// Add the include to the end of the config, so the fragment's blocks win.
+/// Returns false if it was already there.
+pub fn ensure_included(config: &mut ConfigDocument) -> Result<bool> {
+    if includes_fragment(config) {
+        return Ok(false);
+    }
+    let mut include = KdlNode::new("include");
+    include.push(KdlEntry::new(string(FRAGMENT_FILE)));
+    format_node(&mut include, 0);
+    config.doc.nodes_mut().push(include);
+    config.save()?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document(source: &str, path: PathBuf) -> ConfigDocument {
+        ConfigDocument { doc: KdlDocument::parse_v1(source).unwrap(), path, source: source.to_string() }
+    }
+
+    #[test]
+    fn test_fragment() {
+        let dir = std::env::temp_dir().join(format!("nirikiri-fragment-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let source = "input { }\nlayout { gaps 8; }\noutput \"DP-1\" { scale 2; }\nbinds { Mod+T { spawn \"foot\"; }; }\n";
+        let mut config = document(source, dir.join("config.kdl"));
+        std::fs::write(&config.path, source).unwrap();
+
+        // Everything managed is copied into a new fragment; input stays put
+        let mut fragment = load_fragment(&config).unwrap();
+        assert_eq!(owned_blocks(&fragment), vec!["layout", "output \"DP-1\"", "binds"]);
+        assert_eq!(fragment.path, dir.join(FRAGMENT_FILE));
+        assert!(!fragment.changed_on_disk().unwrap());
+        assert!(fragment.locate(&fragment.doc.nodes()[0]).is_none());
+
+        // Blocks the fragment already has are left to it
+        fragment.doc.nodes_mut().retain(|n| n.name().value() != "binds");
+        fragment.save().unwrap();
+        std::fs::write(&fragment.path, "layout { gaps 24; }\n").unwrap();
+        let fragment = load_fragment(&config).unwrap();
+        assert!(fragment.doc.to_string().starts_with("layout { gaps 24; }\n"));
+        assert_eq!(owned_blocks(&fragment).len(), 3);
+
+        assert!(!includes_fragment(&config));
+        assert!(ensure_included(&mut config).unwrap());
+        assert!(!ensure_included(&mut config).unwrap());
+        let saved = std::fs::read_to_string(&config.path).unwrap();
+        assert!(saved.starts_with(source) && saved.trim_end().ends_with("include \"nirikiri.kdl\""));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+
+use crate::model::{ClipboardSettings, ConfigDocument, GeneralField, GeneralSettings, SourceLocation, XwaylandSettings};
+
+/// Parse the General category's settings from their top-level blocks
+pub fn parse_general(config: &ConfigDocument) -> GeneralSettings {
+    let mut settings = GeneralSettings::default();
+
+    if let Some(node) = config.doc.nodes().iter().find(|n| n.name().value() == "xwayland-satellite") {
+        settings.xwayland = parse_xwayland(node);
+    }
+    if let Some(node) = config.doc.nodes().iter().find(|n| n.name().value() == "clipboard") {
+        settings.clipboard = ClipboardSettings {
+            disable_primary: node.children().is_some_and(|c| c.get("disable-primary").is_some()),
+        };
+    }
+
+    settings
+}
+
+/// Find where each General field is set
+pub fn locate_general_fields(config: &ConfigDocument) -> HashMap<GeneralField, SourceLocation> {
+    let mut locations = HashMap::new();
+    for block in config.doc.nodes() {
+        for node in block.children().map(|c| c.nodes()).unwrap_or_default() {
+            let field = GeneralField::all()
+                .iter()
+                .find(|f| f.block() == block.name().value() && f.name() == node.name().value());
+            if let (Some(&field), Some(loc)) = (field, config.locate(node)) {
+                locations.insert(field, loc);
+            }
+        }
+    }
+    locations
+}
+
+fn parse_xwayland(node: &kdl::KdlNode) -> XwaylandSettings {
+    let mut settings = XwaylandSettings::default();
+
+    if let Some(children) = node.children() {
+        for child in children.nodes() {
+            match child.name().value() {
+                "off" => settings.off = true,
+                "path" => settings.path = child.get(0).and_then(|v| v.as_string()).map(str::to_string),
+                _ => {}
+            }
+        }
+    }
+
+    settings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_test_config(content: &str) -> ConfigDocument {
+        ConfigDocument {
+            doc: content.parse().unwrap(),
+            path: std::path::PathBuf::new(),
+            source: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_xwayland() {
+        let config = parse_test_config(r#"
+            layout { gaps 8; }
+            xwayland-satellite {
+                path "~/.cargo/bin/xwayland-satellite"
+            }
+        "#);
+        let settings = parse_general(&config);
+        assert!(!settings.xwayland.off);
+        assert_eq!(settings.xwayland.path.as_deref(), Some("~/.cargo/bin/xwayland-satellite"));
+        let locations = locate_general_fields(&config);
+        assert_eq!(locations[&GeneralField::XwaylandPath].line, 4);
+        assert!(!locations.contains_key(&GeneralField::XwaylandOff));
+
+        let config = parse_test_config("xwayland-satellite { off; }");
+        let settings = parse_general(&config);
+        assert!(settings.xwayland.off);
+        assert_eq!(settings.xwayland.path, None);
+        assert!(!settings.clipboard.disable_primary);
+    }
+}
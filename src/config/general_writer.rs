@@ -0,0 +1,114 @@
+use anyhow::Result;
+use kdl::KdlDocument;
+
+use super::appearance_writer::{changed, remove_node, set_top_level_flag, update_or_add_simple_value, update_top_level_block};
+use super::kdl_value::string;
+use super::template_writer::append_workspace_spawn;
+use crate::model::{ClipboardSettings, ConfigDocument, GeneralSettings, WorkspaceSpawn, XwaylandSettings};
+
+/// Write the General settings to the config document, touching only the
+/// nodes whose value differs from `original` (the settings as loaded), and
+/// append the staged workspace apps. Everything lands in one save.
+pub fn write_general(
+    config: &mut ConfigDocument,
+    original: &GeneralSettings,
+    settings: &GeneralSettings,
+    spawns: &[WorkspaceSpawn],
+) -> Result<()> {
+    apply_general(config, original, settings);
+    for spawn in spawns {
+        append_workspace_spawn(config, spawn);
+    }
+    config.save()
+}
+
+/// Update the General blocks in memory without saving
+pub fn apply_general(config: &mut ConfigDocument, original: &GeneralSettings, settings: &GeneralSettings) {
+    if original.xwayland != settings.xwayland {
+        update_xwayland(&mut config.doc, &original.xwayland, &settings.xwayland);
+    }
+    if original.clipboard != settings.clipboard {
+        update_clipboard(&mut config.doc, &original.clipboard, &settings.clipboard);
+    }
+}
+
+fn update_xwayland(doc: &mut KdlDocument, old: &XwaylandSettings, settings: &XwaylandSettings) {
+    update_top_level_block(doc, "xwayland-satellite", |children| {
+        if changed(Some(&old.off), &settings.off) {
+            set_top_level_flag(children, "off", settings.off);
+        }
+        if changed(Some(&old.path), &settings.path) {
+            match &settings.path {
+                Some(path) => update_or_add_simple_value(children, "path", string(path.clone()), 1),
+                None => remove_node(children, "path"),
+            }
+        }
+    });
+}
+
+fn update_clipboard(doc: &mut KdlDocument, old: &ClipboardSettings, settings: &ClipboardSettings) {
+    update_top_level_block(doc, "clipboard", |children| {
+        if changed(Some(&old.disable_primary), &settings.disable_primary) {
+            set_top_level_flag(children, "disable-primary", settings.disable_primary);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::general_parser::parse_general;
+
+    #[test]
+    fn test_apply_xwayland() {
+        let source = "layout {\n    gaps 8\n}\n";
+        let mut config = ConfigDocument {
+            doc: KdlDocument::parse_v1(source).unwrap(),
+            path: std::path::PathBuf::from("/tmp/test.kdl"),
+            source: source.to_string(),
+        };
+        let original = parse_general(&config);
+        let mut settings = original.clone();
+        settings.xwayland.path = Some("/opt/xwayland-satellite".to_string());
+
+        apply_general(&mut config, &original, &settings);
+        crate::config::kdl_format::ensure_v1_new(&mut config.doc);
+        assert_eq!(
+            config.doc.to_string(),
+            "layout {\n    gaps 8\n}\nxwayland-satellite {\n    path \"/opt/xwayland-satellite\"\n}\n"
+        );
+
+        // Turning it off and back to the default path leaves only off
+        let original = settings.clone();
+        settings.xwayland = XwaylandSettings { off: true, path: None };
+        apply_general(&mut config, &original, &settings);
+        crate::config::kdl_format::ensure_v1_new(&mut config.doc);
+        assert_eq!(config.doc.to_string(), "layout {\n    gaps 8\n}\nxwayland-satellite {\n    off\n}\n");
+
+        // With nothing left in it the block goes away
+        apply_general(&mut config, &settings, &GeneralSettings::default());
+        assert_eq!(config.doc.to_string(), "layout {\n    gaps 8\n}\n");
+    }
+
+    #[test]
+    fn test_apply_clipboard() {
+        let source = "// keep me\nclipboard {\n    disable-primary\n}\n";
+        let mut config = ConfigDocument {
+            doc: KdlDocument::parse_v1(source).unwrap(),
+            path: std::path::PathBuf::from("/tmp/test.kdl"),
+            source: source.to_string(),
+        };
+        let original = parse_general(&config);
+        assert!(original.clipboard.disable_primary);
+        let mut settings = original.clone();
+        settings.clipboard.disable_primary = false;
+
+        // The commented block stays, empty
+        apply_general(&mut config, &original, &settings);
+        assert_eq!(config.doc.to_string(), "// keep me\nclipboard {}\n");
+
+        apply_general(&mut config, &settings, &original);
+        crate::config::kdl_format::ensure_v1_new(&mut config.doc);
+        assert_eq!(config.doc.to_string(), "// keep me\nclipboard {\n    disable-primary\n}\n");
+    }
+}
@@ -0,0 +1,64 @@
+use anyhow::{bail, Context, Result};
+use std::ffi::OsStr;
+use std::path::Path;
+use std::process::{Command, Output};
+
+/// Commit the config file if it lives in a git work tree.
+/// Returns false when there is no repository or the file didn't change.
+pub fn commit_config(path: &Path, message: &str) -> Result<bool> {
+    let dir = path.parent().context("Config path has no parent directory")?;
+    let file = path.file_name().context("Config path has no file name")?;
+
+    let inside = git(dir, &["rev-parse", "--is-inside-work-tree"], None);
+    if !inside.is_ok_and(|out| out.status.success()) {
+        return Ok(false);
+    }
+
+    run(dir, &["add", "--"], file)?;
+    let staged = git(dir, &["diff", "--cached", "--quiet", "--"], Some(file))?;
+    if staged.status.success() {
+        return Ok(false);
+    }
+    run(dir, &["commit", "--quiet", "-m", message, "--"], file)?;
+    Ok(true)
+}
+
+/// Build the commit message from per-change summaries
+pub fn commit_message(summary: &[String]) -> String {
+    if summary.is_empty() {
+        "nirikiri: update config".to_string()
+    } else {
+        format!("nirikiri: {}", summary.join(", "))
+    }
+}
+
+fn git(dir: &Path, args: &[&str], file: Option<&OsStr>) -> Result<Output> {
+    let mut command = Command::new("git");
+    command.current_dir(dir).args(args);
+    if let Some(file) = file {
+        command.arg(file);
+    }
+    command.output().context("Failed to run git")
+}
+
+fn run(dir: &Path, args: &[&str], file: &OsStr) -> Result<()> {
+    let out = git(dir, args, Some(file))?;
+    if !out.status.success() {
+        bail!("git {}: {}", args[0], String::from_utf8_lossy(&out.stderr).trim());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commit_message() {
+        assert_eq!(commit_message(&[]), "nirikiri: update config");
+        assert_eq!(
+            commit_message(&["gaps 16→24".to_string(), "add Mod+T spawn alacritty".to_string()]),
+            "nirikiri: gaps 16→24, add Mod+T spawn alacritty"
+        );
+    }
+}
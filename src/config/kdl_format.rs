@@ -0,0 +1,64 @@
+use kdl::{FormatConfig, KdlDocument, KdlNode, KdlNodeFormat};
+
+/// Format a new or rewritten node for its place `depth` blocks deep.
+///
+/// Only this node is touched, so saving a small change leaves the rest of
+/// the file byte-for-byte as it was. Leading comments the node carries are kept.
+pub fn format_node(node: &mut KdlNode, depth: usize) {
+    if node.format().is_none() {
+        node.set_format(KdlNodeFormat::default());
+    }
+    node.autoformat_config(&FormatConfig::builder().indent_level(depth).build());
+}
+
+/// Convert nodes and entries created since parsing to KDL v1 for niri.
+///
+/// Parsed content already carries its v1 representation, so it is skipped:
+/// converting it again is slow on large files and re-escapes every `/` in
+/// existing strings.
+pub fn ensure_v1_new(doc: &mut KdlDocument) {
+    for node in doc.nodes_mut() {
+        if node.name().repr().is_none() {
+            // Only the name is new when a parsed node was renamed in place
+            let mut renamed = KdlNode::new(node.name().value());
+            renamed.ensure_v1();
+            node.set_name(renamed.name().clone());
+        }
+        for entry in node.entries_mut() {
+            if entry.format().is_none() {
+                entry.ensure_v1();
+            }
+        }
+        if let Some(children) = node.children_mut() {
+            ensure_v1_new(children);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kdl::{KdlEntry, KdlValue};
+
+    #[test]
+    fn test_ensure_v1_new_leaves_parsed_content() {
+        let source = "binds {\n    Mod+T { spawn \"/usr/bin/foot\" r\"a/b\"; }\n}\n";
+        let mut doc = KdlDocument::parse_v1(source).unwrap();
+        let binds = doc.nodes_mut()[0].children_mut().as_mut().unwrap();
+        let mut node = KdlNode::new("Mod+Q");
+        let mut action = KdlNode::new("spawn");
+        action.push(KdlEntry::new(KdlValue::String("never".to_string())));
+        action.push(KdlEntry::new(KdlValue::Bool(true)));
+        let mut actions = KdlDocument::new();
+        actions.nodes_mut().push(action);
+        node.set_children(actions);
+        format_node(&mut node, 1);
+        binds.nodes_mut().push(node);
+
+        ensure_v1_new(&mut doc);
+        assert_eq!(
+            doc.to_string(),
+            "binds {\n    Mod+T { spawn \"/usr/bin/foot\" r\"a/b\"; }\n    Mod+Q {\n        spawn \"never\" true\n    }\n}\n"
+        );
+    }
+}
@@ -0,0 +1,124 @@
+//! Typed KDL values: how numbers, flags, strings and colors are written to
+//! and read from the config, so every writer and parser agrees on them.
+
+use kdl::{KdlNode, KdlValue};
+
+pub fn int(value: impl Into<i128>) -> KdlValue {
+    KdlValue::Integer(value.into())
+}
+
+/// A float; whole numbers keep their `.0` so niri still reads a float
+pub fn float(value: f64) -> KdlValue {
+    KdlValue::Float(value)
+}
+
+pub fn boolean(value: bool) -> KdlValue {
+    KdlValue::Bool(value)
+}
+
+pub fn string(value: impl Into<String>) -> KdlValue {
+    KdlValue::String(value.into())
+}
+
+/// A color as niri takes it: `"#rrggbb"`, `"#rrggbbaa"` or a CSS color
+pub fn color(value: &str) -> KdlValue {
+    string(value)
+}
+
+/// An integer that fits `T`; out-of-range values read as missing rather
+/// than wrapping around
+pub fn as_int<T: TryFrom<i128>>(value: &KdlValue) -> Option<T> {
+    value.as_integer().and_then(|n| T::try_from(n).ok())
+}
+
+/// A float, where an integer such as `1` counts too
+pub fn as_float(value: &KdlValue) -> Option<f64> {
+    value.as_float().or_else(|| value.as_integer().map(|n| n as f64))
+}
+
+pub fn as_bool(value: &KdlValue) -> Option<bool> {
+    value.as_bool()
+}
+
+/// A node's color: a string argument, or the four 0-255 integers older
+/// niri configs use (`active-color 255 200 127 255`), as `#rrggbbaa`
+pub fn node_color(node: &KdlNode) -> Option<String> {
+    let value = node.get(0)?;
+    if let Some(color) = value.as_string() {
+        return Some(color.to_string());
+    }
+    let channels: Vec<u8> = (0..4).map(|i| node.get(i).and_then(as_int::<u8>)).collect::<Option<_>>()?;
+    Some(format!("#{:02x}{:02x}{:02x}{:02x}", channels[0], channels[1], channels[2], channels[3]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::kdl_format::ensure_v1_new;
+    use kdl::{KdlDocument, KdlEntry};
+
+    /// Print `value` as niri would read it
+    fn written(value: KdlValue) -> String {
+        let mut doc = KdlDocument::new();
+        let mut node = KdlNode::new("n");
+        node.push(KdlEntry::new(value));
+        doc.nodes_mut().push(node);
+        ensure_v1_new(&mut doc);
+        doc.to_string().trim().trim_start_matches("n ").to_string()
+    }
+
+    fn parsed(source: &str) -> KdlNode {
+        KdlDocument::parse_v1(source).unwrap().nodes()[0].clone()
+    }
+
+    #[test]
+    fn test_write_values() {
+        assert_eq!(written(int(16i32)), "16");
+        assert_eq!(written(int(-8i32)), "-8");
+        assert_eq!(written(int(150u32)), "150");
+        assert_eq!(written(int(i64::MAX)), "9223372036854775807");
+        assert_eq!(written(float(1.25)), "1.25");
+        assert_eq!(written(float(2.0)), "2.0");
+        assert_eq!(written(float(0.35)), "0.35");
+        assert_eq!(written(boolean(true)), "true");
+        assert_eq!(written(boolean(false)), "false");
+        assert_eq!(written(string("on-overflow")), "\"on-overflow\"");
+        assert_eq!(written(string(String::from("a \"b\""))), r#""a \"b\"""#);
+        assert_eq!(written(color("#7fc8ff")), "\"#7fc8ff\"");
+    }
+
+    #[test]
+    fn test_read_values() {
+        let node = parsed("n 16 -8 1.5 true \"x\" 300 2");
+        assert_eq!(node.get(0).and_then(as_int::<i32>), Some(16));
+        assert_eq!(node.get(1).and_then(as_int::<i32>), Some(-8));
+        // Negative or too large for the type, not a number at all
+        assert_eq!(node.get(1).and_then(as_int::<u32>), None);
+        assert_eq!(node.get(5).and_then(as_int::<u8>), None);
+        assert_eq!(node.get(2).and_then(as_int::<i32>), None);
+        assert_eq!(node.get(4).and_then(as_int::<i32>), None);
+
+        assert_eq!(node.get(2).and_then(as_float), Some(1.5));
+        assert_eq!(node.get(6).and_then(as_float), Some(2.0));
+        assert_eq!(node.get(3).and_then(as_float), None);
+
+        assert_eq!(node.get(3).and_then(as_bool), Some(true));
+        assert_eq!(node.get(0).and_then(as_bool), None);
+    }
+
+    #[test]
+    fn test_node_color() {
+        assert_eq!(node_color(&parsed("c \"#7fc8ff\"")), Some("#7fc8ff".to_string()));
+        assert_eq!(node_color(&parsed("c \"rgb(255 0 0)\"")), Some("rgb(255 0 0)".to_string()));
+        assert_eq!(node_color(&parsed("c 255 200 127 255")), Some("#ffc87fff".to_string()));
+        // Too few channels, or out of range
+        assert_eq!(node_color(&parsed("c 255 200 127")), None);
+        assert_eq!(node_color(&parsed("c 256 0 0 255")), None);
+        assert_eq!(node_color(&parsed("c")), None);
+
+        // Written colors read back the same
+        let mut node = KdlNode::new("c");
+        node.push(KdlEntry::new(color("#112233")));
+        assert_eq!(node_color(&node), Some("#112233".to_string()));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::config::kdl_value::{as_bool, as_int};
 use crate::model::{
     BindingAction, BindingArg, BindingProperties, ConfigDocument, Keybinding, Modifiers,
 };
@@ -11,7 +12,8 @@ pub fn parse_keybindings(config: &ConfigDocument) -> Vec<Keybinding> {
         if node.name().value() == "binds" {
             if let Some(children) = node.children() {
                 for (idx, bind_node) in children.nodes().iter().enumerate() {
-                    if let Some(binding) = parse_single_binding(bind_node, idx) {
+                    if let Some(mut binding) = parse_single_binding(bind_node, idx) {
+                        binding.source = config.locate(bind_node);
                         bindings.push(binding);
                     }
                 }
@@ -23,6 +25,60 @@ pub fn parse_keybindings(config: &ConfigDocument) -> Vec<Keybinding> {
     bindings
 }
 
+/// Parse bind nodes on their own, such as a filled-in binding template
+pub fn parse_bind_nodes(source: &str) -> anyhow::Result<Vec<Keybinding>> {
+    let doc = kdl::KdlDocument::parse_v1(source)?;
+    doc.nodes()
+        .iter()
+        .map(|node| {
+            let mut binding = parse_single_binding(node, 0)
+                .ok_or_else(|| anyhow::anyhow!("{} has no action nirikiri understands", node.name().value()))?;
+            binding.kdl_index = None;
+            Ok(binding)
+        })
+        .collect()
+}
+
+/// Describe the parts of a bind nirikiri doesn't model, which an edit keeps
+/// as they are: unknown properties, arguments on the bind itself, child
+/// nodes after the action and properties of the action node.
+pub fn unmodeled_parts(config: &ConfigDocument, index: usize) -> Vec<String> {
+    let Some(node) = config
+        .doc
+        .nodes()
+        .iter()
+        .find(|n| n.name().value() == "binds")
+        .and_then(|binds| binds.children()?.nodes().get(index))
+    else {
+        return Vec::new();
+    };
+
+    let mut parts = Vec::new();
+    for entry in node.entries() {
+        match entry.name() {
+            Some(name) if !["repeat", "cooldown-ms", "allow-when-locked"].contains(&name.value()) => {
+                parts.push(format!("property {}", name.value()));
+            }
+            Some(_) => {}
+            None => parts.push(format!("argument {}", entry.value())),
+        }
+    }
+    if let Some(children) = node.children() {
+        if let Some(action) = children.nodes().first() {
+            for entry in action.entries() {
+                if let Some(name) = entry.name() {
+                    parts.push(format!("{} property {}", action.name().value(), name.value()));
+                }
+            }
+        }
+        let extra: Vec<&str> = children.nodes().iter().skip(1).map(|n| n.name().value()).collect();
+        if !extra.is_empty() {
+            parts.push(format!("{} more action node(s): {}", extra.len(), extra.join(", ")));
+        }
+    }
+    parts
+}
+
 fn parse_single_binding(node: &kdl::KdlNode, index: usize) -> Option<Keybinding> {
     // Node name is the key combo (e.g., "Mod+T", "XF86AudioRaiseVolume")
     let combo = node.name().value();
@@ -47,6 +103,7 @@ fn parse_single_binding(node: &kdl::KdlNode, index: usize) -> Option<Keybinding>
         properties,
         action,
         kdl_index: Some(index),
+        source: None,
     })
 }
 
@@ -57,17 +114,17 @@ fn parse_binding_properties(node: &kdl::KdlNode) -> BindingProperties {
         if let Some(name) = entry.name() {
             match name.value() {
                 "repeat" => {
-                    if let Some(val) = entry.value().as_bool() {
+                    if let Some(val) = as_bool(entry.value()) {
                         props.repeat = Some(val);
                     }
                 }
                 "cooldown-ms" => {
-                    if let Some(val) = entry.value().as_integer() {
-                        props.cooldown_ms = Some(val as u32);
+                    if let Some(val) = as_int(entry.value()) {
+                        props.cooldown_ms = Some(val);
                     }
                 }
                 "allow-when-locked" => {
-                    if let Some(val) = entry.value().as_bool() {
+                    if let Some(val) = as_bool(entry.value()) {
                         props.allow_when_locked = Some(val);
                     }
                 }
@@ -80,8 +137,11 @@ fn parse_binding_properties(node: &kdl::KdlNode) -> BindingProperties {
 }
 
 fn parse_binding_action(node: &kdl::KdlNode) -> Option<BindingAction> {
-    let children = node.children()?;
-    let action_node = children.nodes().first()?;
+    parse_action_node(node.children()?.nodes().first()?)
+}
+
+/// Parse the action node of a bind (the first child of its block)
+pub(super) fn parse_action_node(action_node: &kdl::KdlNode) -> Option<BindingAction> {
     let action_name = action_node.name().value();
 
     match action_name {
@@ -117,11 +177,11 @@ fn parse_binding_action(node: &kdl::KdlNode) -> Option<BindingAction> {
                 Some(BindingAction::Simple(action_name.to_string()))
             } else {
                 let arg = &entries[0];
-                let binding_arg = if let Some(n) = arg.value().as_integer() {
-                    BindingArg::Number(n as i64)
+                let binding_arg = if let Some(n) = as_int(arg.value()) {
+                    BindingArg::Number(n)
                 } else if let Some(s) = arg.value().as_string() {
                     BindingArg::String(s.to_string())
-                } else if let Some(b) = arg.value().as_bool() {
+                } else if let Some(b) = as_bool(arg.value()) {
                     BindingArg::Bool(b)
                 } else {
                     return Some(BindingAction::Simple(action_name.to_string()));
@@ -150,4 +210,30 @@ mod tests {
         assert!(!mods.shift);
         assert_eq!(key, "XF86AudioRaiseVolume");
     }
+
+    #[test]
+    fn test_binding_source_location() {
+        let content = "// comment\nbinds {\n    // close\n    Mod+Q { close-window; }\n}\n";
+        let config = ConfigDocument {
+            doc: kdl::KdlDocument::parse_v1(content).unwrap(),
+            path: std::path::PathBuf::from("config.kdl"),
+            source: content.to_string(),
+        };
+        let bindings = parse_keybindings(&config);
+        assert_eq!(bindings[0].source.as_ref().unwrap().to_string(), "config.kdl:4");
+    }
+
+    #[test]
+    fn test_unmodeled_parts() {
+        let content = "binds {\n    Mod+T hotkey-overlay-title=\"Terminal\" { spawn \"foot\"; }\n    Mod+Q repeat=false {\n        close-window\n        // then\n        focus-column-left\n    }\n    Mod+W { close-window; }\n}\n";
+        let config = ConfigDocument {
+            doc: kdl::KdlDocument::parse_v1(content).unwrap(),
+            path: std::path::PathBuf::from("config.kdl"),
+            source: content.to_string(),
+        };
+        assert_eq!(unmodeled_parts(&config, 0), vec!["property hotkey-overlay-title"]);
+        assert_eq!(unmodeled_parts(&config, 1), vec!["1 more action node(s): focus-column-left"]);
+        assert!(unmodeled_parts(&config, 2).is_empty());
+        assert!(unmodeled_parts(&config, 9).is_empty());
+    }
 }
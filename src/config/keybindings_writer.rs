@@ -1,15 +1,25 @@
-use anyhow::{Context, Result};
-use kdl::{KdlDocument, KdlEntry, KdlNode, KdlValue};
+use anyhow::{bail, Context, Result};
+use kdl::{KdlDocument, KdlEntry, KdlNode};
 
+use super::kdl_format::format_node;
+use super::kdl_value::{boolean, int, string};
+use super::keybindings_parser::parse_action_node;
 use crate::model::{
     BindingAction, BindingArg, ConfigDocument, Keybinding, KeybindingChange,
 };
 
-/// Apply keybinding changes to the config document
+/// Apply keybinding changes to the config document and save it
 pub fn write_keybindings(
     config: &mut ConfigDocument,
     changes: &[KeybindingChange],
 ) -> Result<()> {
+    apply_keybindings(config, changes)?;
+    config.save()
+}
+
+/// Apply keybinding changes to the binds block in memory, reformatting only
+/// the nodes that were added or rewritten
+pub fn apply_keybindings(config: &mut ConfigDocument, changes: &[KeybindingChange]) -> Result<()> {
     // Find the binds block
     let binds_idx = config
         .doc
@@ -21,52 +31,124 @@ pub fn write_keybindings(
     let binds_node = config.doc.nodes_mut().get_mut(binds_idx).unwrap();
 
     // Ensure children exist
-    if binds_node.children().is_none() {
+    let created = binds_node.children().is_none();
+    if created {
         binds_node.set_children(KdlDocument::new());
     }
 
     let children = binds_node.children_mut().as_mut().unwrap();
 
-    // Process changes in reverse order (deletes first, then modifies, then adds)
-    // This preserves indices during deletion
+    // Rewrite nodes while every index still points at the node it was staged
+    // against, then delete from the end so earlier indices stay valid, then
+    // append new binds in the order they were staged
     let mut sorted_changes: Vec<_> = changes.iter().collect();
-    sorted_changes.sort_by(|a, b| {
-        match (a, b) {
-            (KeybindingChange::Delete(i1), KeybindingChange::Delete(i2)) => i2.cmp(i1), // Delete in reverse order
-            (KeybindingChange::Delete(_), _) => std::cmp::Ordering::Less,
-            (_, KeybindingChange::Delete(_)) => std::cmp::Ordering::Greater,
-            (KeybindingChange::Modify { index: i1, .. }, KeybindingChange::Modify { index: i2, .. }) => i1.cmp(i2),
-            (KeybindingChange::Modify { .. }, _) => std::cmp::Ordering::Less,
-            (_, KeybindingChange::Modify { .. }) => std::cmp::Ordering::Greater,
-            _ => std::cmp::Ordering::Equal,
-        }
+    sorted_changes.sort_by_key(|change| match change {
+        KeybindingChange::Modify { index, .. } => (0, *index),
+        KeybindingChange::Delete(index) => (1, usize::MAX - index),
+        KeybindingChange::Add(_) => (2, 0),
     });
+    let mut touched = std::collections::HashSet::new();
+    for change in &sorted_changes {
+        if let KeybindingChange::Modify { index, .. } | KeybindingChange::Delete(index) = change {
+            if !touched.insert(*index) {
+                bail!("Binding #{index} has more than one staged change");
+            }
+        }
+    }
 
     for change in sorted_changes {
         match change {
             KeybindingChange::Delete(index) => {
-                if *index < children.nodes().len() {
-                    children.nodes_mut().remove(*index);
+                if *index >= children.nodes().len() {
+                    bail!("Binding #{index} to delete no longer exists in the binds block");
                 }
+                children.nodes_mut().remove(*index);
             }
             KeybindingChange::Modify { index, new } => {
-                if *index < children.nodes().len() {
-                    let node = create_keybinding_node(new);
-                    children.nodes_mut()[*index] = node;
+                if *index >= children.nodes().len() {
+                    match &new.source {
+                        Some(loc) => bail!("Binding {} defined at {loc} no longer exists", new.combo()),
+                        None => bail!("Binding {} no longer exists in the binds block", new.combo()),
+                    }
                 }
+                update_keybinding_node(&mut children.nodes_mut()[*index], new);
             }
             KeybindingChange::Add(binding) => {
-                let node = create_keybinding_node(binding);
+                let mut node = create_keybinding_node(binding);
+                format_node(&mut node, 1);
                 children.nodes_mut().push(node);
             }
         }
     }
 
-    // Autoformat the binds block
-    children.autoformat();
-    binds_node.autoformat();
+    if created {
+        binds_node.autoformat();
+    }
+    Ok(())
+}
 
-    config.save()
+/// Render the KDL that would be written for a keybinding, as it will
+/// appear in the saved file
+pub fn keybinding_kdl(binding: &Keybinding) -> String {
+    let mut node = create_keybinding_node(binding);
+    node.ensure_v1();
+    node.to_string().trim().to_string()
+}
+
+/// Binding properties nirikiri edits; any others on a bind node are kept
+const MODELED_PROPERTIES: [&str; 3] = ["repeat", "cooldown-ms", "allow-when-locked"];
+
+/// Rewrite a parsed bind node in place for an edited binding.
+///
+/// Only the combo, the properties nirikiri models and the first action node
+/// change; other properties, further child nodes and comments are kept as
+/// they were, and anything that already matches is left untouched.
+fn update_keybinding_node(node: &mut KdlNode, binding: &Keybinding) {
+    let combo = binding.combo();
+    if node.name().value() != combo {
+        node.set_name(combo);
+    }
+
+    let properties = [
+        binding.properties.repeat.map(boolean),
+        binding.properties.cooldown_ms.map(int),
+        binding.properties.allow_when_locked.map(boolean),
+    ];
+    for (name, value) in MODELED_PROPERTIES.into_iter().zip(properties) {
+        let existing = node
+            .entries()
+            .iter()
+            .position(|e| e.name().is_some_and(|n| n.value() == name));
+        match (existing, value) {
+            (Some(i), Some(value)) if *node.entries()[i].value() == value => {}
+            (Some(i), Some(value)) => node.entries_mut()[i] = KdlEntry::new_prop(name, value),
+            (Some(i), None) => {
+                node.entries_mut().remove(i);
+            }
+            (None, Some(value)) => node.push(KdlEntry::new_prop(name, value)),
+            (None, None) => {}
+        }
+    }
+
+    let Some(children) = node.children_mut().as_mut().filter(|c| !c.nodes().is_empty()) else {
+        // No action to keep the layout of, so write the body from scratch
+        let mut new_node = create_keybinding_node(binding);
+        if let Some(format) = node.format() {
+            new_node.set_format(format.clone());
+        }
+        format_node(&mut new_node, 1);
+        *node = new_node;
+        return;
+    };
+    if parse_action_node(&children.nodes()[0]).as_ref() == Some(&binding.action) {
+        return;
+    }
+    let mut action = create_action_node(&binding.action);
+    match children.nodes()[0].format() {
+        Some(format) => action.set_format(format.clone()),
+        None => format_node(&mut action, 2),
+    }
+    children.nodes_mut()[0] = action;
 }
 
 /// Create a KDL node for a keybinding
@@ -76,13 +158,13 @@ fn create_keybinding_node(binding: &Keybinding) -> KdlNode {
 
     // Add properties
     if let Some(repeat) = binding.properties.repeat {
-        node.push(KdlEntry::new_prop("repeat", KdlValue::Bool(repeat)));
+        node.push(KdlEntry::new_prop("repeat", boolean(repeat)));
     }
     if let Some(cooldown) = binding.properties.cooldown_ms {
-        node.push(KdlEntry::new_prop("cooldown-ms", KdlValue::Integer(cooldown as i128)));
+        node.push(KdlEntry::new_prop("cooldown-ms", int(cooldown)));
     }
     if let Some(allow_locked) = binding.properties.allow_when_locked {
-        node.push(KdlEntry::new_prop("allow-when-locked", KdlValue::Bool(allow_locked)));
+        node.push(KdlEntry::new_prop("allow-when-locked", boolean(allow_locked)));
     }
 
     // Create action child node
@@ -103,14 +185,14 @@ fn create_action_node(action: &BindingAction) -> KdlNode {
         BindingAction::Spawn(args) => {
             let mut node = KdlNode::new("spawn");
             for arg in args {
-                node.push(KdlEntry::new(KdlValue::String(arg.clone())));
+                node.push(KdlEntry::new(string(arg.clone())));
             }
             node.autoformat();
             node
         }
         BindingAction::SpawnSh(cmd) => {
             let mut node = KdlNode::new("spawn-sh");
-            node.push(KdlEntry::new(KdlValue::String(cmd.clone())));
+            node.push(KdlEntry::new(string(cmd.clone())));
             node.autoformat();
             node
         }
@@ -122,9 +204,9 @@ fn create_action_node(action: &BindingAction) -> KdlNode {
         BindingAction::WithArg(name, arg) => {
             let mut node = KdlNode::new(name.as_str());
             let value = match arg {
-                BindingArg::Number(n) => KdlValue::Integer(*n as i128),
-                BindingArg::String(s) => KdlValue::String(s.clone()),
-                BindingArg::Bool(b) => KdlValue::Bool(*b),
+                BindingArg::Number(n) => int(*n),
+                BindingArg::String(s) => string(s.clone()),
+                BindingArg::Bool(b) => boolean(*b),
             };
             node.push(KdlEntry::new(value));
             node.autoformat();
@@ -151,6 +233,7 @@ mod tests {
             properties: BindingProperties::default(),
             action: BindingAction::Simple("close-window".to_string()),
             kdl_index: None,
+            source: None,
         };
 
         let node = create_keybinding_node(&binding);
@@ -174,9 +257,156 @@ mod tests {
             },
             action: BindingAction::Simple("close-window".to_string()),
             kdl_index: None,
+            source: None,
         };
 
         let node = create_keybinding_node(&binding);
         assert!(node.get("repeat").is_some());
     }
+
+    #[test]
+    fn test_keybinding_kdl() {
+        let binding = Keybinding {
+            modifiers: Modifiers {
+                mod_key: true,
+                ctrl: false,
+                shift: false,
+                alt: false,
+            },
+            key: "Q".to_string(),
+            properties: BindingProperties {
+                repeat: Some(false),
+                cooldown_ms: None,
+                allow_when_locked: None,
+            },
+            action: BindingAction::Simple("close-window".to_string()),
+            kdl_index: None,
+            source: None,
+        };
+
+        let kdl = keybinding_kdl(&binding);
+        assert_eq!(kdl, "Mod+Q repeat=false {\n    close-window\n}");
+    }
+
+    #[test]
+    fn test_apply_keybindings_touches_only_changed_nodes() {
+        let source = "binds {\n    // Terminal\n    Mod+T   { spawn \"alacritty\"; }\n    Mod+Q { close-window; }\n    Mod+F{maximize-column;}\n}\n";
+        let mut config = ConfigDocument {
+            doc: KdlDocument::parse_v1(source).unwrap(),
+            path: std::path::PathBuf::from("/tmp/test.kdl"),
+            source: source.to_string(),
+        };
+        let (modifiers, key) = Modifiers::parse("Mod+Q");
+        let quit = Keybinding {
+            modifiers,
+            key,
+            properties: BindingProperties::default(),
+            action: BindingAction::Simple("quit".to_string()),
+            kdl_index: None,
+            source: None,
+        };
+        let (modifiers, key) = Modifiers::parse("Mod+O");
+        let overview = Keybinding {
+            modifiers,
+            key,
+            action: BindingAction::Simple("toggle-overview".to_string()),
+            ..quit.clone()
+        };
+
+        apply_keybindings(
+            &mut config,
+            &[KeybindingChange::Modify { index: 1, new: quit }, KeybindingChange::Add(overview)],
+        )
+        .unwrap();
+        crate::config::kdl_format::ensure_v1_new(&mut config.doc);
+        assert_eq!(
+            config.doc.to_string(),
+            "binds {\n    // Terminal\n    Mod+T   { spawn \"alacritty\"; }\n    Mod+Q { quit; }\n    Mod+F{maximize-column;}\n    Mod+O {\n        toggle-overview\n    }\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_apply_keybindings_order() {
+        let source = "binds {\n    Mod+1 { focus-workspace 1; }\n    Mod+2 { focus-workspace 2; }\n    Mod+3 { focus-workspace 3; }\n    Mod+4 { focus-workspace 4; }\n}\n";
+        let mut config = ConfigDocument {
+            doc: KdlDocument::parse_v1(source).unwrap(),
+            path: std::path::PathBuf::from("/tmp/test.kdl"),
+            source: source.to_string(),
+        };
+        let binding = |combo: &str, action: &str| {
+            let (modifiers, key) = Modifiers::parse(combo);
+            Keybinding {
+                modifiers,
+                key,
+                properties: BindingProperties::default(),
+                action: BindingAction::Simple(action.to_string()),
+                kdl_index: None,
+                source: None,
+            }
+        };
+
+        // Indices refer to the file as loaded, whatever order they're staged in
+        apply_keybindings(
+            &mut config,
+            &[
+                KeybindingChange::Add(binding("Mod+O", "toggle-overview")),
+                KeybindingChange::Delete(1),
+                KeybindingChange::Modify { index: 2, new: binding("Mod+3", "quit") },
+                KeybindingChange::Delete(3),
+                KeybindingChange::Add(binding("Mod+Q", "close-window")),
+            ],
+        )
+        .unwrap();
+        crate::config::kdl_format::ensure_v1_new(&mut config.doc);
+        assert_eq!(
+            config.doc.to_string(),
+            "binds {\n    Mod+1 { focus-workspace 1; }\n    Mod+3 { quit; }\n    Mod+O {\n        toggle-overview\n    }\n    Mod+Q {\n        close-window\n    }\n}\n"
+        );
+
+        let err = apply_keybindings(
+            &mut config,
+            &[KeybindingChange::Delete(1), KeybindingChange::Modify { index: 1, new: binding("Mod+4", "quit") }],
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Binding #1 has more than one staged change");
+    }
+
+    #[test]
+    fn test_modify_keeps_unmodeled_parts() {
+        let source = "binds {\n    Mod+T hotkey-overlay-title=\"Open a Terminal\" { spawn \"/usr/bin/foot\"; }\n    Mod+Q repeat=false {\n        close-window\n        // and then\n        focus-column-left\n    }\n}\n";
+        let mut config = ConfigDocument {
+            doc: KdlDocument::parse_v1(source).unwrap(),
+            path: std::path::PathBuf::from("/tmp/test.kdl"),
+            source: source.to_string(),
+        };
+        let (modifiers, key) = Modifiers::parse("Mod+T");
+        let terminal = Keybinding {
+            modifiers,
+            key,
+            properties: BindingProperties::default(),
+            action: BindingAction::Spawn(vec!["kitty".to_string()]),
+            kdl_index: None,
+            source: None,
+        };
+        let (modifiers, key) = Modifiers::parse("Mod+Shift+Q");
+        let close = Keybinding {
+            modifiers,
+            key,
+            action: BindingAction::Simple("close-window".to_string()),
+            ..terminal.clone()
+        };
+        apply_keybindings(
+            &mut config,
+            &[
+                KeybindingChange::Modify { index: 0, new: terminal },
+                KeybindingChange::Modify { index: 1, new: close },
+            ],
+        )
+        .unwrap();
+        crate::config::kdl_format::ensure_v1_new(&mut config.doc);
+        assert_eq!(
+            config.doc.to_string(),
+            "binds {\n    Mod+T hotkey-overlay-title=\"Open a Terminal\" { spawn \"kitty\"; }\n    Mod+Shift+Q {\n        close-window\n        // and then\n        focus-column-left\n    }\n}\n"
+        );
+    }
 }
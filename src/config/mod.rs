@@ -1,13 +1,32 @@
 pub mod appearance_parser;
 pub mod appearance_writer;
+pub mod default_binds;
+pub mod default_layout;
+pub mod fragment;
+pub mod general_parser;
+pub mod general_writer;
+pub mod git;
+pub mod kdl_format;
+pub mod kdl_value;
 pub mod keybindings_parser;
 pub mod keybindings_writer;
 pub mod parser;
+pub mod template_writer;
 pub mod writer;
 
-pub use appearance_parser::parse_appearance;
-pub use appearance_writer::write_appearance;
-pub use keybindings_parser::parse_keybindings;
-pub use keybindings_writer::write_keybindings;
-pub use parser::{get_configured_positions, load_config};
-pub use writer::write_positions;
+pub use appearance_parser::{locate_appearance_fields, parse_appearance};
+pub use appearance_writer::{appearance_field_kdl, apply_appearance, write_appearance};
+pub use default_binds::load_default_binds;
+pub use default_layout::load_default_appearance;
+pub use fragment::{ensure_included, fragment_path, includes_fragment, load_editable_config, owned_blocks};
+pub use general_parser::{locate_general_fields, parse_general};
+pub use general_writer::{apply_general, write_general};
+pub use git::{commit_config, commit_message};
+pub use keybindings_parser::{parse_bind_nodes, parse_keybindings, unmodeled_parts};
+pub use keybindings_writer::{apply_keybindings, keybinding_kdl, write_keybindings};
+pub use parser::{
+    copy_system_config, default_config_path, discover_config, get_config_path, get_configured_positions, load_config,
+    set_config_path,
+};
+pub use template_writer::{workspace_spawn_kdl, write_template};
+pub use writer::{apply_output_config, write_merged_outputs, write_output_config};
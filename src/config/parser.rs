@@ -1,7 +1,8 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use std::path::PathBuf;
+use std::sync::RwLock;
 
-use crate::model::{ConfigDocument, Position};
+use crate::model::{ConfigDiscovery, ConfigDocument, Position};
 
 /// Load and parse the niri config file
 pub fn load_config() -> Result<ConfigDocument> {
@@ -9,10 +10,57 @@ pub fn load_config() -> Result<ConfigDocument> {
     ConfigDocument::load(path)
 }
 
-/// Get the default niri config path
+/// Config opened instead of the default one (--config or quick-open)
+static CONFIG_OVERRIDE: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// Edit `path` instead of the default config from now on; None goes back
+/// to the default
+pub fn set_config_path(path: Option<PathBuf>) {
+    *CONFIG_OVERRIDE.write().unwrap_or_else(|e| e.into_inner()) = path;
+}
+
+/// Get the path of the config being edited
 pub fn get_config_path() -> Result<PathBuf> {
-    let config_dir = dirs::config_dir().ok_or_else(|| anyhow::anyhow!("Could not find config directory"))?;
-    Ok(config_dir.join("niri").join("config.kdl"))
+    Ok(discover_config()?.path().to_path_buf())
+}
+
+/// Get the config niri itself reads, ignoring --config and quick-open
+pub fn default_config_path() -> Result<PathBuf> {
+    Ok(discover(None)?.path().to_path_buf())
+}
+
+/// Work out which config is edited and why; see `ConfigDiscovery::resolve`
+pub fn discover_config() -> Result<ConfigDiscovery> {
+    discover(CONFIG_OVERRIDE.read().unwrap_or_else(|e| e.into_inner()).clone())
+}
+
+fn discover(override_path: Option<PathBuf>) -> Result<ConfigDiscovery> {
+    let env = |name| std::env::var_os(name).map(PathBuf::from);
+    let mut discovery =
+        ConfigDiscovery::resolve(override_path, env("NIRI_CONFIG"), env("XDG_CONFIG_HOME"), dirs::home_dir(), |p| p.exists())
+            .ok_or_else(|| anyhow::anyhow!("Could not find config directory"))?;
+    let path = discovery.path();
+    discovery.read_only = path.exists() && std::fs::OpenOptions::new().append(true).open(path).is_err();
+    Ok(discovery)
+}
+
+/// Copy the system config to the user path so it can be edited, returning
+/// the copy. niri reads the copy from then on, since a user config beats
+/// /etc. An existing user config is never overwritten.
+pub fn copy_system_config(discovery: &ConfigDiscovery) -> Result<PathBuf> {
+    let target = discovery
+        .user_path()
+        .ok_or_else(|| anyhow::anyhow!("No user config directory to copy to"))?
+        .to_path_buf();
+    if target.exists() {
+        anyhow::bail!("{} already exists", target.display());
+    }
+    if let Some(dir) = target.parent() {
+        std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    std::fs::copy(discovery.path(), &target)
+        .with_context(|| format!("Failed to copy {} to {}", discovery.path().display(), target.display()))?;
+    Ok(target)
 }
 
 /// Extract output positions from config
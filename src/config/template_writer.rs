@@ -0,0 +1,178 @@
+use anyhow::{Context, Result};
+use kdl::{KdlDocument, KdlEntry, KdlNode};
+
+use super::kdl_format::format_node;
+use super::kdl_value::string;
+use crate::model::templates::ConfigTemplate;
+use crate::model::{ConfigDocument, SourceLocation, WorkspaceSpawn};
+
+/// Append a template's nodes to the end of the config without saving.
+/// Returns the index of the first inserted top-level node.
+pub fn append_template(config: &mut ConfigDocument, template: &ConfigTemplate) -> Result<usize> {
+    let template_doc = KdlDocument::parse_v1(template.kdl)
+        .with_context(|| format!("Invalid {} template", template.node))?;
+
+    let first_index = config.doc.nodes().len();
+    for (i, mut node) in template_doc.nodes().iter().cloned().enumerate() {
+        // Keep the template's comments and separate it from the previous block
+        if i == 0 {
+            if let Some(format) = node.format_mut() {
+                format.leading = format!("\n{}", format.leading);
+            }
+        }
+        config.doc.nodes_mut().push(node);
+    }
+
+    Ok(first_index)
+}
+
+/// Insert a template, save the config, and return where the new block starts
+pub fn write_template(
+    config: &mut ConfigDocument,
+    template: &ConfigTemplate,
+) -> Result<SourceLocation> {
+    let index = append_template(config, template)?;
+    config.save()?;
+
+    let location = config.doc.nodes().get(index).and_then(|node| config.locate(node));
+    Ok(location.unwrap_or(SourceLocation {
+        path: config.path.clone(),
+        line: config.source.lines().count(),
+    }))
+}
+
+/// The top-level nodes that open an app on a named workspace. The
+/// workspace declaration is left out when the config already has one.
+pub fn workspace_spawn_nodes(config: &ConfigDocument, spawn: &WorkspaceSpawn) -> Vec<KdlNode> {
+    let entry = |s: &str| KdlEntry::new(string(s));
+    let mut nodes = Vec::new();
+
+    let declared = config.doc.nodes().iter().any(|n| {
+        n.name().value() == "workspace" && n.get(0).and_then(|v| v.as_string()) == Some(&spawn.workspace)
+    });
+    if !declared {
+        let mut workspace = KdlNode::new("workspace");
+        workspace.push(entry(&spawn.workspace));
+        if let Some(output) = &spawn.output {
+            let mut open_on = KdlNode::new("open-on-output");
+            open_on.push(entry(output));
+            let mut children = KdlDocument::new();
+            children.nodes_mut().push(open_on);
+            workspace.set_children(children);
+        }
+        nodes.push(workspace);
+    }
+
+    let mut rule = KdlNode::new("window-rule");
+    let mut matcher = KdlNode::new("match");
+    matcher.push(KdlEntry::new_prop("app-id", string(spawn.app_id.as_str())));
+    let mut open_on = KdlNode::new("open-on-workspace");
+    open_on.push(entry(&spawn.workspace));
+    let mut children = KdlDocument::new();
+    children.nodes_mut().push(matcher);
+    children.nodes_mut().push(open_on);
+    rule.set_children(children);
+    nodes.push(rule);
+
+    if spawn.at_startup {
+        let mut startup = KdlNode::new("spawn-at-startup");
+        for arg in &spawn.command {
+            startup.push(entry(arg));
+        }
+        nodes.push(startup);
+    }
+
+    for node in &mut nodes {
+        format_node(node, 0);
+    }
+    nodes
+}
+
+/// Render the nodes a workspace spawn would add, as they will appear in
+/// the saved file
+pub fn workspace_spawn_kdl(config: &ConfigDocument, spawn: &WorkspaceSpawn) -> String {
+    workspace_spawn_nodes(config, spawn)
+        .into_iter()
+        .map(|mut node| {
+            node.ensure_v1();
+            node.to_string().trim().to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Append every piece of a workspace spawn to the end of the config
+/// without saving
+pub fn append_workspace_spawn(config: &mut ConfigDocument, spawn: &WorkspaceSpawn) {
+    for (i, mut node) in workspace_spawn_nodes(config, spawn).into_iter().enumerate() {
+        if i == 0 {
+            if let Some(format) = node.format_mut() {
+                format.leading = format!("\n{}", format.leading);
+            }
+        }
+        config.doc.nodes_mut().push(node);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::templates::TEMPLATES;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_append_template_keeps_comments() {
+        let content = "binds {\n    Mod+T { spawn \"alacritty\"; }\n}\n";
+        let mut config = ConfigDocument {
+            doc: KdlDocument::parse_v1(content).unwrap(),
+            path: PathBuf::from("config.kdl"),
+            source: content.to_string(),
+        };
+        let template = TEMPLATES.iter().find(|t| t.node == "window-rule").unwrap();
+
+        let index = append_template(&mut config, template).unwrap();
+        assert_eq!(index, 1);
+
+        let output = config.doc.to_string();
+        assert!(output.starts_with(content));
+        assert!(output.contains("// Open the Firefox picture-in-picture player"));
+        assert!(output.contains("open-floating true"));
+
+        let reparsed = KdlDocument::parse_v1(&output).unwrap();
+        assert_eq!(reparsed.nodes()[1].name().value(), "window-rule");
+    }
+
+    #[test]
+    fn test_workspace_spawn_nodes() {
+        let content = "workspace \"chat\"\n";
+        let config = ConfigDocument {
+            doc: KdlDocument::parse_v1(content).unwrap(),
+            path: PathBuf::from("config.kdl"),
+            source: content.to_string(),
+        };
+        let mut spawn = WorkspaceSpawn {
+            workspace: "chat".to_string(),
+            command: vec!["telegram-desktop".to_string(), "-startintray".to_string()],
+            app_id: "^org\\.telegram\\.desktop$".to_string(),
+            output: Some("DP-1".to_string()),
+            at_startup: true,
+        };
+
+        // The workspace is already declared, so only the rule and startup entry are added
+        let kdl = workspace_spawn_kdl(&config, &spawn);
+        let doc = KdlDocument::parse_v1(&kdl).unwrap();
+        let names: Vec<_> = doc.nodes().iter().map(|n| n.name().value()).collect();
+        assert_eq!(names, vec!["window-rule", "spawn-at-startup"]);
+        assert!(kdl.contains("match app-id=\"^org\\\\.telegram\\\\.desktop$\""));
+        assert!(kdl.contains("open-on-workspace \"chat\""));
+        assert!(kdl.contains("spawn-at-startup \"telegram-desktop\" \"-startintray\""));
+
+        spawn.workspace = "music".to_string();
+        spawn.at_startup = false;
+        let kdl = workspace_spawn_kdl(&config, &spawn);
+        let doc = KdlDocument::parse_v1(&kdl).unwrap();
+        let names: Vec<_> = doc.nodes().iter().map(|n| n.name().value()).collect();
+        assert_eq!(names, vec!["workspace", "window-rule"]);
+        assert!(kdl.starts_with("workspace \"music\" {\n    open-on-output \"DP-1\"\n}"));
+    }
+}
@@ -1,15 +1,57 @@
 use anyhow::Result;
 use std::collections::HashMap;
 
-use crate::model::{ConfigDocument, Position};
-
-/// Write pending position changes to the config
-pub fn write_positions(
-    config: &mut ConfigDocument,
-    positions: &HashMap<String, Position>,
-) -> Result<()> {
-    for (name, position) in positions {
-        config.set_output_position(name, *position)?;
-    }
+use crate::model::{ConfigDocument, OutputChange};
+
+/// Write pending output changes to the config
+pub fn write_output_config(config: &mut ConfigDocument, changes: &HashMap<String, OutputChange>) -> Result<()> {
+    apply_output_config(config, changes)?;
     config.save()
 }
+
+/// Fold every output's duplicate blocks into its first one and save.
+/// Returns the names of the outputs that were merged.
+pub fn write_merged_outputs(config: &mut ConfigDocument) -> Result<Vec<String>> {
+    let names = config.duplicate_outputs();
+    for name in &names {
+        config.merge_output_blocks(name);
+    }
+    if !names.is_empty() {
+        config.save()?;
+    }
+    Ok(names)
+}
+
+/// Set the staged fields of each output in memory without saving
+pub fn apply_output_config(config: &mut ConfigDocument, changes: &HashMap<String, OutputChange>) -> Result<()> {
+    for (name, change) in changes {
+        if let Some(enabled) = change.enabled {
+            config.set_output_flag(name, "off", !enabled)?;
+        }
+        if let Some(mode) = &change.mode {
+            config.set_output_mode(name, mode)?;
+        }
+        if let Some(scale) = change.scale {
+            config.set_output_scale(name, scale)?;
+        }
+        if let Some(transform) = change.transform {
+            config.set_output_transform(name, transform)?;
+        }
+        if let Some(position) = change.position {
+            config.set_output_position(name, position)?;
+        }
+        if let Some(vrr) = change.vrr {
+            config.set_output_flag(name, "variable-refresh-rate", vrr)?;
+        }
+        if let Some(color) = &change.background_color {
+            config.set_output_color(name, "background-color", color)?;
+        }
+        if let Some(color) = &change.backdrop_color {
+            config.set_output_color(name, "backdrop-color", color)?;
+        }
+        if let Some(focus) = change.focus_at_startup {
+            config.set_output_flag(name, "focus-at-startup", focus)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,91 @@
+//! Timings shown by the debug overlay (F10 or --debug), for telling a slow
+//! terminal or SSH link apart from a slow niri socket
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Frames kept for the average and worst frame time
+const FRAME_HISTORY: usize = 60;
+
+/// Window the tick rate is counted over
+const TICK_WINDOW: Duration = Duration::from_secs(1);
+
+/// Frame, IPC and event loop timings, collected while the overlay is on
+#[derive(Debug, Default)]
+pub struct Diagnostics {
+    frames: VecDeque<Duration>,
+    /// Last IPC request and how long it took, connecting included
+    pub last_ipc: Option<(String, Duration)>,
+    /// Start times of the ticks in the last second
+    ticks: VecDeque<Instant>,
+    /// Time the last tick spent outside the wait for input
+    pub last_tick_work: Duration,
+}
+
+impl Diagnostics {
+    /// Time taken to draw one frame and flush it to the terminal
+    pub fn record_frame(&mut self, time: Duration) {
+        if self.frames.len() == FRAME_HISTORY {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(time);
+    }
+
+    pub fn record_ipc(&mut self, what: &str, time: Duration) {
+        self.last_ipc = Some((what.to_string(), time));
+    }
+
+    /// One pass of the event loop that started at `start` and did `work`
+    /// besides waiting for input
+    pub fn record_tick(&mut self, start: Instant, work: Duration) {
+        while self.ticks.front().is_some_and(|&t| start.duration_since(t) >= TICK_WINDOW) {
+            self.ticks.pop_front();
+        }
+        self.ticks.push_back(start);
+        self.last_tick_work = work;
+    }
+
+    pub fn last_frame(&self) -> Option<Duration> {
+        self.frames.back().copied()
+    }
+
+    /// Average and worst of the recent frame times
+    pub fn frame_stats(&self) -> Option<(Duration, Duration)> {
+        let max = self.frames.iter().max()?;
+        let total: Duration = self.frames.iter().sum();
+        Some((total / self.frames.len() as u32, *max))
+    }
+
+    /// Ticks in the last second
+    pub fn ticks_per_second(&self) -> usize {
+        self.ticks.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagnostics() {
+        let mut diagnostics = Diagnostics::default();
+        assert_eq!(diagnostics.frame_stats(), None);
+
+        for ms in 1..=FRAME_HISTORY as u64 + 2 {
+            diagnostics.record_frame(Duration::from_millis(ms));
+        }
+        // The oldest two frames fell out of the history
+        assert_eq!(diagnostics.last_frame(), Some(Duration::from_millis(62)));
+        assert_eq!(
+            diagnostics.frame_stats(),
+            Some((Duration::from_micros(32_500), Duration::from_millis(62)))
+        );
+
+        let start = Instant::now();
+        for ms in [0, 400, 800, 1200] {
+            diagnostics.record_tick(start + Duration::from_millis(ms), Duration::from_millis(2));
+        }
+        assert_eq!(diagnostics.ticks_per_second(), 3);
+        assert_eq!(diagnostics.last_tick_work, Duration::from_millis(2));
+    }
+}
@@ -0,0 +1,150 @@
+use std::collections::VecDeque;
+use std::fmt;
+
+use crate::model::SelectableList;
+
+/// Number of past errors kept for the error log dialog
+pub const ERROR_HISTORY_LEN: usize = 20;
+
+/// A failure surfaced to the user, tagged with where it came from
+#[derive(Debug, Clone, PartialEq)]
+pub enum AppError {
+    /// Talking to niri over its IPC socket failed
+    Ipc { context: String, chain: Vec<String> },
+    /// Reading or parsing the config failed
+    Parse { context: String, chain: Vec<String> },
+    /// Writing the config back to disk failed
+    Write { context: String, chain: Vec<String> },
+    /// User input was rejected before anything was attempted
+    Validation(String),
+}
+
+impl AppError {
+    pub fn ipc(context: impl Into<String>, err: &anyhow::Error) -> Self {
+        AppError::Ipc {
+            context: context.into(),
+            chain: chain_of(err),
+        }
+    }
+
+    pub fn parse(context: impl Into<String>, err: &anyhow::Error) -> Self {
+        AppError::Parse {
+            context: context.into(),
+            chain: chain_of(err),
+        }
+    }
+
+    pub fn write(context: impl Into<String>, err: &anyhow::Error) -> Self {
+        AppError::Write {
+            context: context.into(),
+            chain: chain_of(err),
+        }
+    }
+
+    pub fn validation(message: impl Into<String>) -> Self {
+        AppError::Validation(message.into())
+    }
+
+    /// Short label for the error kind (e.g., "IPC")
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AppError::Ipc { .. } => "IPC",
+            AppError::Parse { .. } => "Parse",
+            AppError::Write { .. } => "Write",
+            AppError::Validation(_) => "Validation",
+        }
+    }
+
+    /// What the app was doing when the error happened
+    pub fn context(&self) -> &str {
+        match self {
+            AppError::Ipc { context, .. }
+            | AppError::Parse { context, .. }
+            | AppError::Write { context, .. } => context,
+            AppError::Validation(message) => message,
+        }
+    }
+
+    /// The underlying anyhow context chain, outermost first
+    pub fn chain(&self) -> &[String] {
+        match self {
+            AppError::Ipc { chain, .. }
+            | AppError::Parse { chain, .. }
+            | AppError::Write { chain, .. } => chain,
+            AppError::Validation(_) => &[],
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.chain().first() {
+            Some(cause) => write!(f, "{}: {}", self.context(), cause),
+            None => write!(f, "{}", self.context()),
+        }
+    }
+}
+
+fn chain_of(err: &anyhow::Error) -> Vec<String> {
+    err.chain().map(|cause| cause.to_string()).collect()
+}
+
+/// Recent errors and the state of the error log dialog
+#[derive(Debug, Default)]
+pub struct ErrorLog {
+    /// Most recent error first
+    pub entries: VecDeque<AppError>,
+    pub open: bool,
+    pub list: SelectableList,
+}
+
+impl ErrorLog {
+    pub fn push(&mut self, error: AppError) {
+        self.entries.push_front(error);
+        self.entries.truncate(ERROR_HISTORY_LEN);
+        self.list.reset();
+    }
+
+    pub fn selected(&self) -> Option<&AppError> {
+        self.entries.get(self.list.selected_index)
+    }
+
+    pub fn select_next(&mut self) {
+        self.list.select_next(self.entries.len());
+    }
+
+    pub fn select_prev(&mut self) {
+        self.list.select_prev(self.entries.len());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_chain_is_captured() {
+        let err = std::fs::read("/nonexistent/config.kdl")
+            .context("Failed to read config file")
+            .unwrap_err();
+        let error = AppError::parse("Failed to load config", &err);
+        assert_eq!(error.kind(), "Parse");
+        assert_eq!(error.chain().len(), 2);
+        assert_eq!(
+            error.to_string(),
+            "Failed to load config: Failed to read config file"
+        );
+    }
+
+    #[test]
+    fn test_error_log_keeps_last_n() {
+        let mut log = ErrorLog::default();
+        for i in 0..ERROR_HISTORY_LEN + 5 {
+            log.push(AppError::validation(format!("error {i}")));
+        }
+        assert_eq!(log.entries.len(), ERROR_HISTORY_LEN);
+        let newest = format!("error {}", ERROR_HISTORY_LEN + 4);
+        assert_eq!(log.selected().map(|e| e.context()), Some(newest.as_str()));
+    }
+}
@@ -0,0 +1,214 @@
+//! One-shot commands run from the command line without the TUI
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::cli::OutputsCommand;
+use crate::hooks::{run_hooks, HookEvent, HookPayload};
+use crate::config::{commit_config, commit_message, ensure_included, load_config, load_editable_config, write_output_config};
+use crate::ipc::connect_backend;
+use crate::kanshi;
+use crate::message::OutputMessage;
+use crate::model::{OutputChange, OutputViewModel, Position};
+use crate::output_profiles::{OutputProfile, OutputProfiles};
+use crate::settings::Settings;
+use crate::update::update_output;
+
+/// What an outputs command changed
+#[derive(Debug, Default, Serialize)]
+pub struct OutputsReport {
+    pub moves: Vec<OutputMove>,
+    /// One line per move, as used for commit messages
+    pub summary: Vec<String>,
+    /// The file that was written, None if nothing had to change
+    pub config: Option<PathBuf>,
+    /// Parts of a profile that couldn't be applied, such as outputs that
+    /// aren't connected
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub skipped: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OutputMove {
+    pub output: String,
+    pub from: Position,
+    pub to: Position,
+}
+
+/// Apply an outputs command to the current layout and save the config.
+/// The report is empty if nothing had to change.
+pub fn run_outputs(socket_path: Option<&Path>, command: &OutputsCommand) -> Result<OutputsReport> {
+    let mut view_model = OutputViewModel {
+        outputs: connect_backend(socket_path)?.get_outputs()?,
+        ..Default::default()
+    };
+    let mut skipped = Vec::new();
+    if let Some(name) = &command.profile {
+        let profiles = OutputProfiles::load()?;
+        let Some(profile) = profiles.get(name) else {
+            bail!("No output profile named {name:?}");
+        };
+        let (changes, notes) = profile.changes(&view_model.outputs);
+        view_model.pending_changes = changes;
+        skipped = notes;
+    }
+    let mut messages = Vec::new();
+    if let Some(arrangement) = command.arrange {
+        messages.push(OutputMessage::Arrange(arrangement));
+    }
+    if command.normalize {
+        messages.push(OutputMessage::Normalize);
+    }
+    for message in &messages {
+        update_output(&mut view_model, message);
+    }
+
+    // Leave outputs that are already in place out of the config
+    for (name, change) in view_model.pending_changes.iter_mut() {
+        if view_model.outputs.iter().any(|o| &o.name == name && change.position == Some(o.position)) {
+            change.position = None;
+        }
+    }
+    view_model.pending_changes.retain(|_, change| *change != OutputChange::default());
+    if !view_model.has_pending_changes() {
+        return Ok(OutputsReport { skipped, ..Default::default() });
+    }
+    let mut moves: Vec<OutputMove> = view_model
+        .outputs
+        .iter()
+        .filter_map(|o| {
+            let to = view_model.pending_position(&o.name)?;
+            Some(OutputMove { output: o.name.clone(), from: o.position, to })
+        })
+        .collect();
+    moves.sort_by(|a, b| a.output.cmp(&b.output));
+
+    let summary = view_model.change_summary();
+    let settings = Settings::load()?;
+    let mut config = load_editable_config(settings.managed_fragment)?;
+    let path = config.path.clone();
+    let payload = |event| HookPayload { event, category: Some("Outputs"), config: Some(&path), changes: &summary };
+    run_hooks(&settings.hooks, &payload(HookEvent::PreSave))?;
+    write_output_config(&mut config, &view_model.pending_changes)?;
+    // In managed mode the niri config needs to include the fragment
+    let include_added = if settings.managed_fragment {
+        let mut main = load_config()?;
+        ensure_included(&mut main)?.then_some(main.path)
+    } else {
+        None
+    };
+    if settings.git_commit {
+        if let Some(main_path) = include_added {
+            commit_config(&main_path, "nirikiri: include nirikiri.kdl")?;
+        }
+        commit_config(&config.path, &commit_message(&summary))?;
+    }
+    run_hooks(&settings.hooks, &payload(HookEvent::PostSave))?;
+    Ok(OutputsReport { moves, summary, config: Some(config.path), skipped })
+}
+
+/// A layout in kanshi's config format
+#[derive(Debug, Serialize)]
+pub struct KanshiExport {
+    pub kanshi: String,
+}
+
+/// The current layout, or the saved profile `name`, as a kanshi profile
+pub fn export_kanshi(socket_path: Option<&Path>, name: Option<&str>) -> Result<KanshiExport> {
+    let profile = match name {
+        Some(name) => OutputProfiles::load()?
+            .get(name)
+            .cloned()
+            .ok_or_else(|| anyhow!("No output profile named {name:?}"))?,
+        None => {
+            let outputs = connect_backend(socket_path)?.get_outputs()?;
+            OutputProfile::capture("nirikiri", &outputs, &HashMap::new())
+        }
+    };
+    Ok(KanshiExport { kanshi: kanshi::to_kanshi(&profile) })
+}
+
+/// What a kanshi import saved
+#[derive(Debug, Default, Serialize)]
+pub struct KanshiImport {
+    /// Names of the output profiles saved, replacing any of the same name
+    pub profiles: Vec<String>,
+    /// Parts of the kanshi config that couldn't be taken over
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub skipped: Vec<String>,
+}
+
+/// Save the profiles of a kanshi config as output profiles
+pub fn import_kanshi(socket_path: Option<&Path>, path: &Path) -> Result<KanshiImport> {
+    let content =
+        std::fs::read_to_string(path).with_context(|| format!("Failed to read kanshi config: {}", path.display()))?;
+    // Without niri, outputs named by connector still import as written
+    let outputs = connect_backend(socket_path).and_then(|mut backend| backend.get_outputs()).unwrap_or_default();
+    let (imported, skipped) =
+        kanshi::parse(&content, &outputs).with_context(|| format!("Failed to parse kanshi config: {}", path.display()))?;
+    if imported.is_empty() {
+        bail!("No profiles in {}", path.display());
+    }
+    let mut profiles = OutputProfiles::load()?;
+    let names = imported.iter().map(|p| p.name.clone()).collect();
+    for profile in imported {
+        profiles.set(profile);
+    }
+    profiles.save()?;
+    Ok(KanshiImport { profiles: names, skipped })
+}
+
+/// The `--porcelain` form of a command's result: one JSON object with `ok`
+/// and either the report or the error with its causes
+pub fn porcelain<T: Serialize>(command: &str, result: &Result<T>) -> String {
+    let json = match result {
+        Ok(report) => {
+            let mut json = serde_json::json!({ "command": command, "ok": true });
+            if let (Some(json), serde_json::Value::Object(fields)) =
+                (json.as_object_mut(), serde_json::to_value(report).unwrap_or_default())
+            {
+                json.extend(fields);
+            }
+            json
+        }
+        Err(e) => serde_json::json!({
+            "command": command,
+            "ok": false,
+            "error": e.to_string(),
+            "causes": e.chain().skip(1).map(|c| c.to_string()).collect::<Vec<_>>(),
+        }),
+    };
+    json.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_porcelain() {
+        let report = OutputsReport {
+            moves: vec![OutputMove {
+                output: "DP-1".to_string(),
+                from: Position::new(100, 0),
+                to: Position::new(0, 0),
+            }],
+            summary: vec!["move DP-1 to 0,0".to_string()],
+            config: Some(PathBuf::from("/home/me/.config/niri/config.kdl")),
+            skipped: Vec::new(),
+        };
+        assert_eq!(
+            porcelain("outputs", &Ok(report)),
+            r#"{"command":"outputs","config":"/home/me/.config/niri/config.kdl","moves":[{"from":{"x":100,"y":0},"output":"DP-1","to":{"x":0,"y":0}}],"ok":true,"summary":["move DP-1 to 0,0"]}"#
+        );
+
+        let error: Result<OutputsReport> =
+            Err(anyhow::anyhow!("connection refused").context("Failed to connect to niri"));
+        assert_eq!(
+            porcelain("outputs", &error),
+            r#"{"causes":["connection refused"],"command":"outputs","error":"Failed to connect to niri","ok":false}"#
+        );
+    }
+}
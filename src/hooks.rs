@@ -0,0 +1,175 @@
+//! External commands run before and after saves and after reloads,
+//! configured with `hook` nodes in settings.kdl
+
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// How long a hook may run before it is killed and counts as failed, so a
+/// hung hook can't freeze the TUI
+pub const HOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// When a hook runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum HookEvent {
+    /// Before staged changes are written; a failing hook cancels the save
+    PreSave,
+    PostSave,
+    PostReload,
+}
+
+impl HookEvent {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "pre-save" => Some(HookEvent::PreSave),
+            "post-save" => Some(HookEvent::PostSave),
+            "post-reload" => Some(HookEvent::PostReload),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            HookEvent::PreSave => "pre-save",
+            HookEvent::PostSave => "post-save",
+            HookEvent::PostReload => "post-reload",
+        }
+    }
+}
+
+/// `hook "post-save" "notify-send" "niri config saved"`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hook {
+    pub event: HookEvent,
+    /// Program and its arguments, run without a shell
+    pub command: Vec<String>,
+}
+
+/// What a hook reads from stdin, as one JSON object
+#[derive(Debug, Serialize)]
+pub struct HookPayload<'a> {
+    pub event: HookEvent,
+    /// Category that was saved or reloaded; None for a reload of everything
+    pub category: Option<&'a str>,
+    pub config: Option<&'a Path>,
+    /// One line per change, as used for commit messages
+    pub changes: &'a [String],
+}
+
+/// Run the hooks registered for the payload's event in order, stopping at
+/// the first one that fails or runs longer than `HOOK_TIMEOUT`. Output is
+/// kept off the screen so it doesn't draw over the TUI; a failing hook's
+/// stderr ends up in the error.
+pub fn run_hooks(hooks: &[Hook], payload: &HookPayload) -> Result<()> {
+    run_hooks_within(hooks, payload, HOOK_TIMEOUT)
+}
+
+fn run_hooks_within(hooks: &[Hook], payload: &HookPayload, timeout: Duration) -> Result<()> {
+    let input = serde_json::to_string(payload)?;
+    for hook in hooks.iter().filter(|h| h.event == payload.event) {
+        let program = &hook.command[0];
+        let mut child = Command::new(program)
+            .args(&hook.command[1..])
+            .env("NIRIKIRI_EVENT", payload.event.name())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .with_context(|| format!("Failed to run {} hook {program}", payload.event.name()))?;
+        // Written on a thread so a hook that never reads a large payload
+        // can't block us before the deadline applies. A hook that doesn't
+        // read its input closes the pipe early; that's fine. Dropping the
+        // handle at the end gives the hook EOF.
+        if let Some(mut stdin) = child.stdin.take() {
+            let input = input.clone();
+            std::thread::spawn(move || {
+                let _ = stdin.write_all(input.as_bytes());
+                drop(stdin);
+            });
+        }
+        // Drained on a thread so a chatty hook can't fill the pipe and stall
+        let stderr = child.stderr.take().map(|mut pipe| {
+            std::thread::spawn(move || {
+                let mut text = String::new();
+                let _ = pipe.read_to_string(&mut text);
+                text
+            })
+        });
+
+        let deadline = Instant::now() + timeout;
+        let status = loop {
+            let status = child
+                .try_wait()
+                .with_context(|| format!("Failed to wait for {} hook {program}", payload.event.name()))?;
+            if let Some(status) = status {
+                break status;
+            }
+            if Instant::now() >= deadline {
+                let _ = child.kill();
+                let _ = child.wait();
+                bail!("{} hook {program} timed out after {}s", payload.event.name(), timeout.as_secs_f64());
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        };
+        if !status.success() {
+            let stderr = stderr.and_then(|thread| thread.join().ok()).unwrap_or_default();
+            let reason = stderr.lines().rev().find(|l| !l.trim().is_empty()).unwrap_or_default();
+            bail!("{} hook {program} {status}: {reason}", payload.event.name());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_hooks() {
+        let hook = |event, script: &str| Hook {
+            event,
+            command: vec!["sh".to_string(), "-c".to_string(), script.to_string()],
+        };
+        let changes = vec!["move DP-1 to 0,0".to_string()];
+        let payload = HookPayload {
+            event: HookEvent::PreSave,
+            category: Some("Outputs"),
+            config: Some(Path::new("/tmp/config.kdl")),
+            changes: &changes,
+        };
+
+        // The payload arrives on stdin; hooks for other events don't run
+        let hooks = [
+            hook(HookEvent::PostSave, "exit 1"),
+            hook(HookEvent::PreSave, "grep -q '\"event\":\"pre-save\".*\"changes\":\\[\"move DP-1 to 0,0\"\\]'"),
+        ];
+        run_hooks(&hooks, &payload).unwrap();
+
+        let hooks = [hook(HookEvent::PreSave, "echo \"dotfiles repo is dirty\" >&2; exit 3")];
+        let err = run_hooks(&hooks, &payload).unwrap_err();
+        assert_eq!(err.to_string(), "pre-save hook sh exit status: 3: dotfiles repo is dirty");
+
+        // A hung hook is killed at the deadline and fails the run
+        let hooks = [hook(HookEvent::PreSave, "sleep 5")];
+        let started = Instant::now();
+        let err = run_hooks_within(&hooks, &payload, Duration::from_millis(200)).unwrap_err();
+        assert_eq!(err.to_string(), "pre-save hook sh timed out after 0.2s");
+        assert!(started.elapsed() < Duration::from_secs(2));
+
+        // A payload bigger than the pipe buffer, to a hook that never reads
+        // it, still runs into the deadline rather than blocking the write
+        let changes: Vec<String> = (0..4000).map(|i| format!("move output-{i} to {i},0")).collect();
+        let payload = HookPayload { changes: &changes, ..payload };
+        assert!(serde_json::to_string(&payload).unwrap().len() > 64 * 1024);
+        let started = Instant::now();
+        let err = run_hooks_within(&hooks, &payload, Duration::from_millis(200)).unwrap_err();
+        assert_eq!(err.to_string(), "pre-save hook sh timed out after 0.2s");
+        assert!(started.elapsed() < Duration::from_secs(2));
+        let hooks = [hook(HookEvent::PreSave, "true")];
+        run_hooks_within(&hooks, &payload, Duration::from_secs(5)).unwrap();
+    }
+}
@@ -0,0 +1,220 @@
+//! Translations of user-facing text, gettext style.
+//!
+//! The English text is the message id: code wraps it in `tr("...")` and a
+//! catalog maps it to the translation. Catalogs are PO files read from
+//! `~/.config/nirikiri/locale/<lang>.po`; text without a translation (or
+//! with no catalog at all) shows in English.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use anyhow::{bail, Context, Result};
+
+/// Messages of one language
+#[derive(Debug, Clone, Default)]
+pub struct Catalog {
+    messages: HashMap<String, String>,
+}
+
+static CATALOG: OnceLock<Catalog> = OnceLock::new();
+
+impl Catalog {
+    /// Parse the subset of the PO format nirikiri uses: `msgid`/`msgstr`
+    /// pairs, continuation strings and comments. Empty translations are
+    /// skipped, like gettext's fuzzy-free untranslated entries.
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut messages = HashMap::new();
+        let mut msgid: Option<String> = None;
+        let mut msgstr: Option<String> = None;
+
+        let mut finish = |msgid: &mut Option<String>, msgstr: &mut Option<String>| {
+            if let (Some(id), Some(text)) = (msgid.take(), msgstr.take()) {
+                // The header entry has an empty id
+                if !id.is_empty() && !text.is_empty() {
+                    messages.insert(id, text);
+                }
+            }
+        };
+
+        for (number, line) in content.lines().enumerate() {
+            let line = line.trim();
+            let context = || format!("line {}", number + 1);
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(rest) = line.strip_prefix("msgid ") {
+                finish(&mut msgid, &mut msgstr);
+                msgid = Some(unquote(rest).with_context(context)?);
+            } else if let Some(rest) = line.strip_prefix("msgstr ") {
+                if msgid.is_none() {
+                    bail!("{}: msgstr without msgid", context());
+                }
+                msgstr = Some(unquote(rest).with_context(context)?);
+            } else if line.starts_with('"') {
+                let continued = unquote(line).with_context(context)?;
+                match (&mut msgstr, &mut msgid) {
+                    (Some(text), _) | (None, Some(text)) => text.push_str(&continued),
+                    (None, None) => bail!("{}: string outside an entry", context()),
+                }
+            } else {
+                bail!("{}: unsupported line '{line}'", context());
+            }
+        }
+        finish(&mut msgid, &mut msgstr);
+        Ok(Self { messages })
+    }
+
+    /// Translation of `msgid`, or `msgid` itself when there is none
+    pub fn get<'a>(&'a self, msgid: &'a str) -> &'a str {
+        self.messages.get(msgid).map_or(msgid, String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+}
+
+/// Parse a quoted PO string, handling the usual C escapes
+fn unquote(value: &str) -> Result<String> {
+    let Some(inner) = value.trim().strip_prefix('"').and_then(|v| v.strip_suffix('"')) else {
+        bail!("expected a quoted string, got {value}");
+    };
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some(c @ ('"' | '\\')) => out.push(c),
+            other => bail!("unknown escape \\{}", other.map(String::from).unwrap_or_default()),
+        }
+    }
+    Ok(out)
+}
+
+/// Language code from a locale name, e.g. "pt_BR.UTF-8" gives "pt_BR"
+fn language(locale: &str) -> Option<&str> {
+    let language = locale.split(['.', '@']).next().unwrap_or_default();
+    (!language.is_empty() && language != "C" && language != "POSIX").then_some(language)
+}
+
+/// The language to translate to: `preferred` (from settings) if given,
+/// otherwise the first of LC_ALL, LC_MESSAGES and LANG that is set
+pub fn detect_language(preferred: Option<&str>) -> Option<String> {
+    if let Some(preferred) = preferred {
+        return language(preferred).map(str::to_string);
+    }
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .and_then(|value| language(&value).map(str::to_string))
+}
+
+fn locale_dir() -> Option<PathBuf> {
+    Some(dirs::config_dir()?.join("nirikiri").join("locale"))
+}
+
+/// Load the catalog for `language`, trying "pt_BR.po" before "pt.po".
+/// Ok(None) when there is no catalog for it.
+pub fn load_catalog(language: &str) -> Result<Option<Catalog>> {
+    let Some(dir) = locale_dir() else {
+        return Ok(None);
+    };
+    let base = language.split('_').next().unwrap_or(language);
+    for name in [language, base] {
+        let path = dir.join(format!("{name}.po"));
+        if path.exists() {
+            let content = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read translations: {}", path.display()))?;
+            let catalog = Catalog::parse(&content)
+                .with_context(|| format!("Failed to parse translations: {}", path.display()))?;
+            return Ok(Some(catalog));
+        }
+    }
+    Ok(None)
+}
+
+/// Install the catalog `tr` translates with. Only the first call has an
+/// effect; without one, all text stays in English.
+pub fn install(catalog: Catalog) {
+    let _ = CATALOG.set(catalog);
+}
+
+/// Translate `msgid`
+pub fn tr(msgid: &str) -> &str {
+    match CATALOG.get() {
+        Some(catalog) => catalog.get(msgid),
+        None => msgid,
+    }
+}
+
+/// Translate `msgid` and fill in its `{name}` placeholders. Translations
+/// may reorder the placeholders.
+pub fn tr_args(msgid: &str, args: &[(&str, &dyn std::fmt::Display)]) -> String {
+    let mut text = tr(msgid).to_string();
+    for (name, value) in args {
+        text = text.replace(&format!("{{{name}}}"), &value.to_string());
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_catalog() {
+        let catalog = Catalog::parse(
+            r#"
+# German translations
+msgid ""
+msgstr ""
+"Content-Type: text/plain; charset=UTF-8\n"
+
+msgid "Quit"
+msgstr "Beenden"
+
+msgid "Edit: {name}"
+msgstr "Bearbeiten: "
+"{name}"
+
+msgid "Untranslated"
+msgstr ""
+"#,
+        )
+        .unwrap();
+        assert_eq!(catalog.len(), 2);
+        assert_eq!(catalog.get("Quit"), "Beenden");
+        assert_eq!(catalog.get("Edit: {name}"), "Bearbeiten: {name}");
+        assert_eq!(catalog.get("Untranslated"), "Untranslated");
+        assert_eq!(catalog.get("Save"), "Save");
+
+        assert!(Catalog::parse("msgstr \"x\"").is_err());
+        assert!(Catalog::parse("msgid \"a\\q\"").is_err());
+    }
+
+    #[test]
+    fn test_language() {
+        assert_eq!(detect_language(Some("de_DE.UTF-8")).as_deref(), Some("de_DE"));
+        assert_eq!(detect_language(Some("fr")).as_deref(), Some("fr"));
+        assert_eq!(detect_language(Some("C")), None);
+        assert_eq!(language("sr_RS@latin"), Some("sr_RS"));
+    }
+
+    #[test]
+    fn test_tr_args() {
+        // No catalog is installed in tests, so this is the English text
+        assert_eq!(tr_args("Edit: {name}", &[("name", &"gaps")]), "Edit: gaps");
+        assert_eq!(tr_args("{n} of {total}", &[("n", &2), ("total", &5)]), "2 of 5");
+    }
+}
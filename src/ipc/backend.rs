@@ -0,0 +1,39 @@
+use anyhow::Result;
+use std::path::Path;
+
+use super::NiriClient;
+use crate::model::{OutputMode, OutputState, OutputTransform, Position};
+
+/// Compositor operations the output arrangement UI relies on.
+/// niri is the only implementation today; other compositors can be added
+/// without touching the UI.
+pub trait CompositorBackend {
+    /// Query all outputs and their current state
+    fn get_outputs(&mut self) -> Result<Vec<OutputState>>;
+
+    /// Apply a position to an output without writing the config
+    fn preview_position(&mut self, name: &str, position: Position) -> Result<()>;
+
+    /// Switch an output to another mode without writing the config
+    fn preview_mode(&mut self, name: &str, mode: &OutputMode) -> Result<()>;
+
+    /// Change an output's scale without writing the config
+    fn preview_scale(&mut self, name: &str, scale: f64) -> Result<()>;
+
+    /// Rotate or flip an output without writing the config
+    fn preview_transform(&mut self, name: &str, transform: OutputTransform) -> Result<()>;
+
+    /// Turn variable refresh rate on or off without writing the config
+    fn preview_vrr(&mut self, name: &str, vrr: bool) -> Result<()>;
+
+    /// Turn an output on or off without writing the config
+    fn preview_enabled(&mut self, name: &str, enabled: bool) -> Result<()>;
+
+    /// Ask the compositor to re-read its config file
+    fn reload_config(&mut self) -> Result<()>;
+}
+
+/// Connect to the default backend (niri)
+pub fn connect_backend(socket_path: Option<&Path>) -> Result<Box<dyn CompositorBackend>> {
+    Ok(Box::new(NiriClient::connect(socket_path)?))
+}
@@ -1,6 +1,10 @@
 use anyhow::{Context, Result, bail};
-use niri_ipc::{socket::Socket, Request, Response, Output, OutputConfigChanged, ConfiguredPosition, PositionToSet, Action};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use niri_ipc::{socket::Socket, Event, Request, Response, Output, OutputConfigChanged, ConfiguredPosition, PositionToSet, Action, ModeToSet, ConfiguredMode, ScaleToSet, VrrToSet};
 
+use super::CompositorBackend;
 use crate::model::{OutputMode, OutputState, OutputTransform, Position, Size};
 
 /// Client wrapper for niri IPC
@@ -8,26 +12,59 @@ pub struct NiriClient {
     socket: Socket,
 }
 
-impl NiriClient {
-    pub fn connect() -> Result<Self> {
-        let socket = Socket::connect().context("Failed to connect to niri socket. Is niri running?")?;
-        Ok(Self { socket })
-    }
+/// Environment variable that points nirikiri at a different niri socket
+/// without changing NIRI_SOCKET for child processes
+pub const SOCKET_OVERRIDE_ENV: &str = "NIRIKIRI_SOCKET";
 
-    /// Query all outputs from niri
-    pub fn get_outputs(&mut self) -> Result<Vec<OutputState>> {
-        let reply = self.socket.send(Request::Outputs).context("Failed to send Outputs request")?;
-        let response = reply.map_err(|e| anyhow::anyhow!("niri error: {e}"))?;
+/// Pick the socket to use: the --socket flag, then $NIRIKIRI_SOCKET, then $NIRI_SOCKET
+pub fn resolve_socket_path(flag: Option<PathBuf>) -> Option<PathBuf> {
+    flag.or_else(|| std::env::var_os(SOCKET_OVERRIDE_ENV).map(PathBuf::from))
+        .or_else(|| std::env::var_os(niri_ipc::socket::SOCKET_PATH_ENV).map(PathBuf::from))
+}
 
-        match response {
-            Response::Outputs(outputs) => {
-                outputs
-                    .into_values()
-                    .map(|o| self.convert_output(o))
-                    .collect()
+/// Follow niri's event stream on a background thread, sending a message
+/// each time an output is connected or disconnected. niri has no output
+/// events, but every connected output holds at least one workspace, so the
+/// outputs named in `WorkspacesChanged` follow hotplugs.
+pub fn watch_outputs(socket_path: Option<&Path>) -> Result<Receiver<()>> {
+    let Some(path) = socket_path else {
+        bail!("No niri socket to watch");
+    };
+    let mut socket = Socket::connect_to(path)
+        .with_context(|| format!("Failed to connect to niri socket {}", path.display()))?;
+    let reply = socket.send(Request::EventStream).context("Failed to send EventStream request")?;
+    reply.map_err(|e| anyhow::anyhow!("niri error: {e}"))?;
+
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut read_event = socket.read_events();
+        // The stream opens with the current workspaces, which match the
+        // outputs already loaded
+        let mut outputs: Option<BTreeSet<String>> = None;
+        while let Ok(event) = read_event() {
+            let Event::WorkspacesChanged { workspaces } = event else {
+                continue;
+            };
+            let current: BTreeSet<String> = workspaces.into_iter().filter_map(|w| w.output).collect();
+            let changed = outputs.as_ref().is_some_and(|previous| *previous != current);
+            outputs = Some(current);
+            // Stop once the app has dropped the receiver
+            if changed && sender.send(()).is_err() {
+                break;
             }
-            other => bail!("Unexpected response: {other:?}"),
         }
+    });
+    Ok(receiver)
+}
+
+impl NiriClient {
+    pub fn connect(socket_path: Option<&Path>) -> Result<Self> {
+        let Some(path) = socket_path else {
+            bail!("No niri socket: NIRI_SOCKET is not set and --socket was not given. Is niri running?");
+        };
+        let socket = Socket::connect_to(path)
+            .with_context(|| format!("Failed to connect to niri socket {}", path.display()))?;
+        Ok(Self { socket })
     }
 
     fn convert_output(&self, output: Output) -> Result<OutputState> {
@@ -78,16 +115,71 @@ impl NiriClient {
             position,
             logical_size,
             physical_size,
+            size_mm: output.physical_size.map(|(w, h)| Size::new(w, h)),
             enabled,
+            vrr: output.vrr_enabled,
             connected: true, // If we get it from IPC, it's connected
             configured: false, // Will be set later when merging with config
             make: output.make,
             model: output.model,
+            source: None,
+            duplicate_sources: Vec::new(),
+            background_color: None,
+            backdrop_color: None,
+            focus_at_startup: false,
         })
     }
 
+    /// Query the running niri version (e.g., "25.11 (b35bcae)")
+    pub fn version(&mut self) -> Result<String> {
+        let reply = self.socket.send(Request::Version).context("Failed to send Version request")?;
+        let response = reply.map_err(|e| anyhow::anyhow!("niri error: {e}"))?;
+
+        match response {
+            Response::Version(version) => Ok(version),
+            other => bail!("Unexpected response: {other:?}"),
+        }
+    }
+
+    /// Send an output action and check niri applied it
+    fn send_output_action(&mut self, name: &str, action: niri_ipc::OutputAction) -> Result<()> {
+        let request = Request::Output {
+            output: name.to_string(),
+            action,
+        };
+
+        let reply = self.socket.send(request).context("Failed to send Output request")?;
+        let response = reply.map_err(|e| anyhow::anyhow!("niri error: {e}"))?;
+
+        match response {
+            Response::OutputConfigChanged(OutputConfigChanged::Applied) => Ok(()),
+            Response::OutputConfigChanged(OutputConfigChanged::OutputWasMissing) => {
+                bail!("niri has no output named {name}")
+            }
+            other => bail!("Unexpected response: {other:?}"),
+        }
+    }
+}
+
+impl CompositorBackend for NiriClient {
+    /// Query all outputs from niri
+    fn get_outputs(&mut self) -> Result<Vec<OutputState>> {
+        let reply = self.socket.send(Request::Outputs).context("Failed to send Outputs request")?;
+        let response = reply.map_err(|e| anyhow::anyhow!("niri error: {e}"))?;
+
+        match response {
+            Response::Outputs(outputs) => {
+                outputs
+                    .into_values()
+                    .map(|o| self.convert_output(o))
+                    .collect()
+            }
+            other => bail!("Unexpected response: {other:?}"),
+        }
+    }
+
     /// Reload niri config
-    pub fn reload_config(&mut self) -> Result<()> {
+    fn reload_config(&mut self) -> Result<()> {
         let reply = self.socket.send(Request::Action(Action::LoadConfigFile {}))
             .context("Failed to send LoadConfigFile request")?;
         reply.map_err(|e| anyhow::anyhow!("niri error: {e}"))?;
@@ -95,26 +187,49 @@ impl NiriClient {
     }
 
     /// Preview output position change via IPC
-    pub fn preview_position(&mut self, name: &str, position: Position) -> Result<OutputConfigChanged> {
+    fn preview_position(&mut self, name: &str, position: Position) -> Result<()> {
         let action = niri_ipc::OutputAction::Position {
             position: PositionToSet::Specific(ConfiguredPosition {
                 x: position.x,
                 y: position.y,
             }),
         };
+        self.send_output_action(name, action)
+    }
 
-        let request = Request::Output {
-            output: name.to_string(),
-            action,
+    /// Preview output mode change via IPC
+    fn preview_mode(&mut self, name: &str, mode: &OutputMode) -> Result<()> {
+        let action = niri_ipc::OutputAction::Mode {
+            mode: ModeToSet::Specific(ConfiguredMode {
+                width: u16::try_from(mode.width).context("Mode width out of range")?,
+                height: u16::try_from(mode.height).context("Mode height out of range")?,
+                refresh: Some(mode.refresh_rate),
+            }),
         };
+        self.send_output_action(name, action)
+    }
 
-        let reply = self.socket.send(request).context("Failed to send Output request")?;
-        let response = reply.map_err(|e| anyhow::anyhow!("niri error: {e}"))?;
+    /// Preview output scale change via IPC
+    fn preview_scale(&mut self, name: &str, scale: f64) -> Result<()> {
+        let action = niri_ipc::OutputAction::Scale { scale: ScaleToSet::Specific(scale) };
+        self.send_output_action(name, action)
+    }
 
-        match response {
-            Response::OutputConfigChanged(changed) => Ok(changed),
-            other => bail!("Unexpected response: {other:?}"),
-        }
+    /// Preview output transform change via IPC
+    fn preview_transform(&mut self, name: &str, transform: OutputTransform) -> Result<()> {
+        let action = niri_ipc::OutputAction::Transform { transform: transform.to_niri() };
+        self.send_output_action(name, action)
     }
-}
 
+    /// Preview variable refresh rate change via IPC
+    fn preview_vrr(&mut self, name: &str, vrr: bool) -> Result<()> {
+        let action = niri_ipc::OutputAction::Vrr { vrr: VrrToSet { vrr, on_demand: false } };
+        self.send_output_action(name, action)
+    }
+
+    /// Turn an output on or off via IPC
+    fn preview_enabled(&mut self, name: &str, enabled: bool) -> Result<()> {
+        let action = if enabled { niri_ipc::OutputAction::On } else { niri_ipc::OutputAction::Off };
+        self.send_output_action(name, action)
+    }
+}
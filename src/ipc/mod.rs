@@ -1,3 +1,5 @@
+pub mod backend;
 pub mod client;
 
-pub use client::NiriClient;
+pub use backend::{connect_backend, CompositorBackend};
+pub use client::{resolve_socket_path, watch_outputs, NiriClient};
@@ -0,0 +1,348 @@
+//! Output profiles in kanshi's config format, so a layout can be shared
+//! with kanshi and the wlroots compositors it drives
+
+use anyhow::{bail, Result};
+
+use crate::model::{OutputState, OutputTransform, Position};
+use crate::output_profiles::{OutputProfile, ProfileOutput};
+
+/// The profile as a kanshi `profile` block. Outputs are matched by
+/// connector, since kanshi's descriptions need the serial number too.
+pub fn to_kanshi(profile: &OutputProfile) -> String {
+    let mut kanshi = format!("profile {} {{\n", quoted(&profile.name));
+    for output in &profile.outputs {
+        kanshi.push_str(&format!("    output {}", quoted(&output.name)));
+        if output.enabled {
+            kanshi.push_str(" enable");
+            if let Some(mode) = &output.mode {
+                kanshi.push_str(&format!(" mode {mode}Hz"));
+            }
+            kanshi.push_str(&format!(
+                " position {},{} scale {} transform {}",
+                output.position.x,
+                output.position.y,
+                output.scale,
+                output.transform.as_str()
+            ));
+        } else {
+            kanshi.push_str(" disable");
+        }
+        kanshi.push('\n');
+    }
+    kanshi.push_str("}\n");
+    kanshi
+}
+
+/// Read the profiles of a kanshi config, with a note for each part that
+/// can't be taken over. Outputs given by description are looked up among
+/// the connected `outputs`, which also give modes niri's exact spelling.
+pub fn parse(content: &str, outputs: &[OutputState]) -> Result<(Vec<OutputProfile>, Vec<String>)> {
+    let mut tokens = tokenize(content)?.into_iter();
+    let mut profiles = Vec::new();
+    let mut notes = Vec::new();
+    let mut depth = 0;
+    while let Some(token) = tokens.next() {
+        match token {
+            Token::Word(word) if word == "profile" && depth == 0 => {
+                let mut name = None;
+                loop {
+                    match tokens.next() {
+                        Some(Token::Word(word)) if name.is_none() => name = Some(word),
+                        Some(Token::Open) => break,
+                        _ => bail!("Expected {{ after profile {}", name.unwrap_or_default()),
+                    }
+                }
+                // kanshi allows profiles without a name
+                let name = name.unwrap_or_else(|| format!("kanshi-{}", profiles.len() + 1));
+                profiles.push(parse_profile(name, &mut tokens, outputs, &mut notes)?);
+            }
+            // Global output defaults, includes and other blocks have no
+            // place in a profile
+            Token::Open => depth += 1,
+            Token::Close => depth -= 1,
+            _ => {}
+        }
+    }
+    Ok((profiles, notes))
+}
+
+fn parse_profile(
+    name: String,
+    tokens: &mut impl Iterator<Item = Token>,
+    outputs: &[OutputState],
+    notes: &mut Vec<String>,
+) -> Result<OutputProfile> {
+    let mut profile = OutputProfile { name, outputs: Vec::new(), auto_apply: false };
+    let mut line = Vec::new();
+    loop {
+        let token = tokens.next();
+        match token {
+            Some(Token::Word(word)) => line.push(word),
+            Some(Token::Newline) | Some(Token::Close) => {
+                if line.first().map(String::as_str) == Some("output") {
+                    if let Some(output) = parse_output(&line[1..], outputs, &profile.name, notes) {
+                        profile.outputs.push(output);
+                    }
+                }
+                line.clear();
+                if token == Some(Token::Close) {
+                    return Ok(profile);
+                }
+            }
+            Some(Token::Open) => bail!("Unexpected {{ in profile {}", profile.name),
+            None => bail!("Profile {} is missing its closing }}", profile.name),
+        }
+    }
+}
+
+/// One `output <criteria> <directives>` line of a profile
+fn parse_output(words: &[String], outputs: &[OutputState], profile: &str, notes: &mut Vec<String>) -> Option<ProfileOutput> {
+    let (criteria, directives) = words.split_first()?;
+    let connected = outputs.iter().filter(|o| o.connected).find(|o| {
+        let description = format!("{} {}", o.make, o.model);
+        o.name == *criteria || *criteria == description || criteria.starts_with(&format!("{description} "))
+    });
+    let mut output = match connected {
+        Some(o) => ProfileOutput {
+            name: o.name.clone(),
+            make: o.make.clone(),
+            model: o.model.clone(),
+            enabled: true,
+            position: o.position,
+            mode: o.current_mode().map(|m| m.config_string()),
+            scale: o.scale,
+            transform: o.transform,
+        },
+        // A connector name can be taken as written; a description can't
+        // be turned into one
+        None if !criteria.contains([' ', '*']) => ProfileOutput {
+            name: criteria.clone(),
+            make: String::new(),
+            model: String::new(),
+            enabled: true,
+            position: Position::new(0, 0),
+            mode: None,
+            scale: 1.0,
+            transform: OutputTransform::Normal,
+        },
+        None => {
+            notes.push(format!("{profile}: no connected output matches {criteria:?}"));
+            return None;
+        }
+    };
+
+    let mut directives = directives.iter();
+    while let Some(directive) = directives.next() {
+        let mut value = || directives.next().map(String::as_str).unwrap_or_default();
+        match directive.as_str() {
+            "enable" => output.enabled = true,
+            "disable" => output.enabled = false,
+            "mode" => {
+                let mut mode = value();
+                if mode == "--custom" {
+                    mode = value();
+                }
+                output.mode = Some(resolve_mode(mode, connected));
+            }
+            "position" => match value().split_once(',').map(|(x, y)| (x.parse(), y.parse())) {
+                Some((Ok(x), Ok(y))) => output.position = Position::new(x, y),
+                _ => notes.push(format!("{profile}: {} has an invalid position", output.name)),
+            },
+            "scale" => match value().parse() {
+                Ok(scale) => output.scale = scale,
+                Err(_) => notes.push(format!("{profile}: {} has an invalid scale", output.name)),
+            },
+            "transform" => {
+                let name = value();
+                match OutputTransform::parse(name) {
+                    Some(transform) => output.transform = transform,
+                    None => notes.push(format!("{profile}: {} has an unknown transform {name:?}", output.name)),
+                }
+            }
+            "adaptive_sync" | "alias" => {
+                value();
+            }
+            other => notes.push(format!("{profile}: ignored {other} for {}", output.name)),
+        }
+    }
+    Some(output)
+}
+
+/// A kanshi mode such as `2560x1440@60Hz` as niri spells it, using the
+/// output's own mode with the nearest refresh rate when it has one
+fn resolve_mode(mode: &str, output: Option<&OutputState>) -> String {
+    let mode = mode.trim_end_matches("Hz");
+    let (size, refresh) = match mode.split_once('@') {
+        Some((size, refresh)) => (size, refresh.parse::<f64>().ok()),
+        None => (mode, None),
+    };
+    let Some((width, height)) = size.split_once('x').and_then(|(w, h)| Some((w.parse::<u32>().ok()?, h.parse::<u32>().ok()?)))
+    else {
+        return mode.to_string();
+    };
+    let candidates = output
+        .map(|o| o.modes.iter().filter(|m| m.width == width && m.height == height).collect::<Vec<_>>())
+        .unwrap_or_default();
+    let best = match refresh {
+        Some(refresh) => candidates
+            .into_iter()
+            .filter(|m| (m.refresh_rate - refresh).abs() < 0.5)
+            .min_by(|a, b| (a.refresh_rate - refresh).abs().total_cmp(&(b.refresh_rate - refresh).abs())),
+        None => candidates.iter().find(|m| m.is_preferred).or(candidates.first()).copied(),
+    };
+    best.map_or_else(|| mode.to_string(), |m| m.config_string())
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Word(String),
+    Open,
+    Close,
+    Newline,
+}
+
+/// Split a kanshi config into words, quoted strings and braces, dropping
+/// `#` comments
+fn tokenize(content: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\n' => tokens.push(Token::Newline),
+            '{' => tokens.push(Token::Open),
+            '}' => tokens.push(Token::Close),
+            '#' => {
+                while chars.next_if(|&c| c != '\n').is_some() {}
+            }
+            '"' => {
+                let mut word = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => word.extend(chars.next()),
+                        Some(c) => word.push(c),
+                        None => bail!("Unterminated string {word:?}"),
+                    }
+                }
+                tokens.push(Token::Word(word));
+            }
+            c if c.is_whitespace() => {}
+            c => {
+                let mut word = c.to_string();
+                while let Some(c) = chars.next_if(|c| !c.is_whitespace() && !matches!(c, '{' | '}' | '"' | '#')) {
+                    word.push(c);
+                }
+                tokens.push(Token::Word(word));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+/// A word as kanshi reads it back, quoted when it has spaces or symbols
+fn quoted(word: &str) -> String {
+    if !word.is_empty() && word.chars().all(|c| c.is_alphanumeric() || "-_.".contains(c)) {
+        word.to_string()
+    } else {
+        format!("{word:?}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{OutputMode, Size};
+
+    fn output(name: &str, model: &str) -> OutputState {
+        OutputState {
+            name: name.to_string(),
+            modes: vec![
+                OutputMode { width: 2560, height: 1440, refresh_rate: 59.951, is_preferred: true },
+                OutputMode { width: 2560, height: 1440, refresh_rate: 143.912, is_preferred: false },
+            ],
+            current_mode_index: Some(0),
+            scale: 1.0,
+            transform: OutputTransform::Normal,
+            position: Position::new(0, 0),
+            logical_size: Size::new(2560, 1440),
+            physical_size: Size::new(2560, 1440),
+            size_mm: None,
+            enabled: true,
+            vrr: false,
+            connected: true,
+            configured: true,
+            make: "Dell Inc.".to_string(),
+            model: model.to_string(),
+            source: None,
+            duplicate_sources: Vec::new(),
+            background_color: None,
+            backdrop_color: None,
+            focus_at_startup: false,
+        }
+    }
+
+    #[test]
+    fn test_kanshi_round_trip() {
+        let outputs = vec![output("DP-1", "U2719D"), output("HDMI-A-1", "P2419H")];
+        let mut desk = OutputProfile::capture("my desk", &outputs, &Default::default());
+        desk.outputs[1].position = Position::new(2560, -200);
+        desk.outputs[1].scale = 1.25;
+        desk.outputs[1].transform = OutputTransform::Rotate90;
+        desk.outputs[0].enabled = false;
+
+        let kanshi = to_kanshi(&desk);
+        assert_eq!(
+            kanshi,
+            "profile \"my desk\" {\n    output DP-1 disable\n    output HDMI-A-1 enable mode 2560x1440@59.951Hz \
+             position 2560,-200 scale 1.25 transform 90\n}\n"
+        );
+        let (profiles, notes) = parse(&kanshi, &outputs).unwrap();
+        assert!(notes.is_empty());
+        assert_eq!(profiles, vec![desk]);
+    }
+
+    #[test]
+    fn test_kanshi_import() {
+        let outputs = vec![output("DP-1", "U2719D"), output("HDMI-A-1", "P2419H")];
+        let config = r#"
+            # docked at the office
+            output "Dell Inc. U2719D ABC123" scale 1.5
+
+            profile docked {
+                output "Dell Inc. U2719D ABC123" mode 2560x1440@144Hz position 0,0
+                output eDP-1 disable
+                output "Goldstar Company Ltd LG HDR 4K" enable
+                output HDMI-A-1 position 2560,0 transform flipped-90 adaptive_sync on
+                exec notify-send docked
+            }
+            profile {
+                output * enable
+            }
+        "#;
+        let (profiles, notes) = parse(config, &outputs).unwrap();
+        assert_eq!(profiles.len(), 2);
+        let docked = &profiles[0];
+        assert_eq!(docked.name, "docked");
+        let names: Vec<_> = docked.outputs.iter().map(|o| o.name.as_str()).collect();
+        assert_eq!(names, vec!["DP-1", "eDP-1", "HDMI-A-1"]);
+        // Described outputs resolve to their connector and niri's mode
+        assert_eq!(docked.outputs[0].model, "U2719D");
+        assert_eq!(docked.outputs[0].mode.as_deref(), Some("2560x1440@143.912"));
+        assert!(!docked.outputs[1].enabled);
+        assert_eq!(docked.outputs[2].position, Position::new(2560, 0));
+        assert_eq!(docked.outputs[2].transform, OutputTransform::Flipped90);
+        assert_eq!(profiles[1].name, "kanshi-2");
+        assert!(profiles[1].outputs.is_empty());
+        assert_eq!(
+            notes,
+            vec![
+                "docked: no connected output matches \"Goldstar Company Ltd LG HDR 4K\"",
+                "kanshi-2: no connected output matches \"*\"",
+            ]
+        );
+
+        assert_eq!(resolve_mode("1920x1080", None), "1920x1080");
+        assert!(parse("profile broken {\n output DP-1\n", &outputs).is_err());
+        assert!(parse("profile \"open", &outputs).is_err());
+    }
+}
@@ -0,0 +1,29 @@
+//! nirikiri: a TUI for configuring the niri Wayland compositor.
+//!
+//! The binary in main.rs only owns the terminal; everything else lives here
+//! so benchmarks can drive the config writers directly.
+
+pub mod app;
+pub mod binding_tags;
+pub mod category;
+pub mod cli;
+pub mod color_history;
+pub mod config;
+pub mod diagnostics;
+pub mod error;
+pub mod headless;
+pub mod hooks;
+pub mod i18n;
+pub mod ipc;
+pub mod kanshi;
+pub mod message;
+pub mod model;
+pub mod output_labels;
+pub mod output_profiles;
+pub mod recent_configs;
+pub mod settings;
+pub mod state_file;
+pub mod ui_state;
+pub mod update;
+pub mod view;
+pub mod widgets;
@@ -1,14 +1,4 @@
-mod app;
-mod category;
-mod config;
-mod ipc;
-mod message;
-mod model;
-mod update;
-mod view;
-mod widgets;
-
-use anyhow::Result;
+use anyhow::{Context, Result};
 use crossterm::{
     event::{DisableMouseCapture, EnableMouseCapture},
     execute,
@@ -16,10 +6,78 @@ use crossterm::{
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
 use std::io;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::Instant;
 
-use app::App;
+use nirikiri::app::App;
+use nirikiri::cli::{self, Cli, USAGE};
+use nirikiri::config;
+use nirikiri::headless;
+use nirikiri::message::Message;
+use nirikiri::ipc;
+use nirikiri::model::SourceLocation;
 
 fn main() -> Result<()> {
+    let cli = match Cli::parse(std::env::args().skip(1)) {
+        Ok(cli) => cli,
+        // Scripts asking for JSON get argument errors as JSON too, without the usage text
+        Err(e) if e.porcelain => {
+            let message = e.error.to_string().split("\n\n").next().unwrap_or_default().to_string();
+            println!("{}", headless::porcelain::<()>("arguments", &Err(anyhow::anyhow!(message))));
+            std::process::exit(2);
+        }
+        Err(e) => return Err(e.error),
+    };
+    if cli.help {
+        print!("{USAGE}");
+        return Ok(());
+    }
+    let socket_path = ipc::resolve_socket_path(cli.socket);
+    if let Some(path) = cli.config {
+        // Absolute, so the recent configs list works from any directory
+        let path = std::path::absolute(&path).with_context(|| format!("Invalid config path: {}", path.display()))?;
+        config::set_config_path(Some(path));
+    }
+
+    if let Some(cli::Command::KanshiExport(name)) = &cli.command {
+        let result = headless::export_kanshi(socket_path.as_deref(), name.as_deref());
+        if let Some(export) = run_headless("kanshi", result, cli.porcelain) {
+            print!("{}", export.kanshi);
+        }
+        return Ok(());
+    }
+    if let Some(cli::Command::KanshiImport(path)) = &cli.command {
+        let result = headless::import_kanshi(socket_path.as_deref(), path);
+        let Some(report) = run_headless("kanshi", result, cli.porcelain) else {
+            return Ok(());
+        };
+        for name in report.profiles {
+            println!("Saved output profile {name}");
+        }
+        for note in report.skipped {
+            eprintln!("Skipped: {note}");
+        }
+        return Ok(());
+    }
+
+    if let Some(cli::Command::Outputs(command)) = &cli.command {
+        let result = headless::run_outputs(socket_path.as_deref(), command);
+        let Some(report) = run_headless("outputs", result, cli.porcelain) else {
+            return Ok(());
+        };
+        if report.summary.is_empty() {
+            println!("Outputs are already in place; config left unchanged");
+        }
+        for change in report.summary {
+            println!("{change}");
+        }
+        for note in report.skipped {
+            eprintln!("Skipped: {note}");
+        }
+        return Ok(());
+    }
+
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -28,7 +86,7 @@ fn main() -> Result<()> {
     let mut terminal = Terminal::new(backend)?;
 
     // Run app
-    let result = run_app(&mut terminal);
+    let result = run_app(&mut terminal, socket_path, cli.accessible, cli.debug);
 
     // Restore terminal
     disable_raw_mode()?;
@@ -47,18 +105,80 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()> {
-    let mut app = App::new()?;
+/// Report a one-shot command's failure and exit with status 1. With
+/// `--porcelain` the result is printed as JSON and None is returned;
+/// otherwise the report is handed back for the caller to print.
+fn run_headless<T: serde::Serialize>(name: &str, result: Result<T>, porcelain: bool) -> Option<T> {
+    if porcelain {
+        println!("{}", headless::porcelain(name, &result));
+        if result.is_err() {
+            std::process::exit(1);
+        }
+        return None;
+    }
+    match result {
+        Ok(report) => Some(report),
+        Err(e) => {
+            eprintln!("Error: {e:#}");
+            std::process::exit(1);
+        }
+    }
+}
+
+fn run_app(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    socket_path: Option<PathBuf>,
+    accessible: bool,
+    debug: bool,
+) -> Result<()> {
+    let mut app = App::new(socket_path, accessible)?;
+    if debug {
+        app.update(Message::ToggleDebugOverlay);
+    }
+
+    let result = event_loop(terminal, &mut app);
+    // Quitting restores the outputs itself; an error mustn't leave a
+    // preview running either
+    if result.is_err() {
+        app.restore_outputs();
+    }
+    result
+}
 
+fn event_loop(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, app: &mut App) -> Result<()> {
     loop {
+        let tick = Instant::now();
+
         // Draw (need mutable borrow for scroll updates)
         terminal.draw(|f| app.draw(f))?;
+        let drawn = tick.elapsed();
 
-        // Handle input
-        if let Some(msg) = app.handle_input()? {
+        // Handle input; the wait for it doesn't count as work
+        let input = app.handle_input()?;
+        let waited = tick.elapsed() - drawn;
+        if let Some(msg) = input {
             app.update(msg);
         }
 
+        app.poll_config_load();
+        app.poll_dry_run();
+        app.poll_preview_timeout();
+        app.poll_identify();
+        app.poll_live_preview();
+        app.poll_output_events();
+        app.poll_nested_preview();
+
+        if let Some(diagnostics) = &mut app.diagnostics {
+            diagnostics.record_frame(drawn);
+            diagnostics.record_tick(tick, tick.elapsed() - waited);
+        }
+
+        // Hand the terminal over to an external editor if requested
+        if let Some(loc) = app.editor_request.take() {
+            let result = open_in_editor(terminal, &loc);
+            app.editor_finished(result);
+        }
+
         // Check quit
         if app.should_quit {
             break;
@@ -67,3 +187,39 @@ fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()>
 
     Ok(())
 }
+
+/// Suspend the TUI, open the config in $VISUAL/$EDITOR at the given line, then restore.
+/// Returns whether the file changed on disk.
+fn open_in_editor(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    loc: &SourceLocation,
+) -> Result<bool> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or("vi");
+
+    let before = std::fs::read(&loc.path).ok();
+
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen, DisableMouseCapture)?;
+
+    let status = Command::new(program)
+        .args(parts)
+        .arg(format!("+{}", loc.line))
+        .arg(&loc.path)
+        .status()
+        .with_context(|| format!("Failed to launch {program}"));
+
+    enable_raw_mode()?;
+    execute!(terminal.backend_mut(), EnterAlternateScreen, EnableMouseCapture)?;
+    terminal.clear()?;
+
+    let status = status?;
+    if !status.success() {
+        anyhow::bail!("{program} exited with {status}");
+    }
+
+    Ok(std::fs::read(&loc.path).ok() != before)
+}
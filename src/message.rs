@@ -1,6 +1,10 @@
 use crate::category::Category;
+use crate::error::AppError;
+use crate::model::{Arrangement, BindingFlag, ConflictChoice};
 
-/// All message types for the TEA architecture
+/// All message types for the TEA architecture. Messages that only touch
+/// one category live in that category's enum and are routed to its update
+/// function; the rest are app-wide.
 #[derive(Debug, Clone)]
 #[allow(dead_code)] // Some variants are for future features
 pub enum Message {
@@ -8,66 +12,243 @@ pub enum Message {
     Quit,
     SwitchCategory(Category),
 
+    // Per-category messages
+    Output(OutputMessage),
+    Keybindings(KeybindingsMessage),
+    Appearance(AppearanceMessage),
+    General(GeneralMessage),
+
+    // Config actions
+    Save,
+    Reload,    // Reload the active category, discarding its staged changes
+    ReloadAll, // Reload every category
+    OpenInEditor, // Open the config in $EDITOR at the selected item's line
+
+    // Guided tour overlay
+    StartTutorial,
+    SkipTutorialStep,
+    EndTutorial,
+
+    // Startup health report, shown when a check failed
+    DismissHealthReport,
+
+    // Save conflict prompt, shown when the file changed on disk since loading
+    OverwriteConflict,
+    ReloadAndReapply,
+    CancelSaveConflict,
+
+    // Section templates
+    OpenTemplateGallery,
+    CancelTemplateGallery,
+    InsertTemplate, // Append the selected template and open it in the editor
+
+    // Switching between the live config and recently opened ones
+    OpenQuickOpen,
+    CancelQuickOpen,
+    ConfirmQuickOpen,
+
+    // Which config is edited and why, with a copy offer for a read-only /etc config
+    OpenConfigSource,
+    CloseConfigSource,
+    CopySystemConfig,
+
+    // Wizard opening an app on a named workspace
+    OpenWorkspaceSpawn,
+    CancelWorkspaceSpawn,
+    ConfirmWorkspaceSpawn,
+
+    /// Widen (> 0) or narrow the current category's left pane by steps
+    ResizePane(i32),
+
+    /// Show or hide frame, IPC and event loop timings
+    ToggleDebugOverlay,
+
+    // Error handling
+    Error(AppError),
+    ClearError,
+    OpenErrorLog,  // Show recent errors with their full context chains
+    CloseErrorLog,
+
+    // Keybindings and outputs search
+    StartSearch,
+    UpdateSearch(String),
+    /// Stop typing but keep the filter
+    EndSearch,
+    ClearSearch,
+}
+
+/// Messages for the outputs category
+#[derive(Debug, Clone)]
+#[allow(dead_code)] // Some variants are for future features
+pub enum OutputMessage {
     // Output selection
     SelectOutput(usize),
     SelectNextOutput,
     SelectPrevOutput,
 
-    // Position editing
+    // Mode browser
+    OpenModeBrowser,
+    CloseModeBrowser,
+    SelectNextMode,
+    SelectPrevMode,
+    CycleRefreshFilter, // Step through the output's refresh rates, then show all
+    StageSelectedMode, // Use the highlighted mode for the output
+
+    ToggleOutputPane, // Switch the arrow keys between picking and moving outputs
+
+    // Position and scale editing
     MoveOutput { dx: i32, dy: i32 },
+    StepScale(i32), // Move the scale up or down one of the common steps
+    StageSuggestedScale, // Take the scale a DPI mismatch warning suggests
+    CycleTransform, // Stage the next rotation or flip
+    ToggleVrr,
+    ToggleOutputEnabled, // Stage turning the output off or on
+    ToggleFocusAtStartup, // Stage focusing the output when niri starts
     SetPosition { x: i32, y: i32 },
 
-    // Snap positioning
-    SnapLeft,   // Snap to left of other monitors
-    SnapRight,  // Snap to right of other monitors
-    SnapAbove,  // Snap above other monitors (centered)
-    SnapBelow,  // Snap below other monitors (centered)
-    Normalize,  // Shift all monitors so top-left is at (0,0)
+    // Snap positioning; with axis_lock only the axis of movement changes
+    SnapLeft { axis_lock: bool },  // Snap to left of other monitors
+    SnapRight { axis_lock: bool }, // Snap to right of other monitors
+    SnapAbove { axis_lock: bool }, // Snap above other monitors
+    SnapBelow { axis_lock: bool }, // Snap below other monitors
+    // Next alignment for snapping above/below (stacked) or left/right
+    CycleSnapAlignment { stacked: bool },
+    MoveToOrigin, // Move the selected monitor to (0,0)
+    Normalize,    // Shift all monitors so top-left is at (0,0)
+    MergeOutputBlocks, // Fold duplicate output blocks into the first one and save
+    PersistLiveLayout, // Write the layout niri reports into the config
+    IdentifyOutput, // Briefly turn the selected monitor's picture upside down
+    Arrange(Arrangement), // Line up all enabled monitors in a row or column
 
     // Canvas controls
     PanCanvas { dx: i32, dy: i32 },
+    // Dragging a monitor with the mouse, at terminal cells
+    CanvasPress { column: u16, row: u16 },
+    CanvasDrag { column: u16, row: u16 },
+    CanvasRelease,
     ZoomIn,
     ZoomOut,
     ResetView,
+    ZoomToSelected, // Toggle fitting the canvas around the selected monitor
+    ToggleColumnPreview,
+    ChangePreviewColumns(i32),
 
-    // Config actions
-    Save,
-    Reload,
+    // Label and note for the selected output, kept outside the niri config
+    OpenOutputLabel,
+    CancelOutputLabel,
+    ConfirmOutputLabel,
+
+    // Named output arrangements, kept outside the niri config
+    OpenOutputProfiles,
+    CloseOutputProfiles,
+    SaveOutputProfile, // Save the outputs as staged under the typed name
+    ApplyOutputProfile, // Stage the selected profile and save it in one write
+    DeleteOutputProfile,
+    ToggleProfileAutoApply, // Apply the selected profile whenever its monitors are plugged in
+
+    // Background and backdrop colors of the selected output
+    OpenOutputColors,
+    CancelOutputColors,
+    ConfirmOutputColors,
+
+    // Typing an exact position for the selected output; confirming sends SetPosition
+    OpenOutputPosition,
+    CancelOutputPosition,
+    ConfirmOutputPosition,
+
+    // Prompt shown when a save has positions for outputs niri has turned off
+    EnableAndSave,
+    SaveDisabledPositions,
+    CancelDisabledWarning,
 
     // Preview via IPC
     PreviewChanges,
     RevertPreview,
-
-    // Error handling
-    Error(String),
-    ClearError,
+    KeepPreview, // Stop the countdown that undoes a preview
+    ToggleLivePreview, // Preview every move as it happens
 
     // Refresh outputs from IPC
     RefreshOutputs,
+}
 
-    // Keybindings navigation
+/// Messages for the keybindings category
+#[derive(Debug, Clone)]
+#[allow(dead_code)] // Some variants are for future features
+pub enum KeybindingsMessage {
+    // Navigation
     SelectNextKeybinding,
     SelectPrevKeybinding,
+    PageDownKeybindings,
+    PageUpKeybindings,
     SelectKeybinding(usize),
 
-    // Keybindings search
-    StartSearch,
-    UpdateSearch(String),
-    ClearSearch,
+    // niri default binds
+    ToggleDefaultBinds,
+    ToggleMissingFilter, // Only list spawn binds whose program isn't installed
+    OverrideDefaultBind, // Copy the selected default into the config via the edit dialog
+    ToggleRawKeyNames,   // Show XKB key names instead of friendly labels
+    ToggleCategoryColumn,
+    ToggleFlagsColumn,
+    AdjustComboWidth(i16), // Widen (+) or narrow (-) the combo column
 
-    // Keybindings editing
+    // Editing
     StartEdit,
     CancelEdit,
     ConfirmEdit,
     AddKeybinding,
     DeleteKeybinding,
+    ToggleBindingFlag(BindingFlag), // Flip repeat/allow-when-locked without the edit dialog
+    FixRepeatLint, // Turn repeat off for a binding that would start an app per key repeat
+
+    // Prompt shown when a save would unbind quit or close-window
+    AddFallbackBinds,
+    SaveWithoutFallback,
+    CancelLockoutWarning,
+
+    // Dry run of a spawn binding: show the argv, optionally run it
+    OpenDryRun,
+    RunDryRun,
+    CloseDryRun,
+
+    // Column layout binding generator
+    OpenBindingGenerator,
+    CancelBindingGenerator,
+    ConfirmBindingGenerator,
+
+    // Binding templates from the settings
+    OpenBindingTemplate,
+    CancelBindingTemplate,
+    ConfirmBindingTemplate,
+
+    // Clashes left after staging template or generated binds
+    ResolveConflict(ConflictChoice),
+    KeepRemainingConflicts,
+
+    // User tags on the selected binding, kept outside the niri config
+    OpenBindingTags,
+    CancelBindingTags,
+    ConfirmBindingTags,
+
+    // Overlay of every bind; rebinding captures the next combo pressed
+    OpenBindOverlay,
+    CloseBindOverlay,
+    StartRebind,
+    CancelRebind,
+    RebindTo(String),
+}
 
-    // Appearance navigation
+/// Messages for the appearance category
+#[derive(Debug, Clone)]
+#[allow(dead_code)] // Some variants are for future features
+pub enum AppearanceMessage {
+    // Navigation
     SelectNextAppearanceSetting,
     SelectPrevAppearanceSetting,
+    PageDownAppearance,
+    PageUpAppearance,
     ToggleSection,
 
-    // Appearance editing
+    // Editing
     StartAppearanceEdit,
     CancelAppearanceEdit,
     ConfirmAppearanceEdit,
@@ -77,4 +258,49 @@ pub enum Message {
     CycleEnumForward,
     CycleEnumBackward,
     UpdateAppearanceValue(String),
+    ResetAppearanceToDefault, // Stage niri's default for the selected field
+    SaveAppearanceSection,    // Save only the selected section's changes
+    RevertAppearanceSection,  // Drop the selected section's staged changes
+    RevertAppearanceField,    // Drop the selected field's staged change
+    StartSectionEdit,         // Edit every field of the selected section in one form
+    CancelSectionEdit,
+    ConfirmSectionEdit,
+
+    ToggleNestedPreview, // Start or stop a nested niri showing the staged changes
+}
+
+/// Messages for the general category
+#[derive(Debug, Clone)]
+pub enum GeneralMessage {
+    SelectNextGeneralSetting,
+    SelectPrevGeneralSetting,
+    ToggleGeneralFlag,
+    StartGeneralEdit, // Type a new value for the selected text setting
+    CancelGeneralEdit,
+    ConfirmGeneralEdit,
+    RevertGeneralChanges,
+}
+
+impl From<OutputMessage> for Message {
+    fn from(message: OutputMessage) -> Self {
+        Message::Output(message)
+    }
+}
+
+impl From<KeybindingsMessage> for Message {
+    fn from(message: KeybindingsMessage) -> Self {
+        Message::Keybindings(message)
+    }
+}
+
+impl From<AppearanceMessage> for Message {
+    fn from(message: AppearanceMessage) -> Self {
+        Message::Appearance(message)
+    }
+}
+
+impl From<GeneralMessage> for Message {
+    fn from(message: GeneralMessage) -> Self {
+        Message::General(message)
+    }
 }
@@ -0,0 +1,271 @@
+use super::keybindings::BindingArg;
+use super::values::SizeChange;
+
+/// Kind of positional argument a niri built-in action accepts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArgKind {
+    /// No argument allowed (close-window, quit, ...)
+    None,
+    /// Integer index (focus-column 2)
+    Integer,
+    /// Arbitrary string (focus-monitor "DP-1")
+    String,
+    /// Workspace index or name (focus-workspace 1, focus-workspace "chat")
+    IntegerOrString,
+    /// Size change string (set-column-width "50%", "+10%", "-100")
+    SizeChange,
+}
+
+/// Built-in niri actions that take no arguments
+const NO_ARG_ACTIONS: &[&str] = &[
+    "quit",
+    "power-off-monitors",
+    "power-on-monitors",
+    "close-window",
+    "fullscreen-window",
+    "toggle-windowed-fullscreen",
+    "focus-column-left",
+    "focus-column-right",
+    "focus-column-first",
+    "focus-column-last",
+    "focus-column-right-or-first",
+    "focus-column-left-or-last",
+    "focus-column-or-monitor-left",
+    "focus-column-or-monitor-right",
+    "focus-window-down",
+    "focus-window-up",
+    "focus-window-or-monitor-up",
+    "focus-window-or-monitor-down",
+    "focus-window-down-or-column-left",
+    "focus-window-down-or-column-right",
+    "focus-window-up-or-column-left",
+    "focus-window-up-or-column-right",
+    "focus-window-or-workspace-down",
+    "focus-window-or-workspace-up",
+    "focus-window-top",
+    "focus-window-bottom",
+    "focus-window-down-or-top",
+    "focus-window-up-or-bottom",
+    "focus-window-previous",
+    "move-column-left",
+    "move-column-right",
+    "move-column-to-first",
+    "move-column-to-last",
+    "move-column-left-or-to-monitor-left",
+    "move-column-right-or-to-monitor-right",
+    "move-window-down",
+    "move-window-up",
+    "move-window-down-or-to-workspace-down",
+    "move-window-up-or-to-workspace-up",
+    "consume-or-expel-window-left",
+    "consume-or-expel-window-right",
+    "consume-window-into-column",
+    "expel-window-from-column",
+    "swap-window-left",
+    "swap-window-right",
+    "toggle-column-tabbed-display",
+    "center-column",
+    "center-window",
+    "center-visible-columns",
+    "focus-workspace-down",
+    "focus-workspace-up",
+    "focus-workspace-previous",
+    "move-window-to-workspace-down",
+    "move-window-to-workspace-up",
+    "move-column-to-workspace-down",
+    "move-column-to-workspace-up",
+    "move-workspace-down",
+    "move-workspace-up",
+    "focus-monitor-left",
+    "focus-monitor-right",
+    "focus-monitor-down",
+    "focus-monitor-up",
+    "focus-monitor-previous",
+    "focus-monitor-next",
+    "move-window-to-monitor-left",
+    "move-window-to-monitor-right",
+    "move-window-to-monitor-down",
+    "move-window-to-monitor-up",
+    "move-window-to-monitor-previous",
+    "move-window-to-monitor-next",
+    "move-column-to-monitor-left",
+    "move-column-to-monitor-right",
+    "move-column-to-monitor-down",
+    "move-column-to-monitor-up",
+    "move-column-to-monitor-previous",
+    "move-column-to-monitor-next",
+    "move-workspace-to-monitor-left",
+    "move-workspace-to-monitor-right",
+    "move-workspace-to-monitor-down",
+    "move-workspace-to-monitor-up",
+    "move-workspace-to-monitor-previous",
+    "move-workspace-to-monitor-next",
+    "switch-preset-column-width",
+    "switch-preset-column-width-back",
+    "switch-preset-window-width",
+    "switch-preset-window-height",
+    "reset-window-height",
+    "maximize-column",
+    "maximize-window-to-edges",
+    "expand-column-to-available-width",
+    "show-hotkey-overlay",
+    "toggle-window-floating",
+    "move-window-to-floating",
+    "move-window-to-tiling",
+    "focus-floating",
+    "focus-tiling",
+    "switch-focus-between-floating-and-tiling",
+    "toggle-keyboard-shortcuts-inhibit",
+    "toggle-overview",
+    "open-overview",
+    "close-overview",
+    "screenshot",
+    "screenshot-screen",
+    "screenshot-window",
+    "toggle-window-rule-opacity",
+    "toggle-debug-tint",
+    "debug-toggle-opaque-regions",
+    "debug-toggle-damage",
+    "do-screen-transition",
+    "set-dynamic-cast-window",
+    "set-dynamic-cast-monitor",
+    "clear-dynamic-cast-target",
+    "toggle-window-urgent",
+    "set-window-urgent",
+    "unset-window-urgent",
+    "load-config-file",
+    "unset-workspace-name",
+];
+
+/// Built-in niri actions that require a single positional argument
+const ARG_ACTIONS: &[(&str, ArgKind)] = &[
+    ("focus-workspace", ArgKind::IntegerOrString),
+    ("move-window-to-workspace", ArgKind::IntegerOrString),
+    ("move-column-to-workspace", ArgKind::IntegerOrString),
+    ("focus-column", ArgKind::Integer),
+    ("focus-window-in-column", ArgKind::Integer),
+    ("move-column-to-index", ArgKind::Integer),
+    ("move-workspace-to-index", ArgKind::Integer),
+    ("set-column-width", ArgKind::SizeChange),
+    ("set-window-width", ArgKind::SizeChange),
+    ("set-window-height", ArgKind::SizeChange),
+    ("focus-monitor", ArgKind::String),
+    ("move-window-to-monitor", ArgKind::String),
+    ("move-column-to-monitor", ArgKind::String),
+    ("move-workspace-to-monitor", ArgKind::String),
+    ("set-workspace-name", ArgKind::String),
+    ("switch-layout", ArgKind::IntegerOrString),
+];
+
+/// Look up the argument kind of a built-in action, or None if the name is unknown
+pub fn builtin_arg_kind(name: &str) -> Option<ArgKind> {
+    if NO_ARG_ACTIONS.contains(&name) {
+        return Some(ArgKind::None);
+    }
+    ARG_ACTIONS
+        .iter()
+        .find(|(action, _)| *action == name)
+        .map(|(_, kind)| *kind)
+}
+
+/// Check whether a string is a valid niri size change ("50%", "+10%", "-100", "1000")
+pub fn is_size_change(s: &str) -> bool {
+    SizeChange::parse(s).is_ok()
+}
+
+/// Convert an argument typed for a built-in action into the value written
+/// to the config. Sizes are always strings, indices always integers, and
+/// workspace references are integers unless quoted.
+pub fn builtin_arg(name: &str, kind: ArgKind, arg: &str) -> Result<BindingArg, String> {
+    let quoted = arg.len() >= 2 && arg.starts_with('"') && arg.ends_with('"');
+    let unquoted = arg.trim_matches('"');
+    match kind {
+        ArgKind::None => Err(format!("{name} takes no arguments")),
+        ArgKind::Integer => unquoted
+            .parse::<i64>()
+            .map(BindingArg::Number)
+            .map_err(|_| format!("{name} expects a number, got '{unquoted}'")),
+        ArgKind::SizeChange => SizeChange::parse(unquoted)
+            .map(|size| BindingArg::String(size.to_string()))
+            .map_err(|e| format!("{name} {e}")),
+        ArgKind::String if unquoted.contains(' ') && !quoted => Err(format!("{name} takes a single argument")),
+        ArgKind::String => Ok(BindingArg::String(unquoted.to_string())),
+        ArgKind::IntegerOrString => match unquoted.parse::<i64>() {
+            Ok(n) if !quoted => Ok(BindingArg::Number(n)),
+            _ => Ok(BindingArg::String(unquoted.to_string())),
+        },
+    }
+}
+
+/// Validate "action" or "action arg" as typed into the edit dialog
+pub fn validate_builtin(value: &str) -> Result<(), String> {
+    let value = value.trim();
+    let (name, arg) = match value.split_once(' ') {
+        Some((name, arg)) => (name, Some(arg.trim())),
+        None => (value, None),
+    };
+
+    if name == "spawn" || name == "spawn-sh" {
+        return Err(format!("Use the command action types for {name}"));
+    }
+
+    let Some(kind) = builtin_arg_kind(name) else {
+        return Err(format!("Unknown action '{name}'"));
+    };
+
+    let Some(arg) = arg.filter(|a| !a.is_empty()) else {
+        return match kind {
+            ArgKind::None => Ok(()),
+            _ => Err(format!("{name} requires an argument")),
+        };
+    };
+
+    builtin_arg(name, kind, arg).map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_builtin() {
+        assert!(validate_builtin("close-window").is_ok());
+        assert!(validate_builtin("focus-workspace 3").is_ok());
+        assert!(validate_builtin("focus-workspace chat").is_ok());
+        assert!(validate_builtin("set-column-width +10%").is_ok());
+        assert!(validate_builtin("set-column-width \"-100\"").is_ok());
+        assert!(validate_builtin("set-column-width 800").is_ok());
+
+        assert_eq!(
+            validate_builtin("close-window extra junk"),
+            Err("close-window takes no arguments".to_string())
+        );
+        assert_eq!(
+            validate_builtin("close-windw"),
+            Err("Unknown action 'close-windw'".to_string())
+        );
+        assert!(validate_builtin("focus-column two").is_err());
+        assert!(validate_builtin("focus-workspace").is_err());
+        assert!(validate_builtin("set-column-width wide").is_err());
+    }
+
+    #[test]
+    fn test_builtin_arg() {
+        let arg = |name: &str, value: &str| builtin_arg(name, builtin_arg_kind(name).unwrap(), value);
+        assert_eq!(arg("set-column-width", "800"), Ok(BindingArg::String("800".to_string())));
+        assert_eq!(arg("set-window-height", "\"+10%\""), Ok(BindingArg::String("+10%".to_string())));
+        assert_eq!(arg("focus-column", "2"), Ok(BindingArg::Number(2)));
+        assert_eq!(arg("focus-workspace", "3"), Ok(BindingArg::Number(3)));
+        assert_eq!(arg("focus-workspace", "\"3\""), Ok(BindingArg::String("3".to_string())));
+        assert_eq!(arg("focus-monitor", "\"DP-1\""), Ok(BindingArg::String("DP-1".to_string())));
+    }
+
+    #[test]
+    fn test_is_size_change() {
+        assert!(is_size_change("50%"));
+        assert!(is_size_change("+10%"));
+        assert!(is_size_change("-100"));
+        assert!(!is_size_change("%"));
+        assert!(!is_size_change("wide"));
+    }
+}
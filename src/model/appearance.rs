@@ -1,5 +1,11 @@
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 
+use super::config::SourceLocation;
+use super::selectable_list::SelectableList;
+use super::text_input;
+use super::values::eval_integer;
+
 /// A color value that can be either solid or a gradient
 #[derive(Debug, Clone, PartialEq)]
 pub enum ColorValue {
@@ -19,6 +25,55 @@ impl Default for ColorValue {
     }
 }
 
+impl ColorValue {
+    /// The colors this value is made of: one for solid, both ends of a gradient
+    pub fn colors(&self) -> Vec<&str> {
+        match self {
+            ColorValue::Solid(color) => vec![color],
+            ColorValue::Gradient { from, to, .. } => vec![from, to],
+        }
+    }
+
+    /// A dimmed, desaturated variant for the matching inactive color.
+    /// `factor` is how much saturation and brightness is kept. None if a
+    /// color isn't written in hex.
+    pub fn dimmed(&self, factor: f64) -> Option<ColorValue> {
+        Some(match self {
+            ColorValue::Solid(color) => ColorValue::Solid(dim_hex(color, factor)?),
+            ColorValue::Gradient { from, to, angle, relative_to, color_space } => ColorValue::Gradient {
+                from: dim_hex(from, factor)?,
+                to: dim_hex(to, factor)?,
+                angle: *angle,
+                relative_to: relative_to.clone(),
+                color_space: color_space.clone(),
+            },
+        })
+    }
+}
+
+/// Dim a #rgb, #rgba, #rrggbb or #rrggbbaa color, keeping its alpha
+fn dim_hex(color: &str, factor: f64) -> Option<String> {
+    let hex = color.trim().strip_prefix('#')?;
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channels: Vec<u8> = match hex.len() {
+        3 | 4 => hex.chars().map(|c| c.to_digit(16).unwrap() as u8 * 17).collect(),
+        6 | 8 => (0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap()).collect(),
+        _ => return None,
+    };
+    let [r, g, b] = [channels[0], channels[1], channels[2]].map(f64::from);
+    let luma = 0.299 * r + 0.587 * g + 0.114 * b;
+    let mut dimmed: String = [r, g, b]
+        .iter()
+        .map(|c| format!("{:02x}", ((luma + (c - luma) * factor) * factor).round().clamp(0.0, 255.0) as u8))
+        .collect();
+    if let Some(alpha) = channels.get(3) {
+        dimmed.push_str(&format!("{alpha:02x}"));
+    }
+    Some(format!("#{dimmed}"))
+}
+
 impl fmt::Display for ColorValue {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -58,6 +113,7 @@ impl CenterFocusedColumn {
         }
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Option<Self> {
         match s {
             "never" => Some(CenterFocusedColumn::Never),
@@ -175,7 +231,37 @@ pub struct StrutsSettings {
     pub bottom: Option<i32>,
 }
 
-/// All appearance settings from the layout block
+/// The top-level overview block and the workspace shadow inside it
+#[derive(Debug, Clone, PartialEq)]
+pub struct OverviewSettings {
+    /// How far the overview zooms out, in percent (written as a fraction)
+    pub zoom_percent: i32,
+    pub backdrop_color: ColorValue,
+    pub workspace_shadow_off: bool,
+    pub shadow_softness: i32,
+    pub shadow_spread: i32,
+    pub shadow_offset_x: i32,
+    pub shadow_offset_y: i32,
+    pub shadow_color: ColorValue,
+}
+
+impl Default for OverviewSettings {
+    fn default() -> Self {
+        Self {
+            zoom_percent: 50,
+            backdrop_color: ColorValue::Solid("#262626".to_string()),
+            workspace_shadow_off: false,
+            shadow_softness: 40,
+            shadow_spread: 10,
+            shadow_offset_x: 0,
+            shadow_offset_y: 10,
+            shadow_color: ColorValue::Solid("#00000050".to_string()),
+        }
+    }
+}
+
+/// All appearance settings from the layout block, plus the few top-level
+/// blocks edited alongside them
 #[derive(Debug, Clone, PartialEq)]
 pub struct AppearanceSettings {
     pub gaps: i32,
@@ -184,6 +270,7 @@ pub struct AppearanceSettings {
     pub border: BorderSettings,
     pub shadow: ShadowSettings,
     pub struts: StrutsSettings,
+    pub overview: OverviewSettings,
 }
 
 impl Default for AppearanceSettings {
@@ -195,10 +282,126 @@ impl Default for AppearanceSettings {
             border: BorderSettings::default(),
             shadow: ShadowSettings::default(),
             struts: StrutsSettings::default(),
+            overview: OverviewSettings::default(),
         }
     }
 }
 
+impl AppearanceSettings {
+    /// Read the current value of a field
+    pub fn get(&self, field: AppearanceField) -> FieldValue {
+        match field {
+            AppearanceField::Gaps => FieldValue::Integer(self.gaps),
+            AppearanceField::CenterFocusedColumn => FieldValue::Enum(self.center_focused_column),
+            AppearanceField::FocusRingOff => FieldValue::Boolean(self.focus_ring.off),
+            AppearanceField::FocusRingWidth => FieldValue::Integer(self.focus_ring.width),
+            AppearanceField::FocusRingActiveColor => FieldValue::Color(self.focus_ring.active_color.clone()),
+            AppearanceField::FocusRingInactiveColor => FieldValue::Color(self.focus_ring.inactive_color.clone()),
+            AppearanceField::BorderOff => FieldValue::Boolean(self.border.off),
+            AppearanceField::BorderWidth => FieldValue::Integer(self.border.width),
+            AppearanceField::BorderActiveColor => FieldValue::Color(self.border.active_color.clone()),
+            AppearanceField::BorderInactiveColor => FieldValue::Color(self.border.inactive_color.clone()),
+            AppearanceField::BorderUrgentColor => {
+                match &self.border.urgent_color {
+                    Some(c) => FieldValue::Color(c.clone()),
+                    None => FieldValue::String("(not set)".to_string()),
+                }
+            }
+            AppearanceField::ShadowOn => FieldValue::Boolean(self.shadow.on),
+            AppearanceField::ShadowDrawBehindWindow => FieldValue::Boolean(self.shadow.draw_behind_window),
+            AppearanceField::ShadowSoftness => FieldValue::Integer(self.shadow.softness),
+            AppearanceField::ShadowSpread => FieldValue::Integer(self.shadow.spread),
+            AppearanceField::ShadowOffsetX => FieldValue::Integer(self.shadow.offset_x),
+            AppearanceField::ShadowOffsetY => FieldValue::Integer(self.shadow.offset_y),
+            AppearanceField::ShadowColor => FieldValue::Color(self.shadow.color.clone()),
+            AppearanceField::StrutsLeft => FieldValue::OptionalInteger(self.struts.left),
+            AppearanceField::StrutsRight => FieldValue::OptionalInteger(self.struts.right),
+            AppearanceField::StrutsTop => FieldValue::OptionalInteger(self.struts.top),
+            AppearanceField::StrutsBottom => FieldValue::OptionalInteger(self.struts.bottom),
+            AppearanceField::OverviewZoom => FieldValue::Integer(self.overview.zoom_percent),
+            AppearanceField::OverviewBackdropColor => FieldValue::Color(self.overview.backdrop_color.clone()),
+            AppearanceField::OverviewShadowOff => FieldValue::Boolean(self.overview.workspace_shadow_off),
+            AppearanceField::OverviewShadowSoftness => FieldValue::Integer(self.overview.shadow_softness),
+            AppearanceField::OverviewShadowSpread => FieldValue::Integer(self.overview.shadow_spread),
+            AppearanceField::OverviewShadowOffsetX => FieldValue::Integer(self.overview.shadow_offset_x),
+            AppearanceField::OverviewShadowOffsetY => FieldValue::Integer(self.overview.shadow_offset_y),
+            AppearanceField::OverviewShadowColor => FieldValue::Color(self.overview.shadow_color.clone()),
+        }
+    }
+
+    /// Width of each of `columns` equal columns on an output `output_width`
+    /// logical pixels wide, sized the way niri sizes proportional columns:
+    /// the area between the struts less one gap, split evenly, less a gap each
+    pub fn column_width(&self, output_width: i32, columns: i32) -> i32 {
+        let working = output_width - self.struts.left.unwrap_or(0) - self.struts.right.unwrap_or(0);
+        ((working - self.gaps) / columns.max(1) - self.gaps).max(0)
+    }
+
+    /// How far from each output edge window decorations can reach, as
+    /// (left, top, right, bottom): struts and gaps keep tiles in, and the
+    /// focus ring draws outward into the gap while the border stays inside
+    pub fn window_insets(&self) -> (i32, i32, i32, i32) {
+        let ring = if self.focus_ring.off { 0 } else { self.focus_ring.width };
+        let inset = |strut: Option<i32>| (strut.unwrap_or(0) + self.gaps - ring).max(0);
+        (inset(self.struts.left), inset(self.struts.top), inset(self.struts.right), inset(self.struts.bottom))
+    }
+
+    /// Colors the other color fields are set to, in field order without repeats
+    pub fn colors_except(&self, except: AppearanceField) -> Vec<String> {
+        let mut colors: Vec<String> = Vec::new();
+        for section in AppearanceSection::all() {
+            for &field in section.fields().iter().filter(|&&f| f.is_color() && f != except) {
+                if let FieldValue::Color(value) = self.get(field) {
+                    for color in value.colors() {
+                        if !colors.iter().any(|c| c.eq_ignore_ascii_case(color)) {
+                            colors.push(color.to_string());
+                        }
+                    }
+                }
+            }
+        }
+        colors
+    }
+
+    /// Store a field value, returning false if the value doesn't fit the field
+    pub fn set(&mut self, field: AppearanceField, value: &FieldValue) -> bool {
+        match (field, value) {
+            (AppearanceField::Gaps, FieldValue::Integer(n)) => self.gaps = *n,
+            (AppearanceField::CenterFocusedColumn, FieldValue::Enum(e)) => self.center_focused_column = *e,
+            (AppearanceField::FocusRingOff, FieldValue::Boolean(b)) => self.focus_ring.off = *b,
+            (AppearanceField::FocusRingWidth, FieldValue::Integer(n)) => self.focus_ring.width = *n,
+            (AppearanceField::FocusRingActiveColor, FieldValue::Color(c)) => self.focus_ring.active_color = c.clone(),
+            (AppearanceField::FocusRingInactiveColor, FieldValue::Color(c)) => self.focus_ring.inactive_color = c.clone(),
+            (AppearanceField::BorderOff, FieldValue::Boolean(b)) => self.border.off = *b,
+            (AppearanceField::BorderWidth, FieldValue::Integer(n)) => self.border.width = *n,
+            (AppearanceField::BorderActiveColor, FieldValue::Color(c)) => self.border.active_color = c.clone(),
+            (AppearanceField::BorderInactiveColor, FieldValue::Color(c)) => self.border.inactive_color = c.clone(),
+            (AppearanceField::BorderUrgentColor, FieldValue::Color(c)) => self.border.urgent_color = Some(c.clone()),
+            (AppearanceField::ShadowOn, FieldValue::Boolean(b)) => self.shadow.on = *b,
+            (AppearanceField::ShadowDrawBehindWindow, FieldValue::Boolean(b)) => self.shadow.draw_behind_window = *b,
+            (AppearanceField::ShadowSoftness, FieldValue::Integer(n)) => self.shadow.softness = *n,
+            (AppearanceField::ShadowSpread, FieldValue::Integer(n)) => self.shadow.spread = *n,
+            (AppearanceField::ShadowOffsetX, FieldValue::Integer(n)) => self.shadow.offset_x = *n,
+            (AppearanceField::ShadowOffsetY, FieldValue::Integer(n)) => self.shadow.offset_y = *n,
+            (AppearanceField::ShadowColor, FieldValue::Color(c)) => self.shadow.color = c.clone(),
+            (AppearanceField::StrutsLeft, FieldValue::OptionalInteger(opt)) => self.struts.left = *opt,
+            (AppearanceField::StrutsRight, FieldValue::OptionalInteger(opt)) => self.struts.right = *opt,
+            (AppearanceField::StrutsTop, FieldValue::OptionalInteger(opt)) => self.struts.top = *opt,
+            (AppearanceField::StrutsBottom, FieldValue::OptionalInteger(opt)) => self.struts.bottom = *opt,
+            (AppearanceField::OverviewZoom, FieldValue::Integer(n)) => self.overview.zoom_percent = *n,
+            (AppearanceField::OverviewBackdropColor, FieldValue::Color(c)) => self.overview.backdrop_color = c.clone(),
+            (AppearanceField::OverviewShadowOff, FieldValue::Boolean(b)) => self.overview.workspace_shadow_off = *b,
+            (AppearanceField::OverviewShadowSoftness, FieldValue::Integer(n)) => self.overview.shadow_softness = *n,
+            (AppearanceField::OverviewShadowSpread, FieldValue::Integer(n)) => self.overview.shadow_spread = *n,
+            (AppearanceField::OverviewShadowOffsetX, FieldValue::Integer(n)) => self.overview.shadow_offset_x = *n,
+            (AppearanceField::OverviewShadowOffsetY, FieldValue::Integer(n)) => self.overview.shadow_offset_y = *n,
+            (AppearanceField::OverviewShadowColor, FieldValue::Color(c)) => self.overview.shadow_color = c.clone(),
+            _ => return false,
+        }
+        true
+    }
+}
+
 /// Sections in the appearance settings list
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum AppearanceSection {
@@ -207,6 +410,7 @@ pub enum AppearanceSection {
     Border,
     Shadow,
     Struts,
+    Overview,
 }
 
 impl AppearanceSection {
@@ -217,6 +421,7 @@ impl AppearanceSection {
             AppearanceSection::Border,
             AppearanceSection::Shadow,
             AppearanceSection::Struts,
+            AppearanceSection::Overview,
         ]
     }
 
@@ -227,6 +432,7 @@ impl AppearanceSection {
             AppearanceSection::Border => "Border",
             AppearanceSection::Shadow => "Shadow",
             AppearanceSection::Struts => "Struts",
+            AppearanceSection::Overview => "Overview",
         }
     }
 
@@ -264,12 +470,50 @@ impl AppearanceSection {
                 AppearanceField::StrutsTop,
                 AppearanceField::StrutsBottom,
             ],
+            AppearanceSection::Overview => &[
+                AppearanceField::OverviewZoom,
+                AppearanceField::OverviewBackdropColor,
+                AppearanceField::OverviewShadowOff,
+                AppearanceField::OverviewShadowSoftness,
+                AppearanceField::OverviewShadowSpread,
+                AppearanceField::OverviewShadowOffsetX,
+                AppearanceField::OverviewShadowOffsetY,
+                AppearanceField::OverviewShadowColor,
+            ],
         }
     }
 }
 
+/// Allowed range of an integer field and how far one +/- press moves it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IntegerBounds {
+    pub min: i32,
+    pub max: i32,
+    pub step: i32,
+}
+
+impl IntegerBounds {
+    const fn new(min: i32, max: i32, step: i32) -> Self {
+        Self { min, max, step }
+    }
+
+    pub fn clamp(&self, value: i32) -> i32 {
+        value.clamp(self.min, self.max)
+    }
+
+    pub fn contains(&self, value: i32) -> bool {
+        (self.min..=self.max).contains(&value)
+    }
+}
+
+impl fmt::Display for IntegerBounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} to {} (step {})", self.min, self.max, self.step)
+    }
+}
+
 /// Individual fields that can be edited
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum AppearanceField {
     // General
     Gaps,
@@ -298,6 +542,15 @@ pub enum AppearanceField {
     StrutsRight,
     StrutsTop,
     StrutsBottom,
+    // Overview
+    OverviewZoom,
+    OverviewBackdropColor,
+    OverviewShadowOff,
+    OverviewShadowSoftness,
+    OverviewShadowSpread,
+    OverviewShadowOffsetX,
+    OverviewShadowOffsetY,
+    OverviewShadowColor,
 }
 
 impl AppearanceField {
@@ -325,10 +578,32 @@ impl AppearanceField {
             AppearanceField::StrutsRight => "right",
             AppearanceField::StrutsTop => "top",
             AppearanceField::StrutsBottom => "bottom",
+            AppearanceField::OverviewZoom => "zoom",
+            AppearanceField::OverviewBackdropColor => "backdrop-color",
+            AppearanceField::OverviewShadowOff => "workspace-shadow off",
+            AppearanceField::OverviewShadowSoftness => "workspace-shadow softness",
+            AppearanceField::OverviewShadowSpread => "workspace-shadow spread",
+            AppearanceField::OverviewShadowOffsetX => "workspace-shadow offset x",
+            AppearanceField::OverviewShadowOffsetY => "workspace-shadow offset y",
+            AppearanceField::OverviewShadowColor => "workspace-shadow color",
+        }
+    }
+
+    /// Name prefixed with its block, e.g. "focus-ring width"
+    pub fn qualified_name(&self) -> String {
+        match self.section() {
+            AppearanceSection::General => self.name().to_string(),
+            section => format!("{} {}", section.name().to_lowercase().replace(' ', "-"), self.name()),
         }
     }
 
+    /// What the field does, translated
     pub fn description(&self) -> &'static str {
+        crate::i18n::tr(self.english_description())
+    }
+
+    /// The description as written, which is also its translation id
+    fn english_description(&self) -> &'static str {
         match self {
             AppearanceField::Gaps => "Gap size between windows in logical pixels",
             AppearanceField::CenterFocusedColumn => "When to center the focused column: never, always, or on-overflow",
@@ -352,6 +627,14 @@ impl AppearanceField {
             AppearanceField::StrutsRight => "Right strut (outer gap) in logical pixels",
             AppearanceField::StrutsTop => "Top strut (outer gap) in logical pixels",
             AppearanceField::StrutsBottom => "Bottom strut (outer gap) in logical pixels",
+            AppearanceField::OverviewZoom => "Size of workspaces in the overview, in percent of the screen",
+            AppearanceField::OverviewBackdropColor => "Color behind the workspaces in the overview",
+            AppearanceField::OverviewShadowOff => "Shadow drawn around each workspace in the overview",
+            AppearanceField::OverviewShadowSoftness => "Workspace shadow blur radius in logical pixels",
+            AppearanceField::OverviewShadowSpread => "Workspace shadow expansion in logical pixels",
+            AppearanceField::OverviewShadowOffsetX => "Horizontal workspace shadow offset in logical pixels",
+            AppearanceField::OverviewShadowOffsetY => "Vertical workspace shadow offset in logical pixels",
+            AppearanceField::OverviewShadowColor => "Workspace shadow color (supports alpha, e.g. #00000050)",
         }
     }
 
@@ -378,6 +661,14 @@ impl AppearanceField {
             | AppearanceField::StrutsRight
             | AppearanceField::StrutsTop
             | AppearanceField::StrutsBottom => AppearanceSection::Struts,
+            AppearanceField::OverviewZoom
+            | AppearanceField::OverviewBackdropColor
+            | AppearanceField::OverviewShadowOff
+            | AppearanceField::OverviewShadowSoftness
+            | AppearanceField::OverviewShadowSpread
+            | AppearanceField::OverviewShadowOffsetX
+            | AppearanceField::OverviewShadowOffsetY
+            | AppearanceField::OverviewShadowColor => AppearanceSection::Overview,
         }
     }
 
@@ -388,6 +679,7 @@ impl AppearanceField {
                 | AppearanceField::BorderOff
                 | AppearanceField::ShadowOn
                 | AppearanceField::ShadowDrawBehindWindow
+                | AppearanceField::OverviewShadowOff
         )
     }
 
@@ -396,7 +688,9 @@ impl AppearanceField {
     pub fn is_off_semantic(&self) -> bool {
         matches!(
             self,
-            AppearanceField::FocusRingOff | AppearanceField::BorderOff
+            AppearanceField::FocusRingOff
+                | AppearanceField::BorderOff
+                | AppearanceField::OverviewShadowOff
         )
     }
 
@@ -413,9 +707,45 @@ impl AppearanceField {
                 | AppearanceField::BorderInactiveColor
                 | AppearanceField::BorderUrgentColor
                 | AppearanceField::ShadowColor
+                | AppearanceField::OverviewBackdropColor
+                | AppearanceField::OverviewShadowColor
         )
     }
 
+    /// The inactive color that goes with an active color field
+    pub fn inactive_counterpart(&self) -> Option<AppearanceField> {
+        match self {
+            AppearanceField::FocusRingActiveColor => Some(AppearanceField::FocusRingInactiveColor),
+            AppearanceField::BorderActiveColor => Some(AppearanceField::BorderInactiveColor),
+            _ => None,
+        }
+    }
+
+    /// Range accepted for an integer field; None for other field types
+    pub fn bounds(&self) -> Option<IntegerBounds> {
+        match self {
+            AppearanceField::Gaps => Some(IntegerBounds::new(0, 256, 2)),
+            AppearanceField::FocusRingWidth | AppearanceField::BorderWidth => Some(IntegerBounds::new(0, 64, 1)),
+            AppearanceField::ShadowSoftness => Some(IntegerBounds::new(0, 256, 5)),
+            AppearanceField::ShadowSpread => Some(IntegerBounds::new(-128, 128, 1)),
+            AppearanceField::ShadowOffsetX | AppearanceField::ShadowOffsetY => {
+                Some(IntegerBounds::new(-256, 256, 1))
+            }
+            AppearanceField::StrutsLeft
+            | AppearanceField::StrutsRight
+            | AppearanceField::StrutsTop
+            | AppearanceField::StrutsBottom => Some(IntegerBounds::new(0, 2048, 8)),
+            // niri caps the overview zoom at 0.75
+            AppearanceField::OverviewZoom => Some(IntegerBounds::new(1, 75, 5)),
+            AppearanceField::OverviewShadowSoftness => Some(IntegerBounds::new(0, 256, 5)),
+            AppearanceField::OverviewShadowSpread => Some(IntegerBounds::new(-128, 128, 1)),
+            AppearanceField::OverviewShadowOffsetX | AppearanceField::OverviewShadowOffsetY => {
+                Some(IntegerBounds::new(-256, 256, 1))
+            }
+            _ => None,
+        }
+    }
+
     pub fn is_integer(&self) -> bool {
         matches!(
             self,
@@ -430,6 +760,11 @@ impl AppearanceField {
                 | AppearanceField::StrutsRight
                 | AppearanceField::StrutsTop
                 | AppearanceField::StrutsBottom
+                | AppearanceField::OverviewZoom
+                | AppearanceField::OverviewShadowSoftness
+                | AppearanceField::OverviewShadowSpread
+                | AppearanceField::OverviewShadowOffsetX
+                | AppearanceField::OverviewShadowOffsetY
         )
     }
 }
@@ -463,9 +798,10 @@ impl fmt::Display for FieldValue {
 
 /// A single setting change
 #[derive(Debug, Clone)]
-#[allow(dead_code)] // value field is stored for potential future use (e.g., undo)
 pub struct AppearanceChange {
     pub field: AppearanceField,
+    /// The saved value the change was made against
+    pub original: FieldValue,
     pub value: FieldValue,
 }
 
@@ -561,11 +897,13 @@ pub struct ColorEditState {
     pub gradient_angle: String,
     pub gradient_angle_cursor: usize,
     pub gradient_relative_to: String, // "window" or "workspace-view"
+    /// Quick-pick colors: the theme's other colors, then recently used ones
+    pub palette: Vec<String>,
 }
 
 impl ColorEditState {
     pub fn from_solid(color: &str) -> Self {
-        let len = color.len();
+        let len = text_input::end(color);
         Self {
             is_gradient: false,
             focused_field: ColorEditField::SolidColor,
@@ -578,24 +916,26 @@ impl ColorEditState {
             gradient_angle: String::new(),
             gradient_angle_cursor: 0,
             gradient_relative_to: "window".to_string(),
+            palette: Vec::new(),
         }
     }
 
     pub fn from_gradient(from: &str, to: &str, angle: Option<i32>, relative_to: Option<&str>) -> Self {
         let angle_str = angle.map(|a| a.to_string()).unwrap_or_default();
-        let angle_cursor = angle_str.len();
+        let angle_cursor = text_input::end(&angle_str);
         Self {
             is_gradient: true,
             focused_field: ColorEditField::GradientFrom,
             solid_color: String::new(),
             solid_cursor: 0,
             gradient_from: from.to_string(),
-            gradient_from_cursor: from.len(),
+            gradient_from_cursor: text_input::end(from),
             gradient_to: to.to_string(),
-            gradient_to_cursor: to.len(),
+            gradient_to_cursor: text_input::end(to),
             gradient_angle: angle_str,
             gradient_angle_cursor: angle_cursor,
             gradient_relative_to: relative_to.unwrap_or("window").to_string(),
+            palette: Vec::new(),
         }
     }
 
@@ -606,14 +946,14 @@ impl ColorEditState {
             // Copy solid color to gradient from if empty
             if self.gradient_from.is_empty() && !self.solid_color.is_empty() {
                 self.gradient_from = self.solid_color.clone();
-                self.gradient_from_cursor = self.gradient_from.len();
+                self.gradient_from_cursor = text_input::end(&self.gradient_from);
             }
         } else {
             self.focused_field = ColorEditField::SolidColor;
             // Copy gradient from to solid if empty
             if self.solid_color.is_empty() && !self.gradient_from.is_empty() {
                 self.solid_color = self.gradient_from.clone();
-                self.solid_cursor = self.solid_color.len();
+                self.solid_cursor = text_input::end(&self.solid_color);
             }
         }
     }
@@ -638,17 +978,13 @@ impl ColorEditState {
 
     pub fn insert_char(&mut self, c: char) {
         if let Some((text, cursor)) = self.current_text_mut() {
-            text.insert(*cursor, c);
-            *cursor += 1;
+            text_input::insert_char(text, cursor, c);
         }
     }
 
     pub fn delete_char(&mut self) {
         if let Some((text, cursor)) = self.current_text_mut() {
-            if *cursor > 0 {
-                *cursor -= 1;
-                text.remove(*cursor);
-            }
+            text_input::delete_char(text, cursor);
         }
     }
 
@@ -660,7 +996,28 @@ impl ColorEditState {
 
     pub fn cursor_right(&mut self) {
         if let Some((text, cursor)) = self.current_text_mut() {
-            *cursor = (*cursor + 1).min(text.len());
+            text_input::cursor_right(text, cursor);
+        }
+    }
+
+    /// Put palette entry `index` into the focused color input; the
+    /// type selector counts as the first color input
+    pub fn pick(&mut self, index: usize) -> bool {
+        let Some(color) = self.palette.get(index).cloned() else {
+            return false;
+        };
+        if self.focused_field == ColorEditField::ColorType {
+            self.focused_field = if self.is_gradient { ColorEditField::GradientFrom } else { ColorEditField::SolidColor };
+        }
+        match self.focused_field {
+            ColorEditField::SolidColor | ColorEditField::GradientFrom | ColorEditField::GradientTo => {
+                if let Some((text, cursor)) = self.current_text_mut() {
+                    *cursor = text_input::end(&color);
+                    *text = color;
+                }
+                true
+            }
+            _ => false,
         }
     }
 
@@ -700,16 +1057,22 @@ pub struct AppearanceEditMode {
     pub cursor: usize,
     // For color editing
     pub color_state: Option<ColorEditState>,
+    /// Also stage a dimmed copy of the color as the matching inactive color
+    pub suggest_inactive: bool,
+    /// How much of the color the inactive suggestion keeps
+    pub inactive_factor: f64,
 }
 
 impl AppearanceEditMode {
     pub fn new(field: AppearanceField, initial_value: &str) -> Self {
-        let cursor = initial_value.len();
+        let cursor = text_input::end(initial_value);
         Self {
             field,
             value: initial_value.to_string(),
             cursor,
             color_state: None,
+            suggest_inactive: false,
+            inactive_factor: 0.6,
         }
     }
 
@@ -725,6 +1088,75 @@ impl AppearanceEditMode {
             value: String::new(),
             cursor: 0,
             color_state: Some(color_state),
+            suggest_inactive: false,
+            inactive_factor: 0.6,
+        }
+    }
+
+    /// Offer `palette` as quick-pick colors in the color editor
+    pub fn with_palette(mut self, palette: Vec<String>) -> Self {
+        if let Some(color_state) = &mut self.color_state {
+            color_state.palette = palette;
+        }
+        self
+    }
+
+    pub fn with_inactive_factor(mut self, factor: f64) -> Self {
+        self.inactive_factor = factor;
+        self
+    }
+
+    /// The inactive field and the dimmed color suggested for it, if the
+    /// edited field has an inactive counterpart and a hex color
+    pub fn inactive_suggestion(&self) -> Option<(AppearanceField, ColorValue)> {
+        let inactive = self.field.inactive_counterpart()?;
+        let color = self.color_state.as_ref()?.to_color_value()?;
+        Some((inactive, color.dimmed(self.inactive_factor)?))
+    }
+
+    /// Parse the dialog contents into a value for the edited field
+    pub fn parsed_value(&self) -> Result<FieldValue, String> {
+        if let Some(ref color_state) = self.color_state {
+            return color_state
+                .to_color_value()
+                .map(FieldValue::Color)
+                .ok_or_else(|| "Color value cannot be empty".to_string());
+        }
+
+        let value = self.value.trim();
+        let is_strut = matches!(
+            self.field,
+            AppearanceField::StrutsLeft
+                | AppearanceField::StrutsRight
+                | AppearanceField::StrutsTop
+                | AppearanceField::StrutsBottom
+        );
+        if is_strut {
+            // Struts are optional; an empty value removes the node
+            if value.is_empty() {
+                return Ok(FieldValue::OptionalInteger(None));
+            }
+            return self.parse_integer(value).map(|n| FieldValue::OptionalInteger(Some(n)));
+        }
+        if self.field.is_integer() {
+            return self.parse_integer(value).map(FieldValue::Integer);
+        }
+        Ok(FieldValue::String(value.to_string()))
+    }
+
+    /// Parse an integer, or simple arithmetic giving one, and check it
+    /// against the field's bounds
+    fn parse_integer(&self, value: &str) -> Result<i32, String> {
+        let n = eval_integer(value)
+            .map_err(|e| format!("{} must be a whole number: {e}", self.field.qualified_name()))?;
+        match self.field.bounds() {
+            Some(bounds) if !bounds.contains(n) => Err(format!(
+                "{} must be between {} and {}",
+                self.field.qualified_name(),
+                bounds.min,
+                bounds.max
+            )),
+            _ => Ok(n),
         }
     }
 
@@ -732,17 +1164,15 @@ impl AppearanceEditMode {
         if let Some(ref mut cs) = self.color_state {
             cs.insert_char(c);
         } else {
-            self.value.insert(self.cursor, c);
-            self.cursor += 1;
+            text_input::insert_char(&mut self.value, &mut self.cursor, c);
         }
     }
 
     pub fn delete_char(&mut self) {
         if let Some(ref mut cs) = self.color_state {
             cs.delete_char();
-        } else if self.cursor > 0 {
-            self.cursor -= 1;
-            self.value.remove(self.cursor);
+        } else {
+            text_input::delete_char(&mut self.value, &mut self.cursor);
         }
     }
 
@@ -758,37 +1188,147 @@ impl AppearanceEditMode {
         if let Some(ref mut cs) = self.color_state {
             cs.cursor_right();
         } else {
-            self.cursor = (self.cursor + 1).min(self.value.len());
+            text_input::cursor_right(&self.value, &mut self.cursor);
         }
     }
 
     pub fn cursor_home(&mut self) {
         self.cursor = 0;
         if let Some(ref mut cs) = self.color_state {
-            match cs.focused_field {
-                ColorEditField::SolidColor => cs.solid_cursor = 0,
-                ColorEditField::GradientFrom => cs.gradient_from_cursor = 0,
-                ColorEditField::GradientTo => cs.gradient_to_cursor = 0,
-                ColorEditField::GradientAngle => cs.gradient_angle_cursor = 0,
-                _ => {}
+            if let Some((_, cursor)) = cs.current_text_mut() {
+                *cursor = 0;
             }
         }
     }
 
     pub fn cursor_end(&mut self) {
-        self.cursor = self.value.len();
+        self.cursor = text_input::end(&self.value);
         if let Some(ref mut cs) = self.color_state {
-            match cs.focused_field {
-                ColorEditField::SolidColor => cs.solid_cursor = cs.solid_color.len(),
-                ColorEditField::GradientFrom => cs.gradient_from_cursor = cs.gradient_from.len(),
-                ColorEditField::GradientTo => cs.gradient_to_cursor = cs.gradient_to.len(),
-                ColorEditField::GradientAngle => cs.gradient_angle_cursor = cs.gradient_angle.len(),
-                _ => {}
+            if let Some((text, cursor)) = cs.current_text_mut() {
+                *cursor = text_input::end(text);
             }
         }
     }
 }
 
+/// How one field is edited in the section editor
+#[derive(Debug, Clone)]
+pub enum SectionEditValue {
+    /// Booleans and enums, changed with Space and the arrow keys
+    Choice(FieldValue),
+    /// Integers and solid colors, typed like in the single-field dialog
+    Text(AppearanceEditMode),
+    /// Gradients, which only the single-field color editor can change
+    Fixed(FieldValue),
+}
+
+/// A field of the section editor with the value it started from
+#[derive(Debug, Clone)]
+pub struct SectionEditRow {
+    pub field: AppearanceField,
+    pub original: FieldValue,
+    pub value: SectionEditValue,
+}
+
+impl SectionEditRow {
+    fn new(field: AppearanceField, original: FieldValue) -> Self {
+        let value = match &original {
+            FieldValue::Boolean(_) | FieldValue::Enum(_) => SectionEditValue::Choice(original.clone()),
+            FieldValue::Color(ColorValue::Gradient { .. }) => SectionEditValue::Fixed(original.clone()),
+            FieldValue::Color(ColorValue::Solid(color)) => SectionEditValue::Text(AppearanceEditMode::new(field, color)),
+            FieldValue::Integer(n) | FieldValue::OptionalInteger(Some(n)) => {
+                SectionEditValue::Text(AppearanceEditMode::new(field, &n.to_string()))
+            }
+            FieldValue::OptionalInteger(None) => SectionEditValue::Text(AppearanceEditMode::new(field, "")),
+            // An unset optional color reads as a "(not set)" string
+            FieldValue::String(_) if field.is_color() => SectionEditValue::Text(AppearanceEditMode::new(field, "")),
+            FieldValue::String(s) => SectionEditValue::Text(AppearanceEditMode::new(field, s)),
+        };
+        Self { field, original, value }
+    }
+
+    /// Value the row currently holds, or why it can't be used
+    pub fn parsed_value(&self) -> Result<FieldValue, String> {
+        match &self.value {
+            SectionEditValue::Choice(value) | SectionEditValue::Fixed(value) => Ok(value.clone()),
+            SectionEditValue::Text(edit) if self.field.is_color() => match edit.value.trim() {
+                "" if !matches!(self.original, FieldValue::Color(_)) => Ok(self.original.clone()),
+                "" => Err(format!("{} cannot be empty", self.field.qualified_name())),
+                color => Ok(FieldValue::Color(ColorValue::Solid(color.to_string()))),
+            },
+            SectionEditValue::Text(edit) => edit.parsed_value(),
+        }
+    }
+
+    pub fn is_modified(&self) -> bool {
+        self.parsed_value().map_or(true, |value| value != self.original)
+    }
+}
+
+/// State for editing every field of a section in one form
+#[derive(Debug, Clone)]
+pub struct SectionEditMode {
+    pub section: AppearanceSection,
+    pub rows: Vec<SectionEditRow>,
+    pub focused: usize,
+}
+
+impl SectionEditMode {
+    pub fn new(section: AppearanceSection, settings: &AppearanceSettings) -> Self {
+        let rows = section
+            .fields()
+            .iter()
+            .map(|&field| SectionEditRow::new(field, settings.get(field)))
+            .collect();
+        Self { section, rows, focused: 0 }
+    }
+
+    pub fn focus_next(&mut self) {
+        self.focused = (self.focused + 1) % self.rows.len();
+    }
+
+    pub fn focus_prev(&mut self) {
+        self.focused = (self.focused + self.rows.len() - 1) % self.rows.len();
+    }
+
+    fn focused_value(&mut self) -> Option<&mut SectionEditValue> {
+        self.rows.get_mut(self.focused).map(|row| &mut row.value)
+    }
+
+    /// Flip a boolean or step an enum on the focused row
+    pub fn cycle(&mut self, forward: bool) {
+        if let Some(SectionEditValue::Choice(value)) = self.focused_value() {
+            *value = match &*value {
+                FieldValue::Boolean(b) => FieldValue::Boolean(!*b),
+                FieldValue::Enum(e) if forward => FieldValue::Enum(e.next()),
+                FieldValue::Enum(e) => FieldValue::Enum(e.prev()),
+                other => other.clone(),
+            };
+        }
+    }
+
+    /// Text input of the focused row, if it is typed
+    pub fn focused_text(&mut self) -> Option<&mut AppearanceEditMode> {
+        match self.focused_value() {
+            Some(SectionEditValue::Text(edit)) => Some(edit),
+            _ => None,
+        }
+    }
+
+    /// Fields whose value differs from where the form started, ready to
+    /// stage; the first invalid row makes the whole form invalid
+    pub fn changed_values(&self) -> Result<Vec<(AppearanceField, FieldValue)>, String> {
+        let mut changes = Vec::new();
+        for row in &self.rows {
+            let value = row.parsed_value()?;
+            if value != row.original {
+                changes.push((row.field, value));
+            }
+        }
+        Ok(changes)
+    }
+}
+
 /// A list item in the appearance settings list
 #[derive(Debug, Clone)]
 pub enum AppearanceListItem {
@@ -801,11 +1341,15 @@ pub enum AppearanceListItem {
 pub struct AppearanceViewModel {
     pub settings: AppearanceSettings,
     pub original_settings: AppearanceSettings,
-    pub selected_index: usize,
-    pub scroll_offset: usize,
+    pub list: SelectableList,
     pub collapsed_sections: std::collections::HashSet<AppearanceSection>,
-    pub pending_changes: Vec<AppearanceChange>,
+    /// At most one change per field, in list order
+    pub pending_changes: BTreeMap<AppearanceField, AppearanceChange>,
     pub edit_mode: Option<AppearanceEditMode>,
+    pub section_edit: Option<SectionEditMode>,
+    pub field_sources: HashMap<AppearanceField, SourceLocation>, // Where each field is set in the config
+    pub defaults: AppearanceSettings, // What niri uses for fields the config leaves out
+    pub defaults_release: Option<String>, // niri release `defaults` came from; None until loaded
 }
 
 impl AppearanceViewModel {
@@ -813,51 +1357,62 @@ impl AppearanceViewModel {
         Self {
             original_settings: settings.clone(),
             settings,
-            selected_index: 0,
-            scroll_offset: 0,
+            list: SelectableList::default(),
             collapsed_sections: std::collections::HashSet::new(),
-            pending_changes: Vec::new(),
+            pending_changes: BTreeMap::new(),
             edit_mode: None,
+            section_edit: None,
+            field_sources: HashMap::new(),
+            defaults: AppearanceSettings::default(),
+            defaults_release: None,
         }
     }
 
     /// Get the list of visible items (respecting collapsed sections)
     pub fn visible_items(&self) -> Vec<AppearanceListItem> {
-        let mut items = Vec::new();
-        for section in AppearanceSection::all() {
-            items.push(AppearanceListItem::SectionHeader(*section));
-            if !self.collapsed_sections.contains(section) {
-                for field in section.fields() {
-                    items.push(AppearanceListItem::Field(*field));
-                }
-            }
-        }
-        items
+        self.iter_visible_items().collect()
+    }
+
+    /// The visible items without collecting them, for lookups that only
+    /// need one item or the count
+    pub fn iter_visible_items(&self) -> impl Iterator<Item = AppearanceListItem> + '_ {
+        AppearanceSection::all().iter().flat_map(move |section| {
+            let fields: &[AppearanceField] = if self.collapsed_sections.contains(section) {
+                &[]
+            } else {
+                section.fields()
+            };
+            std::iter::once(AppearanceListItem::SectionHeader(*section))
+                .chain(fields.iter().map(|field| AppearanceListItem::Field(*field)))
+        })
     }
 
     /// Get the currently selected item
     pub fn selected_item(&self) -> Option<AppearanceListItem> {
-        self.visible_items().get(self.selected_index).cloned()
+        self.iter_visible_items().nth(self.list.selected_index)
     }
 
     /// Select next item
     pub fn select_next(&mut self) {
-        let count = self.visible_items().len();
-        if count > 0 {
-            self.selected_index = (self.selected_index + 1) % count;
-        }
+        let count = self.iter_visible_items().count();
+        self.list.select_next(count);
     }
 
     /// Select previous item
     pub fn select_prev(&mut self) {
-        let count = self.visible_items().len();
-        if count > 0 {
-            if self.selected_index == 0 {
-                self.selected_index = count - 1;
-            } else {
-                self.selected_index -= 1;
-            }
-        }
+        let count = self.iter_visible_items().count();
+        self.list.select_prev(count);
+    }
+
+    /// Move the selection down a page
+    pub fn page_down(&mut self) {
+        let count = self.iter_visible_items().count();
+        self.list.page_down(count);
+    }
+
+    /// Move the selection up a page
+    pub fn page_up(&mut self) {
+        self.list.page_up();
     }
 
     /// Toggle section collapsed state
@@ -878,14 +1433,7 @@ impl AppearanceViewModel {
 
     /// Update scroll offset for visible area
     pub fn update_scroll(&mut self, visible_height: usize) {
-        if visible_height == 0 {
-            return;
-        }
-        if self.selected_index < self.scroll_offset {
-            self.scroll_offset = self.selected_index;
-        } else if self.selected_index >= self.scroll_offset + visible_height {
-            self.scroll_offset = self.selected_index - visible_height + 1;
-        }
+        self.list.update_scroll(visible_height);
     }
 
     /// Check if there are pending changes
@@ -895,73 +1443,55 @@ impl AppearanceViewModel {
 
     /// Get the current value for a field
     pub fn get_field_value(&self, field: AppearanceField) -> FieldValue {
-        match field {
-            AppearanceField::Gaps => FieldValue::Integer(self.settings.gaps),
-            AppearanceField::CenterFocusedColumn => FieldValue::Enum(self.settings.center_focused_column),
-            AppearanceField::FocusRingOff => FieldValue::Boolean(self.settings.focus_ring.off),
-            AppearanceField::FocusRingWidth => FieldValue::Integer(self.settings.focus_ring.width),
-            AppearanceField::FocusRingActiveColor => FieldValue::Color(self.settings.focus_ring.active_color.clone()),
-            AppearanceField::FocusRingInactiveColor => FieldValue::Color(self.settings.focus_ring.inactive_color.clone()),
-            AppearanceField::BorderOff => FieldValue::Boolean(self.settings.border.off),
-            AppearanceField::BorderWidth => FieldValue::Integer(self.settings.border.width),
-            AppearanceField::BorderActiveColor => FieldValue::Color(self.settings.border.active_color.clone()),
-            AppearanceField::BorderInactiveColor => FieldValue::Color(self.settings.border.inactive_color.clone()),
-            AppearanceField::BorderUrgentColor => {
-                match &self.settings.border.urgent_color {
-                    Some(c) => FieldValue::Color(c.clone()),
-                    None => FieldValue::String("(not set)".to_string()),
-                }
-            }
-            AppearanceField::ShadowOn => FieldValue::Boolean(self.settings.shadow.on),
-            AppearanceField::ShadowDrawBehindWindow => FieldValue::Boolean(self.settings.shadow.draw_behind_window),
-            AppearanceField::ShadowSoftness => FieldValue::Integer(self.settings.shadow.softness),
-            AppearanceField::ShadowSpread => FieldValue::Integer(self.settings.shadow.spread),
-            AppearanceField::ShadowOffsetX => FieldValue::Integer(self.settings.shadow.offset_x),
-            AppearanceField::ShadowOffsetY => FieldValue::Integer(self.settings.shadow.offset_y),
-            AppearanceField::ShadowColor => FieldValue::Color(self.settings.shadow.color.clone()),
-            AppearanceField::StrutsLeft => FieldValue::OptionalInteger(self.settings.struts.left),
-            AppearanceField::StrutsRight => FieldValue::OptionalInteger(self.settings.struts.right),
-            AppearanceField::StrutsTop => FieldValue::OptionalInteger(self.settings.struts.top),
-            AppearanceField::StrutsBottom => FieldValue::OptionalInteger(self.settings.struts.bottom),
-        }
+        self.settings.get(field)
     }
 
-    /// Set a field value and track the change
+    /// Set a field value and track the change. Setting a field back to its
+    /// saved value unstages it.
     pub fn set_field_value(&mut self, field: AppearanceField, value: FieldValue) {
-        match (field, &value) {
-            (AppearanceField::Gaps, FieldValue::Integer(n)) => self.settings.gaps = *n,
-            (AppearanceField::CenterFocusedColumn, FieldValue::Enum(e)) => self.settings.center_focused_column = *e,
-            (AppearanceField::FocusRingOff, FieldValue::Boolean(b)) => self.settings.focus_ring.off = *b,
-            (AppearanceField::FocusRingWidth, FieldValue::Integer(n)) => self.settings.focus_ring.width = *n,
-            (AppearanceField::FocusRingActiveColor, FieldValue::Color(c)) => self.settings.focus_ring.active_color = c.clone(),
-            (AppearanceField::FocusRingInactiveColor, FieldValue::Color(c)) => self.settings.focus_ring.inactive_color = c.clone(),
-            (AppearanceField::BorderOff, FieldValue::Boolean(b)) => self.settings.border.off = *b,
-            (AppearanceField::BorderWidth, FieldValue::Integer(n)) => self.settings.border.width = *n,
-            (AppearanceField::BorderActiveColor, FieldValue::Color(c)) => self.settings.border.active_color = c.clone(),
-            (AppearanceField::BorderInactiveColor, FieldValue::Color(c)) => self.settings.border.inactive_color = c.clone(),
-            (AppearanceField::BorderUrgentColor, FieldValue::Color(c)) => self.settings.border.urgent_color = Some(c.clone()),
-            (AppearanceField::ShadowOn, FieldValue::Boolean(b)) => self.settings.shadow.on = *b,
-            (AppearanceField::ShadowDrawBehindWindow, FieldValue::Boolean(b)) => self.settings.shadow.draw_behind_window = *b,
-            (AppearanceField::ShadowSoftness, FieldValue::Integer(n)) => self.settings.shadow.softness = *n,
-            (AppearanceField::ShadowSpread, FieldValue::Integer(n)) => self.settings.shadow.spread = *n,
-            (AppearanceField::ShadowOffsetX, FieldValue::Integer(n)) => self.settings.shadow.offset_x = *n,
-            (AppearanceField::ShadowOffsetY, FieldValue::Integer(n)) => self.settings.shadow.offset_y = *n,
-            (AppearanceField::ShadowColor, FieldValue::Color(c)) => self.settings.shadow.color = c.clone(),
-            (AppearanceField::StrutsLeft, FieldValue::OptionalInteger(opt)) => self.settings.struts.left = *opt,
-            (AppearanceField::StrutsRight, FieldValue::OptionalInteger(opt)) => self.settings.struts.right = *opt,
-            (AppearanceField::StrutsTop, FieldValue::OptionalInteger(opt)) => self.settings.struts.top = *opt,
-            (AppearanceField::StrutsBottom, FieldValue::OptionalInteger(opt)) => self.settings.struts.bottom = *opt,
-            _ => return,
-        }
-
-        // Remove any existing change for this field and add the new one
-        self.pending_changes.retain(|c| c.field != field);
-        self.pending_changes.push(AppearanceChange { field, value });
+        if !self.settings.set(field, &value) {
+            return;
+        }
+
+        let original = self.original_settings.get(field);
+        if value == original {
+            self.pending_changes.remove(&field);
+        } else {
+            self.pending_changes.insert(field, AppearanceChange { field, original, value });
+        }
     }
 
     /// Check if a field has been modified
     pub fn is_field_modified(&self, field: AppearanceField) -> bool {
-        self.pending_changes.iter().any(|c| c.field == field)
+        self.pending_changes.contains_key(&field)
+    }
+
+    /// The staged change to a field, with the value it replaces
+    pub fn pending_change(&self, field: AppearanceField) -> Option<&AppearanceChange> {
+        self.pending_changes.get(&field)
+    }
+
+    /// Drop a field's staged change, restoring the saved value
+    pub fn revert_field(&mut self, field: AppearanceField) {
+        if let Some(change) = self.pending_changes.remove(&field) {
+            self.settings.set(field, &change.original);
+        }
+    }
+
+    /// Check whether the config sets a field, rather than leaving it to niri
+    pub fn is_field_explicit(&self, field: AppearanceField) -> bool {
+        self.field_sources.contains_key(&field)
+    }
+
+    /// Check whether a field currently holds niri's default value
+    pub fn is_field_default(&self, field: AppearanceField) -> bool {
+        self.settings.get(field) == self.defaults.get(field)
+    }
+
+    /// Stage niri's default value for a field
+    pub fn reset_to_default(&mut self, field: AppearanceField) {
+        let value = self.defaults.get(field);
+        self.set_field_value(field, value);
     }
 
     /// Toggle a boolean field
@@ -971,14 +1501,18 @@ impl AppearanceViewModel {
         }
     }
 
-    /// Increment an integer field
-    pub fn increment_field(&mut self, field: AppearanceField, amount: i32) {
+    /// Move an integer field by `steps` of its step size, staying in bounds
+    pub fn increment_field(&mut self, field: AppearanceField, steps: i32) {
+        let Some(bounds) = field.bounds() else {
+            return;
+        };
+        let adjust = |n: i32| bounds.clamp(n.saturating_add(steps * bounds.step));
         match self.get_field_value(field) {
             FieldValue::Integer(n) => {
-                self.set_field_value(field, FieldValue::Integer(n + amount));
+                self.set_field_value(field, FieldValue::Integer(adjust(n)));
             }
             FieldValue::OptionalInteger(opt) => {
-                let new_val = opt.unwrap_or(0) + amount;
+                let new_val = adjust(opt.unwrap_or(0));
                 self.set_field_value(field, FieldValue::OptionalInteger(Some(new_val)));
             }
             _ => {}
@@ -999,11 +1533,103 @@ impl AppearanceViewModel {
         self.pending_changes.clear();
     }
 
-    /// Apply pending changes to original (after save)
+    /// Re-stage field changes after a reload. A field whose value on disk no
+    /// longer matches the one the edit started from was changed externally,
+    /// so the edit is dropped and described instead.
+    pub fn rebase_changes(&mut self, changes: BTreeMap<AppearanceField, AppearanceChange>) -> Vec<String> {
+        let mut skipped = Vec::new();
+        for change in changes.into_values() {
+            let on_disk = self.original_settings.get(change.field);
+            if on_disk == change.original {
+                self.set_field_value(change.field, change.value);
+            } else {
+                skipped.push(format!("{}: changed on disk to {on_disk}", change.field.qualified_name()));
+            }
+        }
+        skipped
+    }
+
+    /// One line per staged change with old and new value, for commit messages
+    pub fn change_summary(&self) -> Vec<String> {
+        self.summarize_changes(|_| true)
+    }
+
+    /// Like `change_summary`, for one section's changes
+    pub fn section_change_summary(&self, section: AppearanceSection) -> Vec<String> {
+        self.summarize_changes(|change| change.field.section() == section)
+    }
+
+    fn summarize_changes(&self, include: impl Fn(&AppearanceChange) -> bool) -> Vec<String> {
+        self.pending_changes
+            .values()
+            .filter(|change| include(change))
+            .map(|change| format!("{} {}→{}", change.field.qualified_name(), change.original, change.value))
+            .collect()
+    }
+
+    /// Mark the staged changes as saved
     pub fn apply_changes(&mut self) {
         self.original_settings = self.settings.clone();
         self.pending_changes.clear();
     }
+
+    /// Section of the selected header or field
+    pub fn selected_section(&self) -> Option<AppearanceSection> {
+        match self.selected_item()? {
+            AppearanceListItem::SectionHeader(section) => Some(section),
+            AppearanceListItem::Field(field) => Some(field.section()),
+        }
+    }
+
+    /// Check if a section has staged changes
+    pub fn has_section_changes(&self, section: AppearanceSection) -> bool {
+        self.pending_changes.keys().any(|field| field.section() == section)
+    }
+
+    /// The saved settings with only one section's staged changes applied
+    pub fn settings_with_section(&self, section: AppearanceSection) -> AppearanceSettings {
+        let mut settings = self.original_settings.clone();
+        for change in self.pending_changes.values().filter(|c| c.field.section() == section) {
+            settings.set(change.field, &change.value);
+        }
+        settings
+    }
+
+    /// Drop one section's staged changes, restoring the saved values
+    pub fn revert_section(&mut self, section: AppearanceSection) {
+        for change in self.pending_changes.values().filter(|c| c.field.section() == section) {
+            self.settings.set(change.field, &change.original);
+        }
+        self.pending_changes.retain(|field, _| field.section() != section);
+    }
+
+    /// Open the section editor on the selected section
+    pub fn start_section_edit(&mut self) {
+        if let Some(section) = self.selected_section() {
+            self.section_edit = Some(SectionEditMode::new(section, &self.settings));
+        }
+    }
+
+    /// Stage every changed field of the section editor and close it.
+    /// Returns how many fields were staged; on error the editor stays open.
+    pub fn confirm_section_edit(&mut self) -> Result<usize, String> {
+        let Some(edit) = &self.section_edit else {
+            return Ok(0);
+        };
+        let changes = edit.changed_values()?;
+        let count = changes.len();
+        for (field, value) in changes {
+            self.set_field_value(field, value);
+        }
+        self.section_edit = None;
+        Ok(count)
+    }
+
+    /// Mark one section's changes as saved, keeping the others staged
+    pub fn apply_section(&mut self, section: AppearanceSection) {
+        self.original_settings = self.settings_with_section(section);
+        self.pending_changes.retain(|field, _| field.section() != section);
+    }
 }
 
 #[cfg(test)]
@@ -1058,4 +1684,215 @@ mod tests {
         vm.toggle_section(AppearanceSection::General);
         assert_eq!(vm.visible_items().len(), initial_count);
     }
+
+    #[test]
+    fn test_reset_to_default() {
+        let mut vm = AppearanceViewModel::new(AppearanceSettings {
+            gaps: 8,
+            ..Default::default()
+        });
+        vm.defaults.gaps = 12;
+        assert!(!vm.is_field_default(AppearanceField::Gaps));
+        assert!(vm.is_field_default(AppearanceField::FocusRingWidth));
+
+        vm.reset_to_default(AppearanceField::Gaps);
+        assert_eq!(vm.settings.gaps, 12);
+        assert!(vm.is_field_default(AppearanceField::Gaps));
+        assert!(vm.is_field_modified(AppearanceField::Gaps));
+    }
+
+    #[test]
+    fn test_changes_diff_against_original() {
+        let mut vm = AppearanceViewModel::new(AppearanceSettings::default());
+        vm.increment_field(AppearanceField::Gaps, 1);
+        vm.increment_field(AppearanceField::Gaps, 1);
+        vm.toggle_boolean(AppearanceField::ShadowOn);
+        assert_eq!(vm.change_summary(), vec!["gaps 16→20", "shadow on off→on"]);
+
+        // Stepping back to the saved value unstages the field
+        vm.increment_field(AppearanceField::Gaps, -2);
+        assert!(!vm.is_field_modified(AppearanceField::Gaps));
+        assert_eq!(vm.pending_changes.len(), 1);
+
+        vm.set_field_value(AppearanceField::BorderWidth, FieldValue::Integer(2));
+        vm.revert_field(AppearanceField::ShadowOn);
+        assert!(!vm.settings.shadow.on);
+        assert_eq!(vm.pending_change(AppearanceField::BorderWidth).unwrap().original, FieldValue::Integer(4));
+
+        // After a reload, edits whose starting value changed on disk are dropped
+        let changes = std::mem::take(&mut vm.pending_changes);
+        let mut reloaded = AppearanceViewModel::new(AppearanceSettings::default());
+        assert!(reloaded.rebase_changes(changes.clone()).is_empty());
+        assert!(reloaded.is_field_modified(AppearanceField::BorderWidth));
+
+        let mut on_disk = AppearanceSettings::default();
+        on_disk.border.width = 6;
+        let mut reloaded = AppearanceViewModel::new(on_disk);
+        assert_eq!(reloaded.rebase_changes(changes).len(), 1);
+        assert!(!reloaded.has_pending_changes());
+    }
+
+    #[test]
+    fn test_section_apply_and_revert() {
+        let mut vm = AppearanceViewModel::new(AppearanceSettings::default());
+        vm.set_field_value(AppearanceField::BorderWidth, FieldValue::Integer(2));
+        vm.set_field_value(AppearanceField::ShadowSoftness, FieldValue::Integer(10));
+        vm.set_field_value(AppearanceField::Gaps, FieldValue::Integer(24));
+
+        let partial = vm.settings_with_section(AppearanceSection::Shadow);
+        assert_eq!(partial.shadow.softness, 10);
+        assert_eq!(partial.border.width, 4);
+
+        vm.revert_section(AppearanceSection::Border);
+        assert_eq!(vm.settings.border.width, 4);
+        assert!(!vm.has_section_changes(AppearanceSection::Border));
+
+        vm.apply_section(AppearanceSection::Shadow);
+        assert_eq!(vm.original_settings.shadow.softness, 10);
+        assert_eq!(vm.original_settings.gaps, 16);
+        assert_eq!(vm.settings.gaps, 24);
+        assert!(vm.has_section_changes(AppearanceSection::General));
+        assert!(!vm.has_section_changes(AppearanceSection::Shadow));
+    }
+
+    #[test]
+    fn test_integer_bounds() {
+        let mut vm = AppearanceViewModel::new(AppearanceSettings::default());
+        vm.increment_field(AppearanceField::Gaps, 1);
+        assert_eq!(vm.settings.gaps, 18);
+        vm.set_field_value(AppearanceField::BorderWidth, FieldValue::Integer(1));
+        vm.increment_field(AppearanceField::BorderWidth, -3);
+        assert_eq!(vm.settings.border.width, 0);
+        vm.increment_field(AppearanceField::StrutsTop, 1);
+        assert_eq!(vm.settings.struts.top, Some(8));
+
+        let edit = AppearanceEditMode::new(AppearanceField::Gaps, "-5");
+        assert_eq!(edit.parsed_value(), Err("gaps must be between 0 and 256".to_string()));
+        let edit = AppearanceEditMode::new(AppearanceField::BorderWidth, "10000");
+        assert!(edit.parsed_value().is_err());
+        let edit = AppearanceEditMode::new(AppearanceField::ShadowOffsetY, "-4");
+        assert_eq!(edit.parsed_value(), Ok(FieldValue::Integer(-4)));
+        let edit = AppearanceEditMode::new(AppearanceField::Gaps, "16+8");
+        assert_eq!(edit.parsed_value(), Ok(FieldValue::Integer(24)));
+    }
+
+    #[test]
+    fn test_section_edit() {
+        let mut vm = AppearanceViewModel::new(AppearanceSettings::default());
+        vm.list.selected_index = vm
+            .visible_items()
+            .iter()
+            .position(|item| matches!(item, AppearanceListItem::SectionHeader(AppearanceSection::Shadow)))
+            .unwrap();
+        vm.start_section_edit();
+        let edit = vm.section_edit.as_mut().unwrap();
+        assert_eq!(edit.rows.len(), AppearanceSection::Shadow.fields().len());
+        // An unset urgent color stays unset rather than failing the form
+        let border = SectionEditMode::new(AppearanceSection::Border, &AppearanceSettings::default());
+        assert_eq!(border.changed_values(), Ok(Vec::new()));
+
+        // on: toggled with Space
+        edit.cycle(true);
+        // softness: typed
+        edit.focus_next();
+        edit.focus_next();
+        let softness = edit.focused_text().unwrap();
+        softness.cursor_end();
+        softness.delete_char();
+        softness.delete_char();
+        softness.insert_char('4');
+        assert!(edit.rows[2].is_modified());
+        assert!(!edit.rows[1].is_modified());
+
+        // An out-of-range spread blocks the whole form
+        edit.focus_next();
+        edit.focused_text().unwrap().insert_char('9');
+        edit.focused_text().unwrap().insert_char('9');
+        assert!(vm.confirm_section_edit().unwrap_err().contains("spread"));
+        assert!(vm.section_edit.is_some() && vm.pending_changes.is_empty());
+
+        let edit = vm.section_edit.as_mut().unwrap();
+        edit.focused_text().unwrap().delete_char();
+        edit.focused_text().unwrap().delete_char();
+        assert_eq!(vm.confirm_section_edit(), Ok(2));
+        assert!(vm.section_edit.is_none());
+        assert!(vm.settings.shadow.on);
+        assert_eq!(vm.settings.shadow.softness, 4);
+        assert_eq!(vm.pending_changes.len(), 2);
+    }
+
+    #[test]
+    fn test_column_width() {
+        let mut settings = AppearanceSettings { gaps: 16, ..Default::default() };
+        assert_eq!(settings.column_width(1920, 1), 1888);
+        assert_eq!(settings.column_width(1920, 2), 936);
+        // Columns and gaps fill the width exactly
+        assert_eq!(4 * settings.column_width(1920, 4) + 5 * 16, 1920);
+
+        settings.struts.left = Some(64);
+        settings.struts.right = Some(64);
+        assert_eq!(settings.column_width(1920, 2), 872);
+        assert_eq!(settings.column_width(100, 4), 0);
+    }
+
+    #[test]
+    fn test_window_insets() {
+        let mut settings = AppearanceSettings { gaps: 16, ..Default::default() };
+        settings.focus_ring.width = 4;
+        settings.struts.top = Some(32);
+        assert_eq!(settings.window_insets(), (12, 44, 12, 12));
+
+        // The border is drawn inside the tile, so only the ring counts
+        settings.focus_ring.off = true;
+        settings.border.width = 8;
+        assert_eq!(settings.window_insets(), (16, 48, 16, 16));
+
+        settings.gaps = 0;
+        settings.focus_ring.off = false;
+        assert_eq!(settings.window_insets(), (0, 28, 0, 0));
+    }
+
+    #[test]
+    fn test_color_palette() {
+        let mut settings = AppearanceSettings::default();
+        settings.border.active_color = ColorValue::Gradient {
+            from: "#FFC87F".to_string(),
+            to: "#505050".to_string(),
+            angle: None,
+            relative_to: None,
+            color_space: None,
+        };
+        assert_eq!(
+            settings.colors_except(AppearanceField::FocusRingActiveColor),
+            vec!["#505050", "#FFC87F", "#9b0000", "#0007", "#262626", "#00000050"]
+        );
+
+        let edit = AppearanceEditMode::new_color(AppearanceField::BorderInactiveColor, &ColorValue::Solid("#000".into()))
+            .with_palette(vec!["#ffc87f".to_string(), "#ff0000".to_string()]);
+        let mut cs = edit.color_state.unwrap();
+        assert!(cs.pick(1));
+        assert_eq!(cs.solid_color, "#ff0000");
+        cs.toggle_type();
+        cs.focused_field = ColorEditField::GradientTo;
+        assert!(cs.pick(0));
+        assert_eq!(cs.to_color_value().unwrap().colors(), vec!["#ff0000", "#ffc87f"]);
+        assert!(!cs.pick(5));
+    }
+
+    #[test]
+    fn test_inactive_suggestion() {
+        let color = ColorValue::Solid("#7fc8ff".to_string());
+        assert_eq!(color.dimmed(1.0), Some(ColorValue::Solid("#7fc8ff".to_string())));
+        assert_eq!(color.dimmed(0.5), Some(ColorValue::Solid("#4e606e".to_string())));
+        assert_eq!(ColorValue::Solid("#fff8".into()).dimmed(0.0), Some(ColorValue::Solid("#00000088".into())));
+        assert_eq!(ColorValue::Solid("red".into()).dimmed(0.5), None);
+
+        let edit = AppearanceEditMode::new_color(AppearanceField::BorderActiveColor, &color).with_inactive_factor(0.5);
+        assert_eq!(
+            edit.inactive_suggestion(),
+            Some((AppearanceField::BorderInactiveColor, ColorValue::Solid("#4e606e".to_string())))
+        );
+        let edit = AppearanceEditMode::new_color(AppearanceField::ShadowColor, &color);
+        assert_eq!(edit.inactive_suggestion(), None);
+    }
 }
@@ -0,0 +1,151 @@
+//! Every configured bind on one screen, grouped the way niri's hotkey
+//! overlay groups them, with a rebind flow: pick a bind, press the new
+//! combo, and the change is staged like an edit.
+
+use super::keybindings::{BindingStatus, EffectiveBinding, Keybinding};
+use super::selectable_list::SelectableList;
+
+/// A bind listed in the overlay
+#[derive(Debug, Clone)]
+pub struct OverlayEntry {
+    pub category: &'static str,
+    pub binding: Keybinding,
+    /// Index into the configured bindings; None for one added this session
+    pub original_index: Option<usize>,
+    pub staged: bool,
+}
+
+/// Waiting for the new combo of the selected bind
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyCapture {
+    /// Add Mod to the combo pressed. niri takes Mod combos before the
+    /// terminal sees them, so Mod is toggled with Tab instead of held.
+    pub mod_key: bool,
+    /// Why the last combo pressed wasn't taken
+    pub rejected: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BindOverlay {
+    pub entries: Vec<OverlayEntry>,
+    pub list: SelectableList,
+    pub capture: Option<KeyCapture>,
+}
+
+impl BindOverlay {
+    pub fn new(bindings: &[EffectiveBinding]) -> Self {
+        let mut overlay = Self::default();
+        overlay.refresh(bindings);
+        overlay
+    }
+
+    /// Rebuild the entries from the bindings, sorted by category and then
+    /// combo, keeping the selection on the same bind where it still exists.
+    /// niri's defaults aren't in the config, so they aren't listed.
+    pub fn refresh(&mut self, bindings: &[EffectiveBinding]) {
+        let selected = self.selected().map(|e| (e.original_index, e.binding.combo()));
+        let mut entries: Vec<OverlayEntry> = bindings
+            .iter()
+            .filter(|eb| eb.status != BindingStatus::Default)
+            .map(|eb| OverlayEntry {
+                category: eb.binding.action.category(),
+                binding: eb.binding.clone(),
+                original_index: eb.original_index,
+                staged: eb.status != BindingStatus::Unchanged,
+            })
+            .collect();
+        entries.sort_by(|a, b| a.category.cmp(b.category).then_with(|| a.binding.combo().cmp(&b.binding.combo())));
+        self.entries = entries;
+
+        let index = selected.and_then(|(original_index, combo)| {
+            self.entries.iter().position(|e| match original_index {
+                Some(_) => e.original_index == original_index,
+                None => e.original_index.is_none() && e.binding.combo() == combo,
+            })
+        });
+        match index {
+            Some(index) => self.list.selected_index = index,
+            None => self.list.clamp(self.entries.len()),
+        }
+    }
+
+    pub fn selected(&self) -> Option<&OverlayEntry> {
+        self.entries.get(self.list.selected_index)
+    }
+
+    pub fn select_next(&mut self) {
+        self.list.select_next(self.entries.len());
+    }
+
+    pub fn select_prev(&mut self) {
+        self.list.select_prev(self.entries.len());
+    }
+
+    /// Start waiting for the selected bind's new combo, with Mod on if the
+    /// bind uses it now
+    pub fn start_capture(&mut self) {
+        if let Some(entry) = self.selected() {
+            self.capture = Some(KeyCapture {
+                mod_key: entry.binding.modifiers.mod_key,
+                rejected: None,
+            });
+        }
+    }
+
+    pub fn toggle_capture_mod(&mut self) {
+        if let Some(capture) = &mut self.capture {
+            capture.mod_key = !capture.mod_key;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::keybindings::{BindingAction, BindingProperties, KeybindingsViewModel, Modifiers};
+
+    fn binding(combo: &str, action: BindingAction) -> Keybinding {
+        let (modifiers, key) = Modifiers::parse(combo);
+        Keybinding {
+            modifiers,
+            key,
+            properties: BindingProperties::default(),
+            action,
+            kdl_index: None,
+            source: None,
+        }
+    }
+
+    #[test]
+    fn test_bind_overlay() {
+        let mut vm = KeybindingsViewModel {
+            bindings: vec![
+                binding("Mod+T", BindingAction::Spawn(vec!["foot".to_string()])),
+                binding("Mod+Q", BindingAction::Simple("close-window".to_string())),
+                binding("Mod+Left", BindingAction::Simple("focus-column-left".to_string())),
+            ],
+            ..Default::default()
+        };
+        let mut overlay = BindOverlay::new(&vm.effective_bindings());
+        let listed: Vec<_> = overlay.entries.iter().map(|e| (e.category, e.binding.combo())).collect();
+        assert_eq!(
+            listed,
+            vec![
+                ("Focus", "Mod+Left".to_string()),
+                ("Program Execution", "Mod+T".to_string()),
+                ("Window Management", "Mod+Q".to_string()),
+            ]
+        );
+
+        // The selection follows the rebound bind to its new place
+        overlay.select_next();
+        overlay.start_capture();
+        assert_eq!(overlay.capture.as_ref().map(|c| c.mod_key), Some(true));
+        let entry = overlay.selected().unwrap().clone();
+        vm.rebind(entry.original_index, &entry.binding, "Mod+Return").unwrap();
+        overlay.refresh(&vm.effective_bindings());
+        let selected = overlay.selected().unwrap();
+        assert_eq!(selected.binding.combo(), "Mod+Return");
+        assert!(selected.staged);
+    }
+}
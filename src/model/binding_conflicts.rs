@@ -0,0 +1,85 @@
+use super::keybindings::Keybinding;
+
+/// Extra modifiers tried, in order, when looking for a free combo for a
+/// clashing binding
+pub const REBIND_MODIFIERS: &[&str] = &["Shift", "Ctrl", "Alt", "Ctrl+Shift", "Alt+Shift", "Ctrl+Alt"];
+
+/// What to do with an incoming binding whose combo is already configured
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictChoice {
+    /// Leave the configured binding and drop the incoming one
+    Keep,
+    /// Stage the incoming action on the configured binding's combo
+    Replace,
+    /// Stage the incoming action on the suggested free combo
+    Rebind,
+}
+
+/// An incoming binding and the configured one holding its combo
+#[derive(Debug, Clone)]
+pub struct BindingConflict {
+    pub incoming: Keybinding,
+    pub existing: Keybinding,
+}
+
+/// State for the dialog that walks through the clashes left after staging
+/// a template or generated bindings, one at a time
+#[derive(Debug, Clone)]
+pub struct ConflictResolver {
+    /// Where the incoming bindings came from, e.g. the template name
+    pub source: String,
+    pub conflicts: Vec<BindingConflict>,
+    pub index: usize,
+    /// Free combo for the current clash, given the changes staged so far
+    pub suggestion: Option<Keybinding>,
+    /// How many clashes got each choice, for the closing notice
+    pub kept: usize,
+    pub replaced: usize,
+    pub rebound: usize,
+}
+
+impl ConflictResolver {
+    pub fn new(source: impl Into<String>, conflicts: Vec<BindingConflict>) -> Self {
+        Self {
+            source: source.into(),
+            conflicts,
+            index: 0,
+            suggestion: None,
+            kept: 0,
+            replaced: 0,
+            rebound: 0,
+        }
+    }
+
+    pub fn current(&self) -> Option<&BindingConflict> {
+        self.conflicts.get(self.index)
+    }
+
+    /// Count the choice and move to the next clash; false once none are left
+    pub fn advance(&mut self, choice: ConflictChoice) -> bool {
+        match choice {
+            ConflictChoice::Keep => self.kept += 1,
+            ConflictChoice::Replace => self.replaced += 1,
+            ConflictChoice::Rebind => self.rebound += 1,
+        }
+        self.index += 1;
+        self.suggestion = None;
+        self.index < self.conflicts.len()
+    }
+
+    /// Keep the configured binds for every clash not decided yet
+    pub fn keep_rest(&mut self) {
+        self.kept += self.conflicts.len().saturating_sub(self.index);
+        self.index = self.conflicts.len();
+    }
+
+    /// One-line outcome, e.g. "App launcher: 1 replaced, 1 rebound"
+    pub fn summary(&self) -> String {
+        let parts: Vec<String> = [(self.replaced, "replaced"), (self.rebound, "rebound"), (self.kept, "kept")]
+            .iter()
+            .filter(|(count, _)| *count > 0)
+            .map(|(count, what)| format!("{count} {what}"))
+            .collect();
+        format!("{}: {}", self.source, parts.join(", "))
+    }
+}
@@ -0,0 +1,111 @@
+use super::keybindings::{BindingAction, BindingArg, BindingProperties, Keybinding, Modifiers};
+
+/// Modifier combinations offered by the generator
+const MODIFIER_CHOICES: &[&str] = &["Mod", "Mod+Ctrl", "Mod+Alt", "Mod+Ctrl+Alt", "Ctrl+Alt"];
+
+/// Keys and actions staged by the column layout generator
+const COLUMN_LAYOUT_ACTIONS: &[(&str, &str, Option<&str>)] = &[
+    ("R", "switch-preset-column-width", None),
+    ("F", "maximize-column", None),
+    ("C", "center-column", None),
+    ("Minus", "set-column-width", Some("-10%")),
+    ("Equal", "set-column-width", Some("+10%")),
+];
+
+/// A binding the generator would add, and whether its combo is already taken
+#[derive(Debug, Clone)]
+pub struct GeneratedBinding {
+    pub binding: Keybinding,
+    pub conflicts: bool,
+}
+
+/// State for the column width / layout binding generator dialog
+#[derive(Debug, Clone, Default)]
+pub struct BindingGenerator {
+    pub modifier_index: usize,
+}
+
+impl BindingGenerator {
+    /// The currently chosen modifier prefix (e.g., "Mod+Ctrl")
+    pub fn modifier(&self) -> &'static str {
+        MODIFIER_CHOICES[self.modifier_index % MODIFIER_CHOICES.len()]
+    }
+
+    pub fn next_modifier(&mut self) {
+        self.modifier_index = (self.modifier_index + 1) % MODIFIER_CHOICES.len();
+    }
+
+    pub fn prev_modifier(&mut self) {
+        self.modifier_index = if self.modifier_index == 0 {
+            MODIFIER_CHOICES.len() - 1
+        } else {
+            self.modifier_index - 1
+        };
+    }
+
+    /// Build the bindings for the chosen modifier, marking combos that already exist
+    pub fn generate(&self, existing: &[Keybinding]) -> Vec<GeneratedBinding> {
+        COLUMN_LAYOUT_ACTIONS
+            .iter()
+            .map(|(key, action, arg)| {
+                let (modifiers, key) = Modifiers::parse(&format!("{}+{}", self.modifier(), key));
+                let action = match arg {
+                    Some(arg) => {
+                        BindingAction::WithArg(action.to_string(), BindingArg::String(arg.to_string()))
+                    }
+                    None => BindingAction::Simple(action.to_string()),
+                };
+                let binding = Keybinding {
+                    modifiers,
+                    key,
+                    properties: BindingProperties::default(),
+                    action,
+                    kdl_index: None,
+                    source: None,
+                };
+                let conflicts = existing.iter().any(|b| b.same_combo(&binding));
+                GeneratedBinding { binding, conflicts }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_skips_existing_combos() {
+        let generator = BindingGenerator::default();
+        let (modifiers, key) = Modifiers::parse("Mod+f");
+        let existing = vec![Keybinding {
+            modifiers,
+            key,
+            properties: BindingProperties::default(),
+            action: BindingAction::Simple("fullscreen-window".to_string()),
+            kdl_index: Some(0),
+            source: None,
+        }];
+
+        let generated = generator.generate(&existing);
+        let maximize = generated
+            .iter()
+            .find(|g| g.binding.combo() == "Mod+F")
+            .unwrap();
+        assert!(maximize.conflicts);
+        assert!(generated.iter().filter(|g| g.conflicts).count() == 1);
+    }
+
+    #[test]
+    fn test_modifier_cycle() {
+        let mut generator = BindingGenerator::default();
+        assert_eq!(generator.modifier(), "Mod");
+        generator.prev_modifier();
+        assert_eq!(generator.modifier(), "Ctrl+Alt");
+        generator.next_modifier();
+        generator.next_modifier();
+        assert_eq!(generator.modifier(), "Mod+Ctrl");
+        let generated = generator.generate(&[]);
+        assert_eq!(generated[0].binding.combo(), "Mod+Ctrl+R");
+    }
+}
@@ -0,0 +1,259 @@
+/// A set of binds with `{name}` placeholders, defined in nirikiri's settings:
+///
+/// ```kdl
+/// binding-template "App launcher" key="T" {
+///     "Mod+{key}" { spawn "{cmd}"; }
+/// }
+/// ```
+///
+/// Placeholders may only appear inside strings. Properties of the template
+/// node are the values a prompt starts with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BindingTemplate {
+    pub name: String,
+    /// The bind nodes, as KDL
+    pub body: String,
+    pub defaults: Vec<(String, String)>,
+}
+
+impl BindingTemplate {
+    /// Placeholder names in order of first use
+    pub fn variables(&self) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        let mut rest = self.body.as_str();
+        while let Some(start) = rest.find('{') {
+            rest = &rest[start..];
+            if let Some(name) = placeholder(rest).filter(|name| !names.iter().any(|n| n == name)) {
+                names.push(name.to_string());
+            }
+            rest = &rest[1..];
+        }
+        names
+    }
+
+    /// The body with each placeholder replaced by its value, escaped for
+    /// use inside a KDL string. Values are inserted in one pass, so a value
+    /// that looks like a placeholder stays as typed.
+    pub fn render(&self, values: &[(String, String)]) -> String {
+        let mut body = String::with_capacity(self.body.len());
+        let mut rest = self.body.as_str();
+        while let Some(start) = rest.find('{') {
+            body.push_str(&rest[..start]);
+            rest = &rest[start..];
+            let value = placeholder(rest).and_then(|name| values.iter().find(|(n, _)| n == name));
+            match value {
+                Some((name, value)) => {
+                    body.push_str(&value.replace('\\', "\\\\").replace('"', "\\\""));
+                    rest = &rest[name.len() + 2..];
+                }
+                None => {
+                    body.push('{');
+                    rest = &rest[1..];
+                }
+            }
+        }
+        body.push_str(rest);
+        body
+    }
+}
+
+/// The name of the `{name}` placeholder `text` starts with, if it does
+fn placeholder(text: &str) -> Option<&str> {
+    let inner = text.strip_prefix('{')?;
+    let end = inner.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '-'))?;
+    (end > 0 && inner[end..].starts_with('}')).then(|| &inner[..end])
+}
+
+/// One variable being filled in
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemplateField {
+    pub name: String,
+    pub value: String,
+    pub cursor: usize,
+}
+
+impl TemplateField {
+    /// A field with the cursor after `value`
+    pub fn new(name: impl Into<String>, value: impl Into<String>) -> Self {
+        let value = value.into();
+        Self { name: name.into(), cursor: value.len(), value }
+    }
+
+    pub fn insert_char(&mut self, c: char) {
+        self.value.insert(self.cursor, c);
+        self.cursor += c.len_utf8();
+    }
+
+    pub fn delete_char(&mut self) {
+        if let Some(c) = self.value[..self.cursor].chars().next_back() {
+            self.cursor -= c.len_utf8();
+            self.value.remove(self.cursor);
+        }
+    }
+
+    pub fn cursor_left(&mut self) {
+        if let Some(c) = self.value[..self.cursor].chars().next_back() {
+            self.cursor -= c.len_utf8();
+        }
+    }
+
+    pub fn cursor_right(&mut self) {
+        if let Some(c) = self.value[self.cursor..].chars().next() {
+            self.cursor += c.len_utf8();
+        }
+    }
+}
+
+/// State for the dialog that fills in a binding template. Focus 0 is the
+/// template chooser, the variables follow.
+#[derive(Debug, Clone)]
+pub struct TemplatePrompt {
+    pub templates: Vec<BindingTemplate>,
+    pub template_index: usize,
+    pub fields: Vec<TemplateField>,
+    pub focused: usize,
+}
+
+impl TemplatePrompt {
+    /// None when no templates are defined
+    pub fn new(templates: Vec<BindingTemplate>) -> Option<Self> {
+        if templates.is_empty() {
+            return None;
+        }
+        let mut prompt = Self { templates, template_index: 0, fields: Vec::new(), focused: 0 };
+        prompt.reset_fields();
+        // Start on the first variable when there is only one template
+        if prompt.templates.len() == 1 && !prompt.fields.is_empty() {
+            prompt.focused = 1;
+        }
+        Some(prompt)
+    }
+
+    pub fn template(&self) -> &BindingTemplate {
+        &self.templates[self.template_index]
+    }
+
+    fn reset_fields(&mut self) {
+        let template = &self.templates[self.template_index];
+        self.fields = template
+            .variables()
+            .into_iter()
+            .map(|name| {
+                let value = template
+                    .defaults
+                    .iter()
+                    .find(|(n, _)| *n == name)
+                    .map(|(_, v)| v.clone())
+                    .unwrap_or_default();
+                TemplateField::new(name, value)
+            })
+            .collect();
+    }
+
+    pub fn cycle_template(&mut self, forward: bool) {
+        let count = self.templates.len();
+        self.template_index = if forward {
+            (self.template_index + 1) % count
+        } else {
+            (self.template_index + count - 1) % count
+        };
+        self.reset_fields();
+    }
+
+    pub fn focus_next(&mut self) {
+        self.focused = (self.focused + 1) % (self.fields.len() + 1);
+    }
+
+    pub fn focus_prev(&mut self) {
+        self.focused = (self.focused + self.fields.len()) % (self.fields.len() + 1);
+    }
+
+    /// The variable being edited, None while the chooser is focused
+    pub fn focused_field(&mut self) -> Option<&mut TemplateField> {
+        self.focused.checked_sub(1).and_then(|i| self.fields.get_mut(i))
+    }
+
+    pub fn insert_char(&mut self, c: char) {
+        if let Some(field) = self.focused_field() {
+            field.insert_char(c);
+        }
+    }
+
+    pub fn delete_char(&mut self) {
+        if let Some(field) = self.focused_field() {
+            field.delete_char();
+        }
+    }
+
+    pub fn cursor_left(&mut self) {
+        if let Some(field) = self.focused_field() {
+            field.cursor_left();
+        }
+    }
+
+    pub fn cursor_right(&mut self) {
+        if let Some(field) = self.focused_field() {
+            field.cursor_right();
+        }
+    }
+
+    /// The filled-in bind nodes, or the first variable left empty
+    pub fn render(&self) -> Result<String, String> {
+        if let Some(empty) = self.fields.iter().find(|f| f.value.trim().is_empty()) {
+            return Err(format!("{} needs a value", empty.name));
+        }
+        let values: Vec<(String, String)> =
+            self.fields.iter().map(|f| (f.name.clone(), f.value.trim().to_string())).collect();
+        Ok(self.template().render(&values))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn launcher() -> BindingTemplate {
+        BindingTemplate {
+            name: "App launcher".to_string(),
+            body: "\"Mod+{key}\" { spawn \"{cmd}\"; }\n\"Mod+Shift+{key}\" { spawn \"{cmd}\" \"--new\"; }".to_string(),
+            defaults: vec![("key".to_string(), "T".to_string())],
+        }
+    }
+
+    #[test]
+    fn test_template_variables() {
+        let template = launcher();
+        assert_eq!(template.variables(), vec!["key", "cmd"]);
+        assert_eq!(
+            template.render(&[("key".to_string(), "B".to_string()), ("cmd".to_string(), "say \"hi\"".to_string())]),
+            "\"Mod+B\" { spawn \"say \\\"hi\\\"\"; }\n\"Mod+Shift+B\" { spawn \"say \\\"hi\\\"\" \"--new\"; }"
+        );
+
+        // A value that names another placeholder isn't substituted again
+        assert_eq!(
+            template.render(&[("key".to_string(), "{cmd}".to_string()), ("cmd".to_string(), "foot".to_string())]),
+            "\"Mod+{cmd}\" { spawn \"foot\"; }\n\"Mod+Shift+{cmd}\" { spawn \"foot\" \"--new\"; }"
+        );
+    }
+
+    #[test]
+    fn test_template_prompt() {
+        let mut prompt = TemplatePrompt::new(vec![launcher()]).unwrap();
+        assert_eq!(prompt.focused, 1);
+        assert_eq!(prompt.fields[0].value, "T");
+        assert_eq!(prompt.render(), Err("cmd needs a value".to_string()));
+
+        prompt.focus_next();
+        for c in "foot".chars() {
+            prompt.insert_char(c);
+        }
+        prompt.cursor_left();
+        prompt.delete_char();
+        assert_eq!(prompt.fields[1].value, "fot");
+        assert!(prompt.render().unwrap().starts_with("\"Mod+T\" { spawn \"fot\"; }"));
+
+        prompt.focus_next();
+        assert_eq!(prompt.focused, 0);
+        assert!(TemplatePrompt::new(Vec::new()).is_none());
+    }
+}
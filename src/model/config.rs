@@ -1,23 +1,168 @@
 use anyhow::{Context, Result};
-use kdl::{KdlDocument, KdlNode, KdlEntry, KdlValue};
+use kdl::{KdlDocument, KdlNode, KdlEntry};
+use std::fmt;
 use std::path::PathBuf;
 
-use super::output::Position;
+use crate::config::kdl_format::{ensure_v1_new, format_node};
+use crate::config::kdl_value::{as_int, color as kdl_color, float, int, string};
+use super::output::{OutputMode, OutputTransform, Position};
+
+/// Where an item was defined in the config file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceLocation {
+    pub path: PathBuf,
+    pub line: usize, // 1-indexed
+}
+
+impl fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let file = self
+            .path
+            .file_name()
+            .map(|n| n.to_string_lossy())
+            .unwrap_or_else(|| self.path.to_string_lossy());
+        write!(f, "{}:{}", file, self.line)
+    }
+}
 
 /// Wrapper around KdlDocument that preserves formatting
 pub struct ConfigDocument {
     pub doc: KdlDocument,
     pub path: PathBuf,
+    /// Text the document was parsed from, used to map node spans to line numbers
+    pub source: String,
 }
 
+/// Configs larger than this are refused rather than parsed
+pub const MAX_CONFIG_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Configs larger than this are parsed off the UI thread
+pub const BACKGROUND_LOAD_BYTES: u64 = 512 * 1024;
+
 impl ConfigDocument {
     pub fn load(path: PathBuf) -> Result<Self> {
-        let content = std::fs::read_to_string(&path)
+        let size = std::fs::metadata(&path)
+            .with_context(|| format!("Failed to read config file: {}", path.display()))?
+            .len();
+        if size > MAX_CONFIG_BYTES {
+            anyhow::bail!(
+                "Config file {} is {:.1} MiB, larger than the {} MiB limit",
+                path.display(),
+                size as f64 / (1024.0 * 1024.0),
+                MAX_CONFIG_BYTES / (1024 * 1024)
+            );
+        }
+        let bytes = std::fs::read(&path)
             .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+        let content = decode_utf8(bytes)
+            .with_context(|| format!("Config file is not valid UTF-8: {}", path.display()))?;
         // niri uses KDL v1 syntax, so parse explicitly as v1
         let doc = KdlDocument::parse_v1(&content)
             .with_context(|| format!("Failed to parse KDL config from {}", path.display()))?;
-        Ok(Self { doc, path })
+        Ok(Self { doc, path, source: content })
+    }
+
+    /// Get the source location of a node parsed from this document.
+    ///
+    /// Nodes created after loading have no meaningful span and return None.
+    pub fn locate(&self, node: &KdlNode) -> Option<SourceLocation> {
+        // Use the name's span so leading comments and blank lines are not counted
+        let offset = node.name().span().offset();
+        if offset == 0 && !self.source.starts_with(node.name().value()) {
+            return None;
+        }
+        let prefix = self.source.get(..offset)?;
+        Some(SourceLocation {
+            path: self.path.clone(),
+            line: prefix.matches('\n').count() + 1,
+        })
+    }
+
+    /// Get the source location of an output block
+    pub fn output_location(&self, name: &str) -> Option<SourceLocation> {
+        let (idx, _commented) = self.find_output_node(name)?;
+        self.locate(self.doc.nodes().get(idx)?)
+    }
+
+    /// Indices of every `output` block for the named output, in file order
+    fn output_nodes(&self, name: &str) -> Vec<usize> {
+        self.doc
+            .nodes()
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| node.name().value() == "output" && node.get(0).and_then(|v| v.as_string()) == Some(name))
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    /// Where each of the named output's blocks is defined, in file order
+    pub fn output_locations(&self, name: &str) -> Vec<SourceLocation> {
+        self.output_nodes(name).into_iter().filter_map(|idx| self.locate(&self.doc.nodes()[idx])).collect()
+    }
+
+    /// Outputs with more than one `output` block, by first appearance. Only
+    /// the first block is read and written.
+    pub fn duplicate_outputs(&self) -> Vec<String> {
+        let mut seen = Vec::new();
+        let mut duplicates = Vec::new();
+        for node in self.doc.nodes().iter().filter(|n| n.name().value() == "output") {
+            let Some(name) = node.get(0).and_then(|v| v.as_string()) else {
+                continue;
+            };
+            if seen.contains(&name) {
+                if !duplicates.iter().any(|d| d == name) {
+                    duplicates.push(name.to_string());
+                }
+            } else {
+                seen.push(name);
+            }
+        }
+        duplicates
+    }
+
+    /// Fold the named output's later blocks into the first one and remove
+    /// them. Settings the first block already has win, as they did before;
+    /// the others are moved over. Returns the number of blocks removed.
+    pub fn merge_output_blocks(&mut self, name: &str) -> usize {
+        let indices = self.output_nodes(name);
+        let Some((&first, rest)) = indices.split_first() else {
+            return 0;
+        };
+        // Remove from the end so earlier indices stay valid
+        let mut moved = Vec::new();
+        for &idx in rest.iter().rev() {
+            let node = self.doc.nodes_mut().remove(idx);
+            if let Some(children) = node.children() {
+                moved.splice(0..0, children.nodes().iter().cloned());
+            }
+        }
+
+        let node = &mut self.doc.nodes_mut()[first];
+        if node.children().is_none() {
+            node.set_children(KdlDocument::new());
+        }
+        let children = node.children_mut().as_mut().unwrap();
+        for mut child in moved {
+            if children.nodes().iter().all(|n| n.name().value() != child.name().value()) {
+                // A block's first child holds the line break after its `{`
+                if let Some(format) = child.format_mut() {
+                    format.leading = format.leading.trim_start_matches('\n').to_string();
+                }
+                children.nodes_mut().push(child);
+            }
+        }
+        rest.len()
+    }
+
+    /// Check whether the file on disk differs from what was loaded or last saved
+    pub fn changed_on_disk(&self) -> Result<bool> {
+        if !self.path.exists() {
+            // A document started for a file that doesn't exist yet is unchanged
+            return Ok(!self.source.is_empty());
+        }
+        let content = std::fs::read(&self.path)
+            .with_context(|| format!("Failed to read config file: {}", self.path.display()))?;
+        Ok(content != self.source.as_bytes())
     }
 
     pub fn save(&mut self) -> Result<()> {
@@ -29,10 +174,17 @@ impl ConfigDocument {
         }
 
         // Ensure v1 format for niri compatibility
-        self.doc.ensure_v1();
+        ensure_v1_new(&mut self.doc);
+
+        let content = self.doc.to_string();
+        std::fs::write(&self.path, &content)
+            .with_context(|| format!("Failed to write config file: {}", self.path.display()))?;
 
-        std::fs::write(&self.path, self.doc.to_string())
-            .with_context(|| "Failed to write config file")?;
+        // Re-parse what was written so node spans match the file on disk again
+        if let Ok(doc) = KdlDocument::parse_v1(&content) {
+            self.doc = doc;
+        }
+        self.source = content;
         Ok(())
     }
 
@@ -67,22 +219,95 @@ impl ConfigDocument {
 
         for child in children.nodes() {
             if child.name().value() == "position" {
-                let x = child
-                    .get("x")
-                    .and_then(|v| v.as_integer())
-                    .unwrap_or(0) as i32;
-                let y = child
-                    .get("y")
-                    .and_then(|v| v.as_integer())
-                    .unwrap_or(0) as i32;
+                let x = child.get("x").and_then(as_int).unwrap_or(0);
+                let y = child.get("y").and_then(as_int).unwrap_or(0);
                 return Some(Position::new(x, y));
             }
         }
         None
     }
 
+    /// The string argument of a child of an output block, such as
+    /// `background-color "#003300"`
+    pub fn get_output_string(&self, name: &str, child: &str) -> Option<String> {
+        let (idx, _commented) = self.find_output_node(name)?;
+        let children = self.doc.nodes().get(idx)?.children()?;
+        let node = children.nodes().iter().find(|n| n.name().value() == child)?;
+        node.get(0).and_then(|v| v.as_string()).map(str::to_string)
+    }
+
+    /// Whether an output block has a flag child such as `focus-at-startup`
+    pub fn has_output_flag(&self, name: &str, flag: &str) -> bool {
+        self.find_output_node(name)
+            .and_then(|(idx, _)| self.doc.nodes().get(idx)?.children())
+            .is_some_and(|children| children.nodes().iter().any(|n| n.name().value() == flag))
+    }
+
     /// Update or create position for an output
     pub fn set_output_position(&mut self, name: &str, position: Position) -> Result<()> {
+        self.set_output_child(
+            name,
+            "position",
+            vec![
+                KdlEntry::new_prop("x", int(position.x)),
+                KdlEntry::new_prop("y", int(position.y)),
+            ],
+        )
+    }
+
+    /// Update or create the mode of an output, e.g. `mode "2560x1440@143.912"`
+    pub fn set_output_mode(&mut self, name: &str, mode: &OutputMode) -> Result<()> {
+        self.set_output_child(name, "mode", vec![KdlEntry::new(string(mode.config_string()))])
+    }
+
+    /// Update or create the scale of an output
+    pub fn set_output_scale(&mut self, name: &str, scale: f64) -> Result<()> {
+        self.set_output_child(name, "scale", vec![KdlEntry::new(float(scale))])
+    }
+
+    /// Update or create the transform of an output, e.g. `transform "90"`
+    pub fn set_output_transform(&mut self, name: &str, transform: OutputTransform) -> Result<()> {
+        self.set_output_child(name, "transform", vec![KdlEntry::new(string(transform.as_str()))])
+    }
+
+    /// Set a color child of an output block, e.g. `backdrop-color "#001100"`;
+    /// an empty color removes it
+    pub fn set_output_color(&mut self, name: &str, child: &str, color: &str) -> Result<()> {
+        if color.is_empty() {
+            return self.set_output_flag(name, child, false);
+        }
+        self.set_output_child(name, child, vec![KdlEntry::new(kdl_color(color))])
+    }
+
+    /// Add or remove a flag child of an output block, such as `off` or
+    /// `variable-refresh-rate`. A flag that is already set keeps its
+    /// properties (e.g. `on-demand=true`).
+    pub fn set_output_flag(&mut self, name: &str, flag: &str, on: bool) -> Result<()> {
+        let existing = self.find_output_node(name).and_then(|(idx, _)| {
+            let children = self.doc.nodes()[idx].children()?;
+            Some((idx, children.nodes().iter().position(|n| n.name().value() == flag)?))
+        });
+        match (existing, on) {
+            (Some(_), true) | (None, false) => {}
+            (None, true) => self.set_output_child(name, flag, Vec::new())?,
+            (Some((idx, child)), false) => {
+                let children = self.doc.nodes_mut()[idx].children_mut().as_mut().unwrap();
+                let removed = children.nodes_mut().remove(child);
+                // The first child holds the line break after `{`; pass it on
+                let breaks_line = removed.format().is_some_and(|f| f.leading.starts_with('\n'));
+                if let Some(format) = children.nodes_mut().get_mut(child).and_then(|n| n.format_mut()) {
+                    if breaks_line && !format.leading.starts_with('\n') {
+                        format.leading.insert(0, '\n');
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Replace the entries of a child node in an output block, creating the
+    /// child and the block as needed
+    fn set_output_child(&mut self, name: &str, child: &str, entries: Vec<KdlEntry>) -> Result<()> {
         if let Some((idx, commented)) = self.find_output_node(name) {
             // Get mutable access to the node
             let node = self.doc.nodes_mut().get_mut(idx).unwrap();
@@ -99,45 +324,179 @@ impl ConfigDocument {
 
             let children = node.children_mut().as_mut().unwrap();
 
-            // Find or create position node
-            let position_idx = children
-                .nodes()
-                .iter()
-                .position(|n| n.name().value() == "position");
-
-            if let Some(pos_idx) = position_idx {
-                // Update existing position node
-                let pos_node = children.nodes_mut().get_mut(pos_idx).unwrap();
-                pos_node.entries_mut().clear();
-                pos_node.push(KdlEntry::new_prop("x", KdlValue::Integer(position.x as i128)));
-                pos_node.push(KdlEntry::new_prop("y", KdlValue::Integer(position.y as i128)));
-                pos_node.autoformat();
+            if let Some(existing) = children.nodes_mut().iter_mut().find(|n| n.name().value() == child) {
+                // Update the existing node, keeping its indentation
+                existing.entries_mut().clear();
+                existing.entries_mut().extend(entries);
             } else {
-                // Create new position node
-                let mut pos_node = KdlNode::new("position");
-                pos_node.push(KdlEntry::new_prop("x", KdlValue::Integer(position.x as i128)));
-                pos_node.push(KdlEntry::new_prop("y", KdlValue::Integer(position.y as i128)));
-                pos_node.autoformat();
-                children.nodes_mut().push(pos_node);
+                let mut new_node = KdlNode::new(child);
+                new_node.entries_mut().extend(entries);
+                format_node(&mut new_node, 1);
+                children.nodes_mut().push(new_node);
             }
         } else {
             // Create new output node with proper formatting
             let mut output_node = KdlNode::new("output");
-            output_node.push(KdlEntry::new(KdlValue::String(name.to_string())));
+            output_node.push(KdlEntry::new(string(name)));
 
             let mut children = KdlDocument::new();
-            let mut pos_node = KdlNode::new("position");
-            pos_node.push(KdlEntry::new_prop("x", KdlValue::Integer(position.x as i128)));
-            pos_node.push(KdlEntry::new_prop("y", KdlValue::Integer(position.y as i128)));
-            pos_node.autoformat();
-            children.nodes_mut().push(pos_node);
-            children.autoformat();
+            let mut new_node = KdlNode::new(child);
+            new_node.entries_mut().extend(entries);
+            children.nodes_mut().push(new_node);
 
             output_node.set_children(children);
-            output_node.autoformat();
+            format_node(&mut output_node, 0);
             self.doc.nodes_mut().push(output_node);
         }
         Ok(())
     }
 }
 
+/// Decode config bytes, reporting where the first invalid UTF-8 sequence is
+fn decode_utf8(bytes: Vec<u8>) -> Result<String> {
+    String::from_utf8(bytes).map_err(|e| {
+        let valid = e.utf8_error().valid_up_to();
+        let bytes = e.as_bytes();
+        let prefix = &bytes[..valid];
+        let line = prefix.iter().filter(|&&b| b == b'\n').count() + 1;
+        let line_start = prefix.iter().rposition(|&b| b == b'\n').map_or(0, |p| p + 1);
+        anyhow::anyhow!(
+            "invalid byte 0x{:02x} at line {}, column {}",
+            bytes[valid],
+            line,
+            valid - line_start + 1
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_utf8_reports_position() {
+        assert_eq!(decode_utf8(b"binds {\n}\n".to_vec()).unwrap(), "binds {\n}\n");
+
+        let err = decode_utf8(b"binds {\n    Mod+T \xff\n}".to_vec()).unwrap_err();
+        assert_eq!(err.to_string(), "invalid byte 0xff at line 2, column 11");
+    }
+
+    #[test]
+    fn test_load_rejects_non_utf8() {
+        let path = std::env::temp_dir().join(format!("nirikiri-latin1-{}.kdl", std::process::id()));
+        std::fs::write(&path, b"// caf\xe9\noutput \"DP-1\" {}\n").unwrap();
+        let err = ConfigDocument::load(path.clone()).err().unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let chain: Vec<String> = err.chain().map(|c| c.to_string()).collect();
+        assert!(chain[0].starts_with("Config file is not valid UTF-8"));
+        assert_eq!(chain[1], "invalid byte 0xe9 at line 1, column 7");
+    }
+
+    #[test]
+    fn test_changed_on_disk() {
+        let path = std::env::temp_dir().join(format!("nirikiri-conflict-{}.kdl", std::process::id()));
+        std::fs::write(&path, "layout { gaps 16; }\n").unwrap();
+        let config = ConfigDocument::load(path.clone()).unwrap();
+        assert!(!config.changed_on_disk().unwrap());
+
+        std::fs::write(&path, "layout { gaps 24; }\n").unwrap();
+        assert!(config.changed_on_disk().unwrap());
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(config.changed_on_disk().unwrap());
+    }
+
+    #[test]
+    fn test_set_output_mode_and_scale() {
+        let source = "output \"DP-1\" {\n    mode \"1920x1080@60.000\"\n    position x=0 y=0\n}\n";
+        let mut config = ConfigDocument {
+            doc: KdlDocument::parse_v1(source).unwrap(),
+            path: PathBuf::from("config.kdl"),
+            source: source.to_string(),
+        };
+        let mode = |width, height, refresh_rate| OutputMode { width, height, refresh_rate, is_preferred: false };
+        config.set_output_mode("DP-1", &mode(2560, 1440, 143.912)).unwrap();
+        config.set_output_mode("HDMI-A-1", &mode(3840, 2160, 60.0)).unwrap();
+        config.set_output_scale("DP-1", 1.25).unwrap();
+        config.set_output_scale("HDMI-A-1", 2.0).unwrap();
+        ensure_v1_new(&mut config.doc);
+
+        assert_eq!(
+            config.doc.to_string(),
+            "output \"DP-1\" {\n    mode \"2560x1440@143.912\"\n    position x=0 y=0\n    scale 1.25\n}\n\
+             output \"HDMI-A-1\" {\n    mode \"3840x2160@60.000\"\n    scale 2.0\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_set_output_transform_and_flags() {
+        let source = "output \"DP-1\" {\n    off\n    variable-refresh-rate on-demand=true\n    position x=0 y=0\n}\n";
+        let mut config = ConfigDocument {
+            doc: KdlDocument::parse_v1(source).unwrap(),
+            path: PathBuf::from("config.kdl"),
+            source: source.to_string(),
+        };
+        config.set_output_flag("DP-1", "off", false).unwrap();
+        config.set_output_flag("DP-1", "variable-refresh-rate", true).unwrap();
+        config.set_output_transform("DP-1", OutputTransform::Rotate90).unwrap();
+        config.set_output_flag("HDMI-A-1", "off", false).unwrap();
+        config.set_output_flag("HDMI-A-1", "off", true).unwrap();
+        ensure_v1_new(&mut config.doc);
+
+        assert_eq!(
+            config.doc.to_string(),
+            "output \"DP-1\" {\n    variable-refresh-rate on-demand=true\n    position x=0 y=0\n    transform \"90\"\n}\n\
+             output \"HDMI-A-1\" {\n    off\n}\n"
+        );
+
+        assert!(config.has_output_flag("HDMI-A-1", "off"));
+        assert!(!config.has_output_flag("HDMI-A-1", "focus-at-startup"));
+        config.set_output_flag("DP-1", "variable-refresh-rate", false).unwrap();
+        ensure_v1_new(&mut config.doc);
+        assert!(!config.doc.to_string().contains("variable-refresh-rate"));
+    }
+
+    #[test]
+    fn test_set_output_colors() {
+        let source = "output \"DP-1\" {\n    background-color \"#003300\"\n    position x=0 y=0\n}\n";
+        let mut config = ConfigDocument {
+            doc: KdlDocument::parse_v1(source).unwrap(),
+            path: PathBuf::from("config.kdl"),
+            source: source.to_string(),
+        };
+        assert_eq!(config.get_output_string("DP-1", "background-color").as_deref(), Some("#003300"));
+        assert_eq!(config.get_output_string("DP-1", "backdrop-color"), None);
+
+        config.set_output_color("DP-1", "background-color", "").unwrap();
+        config.set_output_color("DP-1", "backdrop-color", "#001100").unwrap();
+        ensure_v1_new(&mut config.doc);
+        assert_eq!(
+            config.doc.to_string(),
+            "output \"DP-1\" {\n    position x=0 y=0\n    backdrop-color \"#001100\"\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_merge_duplicate_output_blocks() {
+        let source = "output \"DP-1\" {\n    scale 2\n}\n\n// Added later\noutput \"eDP-1\" {\n    off\n}\n\
+                      output \"DP-1\" {\n    position x=1920 y=0\n    scale 1.5\n}\noutput \"DP-1\"\n";
+        let mut config = ConfigDocument {
+            doc: KdlDocument::parse_v1(source).unwrap(),
+            path: PathBuf::from("config.kdl"),
+            source: source.to_string(),
+        };
+        assert_eq!(config.duplicate_outputs(), vec!["DP-1"]);
+        let lines: Vec<usize> = config.output_locations("DP-1").iter().map(|loc| loc.line).collect();
+        assert_eq!(lines, vec![1, 9, 13]);
+
+        assert_eq!(config.merge_output_blocks("DP-1"), 2);
+        assert_eq!(config.merge_output_blocks("eDP-1"), 0);
+        ensure_v1_new(&mut config.doc);
+        assert_eq!(
+            config.doc.to_string(),
+            "output \"DP-1\" {\n    scale 2\n    position x=1920 y=0\n}\n\n// Added later\noutput \"eDP-1\" {\n    off\n}\n"
+        );
+        assert!(config.duplicate_outputs().is_empty());
+    }
+}
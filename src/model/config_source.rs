@@ -0,0 +1,194 @@
+use std::path::{Path, PathBuf};
+
+/// Where a candidate config path comes from, in order of precedence
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigSource {
+    /// --config or a config picked with Ctrl+O
+    Override,
+    /// $NIRI_CONFIG, which niri reads instead of everything below
+    NiriConfig,
+    /// $XDG_CONFIG_HOME/niri/config.kdl
+    XdgConfigHome,
+    /// ~/.config/niri/config.kdl, used when $XDG_CONFIG_HOME is unset
+    Home,
+    /// /etc/niri/config.kdl, read by niri only when no user config exists
+    System,
+}
+
+impl ConfigSource {
+    pub fn label(self) -> &'static str {
+        match self {
+            ConfigSource::Override => "--config / Ctrl+O",
+            ConfigSource::NiriConfig => "$NIRI_CONFIG",
+            ConfigSource::XdgConfigHome => "$XDG_CONFIG_HOME",
+            ConfigSource::Home => "~/.config",
+            ConfigSource::System => "/etc",
+        }
+    }
+}
+
+/// One place a config was looked for
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigCandidate {
+    pub source: ConfigSource,
+    /// None when the variable behind it isn't set
+    pub path: Option<PathBuf>,
+    pub exists: bool,
+}
+
+/// The config nirikiri edits and every place it was looked for, shown in
+/// the config source dialog
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigDiscovery {
+    pub candidates: Vec<ConfigCandidate>,
+    /// Index into `candidates` of the config being edited
+    pub chosen: usize,
+    /// The chosen config exists but can't be written
+    pub read_only: bool,
+}
+
+/// niri's system-wide fallback config
+pub const SYSTEM_CONFIG: &str = "/etc/niri/config.kdl";
+
+impl ConfigDiscovery {
+    /// Pick the config the way niri does: $NIRI_CONFIG if set, else the
+    /// user config if it exists, else /etc if that exists, else the user
+    /// path, where niri writes its default config on first start. An
+    /// override from --config or Ctrl+O beats all of them. Empty variables
+    /// count as unset and a relative $XDG_CONFIG_HOME is ignored, as the
+    /// XDG spec asks.
+    pub fn resolve(
+        override_path: Option<PathBuf>,
+        niri_config: Option<PathBuf>,
+        xdg_config_home: Option<PathBuf>,
+        home: Option<PathBuf>,
+        exists: impl Fn(&Path) -> bool,
+    ) -> Option<Self> {
+        let set = |path: Option<PathBuf>| path.filter(|p| !p.as_os_str().is_empty());
+        let candidate = |source, path: Option<PathBuf>| ConfigCandidate {
+            source,
+            exists: path.as_deref().is_some_and(&exists),
+            path,
+        };
+
+        let mut candidates = Vec::new();
+        let override_path = set(override_path);
+        if override_path.is_some() {
+            candidates.push(candidate(ConfigSource::Override, override_path));
+        }
+        candidates.push(candidate(ConfigSource::NiriConfig, set(niri_config)));
+        let user = match set(xdg_config_home).filter(|p| p.is_absolute()) {
+            Some(dir) => candidate(ConfigSource::XdgConfigHome, Some(dir.join("niri").join("config.kdl"))),
+            None => candidate(
+                ConfigSource::Home,
+                set(home).map(|home| home.join(".config").join("niri").join("config.kdl")),
+            ),
+        };
+        candidates.push(user);
+        candidates.push(candidate(ConfigSource::System, Some(PathBuf::from(SYSTEM_CONFIG))));
+
+        let user = candidates.len() - 2;
+        let system = candidates.len() - 1;
+        let chosen = if let Some(i) = candidates.iter().position(|c| {
+            matches!(c.source, ConfigSource::Override | ConfigSource::NiriConfig) && c.path.is_some()
+        }) {
+            i
+        } else if candidates[user].path.is_none() {
+            // Without a home directory only the system config is left
+            if !candidates[system].exists {
+                return None;
+            }
+            system
+        } else if !candidates[user].exists && candidates[system].exists {
+            system
+        } else {
+            user
+        };
+        Some(Self { candidates, chosen, read_only: false })
+    }
+
+    pub fn chosen(&self) -> &ConfigCandidate {
+        &self.candidates[self.chosen]
+    }
+
+    pub fn path(&self) -> &Path {
+        self.chosen().path.as_deref().unwrap_or(Path::new(SYSTEM_CONFIG))
+    }
+
+    /// The per-user config path, where a system config is copied to
+    pub fn user_path(&self) -> Option<&Path> {
+        self.candidates
+            .iter()
+            .find(|c| matches!(c.source, ConfigSource::XdgConfigHome | ConfigSource::Home))
+            .and_then(|c| c.path.as_deref())
+    }
+
+    /// Only a system config we can't write was found; copying it to the
+    /// user path makes it editable
+    pub fn needs_copy(&self) -> bool {
+        self.chosen().source == ConfigSource::System && self.read_only && self.user_path().is_some()
+    }
+
+    /// Why the chosen config won over the others
+    pub fn reason(&self) -> String {
+        let chosen = self.chosen();
+        match chosen.source {
+            ConfigSource::Override => "Opened with --config or Ctrl+O, which takes precedence over niri's own lookup".into(),
+            ConfigSource::NiriConfig => "$NIRI_CONFIG is set, so niri reads this file and ignores the others".into(),
+            ConfigSource::XdgConfigHome | ConfigSource::Home if chosen.exists => {
+                "The user config exists; niri only falls back to /etc without one".into()
+            }
+            ConfigSource::XdgConfigHome | ConfigSource::Home => {
+                "No config exists yet; niri writes its default config here on first start".into()
+            }
+            ConfigSource::System => "No user config exists, so niri reads the system-wide one".into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_discovery_precedence() {
+        let home = Some(PathBuf::from("/home/me"));
+        let user = PathBuf::from("/home/me/.config/niri/config.kdl");
+        let xdg_user = PathBuf::from("/xdg/niri/config.kdl");
+
+        // Nothing exists: the user path, where niri creates its default
+        let discovery = ConfigDiscovery::resolve(None, None, None, home.clone(), |_| false).unwrap();
+        assert_eq!(discovery.path(), user);
+        assert_eq!(discovery.chosen().source, ConfigSource::Home);
+
+        // Only /etc exists: niri falls back to it
+        let discovery =
+            ConfigDiscovery::resolve(None, None, None, home.clone(), |p| p == Path::new(SYSTEM_CONFIG)).unwrap();
+        assert_eq!(discovery.chosen().source, ConfigSource::System);
+        assert_eq!(discovery.user_path(), Some(user.as_path()));
+        assert!(!discovery.needs_copy());
+        let read_only = ConfigDiscovery { read_only: true, ..discovery };
+        assert!(read_only.needs_copy());
+
+        // An existing user config wins over /etc; a relative XDG dir is ignored
+        let discovery = ConfigDiscovery::resolve(None, None, Some("xdg".into()), home.clone(), |_| true).unwrap();
+        assert_eq!(discovery.path(), user);
+        let discovery = ConfigDiscovery::resolve(None, None, Some("/xdg".into()), home.clone(), |_| true).unwrap();
+        assert_eq!(discovery.path(), xdg_user);
+        assert_eq!(discovery.chosen().source, ConfigSource::XdgConfigHome);
+
+        // $NIRI_CONFIG wins even when missing, unless empty; the override beats it
+        let discovery =
+            ConfigDiscovery::resolve(None, Some("/n.kdl".into()), None, home.clone(), |_| true).unwrap();
+        assert_eq!(discovery.path(), Path::new("/n.kdl"));
+        let discovery = ConfigDiscovery::resolve(None, Some("".into()), None, home.clone(), |_| true).unwrap();
+        assert_eq!(discovery.path(), user);
+        let discovery =
+            ConfigDiscovery::resolve(Some("/o.kdl".into()), Some("/n.kdl".into()), None, home, |_| false).unwrap();
+        assert_eq!(discovery.path(), Path::new("/o.kdl"));
+        assert_eq!(discovery.candidates.len(), 4);
+
+        // No home and no system config leaves nothing to edit
+        assert!(ConfigDiscovery::resolve(None, None, None, None, |_| false).is_none());
+    }
+}
@@ -0,0 +1,183 @@
+//! Dry run of a spawn binding: the exact argv niri would execute, and
+//! optionally the output of running it once.
+
+use std::io::Read;
+use std::process::{Command, ExitStatus, Stdio};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Longest a test run may take before it is killed
+pub const RUN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Output kept per stream; the rest is dropped
+const MAX_OUTPUT: u64 = 64 * 1024;
+
+/// Result of running a command once
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunOutput {
+    /// How the command ended, e.g. "exited with 0"
+    pub status: String,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+/// The dry-run dialog: what would run, and the output once it has
+#[derive(Debug)]
+pub struct DryRun {
+    /// Combo of the binding, for the title
+    pub combo: String,
+    /// The argv, or why there is none (e.g. an unterminated quote)
+    pub argv: Result<Vec<String>, String>,
+    pub output: Option<RunOutput>,
+    /// Set while the command runs in the background
+    pub running: Option<Receiver<RunOutput>>,
+}
+
+impl DryRun {
+    pub fn new(combo: String, argv: Result<Vec<String>, String>) -> Self {
+        Self { combo, argv, output: None, running: None }
+    }
+
+    /// Run the command on a background thread; see `run_captured`
+    pub fn start(&mut self) {
+        let Ok(argv) = &self.argv else {
+            return;
+        };
+        if self.running.is_some() {
+            return;
+        }
+        let argv = argv.clone();
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = sender.send(run_captured(&argv, RUN_TIMEOUT));
+        });
+        self.output = None;
+        self.running = Some(receiver);
+    }
+
+    /// Pick up the output of a finished run. Returns whether it changed.
+    pub fn poll(&mut self) -> bool {
+        let Some(receiver) = &self.running else {
+            return false;
+        };
+        let output = match receiver.try_recv() {
+            Ok(output) => output,
+            Err(mpsc::TryRecvError::Empty) => return false,
+            Err(mpsc::TryRecvError::Disconnected) => RunOutput {
+                status: "runner thread exited".to_string(),
+                stdout: String::new(),
+                stderr: String::new(),
+            },
+        };
+        self.running = None;
+        self.output = Some(output);
+        true
+    }
+}
+
+/// Quote an argv the way a shell would need it, for display
+pub fn shell_quote(argv: &[String]) -> String {
+    argv.iter()
+        .map(|arg| {
+            let plain = !arg.is_empty()
+                && arg.chars().all(|c| c.is_ascii_alphanumeric() || "-_./=:,+@%".contains(c));
+            if plain {
+                arg.clone()
+            } else {
+                format!("'{}'", arg.replace('\'', r"'\''"))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Run `argv` with no stdin from the home directory, capturing its output,
+/// and kill it after `timeout`. This keeps a test run from waiting on input
+/// or running forever; it is not a security sandbox, the command runs with
+/// the user's permissions.
+pub fn run_captured(argv: &[String], timeout: Duration) -> RunOutput {
+    let failed = |status: String| RunOutput { status, stdout: String::new(), stderr: String::new() };
+    let Some((program, args)) = argv.split_first() else {
+        return failed("nothing to run".to_string());
+    };
+    let mut command = Command::new(program);
+    command.args(args).stdin(Stdio::null()).stdout(Stdio::piped()).stderr(Stdio::piped());
+    if let Some(home) = dirs::home_dir() {
+        command.current_dir(home);
+    }
+    let mut child = match command.spawn() {
+        Ok(child) => child,
+        Err(e) => return failed(format!("failed to start {program}: {e}")),
+    };
+    let stdout = read_in_background(child.stdout.take());
+    let stderr = read_in_background(child.stderr.take());
+
+    let started = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break describe(status),
+            Ok(None) if started.elapsed() >= timeout => {
+                let _ = child.kill();
+                let _ = child.wait();
+                break format!("killed after {}s", timeout.as_secs());
+            }
+            Ok(None) => thread::sleep(Duration::from_millis(20)),
+            Err(e) => break format!("failed to wait for {program}: {e}"),
+        }
+    };
+
+    // Anything the command started in the background may hold the pipes
+    // open, so don't wait for them to close
+    let collect = |receiver: Receiver<String>| receiver.recv_timeout(Duration::from_millis(200)).unwrap_or_default();
+    RunOutput { status, stdout: collect(stdout), stderr: collect(stderr) }
+}
+
+fn read_in_background(pipe: Option<impl Read + Send + 'static>) -> Receiver<String> {
+    let (sender, receiver) = mpsc::channel();
+    if let Some(pipe) = pipe {
+        thread::spawn(move || {
+            let mut bytes = Vec::new();
+            let _ = pipe.take(MAX_OUTPUT).read_to_end(&mut bytes);
+            let _ = sender.send(String::from_utf8_lossy(&bytes).into_owned());
+        });
+    }
+    receiver
+}
+
+fn describe(status: ExitStatus) -> String {
+    match status.code() {
+        Some(code) => format!("exited with {code}"),
+        None => "killed by a signal".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(argv: &[&str]) -> Vec<String> {
+        argv.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote(&args(&["wpctl", "set-volume", "@DEFAULT_AUDIO_SINK@", "5%+"])), "wpctl set-volume @DEFAULT_AUDIO_SINK@ 5%+");
+        assert_eq!(shell_quote(&args(&["sh", "-c", "echo 'hi' there"])), r"sh -c 'echo '\''hi'\'' there'");
+        assert_eq!(shell_quote(&args(&["printf", ""])), "printf ''");
+    }
+
+    #[test]
+    fn test_run_captured() {
+        let out = run_captured(&args(&["sh", "-c", "echo out; echo err >&2; exit 3"]), RUN_TIMEOUT);
+        assert_eq!(out.status, "exited with 3");
+        assert_eq!(out.stdout, "out\n");
+        assert_eq!(out.stderr, "err\n");
+
+        let out = run_captured(&args(&["sh", "-c", "sleep 5"]), Duration::from_millis(100));
+        assert!(out.status.starts_with("killed after"), "{out:?}");
+
+        let out = run_captured(&args(&["nirikiri-no-such-program"]), RUN_TIMEOUT);
+        assert!(out.status.starts_with("failed to start"), "{out:?}");
+    }
+}
@@ -0,0 +1,301 @@
+use std::collections::HashMap;
+
+use super::binding_template::TemplateField;
+use super::config::SourceLocation;
+use super::selectable_list::SelectableList;
+use super::workspace_spawn::WorkspaceSpawn;
+
+/// The top-level xwayland-satellite block (niri 25.08 and later)
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct XwaylandSettings {
+    pub off: bool,
+    /// Binary to run; None leaves it to niri, which looks up
+    /// xwayland-satellite in PATH
+    pub path: Option<String>,
+}
+
+/// The top-level clipboard block
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ClipboardSettings {
+    /// Turn off the primary selection (middle-click paste)
+    pub disable_primary: bool,
+}
+
+/// Program niri runs for Xwayland when no path is configured
+pub const DEFAULT_XWAYLAND_PATH: &str = "xwayland-satellite";
+
+/// Settings from top-level blocks that belong to no other category
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GeneralSettings {
+    pub xwayland: XwaylandSettings,
+    pub clipboard: ClipboardSettings,
+}
+
+/// One editable node in a General block
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GeneralField {
+    XwaylandOff,
+    XwaylandPath,
+    ClipboardDisablePrimary,
+}
+
+impl GeneralField {
+    /// All fields in list order
+    pub fn all() -> &'static [GeneralField] {
+        &[GeneralField::XwaylandOff, GeneralField::XwaylandPath, GeneralField::ClipboardDisablePrimary]
+    }
+
+    /// Top-level block the field is a node of
+    pub fn block(&self) -> &'static str {
+        match self {
+            GeneralField::XwaylandOff | GeneralField::XwaylandPath => "xwayland-satellite",
+            GeneralField::ClipboardDisablePrimary => "clipboard",
+        }
+    }
+
+    /// Node name inside the block
+    pub fn name(&self) -> &'static str {
+        match self {
+            GeneralField::XwaylandOff => "off",
+            GeneralField::XwaylandPath => "path",
+            GeneralField::ClipboardDisablePrimary => "disable-primary",
+        }
+    }
+
+    /// Block and node name, e.g. "xwayland-satellite.path"
+    pub fn qualified_name(&self) -> String {
+        format!("{}.{}", self.block(), self.name())
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            GeneralField::XwaylandOff => "Don't start xwayland-satellite for X11 apps (niri 25.08+)",
+            GeneralField::XwaylandPath => "xwayland-satellite binary to run; empty uses the one in PATH",
+            GeneralField::ClipboardDisablePrimary => "Turn off the primary selection, so a middle click doesn't paste",
+        }
+    }
+
+    /// Flags toggle with Space; the rest are typed in
+    pub fn is_boolean(&self) -> bool {
+        matches!(self, GeneralField::XwaylandOff | GeneralField::ClipboardDisablePrimary)
+    }
+}
+
+impl GeneralSettings {
+    /// The field's value as shown in the list and change summaries
+    pub fn display(&self, field: GeneralField) -> String {
+        let flag = |on: bool| if on { "on" } else { "off" }.to_string();
+        match field {
+            GeneralField::XwaylandOff => flag(self.xwayland.off),
+            GeneralField::XwaylandPath => self.xwayland.path.clone().unwrap_or_else(|| DEFAULT_XWAYLAND_PATH.to_string()),
+            GeneralField::ClipboardDisablePrimary => flag(self.clipboard.disable_primary),
+        }
+    }
+
+    /// Flip a flag; false for fields that aren't one
+    pub fn toggle(&mut self, field: GeneralField) -> bool {
+        match field {
+            GeneralField::XwaylandOff => self.xwayland.off = !self.xwayland.off,
+            GeneralField::ClipboardDisablePrimary => self.clipboard.disable_primary = !self.clipboard.disable_primary,
+            GeneralField::XwaylandPath => return false,
+        }
+        true
+    }
+
+    /// Store typed text; false for fields that aren't typed in
+    pub fn set_text(&mut self, field: GeneralField, text: &str) -> bool {
+        let text = text.trim();
+        match field {
+            // Clearing the path, or typing niri's default, leaves it unset
+            GeneralField::XwaylandPath => {
+                self.xwayland.path =
+                    Some(text).filter(|s| !s.is_empty() && *s != DEFAULT_XWAYLAND_PATH).map(str::to_string)
+            }
+            GeneralField::XwaylandOff | GeneralField::ClipboardDisablePrimary => return false,
+        }
+        true
+    }
+
+    /// Copy one field's value over from `other`
+    fn copy_field(&mut self, other: &GeneralSettings, field: GeneralField) {
+        match field {
+            GeneralField::XwaylandOff => self.xwayland.off = other.xwayland.off,
+            GeneralField::XwaylandPath => self.xwayland.path = other.xwayland.path.clone(),
+            GeneralField::ClipboardDisablePrimary => self.clipboard.disable_primary = other.clipboard.disable_primary,
+        }
+    }
+}
+
+/// State for the General category
+#[derive(Debug, Clone, Default)]
+pub struct GeneralViewModel {
+    /// Settings with the staged changes
+    pub settings: GeneralSettings,
+    /// Settings as loaded, to diff against when saving
+    pub original_settings: GeneralSettings,
+    pub list: SelectableList,
+    /// Text field open for the selected field
+    pub edit: Option<TemplateField>,
+    /// Where each field is set in the config, if it is
+    pub field_sources: HashMap<GeneralField, SourceLocation>,
+    /// Apps from the workspace wizard whose workspace, window rule and
+    /// startup entry are added on the next save
+    pub workspace_spawns: Vec<WorkspaceSpawn>,
+}
+
+impl GeneralViewModel {
+    pub fn new(settings: GeneralSettings) -> Self {
+        Self { original_settings: settings.clone(), settings, ..Default::default() }
+    }
+
+    pub fn selected_field(&self) -> Option<GeneralField> {
+        GeneralField::all().get(self.list.selected_index).copied()
+    }
+
+    pub fn select_next(&mut self) {
+        self.list.select_next(GeneralField::all().len());
+    }
+
+    pub fn select_prev(&mut self) {
+        self.list.select_prev(GeneralField::all().len());
+    }
+
+    pub fn is_field_modified(&self, field: GeneralField) -> bool {
+        self.settings.display(field) != self.original_settings.display(field)
+    }
+
+    /// Fields whose staged value differs from the saved one
+    pub fn changed_fields(&self) -> Vec<GeneralField> {
+        GeneralField::all().iter().copied().filter(|&f| self.is_field_modified(f)).collect()
+    }
+
+    pub fn has_pending_changes(&self) -> bool {
+        self.settings != self.original_settings || !self.workspace_spawns.is_empty()
+    }
+
+    /// One line per staged change, for hooks and commit messages
+    pub fn change_summary(&self) -> Vec<String> {
+        self.changed_fields()
+            .into_iter()
+            .map(|field| {
+                format!(
+                    "{} {}→{}",
+                    field.qualified_name(),
+                    self.original_settings.display(field),
+                    self.settings.display(field)
+                )
+            })
+            .chain(
+                self.workspace_spawns
+                    .iter()
+                    .map(|spawn| format!("open {} on workspace {}", spawn.command[0], spawn.workspace)),
+            )
+            .collect()
+    }
+
+    /// Stage the pieces of a workspace app for the next save
+    pub fn stage_workspace_spawn(&mut self, spawn: WorkspaceSpawn) {
+        self.workspace_spawns.push(spawn);
+    }
+
+    /// Mark the staged changes as saved
+    pub fn apply_changes(&mut self) {
+        self.original_settings = self.settings.clone();
+        self.workspace_spawns.clear();
+    }
+
+    /// Drop the staged changes
+    pub fn reset_changes(&mut self) {
+        self.settings = self.original_settings.clone();
+        self.workspace_spawns.clear();
+        self.edit = None;
+    }
+
+    /// Stage `staged`'s changes on top of freshly loaded settings. Every
+    /// field and workspace app can always be re-applied.
+    pub fn rebase_changes(&mut self, staged: &GeneralViewModel) {
+        for &field in GeneralField::all() {
+            if staged.is_field_modified(field) {
+                self.settings.copy_field(&staged.settings, field);
+            }
+        }
+        self.workspace_spawns = staged.workspace_spawns.clone();
+    }
+
+    /// Flip the selected flag
+    pub fn toggle_selected(&mut self) {
+        if let Some(field) = self.selected_field() {
+            self.settings.toggle(field);
+        }
+    }
+
+    /// Open a text field on the selected field, if it is typed in
+    pub fn start_edit(&mut self) {
+        if let Some(field) = self.selected_field().filter(|f| !f.is_boolean()) {
+            self.edit = Some(TemplateField::new(field.qualified_name(), self.settings.display(field)));
+        }
+    }
+
+    /// Stage the typed text and close the field
+    pub fn confirm_edit(&mut self) {
+        if let (Some(edit), Some(field)) = (self.edit.take(), self.selected_field()) {
+            self.settings.set_text(field, &edit.value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_general_view_model() {
+        let mut view_model = GeneralViewModel::new(GeneralSettings::default());
+        view_model.toggle_selected();
+        assert!(view_model.settings.xwayland.off);
+
+        view_model.select_next();
+        view_model.start_edit();
+        let edit = view_model.edit.as_mut().unwrap();
+        assert_eq!(edit.value, DEFAULT_XWAYLAND_PATH);
+        edit.value = "/opt/xwayland-satellite ".to_string();
+        view_model.confirm_edit();
+        assert_eq!(view_model.settings.xwayland.path.as_deref(), Some("/opt/xwayland-satellite"));
+        assert_eq!(
+            view_model.change_summary(),
+            vec![
+                "xwayland-satellite.off off→on".to_string(),
+                "xwayland-satellite.path xwayland-satellite→/opt/xwayland-satellite".to_string(),
+            ]
+        );
+
+        view_model.stage_workspace_spawn(WorkspaceSpawn {
+            workspace: "chat".to_string(),
+            command: vec!["telegram-desktop".to_string()],
+            app_id: "telegram".to_string(),
+            output: None,
+            at_startup: true,
+        });
+        assert_eq!(view_model.change_summary().len(), 3);
+        assert_eq!(view_model.change_summary()[2], "open telegram-desktop on workspace chat");
+
+        // Re-applied on top of a reloaded config that changed the path
+        let mut reloaded = GeneralViewModel::new(GeneralSettings {
+            xwayland: XwaylandSettings { off: false, path: Some("/usr/bin/xwls".to_string()) },
+            ..Default::default()
+        });
+        reloaded.rebase_changes(&view_model);
+        assert_eq!(reloaded.settings, view_model.settings);
+        assert_eq!(reloaded.workspace_spawns, view_model.workspace_spawns);
+        view_model.apply_changes();
+        assert!(!view_model.has_pending_changes());
+
+        // Typing the default leaves the path unset again
+        view_model.start_edit();
+        view_model.edit.as_mut().unwrap().value = DEFAULT_XWAYLAND_PATH.to_string();
+        view_model.confirm_edit();
+        assert_eq!(view_model.settings.xwayland.path, None);
+        view_model.reset_changes();
+        assert!(!view_model.has_pending_changes());
+    }
+}
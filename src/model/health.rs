@@ -0,0 +1,215 @@
+use std::path::Path;
+
+use super::config::ConfigDocument;
+
+/// Outcome of a single startup check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Ok,
+    /// nirikiri works, but part of it won't
+    Warning,
+    /// Nothing in the affected categories can be loaded or saved
+    Failed,
+}
+
+/// One line of the startup health report
+#[derive(Debug, Clone)]
+pub struct HealthCheck {
+    pub label: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+    /// What to do about a problem
+    pub hint: Option<String>,
+}
+
+impl HealthCheck {
+    fn ok(label: &'static str, detail: impl Into<String>) -> Self {
+        Self { label, status: CheckStatus::Ok, detail: detail.into(), hint: None }
+    }
+
+    fn problem(label: &'static str, status: CheckStatus, detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self { label, status, detail: detail.into(), hint: Some(hint.into()) }
+    }
+}
+
+/// Checks run on launch, shown before the main UI when any of them fail
+#[derive(Debug, Clone, Default)]
+pub struct HealthReport {
+    pub checks: Vec<HealthCheck>,
+}
+
+impl HealthReport {
+    /// Record whether niri answered over IPC
+    pub fn check_niri(&mut self, result: Result<(), &anyhow::Error>) {
+        self.checks.push(match result {
+            Ok(()) => HealthCheck::ok("niri reachable", "IPC socket answered"),
+            Err(e) => HealthCheck::problem(
+                "niri reachable",
+                CheckStatus::Failed,
+                format!("{e:#}"),
+                "Run nirikiri inside a niri session, or pass --socket / set NIRI_SOCKET",
+            ),
+        });
+    }
+
+    /// Check the config file at `path` (None if no config directory could be
+    /// found). `loaded` is the parse result, or None while a large file is
+    /// still being parsed in the background.
+    pub fn check_config(&mut self, path: Option<&Path>, loaded: Option<Result<&ConfigDocument, String>>) {
+        if let Some(path) = path.filter(|p| !p.exists()) {
+            self.checks.push(HealthCheck::problem(
+                "Config exists",
+                CheckStatus::Failed,
+                format!("{} not found", path.display()),
+                "Start niri once to have it write its default config there",
+            ));
+            self.check_writable(path);
+            return;
+        }
+        match loaded {
+            Some(Ok(config)) => {
+                self.checks.push(HealthCheck::ok("Config parses", config.path.display().to_string()));
+                self.check_blocks(config);
+            }
+            Some(Err(e)) => self.checks.push(HealthCheck::problem(
+                "Config parses",
+                CheckStatus::Failed,
+                e,
+                "Fix the reported line (niri validate shows it too), or restore config.kdl.bak",
+            )),
+            None => self.checks.push(HealthCheck::ok("Config parses", "large file, loading in background")),
+        }
+        if let Some(path) = path {
+            self.check_writable(path);
+        }
+    }
+
+    fn check_blocks(&mut self, config: &ConfigDocument) {
+        let has = |name: &str| config.doc.nodes().iter().any(|n| n.name().value() == name);
+        self.checks.push(if has("binds") {
+            HealthCheck::ok("binds block", "present")
+        } else {
+            HealthCheck::problem(
+                "binds block",
+                CheckStatus::Warning,
+                "missing",
+                "Add an empty binds { } block to the config so keybinding edits can be saved",
+            )
+        });
+        // The appearance writer creates the layout block, so its absence is fine
+        self.checks.push(HealthCheck::ok(
+            "layout block",
+            if has("layout") { "present" } else { "missing; created when Appearance is saved" },
+        ));
+    }
+
+    fn check_writable(&mut self, path: &Path) {
+        if path.exists() {
+            self.checks.push(
+                match std::fs::OpenOptions::new().append(true).open(path) {
+                    Ok(_) => HealthCheck::ok("Config writable", "yes"),
+                    Err(e) => HealthCheck::problem(
+                        "Config writable",
+                        CheckStatus::Failed,
+                        e.to_string(),
+                        format!(
+                            "Make {} writable (chmod u+w), or edit the file that generates it",
+                            path.display()
+                        ),
+                    ),
+                },
+            );
+        }
+
+        // Backups are written next to the config
+        let Some(dir) = path.parent() else {
+            return;
+        };
+        let probe = dir.join(format!(".nirikiri-write-test-{}", std::process::id()));
+        self.checks.push(match std::fs::write(&probe, b"") {
+            Ok(()) => {
+                let _ = std::fs::remove_file(&probe);
+                HealthCheck::ok("Backup dir writable", dir.display().to_string())
+            }
+            Err(e) => HealthCheck::problem(
+                "Backup dir writable",
+                CheckStatus::Failed,
+                format!("{}: {e}", dir.display()),
+                "Create the directory or make it writable; saves copy the config to config.kdl.bak first",
+            ),
+        });
+    }
+
+    /// In managed mode, check that the niri config includes the fragment
+    pub fn check_fragment(&mut self, fragment: &Path, included: bool) {
+        let name = fragment.file_name().unwrap_or_default().to_string_lossy();
+        self.checks.push(if included {
+            HealthCheck::ok("Fragment included", format!("{name} is included"))
+        } else {
+            HealthCheck::problem(
+                "Fragment included",
+                CheckStatus::Warning,
+                format!("config.kdl doesn't include {name} yet"),
+                format!("It is added on the next save, or add `include \"{name}\"` at the end of config.kdl"),
+            )
+        });
+    }
+
+    /// Whether the report needs to be shown at all
+    pub fn has_problems(&self) -> bool {
+        self.checks.iter().any(|c| c.status != CheckStatus::Ok)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(source: &str, path: &Path) -> ConfigDocument {
+        ConfigDocument {
+            doc: kdl::KdlDocument::parse_v1(source).unwrap(),
+            path: path.to_path_buf(),
+            source: source.to_string(),
+        }
+    }
+
+    fn status(report: &HealthReport, label: &str) -> CheckStatus {
+        report.checks.iter().find(|c| c.label == label).unwrap().status
+    }
+
+    #[test]
+    fn test_health_report() {
+        let dir = std::env::temp_dir().join(format!("nirikiri-health-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.kdl");
+        std::fs::write(&path, "binds { Mod+T { spawn \"foot\"; }; }\n").unwrap();
+
+        let mut report = HealthReport::default();
+        report.check_niri(Ok(()));
+        report.check_config(Some(&path), Some(Ok(&config("binds { Mod+T { spawn \"foot\"; }; }", &path))));
+        assert!(!report.has_problems(), "{report:?}");
+
+        let mut report = HealthReport::default();
+        report.check_config(Some(&path), Some(Ok(&config("layout { gaps 8; }", &path))));
+        assert_eq!(status(&report, "binds block"), CheckStatus::Warning);
+        assert!(report.has_problems());
+
+        let mut report = HealthReport::default();
+        report.check_niri(Err(&anyhow::anyhow!("NIRI_SOCKET not set")));
+        report.check_config(Some(&path), Some(Err("line 3: unexpected }".to_string())));
+        assert_eq!(status(&report, "niri reachable"), CheckStatus::Failed);
+        assert_eq!(status(&report, "Config parses"), CheckStatus::Failed);
+        assert!(report.checks.iter().all(|c| c.label != "binds block"));
+
+        let mut report = HealthReport::default();
+        report.check_fragment(&dir.join("nirikiri.kdl"), false);
+        assert_eq!(status(&report, "Fragment included"), CheckStatus::Warning);
+
+        let mut report = HealthReport::default();
+        report.check_config(Some(&dir.join("missing.kdl")), Some(Err("not found".to_string())));
+        assert_eq!(status(&report, "Config exists"), CheckStatus::Failed);
+        assert_eq!(status(&report, "Backup dir writable"), CheckStatus::Ok);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,5 +1,20 @@
+use std::collections::BTreeMap;
 use std::fmt;
 
+use crate::binding_tags::{BindingTagEditor, BindingTags};
+
+use super::actions;
+use super::bind_overlay::BindOverlay;
+use super::binding_conflicts::{BindingConflict, ConflictChoice, ConflictResolver, REBIND_MODIFIERS};
+use super::binding_generator::BindingGenerator;
+use super::binding_template::TemplatePrompt;
+use super::dry_run::DryRun;
+use super::config::SourceLocation;
+use super::keys::{canonical_key, key_label, same_key};
+use super::selectable_list::SelectableList;
+use super::text_input;
+use super::values::parse_duration_ms;
+
 /// Modifier keys for a keybinding
 #[derive(Debug, Clone, Default, PartialEq)]
 pub struct Modifiers {
@@ -10,23 +25,58 @@ pub struct Modifiers {
 }
 
 impl Modifiers {
+    /// Split a combo into modifiers and key, ignoring unknown modifier tokens
     pub fn parse(combo: &str) -> (Self, String) {
         let mut mods = Modifiers::default();
         let parts: Vec<&str> = combo.split('+').collect();
         let key = parts.last().unwrap_or(&"").to_string();
 
         for part in &parts[..parts.len().saturating_sub(1)] {
-            match part.to_lowercase().as_str() {
-                "mod" | "super" | "logo" => mods.mod_key = true,
-                "ctrl" | "control" => mods.ctrl = true,
-                "shift" => mods.shift = true,
-                "alt" => mods.alt = true,
-                _ => {}
-            }
+            mods.set(part);
         }
 
         (mods, key)
     }
+
+    /// Like `parse`, but reject empty keys, empty tokens and unknown modifiers
+    pub fn parse_strict(combo: &str) -> Result<(Self, String), String> {
+        let combo = combo.trim();
+        if combo.is_empty() {
+            return Err("Key combo cannot be empty".to_string());
+        }
+        let mut mods = Modifiers::default();
+        let parts: Vec<&str> = combo.split('+').collect();
+        let (key, modifiers) = parts.split_last().unwrap_or((&"", &[]));
+
+        for part in modifiers {
+            if part.is_empty() {
+                return Err(format!("Empty modifier in \"{combo}\""));
+            }
+            if !mods.set(part) {
+                return Err(format!("Unknown modifier \"{part}\" (expected Mod, Super, Ctrl, Shift or Alt)"));
+            }
+        }
+        if key.is_empty() {
+            return Err(format!("Missing key after \"{combo}\""));
+        }
+        if key.chars().any(char::is_whitespace) {
+            return Err(format!("Key \"{key}\" cannot contain spaces"));
+        }
+
+        Ok((mods, key.to_string()))
+    }
+
+    /// Turn on the modifier named by `token`; false if it isn't one
+    fn set(&mut self, token: &str) -> bool {
+        match token.to_lowercase().as_str() {
+            "mod" | "super" | "logo" | "win" => self.mod_key = true,
+            "ctrl" | "control" => self.ctrl = true,
+            "shift" => self.shift = true,
+            "alt" => self.alt = true,
+            _ => return false,
+        }
+        true
+    }
 }
 
 impl fmt::Display for Modifiers {
@@ -49,7 +99,7 @@ impl fmt::Display for Modifiers {
 }
 
 /// Properties that can be set on a keybinding
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct BindingProperties {
     pub repeat: Option<bool>,            // defaults to true
     pub cooldown_ms: Option<u32>,        // delay between repeats
@@ -61,10 +111,62 @@ impl BindingProperties {
     pub fn has_custom_properties(&self) -> bool {
         self.repeat.is_some() || self.cooldown_ms.is_some() || self.allow_when_locked.is_some()
     }
+
+    /// Short list of properties that differ from niri's defaults
+    pub fn flags(&self) -> String {
+        let mut flags = Vec::new();
+        if self.repeat == Some(false) {
+            flags.push("no-repeat".to_string());
+        }
+        if let Some(ms) = self.cooldown_ms {
+            flags.push(format!("{ms}ms"));
+        }
+        if self.allow_when_locked == Some(true) {
+            flags.push("locked".to_string());
+        }
+        flags.join(" ")
+    }
+}
+
+/// Simple on/off binding properties that can be flipped from the list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BindingFlag {
+    Repeat,
+    AllowWhenLocked,
+}
+
+impl BindingFlag {
+    /// Flip the flag between niri's default (property left out) and its opposite
+    pub fn toggle(&self, properties: &mut BindingProperties) {
+        match self {
+            BindingFlag::Repeat => {
+                properties.repeat = match properties.repeat {
+                    Some(false) => None,
+                    _ => Some(false),
+                };
+            }
+            BindingFlag::AllowWhenLocked => {
+                properties.allow_when_locked = match properties.allow_when_locked {
+                    Some(true) => None,
+                    _ => Some(true),
+                };
+            }
+        }
+    }
+
+    /// Describe the flag's state after a toggle, e.g. "repeat off"
+    pub fn describe(&self, properties: &BindingProperties) -> &'static str {
+        match self {
+            BindingFlag::Repeat if properties.repeat == Some(false) => "repeat off",
+            BindingFlag::Repeat => "repeat on",
+            BindingFlag::AllowWhenLocked if properties.allow_when_locked == Some(true) => "allowed when locked",
+            BindingFlag::AllowWhenLocked => "not allowed when locked",
+        }
+    }
 }
 
 /// Action to perform when a keybinding is triggered
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum BindingAction {
     /// Spawn a command with arguments: spawn "cmd" "arg1" "arg2"
     Spawn(Vec<String>),
@@ -77,7 +179,7 @@ pub enum BindingAction {
 }
 
 /// Argument for an action
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum BindingArg {
     Number(i64),
     String(String),
@@ -112,6 +214,16 @@ impl fmt::Display for BindingAction {
 }
 
 impl BindingAction {
+    /// The argv niri executes for a spawn binding; spawn-sh runs its
+    /// command through `sh -c`. None for built-in actions.
+    pub fn argv(&self) -> Option<Vec<String>> {
+        match self {
+            BindingAction::Spawn(args) => Some(args.clone()),
+            BindingAction::SpawnSh(cmd) => Some(vec!["sh".to_string(), "-c".to_string(), cmd.clone()]),
+            _ => None,
+        }
+    }
+
     /// Get a short description for display in the list
     pub fn short_description(&self) -> String {
         match self {
@@ -140,6 +252,16 @@ impl BindingAction {
         }
     }
 
+    /// Describe the action in full, as niri's config spells it
+    pub fn full_description(&self) -> String {
+        match self {
+            BindingAction::Spawn(args) => format!("spawn {}", args.join(" ")),
+            BindingAction::SpawnSh(cmd) => format!("spawn-sh {cmd}"),
+            BindingAction::Simple(action) => action.clone(),
+            BindingAction::WithArg(action, arg) => format!("{action} {arg}"),
+        }
+    }
+
     /// Get the action category for grouping
     pub fn category(&self) -> &'static str {
         match self {
@@ -169,38 +291,227 @@ pub struct Keybinding {
     pub action: BindingAction,
     #[allow(dead_code)]
     pub kdl_index: Option<usize>, // Position in the KDL binds block for editing
+    pub source: Option<SourceLocation>, // Where the binding is defined in the config
+}
+
+/// Whether `program` can be run: an executable file when it is a path
+/// (`~/` is expanded), otherwise one in a directory of `path_var` ($PATH)
+pub fn program_installed(program: &str, path_var: Option<&std::ffi::OsStr>) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    let executable = |path: &std::path::Path| {
+        std::fs::metadata(path).is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+    };
+    if program.contains('/') {
+        return match program.strip_prefix("~/") {
+            Some(rest) => dirs::home_dir().is_some_and(|home| executable(&home.join(rest))),
+            None => executable(std::path::Path::new(program)),
+        };
+    }
+    path_var.is_some_and(|paths| std::env::split_paths(paths).any(|dir| executable(&dir.join(program))))
 }
 
+/// Programs that open a new window and keep running, so a held key with
+/// repeat on starts one per key repeat
+const LONG_RUNNING_APPS: &[&str] = &[
+    "alacritty", "foot", "kitty", "wezterm", "ghostty", "konsole", "gnome-terminal", "ptyxis", "xterm",
+    "urxvt", "tilix", "terminator", "firefox", "chromium", "google-chrome-stable", "brave", "librewolf",
+    "nautilus", "thunar", "dolphin", "nemo", "pcmanfm", "code", "emacs", "thunderbird", "obs",
+];
+
 impl Keybinding {
+    /// The long-running program this binding starts once per key repeat
+    /// while held: a spawn of a known GUI app with repeat on (niri's
+    /// default) and no cooldown
+    pub fn repeat_lint(&self) -> Option<&'static str> {
+        if self.properties.repeat == Some(false) || self.properties.cooldown_ms.is_some() {
+            return None;
+        }
+        let program = match &self.action {
+            BindingAction::Spawn(args) => args.first()?.as_str(),
+            // Anything more than a plain command line may well be fine
+            BindingAction::SpawnSh(cmd) if !cmd.contains(['|', ';', '&']) => cmd.split_whitespace().next()?,
+            _ => return None,
+        };
+        let name = program.rsplit('/').next().unwrap_or(program);
+        LONG_RUNNING_APPS.iter().copied().find(|app| *app == name)
+    }
+
+    /// The program a spawn binding starts: the first argument of spawn, or
+    /// the first word of a spawn-sh command after any VAR=value and exec.
+    /// None when the shell would have to expand it first.
+    pub fn spawn_program(&self) -> Option<&str> {
+        let program = match &self.action {
+            BindingAction::Spawn(args) => args.first()?.as_str(),
+            BindingAction::SpawnSh(cmd) => cmd
+                .split_whitespace()
+                .find(|word| *word != "exec" && !word.contains('='))?,
+            _ => return None,
+        };
+        (!program.is_empty() && !program.contains(['$', '`', '"', '\'', '(', '{'])).then_some(program)
+    }
+
     /// Get the full key combo string (e.g., "Mod+Shift+T")
     pub fn combo(&self) -> String {
         let mods = self.modifiers.to_string();
+        let key = canonical_key(&self.key);
         if mods.is_empty() {
-            self.key.clone()
+            key
         } else {
-            format!("{}+{}", mods, self.key)
+            format!("{mods}+{key}")
         }
     }
 
+    /// Combo with a friendly key label (e.g. "Mod+Print Screen"), or the raw
+    /// XKB combo when `raw` is set or the key has no label
+    pub fn display_combo(&self, raw: bool) -> String {
+        match key_label(&self.key).filter(|_| !raw) {
+            Some(label) => {
+                let mods = self.modifiers.to_string();
+                if mods.is_empty() {
+                    label.to_string()
+                } else {
+                    format!("{mods}+{label}")
+                }
+            }
+            None => self.combo(),
+        }
+    }
+
+    /// Check whether two bindings are triggered by the same key combo
+    pub fn same_combo(&self, other: &Keybinding) -> bool {
+        self.modifiers == other.modifiers && same_key(&self.key, &other.key)
+    }
+
     /// Check if this keybinding matches a search query
     pub fn matches_search(&self, query: &str) -> bool {
-        let query = query.to_lowercase();
-        let combo = self.combo().to_lowercase();
-        let action_str = self.action.short_description().to_lowercase();
+        self.matches_lowercase_query(&query.to_lowercase())
+    }
 
-        combo.contains(&query) || action_str.contains(&query)
+    /// `matches_search` for a query that is already lowercase, so filtering
+    /// a whole list lowercases it once
+    fn matches_lowercase_query(&self, query: &str) -> bool {
+        if self.combo().to_lowercase().contains(query) {
+            return true;
+        }
+        // The friendly label only differs from the combo for keys that have one
+        if key_label(&self.key).is_some() && self.display_combo(false).to_lowercase().contains(query) {
+            return true;
+        }
+        self.action.short_description().to_lowercase().contains(query)
     }
 }
 
 /// Pending change to a keybinding
 #[derive(Debug, Clone)]
-#[allow(dead_code)] // Add and Modify variants are for future expansion
 pub enum KeybindingChange {
     Add(Keybinding),
     Modify { index: usize, new: Keybinding },
     Delete(usize),
 }
 
+/// Staged edit to a configured binding
+#[derive(Debug, Clone)]
+enum BindingEdit {
+    Modify(Keybinding),
+    Delete,
+}
+
+/// Staged keybinding changes, at most one per binding. Edits to configured
+/// bindings are keyed by their index in `bindings` and new bindings by
+/// combo; staging another change for the same binding replaces the earlier one.
+#[derive(Debug, Clone, Default)]
+pub struct PendingBindingChanges {
+    edits: BTreeMap<usize, BindingEdit>,
+    /// New bindings in the order they were staged
+    added: Vec<Keybinding>,
+}
+
+impl PendingBindingChanges {
+    /// Stage a change, replacing any earlier one for the same binding
+    pub fn stage(&mut self, change: KeybindingChange) {
+        match change {
+            KeybindingChange::Add(binding) => self.add(binding),
+            KeybindingChange::Modify { index, new } => self.modify(index, new),
+            KeybindingChange::Delete(index) => self.delete(index),
+        }
+    }
+
+    /// Stage a new binding; one already staged on the same combo is replaced in place
+    pub fn add(&mut self, binding: Keybinding) {
+        match self.added.iter_mut().find(|b| b.same_combo(&binding)) {
+            Some(added) => *added = binding,
+            None => self.added.push(binding),
+        }
+    }
+
+    pub fn modify(&mut self, index: usize, new: Keybinding) {
+        self.edits.insert(index, BindingEdit::Modify(new));
+    }
+
+    pub fn delete(&mut self, index: usize) {
+        self.edits.insert(index, BindingEdit::Delete);
+    }
+
+    /// Drop the staged edit to the configured binding at `index`
+    pub fn revert(&mut self, index: usize) {
+        self.edits.remove(&index);
+    }
+
+    /// Unstage the new binding on the same combo as `binding`
+    pub fn remove_added(&mut self, binding: &Keybinding) {
+        self.added.retain(|b| !b.same_combo(binding));
+    }
+
+    /// The staged replacement for the configured binding at `index`
+    pub fn modified(&self, index: usize) -> Option<&Keybinding> {
+        match self.edits.get(&index) {
+            Some(BindingEdit::Modify(new)) => Some(new),
+            _ => None,
+        }
+    }
+
+    pub fn is_deleted(&self, index: usize) -> bool {
+        matches!(self.edits.get(&index), Some(BindingEdit::Delete))
+    }
+
+    pub fn added(&self) -> &[Keybinding] {
+        &self.added
+    }
+
+    pub fn len(&self) -> usize {
+        self.edits.len() + self.added.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edits.is_empty() && self.added.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.edits.clear();
+        self.added.clear();
+    }
+
+    /// The staged changes: edits by binding index, then new bindings in the
+    /// order they were staged
+    pub fn to_changes(&self) -> Vec<KeybindingChange> {
+        let edits = self.edits.iter().map(|(&index, edit)| match edit {
+            BindingEdit::Modify(new) => KeybindingChange::Modify { index, new: new.clone() },
+            BindingEdit::Delete => KeybindingChange::Delete(index),
+        });
+        edits.chain(self.added.iter().cloned().map(KeybindingChange::Add)).collect()
+    }
+}
+
+impl FromIterator<KeybindingChange> for PendingBindingChanges {
+    fn from_iter<I: IntoIterator<Item = KeybindingChange>>(changes: I) -> Self {
+        let mut pending = Self::default();
+        for change in changes {
+            pending.stage(change);
+        }
+        pending
+    }
+}
+
 /// Which field is being edited in edit mode
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub enum EditField {
@@ -209,6 +520,7 @@ pub enum EditField {
     ActionType,
     ActionValue,
     Repeat,
+    Cooldown,
     AllowWhenLocked,
 }
 
@@ -218,7 +530,8 @@ impl EditField {
             EditField::KeyCombo => EditField::ActionType,
             EditField::ActionType => EditField::ActionValue,
             EditField::ActionValue => EditField::Repeat,
-            EditField::Repeat => EditField::AllowWhenLocked,
+            EditField::Repeat => EditField::Cooldown,
+            EditField::Cooldown => EditField::AllowWhenLocked,
             EditField::AllowWhenLocked => EditField::KeyCombo,
         }
     }
@@ -229,7 +542,8 @@ impl EditField {
             EditField::ActionType => EditField::KeyCombo,
             EditField::ActionValue => EditField::ActionType,
             EditField::Repeat => EditField::ActionValue,
-            EditField::AllowWhenLocked => EditField::Repeat,
+            EditField::Cooldown => EditField::Repeat,
+            EditField::AllowWhenLocked => EditField::Cooldown,
         }
     }
 }
@@ -281,6 +595,8 @@ pub struct EditMode {
     pub action_value: String,     // Command or action name
     pub action_value_cursor: usize, // Cursor position in action_value
     pub repeat: Option<bool>,
+    pub cooldown: String,         // e.g., "150", "150ms" or "1.5s"; empty for none
+    pub cooldown_cursor: usize,
     pub allow_when_locked: Option<bool>,
 }
 
@@ -289,8 +605,10 @@ impl EditMode {
     pub fn from_binding(index: usize, binding: &Keybinding) -> Self {
         let (action_type, action_value) = Self::action_to_parts(&binding.action);
         let key_combo = binding.combo();
-        let key_combo_cursor = key_combo.len();
-        let action_value_cursor = action_value.len();
+        let key_combo_cursor = text_input::end(&key_combo);
+        let action_value_cursor = text_input::end(&action_value);
+        let cooldown = binding.properties.cooldown_ms.map(|ms| ms.to_string()).unwrap_or_default();
+        let cooldown_cursor = text_input::end(&cooldown);
         Self {
             original_index: index,
             is_new: false,
@@ -301,6 +619,8 @@ impl EditMode {
             action_value,
             action_value_cursor,
             repeat: binding.properties.repeat,
+            cooldown,
+            cooldown_cursor,
             allow_when_locked: binding.properties.allow_when_locked,
         }
     }
@@ -317,85 +637,61 @@ impl EditMode {
             action_value: String::new(),
             action_value_cursor: 0,
             repeat: None,
+            cooldown: String::new(),
+            cooldown_cursor: 0,
             allow_when_locked: None,
         }
     }
 
+    /// The focused text field and its cursor, if the focus is on one
+    fn focused_text_mut(&mut self) -> Option<(&mut String, &mut usize)> {
+        match self.focused_field {
+            EditField::KeyCombo => Some((&mut self.key_combo, &mut self.key_combo_cursor)),
+            EditField::ActionValue => Some((&mut self.action_value, &mut self.action_value_cursor)),
+            EditField::Cooldown => Some((&mut self.cooldown, &mut self.cooldown_cursor)),
+            _ => None,
+        }
+    }
+
     /// Insert a character at the current cursor position for the focused text field
     pub fn insert_char(&mut self, c: char) {
-        match self.focused_field {
-            EditField::KeyCombo => {
-                self.key_combo.insert(self.key_combo_cursor, c);
-                self.key_combo_cursor += 1;
-            }
-            EditField::ActionValue => {
-                self.action_value.insert(self.action_value_cursor, c);
-                self.action_value_cursor += 1;
-            }
-            _ => {}
+        if let Some((text, cursor)) = self.focused_text_mut() {
+            text_input::insert_char(text, cursor, c);
         }
     }
 
     /// Delete the character before the cursor
     pub fn delete_char(&mut self) {
-        match self.focused_field {
-            EditField::KeyCombo => {
-                if self.key_combo_cursor > 0 {
-                    self.key_combo_cursor -= 1;
-                    self.key_combo.remove(self.key_combo_cursor);
-                }
-            }
-            EditField::ActionValue => {
-                if self.action_value_cursor > 0 {
-                    self.action_value_cursor -= 1;
-                    self.action_value.remove(self.action_value_cursor);
-                }
-            }
-            _ => {}
+        if let Some((text, cursor)) = self.focused_text_mut() {
+            text_input::delete_char(text, cursor);
         }
     }
 
     /// Move cursor left in the focused text field
     pub fn cursor_left(&mut self) {
-        match self.focused_field {
-            EditField::KeyCombo => {
-                self.key_combo_cursor = self.key_combo_cursor.saturating_sub(1);
-            }
-            EditField::ActionValue => {
-                self.action_value_cursor = self.action_value_cursor.saturating_sub(1);
-            }
-            _ => {}
+        if let Some((_, cursor)) = self.focused_text_mut() {
+            *cursor = cursor.saturating_sub(1);
         }
     }
 
     /// Move cursor right in the focused text field
     pub fn cursor_right(&mut self) {
-        match self.focused_field {
-            EditField::KeyCombo => {
-                self.key_combo_cursor = (self.key_combo_cursor + 1).min(self.key_combo.len());
-            }
-            EditField::ActionValue => {
-                self.action_value_cursor = (self.action_value_cursor + 1).min(self.action_value.len());
-            }
-            _ => {}
+        if let Some((text, cursor)) = self.focused_text_mut() {
+            text_input::cursor_right(text, cursor);
         }
     }
 
     /// Move cursor to start of the focused text field
     pub fn cursor_home(&mut self) {
-        match self.focused_field {
-            EditField::KeyCombo => self.key_combo_cursor = 0,
-            EditField::ActionValue => self.action_value_cursor = 0,
-            _ => {}
+        if let Some((_, cursor)) = self.focused_text_mut() {
+            *cursor = 0;
         }
     }
 
     /// Move cursor to end of the focused text field
     pub fn cursor_end(&mut self) {
-        match self.focused_field {
-            EditField::KeyCombo => self.key_combo_cursor = self.key_combo.len(),
-            EditField::ActionValue => self.action_value_cursor = self.action_value.len(),
-            _ => {}
+        if let Some((text, cursor)) = self.focused_text_mut() {
+            *cursor = text_input::end(text);
         }
     }
 
@@ -411,6 +707,10 @@ impl EditMode {
             BindingAction::Simple(name) => {
                 (ActionType::BuiltIn, name.clone())
             }
+            // Quote strings that would otherwise read back as a number
+            BindingAction::WithArg(name, BindingArg::String(arg)) if arg.parse::<i64>().is_ok() => {
+                (ActionType::BuiltIn, format!("{name} \"{arg}\""))
+            }
             BindingAction::WithArg(name, arg) => {
                 (ActionType::BuiltIn, format!("{name} {arg}"))
             }
@@ -424,21 +724,71 @@ impl EditMode {
         }
 
         let action = self.build_action()?;
-        let (modifiers, key) = Modifiers::parse(&self.key_combo);
+        let (modifiers, key) = Modifiers::parse_strict(&self.key_combo).ok()?;
+        let cooldown_ms = self.parsed_cooldown().ok()?;
 
         Some(Keybinding {
             modifiers,
-            key,
+            key: canonical_key(&key),
             properties: BindingProperties {
                 repeat: self.repeat,
-                cooldown_ms: None,
+                cooldown_ms,
                 allow_when_locked: self.allow_when_locked,
             },
             action,
             kdl_index: None,
+            source: None,
         })
     }
 
+    /// Check the key combo, reporting unknown modifiers and missing keys
+    pub fn validate_combo(&self) -> Result<(), String> {
+        Modifiers::parse_strict(&self.key_combo).map(|_| ())
+    }
+
+    /// Cooldown in milliseconds, or None when the field is empty
+    pub fn parsed_cooldown(&self) -> Result<Option<u32>, String> {
+        if self.cooldown.trim().is_empty() {
+            return Ok(None);
+        }
+        parse_duration_ms(&self.cooldown).map(Some).map_err(|e| format!("cooldown: {e}"))
+    }
+
+    /// Check every field, reporting the first problem
+    pub fn validate(&self) -> Result<(), String> {
+        self.validate_combo()?;
+        self.validate_action()?;
+        self.parsed_cooldown().map(|_| ())
+    }
+
+    /// Check the action value against the selected action type
+    pub fn validate_action(&self) -> Result<(), String> {
+        let value = self.action_value.trim();
+        match self.action_type {
+            ActionType::Spawn if value.is_empty() => Err("Command cannot be empty".to_string()),
+            ActionType::SpawnSh if value.is_empty() => {
+                Err("Shell command cannot be empty".to_string())
+            }
+            ActionType::Spawn if has_unterminated_quote(value) => {
+                Err("Unterminated quote in command".to_string())
+            }
+            ActionType::Spawn | ActionType::SpawnSh => Ok(()),
+            ActionType::BuiltIn if value.is_empty() => Err("Action cannot be empty".to_string()),
+            ActionType::BuiltIn => actions::validate_builtin(value),
+        }
+    }
+
+    /// The argv the action as typed would run, after quote parsing
+    pub fn dry_run_argv(&self) -> Result<Vec<String>, String> {
+        if self.action_type == ActionType::BuiltIn {
+            return Err("Only spawn and spawn-sh bindings run a command".to_string());
+        }
+        self.validate_action()?;
+        self.build_action()
+            .and_then(|action| action.argv())
+            .ok_or_else(|| "Command cannot be empty".to_string())
+    }
+
     /// Build action from current edit state
     fn build_action(&self) -> Option<BindingAction> {
         let value = self.action_value.trim();
@@ -468,7 +818,12 @@ impl EditMode {
                     Some(BindingAction::Simple(action_name.to_string()))
                 } else {
                     let arg_str = parts[1].trim();
-                    let arg = if let Ok(n) = arg_str.parse::<i64>() {
+                    // Known actions get the argument type niri expects
+                    let typed = actions::builtin_arg_kind(action_name)
+                        .and_then(|kind| actions::builtin_arg(action_name, kind, arg_str).ok());
+                    let arg = if let Some(arg) = typed {
+                        arg
+                    } else if let Ok(n) = arg_str.parse::<i64>() {
                         BindingArg::Number(n)
                     } else if arg_str == "true" {
                         BindingArg::Bool(true)
@@ -512,8 +867,21 @@ impl EditMode {
     }
 }
 
+/// Check whether a command line opens a quote it never closes
+pub(crate) fn has_unterminated_quote(s: &str) -> bool {
+    let mut open: Option<char> = None;
+    for c in s.chars() {
+        match (open, c) {
+            (None, '"' | '\'') => open = Some(c),
+            (Some(q), c) if c == q => open = None,
+            _ => {}
+        }
+    }
+    open.is_some()
+}
+
 /// Parse command arguments, handling quoted strings
-fn parse_command_args(s: &str) -> Vec<String> {
+pub(crate) fn parse_command_args(s: &str) -> Vec<String> {
     let mut args = Vec::new();
     let mut current = String::new();
     let mut in_quotes = false;
@@ -547,72 +915,115 @@ fn parse_command_args(s: &str) -> Vec<String> {
     args
 }
 
+/// Actions without which a session can't be left or a stuck window closed,
+/// with the combos a fallback bind may use (niri's defaults first)
+const ESSENTIAL_ACTIONS: [(&str, &[&str]); 2] = [
+    ("quit", &["Mod+Shift+E", "Ctrl+Alt+Delete", "Mod+Shift+Ctrl+E"]),
+    ("close-window", &["Mod+Q", "Mod+Shift+Q", "Mod+Shift+Ctrl+Q"]),
+];
+
 /// Status of a binding in the effective list
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum BindingStatus {
     Unchanged,
     Modified,
     Added,
+    Default, // From niri's defaults, not in the user config
 }
 
-/// A binding with its effective state for display
-#[derive(Debug, Clone)]
-pub struct EffectiveBinding {
-    pub binding: Keybinding,
+/// A binding with its effective state for display, borrowed from the view
+/// model so listing bindings every frame doesn't copy them
+#[derive(Debug, Clone, Copy)]
+pub struct EffectiveBinding<'a> {
+    pub binding: &'a Keybinding,
     pub original_index: Option<usize>, // None for added bindings
     pub status: BindingStatus,
 }
 
+/// Optional columns and sizing of the keybindings table
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeybindingColumns {
+    pub category: bool,
+    pub flags: bool,
+    pub combo_width: u16,
+}
+
+impl KeybindingColumns {
+    pub const MIN_COMBO_WIDTH: u16 = 8;
+    pub const MAX_COMBO_WIDTH: u16 = 40;
+
+    /// Grow or shrink the combo column, within limits
+    pub fn adjust_combo_width(&mut self, delta: i16) {
+        self.combo_width = self
+            .combo_width
+            .saturating_add_signed(delta)
+            .clamp(Self::MIN_COMBO_WIDTH, Self::MAX_COMBO_WIDTH);
+    }
+}
+
+impl Default for KeybindingColumns {
+    fn default() -> Self {
+        Self {
+            category: false,
+            flags: true,
+            combo_width: 18,
+        }
+    }
+}
+
 /// View model for the keybindings category
 #[derive(Debug, Default)]
 pub struct KeybindingsViewModel {
     pub bindings: Vec<Keybinding>,
-    pub selected_index: usize,
-    pub scroll_offset: usize,
+    pub list: SelectableList,
     pub search_query: String,
-    pub pending_changes: Vec<KeybindingChange>,
+    pub pending_changes: PendingBindingChanges,
     pub search_mode: bool,
     pub edit_mode: Option<EditMode>,
+    pub generator: Option<BindingGenerator>, // Column layout binding generator dialog
+    pub template_prompt: Option<TemplatePrompt>, // Filling in a binding template from the settings
+    pub dry_run: Option<DryRun>, // What a spawn binding would execute, and its output
+    pub defaults: Vec<Keybinding>, // niri's default binds, loaded on first use
+    pub defaults_release: Option<String>, // niri release the defaults snapshot is from
+    pub show_defaults: bool,
+    pub raw_key_names: bool, // Show XKB names instead of friendly key labels
+    pub columns: KeybindingColumns,
+    /// Only list spawn binds whose program isn't installed
+    pub missing_only: bool,
+    /// Spawned programs that weren't found when the filter was turned on
+    pub missing_programs: std::collections::HashSet<String>,
+    /// Essential actions the staged changes would unbind, while the save
+    /// waits for the user to add fallbacks or go ahead anyway
+    pub lockout_warning: Option<Vec<&'static str>>,
+    /// User-defined tags by combo, searched with `#tag`
+    pub tags: BindingTags,
+    pub tag_editor: Option<BindingTagEditor>,
+    /// Clashes between staged template or generated binds and configured ones
+    pub conflict_resolver: Option<ConflictResolver>,
+    /// Every bind grouped by category, for rebinding one after another
+    pub bind_overlay: Option<BindOverlay>,
 }
 
 impl KeybindingsViewModel {
     /// Get effective bindings with pending changes applied
-    pub fn effective_bindings(&self) -> Vec<EffectiveBinding> {
-        let mut result = Vec::new();
-
-        // Build a set of deleted indices
-        let deleted: std::collections::HashSet<usize> = self.pending_changes
-            .iter()
-            .filter_map(|c| match c {
-                KeybindingChange::Delete(idx) => Some(*idx),
-                _ => None,
-            })
-            .collect();
-
-        // Build a map of modified bindings
-        let modified: std::collections::HashMap<usize, &Keybinding> = self.pending_changes
-            .iter()
-            .filter_map(|c| match c {
-                KeybindingChange::Modify { index, new } => Some((*index, new)),
-                _ => None,
-            })
-            .collect();
+    pub fn effective_bindings(&self) -> Vec<EffectiveBinding<'_>> {
+        let mut result = Vec::with_capacity(self.bindings.len() + self.pending_changes.len());
 
         // Process original bindings
         for (idx, binding) in self.bindings.iter().enumerate() {
-            if deleted.contains(&idx) {
+            if self.pending_changes.is_deleted(idx) {
                 continue; // Skip deleted
             }
 
-            if let Some(new_binding) = modified.get(&idx) {
+            if let Some(new_binding) = self.pending_changes.modified(idx) {
                 result.push(EffectiveBinding {
-                    binding: (*new_binding).clone(),
+                    binding: new_binding,
                     original_index: Some(idx),
                     status: BindingStatus::Modified,
                 });
             } else {
                 result.push(EffectiveBinding {
-                    binding: binding.clone(),
+                    binding,
                     original_index: Some(idx),
                     status: BindingStatus::Unchanged,
                 });
@@ -620,43 +1031,265 @@ impl KeybindingsViewModel {
         }
 
         // Add new bindings
-        for change in &self.pending_changes {
-            if let KeybindingChange::Add(binding) = change {
-                result.push(EffectiveBinding {
-                    binding: binding.clone(),
-                    original_index: None,
-                    status: BindingStatus::Added,
-                });
+        for binding in self.pending_changes.added() {
+            result.push(EffectiveBinding {
+                binding,
+                original_index: None,
+                status: BindingStatus::Added,
+            });
+        }
+
+        // niri defaults whose combo the user hasn't bound
+        if self.show_defaults {
+            let configured = result.len();
+            for d in &self.defaults {
+                if !result[..configured].iter().any(|eb| eb.binding.same_combo(d)) {
+                    result.push(EffectiveBinding {
+                        binding: d,
+                        original_index: None,
+                        status: BindingStatus::Default,
+                    });
+                }
             }
         }
 
         result
     }
 
-    /// Get filtered effective bindings based on search query
-    pub fn filtered_bindings(&self) -> Vec<EffectiveBinding> {
+    /// Number of `bindings` per action category, largest first
+    pub fn category_counts(bindings: &[EffectiveBinding]) -> Vec<(&'static str, usize)> {
+        let mut counts: Vec<(&'static str, usize)> = Vec::new();
+        for eb in bindings {
+            let category = eb.binding.action.category();
+            match counts.iter_mut().find(|(c, _)| *c == category) {
+                Some((_, n)) => *n += 1,
+                None => counts.push((category, 1)),
+            }
+        }
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        counts
+    }
+
+    /// Find a configured binding on the same combo as `binding`, other than
+    /// the one at `editing`. niri defaults can be overridden, so they don't count.
+    pub fn conflicting_binding(&self, binding: &Keybinding, editing: Option<usize>) -> Option<Keybinding> {
+        self.effective_bindings()
+            .into_iter()
+            .filter(|eb| eb.status != BindingStatus::Default)
+            .filter(|eb| editing.is_none() || eb.original_index != editing)
+            .find(|eb| eb.binding.same_combo(binding))
+            .map(|eb| eb.binding.clone())
+    }
+
+    /// One line per staged change, for commit messages
+    pub fn change_summary(&self) -> Vec<String> {
+        self.pending_changes
+            .to_changes()
+            .into_iter()
+            .map(|change| match change {
+                KeybindingChange::Add(b) => format!("add {} {}", b.combo(), b.action.full_description()),
+                KeybindingChange::Modify { new, .. } => {
+                    format!("change {} {}", new.combo(), new.action.full_description())
+                }
+                KeybindingChange::Delete(idx) => match self.bindings.get(idx) {
+                    Some(b) => format!("remove {}", b.combo()),
+                    None => "remove binding".to_string(),
+                },
+            })
+            .collect()
+    }
+
+    /// Re-stage changes made against `old` bindings onto the freshly loaded
+    /// ones, following each edited binding by its key combo. Changes whose
+    /// binding was removed or edited on disk are dropped and described.
+    pub fn rebase_changes(&mut self, old: &[Keybinding], changes: PendingBindingChanges) -> Vec<String> {
+        let mut skipped = Vec::new();
+        for change in changes.to_changes() {
+            match change {
+                KeybindingChange::Add(binding) => {
+                    if self.bindings.iter().any(|b| b.same_combo(&binding)) {
+                        skipped.push(format!("add {}: now bound on disk", binding.combo()));
+                    } else {
+                        self.pending_changes.add(binding);
+                    }
+                }
+                KeybindingChange::Modify { index, new } => {
+                    let Some(original) = old.get(index) else {
+                        skipped.push(format!("change {}: original binding unknown", new.combo()));
+                        continue;
+                    };
+                    match self.bindings.iter().position(|b| b.same_combo(original)) {
+                        Some(i) if self.bindings[i].action == original.action => {
+                            self.pending_changes.modify(i, new);
+                        }
+                        Some(_) => skipped.push(format!("change {}: also edited on disk", original.combo())),
+                        None => skipped.push(format!("change {}: removed on disk", original.combo())),
+                    }
+                }
+                KeybindingChange::Delete(index) => {
+                    let Some(original) = old.get(index) else {
+                        continue;
+                    };
+                    match self.bindings.iter().position(|b| b.same_combo(original)) {
+                        Some(i) => self.pending_changes.delete(i),
+                        None => skipped.push(format!("remove {}: already removed on disk", original.combo())),
+                    }
+                }
+            }
+        }
+        skipped
+    }
+
+    /// Get filtered effective bindings based on search query and the
+    /// missing binaries filter. Words of the query starting with # only
+    /// match bindings with that tag; the rest is matched as text.
+    pub fn filtered_bindings(&self) -> Vec<EffectiveBinding<'_>> {
         let effective = self.effective_bindings();
-        if self.search_query.is_empty() {
+        if self.search_query.is_empty() && !self.missing_only {
             effective
         } else {
+            let query = self.search_query.to_lowercase();
+            let (tags, words): (Vec<&str>, Vec<&str>) = query.split_whitespace().partition(|w| w.starts_with('#'));
+            // Without tags the query is matched as typed, spaces included
+            let text = if tags.is_empty() { query.clone() } else { words.join(" ") };
+            let tags: Vec<&str> = tags.iter().map(|t| &t[1..]).filter(|t| !t.is_empty()).collect();
             effective
                 .into_iter()
-                .filter(|eb| eb.binding.matches_search(&self.search_query))
+                .filter(|eb| eb.binding.matches_lowercase_query(&text))
+                .filter(|eb| self.tags.has_all(&eb.binding.combo(), &tags))
+                .filter(|eb| !self.missing_only || self.is_missing(eb.binding))
                 .collect()
         }
     }
 
+    /// Whether the binding spawns a program found missing by the last scan
+    pub fn is_missing(&self, binding: &Keybinding) -> bool {
+        binding.spawn_program().is_some_and(|p| self.missing_programs.contains(p))
+    }
+
+    /// Turn the missing binaries filter on or off. Turning it on checks
+    /// every spawn bind against `path_var` ($PATH) again, so programs
+    /// installed or removed meanwhile are picked up. Returns the number of
+    /// binds listed.
+    pub fn toggle_missing_filter(&mut self, path_var: Option<&std::ffi::OsStr>) -> usize {
+        self.missing_only = !self.missing_only;
+        self.list.reset();
+        if self.missing_only {
+            self.missing_programs = self
+                .effective_bindings()
+                .iter()
+                .filter_map(|eb| eb.binding.spawn_program())
+                .filter(|program| !program_installed(program, path_var))
+                .map(str::to_string)
+                .collect();
+        }
+        self.visible_count()
+    }
+
     /// Get the currently selected binding
-    #[allow(dead_code)]
     pub fn selected_binding(&self) -> Option<Keybinding> {
         let filtered = self.filtered_bindings();
-        filtered.get(self.selected_index).map(|eb| eb.binding.clone())
+        filtered.get(self.list.selected_index).map(|eb| eb.binding.clone())
     }
 
     /// Get the currently selected effective binding (with status)
-    pub fn selected_effective_binding(&self) -> Option<EffectiveBinding> {
-        let filtered = self.filtered_bindings();
-        filtered.get(self.selected_index).cloned()
+    pub fn selected_effective_binding(&self) -> Option<EffectiveBinding<'_>> {
+        self.filtered_bindings().get(self.list.selected_index).copied()
+    }
+
+    /// Flip a simple property of the selected binding without opening the
+    /// editor. The change is staged like an edit; flipping it back unstages it.
+    pub fn toggle_selected_flag(&mut self, flag: BindingFlag) -> Result<Keybinding, String> {
+        self.modify_selected_properties(|properties| flag.toggle(properties))
+    }
+
+    /// Stage repeat=false for the selected binding if it has a repeat lint
+    pub fn fix_selected_repeat(&mut self) -> Result<Keybinding, String> {
+        let eb = self.selected_effective_binding().ok_or("No binding selected")?;
+        if eb.binding.repeat_lint().is_none() {
+            return Err(format!("{} is fine to hold", eb.binding.combo()));
+        }
+        self.modify_selected_properties(|properties| properties.repeat = Some(false))
+    }
+
+    /// Bindings in view that start a long-running app per key repeat
+    pub fn repeat_lint_count(&self) -> usize {
+        Self::count_repeat_lints(&self.filtered_bindings())
+    }
+
+    /// Configured `bindings` that start a long-running app per key repeat
+    pub fn count_repeat_lints(bindings: &[EffectiveBinding]) -> usize {
+        bindings
+            .iter()
+            .filter(|eb| eb.status != BindingStatus::Default && eb.binding.repeat_lint().is_some())
+            .count()
+    }
+
+    /// Stage a change to the selected binding's properties
+    fn modify_selected_properties(&mut self, change: impl FnOnce(&mut BindingProperties)) -> Result<Keybinding, String> {
+        let eb = self.selected_effective_binding().ok_or("No binding selected")?;
+        let (status, original_index) = (eb.status, eb.original_index);
+        let mut new = eb.binding.clone();
+        change(&mut new.properties);
+
+        match (status, original_index) {
+            (BindingStatus::Default, _) => {
+                return Err("niri defaults are not in the config; press o to override first".to_string());
+            }
+            (_, Some(index)) => {
+                let original = &self.bindings[index];
+                let unchanged = original.same_combo(&new)
+                    && original.action == new.action
+                    && original.properties == new.properties;
+                if unchanged {
+                    self.pending_changes.revert(index);
+                } else {
+                    self.pending_changes.modify(index, new.clone());
+                }
+            }
+            (_, None) => self.pending_changes.add(new.clone()),
+        }
+        Ok(new)
+    }
+
+    /// Stage moving a configured binding to another combo, keeping its
+    /// action and properties. `original_index` is None for a binding added
+    /// this session. Moving a binding back to its saved combo unstages it.
+    pub fn rebind(&mut self, original_index: Option<usize>, old: &Keybinding, combo: &str) -> Result<Keybinding, String> {
+        let (modifiers, key) = Modifiers::parse_strict(combo)?;
+        let mut new = old.clone();
+        new.modifiers = modifiers;
+        new.key = canonical_key(&key);
+        if new.same_combo(old) {
+            return Err(format!("{} is already on {}", old.action.short_description(), old.combo()));
+        }
+        if let Some(existing) = self.conflicting_binding(&new, original_index) {
+            return Err(format!(
+                "{} is already bound to {}",
+                existing.combo(),
+                existing.action.short_description()
+            ));
+        }
+
+        match original_index {
+            Some(index) => {
+                let original = &self.bindings[index];
+                let unchanged = original.same_combo(&new)
+                    && original.action == new.action
+                    && original.properties == new.properties;
+                if unchanged {
+                    self.pending_changes.revert(index);
+                } else {
+                    new.source = original.source.clone();
+                    self.pending_changes.modify(index, new.clone());
+                }
+            }
+            None => {
+                self.pending_changes.remove_added(old);
+                self.pending_changes.add(new.clone());
+            }
+        }
+        Ok(new)
     }
 
     /// Get the count of visible bindings
@@ -667,35 +1300,36 @@ impl KeybindingsViewModel {
     /// Select next binding
     pub fn select_next(&mut self) {
         let count = self.visible_count();
-        if count > 0 {
-            self.selected_index = (self.selected_index + 1) % count;
-        }
+        self.list.select_next(count);
     }
 
     /// Select previous binding
     pub fn select_prev(&mut self) {
         let count = self.visible_count();
-        if count > 0 {
-            if self.selected_index == 0 {
-                self.selected_index = count - 1;
-            } else {
-                self.selected_index -= 1;
-            }
-        }
+        self.list.select_prev(count);
+    }
+
+    /// Move the selection down a page
+    pub fn page_down(&mut self) {
+        let count = self.visible_count();
+        self.list.page_down(count);
+    }
+
+    /// Move the selection up a page
+    pub fn page_up(&mut self) {
+        self.list.page_up();
     }
 
     /// Set search query and reset selection
     pub fn set_search(&mut self, query: String) {
         self.search_query = query;
-        self.selected_index = 0;
-        self.scroll_offset = 0;
+        self.list.reset();
     }
 
     /// Clear search
     pub fn clear_search(&mut self) {
         self.search_query.clear();
-        self.selected_index = 0;
-        self.scroll_offset = 0;
+        self.list.reset();
         self.search_mode = false;
     }
 
@@ -704,19 +1338,193 @@ impl KeybindingsViewModel {
         !self.pending_changes.is_empty()
     }
 
-    /// Update scroll offset for visible area
-    pub fn update_scroll(&mut self, visible_height: usize) {
-        if visible_height == 0 {
-            return;
+    /// Stage the generator's bindings, skipping combos that already exist.
+    /// Returns how many bindings were staged, and the skipped ones.
+    pub fn stage_generated(&mut self, generator: &BindingGenerator) -> (usize, Vec<Keybinding>) {
+        let existing: Vec<Keybinding> = self
+            .effective_bindings()
+            .into_iter()
+            .map(|eb| eb.binding.clone())
+            .collect();
+        let mut staged = 0;
+        let mut skipped = Vec::new();
+        for generated in generator.generate(&existing) {
+            if generated.conflicts {
+                skipped.push(generated.binding);
+            } else {
+                self.pending_changes.add(generated.binding);
+                staged += 1;
+            }
+        }
+        (staged, skipped)
+    }
+
+    /// Essential actions (quit, close-window) the saved config binds but
+    /// the staged changes would leave without any bind
+    pub fn lost_essential_actions(&self) -> Vec<&'static str> {
+        let effective = self.effective_bindings();
+        let is_action = |b: &Keybinding, action: &str| b.action == BindingAction::Simple(action.to_string());
+        ESSENTIAL_ACTIONS
+            .iter()
+            .map(|(action, _)| *action)
+            .filter(|action| self.bindings.iter().any(|b| is_action(b, action)))
+            .filter(|action| {
+                !effective
+                    .iter()
+                    .any(|eb| eb.status != BindingStatus::Default && is_action(eb.binding, action))
+            })
+            .collect()
+    }
+
+    /// Stage a bind for each of `actions` on the first fallback combo that
+    /// is still free. Returns the staged combos; an action whose fallbacks
+    /// are all taken gets none.
+    pub fn stage_fallback_binds(&mut self, actions: &[&str]) -> Vec<String> {
+        let mut bindings = Vec::new();
+        for action in actions {
+            let Some((_, combos)) = ESSENTIAL_ACTIONS.iter().find(|(a, _)| a == action) else {
+                continue;
+            };
+            let free = combos.iter().map(|combo| Modifiers::parse(combo)).find_map(|(modifiers, key)| {
+                let binding = Keybinding {
+                    modifiers,
+                    key,
+                    properties: BindingProperties::default(),
+                    action: BindingAction::Simple(action.to_string()),
+                    kdl_index: None,
+                    source: None,
+                };
+                self.conflicting_binding(&binding, None).is_none().then_some(binding)
+            });
+            bindings.extend(free);
+        }
+        let combos = bindings.iter().map(Keybinding::combo).collect();
+        self.stage_new_bindings(bindings);
+        combos
+    }
+
+    /// Stage new bindings, skipping combos that are already configured (or
+    /// come earlier in `bindings`). Returns the skipped bindings.
+    pub fn stage_new_bindings(&mut self, bindings: Vec<Keybinding>) -> Vec<Keybinding> {
+        let mut existing: Vec<Keybinding> = self
+            .effective_bindings()
+            .into_iter()
+            .filter(|eb| eb.status != BindingStatus::Default)
+            .map(|eb| eb.binding.clone())
+            .collect();
+        let mut skipped = Vec::new();
+        for binding in bindings {
+            if existing.iter().any(|b| b.same_combo(&binding)) {
+                skipped.push(binding);
+            } else {
+                existing.push(binding.clone());
+                self.pending_changes.add(binding);
+            }
         }
+        skipped
+    }
 
-        // Ensure selected item is visible
-        if self.selected_index < self.scroll_offset {
-            self.scroll_offset = self.selected_index;
-        } else if self.selected_index >= self.scroll_offset + visible_height {
-            self.scroll_offset = self.selected_index - visible_height + 1;
+    /// Open the conflict dialog for the `incoming` bindings whose combo is
+    /// configured already. Returns how many clashes it walks through.
+    pub fn start_conflict_resolution(&mut self, source: &str, incoming: Vec<Keybinding>) -> usize {
+        let conflicts: Vec<BindingConflict> = incoming
+            .into_iter()
+            .filter_map(|incoming| {
+                let existing = self.conflicting_binding(&incoming, None)?;
+                Some(BindingConflict { incoming, existing })
+            })
+            .collect();
+        let count = conflicts.len();
+        if count > 0 {
+            let mut resolver = ConflictResolver::new(source, conflicts);
+            resolver.suggestion = self.suggest_free_combo(&resolver.conflicts[0].incoming);
+            self.conflict_resolver = Some(resolver);
+        }
+        count
+    }
+
+    /// `binding` moved to the first combo with extra modifiers that nothing
+    /// is bound to, keeping its key
+    pub fn suggest_free_combo(&self, binding: &Keybinding) -> Option<Keybinding> {
+        REBIND_MODIFIERS.iter().find_map(|extra| {
+            let mut modifiers = binding.modifiers.clone();
+            for token in extra.split('+') {
+                modifiers.set(token);
+            }
+            let candidate = Keybinding { modifiers, kdl_index: None, source: None, ..binding.clone() };
+            let free = !candidate.same_combo(binding) && self.conflicting_binding(&candidate, None).is_none();
+            free.then_some(candidate)
+        })
+    }
+
+    /// Stage `choice` for the clash the dialog shows and move to the next.
+    /// Returns the dialog's summary once the last clash is decided.
+    pub fn resolve_conflict(&mut self, choice: ConflictChoice) -> Result<Option<String>, String> {
+        let Some(mut resolver) = self.conflict_resolver.take() else {
+            return Ok(None);
+        };
+        let Some(conflict) = resolver.current().cloned() else {
+            return Ok(Some(resolver.summary()));
+        };
+        match choice {
+            ConflictChoice::Keep => {}
+            ConflictChoice::Replace => {
+                let target = self
+                    .effective_bindings()
+                    .into_iter()
+                    .filter(|eb| eb.status != BindingStatus::Default)
+                    .find(|eb| eb.binding.same_combo(&conflict.incoming))
+                    .map(|eb| (eb.original_index, eb.binding.kdl_index, eb.binding.source.clone()));
+                match target {
+                    Some((Some(index), kdl_index, source)) => {
+                        self.pending_changes.modify(index, Keybinding { kdl_index, source, ..conflict.incoming });
+                    }
+                    // A bind staged earlier; adding on its combo replaces it
+                    _ => self.pending_changes.add(conflict.incoming),
+                }
+            }
+            ConflictChoice::Rebind => match resolver.suggestion.take() {
+                Some(binding) => self.pending_changes.add(binding),
+                None => {
+                    let combo = conflict.incoming.combo();
+                    self.conflict_resolver = Some(resolver);
+                    return Err(format!("No free combo near {combo}; keep or replace it instead"));
+                }
+            },
+        }
+        if resolver.advance(choice) {
+            resolver.suggestion = resolver.current().and_then(|c| self.suggest_free_combo(&c.incoming));
+            self.conflict_resolver = Some(resolver);
+            Ok(None)
+        } else {
+            Ok(Some(resolver.summary()))
         }
     }
+
+    /// Close the conflict dialog, keeping the configured binds for the
+    /// clashes not decided yet. Returns the dialog's summary.
+    pub fn keep_remaining_conflicts(&mut self) -> Option<String> {
+        let mut resolver = self.conflict_resolver.take()?;
+        resolver.keep_rest();
+        Some(resolver.summary())
+    }
+
+    /// Show or hide niri's default binds; returns true if they still need loading
+    pub fn toggle_defaults(&mut self) -> bool {
+        self.show_defaults = !self.show_defaults;
+        self.list.reset();
+        self.show_defaults && self.defaults_release.is_none()
+    }
+
+    /// Switch between friendly key labels and raw XKB names
+    pub fn toggle_raw_key_names(&mut self) {
+        self.raw_key_names = !self.raw_key_names;
+    }
+
+    /// Update scroll offset for visible area
+    pub fn update_scroll(&mut self, visible_height: usize) {
+        self.list.update_scroll(visible_height);
+    }
 }
 
 #[cfg(test)]
@@ -731,4 +1539,637 @@ mod tests {
         assert_eq!(parse_command_args("sh -c 'echo hello'"),
             vec!["sh", "-c", "echo hello"]);
     }
+
+    #[test]
+    fn test_validate_action() {
+        let mut edit = EditMode::new_binding();
+        assert!(edit.validate_action().is_err());
+        edit.action_value = "sh -c 'echo hello".to_string();
+        assert_eq!(
+            edit.validate_action(),
+            Err("Unterminated quote in command".to_string())
+        );
+        edit.action_value = "sh -c 'echo hello'".to_string();
+        assert!(edit.validate_action().is_ok());
+
+        edit.action_type = ActionType::BuiltIn;
+        edit.action_value = "close-window extra junk".to_string();
+        assert!(edit.validate_action().is_err());
+        edit.action_value = "focus-workspace 2".to_string();
+        assert!(edit.validate_action().is_ok());
+        assert!(edit.dry_run_argv().is_err());
+    }
+
+    #[test]
+    fn test_dry_run_argv() {
+        let mut edit = EditMode::new_binding();
+        edit.action_value = "notify-send 'Hello there' \"it's me\"".to_string();
+        assert_eq!(edit.dry_run_argv().unwrap(), vec!["notify-send", "Hello there", "it's me"]);
+        edit.action_value = "notify-send 'Hello there".to_string();
+        assert_eq!(edit.dry_run_argv(), Err("Unterminated quote in command".to_string()));
+
+        edit.action_type = ActionType::SpawnSh;
+        edit.action_value = "grim - | wl-copy".to_string();
+        assert_eq!(edit.dry_run_argv().unwrap(), vec!["sh", "-c", "grim - | wl-copy"]);
+    }
+
+    #[test]
+    fn test_typed_action_args_and_cooldown() {
+        let mut edit = EditMode::new_binding();
+        edit.key_combo = "Mod+Minus".to_string();
+        edit.action_type = ActionType::BuiltIn;
+        edit.action_value = "set-column-width -100".to_string();
+        edit.cooldown = "1.5s".to_string();
+        let binding = edit.to_keybinding().unwrap();
+        assert_eq!(
+            binding.action,
+            BindingAction::WithArg("set-column-width".to_string(), BindingArg::String("-100".to_string()))
+        );
+        assert_eq!(binding.properties.cooldown_ms, Some(1500));
+
+        edit.action_value = "focus-workspace \"chat\"".to_string();
+        assert_eq!(
+            edit.to_keybinding().unwrap().action,
+            BindingAction::WithArg("focus-workspace".to_string(), BindingArg::String("chat".to_string()))
+        );
+
+        edit.cooldown = "later".to_string();
+        assert!(edit.validate().is_err());
+        assert!(edit.to_keybinding().is_none());
+
+        // Editing keeps an existing cooldown and string arguments
+        let edit = EditMode::from_binding(0, &binding);
+        assert_eq!(edit.cooldown, "1500");
+        assert_eq!(edit.action_value, "set-column-width \"-100\"");
+        assert_eq!(edit.to_keybinding().unwrap().action, binding.action);
+    }
+
+    #[test]
+    fn test_defaults_hidden_when_bound() {
+        let binding = |combo: &str, action: &str| {
+            let (modifiers, key) = Modifiers::parse(combo);
+            Keybinding {
+                modifiers,
+                key,
+                properties: BindingProperties::default(),
+                action: BindingAction::Simple(action.to_string()),
+                kdl_index: None,
+                source: None,
+            }
+        };
+        let mut view_model = KeybindingsViewModel {
+            bindings: vec![binding("Mod+q", "close-window")],
+            defaults: vec![
+                binding("Mod+Q", "close-window"),
+                binding("Mod+F", "maximize-column"),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(view_model.effective_bindings().len(), 1);
+
+        view_model.show_defaults = true;
+        let effective = view_model.effective_bindings();
+        assert_eq!(effective.len(), 2);
+        assert_eq!(effective[1].binding.combo(), "Mod+F");
+        assert_eq!(effective[1].status, BindingStatus::Default);
+    }
+
+    #[test]
+    fn test_search_by_tag() {
+        let binding = |combo: &str, action: &str| {
+            let (modifiers, key) = Modifiers::parse(combo);
+            Keybinding {
+                modifiers,
+                key,
+                properties: BindingProperties::default(),
+                action: BindingAction::Simple(action.to_string()),
+                kdl_index: None,
+                source: None,
+            }
+        };
+        let mut view_model = KeybindingsViewModel {
+            bindings: vec![
+                binding("Mod+Q", "close-window"),
+                binding("Mod+F", "maximize-column"),
+                binding("Mod+C", "center-column"),
+            ],
+            ..Default::default()
+        };
+        view_model.tags.set("Mod+F", "work");
+        view_model.tags.set("Mod+C", "work experimental");
+        let combos = |view_model: &KeybindingsViewModel| -> Vec<String> {
+            view_model.filtered_bindings().iter().map(|eb| eb.binding.combo()).collect()
+        };
+
+        view_model.set_search("#Work".to_string());
+        assert_eq!(combos(&view_model), ["Mod+F", "Mod+C"]);
+        view_model.set_search("#work center".to_string());
+        assert_eq!(combos(&view_model), ["Mod+C"]);
+        view_model.set_search("#work #experimental".to_string());
+        assert_eq!(combos(&view_model), ["Mod+C"]);
+        // A lone # while typing doesn't hide everything
+        view_model.set_search("#".to_string());
+        assert_eq!(combos(&view_model).len(), 3);
+    }
+
+    #[test]
+    fn test_rebase_changes() {
+        let binding = |combo: &str, action: &str| {
+            let (modifiers, key) = Modifiers::parse(combo);
+            Keybinding {
+                modifiers,
+                key,
+                properties: BindingProperties::default(),
+                action: BindingAction::Simple(action.to_string()),
+                kdl_index: None,
+                source: None,
+            }
+        };
+        let old = vec![
+            binding("Mod+Q", "close-window"),
+            binding("Mod+F", "maximize-column"),
+            binding("Mod+C", "center-column"),
+        ];
+        // On disk: a binding was inserted at the top and Mod+F was edited
+        let mut view_model = KeybindingsViewModel {
+            bindings: vec![
+                binding("Mod+T", "toggle-overview"),
+                binding("Mod+Q", "close-window"),
+                binding("Mod+F", "fullscreen-window"),
+            ],
+            ..Default::default()
+        };
+        let skipped = view_model.rebase_changes(
+            &old,
+            [
+                KeybindingChange::Delete(0),
+                KeybindingChange::Modify { index: 1, new: binding("Mod+F", "expand-column-to-available-width") },
+                KeybindingChange::Delete(2),
+                KeybindingChange::Add(binding("Mod+T", "toggle-overview")),
+                KeybindingChange::Add(binding("Mod+O", "toggle-overview")),
+            ]
+            .into_iter()
+            .collect(),
+        );
+
+        assert!(matches!(
+            view_model.pending_changes.to_changes()[..],
+            [KeybindingChange::Delete(1), KeybindingChange::Add(_)]
+        ));
+        assert_eq!(
+            skipped,
+            vec![
+                "change Mod+F: also edited on disk",
+                "remove Mod+C: already removed on disk",
+                "add Mod+T: now bound on disk",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pending_changes_last_write_wins() {
+        let binding = |combo: &str, action: &str| {
+            let (modifiers, key) = Modifiers::parse(combo);
+            Keybinding {
+                modifiers,
+                key,
+                properties: BindingProperties::default(),
+                action: BindingAction::Simple(action.to_string()),
+                kdl_index: None,
+                source: None,
+            }
+        };
+        let mut pending = PendingBindingChanges::default();
+        pending.add(binding("Mod+O", "toggle-overview"));
+        pending.modify(2, binding("Mod+F", "maximize-column"));
+        pending.delete(0);
+        pending.modify(2, binding("Mod+F", "fullscreen-window"));
+        pending.add(binding("Mod+T", "toggle-overview"));
+        pending.modify(0, binding("Mod+Q", "quit"));
+        pending.add(binding("Mod+o", "open-overview"));
+        pending.delete(1);
+        pending.modify(1, binding("Mod+C", "center-column"));
+        pending.delete(3);
+
+        // One change per binding, edits by index, new binds in staging order
+        assert_eq!(pending.len(), 6);
+        let changes: Vec<String> = pending
+            .to_changes()
+            .iter()
+            .map(|change| match change {
+                KeybindingChange::Add(b) => format!("add {} {}", b.combo(), b.action.short_description()),
+                KeybindingChange::Modify { index, new } => format!("modify {index} {}", new.action.short_description()),
+                KeybindingChange::Delete(index) => format!("delete {index}"),
+            })
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                "modify 0 quit",
+                "modify 1 center-column",
+                "modify 2 fullscreen-window",
+                "delete 3",
+                "add Mod+O open-overview",
+                "add Mod+T toggle-overview",
+            ]
+        );
+
+        pending.revert(1);
+        pending.remove_added(&binding("Mod+O", "toggle-overview"));
+        assert!(pending.modified(1).is_none() && pending.is_deleted(3));
+        assert_eq!(pending.len(), 4);
+
+        // Re-editing a binding through the view model doesn't stack changes
+        let mut vm = KeybindingsViewModel {
+            bindings: vec![binding("Mod+Q", "close-window")],
+            ..Default::default()
+        };
+        vm.toggle_selected_flag(BindingFlag::Repeat).unwrap();
+        vm.toggle_selected_flag(BindingFlag::AllowWhenLocked).unwrap();
+        vm.pending_changes.delete(0);
+        assert_eq!(vm.change_summary(), vec!["remove Mod+Q"]);
+    }
+
+    #[test]
+    fn test_combo_normalization() {
+        let binding = |combo: &str| {
+            let (modifiers, key) = Modifiers::parse(combo);
+            Keybinding {
+                modifiers,
+                key,
+                properties: BindingProperties::default(),
+                action: BindingAction::Simple("close-window".to_string()),
+                kdl_index: None,
+                source: None,
+            }
+        };
+        let a = binding("Shift+Mod+t");
+        let b = binding("Mod+Shift+T");
+        assert_eq!(a.combo(), "Mod+Shift+T");
+        assert!(a.same_combo(&b));
+        assert_eq!(binding("ctrl+alt+xf86audiomute").combo(), "Ctrl+Alt+XF86AudioMute");
+        assert_eq!(binding("Mod+print").display_combo(false), "Mod+Print Screen");
+        assert_eq!(binding("Mod+print").display_combo(true), "Mod+Print");
+        assert_eq!(binding("Mod+t").display_combo(false), "Mod+T");
+
+        let mut view_model = KeybindingsViewModel {
+            bindings: vec![b.clone(), binding("Mod+Q")],
+            defaults: vec![binding("Mod+O")],
+            show_defaults: true,
+            ..Default::default()
+        };
+        assert_eq!(view_model.conflicting_binding(&a, None).unwrap().combo(), "Mod+Shift+T");
+        // Editing the binding itself is not a conflict
+        assert!(view_model.conflicting_binding(&a, Some(0)).is_none());
+        assert!(view_model.conflicting_binding(&binding("Mod+o"), None).is_none());
+
+        view_model.pending_changes.add(binding("Mod+Ctrl+T"));
+        assert!(view_model.conflicting_binding(&binding("Ctrl+Mod+t"), None).is_some());
+    }
+
+    #[test]
+    fn test_category_counts() {
+        let binding = |combo: &str, action: BindingAction| {
+            let (modifiers, key) = Modifiers::parse(combo);
+            Keybinding {
+                modifiers,
+                key,
+                properties: BindingProperties::default(),
+                action,
+                kdl_index: None,
+                source: None,
+            }
+        };
+        let view_model = KeybindingsViewModel {
+            bindings: vec![
+                binding("Mod+H", BindingAction::Simple("focus-column-left".to_string())),
+                binding("Mod+T", BindingAction::Spawn(vec!["foot".to_string()])),
+                binding("Mod+L", BindingAction::Simple("focus-column-right".to_string())),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            KeybindingsViewModel::category_counts(&view_model.filtered_bindings()),
+            vec![("Focus", 2), ("Program Execution", 1)]
+        );
+    }
+
+    #[test]
+    fn test_binding_flags_and_columns() {
+        let props = BindingProperties {
+            repeat: Some(false),
+            cooldown_ms: Some(150),
+            allow_when_locked: Some(true),
+        };
+        assert_eq!(props.flags(), "no-repeat 150ms locked");
+        assert_eq!(BindingProperties::default().flags(), "");
+
+        let mut columns = KeybindingColumns::default();
+        columns.adjust_combo_width(-100);
+        assert_eq!(columns.combo_width, KeybindingColumns::MIN_COMBO_WIDTH);
+        columns.adjust_combo_width(4);
+        assert_eq!(columns.combo_width, KeybindingColumns::MIN_COMBO_WIDTH + 4);
+        columns.adjust_combo_width(100);
+        assert_eq!(columns.combo_width, KeybindingColumns::MAX_COMBO_WIDTH);
+    }
+
+    #[test]
+    fn test_toggle_selected_flag() {
+        let (modifiers, key) = Modifiers::parse("XF86AudioRaiseVolume");
+        let volume = Keybinding {
+            modifiers,
+            key,
+            properties: BindingProperties::default(),
+            action: BindingAction::SpawnSh("wpctl set-volume @DEFAULT_AUDIO_SINK@ 0.1+".to_string()),
+            kdl_index: Some(0),
+            source: None,
+        };
+        let mut vm = KeybindingsViewModel {
+            bindings: vec![volume],
+            ..Default::default()
+        };
+
+        let new = vm.toggle_selected_flag(BindingFlag::AllowWhenLocked).unwrap();
+        assert_eq!(new.properties.allow_when_locked, Some(true));
+        vm.toggle_selected_flag(BindingFlag::Repeat).unwrap();
+        assert_eq!(vm.pending_changes.len(), 1);
+        assert_eq!(vm.selected_effective_binding().unwrap().binding.properties.flags(), "no-repeat locked");
+
+        // Flipping both back leaves nothing staged
+        vm.toggle_selected_flag(BindingFlag::Repeat).unwrap();
+        vm.toggle_selected_flag(BindingFlag::AllowWhenLocked).unwrap();
+        assert!(vm.pending_changes.is_empty());
+    }
+
+    #[test]
+    fn test_rebind() {
+        let binding = |combo: &str, action: &str| {
+            let (modifiers, key) = Modifiers::parse(combo);
+            Keybinding {
+                modifiers,
+                key,
+                properties: BindingProperties { repeat: Some(false), ..Default::default() },
+                action: BindingAction::Simple(action.to_string()),
+                kdl_index: None,
+                source: None,
+            }
+        };
+        let mut vm = KeybindingsViewModel {
+            bindings: vec![binding("Mod+Q", "close-window"), binding("Mod+F", "maximize-column")],
+            ..Default::default()
+        };
+        let close = vm.bindings[0].clone();
+
+        // Keys are spelled the way niri's config spells them
+        let new = vm.rebind(Some(0), &close, "Mod+Shift+return").unwrap();
+        assert_eq!(new.combo(), "Mod+Shift+Return");
+        assert_eq!(new.properties.repeat, Some(false));
+        assert_eq!(vm.pending_changes.modified(0).map(|b| b.combo()), Some("Mod+Shift+Return".to_string()));
+
+        assert_eq!(vm.rebind(Some(0), &new, "Mod+F").unwrap_err(), "Mod+F is already bound to maximize-column");
+        assert!(vm.rebind(Some(0), &new, "Mod+Shift+Return").is_err());
+        assert!(vm.rebind(Some(0), &new, "Hyper+X").is_err());
+
+        // Back on the saved combo, nothing is staged
+        vm.rebind(Some(0), &new, "Mod+Q").unwrap();
+        assert!(vm.pending_changes.is_empty());
+
+        // A bind added this session moves instead of being duplicated
+        let added = binding("Mod+W", "toggle-window-floating");
+        vm.pending_changes.add(added.clone());
+        vm.rebind(None, &added, "Mod+V").unwrap();
+        let combos: Vec<_> = vm.pending_changes.added().iter().map(|b| b.combo()).collect();
+        assert_eq!(combos, vec!["Mod+V"]);
+    }
+
+    #[test]
+    fn test_repeat_lint() {
+        let (modifiers, key) = Modifiers::parse("Mod+T");
+        let mut terminal = Keybinding {
+            modifiers,
+            key,
+            properties: BindingProperties::default(),
+            action: BindingAction::Spawn(vec!["/usr/bin/foot".to_string()]),
+            kdl_index: Some(0),
+            source: None,
+        };
+        assert_eq!(terminal.repeat_lint(), Some("foot"));
+        terminal.properties.cooldown_ms = Some(500);
+        assert_eq!(terminal.repeat_lint(), None);
+        terminal.properties.cooldown_ms = None;
+        terminal.action = BindingAction::SpawnSh("kitty --single-instance".to_string());
+        assert_eq!(terminal.repeat_lint(), Some("kitty"));
+        terminal.action = BindingAction::SpawnSh("pgrep foot || foot".to_string());
+        assert_eq!(terminal.repeat_lint(), None);
+        terminal.action = BindingAction::Spawn(vec!["foot".to_string()]);
+
+        let mut vm = KeybindingsViewModel {
+            bindings: vec![terminal],
+            ..Default::default()
+        };
+        assert_eq!(vm.repeat_lint_count(), 1);
+        let fixed = vm.fix_selected_repeat().unwrap();
+        assert_eq!(fixed.properties.repeat, Some(false));
+        assert_eq!(vm.repeat_lint_count(), 0);
+        assert!(vm.fix_selected_repeat().is_err());
+    }
+
+    #[test]
+    fn test_missing_binaries_filter() {
+        let dir = std::env::temp_dir().join(format!("nirikiri-path-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let foot = dir.join("foot");
+        std::fs::write(&foot, "#!/bin/sh\n").unwrap();
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&foot, std::fs::Permissions::from_mode(0o755)).unwrap();
+        std::fs::write(dir.join("notes.txt"), "").unwrap();
+        let path_var = dir.clone().into_os_string();
+
+        assert!(program_installed("foot", Some(&path_var)));
+        assert!(program_installed(foot.to_str().unwrap(), None));
+        assert!(!program_installed("notes.txt", Some(&path_var)));
+        assert!(!program_installed("alacritty", Some(&path_var)));
+
+        let binding = |combo: &str, action: BindingAction| {
+            let (modifiers, key) = Modifiers::parse(combo);
+            Keybinding { modifiers, key, properties: BindingProperties::default(), action, kdl_index: None, source: None }
+        };
+        let sh = |cmd: &str| binding("Mod+X", BindingAction::SpawnSh(cmd.to_string()));
+        assert_eq!(sh("WAYLAND_DISPLAY=wayland-1 exec fuzzel -d").spawn_program(), Some("fuzzel"));
+        assert_eq!(sh("$TERMINAL -e htop").spawn_program(), None);
+
+        let mut vm = KeybindingsViewModel {
+            bindings: vec![
+                binding("Mod+T", BindingAction::Spawn(vec!["foot".to_string()])),
+                binding("Mod+Return", BindingAction::Spawn(vec!["alacritty".to_string()])),
+                binding("Mod+D", BindingAction::SpawnSh("alacritty -e fzf".to_string())),
+                binding("Mod+Q", BindingAction::Simple("close-window".to_string())),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(vm.toggle_missing_filter(Some(&path_var)), 2);
+        assert_eq!(vm.selected_binding().unwrap().combo(), "Mod+Return");
+        assert_eq!(vm.toggle_missing_filter(Some(&path_var)), 4);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_lost_essential_actions() {
+        let binding = |combo: &str, action: &str| {
+            let (modifiers, key) = Modifiers::parse(combo);
+            let action = BindingAction::Simple(action.to_string());
+            Keybinding { modifiers, key, properties: BindingProperties::default(), action, kdl_index: None, source: None }
+        };
+        let mut vm = KeybindingsViewModel {
+            bindings: vec![
+                binding("Mod+Q", "close-window"),
+                binding("Mod+Shift+E", "quit"),
+                binding("Mod+Shift+Q", "fullscreen-window"),
+            ],
+            ..Default::default()
+        };
+        assert!(vm.lost_essential_actions().is_empty());
+
+        vm.pending_changes.delete(0);
+        vm.pending_changes.modify(1, binding("Mod+Shift+E", "spawn-sh"));
+        assert_eq!(vm.lost_essential_actions(), vec!["quit", "close-window"]);
+
+        // Mod+Shift+E is taken by the modified bind and Mod+Shift+Q by another
+        let staged = vm.stage_fallback_binds(&["quit", "close-window"]);
+        assert_eq!(staged, vec!["Ctrl+Alt+Delete", "Mod+Q"]);
+        assert!(vm.lost_essential_actions().is_empty());
+
+        // A config that never bound them isn't flagged
+        let vm = KeybindingsViewModel { bindings: vec![binding("Mod+F", "fullscreen-window")], ..Default::default() };
+        assert!(vm.lost_essential_actions().is_empty());
+    }
+
+    #[test]
+    fn test_resolve_conflicts() {
+        let binding = |combo: &str, action: &str| {
+            let (modifiers, key) = Modifiers::parse(combo);
+            let action = BindingAction::Simple(action.to_string());
+            Keybinding { modifiers, key, properties: BindingProperties::default(), action, kdl_index: None, source: None }
+        };
+        let mut vm = KeybindingsViewModel {
+            bindings: vec![
+                binding("Mod+T", "toggle-overview"),
+                binding("Mod+F", "fullscreen-window"),
+                binding("Mod+Shift+F", "maximize-column"),
+                binding("Mod+Q", "close-window"),
+            ],
+            ..Default::default()
+        };
+        let incoming = vec![
+            binding("Mod+T", "spawn"),
+            binding("Mod+F", "center-column"),
+            binding("Mod+Q", "quit"),
+            binding("Mod+O", "open-overview"),
+        ];
+        let skipped = vm.stage_new_bindings(incoming);
+        assert_eq!(vm.pending_changes.len(), 1);
+        assert_eq!(vm.start_conflict_resolution("Template", skipped), 3);
+
+        // Replacing keeps the configured bind's place in the config
+        assert_eq!(vm.resolve_conflict(ConflictChoice::Replace), Ok(None));
+        assert_eq!(vm.pending_changes.modified(0).unwrap().action, BindingAction::Simple("spawn".to_string()));
+
+        // Mod+Shift+F is taken, so the suggestion adds Ctrl instead
+        let suggestion = vm.conflict_resolver.as_ref().unwrap().suggestion.clone().unwrap();
+        assert_eq!(suggestion.combo(), "Mod+Ctrl+F");
+        assert_eq!(vm.resolve_conflict(ConflictChoice::Rebind), Ok(None));
+        assert!(vm.conflicting_binding(&suggestion, None).is_some());
+        assert_eq!(vm.conflict_resolver.as_ref().unwrap().suggestion.as_ref().unwrap().combo(), "Mod+Shift+Q");
+
+        assert_eq!(vm.keep_remaining_conflicts().as_deref(), Some("Template: 1 replaced, 1 rebound, 1 kept"));
+        assert!(vm.conflict_resolver.is_none());
+        assert_eq!(vm.pending_changes.len(), 3);
+    }
+
+    #[test]
+    fn test_parse_strict_edge_cases() {
+        let (mods, key) = Modifiers::parse_strict(" Super+ctrl+T ").unwrap();
+        assert!(mods.mod_key && mods.ctrl && !mods.shift);
+        assert_eq!(key, "T");
+        assert_eq!(Modifiers::parse_strict("XF86AudioMute").unwrap().1, "XF86AudioMute");
+
+        assert!(Modifiers::parse_strict("").is_err());
+        assert!(Modifiers::parse_strict("Mod+").is_err());
+        assert!(Modifiers::parse_strict("+T").is_err());
+        assert!(Modifiers::parse_strict("Mod++T").is_err());
+        assert!(Modifiers::parse_strict("Mod+Shift T").is_err());
+        assert_eq!(
+            Modifiers::parse_strict("Hyper+T").unwrap_err(),
+            "Unknown modifier \"Hyper\" (expected Mod, Super, Ctrl, Shift or Alt)"
+        );
+
+        let mut edit = EditMode::new_binding();
+        edit.key_combo = "Mod+Hyper+T".to_string();
+        edit.action_value = "close-window".to_string();
+        assert!(edit.validate_combo().is_err());
+        assert!(edit.to_keybinding().is_none());
+    }
+
+    mod prop {
+        use super::*;
+        use proptest::prelude::*;
+
+        fn modifiers() -> impl Strategy<Value = Modifiers> {
+            (any::<bool>(), any::<bool>(), any::<bool>(), any::<bool>()).prop_map(
+                |(mod_key, ctrl, shift, alt)| Modifiers { mod_key, ctrl, shift, alt },
+            )
+        }
+
+        fn combo(mods: &Modifiers, key: &str) -> String {
+            let mods = mods.to_string();
+            if mods.is_empty() {
+                key.to_string()
+            } else {
+                format!("{mods}+{key}")
+            }
+        }
+
+        proptest! {
+            #[test]
+            fn parse_never_panics(s in "\\PC*") {
+                let _ = Modifiers::parse(&s);
+                let _ = Modifiers::parse_strict(&s);
+            }
+
+            #[test]
+            fn display_round_trips(mods in modifiers(), key in "[A-Za-z0-9_]{1,12}") {
+                let combo = combo(&mods, &key);
+                prop_assert_eq!(Modifiers::parse_strict(&combo), Ok((mods.clone(), key.clone())));
+                prop_assert_eq!(Modifiers::parse(&combo), (mods, key));
+            }
+
+            #[test]
+            fn strict_agrees_with_lenient(s in "[A-Za-z+]{0,24}") {
+                if let Ok(parsed) = Modifiers::parse_strict(&s) {
+                    prop_assert_eq!(parsed, Modifiers::parse(&s));
+                }
+            }
+
+            #[test]
+            fn strict_rejects_unknown_modifiers(
+                token in "[A-Za-z]{1,8}".prop_filter("known modifier", |t| {
+                    !matches!(
+                        t.to_lowercase().as_str(),
+                        "mod" | "super" | "logo" | "win" | "ctrl" | "control" | "shift" | "alt"
+                    )
+                }),
+                key in "[A-Za-z0-9]{1,6}",
+            ) {
+                let combo = format!("Mod+{token}+{key}");
+                prop_assert!(Modifiers::parse_strict(&combo).is_err());
+            }
+
+            #[test]
+            fn strict_rejects_trailing_plus(mods in modifiers(), key in "[A-Za-z0-9]{1,6}") {
+                let combo = format!("{}+", combo(&mods, &key));
+                prop_assert!(Modifiers::parse_strict(&combo).is_err());
+            }
+        }
+    }
 }
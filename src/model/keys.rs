@@ -0,0 +1,265 @@
+//! XKB key names as niri matches them
+
+/// Key names whose canonical spelling isn't just the name with its first
+/// letter kept. niri looks keysyms up case-insensitively, so any casing of
+/// these in a config refers to the same key.
+const KEY_NAMES: &[&str] = &[
+    "Return",
+    "Escape",
+    "Tab",
+    "BackSpace",
+    "Delete",
+    "Insert",
+    "Home",
+    "End",
+    "Page_Up",
+    "Page_Down",
+    "Left",
+    "Right",
+    "Up",
+    "Down",
+    "Print",
+    "Pause",
+    "Scroll_Lock",
+    "Caps_Lock",
+    "Num_Lock",
+    "Menu",
+    "space",
+    "comma",
+    "period",
+    "slash",
+    "backslash",
+    "minus",
+    "equal",
+    "plus",
+    "semicolon",
+    "apostrophe",
+    "grave",
+    "bracketleft",
+    "bracketright",
+    "XF86AudioRaiseVolume",
+    "XF86AudioLowerVolume",
+    "XF86AudioMute",
+    "XF86AudioMicMute",
+    "XF86AudioPlay",
+    "XF86AudioPause",
+    "XF86AudioStop",
+    "XF86AudioNext",
+    "XF86AudioPrev",
+    "XF86MonBrightnessUp",
+    "XF86MonBrightnessDown",
+    "XF86Calculator",
+    "XF86Mail",
+    "XF86Search",
+    "XF86Explorer",
+    "XF86PowerOff",
+    "XF86Sleep",
+    "MouseLeft",
+    "MouseRight",
+    "MouseMiddle",
+    "MouseBack",
+    "MouseForward",
+    "WheelScrollUp",
+    "WheelScrollDown",
+    "WheelScrollLeft",
+    "WheelScrollRight",
+    "TouchpadScrollUp",
+    "TouchpadScrollDown",
+    "TouchpadScrollLeft",
+    "TouchpadScrollRight",
+];
+
+/// Canonical spelling of a key name: letters are upper case (as in niri's
+/// default config), function keys are `F<n>`, and known keysyms use their
+/// XKB spelling. Anything else is returned unchanged.
+pub fn canonical_key(key: &str) -> String {
+    let mut chars = key.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return c.to_uppercase().collect();
+    }
+    if let Some(n) = key.strip_prefix(['f', 'F']) {
+        if !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()) {
+            return format!("F{n}");
+        }
+    }
+    KEY_NAMES
+        .iter()
+        .find(|name| name.eq_ignore_ascii_case(key))
+        .map_or_else(|| key.to_string(), |name| name.to_string())
+}
+
+/// Friendly labels for keys whose XKB names are cryptic
+const KEY_LABELS: &[(&str, &str)] = &[
+    ("Print", "Print Screen"),
+    ("Return", "Enter"),
+    ("Escape", "Esc"),
+    ("BackSpace", "Backspace"),
+    ("Page_Up", "Page Up"),
+    ("Page_Down", "Page Down"),
+    ("Left", "←"),
+    ("Right", "→"),
+    ("Up", "↑"),
+    ("Down", "↓"),
+    ("Scroll_Lock", "Scroll Lock"),
+    ("Caps_Lock", "Caps Lock"),
+    ("Num_Lock", "Num Lock"),
+    ("space", "Space"),
+    ("comma", ","),
+    ("period", "."),
+    ("slash", "/"),
+    ("backslash", "\\"),
+    ("minus", "-"),
+    ("equal", "="),
+    ("plus", "+"),
+    ("semicolon", ";"),
+    ("apostrophe", "'"),
+    ("grave", "`"),
+    ("bracketleft", "["),
+    ("bracketright", "]"),
+    ("XF86AudioRaiseVolume", "Volume Up 🔊"),
+    ("XF86AudioLowerVolume", "Volume Down 🔉"),
+    ("XF86AudioMute", "Mute 🔇"),
+    ("XF86AudioMicMute", "Mic Mute"),
+    ("XF86AudioPlay", "Play ⏯"),
+    ("XF86AudioPause", "Pause ⏸"),
+    ("XF86AudioStop", "Stop ⏹"),
+    ("XF86AudioNext", "Next Track ⏭"),
+    ("XF86AudioPrev", "Previous Track ⏮"),
+    ("XF86MonBrightnessUp", "Brightness Up 🔆"),
+    ("XF86MonBrightnessDown", "Brightness Down 🔅"),
+    ("XF86Calculator", "Calculator"),
+    ("XF86Mail", "Mail"),
+    ("XF86Search", "Search"),
+    ("XF86Explorer", "Files"),
+    ("XF86PowerOff", "Power"),
+    ("XF86Sleep", "Sleep"),
+    ("MouseLeft", "Left Click"),
+    ("MouseRight", "Right Click"),
+    ("MouseMiddle", "Middle Click"),
+    ("MouseBack", "Mouse Back"),
+    ("MouseForward", "Mouse Forward"),
+    ("WheelScrollUp", "Wheel Up"),
+    ("WheelScrollDown", "Wheel Down"),
+    ("WheelScrollLeft", "Wheel Left"),
+    ("WheelScrollRight", "Wheel Right"),
+    ("TouchpadScrollUp", "Touchpad Up"),
+    ("TouchpadScrollDown", "Touchpad Down"),
+    ("TouchpadScrollLeft", "Touchpad Left"),
+    ("TouchpadScrollRight", "Touchpad Right"),
+];
+
+/// Friendly label for a key, or None when its XKB name reads fine as is
+pub fn key_label(key: &str) -> Option<&'static str> {
+    KEY_LABELS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(key))
+        .map(|(_, label)| *label)
+}
+
+/// Symbols typed with Shift on a US layout and the key they are on
+const SHIFTED_SYMBOLS: &[(char, &str)] = &[
+    ('!', "1"),
+    ('@', "2"),
+    ('#', "3"),
+    ('$', "4"),
+    ('%', "5"),
+    ('^', "6"),
+    ('&', "7"),
+    ('*', "8"),
+    ('(', "9"),
+    (')', "0"),
+    ('_', "minus"),
+    ('+', "equal"),
+    ('{', "bracketleft"),
+    ('}', "bracketright"),
+    ('|', "backslash"),
+    (':', "semicolon"),
+    ('"', "apostrophe"),
+    ('~', "grave"),
+    ('<', "comma"),
+    ('>', "period"),
+    ('?', "slash"),
+];
+
+/// Symbols typed without Shift and their XKB names
+const SYMBOLS: &[(char, &str)] = &[
+    (' ', "space"),
+    (',', "comma"),
+    ('.', "period"),
+    ('/', "slash"),
+    ('\\', "backslash"),
+    ('-', "minus"),
+    ('=', "equal"),
+    (';', "semicolon"),
+    ('\'', "apostrophe"),
+    ('`', "grave"),
+    ('[', "bracketleft"),
+    (']', "bracketright"),
+];
+
+/// The key a typed character comes from, as niri names it, and whether
+/// Shift was held for it. niri matches the unshifted key, so `?` is
+/// `Shift+slash`; shifted symbols assume a US layout.
+pub fn char_key(c: char) -> Option<(String, bool)> {
+    if c.is_ascii_alphabetic() {
+        return Some((c.to_ascii_uppercase().to_string(), c.is_ascii_uppercase()));
+    }
+    if c.is_ascii_digit() {
+        return Some((c.to_string(), false));
+    }
+    if let Some((_, name)) = SYMBOLS.iter().find(|(symbol, _)| *symbol == c) {
+        return Some((name.to_string(), false));
+    }
+    SHIFTED_SYMBOLS
+        .iter()
+        .find(|(symbol, _)| *symbol == c)
+        .map(|(_, name)| (name.to_string(), true))
+}
+
+/// Whether two key names refer to the same key
+pub fn same_key(a: &str, b: &str) -> bool {
+    a.eq_ignore_ascii_case(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonical_key() {
+        assert_eq!(canonical_key("t"), "T");
+        assert_eq!(canonical_key("T"), "T");
+        assert_eq!(canonical_key("1"), "1");
+        assert_eq!(canonical_key("f12"), "F12");
+        assert_eq!(canonical_key("return"), "Return");
+        assert_eq!(canonical_key("PAGE_DOWN"), "Page_Down");
+        assert_eq!(canonical_key("Space"), "space");
+        assert_eq!(canonical_key("xf86audioraisevolume"), "XF86AudioRaiseVolume");
+        assert_eq!(canonical_key("Fancy_Key"), "Fancy_Key");
+        assert!(same_key("Print", "print"));
+    }
+
+    #[test]
+    fn test_char_key() {
+        assert_eq!(char_key('t'), Some(("T".to_string(), false)));
+        assert_eq!(char_key('T'), Some(("T".to_string(), true)));
+        assert_eq!(char_key('5'), Some(("5".to_string(), false)));
+        assert_eq!(char_key('/'), Some(("slash".to_string(), false)));
+        assert_eq!(char_key('?'), Some(("slash".to_string(), true)));
+        assert_eq!(char_key('%'), Some(("5".to_string(), true)));
+        assert_eq!(char_key(' '), Some(("space".to_string(), false)));
+        assert_eq!(char_key('é'), None);
+        // Every name is one niri knows
+        for c in "az09,./\\-=;'`[]!?{}|~".chars() {
+            let (key, _) = char_key(c).unwrap();
+            assert_eq!(canonical_key(&key), key);
+        }
+    }
+
+    #[test]
+    fn test_key_label() {
+        assert_eq!(key_label("print"), Some("Print Screen"));
+        assert_eq!(key_label("XF86AudioRaiseVolume"), Some("Volume Up 🔊"));
+        assert_eq!(key_label("T"), None);
+    }
+}
@@ -1,16 +1,51 @@
+pub mod actions;
 pub mod appearance;
+pub mod bind_overlay;
+pub mod binding_conflicts;
+pub mod binding_generator;
+pub mod binding_template;
 pub mod config;
+pub mod config_source;
+pub mod dry_run;
+pub mod general;
+pub mod health;
 pub mod keybindings;
+pub mod keys;
+pub mod nested_preview;
 pub mod output;
+pub mod quick_open;
+pub mod selectable_list;
+pub mod templates;
+pub mod text_input;
+pub mod tutorial;
+pub mod values;
+pub mod workspace_spawn;
 
 pub use appearance::{
     AppearanceEditMode, AppearanceField, AppearanceListItem, AppearanceSection,
     AppearanceSettings, AppearanceViewModel, BorderSettings, CenterFocusedColumn,
-    ColorEditField, ColorValue, FieldValue, FocusRingSettings, ShadowSettings, StrutsSettings,
+    ColorEditField, ColorValue, FieldValue, FocusRingSettings, IntegerBounds, SectionEditMode, SectionEditValue,
+    OverviewSettings, ShadowSettings, StrutsSettings,
 };
-pub use config::ConfigDocument;
+pub use bind_overlay::{BindOverlay, KeyCapture};
+pub use binding_conflicts::{BindingConflict, ConflictChoice, ConflictResolver};
+pub use binding_generator::BindingGenerator;
+pub use binding_template::{BindingTemplate, TemplateField, TemplatePrompt};
+pub use config_source::{ConfigCandidate, ConfigDiscovery, ConfigSource};
+pub use config::{ConfigDocument, SourceLocation, BACKGROUND_LOAD_BYTES};
+pub use dry_run::{DryRun, RunOutput};
+pub use general::{
+    ClipboardSettings, GeneralField, GeneralSettings, GeneralViewModel, XwaylandSettings, DEFAULT_XWAYLAND_PATH,
+};
+pub use health::{CheckStatus, HealthCheck, HealthReport};
 pub use keybindings::{
-    ActionType, BindingAction, BindingArg, BindingProperties, BindingStatus, EditField,
-    EditMode, Keybinding, KeybindingChange, KeybindingsViewModel, Modifiers,
+    ActionType, BindingAction, BindingArg, BindingFlag, BindingProperties, BindingStatus, EditField, EffectiveBinding,
+    EditMode, Keybinding, KeybindingChange, KeybindingColumns, KeybindingsViewModel, Modifiers, PendingBindingChanges,
 };
-pub use output::{OutputMode, OutputState, OutputTransform, OutputViewModel, Position, Size};
+pub use nested_preview::NestedPreview;
+pub use quick_open::{QuickOpen, QuickOpenEntry};
+pub use selectable_list::SelectableList;
+pub use templates::TemplateGallery;
+pub use workspace_spawn::{WorkspaceSpawn, WorkspaceSpawnWizard};
+pub use tutorial::{Tutorial, TutorialStep};
+pub use output::{Arrangement, DpiMismatch, LayoutIssue, ModeBrowser, OutputColorEditor, OutputMode, OutputPane, OutputPositionEditor, OutputChange, OutputState, OutputTransform, OutputViewModel, Position, Size, SnapAlign, SnapAlignment, position_moves};
@@ -0,0 +1,160 @@
+//! Appearance preview in a nested niri: a child niri running in a window,
+//! reading a temporary copy of the config with the staged appearance
+//! changes, so trying them out never touches the live session.
+
+use anyhow::{bail, Context, Result};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+
+use super::appearance::AppearanceSettings;
+use super::config::ConfigDocument;
+use crate::config::fragment::FRAGMENT_FILE;
+use crate::config::kdl_format::ensure_v1_new;
+
+/// Name of the config file in the preview directory
+const CONFIG_FILE: &str = "config.kdl";
+
+/// Where the nested niri's output goes, for when it exits right away
+const LOG_FILE: &str = "niri.log";
+
+/// Files the nested niri reads: the config with the staged changes, or in
+/// managed mode the main config including the fragment with them
+pub fn preview_files(edited: &ConfigDocument, main: Option<&ConfigDocument>) -> Vec<(&'static str, String)> {
+    let render = |config: &ConfigDocument| {
+        let mut doc = config.doc.clone();
+        ensure_v1_new(&mut doc);
+        doc.to_string()
+    };
+    match main {
+        Some(main) => {
+            let mut config = render(main);
+            if !crate::config::includes_fragment(main) {
+                config.push_str(&format!("include \"{FRAGMENT_FILE}\"\n"));
+            }
+            // The fragment goes first so a reload triggered by the config sees it
+            vec![(FRAGMENT_FILE, render(edited)), (CONFIG_FILE, config)]
+        }
+        None => vec![(CONFIG_FILE, render(edited))],
+    }
+}
+
+/// A running nested niri and its temporary config directory, both cleaned
+/// up on drop
+#[derive(Debug)]
+pub struct NestedPreview {
+    child: Child,
+    dir: PathBuf,
+    /// Settings the files were last written with, to skip needless rewrites
+    pub settings: AppearanceSettings,
+}
+
+impl NestedPreview {
+    /// Write the files to a fresh temporary directory and start niri on
+    /// them. niri opens a window instead of taking over the session when
+    /// started under Wayland or X11.
+    pub fn launch(files: &[(&'static str, String)], settings: AppearanceSettings) -> Result<Self> {
+        if std::env::var_os("WAYLAND_DISPLAY").is_none() && std::env::var_os("DISPLAY").is_none() {
+            bail!("A nested niri needs a running Wayland or X11 session to open its window in");
+        }
+        let dir = std::env::temp_dir().join(format!("nirikiri-nested-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+        write_files(&dir, files)?;
+
+        let log = File::create(dir.join(LOG_FILE)).context("Failed to create the nested niri log")?;
+        let child = Command::new("niri")
+            .arg("--config")
+            .arg(dir.join(CONFIG_FILE))
+            .stdin(Stdio::null())
+            .stdout(log.try_clone().context("Failed to open the nested niri log")?)
+            .stderr(log)
+            // The nested niri answers on its own socket; its clients must
+            // not talk to the live session
+            .env_remove("NIRI_SOCKET")
+            .spawn();
+        match child {
+            Ok(child) => Ok(Self { child, dir, settings }),
+            Err(e) => {
+                let _ = std::fs::remove_dir_all(&dir);
+                Err(anyhow::Error::new(e).context("Failed to start niri"))
+            }
+        }
+    }
+
+    /// Replace the files with new contents; niri reloads on its own
+    pub fn update(&mut self, files: &[(&'static str, String)], settings: AppearanceSettings) -> Result<()> {
+        write_files(&self.dir, files)?;
+        self.settings = settings;
+        Ok(())
+    }
+
+    /// Describe how niri exited, with the last line it logged, once it has
+    pub fn poll_exit(&mut self) -> Option<String> {
+        let status = match self.child.try_wait() {
+            Ok(Some(status)) => status.to_string(),
+            Ok(None) => return None,
+            Err(e) => e.to_string(),
+        };
+        let log = std::fs::read_to_string(self.dir.join(LOG_FILE)).unwrap_or_default();
+        Some(match log.lines().rev().find(|l| !l.trim().is_empty()) {
+            Some(line) => format!("{status}: {}", line.trim()),
+            None => status,
+        })
+    }
+
+    pub fn config_path(&self) -> PathBuf {
+        self.dir.join(CONFIG_FILE)
+    }
+}
+
+impl Drop for NestedPreview {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// Write each file next to its final name and rename it into place, so
+/// niri's file watcher never reloads a half-written config
+fn write_files(dir: &Path, files: &[(&'static str, String)]) -> Result<()> {
+    for (name, content) in files {
+        let path = dir.join(name);
+        let staging = dir.join(format!("{name}.new"));
+        std::fs::write(&staging, content).with_context(|| format!("Failed to write {}", staging.display()))?;
+        std::fs::rename(&staging, &path).with_context(|| format!("Failed to replace {}", path.display()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kdl::KdlDocument;
+
+    fn document(source: &str, path: &str) -> ConfigDocument {
+        ConfigDocument { doc: KdlDocument::parse_v1(source).unwrap(), path: path.into(), source: source.to_string() }
+    }
+
+    #[test]
+    fn test_preview_files() {
+        let edited = document("layout {\n    gaps 8\n}\n", "config.kdl");
+        assert_eq!(preview_files(&edited, None), vec![(CONFIG_FILE, "layout {\n    gaps 8\n}\n".to_string())]);
+
+        // Managed mode: the main config gains the include if it lacks one
+        let main = document("binds {\n}\n", "config.kdl");
+        let files = preview_files(&edited, Some(&main));
+        assert_eq!(files[0], (FRAGMENT_FILE, "layout {\n    gaps 8\n}\n".to_string()));
+        assert_eq!(files[1], (CONFIG_FILE, "binds {\n}\ninclude \"nirikiri.kdl\"\n".to_string()));
+
+        let main = document("include \"nirikiri.kdl\"\n", "config.kdl");
+        assert_eq!(preview_files(&edited, Some(&main))[1].1, "include \"nirikiri.kdl\"\n");
+
+        let dir = std::env::temp_dir().join(format!("nirikiri-nested-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        write_files(&dir, &files).unwrap();
+        assert_eq!(std::fs::read_to_string(dir.join(CONFIG_FILE)).unwrap(), files[1].1);
+        assert!(!dir.join("config.kdl.new").exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
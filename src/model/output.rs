@@ -1,7 +1,14 @@
+use serde::Serialize;
 use std::collections::HashMap;
 
+use super::appearance::AppearanceSettings;
+use super::binding_template::TemplateField;
+use super::config::SourceLocation;
+use super::selectable_list::SelectableList;
+use super::values::eval_integer;
+
 /// Physical position in logical pixels
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
 pub struct Position {
     pub x: i32,
     pub y: i32,
@@ -35,6 +42,79 @@ pub struct OutputMode {
     pub is_preferred: bool,
 }
 
+impl OutputMode {
+    /// Refresh rate rounded to whole Hz, used for filtering
+    pub fn refresh_hz(&self) -> u32 {
+        self.refresh_rate.round() as u32
+    }
+
+    /// The mode as niri's config spells it, e.g. `2560x1440@143.912`
+    pub fn config_string(&self) -> String {
+        format!("{}x{}@{:.3}", self.width, self.height, self.refresh_rate)
+    }
+}
+
+/// Why an output can't be turned off
+fn last_output_on(name: &str) -> String {
+    format!("{name} is the last output on; turning it off would leave no screen")
+}
+
+/// State for the per-output mode list browser
+#[derive(Debug, Clone, Default)]
+pub struct ModeBrowser {
+    pub list: SelectableList, // Indexes the filtered list
+    pub refresh_filter: Option<u32>, // Only show modes at this rounded rate
+}
+
+impl ModeBrowser {
+    /// Modes of the output that pass the refresh filter, with their index in `output.modes`
+    pub fn visible_modes<'a>(&self, output: &'a OutputState) -> Vec<(usize, &'a OutputMode)> {
+        output
+            .modes
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| self.refresh_filter.is_none_or(|hz| m.refresh_hz() == hz))
+            .collect()
+    }
+
+    /// Distinct rounded refresh rates offered by the output, highest first
+    pub fn refresh_rates(output: &OutputState) -> Vec<u32> {
+        let mut rates: Vec<u32> = output.modes.iter().map(|m| m.refresh_hz()).collect();
+        rates.sort_unstable_by(|a, b| b.cmp(a));
+        rates.dedup();
+        rates
+    }
+
+    /// Cycle the filter through all rates, then back to showing everything
+    pub fn cycle_refresh_filter(&mut self, output: &OutputState) {
+        let rates = Self::refresh_rates(output);
+        self.refresh_filter = match self.refresh_filter {
+            None => rates.first().copied(),
+            Some(hz) => rates
+                .iter()
+                .position(|&r| r == hz)
+                .and_then(|i| rates.get(i + 1))
+                .copied(),
+        };
+        self.list.reset();
+    }
+
+    /// Index into `output.modes` of the highlighted mode
+    pub fn selected_mode_index(&self, output: &OutputState) -> Option<usize> {
+        self.visible_modes(output)
+            .get(self.list.selected_index)
+            .map(|(i, _)| *i)
+    }
+
+    pub fn select_next(&mut self, output: &OutputState) {
+        self.list.select_next(self.visible_modes(output).len());
+    }
+
+    pub fn select_prev(&mut self, output: &OutputState) {
+        self.list.select_prev(self.visible_modes(output).len());
+    }
+}
+
 /// Transform for output rotation/flip
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum OutputTransform {
@@ -63,6 +143,73 @@ impl OutputTransform {
         }
     }
 
+    /// The transform spelled as in niri's config
+    pub fn parse(name: &str) -> Option<Self> {
+        let mut transform = OutputTransform::Normal;
+        loop {
+            if transform.as_str() == name {
+                return Some(transform);
+            }
+            transform = transform.next();
+            if transform == OutputTransform::Normal {
+                return None;
+            }
+        }
+    }
+
+    /// The same transform turned half a turn, which turns the picture
+    /// upside down without changing the monitor's size in the layout
+    pub fn upside_down(&self) -> Self {
+        match self {
+            OutputTransform::Normal => OutputTransform::Rotate180,
+            OutputTransform::Rotate90 => OutputTransform::Rotate270,
+            OutputTransform::Rotate180 => OutputTransform::Normal,
+            OutputTransform::Rotate270 => OutputTransform::Rotate90,
+            OutputTransform::Flipped => OutputTransform::Flipped180,
+            OutputTransform::Flipped90 => OutputTransform::Flipped270,
+            OutputTransform::Flipped180 => OutputTransform::Flipped,
+            OutputTransform::Flipped270 => OutputTransform::Flipped90,
+        }
+    }
+
+    /// Whether width and height swap places on screen
+    pub fn is_rotated(&self) -> bool {
+        matches!(
+            self,
+            OutputTransform::Rotate90
+                | OutputTransform::Rotate270
+                | OutputTransform::Flipped90
+                | OutputTransform::Flipped270
+        )
+    }
+
+    /// The next transform when cycling: the rotations, then the flipped ones
+    pub fn next(&self) -> Self {
+        match self {
+            OutputTransform::Normal => OutputTransform::Rotate90,
+            OutputTransform::Rotate90 => OutputTransform::Rotate180,
+            OutputTransform::Rotate180 => OutputTransform::Rotate270,
+            OutputTransform::Rotate270 => OutputTransform::Flipped,
+            OutputTransform::Flipped => OutputTransform::Flipped90,
+            OutputTransform::Flipped90 => OutputTransform::Flipped180,
+            OutputTransform::Flipped180 => OutputTransform::Flipped270,
+            OutputTransform::Flipped270 => OutputTransform::Normal,
+        }
+    }
+
+    pub fn to_niri(self) -> niri_ipc::Transform {
+        match self {
+            OutputTransform::Normal => niri_ipc::Transform::Normal,
+            OutputTransform::Rotate90 => niri_ipc::Transform::_90,
+            OutputTransform::Rotate180 => niri_ipc::Transform::_180,
+            OutputTransform::Rotate270 => niri_ipc::Transform::_270,
+            OutputTransform::Flipped => niri_ipc::Transform::Flipped,
+            OutputTransform::Flipped90 => niri_ipc::Transform::Flipped90,
+            OutputTransform::Flipped180 => niri_ipc::Transform::Flipped180,
+            OutputTransform::Flipped270 => niri_ipc::Transform::Flipped270,
+        }
+    }
+
     pub fn from_niri(transform: &niri_ipc::Transform) -> Self {
         match transform {
             niri_ipc::Transform::Normal => OutputTransform::Normal,
@@ -89,19 +236,115 @@ pub struct OutputState {
     pub position: Position,
     pub logical_size: Size,
     pub physical_size: Size,
+    pub size_mm: Option<Size>, // Physical dimensions reported by the monitor (EDID)
     pub enabled: bool,
+    pub vrr: bool, // Variable refresh rate is on
     pub connected: bool,
     pub configured: bool,
     pub make: String,
     pub model: String,
+    pub source: Option<SourceLocation>, // Where the output block is defined in the config
+    /// Further blocks for the same output, which are ignored for the first
+    pub duplicate_sources: Vec<SourceLocation>,
+    /// `background-color` and `backdrop-color` from the output block; niri
+    /// doesn't report them over IPC
+    pub background_color: Option<String>,
+    pub backdrop_color: Option<String>,
+    /// `focus-at-startup` is set in the output block; config-only like the colors
+    pub focus_at_startup: bool,
 }
 
 impl OutputState {
+    /// Case-insensitive match against connector name, make and model
+    pub fn matches_search(&self, query: &str) -> bool {
+        let query = query.to_lowercase();
+        [&self.name, &self.make, &self.model]
+            .iter()
+            .any(|field| field.to_lowercase().contains(&query))
+    }
+
     pub fn current_mode(&self) -> Option<&OutputMode> {
         self.current_mode_index
             .and_then(|idx| self.modes.get(idx))
     }
 
+    /// Pixel density of the current mode, if the monitor reports its physical size
+    pub fn ppi(&self) -> Option<f64> {
+        let mode = self.current_mode()?;
+        let mm = self.size_mm.filter(|s| s.width > 0 && s.height > 0)?;
+        let diagonal_px = (mode.width as f64).hypot(mode.height as f64);
+        let diagonal_in = (mm.width as f64).hypot(mm.height as f64) / 25.4;
+        Some(diagonal_px / diagonal_in)
+    }
+
+    /// Logical size the current mode maps to at this scale, before rounding
+    pub fn exact_logical_size(&self) -> Option<(f64, f64)> {
+        let mode = self.current_mode()?;
+        if self.scale <= 0.0 {
+            return None;
+        }
+        Some((mode.width as f64 / self.scale, mode.height as f64 / self.scale))
+    }
+
+    /// Whether fractional scaling leaves a non-integer logical size
+    pub fn has_fractional_logical_size(&self) -> bool {
+        self.exact_logical_size().is_some_and(|(w, h)| {
+            (w - w.round()).abs() > 1e-6 || (h - h.round()).abs() > 1e-6
+        })
+    }
+
+    /// Take on a change that was written, so the view matches what niri
+    /// will report after re-reading the config
+    pub fn apply_change(&mut self, change: &OutputChange) {
+        if let Some(position) = change.position {
+            self.position = position;
+        }
+        if let Some(mode) = &change.mode {
+            self.current_mode_index = self.modes.iter().position(|m| m == mode);
+            self.physical_size = Size::new(mode.width, mode.height);
+        }
+        if let Some(scale) = change.scale {
+            self.scale = scale;
+        }
+        if let Some(transform) = change.transform {
+            self.transform = transform;
+        }
+        if let Some(vrr) = change.vrr {
+            self.vrr = vrr;
+        }
+        if let Some(enabled) = change.enabled {
+            self.enabled = enabled;
+        }
+        if let Some(color) = &change.background_color {
+            self.background_color = Some(color.clone()).filter(|c| !c.is_empty());
+        }
+        if let Some(color) = &change.backdrop_color {
+            self.backdrop_color = Some(color.clone()).filter(|c| !c.is_empty());
+        }
+        if let Some(focus) = change.focus_at_startup {
+            self.focus_at_startup = focus;
+        }
+        if change.mode.is_some() || change.scale.is_some() || change.transform.is_some() {
+            if let Some((w, h)) = self.exact_logical_size() {
+                let (w, h) = (w.round() as u32, h.round() as u32);
+                self.logical_size = if self.transform.is_rotated() { Size::new(h, w) } else { Size::new(w, h) };
+            }
+        }
+        self.configured = true;
+    }
+
+    /// The position, mode, scale and transform niri reports, as a change
+    /// that writes them into the config
+    pub fn live_layout(&self) -> OutputChange {
+        OutputChange {
+            position: Some(self.position),
+            mode: self.current_mode().cloned(),
+            scale: Some(self.scale),
+            transform: Some(self.transform),
+            ..Default::default()
+        }
+    }
+
     pub fn mode_string(&self) -> String {
         self.current_mode()
             .map(|m| format!("{}x{}@{:.2}Hz", m.width, m.height, m.refresh_rate))
@@ -109,26 +352,733 @@ impl OutputState {
     }
 }
 
+/// The outputs pane that j/k, hjkl and the arrow keys act on
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputPane {
+    /// They pick an output
+    List,
+    /// They move the selected monitor
+    #[default]
+    Canvas,
+}
+
+impl OutputPane {
+    pub fn toggle(self) -> Self {
+        match self {
+            OutputPane::List => OutputPane::Canvas,
+            OutputPane::Canvas => OutputPane::List,
+        }
+    }
+}
+
+/// How to line up all enabled outputs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arrangement {
+    /// Side by side, top edges aligned
+    Row,
+    /// Stacked, left edges aligned
+    Column,
+}
+
+impl Arrangement {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "row" => Some(Arrangement::Row),
+            "column" => Some(Arrangement::Column),
+            _ => None,
+        }
+    }
+}
+
+/// Which edges line up when snapping an output next to another one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapAlign {
+    /// Top edges beside the reference, left edges above or below it
+    Start,
+    Center,
+    /// Bottom or right edges
+    End,
+}
+
+impl SnapAlign {
+    pub fn next(self) -> Self {
+        match self {
+            SnapAlign::Start => SnapAlign::Center,
+            SnapAlign::Center => SnapAlign::End,
+            SnapAlign::End => SnapAlign::Start,
+        }
+    }
+
+    /// Where an output `len` long starts along an edge of the reference
+    /// that starts at `start` and is `reference` long
+    pub fn place(self, start: i32, reference: u32, len: u32) -> i32 {
+        match self {
+            SnapAlign::Start => start,
+            SnapAlign::Center => start + (reference as i32 - len as i32) / 2,
+            SnapAlign::End => start + reference as i32 - len as i32,
+        }
+    }
+
+    /// Name for snapping left or right, where vertical edges meet
+    pub fn beside_name(self) -> &'static str {
+        match self {
+            SnapAlign::Start => "top",
+            SnapAlign::Center => "center",
+            SnapAlign::End => "bottom",
+        }
+    }
+
+    /// Name for snapping above or below
+    pub fn stacked_name(self) -> &'static str {
+        match self {
+            SnapAlign::Start => "left",
+            SnapAlign::Center => "center",
+            SnapAlign::End => "right",
+        }
+    }
+}
+
+/// Alignment the snap keys use, cycled separately for each direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SnapAlignment {
+    /// Snapping left or right of the reference
+    pub beside: SnapAlign,
+    /// Snapping above or below it
+    pub stacked: SnapAlign,
+}
+
+impl Default for SnapAlignment {
+    /// Top edges beside, centered above and below
+    fn default() -> Self {
+        Self { beside: SnapAlign::Start, stacked: SnapAlign::Center }
+    }
+}
+
+/// Order to send staged positions in so no output is moved onto one that
+/// hasn't moved out of the way yet; niri would push the two apart for a
+/// moment and the screens would jump. `outputs` is the layout niri has now.
+/// Outputs that block each other (e.g. two swapping places) are parked to
+/// the right of everything first, so some moves appear twice.
+pub fn position_moves(outputs: &[OutputState], changes: &HashMap<String, OutputChange>) -> Vec<(String, Position)> {
+    // Left, top, right, bottom of an output of `size` at `position`
+    let rect = |position: Position, size: Size| {
+        let (x, y) = (position.x as i64, position.y as i64);
+        (x, y, x + size.width as i64, y + size.height as i64)
+    };
+    let overlaps = |a: (i64, i64, i64, i64), b: (i64, i64, i64, i64)| a.0 < b.2 && b.0 < a.2 && a.1 < b.3 && b.1 < a.3;
+
+    let mut current: HashMap<&str, (Position, Size)> = outputs
+        .iter()
+        .filter(|o| o.enabled)
+        .map(|o| (o.name.as_str(), (o.position, o.logical_size)))
+        .collect();
+    let mut pending: Vec<(&str, Position)> = changes
+        .iter()
+        .filter_map(|(name, change)| Some((name.as_str(), change.position?)))
+        .filter(|(name, target)| current.get(name).is_none_or(|&(position, _)| position != *target))
+        .collect();
+    pending.sort_by_key(|&(name, _)| name);
+
+    let mut moves = Vec::new();
+    let mut parked: Vec<&str> = Vec::new();
+    while !pending.is_empty() {
+        // Only outputs that are still going to move can be in the way
+        let blocked = |name: &str, target: Position| {
+            let size = current.get(name).map_or(Size::default(), |&(_, size)| size);
+            pending.iter().any(|&(other, _)| {
+                other != name
+                    && current.get(other).is_some_and(|&(position, other_size)| {
+                        overlaps(rect(target, size), rect(position, other_size))
+                    })
+            })
+        };
+        if let Some(i) = pending.iter().position(|&(name, target)| !blocked(name, target)) {
+            let (name, target) = pending.remove(i);
+            if let Some(entry) = current.get_mut(name) {
+                entry.0 = target;
+            }
+            moves.push((name.to_string(), target));
+            continue;
+        }
+        // Everything left waits on another: park one out of the way
+        let Some(&(name, _)) = pending.iter().find(|(name, _)| !parked.contains(name)) else {
+            break;
+        };
+        let right = current
+            .values()
+            .map(|&(position, size)| rect(position, size).2)
+            .chain(pending.iter().map(|&(other, target)| {
+                rect(target, current.get(other).map_or(Size::default(), |&(_, size)| size)).2
+            }))
+            .max()
+            .unwrap_or(0);
+        let spot = Position::new(right as i32, 0);
+        if let Some(entry) = current.get_mut(name) {
+            entry.0 = spot;
+        }
+        parked.push(name);
+        moves.push((name.to_string(), spot));
+    }
+    moves
+}
+
+/// How far apart the effective pixel densities of neighbouring outputs may
+/// be before the cursor visibly changes size crossing between them
+const DPI_MISMATCH_RATIO: f64 = 1.25;
+
+/// Two touching outputs whose logical pixels differ a lot in physical size
+#[derive(Debug, Clone, PartialEq)]
+pub struct DpiMismatch {
+    /// The denser output, whose scale the suggestion is for
+    pub output: String,
+    pub neighbour: String,
+    /// Physical pixels per inch divided by scale
+    pub effective_ppi: f64,
+    pub neighbour_effective_ppi: f64,
+    /// Scale for `output` that brings it close to its neighbour
+    pub suggested_scale: f64,
+}
+
+impl DpiMismatch {
+    pub fn message(&self) -> String {
+        format!(
+            "cursor will jump sizes crossing {}→{} ({:.0} vs {:.0} ppi); scale {} on {} would match",
+            self.output,
+            self.neighbour,
+            self.effective_ppi,
+            self.neighbour_effective_ppi,
+            self.suggested_scale,
+            self.output
+        )
+    }
+}
+
+/// Placement niri would not keep as staged: it moves overlapping outputs
+/// apart, and the cursor can't cross a gap between outputs
+#[derive(Debug, Clone, PartialEq)]
+pub enum LayoutIssue {
+    /// The two outputs cover some of the same logical pixels; `windows` is
+    /// whether that includes the area windows and their focus rings use,
+    /// rather than only struts and outer gaps
+    Overlap { output: String, other: String, windows: bool },
+    /// `output` doesn't touch the rest of the layout; `other` is the nearest
+    /// output there and `distance` the logical pixels between them
+    Gap { output: String, other: String, distance: u32 },
+}
+
+impl LayoutIssue {
+    pub fn message(&self) -> String {
+        match self {
+            LayoutIssue::Overlap { output, other, windows: true } => {
+                format!("{output} overlaps {other}; niri will move one")
+            }
+            LayoutIssue::Overlap { output, other, windows: false } => {
+                format!("{output} overlaps {other} only in struts and gaps; niri will still move one")
+            }
+            LayoutIssue::Gap { output, other, distance: 0 } => {
+                format!("{output} only touches {other} at a corner; the cursor can't cross")
+            }
+            LayoutIssue::Gap { output, other, distance } => {
+                format!("{output} is {distance}px away from {other}; the cursor can't cross")
+            }
+        }
+    }
+
+    /// Whether the issue involves the named output
+    pub fn involves(&self, name: &str) -> bool {
+        match self {
+            LayoutIssue::Overlap { output, other, .. } | LayoutIssue::Gap { output, other, .. } => {
+                output == name || other == name
+            }
+        }
+    }
+}
+
+/// Scales offered when stepping with `<` and `>`; niri accepts any value,
+/// these are the ones that give a sensible logical size on common panels
+pub const SCALE_STEPS: [f64; 10] = [0.5, 0.75, 1.0, 1.25, 1.5, 1.75, 2.0, 2.5, 3.0, 4.0];
+
+/// Staged edits to one output; fields left None keep their current value
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OutputChange {
+    pub position: Option<Position>,
+    pub mode: Option<OutputMode>, // Picked in the mode browser
+    pub scale: Option<f64>,
+    pub transform: Option<OutputTransform>,
+    pub vrr: Option<bool>,
+    pub enabled: Option<bool>, // false writes `off`
+    /// Config-only colors; an empty string removes the node
+    pub background_color: Option<String>,
+    pub backdrop_color: Option<String>,
+    pub focus_at_startup: Option<bool>,
+}
+
+impl OutputChange {
+    fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Take over every field `other` sets, keeping the rest
+    fn merge(&mut self, other: OutputChange) {
+        self.position = other.position.or(self.position);
+        self.mode = other.mode.or(self.mode.take());
+        self.scale = other.scale.or(self.scale);
+        self.transform = other.transform.or(self.transform);
+        self.vrr = other.vrr.or(self.vrr);
+        self.enabled = other.enabled.or(self.enabled);
+        self.background_color = other.background_color.or(self.background_color.take());
+        self.backdrop_color = other.backdrop_color.or(self.backdrop_color.take());
+        self.focus_at_startup = other.focus_at_startup.or(self.focus_at_startup);
+    }
+
+    /// One line per staged field, for commit messages
+    pub fn summary(&self, name: &str) -> Vec<String> {
+        let mut lines = Vec::new();
+        if let Some(pos) = self.position {
+            lines.push(format!("move {name} to {},{}", pos.x, pos.y));
+        }
+        if let Some(mode) = &self.mode {
+            lines.push(format!("set {name} mode to {}", mode.config_string()));
+        }
+        if let Some(scale) = self.scale {
+            lines.push(format!("set {name} scale to {scale}"));
+        }
+        if let Some(transform) = self.transform {
+            lines.push(format!("set {name} transform to {}", transform.as_str()));
+        }
+        if let Some(vrr) = self.vrr {
+            lines.push(format!("turn {} variable refresh rate on {name}", if vrr { "on" } else { "off" }));
+        }
+        if let Some(enabled) = self.enabled {
+            lines.push(format!("turn {name} {}", if enabled { "on" } else { "off" }));
+        }
+        for (node, color) in [("background-color", &self.background_color), ("backdrop-color", &self.backdrop_color)] {
+            match color.as_deref() {
+                Some("") => lines.push(format!("remove {name} {node}")),
+                Some(color) => lines.push(format!("set {name} {node} to {color}")),
+                None => {}
+            }
+        }
+        match self.focus_at_startup {
+            Some(true) => lines.push(format!("focus {name} at startup")),
+            Some(false) => lines.push(format!("stop focusing {name} at startup")),
+            None => {}
+        }
+        lines
+    }
+}
+
+const BACKGROUND: usize = 0;
+const BACKDROP: usize = 1;
+
+/// State for the dialog that edits an output's background and backdrop colors
+#[derive(Debug, Clone)]
+pub struct OutputColorEditor {
+    pub output: String,
+    pub fields: Vec<TemplateField>,
+    pub focused: usize,
+}
+
+impl OutputColorEditor {
+    /// Start from the output's colors with any staged change applied
+    pub fn new(output: &OutputState) -> Self {
+        let color = |c: &Option<String>| c.clone().unwrap_or_default();
+        Self {
+            output: output.name.clone(),
+            fields: vec![
+                TemplateField::new("Background", color(&output.background_color)),
+                TemplateField::new("Backdrop", color(&output.backdrop_color)),
+            ],
+            focused: BACKGROUND,
+        }
+    }
+
+    pub fn focus_next(&mut self) {
+        self.focused = (self.focused + 1) % self.fields.len();
+    }
+
+    pub fn field_mut(&mut self) -> &mut TemplateField {
+        &mut self.fields[self.focused]
+    }
+
+    pub fn background(&self) -> &str {
+        &self.fields[BACKGROUND].value
+    }
+
+    pub fn backdrop(&self) -> &str {
+        &self.fields[BACKDROP].value
+    }
+}
+
+/// State for the dialog that types an exact position for an output
+#[derive(Debug, Clone)]
+pub struct OutputPositionEditor {
+    pub output: String,
+    /// X and Y; each takes arithmetic such as "1920+1280"
+    pub fields: Vec<TemplateField>,
+    pub focused: usize,
+    /// Why the last confirm was refused, shown in the dialog
+    pub error: Option<String>,
+}
+
+impl OutputPositionEditor {
+    pub fn new(output: &str, position: Position) -> Self {
+        Self {
+            output: output.to_string(),
+            fields: vec![
+                TemplateField::new("X", position.x.to_string()),
+                TemplateField::new("Y", position.y.to_string()),
+            ],
+            focused: 0,
+            error: None,
+        }
+    }
+
+    pub fn focus_next(&mut self) {
+        self.focused = (self.focused + 1) % self.fields.len();
+    }
+
+    pub fn field_mut(&mut self) -> &mut TemplateField {
+        self.error = None;
+        &mut self.fields[self.focused]
+    }
+
+    /// The typed position, or which coordinate doesn't evaluate
+    pub fn position(&self) -> Result<Position, String> {
+        let coordinate = |field: &TemplateField| {
+            eval_integer(&field.value).map_err(|e| format!("{}: {e}", field.name))
+        };
+        Ok(Position::new(coordinate(&self.fields[0])?, coordinate(&self.fields[1])?))
+    }
+}
+
 /// View model for displaying outputs
 #[derive(Debug, Clone, Default)]
 pub struct OutputViewModel {
     pub outputs: Vec<OutputState>,
-    pub selected_index: usize,
-    pub pending_changes: HashMap<String, Position>,
+    /// Selection as an index into `outputs`, kept within the search matches
+    pub list: SelectableList,
+    pub pending_changes: HashMap<String, OutputChange>,
+    pub mode_browser: Option<ModeBrowser>,
+    pub search_query: String,
+    pub search_mode: bool,
+    pub snap_alignment: SnapAlignment,
+    /// Outputs niri has turned off that the staged positions are for,
+    /// while the save waits for the user to enable them or go ahead anyway
+    pub disabled_warning: Option<Vec<String>>,
+    pub focused_pane: OutputPane,
 }
 
 impl OutputViewModel {
+    /// Re-stage changes after a reload, skipping outputs that are gone and
+    /// modes they no longer offer
+    pub fn rebase_changes(&mut self, changes: HashMap<String, OutputChange>) -> Vec<String> {
+        let mut skipped = Vec::new();
+        for (name, mut change) in changes {
+            let Some(output) = self.outputs.iter().find(|o| o.name == name) else {
+                self.pending_changes.remove(&name);
+                skipped.extend(change.summary(&name).into_iter().map(|line| format!("{line}: output no longer present")));
+                continue;
+            };
+            if let Some(mode) = change.mode.take_if(|mode| !output.modes.contains(mode)) {
+                skipped.push(format!("mode {name}: {} no longer offered", mode.config_string()));
+            }
+            if !change.is_empty() {
+                self.pending_changes.insert(name, change);
+            }
+        }
+        skipped.sort();
+        skipped
+    }
+
+    /// Changes that put every staged field back to what niri reports, for
+    /// undoing a preview. Modes are only restored when the current one is known.
+    pub fn revert_changes(&self) -> HashMap<String, OutputChange> {
+        let mut reverts = HashMap::new();
+        for (name, change) in &self.pending_changes {
+            let Some(output) = self.outputs.iter().find(|o| &o.name == name) else {
+                continue;
+            };
+            let revert = OutputChange {
+                position: change.position.map(|_| output.position),
+                mode: change.mode.as_ref().and_then(|_| output.current_mode().cloned()),
+                scale: change.scale.map(|_| output.scale),
+                transform: change.transform.map(|_| output.transform),
+                vrr: change.vrr.map(|_| output.vrr),
+                enabled: change.enabled.map(|_| output.enabled),
+                // Colors aren't previewed, so there is nothing to undo
+                ..Default::default()
+            };
+            reverts.insert(name.clone(), revert);
+        }
+        reverts
+    }
+
+    /// Like `revert_changes`, for a preview sent while `earlier` still
+    /// undoes a running one. niri reports the earlier preview's values by
+    /// now, so fields it changed go back to the values in `earlier` instead.
+    pub fn revert_changes_over(&self, earlier: HashMap<String, OutputChange>) -> HashMap<String, OutputChange> {
+        let mut reverts = self.revert_changes();
+        for (name, old) in earlier {
+            let change = reverts.entry(name).or_default();
+            change.position = old.position.or(change.position);
+            change.mode = old.mode.or(change.mode.take());
+            change.scale = old.scale.or(change.scale);
+            change.transform = old.transform.or(change.transform);
+            change.vrr = old.vrr.or(change.vrr);
+            change.enabled = old.enabled.or(change.enabled);
+        }
+        reverts
+    }
+
+    /// One line per staged change, for commit messages
+    pub fn change_summary(&self) -> Vec<String> {
+        let mut names: Vec<&String> = self.pending_changes.keys().collect();
+        names.sort();
+        names
+            .into_iter()
+            .flat_map(|name| self.pending_changes[name].summary(name))
+            .collect()
+    }
+
+    pub fn pending_position(&self, name: &str) -> Option<Position> {
+        self.pending_changes.get(name)?.position
+    }
+
+    pub fn pending_mode(&self, name: &str) -> Option<&OutputMode> {
+        self.pending_changes.get(name)?.mode.as_ref()
+    }
+
+    pub fn pending_scale(&self, name: &str) -> Option<f64> {
+        self.pending_changes.get(name)?.scale
+    }
+
+    /// Edit the staged change of an output, dropping it once nothing is left
+    fn update_change(&mut self, name: &str, edit: impl FnOnce(&mut OutputChange)) {
+        let change = self.pending_changes.entry(name.to_string()).or_default();
+        edit(change);
+        if change.is_empty() {
+            self.pending_changes.remove(name);
+        }
+    }
+
+    /// Stage a mode for an output; picking its current mode drops the change
+    pub fn stage_mode(&mut self, name: &str, mode: OutputMode) {
+        let current = self.outputs.iter().find(|o| o.name == name).and_then(|o| o.current_mode());
+        let mode = Some(mode).filter(|mode| current != Some(mode));
+        self.update_change(name, |change| change.mode = mode);
+    }
+
+    /// Step the output's scale through [`SCALE_STEPS`], up when `steps` > 0.
+    /// Going back to the current scale drops the change.
+    pub fn step_scale(&mut self, name: &str, steps: i32) {
+        let Some(output) = self.outputs.iter().find(|o| o.name == name) else {
+            return;
+        };
+        let mut scale = self.pending_scale(name).unwrap_or(output.scale);
+        for _ in 0..steps.unsigned_abs() {
+            let next = if steps > 0 {
+                SCALE_STEPS.iter().find(|&&s| s > scale + 1e-6)
+            } else {
+                SCALE_STEPS.iter().rev().find(|&&s| s < scale - 1e-6)
+            };
+            match next {
+                Some(&next) => scale = next,
+                None => break,
+            }
+        }
+        let staged = Some(scale).filter(|s| (s - output.scale).abs() > 1e-6);
+        self.update_change(name, |change| change.scale = staged);
+    }
+
+    /// Stage the scale a DPI mismatch suggests for the selected output, or
+    /// for the first flagged output when the selected one isn't flagged.
+    /// Returns the mismatch it acted on.
+    pub fn stage_suggested_scale(&mut self) -> Option<DpiMismatch> {
+        let mismatches = self.dpi_mismatches();
+        let selected = self.selected_output().map(|o| o.name.clone());
+        let mismatch = mismatches
+            .iter()
+            .find(|m| Some(&m.output) == selected.as_ref())
+            .or_else(|| mismatches.first())?
+            .clone();
+        let current = self.outputs.iter().find(|o| o.name == mismatch.output)?.scale;
+        let staged = Some(mismatch.suggested_scale).filter(|s| (s - current).abs() > 1e-6);
+        self.update_change(&mismatch.output, |change| change.scale = staged);
+        Some(mismatch)
+    }
+
+    /// Stage the next transform for an output; cycling back to its current
+    /// one drops the change
+    pub fn cycle_transform(&mut self, name: &str) {
+        let Some(output) = self.outputs.iter().find(|o| o.name == name) else {
+            return;
+        };
+        let current = output.transform;
+        let next = self.pending_changes.get(name).and_then(|c| c.transform).unwrap_or(current).next();
+        self.update_change(name, |change| change.transform = Some(next).filter(|&t| t != current));
+    }
+
+    /// Stage turning variable refresh rate on or off
+    pub fn toggle_vrr(&mut self, name: &str) {
+        let Some(output) = self.outputs.iter().find(|o| o.name == name) else {
+            return;
+        };
+        let current = output.vrr;
+        let vrr = !self.pending_changes.get(name).and_then(|c| c.vrr).unwrap_or(current);
+        self.update_change(name, |change| change.vrr = Some(vrr).filter(|&v| v != current));
+    }
+
+    /// Stage focusing the output when niri starts, or stop doing so. niri
+    /// picks the first such output in the config, so turning it on for one
+    /// output turns it off for the others.
+    pub fn toggle_focus_at_startup(&mut self, name: &str) {
+        let focused = |view_model: &Self, output: &OutputState| {
+            view_model
+                .pending_changes
+                .get(&output.name)
+                .and_then(|c| c.focus_at_startup)
+                .unwrap_or(output.focus_at_startup)
+        };
+        let Some(output) = self.outputs.iter().find(|o| o.name == name) else {
+            return;
+        };
+        let focus = !focused(self, output);
+        let others: Vec<(String, bool)> = self
+            .outputs
+            .iter()
+            .filter(|o| focus && o.name != name && focused(self, o))
+            .map(|o| (o.name.clone(), o.focus_at_startup))
+            .collect();
+        let current = output.focus_at_startup;
+        self.update_change(name, |change| change.focus_at_startup = Some(focus).filter(|&f| f != current));
+        for (other, current) in others {
+            self.update_change(&other, |change| change.focus_at_startup = Some(false).filter(|&f| f != current));
+        }
+    }
+
+    /// Stage turning the output off or back on. Turning off the last
+    /// output that is on is refused, since it would leave no screen to
+    /// turn it back on from.
+    pub fn toggle_enabled(&mut self, name: &str) -> Result<(), String> {
+        let Some(output) = self.outputs.iter().find(|o| o.name == name) else {
+            return Ok(());
+        };
+        let current = output.enabled;
+        let enabled = !self.pending_changes.get(name).and_then(|c| c.enabled).unwrap_or(current);
+        if !enabled && self.all_off_without(name) {
+            return Err(last_output_on(name));
+        }
+        self.update_change(name, |change| change.enabled = Some(enabled).filter(|&e| e != current));
+        Ok(())
+    }
+
+    /// Stage `changes` on top of the staged ones, field by field. Refused,
+    /// leaving the staged changes as they were, when it would turn every
+    /// output off.
+    pub fn stage_changes(&mut self, changes: HashMap<String, OutputChange>) -> Result<(), String> {
+        let before = self.pending_changes.clone();
+        let mut turned_off: Vec<String> =
+            changes.iter().filter(|(_, c)| c.enabled == Some(false)).map(|(name, _)| name.clone()).collect();
+        turned_off.sort();
+        for (name, change) in changes {
+            self.update_change(&name, |staged| staged.merge(change));
+        }
+        if self.all_off() {
+            self.pending_changes = before;
+            return Err(last_output_on(turned_off.first().map_or("", String::as_str)));
+        }
+        Ok(())
+    }
+
+    /// Whether the staged changes leave every output off
+    pub fn all_off(&self) -> bool {
+        !self.outputs.is_empty() && self.all_off_without("")
+    }
+
+    /// Whether no output other than `name` is on once staged
+    fn all_off_without(&self, name: &str) -> bool {
+        !self
+            .outputs
+            .iter()
+            .filter(|o| o.name != name)
+            .filter_map(|o| self.staged_output(&o.name))
+            .any(|o| o.enabled)
+    }
+
+    /// Outputs with a staged position that niri reports as off and that
+    /// aren't staged to be turned on, so the new position won't show
+    pub fn positions_on_disabled(&self) -> Vec<String> {
+        self.outputs
+            .iter()
+            .filter(|output| {
+                let change = self.pending_changes.get(&output.name);
+                !output.enabled
+                    && change.is_some_and(|c| c.position.is_some())
+                    && change.and_then(|c| c.enabled) != Some(true)
+            })
+            .map(|output| output.name.clone())
+            .collect()
+    }
+
+    /// Stage turning the outputs on
+    pub fn stage_enabled(&mut self, names: &[String]) {
+        for name in names {
+            if self.outputs.iter().any(|o| &o.name == name && !o.enabled) {
+                self.update_change(name, |change| change.enabled = Some(true));
+            }
+        }
+    }
+
+    /// Stage the output's background and backdrop colors; an empty value
+    /// removes the node
+    pub fn stage_colors(&mut self, name: &str, background: &str, backdrop: &str) {
+        let Some(output) = self.outputs.iter().find(|o| o.name == name) else {
+            return;
+        };
+        let differs = |value: &str, current: &Option<String>| {
+            let value = value.trim();
+            (value != current.as_deref().unwrap_or_default()).then(|| value.to_string())
+        };
+        let background = differs(background, &output.background_color);
+        let backdrop = differs(backdrop, &output.backdrop_color);
+        self.update_change(name, |change| {
+            change.background_color = background;
+            change.backdrop_color = backdrop;
+        });
+    }
+
+    /// Whether the output has any staged change
+    pub fn is_modified(&self, name: &str) -> bool {
+        self.pending_changes.contains_key(name)
+    }
+
+    /// The selected output, None while the search hides it so nothing
+    /// acts on an output the list doesn't show
     pub fn selected_output(&self) -> Option<&OutputState> {
-        self.outputs.get(self.selected_index)
+        self.outputs.get(self.list.selected_index).filter(|output| self.is_visible(output))
     }
 
     #[allow(dead_code)] // For future features
     pub fn selected_output_mut(&mut self) -> Option<&mut OutputState> {
-        self.outputs.get_mut(self.selected_index)
+        let query = self.search_query.clone();
+        self.outputs
+            .get_mut(self.list.selected_index)
+            .filter(|output| query.is_empty() || output.matches_search(&query))
+    }
+
+    /// An output as it will be once its staged change is saved, with the
+    /// logical size its staged mode, scale and transform give it
+    pub fn staged_output(&self, name: &str) -> Option<OutputState> {
+        let mut staged = self.outputs.iter().find(|o| o.name == name)?.clone();
+        if let Some(change) = self.pending_changes.get(name) {
+            staged.apply_change(change);
+        }
+        Some(staged)
     }
 
     pub fn get_display_position(&self, name: &str) -> Option<Position> {
-        self.pending_changes.get(name).copied().or_else(|| {
+        self.pending_position(name).or_else(|| {
             self.outputs
                 .iter()
                 .find(|o| o.name == name)
@@ -140,27 +1090,913 @@ impl OutputViewModel {
         !self.pending_changes.is_empty()
     }
 
+    /// Where each enabled output is shown, staged positions included
+    pub fn display_positions(&self) -> Vec<(String, Position)> {
+        self.outputs
+            .iter()
+            .filter(|o| o.enabled)
+            .map(|o| (o.name.clone(), self.get_display_position(&o.name).unwrap_or(o.position)))
+            .collect()
+    }
+
+    /// `DP-1: (100,501)→(0,451)` for each output shown somewhere else than
+    /// in `before`, to report what an arrange or normalize did
+    pub fn moves_since(&self, before: &[(String, Position)]) -> Vec<String> {
+        self.display_positions()
+            .into_iter()
+            .filter_map(|(name, to)| {
+                let (_, from) = before.iter().find(|(n, _)| *n == name)?;
+                (*from != to).then(|| format!("{name}: ({},{})→({},{})", from.x, from.y, to.x, to.y))
+            })
+            .collect()
+    }
+
     pub fn apply_pending_change(&mut self, name: &str, position: Position) {
-        self.pending_changes.insert(name.to_string(), position);
+        self.pending_changes.entry(name.to_string()).or_default().position = Some(position);
+    }
+
+    /// Stage a position to the right of the rightmost monitor for every output
+    /// that has no config entry and overlaps another output. Returns the
+    /// names of the outputs that were moved.
+    pub fn stage_new_output_positions(&mut self) -> Vec<String> {
+        let mut staged = Vec::new();
+        for i in 0..self.outputs.len() {
+            let output = &self.outputs[i];
+            if output.configured
+                || !output.enabled
+                || self.pending_position(&output.name).is_some()
+                || !self.overlaps_other(i)
+            {
+                continue;
+            }
+
+            // Rightmost edge among the other enabled outputs, top-aligned with that monitor
+            let target = self
+                .outputs
+                .iter()
+                .enumerate()
+                .filter(|(j, o)| *j != i && o.enabled)
+                .map(|(_, o)| {
+                    let pos = self.get_display_position(&o.name).unwrap_or(o.position);
+                    Position::new(pos.x + o.logical_size.width as i32, pos.y)
+                })
+                .max_by_key(|p| p.x);
+
+            if let Some(position) = target {
+                let name = self.outputs[i].name.clone();
+                self.apply_pending_change(&name, position);
+                staged.push(name);
+            }
+        }
+        staged
+    }
+
+    /// Stage positions lining up every enabled output from the origin, keeping
+    /// their current left-to-right (or top-to-bottom) order
+    pub fn arrange(&mut self, arrangement: Arrangement) {
+        let mut placed: Vec<(String, Position, Size)> = self
+            .outputs
+            .iter()
+            .filter(|o| o.enabled)
+            .map(|o| {
+                let pos = self.get_display_position(&o.name).unwrap_or(o.position);
+                (o.name.clone(), pos, o.logical_size)
+            })
+            .collect();
+        match arrangement {
+            Arrangement::Row => placed.sort_by_key(|(_, pos, _)| (pos.x, pos.y)),
+            Arrangement::Column => placed.sort_by_key(|(_, pos, _)| (pos.y, pos.x)),
+        }
+
+        let mut offset = 0;
+        for (name, _, size) in placed {
+            let position = match arrangement {
+                Arrangement::Row => Position::new(offset, 0),
+                Arrangement::Column => Position::new(0, offset),
+            };
+            offset += match arrangement {
+                Arrangement::Row => size.width as i32,
+                Arrangement::Column => size.height as i32,
+            };
+            self.apply_pending_change(&name, position);
+        }
+    }
+
+    /// Whether the output at `index` overlaps any other enabled output
+    fn overlaps_other(&self, index: usize) -> bool {
+        let output = &self.outputs[index];
+        let a = self.get_display_position(&output.name).unwrap_or(output.position);
+        let (aw, ah) = (output.logical_size.width as i32, output.logical_size.height as i32);
+        self.outputs.iter().enumerate().any(|(j, other)| {
+            if j == index || !other.enabled {
+                return false;
+            }
+            let b = self.get_display_position(&other.name).unwrap_or(other.position);
+            let (bw, bh) = (other.logical_size.width as i32, other.logical_size.height as i32);
+            a.x < b.x + bw && b.x < a.x + aw && a.y < b.y + bh && b.y < a.y + ah
+        })
+    }
+
+    /// Pairs of touching enabled outputs whose effective densities differ by
+    /// more than `DPI_MISMATCH_RATIO`. Outputs that don't report their
+    /// physical size are skipped.
+    pub fn dpi_mismatches(&self) -> Vec<DpiMismatch> {
+        let rect = |o: &OutputState| {
+            (o.position.x, o.position.y, o.position.x + o.logical_size.width as i32, o.position.y + o.logical_size.height as i32)
+        };
+        let enabled: Vec<OutputState> = self
+            .outputs
+            .iter()
+            .filter_map(|o| self.staged_output(&o.name))
+            .filter(|o| o.enabled)
+            .collect();
+
+        let mut mismatches = Vec::new();
+        for (i, a) in enabled.iter().enumerate() {
+            for b in &enabled[i + 1..] {
+                let (Some(a_ppi), Some(b_ppi)) = (a.ppi(), b.ppi()) else {
+                    continue;
+                };
+                let (ax0, ay0, ax1, ay1) = rect(a);
+                let (bx0, by0, bx1, by1) = rect(b);
+                let side_by_side = (ax1 == bx0 || bx1 == ax0) && ay0 < by1 && by0 < ay1;
+                let stacked = (ay1 == by0 || by1 == ay0) && ax0 < bx1 && bx0 < ax1;
+                if !side_by_side && !stacked {
+                    continue;
+                }
+
+                let (a_eff, b_eff) = (a_ppi / a.scale, b_ppi / b.scale);
+                if a_eff.max(b_eff) / a_eff.min(b_eff) <= DPI_MISMATCH_RATIO {
+                    continue;
+                }
+                let (dense, dense_ppi, dense_eff, other, other_eff) = if a_eff > b_eff {
+                    (a, a_ppi, a_eff, b, b_eff)
+                } else {
+                    (b, b_ppi, b_eff, a, a_eff)
+                };
+                // Round to a step niri's fractional scaling represents well
+                let suggested_scale = ((dense_ppi / other_eff) * 4.0).round() / 4.0;
+                mismatches.push(DpiMismatch {
+                    output: dense.name.clone(),
+                    neighbour: other.name.clone(),
+                    effective_ppi: dense_eff,
+                    neighbour_effective_ppi: other_eff,
+                    suggested_scale: suggested_scale.max(1.0),
+                });
+            }
+        }
+        mismatches
+    }
+
+    /// Overlaps and gaps in the layout with every staged change applied,
+    /// including scale and mode changes that resize an output. Gaps are
+    /// reported for outputs outside the largest group of touching outputs.
+    /// With the layout settings, overlaps are also checked against the area
+    /// windows can reach inside struts, gaps and the focus ring.
+    pub fn layout_issues(&self, layout: Option<&AppearanceSettings>) -> Vec<LayoutIssue> {
+        // Name, left, top, right, bottom
+        type Rect<'a> = (&'a str, i64, i64, i64, i64);
+        let rects: Vec<Rect> = self
+            .outputs
+            .iter()
+            .filter_map(|output| {
+                let staged = self.staged_output(&output.name)?;
+                let (x, y) = (staged.position.x as i64, staged.position.y as i64);
+                let size = staged.logical_size;
+                staged
+                    .enabled
+                    .then_some((output.name.as_str(), x, y, x + size.width as i64, y + size.height as i64))
+            })
+            .collect();
+        // Separation along each axis, 0 where the ranges meet or overlap
+        let separation = |a: &Rect, b: &Rect| {
+            ((b.1 - a.3).max(a.1 - b.3).max(0), (b.2 - a.4).max(a.2 - b.4).max(0))
+        };
+        let overlaps = |a: &Rect, b: &Rect| {
+            a.1 < b.3 && b.1 < a.3 && a.2 < b.4 && b.2 < a.4
+        };
+        // Same check on each output's usable area, inside struts, gaps and the focus ring
+        let (left, top, right, bottom) = layout.map(AppearanceSettings::window_insets).unwrap_or_default();
+        let (left, top, right, bottom) = (left as i64, top as i64, right as i64, bottom as i64);
+        let windows_overlap = |a: &Rect, b: &Rect| {
+            a.1 + left < b.3 - right && b.1 + left < a.3 - right && a.2 + top < b.4 - bottom && b.2 + top < a.4 - bottom
+        };
+        // Sharing a stretch of edge, not just a corner
+        let touches = |a: &Rect, b: &Rect| {
+            let side_by_side = (a.3 == b.1 || b.3 == a.1) && a.2 < b.4 && b.2 < a.4;
+            let stacked = (a.4 == b.2 || b.4 == a.2) && a.1 < b.3 && b.1 < a.3;
+            side_by_side || stacked || overlaps(a, b)
+        };
+
+        let mut issues = Vec::new();
+        let mut group: Vec<usize> = (0..rects.len()).collect();
+        for i in 0..rects.len() {
+            for j in i + 1..rects.len() {
+                if overlaps(&rects[i], &rects[j]) {
+                    issues.push(LayoutIssue::Overlap {
+                        output: rects[j].0.to_string(),
+                        other: rects[i].0.to_string(),
+                        windows: windows_overlap(&rects[i], &rects[j]),
+                    });
+                }
+                if touches(&rects[i], &rects[j]) {
+                    // Merge j's group into i's
+                    let (from, to) = (group[j], group[i]);
+                    group.iter_mut().filter(|g| **g == from).for_each(|g| *g = to);
+                }
+            }
+        }
+
+        let largest = (0..rects.len()).max_by_key(|&g| (group.iter().filter(|&&o| o == g).count(), std::cmp::Reverse(g)));
+        if let Some(largest) = largest {
+            for (i, rect) in rects.iter().enumerate().filter(|&(i, _)| group[i] != largest) {
+                let nearest = rects
+                    .iter()
+                    .enumerate()
+                    .filter(|&(j, _)| group[j] == largest)
+                    .map(|(_, other)| {
+                        let (dx, dy) = separation(rect, other);
+                        (dx + dy, other.0)
+                    })
+                    .min();
+                if let Some((distance, other)) = nearest {
+                    issues.push(LayoutIssue::Gap {
+                        output: rects[i].0.to_string(),
+                        other: other.to_string(),
+                        distance: distance as u32,
+                    });
+                }
+            }
+        }
+        issues
     }
 
     pub fn clear_pending_changes(&mut self) {
         self.pending_changes.clear();
     }
 
-    pub fn select_next(&mut self) {
-        if !self.outputs.is_empty() {
-            self.selected_index = (self.selected_index + 1) % self.outputs.len();
+    /// Whether the output passes the search filter
+    pub fn is_visible(&self, output: &OutputState) -> bool {
+        self.search_query.is_empty() || output.matches_search(&self.search_query)
+    }
+
+    /// Indices of the outputs passing the search filter, in list order
+    pub fn visible_indices(&self) -> Vec<usize> {
+        (0..self.outputs.len())
+            .filter(|&idx| self.is_visible(&self.outputs[idx]))
+            .collect()
+    }
+
+    /// Set the search query, moving the selection to the first match when
+    /// the selected output no longer matches
+    pub fn set_search(&mut self, query: String) {
+        self.search_query = query;
+        let selected_visible = self
+            .outputs
+            .get(self.list.selected_index)
+            .is_some_and(|output| self.is_visible(output));
+        if !selected_visible {
+            if let Some(&first) = self.visible_indices().first() {
+                self.list.selected_index = first;
+            }
         }
     }
 
+    /// Clear search, keeping the selection where it is
+    pub fn clear_search(&mut self) {
+        self.search_query.clear();
+        self.search_mode = false;
+    }
+
+    pub fn select_next(&mut self) {
+        let visible = self.visible_indices();
+        self.list.select_next_in(&visible);
+    }
+
     pub fn select_prev(&mut self) {
-        if !self.outputs.is_empty() {
-            self.selected_index = if self.selected_index == 0 {
-                self.outputs.len() - 1
-            } else {
-                self.selected_index - 1
-            };
+        let visible = self.visible_indices();
+        self.list.select_prev_in(&visible);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn output_with_modes(modes: &[(u32, u32, f64, bool)]) -> OutputState {
+        OutputState {
+            name: "DP-1".to_string(),
+            modes: modes
+                .iter()
+                .map(|&(width, height, refresh_rate, is_preferred)| OutputMode {
+                    width,
+                    height,
+                    refresh_rate,
+                    is_preferred,
+                })
+                .collect(),
+            current_mode_index: Some(0),
+            scale: 1.0,
+            transform: OutputTransform::Normal,
+            position: Position::default(),
+            logical_size: Size::default(),
+            physical_size: Size::default(),
+            size_mm: None,
+            enabled: true,
+            vrr: false,
+            connected: true,
+            configured: false,
+            make: String::new(),
+            model: String::new(),
+            source: None,
+            duplicate_sources: Vec::new(),
+            background_color: None,
+            backdrop_color: None,
+            focus_at_startup: false,
+        }
+    }
+
+    #[test]
+    fn test_ppi_and_fractional_logical_size() {
+        let mut output = output_with_modes(&[(2560, 1440, 60.0, true)]);
+        assert_eq!(output.ppi(), None);
+        output.size_mm = Some(Size::new(597, 336));
+        assert_eq!(output.ppi().map(|p| p.round()), Some(109.0));
+
+        assert!(!output.has_fractional_logical_size());
+        output.scale = 1.5;
+        assert!(output.has_fractional_logical_size());
+        output.scale = 1.25;
+        assert!(!output.has_fractional_logical_size());
+    }
+
+    #[test]
+    fn test_stage_new_output_positions() {
+        let mut laptop = output_with_modes(&[]);
+        laptop.name = "eDP-1".to_string();
+        laptop.logical_size = Size::new(1920, 1200);
+        laptop.configured = true;
+
+        let mut external = output_with_modes(&[]);
+        external.name = "DP-1".to_string();
+        external.logical_size = Size::new(2560, 1440);
+        external.position = Position::new(1920, 200);
+        external.configured = true;
+
+        let mut new_output = output_with_modes(&[]);
+        new_output.name = "HDMI-A-1".to_string();
+        new_output.logical_size = Size::new(1920, 1080);
+
+        let mut view_model = OutputViewModel {
+            outputs: vec![laptop, external, new_output],
+            ..Default::default()
+        };
+        assert_eq!(view_model.stage_new_output_positions(), vec!["HDMI-A-1"]);
+        assert_eq!(view_model.pending_position("HDMI-A-1"), Some(Position::new(4480, 200)));
+
+        // Already non-overlapping, nothing more to do
+        assert!(view_model.stage_new_output_positions().is_empty());
+    }
+
+    #[test]
+    fn test_dpi_mismatches() {
+        let mut laptop = output_with_modes(&[(2880, 1800, 60.0, true)]);
+        laptop.name = "eDP-1".to_string();
+        laptop.size_mm = Some(Size::new(302, 189));
+        laptop.scale = 1.5;
+        laptop.logical_size = Size::new(1920, 1200);
+
+        let mut external = output_with_modes(&[(1920, 1080, 60.0, true)]);
+        external.name = "HDMI-A-1".to_string();
+        external.size_mm = Some(Size::new(531, 299));
+        external.logical_size = Size::new(1920, 1080);
+        external.position = Position::new(1920, 0);
+
+        let mut view_model = OutputViewModel {
+            outputs: vec![laptop, external],
+            ..Default::default()
+        };
+        let mismatches = view_model.dpi_mismatches();
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].output, "eDP-1");
+        assert_eq!(mismatches[0].neighbour, "HDMI-A-1");
+        assert_eq!(mismatches[0].suggested_scale, 2.75);
+        assert!(mismatches[0].message().starts_with("cursor will jump sizes crossing eDP-1→HDMI-A-1"));
+
+        // Not touching: the cursor can't cross directly
+        view_model.apply_pending_change("HDMI-A-1", Position::new(2000, 0));
+        assert!(view_model.dpi_mismatches().is_empty());
+
+        // Staging the suggestion targets the flagged output
+        view_model.clear_pending_changes();
+        view_model.list.selected_index = 1;
+        let staged = view_model.stage_suggested_scale().unwrap();
+        assert_eq!(staged.output, "eDP-1");
+        assert_eq!(view_model.pending_scale("eDP-1"), Some(2.75));
+        assert_eq!(view_model.pending_scale("HDMI-A-1"), None);
+        // The staged scale shrinks the laptop away from the external monitor
+        assert_eq!(view_model.staged_output("eDP-1").unwrap().logical_size, Size::new(1047, 655));
+        assert!(view_model.dpi_mismatches().is_empty());
+
+        // Close enough densities are fine
+        view_model.clear_pending_changes();
+        view_model.outputs[0].scale = 2.5;
+        assert!(view_model.dpi_mismatches().is_empty());
+    }
+
+    #[test]
+    fn test_revert_changes() {
+        let mut output = output_with_modes(&[(2560, 1440, 59.951, true), (1920, 1080, 60.0, false)]);
+        output.name = "DP-1".to_string();
+        output.current_mode_index = Some(0);
+        output.position = Position::new(1920, 0);
+        let mut view_model = OutputViewModel {
+            outputs: vec![output],
+            ..Default::default()
+        };
+        view_model.apply_pending_change("DP-1", Position::new(0, 0));
+        view_model.toggle_vrr("DP-1");
+        let mode = view_model.outputs[0].modes[1].clone();
+        view_model.stage_mode("DP-1", mode);
+
+        let reverts = view_model.revert_changes();
+        let revert = &reverts["DP-1"];
+        assert_eq!(revert.position, Some(Position::new(1920, 0)));
+        assert_eq!(revert.mode.as_ref(), view_model.outputs[0].modes.first());
+        assert_eq!(revert.vrr, Some(false));
+        assert_eq!((revert.scale, revert.enabled), (None, None));
+    }
+
+    #[test]
+    fn test_stage_changes() {
+        let mut laptop = output_with_modes(&[]);
+        laptop.name = "eDP-1".to_string();
+        let mut view_model = OutputViewModel {
+            outputs: vec![output_with_modes(&[]), laptop],
+            ..Default::default()
+        };
+        view_model.toggle_vrr("DP-1");
+
+        // Fields the changes leave alone stay staged
+        let mut changes = HashMap::new();
+        changes.insert("DP-1".to_string(), OutputChange { position: Some(Position::new(0, 0)), ..Default::default() });
+        view_model.stage_changes(changes).unwrap();
+        let staged = &view_model.pending_changes["DP-1"];
+        assert_eq!((staged.position, staged.vrr), (Some(Position::new(0, 0)), Some(true)));
+
+        // Turning everything off is refused and stages nothing
+        view_model.toggle_enabled("DP-1").unwrap();
+        let before = view_model.pending_changes.clone();
+        let mut changes = HashMap::new();
+        changes.insert("eDP-1".to_string(), OutputChange { enabled: Some(false), ..Default::default() });
+        let err = view_model.stage_changes(changes).unwrap_err();
+        assert_eq!(err, "eDP-1 is the last output on; turning it off would leave no screen");
+        assert_eq!(view_model.pending_changes, before);
+    }
+
+    #[test]
+    fn test_revert_changes_over_earlier_preview() {
+        let mut output = output_with_modes(&[(2560, 1440, 59.951, true)]);
+        output.position = Position::new(1920, 0);
+        let mut view_model = OutputViewModel {
+            outputs: vec![output],
+            ..Default::default()
+        };
+        view_model.apply_pending_change("DP-1", Position::new(0, 0));
+        let first = view_model.revert_changes();
+
+        // A refresh while the first preview runs reports its position
+        view_model.outputs[0].position = Position::new(0, 0);
+        view_model.apply_pending_change("DP-1", Position::new(100, 0));
+        view_model.step_scale("DP-1", 1);
+        let reverts = view_model.revert_changes_over(first);
+        let revert = &reverts["DP-1"];
+        assert_eq!(revert.position, Some(Position::new(1920, 0)));
+        assert_eq!(revert.scale, Some(1.0));
+    }
+
+    #[test]
+    fn test_position_editor() {
+        let mut editor = OutputPositionEditor::new("DP-1", Position::new(1920, 0));
+        assert_eq!(editor.position(), Ok(Position::new(1920, 0)));
+
+        editor.focus_next();
+        editor.field_mut().delete_char();
+        for c in "-1080/2".chars() {
+            editor.field_mut().insert_char(c);
+        }
+        assert_eq!(editor.position(), Ok(Position::new(1920, -540)));
+
+        editor.field_mut().insert_char('x');
+        assert!(editor.position().unwrap_err().starts_with("Y: "));
+    }
+
+    #[test]
+    fn test_stage_colors() {
+        let mut output = output_with_modes(&[]);
+        output.background_color = Some("#003300".to_string());
+        let mut view_model = OutputViewModel { outputs: vec![output], ..Default::default() };
+        let name = view_model.outputs[0].name.clone();
+
+        view_model.stage_colors(&name, " #003300 ", "");
+        assert!(!view_model.is_modified(&name));
+
+        view_model.stage_colors(&name, "", "black");
+        let change = &view_model.pending_changes[&name];
+        assert_eq!((change.background_color.as_deref(), change.backdrop_color.as_deref()), (Some(""), Some("black")));
+        assert_eq!(change.summary("DP-1"), ["remove DP-1 background-color", "set DP-1 backdrop-color to black"]);
+
+        let mut staged = view_model.outputs[0].clone();
+        staged.apply_change(change);
+        let editor = OutputColorEditor::new(&staged);
+        assert_eq!((editor.background(), editor.backdrop()), ("", "black"));
+    }
+
+    #[test]
+    fn test_snap_align() {
+        // A 1080 tall portrait monitor next to a 1440 tall display at y=100
+        assert_eq!(SnapAlign::Start.place(100, 1440, 1080), 100);
+        assert_eq!(SnapAlign::Center.place(100, 1440, 1080), 280);
+        assert_eq!(SnapAlign::End.place(100, 1440, 1080), 460);
+        // Larger than the reference overhangs evenly or on one side
+        assert_eq!(SnapAlign::Center.place(0, 1080, 1440), -180);
+        assert_eq!(SnapAlign::End.place(0, 1080, 1440), -360);
+
+        let alignment = SnapAlignment::default();
+        assert_eq!((alignment.beside, alignment.stacked), (SnapAlign::Start, SnapAlign::Center));
+        assert_eq!(SnapAlign::End.next(), SnapAlign::Start);
+        assert_eq!(SnapAlign::End.beside_name(), "bottom");
+    }
+
+    #[test]
+    fn test_position_moves() {
+        let output = |name: &str, x| {
+            let mut output = output_with_modes(&[]);
+            output.name = name.to_string();
+            output.logical_size = Size::new(1920, 1080);
+            output.position = Position::new(x, 0);
+            output
+        };
+        let outputs = vec![output("A", 0), output("B", 1920), output("C", 3840)];
+        let to = |x| OutputChange { position: Some(Position::new(x, 0)), ..Default::default() };
+        let names = |moves: &[(String, Position)]| moves.iter().map(|(n, p)| format!("{n}@{}", p.x)).collect::<Vec<_>>();
+
+        // Shifting the row left: A has to go before B can take its place
+        let changes = HashMap::from([("A".to_string(), to(-1920)), ("B".to_string(), to(0))]);
+        assert_eq!(names(&position_moves(&outputs, &changes)), ["A@-1920", "B@0"]);
+        let changes = HashMap::from([("B".to_string(), to(0)), ("C".to_string(), to(1920)), ("A".to_string(), to(-1920))]);
+        assert_eq!(names(&position_moves(&outputs, &changes)), ["A@-1920", "B@0", "C@1920"]);
+
+        // Swapping A and C parks A past the far edge first
+        let changes = HashMap::from([("A".to_string(), to(3840)), ("C".to_string(), to(0))]);
+        assert_eq!(names(&position_moves(&outputs, &changes)), ["A@5760", "C@0", "A@3840"]);
+
+        // Unmoved outputs and positions niri already has are left alone
+        let changes = HashMap::from([("B".to_string(), to(1920)), ("C".to_string(), to(0))]);
+        assert_eq!(names(&position_moves(&outputs, &changes)), ["C@0"]);
+    }
+
+    #[test]
+    fn test_upside_down() {
+        let mut transform = OutputTransform::Normal;
+        loop {
+            let flipped = transform.upside_down();
+            assert_ne!(flipped, transform);
+            assert_eq!(flipped.upside_down(), transform);
+            assert_eq!(flipped.is_rotated(), transform.is_rotated());
+            transform = transform.next();
+            if transform == OutputTransform::Normal {
+                break;
+            }
         }
     }
+
+    #[test]
+    fn test_moves_since() {
+        let output = |name: &str, x, y| {
+            let mut output = output_with_modes(&[]);
+            output.name = name.to_string();
+            output.logical_size = Size::new(1920, 1080);
+            output.position = Position::new(x, y);
+            output
+        };
+        let mut view_model = OutputViewModel {
+            outputs: vec![output("DP-1", 100, 501), output("HDMI-A-1", 2020, 50), output("eDP-1", 0, 0)],
+            ..Default::default()
+        };
+        view_model.outputs[2].enabled = false;
+        let before = view_model.display_positions();
+        assert_eq!(before.len(), 2);
+        view_model.apply_pending_change("DP-1", Position::new(0, 451));
+        view_model.apply_pending_change("HDMI-A-1", Position::new(1920, 0));
+        let after = view_model.display_positions();
+        assert_eq!(view_model.moves_since(&before), ["DP-1: (100,501)→(0,451)", "HDMI-A-1: (2020,50)→(1920,0)"]);
+        assert!(view_model.moves_since(&after).is_empty());
+    }
+
+    #[test]
+    fn test_layout_issues() {
+        let mut laptop = output_with_modes(&[]);
+        laptop.name = "eDP-1".to_string();
+        laptop.logical_size = Size::new(1920, 1200);
+
+        let mut external = output_with_modes(&[]);
+        external.name = "DP-1".to_string();
+        external.logical_size = Size::new(2560, 1440);
+        external.position = Position::new(1920, 0);
+
+        let mut tv = output_with_modes(&[]);
+        tv.name = "HDMI-A-1".to_string();
+        tv.logical_size = Size::new(1600, 900);
+        tv.position = Position::new(0, 1200);
+
+        let mut view_model = OutputViewModel {
+            outputs: vec![laptop, external, tv],
+            ..Default::default()
+        };
+        assert!(view_model.layout_issues(None).is_empty());
+
+        view_model.apply_pending_change("DP-1", Position::new(1800, 0));
+        let issues = view_model.layout_issues(None);
+        assert_eq!(
+            issues,
+            vec![LayoutIssue::Overlap { output: "DP-1".to_string(), other: "eDP-1".to_string(), windows: true }]
+        );
+        assert!(issues[0].involves("eDP-1") && !issues[0].involves("HDMI-A-1"));
+
+        // A 120px overlap stays within two 64px struts, so windows don't meet
+        let mut layout = AppearanceSettings { gaps: 16, ..Default::default() };
+        layout.struts.left = Some(64);
+        layout.struts.right = Some(64);
+        assert!(matches!(view_model.layout_issues(Some(&layout))[0], LayoutIssue::Overlap { windows: false, .. }));
+        layout.struts.right = None;
+        assert!(matches!(view_model.layout_issues(Some(&layout))[0], LayoutIssue::Overlap { windows: true, .. }));
+
+        view_model.apply_pending_change("DP-1", Position::new(2000, 0));
+        assert_eq!(
+            view_model.layout_issues(None),
+            vec![LayoutIssue::Gap { output: "DP-1".to_string(), other: "eDP-1".to_string(), distance: 80 }]
+        );
+
+        // Corner contact doesn't let the cursor across
+        view_model.apply_pending_change("DP-1", Position::new(1920, -1440));
+        assert!(view_model.layout_issues(None)[0].message().contains("only touches eDP-1 at a corner"));
+
+        // Turning off the laptop leaves nothing bridging the other two
+        view_model.apply_pending_change("DP-1", Position::new(1920, 0));
+        view_model.toggle_enabled("eDP-1").unwrap();
+        assert_eq!(
+            view_model.layout_issues(None),
+            vec![LayoutIssue::Gap { output: "HDMI-A-1".to_string(), other: "DP-1".to_string(), distance: 320 }]
+        );
+    }
+
+    #[test]
+    fn test_arrange() {
+        let mut left = output_with_modes(&[]);
+        left.name = "DP-1".to_string();
+        left.logical_size = Size::new(2560, 1440);
+        left.position = Position::new(-2560, 300);
+
+        let mut right = output_with_modes(&[]);
+        right.name = "eDP-1".to_string();
+        right.logical_size = Size::new(1920, 1200);
+
+        let mut off = output_with_modes(&[]);
+        off.name = "HDMI-A-1".to_string();
+        off.enabled = false;
+
+        let mut view_model = OutputViewModel {
+            outputs: vec![right, off, left],
+            ..Default::default()
+        };
+        view_model.arrange(Arrangement::Row);
+        assert_eq!(view_model.pending_position("DP-1"), Some(Position::new(0, 0)));
+        assert_eq!(view_model.pending_position("eDP-1"), Some(Position::new(2560, 0)));
+        assert_eq!(view_model.pending_position("HDMI-A-1"), None);
+
+        view_model.arrange(Arrangement::Column);
+        assert_eq!(view_model.pending_position("DP-1"), Some(Position::new(0, 0)));
+        assert_eq!(view_model.pending_position("eDP-1"), Some(Position::new(0, 1440)));
+    }
+
+    #[test]
+    fn test_mode_browser_refresh_filter() {
+        let output = output_with_modes(&[
+            (2560, 1440, 143.973, true),
+            (2560, 1440, 59.951, false),
+            (1920, 1080, 144.0, false),
+            (1920, 1080, 60.0, false),
+        ]);
+        let mut browser = ModeBrowser::default();
+        assert_eq!(ModeBrowser::refresh_rates(&output), vec![144, 60]);
+        assert_eq!(browser.visible_modes(&output).len(), 4);
+
+        browser.cycle_refresh_filter(&output);
+        assert_eq!(browser.refresh_filter, Some(144));
+        browser.select_next(&output);
+        assert_eq!(browser.selected_mode_index(&output), Some(2));
+        // Wraps within the filtered modes
+        browser.select_next(&output);
+        assert_eq!(browser.selected_mode_index(&output), Some(0));
+        browser.select_prev(&output);
+        assert_eq!(browser.selected_mode_index(&output), Some(2));
+
+        browser.cycle_refresh_filter(&output);
+        assert_eq!(browser.selected_mode_index(&output), Some(1));
+        browser.cycle_refresh_filter(&output);
+        assert_eq!(browser.refresh_filter, None);
+    }
+
+    #[test]
+    fn test_stage_mode_and_scale() {
+        let mut output = output_with_modes(&[(2560, 1440, 59.951, true), (2560, 1440, 143.912, false)]);
+        output.current_mode_index = Some(0);
+        output.scale = 1.0;
+        let mut view_model = OutputViewModel {
+            outputs: vec![output],
+            ..Default::default()
+        };
+
+        let fast = view_model.outputs[0].modes[1].clone();
+        view_model.stage_mode("DP-1", fast.clone());
+        view_model.step_scale("DP-1", 2);
+        assert!(view_model.is_modified("DP-1"));
+        assert_eq!(
+            view_model.change_summary(),
+            vec!["set DP-1 mode to 2560x1440@143.912", "set DP-1 scale to 1.5"]
+        );
+
+        // Going back to the current mode and scale is not a change
+        let current = view_model.outputs[0].modes[0].clone();
+        view_model.stage_mode("DP-1", current);
+        view_model.step_scale("DP-1", -2);
+        assert!(!view_model.has_pending_changes());
+
+        // A fractional scale outside the steps moves to the neighbouring step
+        view_model.outputs[0].scale = 1.6;
+        view_model.step_scale("DP-1", -1);
+        assert_eq!(view_model.pending_scale("DP-1"), Some(1.5));
+        view_model.clear_pending_changes();
+
+        // After a reload the mode must still be offered
+        let mut changes = HashMap::new();
+        let change = OutputChange { mode: Some(fast), scale: Some(2.0), ..Default::default() };
+        changes.insert("DP-1".to_string(), change);
+        changes.insert("HDMI-A-1".to_string(), OutputChange { scale: Some(2.0), ..Default::default() });
+        view_model.outputs[0].modes.truncate(1);
+        assert_eq!(
+            view_model.rebase_changes(changes),
+            vec!["mode DP-1: 2560x1440@143.912 no longer offered", "set HDMI-A-1 scale to 2: output no longer present"]
+        );
+        assert_eq!(view_model.pending_mode("DP-1"), None);
+        assert_eq!(view_model.pending_scale("DP-1"), Some(2.0));
+
+        // Saving takes the change on, logical size included
+        let change = view_model.pending_changes["DP-1"].clone();
+        view_model.outputs[0].apply_change(&change);
+        assert_eq!(view_model.outputs[0].logical_size, Size::new(1280, 720));
+    }
+
+    #[test]
+    fn test_live_layout() {
+        let mut output = output_with_modes(&[(2560, 1440, 59.951, true), (1920, 1080, 60.0, false)]);
+        output.position = Position::new(1920, 0);
+        output.scale = 1.25;
+        output.transform = OutputTransform::Rotate90;
+
+        let change = output.live_layout();
+        assert_eq!(
+            change.summary("DP-1"),
+            vec!["move DP-1 to 1920,0", "set DP-1 mode to 2560x1440@59.951", "set DP-1 scale to 1.25", "set DP-1 transform to 90"]
+        );
+    }
+
+    #[test]
+    fn test_focus_at_startup() {
+        let mut laptop = output_with_modes(&[]);
+        laptop.name = "eDP-1".to_string();
+        laptop.focus_at_startup = true;
+        let mut view_model = OutputViewModel {
+            outputs: vec![laptop, output_with_modes(&[])],
+            ..Default::default()
+        };
+
+        // Picking another output takes the focus from the configured one
+        view_model.toggle_focus_at_startup("DP-1");
+        assert_eq!(
+            view_model.change_summary(),
+            vec!["focus DP-1 at startup", "stop focusing eDP-1 at startup"]
+        );
+
+        // Picking it back drops both changes
+        view_model.toggle_focus_at_startup("eDP-1");
+        assert!(!view_model.has_pending_changes());
+        view_model.toggle_focus_at_startup("eDP-1");
+        assert_eq!(view_model.change_summary(), vec!["stop focusing eDP-1 at startup"]);
+    }
+
+    #[test]
+    fn test_positions_on_disabled() {
+        let mut off = output_with_modes(&[]);
+        off.name = "HDMI-A-1".to_string();
+        off.enabled = false;
+        let mut view_model = OutputViewModel {
+            outputs: vec![output_with_modes(&[]), off],
+            ..Default::default()
+        };
+        view_model.apply_pending_change("DP-1", Position::new(0, 0));
+        view_model.apply_pending_change("HDMI-A-1", Position::new(1920, 0));
+        assert_eq!(view_model.positions_on_disabled(), vec!["HDMI-A-1"]);
+
+        // Staging the output on clears the warning
+        view_model.stage_enabled(&["HDMI-A-1".to_string(), "DP-1".to_string()]);
+        assert!(view_model.positions_on_disabled().is_empty());
+        assert_eq!(view_model.pending_changes["HDMI-A-1"].enabled, Some(true));
+        assert_eq!(view_model.pending_changes["DP-1"].enabled, None);
+    }
+
+    #[test]
+    fn test_stage_transform_vrr_and_power() {
+        let mut output = output_with_modes(&[(2560, 1440, 59.951, true)]);
+        output.physical_size = Size::new(2560, 1440);
+        let mut laptop = output_with_modes(&[]);
+        laptop.name = "eDP-1".to_string();
+        let mut view_model = OutputViewModel {
+            outputs: vec![output, laptop],
+            ..Default::default()
+        };
+
+        view_model.cycle_transform("DP-1");
+        view_model.toggle_vrr("DP-1");
+        view_model.toggle_enabled("DP-1").unwrap();
+        assert_eq!(
+            view_model.change_summary(),
+            vec!["set DP-1 transform to 90", "turn on variable refresh rate on DP-1", "turn DP-1 off"]
+        );
+
+        // The last output on stays on
+        assert!(view_model.toggle_enabled("eDP-1").is_err());
+        assert!(!view_model.all_off());
+
+        // Toggling back and cycling all the way round drop the changes
+        view_model.toggle_vrr("DP-1");
+        view_model.toggle_enabled("DP-1").unwrap();
+        for _ in 0..7 {
+            view_model.cycle_transform("DP-1");
+        }
+        assert!(!view_model.has_pending_changes());
+
+        // A rotated output swaps its logical width and height
+        view_model.cycle_transform("DP-1");
+        let change = view_model.pending_changes["DP-1"].clone();
+        view_model.outputs[0].apply_change(&change);
+        assert_eq!(view_model.outputs[0].logical_size, Size::new(1440, 2560));
+    }
+
+    #[test]
+    fn test_output_search() {
+        let mut laptop = output_with_modes(&[]);
+        laptop.name = "eDP-1".to_string();
+        laptop.make = "BOE".to_string();
+        let mut dell = output_with_modes(&[]);
+        dell.name = "DP-1".to_string();
+        dell.make = "Dell Inc.".to_string();
+        dell.model = "U2720Q".to_string();
+        let mut tv = output_with_modes(&[]);
+        tv.name = "HDMI-A-1".to_string();
+        tv.make = "LG Electronics".to_string();
+
+        let mut view_model = OutputViewModel {
+            outputs: vec![laptop, dell, tv],
+            ..Default::default()
+        };
+        view_model.set_search("u27".to_string());
+        assert_eq!(view_model.visible_indices(), vec![1]);
+        assert_eq!(view_model.list.selected_index, 1);
+
+        // Navigation stays within the matches
+        view_model.set_search("e".to_string());
+        assert_eq!(view_model.visible_indices(), vec![0, 1, 2]);
+        view_model.set_search("hdmi".to_string());
+        assert_eq!(view_model.list.selected_index, 2);
+        view_model.set_search("dp".to_string());
+        assert_eq!(view_model.list.selected_index, 0);
+        view_model.select_next();
+        assert_eq!(view_model.list.selected_index, 1);
+        view_model.select_next();
+        assert_eq!(view_model.list.selected_index, 0);
+        view_model.select_prev();
+        assert_eq!(view_model.list.selected_index, 1);
+
+        // With nothing matching, nothing is selected to move or change
+        view_model.set_search("nope".to_string());
+        assert!(view_model.visible_indices().is_empty());
+        assert!(view_model.selected_output().is_none());
+
+        view_model.clear_search();
+        assert_eq!(view_model.visible_indices().len(), 3);
+        assert_eq!(view_model.list.selected_index, 1);
+        assert_eq!(view_model.selected_output().unwrap().name, "DP-1");
+    }
 }
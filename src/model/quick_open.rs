@@ -0,0 +1,92 @@
+use std::path::{Path, PathBuf};
+
+use super::selectable_list::SelectableList;
+
+/// One config the quick-open dialog offers
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuickOpenEntry {
+    pub path: PathBuf,
+    /// The default config niri itself reads
+    pub live: bool,
+    /// The config being edited right now
+    pub current: bool,
+    pub exists: bool,
+}
+
+/// State for the dialog that switches between the live config and recently
+/// opened alternatives
+#[derive(Debug, Clone, Default)]
+pub struct QuickOpen {
+    pub entries: Vec<QuickOpenEntry>,
+    pub list: SelectableList,
+}
+
+impl QuickOpen {
+    /// The live config first, then `recent` (most recent first), with the
+    /// one after the current config selected so Ctrl+O, Enter toggles
+    /// between the two most recent
+    pub fn new(live: Option<&Path>, recent: &[PathBuf], current: Option<&Path>) -> Self {
+        let mut entries: Vec<QuickOpenEntry> = Vec::new();
+        for (path, is_live) in live.into_iter().map(|p| (p, true)).chain(recent.iter().map(|p| (p.as_path(), false))) {
+            if entries.iter().any(|e| e.path == path) {
+                continue;
+            }
+            entries.push(QuickOpenEntry {
+                path: path.to_path_buf(),
+                live: is_live,
+                current: current == Some(path),
+                exists: path.exists(),
+            });
+        }
+        let selected_index = entries.iter().position(|e| !e.current && e.exists).unwrap_or(0);
+        Self { entries, list: SelectableList { selected_index, ..Default::default() } }
+    }
+
+    pub fn selected(&self) -> Option<&QuickOpenEntry> {
+        self.entries.get(self.list.selected_index)
+    }
+
+    pub fn select_next(&mut self) {
+        self.list.select_next(self.entries.len());
+    }
+
+    pub fn select_prev(&mut self) {
+        self.list.select_prev(self.entries.len());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quick_open_entries() {
+        let dir = std::env::temp_dir().join(format!("nirikiri-quick-open-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let live = dir.join("config.kdl");
+        let test = dir.join("test.kdl");
+        std::fs::write(&live, "").unwrap();
+        std::fs::write(&test, "").unwrap();
+        let missing = dir.join("gone.kdl");
+
+        let recent = vec![test.clone(), live.clone(), missing.clone()];
+        let dialog = QuickOpen::new(Some(&live), &recent, Some(&test));
+        let paths: Vec<_> = dialog.entries.iter().map(|e| e.path.clone()).collect();
+        assert_eq!(paths, vec![live.clone(), test.clone(), missing]);
+        assert!(dialog.entries[0].live && !dialog.entries[1].live);
+        assert!(dialog.entries[1].current);
+        assert!(!dialog.entries[2].exists);
+        assert_eq!(dialog.selected().unwrap().path, live);
+
+        // Editing the live config: the most recent alternative is preselected
+        let mut dialog = QuickOpen::new(Some(&live), &recent, Some(&live));
+        assert_eq!(dialog.selected().unwrap().path, test);
+        dialog.select_next();
+        assert_eq!(dialog.list.selected_index, 2);
+        // Wraps back to the live config
+        dialog.select_next();
+        assert_eq!(dialog.selected().unwrap().path, live);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,170 @@
+/// Selection and scroll position for a list whose items live in a view
+/// model, so it works for lists rebuilt on every frame (filtered bindings,
+/// appearance items under collapsed sections) as well as stored ones
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SelectableList {
+    pub selected_index: usize,
+    pub scroll_offset: usize,
+    /// Rows shown at the last render; one page for `page_down`/`page_up`
+    pub page_size: usize,
+}
+
+impl SelectableList {
+    /// Back to the top, e.g. after the list is filtered
+    pub fn reset(&mut self) {
+        self.selected_index = 0;
+        self.scroll_offset = 0;
+    }
+
+    /// Move down one of `count` items, wrapping to the first
+    pub fn select_next(&mut self, count: usize) {
+        if count > 0 {
+            self.selected_index = (self.selected_index + 1) % count;
+        }
+    }
+
+    /// Move up one of `count` items, wrapping to the last
+    pub fn select_prev(&mut self, count: usize) {
+        if count > 0 {
+            self.selected_index = if self.selected_index == 0 {
+                count - 1
+            } else {
+                (self.selected_index - 1).min(count - 1)
+            };
+        }
+    }
+
+    /// Move down a page, stopping at the last item
+    pub fn page_down(&mut self, count: usize) {
+        if count > 0 {
+            self.selected_index = (self.selected_index + self.page_size.max(1)).min(count - 1);
+        }
+    }
+
+    /// Move up a page, stopping at the first item
+    pub fn page_up(&mut self) {
+        self.selected_index = self.selected_index.saturating_sub(self.page_size.max(1));
+    }
+
+    /// Select `index` if it is one of the `count` items
+    pub fn select(&mut self, index: usize, count: usize) -> bool {
+        let valid = index < count;
+        if valid {
+            self.selected_index = index;
+        }
+        valid
+    }
+
+    /// Keep the selection inside a list that shrank to `count` items
+    pub fn clamp(&mut self, count: usize) {
+        self.selected_index = self.selected_index.min(count.saturating_sub(1));
+    }
+
+    /// Move to the next of `visible`, wrapping, where the selection and
+    /// `visible` index the full list rather than the filtered view
+    pub fn select_next_in(&mut self, visible: &[usize]) {
+        if let Some(&next) = visible
+            .iter()
+            .find(|&&idx| idx > self.selected_index)
+            .or_else(|| visible.first())
+        {
+            self.selected_index = next;
+        }
+    }
+
+    /// Move to the previous of `visible`, wrapping; see `select_next_in`
+    pub fn select_prev_in(&mut self, visible: &[usize]) {
+        if let Some(&prev) = visible
+            .iter()
+            .rev()
+            .find(|&&idx| idx < self.selected_index)
+            .or_else(|| visible.last())
+        {
+            self.selected_index = prev;
+        }
+    }
+
+    /// Scroll so the selection is within `visible_height` rows, and remember
+    /// the height as the page size
+    pub fn update_scroll(&mut self, visible_height: usize) {
+        self.page_size = visible_height;
+        if visible_height == 0 {
+            return;
+        }
+        if self.selected_index < self.scroll_offset {
+            self.scroll_offset = self.selected_index;
+        } else if self.selected_index >= self.scroll_offset + visible_height {
+            self.scroll_offset = self.selected_index - visible_height + 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selectable_list() {
+        let mut list = SelectableList::default();
+        list.select_prev(3);
+        assert_eq!(list.selected_index, 2);
+        list.select_next(3);
+        assert_eq!(list.selected_index, 0);
+        list.select_next(0);
+        assert_eq!(list.selected_index, 0);
+
+        // Scrolling follows the selection, paging stops at the ends
+        list.update_scroll(4);
+        list.page_down(10);
+        assert_eq!(list.selected_index, 4);
+        list.update_scroll(4);
+        assert_eq!(list.scroll_offset, 1);
+        list.page_down(10);
+        list.update_scroll(4);
+        list.page_down(10);
+        list.update_scroll(4);
+        assert_eq!((list.selected_index, list.scroll_offset), (9, 6));
+        list.page_up();
+        list.update_scroll(4);
+        assert_eq!((list.selected_index, list.scroll_offset), (5, 5));
+
+        // Filtered view over indices into the full list
+        let visible = [1, 4, 7];
+        list.select_next_in(&visible);
+        assert_eq!(list.selected_index, 7);
+        list.select_next_in(&visible);
+        assert_eq!(list.selected_index, 1);
+        list.select_prev_in(&visible);
+        assert_eq!(list.selected_index, 7);
+
+        assert!(!list.select(3, 3));
+        list.clamp(3);
+        assert_eq!(list.selected_index, 2);
+    }
+
+    #[test]
+    fn test_clamp_and_scroll() {
+        let mut list = SelectableList { selected_index: 8, scroll_offset: 5, page_size: 0 };
+        list.clamp(0);
+        assert_eq!(list.selected_index, 0);
+        list.select_prev(0);
+        list.page_down(0);
+        assert_eq!(list.selected_index, 0);
+
+        // Moving above the window scrolls back up to the selection
+        list.update_scroll(3);
+        assert_eq!(list.scroll_offset, 0);
+        list.select_prev(10);
+        list.update_scroll(3);
+        assert_eq!((list.selected_index, list.scroll_offset), (9, 7));
+
+        // A list that shrank keeps the selection on its last item
+        list.clamp(4);
+        list.update_scroll(3);
+        assert_eq!((list.selected_index, list.scroll_offset), (3, 3));
+
+        // No rows to show leaves the scroll alone
+        list.update_scroll(0);
+        assert_eq!((list.scroll_offset, list.page_size), (3, 0));
+    }
+}
@@ -0,0 +1,175 @@
+use super::config::ConfigDocument;
+use super::selectable_list::SelectableList;
+
+/// A commented example block that can be appended to the config
+#[derive(Debug)]
+pub struct ConfigTemplate {
+    /// Top-level node name the template creates
+    pub node: &'static str,
+    pub title: &'static str,
+    /// KDL v1 text inserted into the config, comments included
+    pub kdl: &'static str,
+}
+
+/// Templates for sections nirikiri has no dedicated editor for,
+/// adapted from niri's default config
+pub const TEMPLATES: &[ConfigTemplate] = &[
+    ConfigTemplate {
+        node: "window-rule",
+        title: "Window rule",
+        kdl: r##"// Open the Firefox picture-in-picture player as floating by default.
+window-rule {
+    // This app-id regular expression will work for both:
+    // - host Firefox (app-id is "firefox")
+    // - Flatpak Firefox (app-id is "org.mozilla.firefox")
+    match app-id=r#"firefox$"# title="^Picture-in-Picture$"
+    open-floating true
+}
+"##,
+    },
+    ConfigTemplate {
+        node: "layer-rule",
+        title: "Layer rule",
+        kdl: r#"// Block out notifications from screencasts.
+layer-rule {
+    match namespace="^notifications$"
+    block-out-from "screencast"
+}
+"#,
+    },
+    ConfigTemplate {
+        node: "workspace",
+        title: "Named workspace",
+        kdl: r#"// Named workspaces always exist, even when empty.
+workspace "chat" {
+    // Keep this workspace on a specific monitor.
+    // open-on-output "DP-1"
+}
+"#,
+    },
+    ConfigTemplate {
+        node: "spawn-at-startup",
+        title: "Startup program",
+        kdl: r#"// Programs to run when niri starts.
+spawn-at-startup "waybar"
+"#,
+    },
+    ConfigTemplate {
+        node: "environment",
+        title: "Environment variables",
+        kdl: r#"// Variables set for processes spawned by niri.
+environment {
+    // QT_QPA_PLATFORM "wayland"
+    // DISPLAY null
+}
+"#,
+    },
+    ConfigTemplate {
+        node: "cursor",
+        title: "Cursor",
+        kdl: r#"cursor {
+    // xcursor-theme "breeze_cursors"
+    // xcursor-size 48
+    // hide-when-typing
+    // hide-after-inactive-ms 1000
+}
+"#,
+    },
+    ConfigTemplate {
+        node: "hotkey-overlay",
+        title: "Hotkey overlay",
+        kdl: r#"hotkey-overlay {
+    // Don't show the "Important Hotkeys" pop-up at startup.
+    skip-at-startup
+}
+"#,
+    },
+    ConfigTemplate {
+        node: "screenshot-path",
+        title: "Screenshot path",
+        kdl: r#"// Where screenshots are saved, formatted with strftime(3).
+screenshot-path "~/Pictures/Screenshots/Screenshot from %Y-%m-%d %H-%M-%S.png"
+"#,
+    },
+    ConfigTemplate {
+        node: "animations",
+        title: "Animations",
+        kdl: r#"animations {
+    // Uncomment to turn off all animations.
+    // off
+
+    // Slow down all animations by this factor.
+    // slowdown 3.0
+}
+"#,
+    },
+];
+
+/// State for the template chooser dialog
+#[derive(Debug, Clone)]
+pub struct TemplateGallery {
+    /// Templates whose section is not yet in the config
+    pub templates: Vec<&'static ConfigTemplate>,
+    pub list: SelectableList,
+}
+
+impl TemplateGallery {
+    /// Offer templates only for sections the config does not define yet
+    pub fn for_config(config: &ConfigDocument) -> Self {
+        let templates = TEMPLATES
+            .iter()
+            .filter(|t| {
+                !config
+                    .doc
+                    .nodes()
+                    .iter()
+                    .any(|node| node.name().value() == t.node)
+            })
+            .collect();
+        Self {
+            templates,
+            list: SelectableList::default(),
+        }
+    }
+
+    pub fn selected(&self) -> Option<&'static ConfigTemplate> {
+        self.templates.get(self.list.selected_index).copied()
+    }
+
+    pub fn select_next(&mut self) {
+        self.list.select_next(self.templates.len());
+    }
+
+    pub fn select_prev(&mut self) {
+        self.list.select_prev(self.templates.len());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kdl::KdlDocument;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_templates_parse_as_v1() {
+        for template in TEMPLATES {
+            let doc = KdlDocument::parse_v1(template.kdl)
+                .unwrap_or_else(|e| panic!("{} template: {e}", template.node));
+            assert_eq!(doc.nodes()[0].name().value(), template.node);
+        }
+    }
+
+    #[test]
+    fn test_gallery_skips_existing_sections() {
+        let content = "cursor {\n}\nwindow-rule {\n}\n";
+        let config = ConfigDocument {
+            doc: KdlDocument::parse_v1(content).unwrap(),
+            path: PathBuf::from("config.kdl"),
+            source: content.to_string(),
+        };
+        let gallery = TemplateGallery::for_config(&config);
+        assert_eq!(gallery.templates.len(), TEMPLATES.len() - 2);
+        assert!(gallery.templates.iter().all(|t| t.node != "cursor"));
+    }
+}
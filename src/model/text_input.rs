@@ -0,0 +1,59 @@
+//! Cursor editing for single-line text fields. Cursors count chars rather
+//! than bytes, so they stay on a char boundary and match the column the
+//! views draw them at.
+
+/// Byte offset of the char at `cursor`, or the end of `text`
+fn byte_index(text: &str, cursor: usize) -> usize {
+    text.char_indices().nth(cursor).map_or(text.len(), |(i, _)| i)
+}
+
+/// Cursor position at the end of `text`
+pub fn end(text: &str) -> usize {
+    text.chars().count()
+}
+
+/// Insert `c` at the cursor and move past it
+pub fn insert_char(text: &mut String, cursor: &mut usize, c: char) {
+    text.insert(byte_index(text, *cursor), c);
+    *cursor += 1;
+}
+
+/// Delete the char before the cursor
+pub fn delete_char(text: &mut String, cursor: &mut usize) {
+    if *cursor > 0 {
+        *cursor -= 1;
+        text.remove(byte_index(text, *cursor));
+    }
+}
+
+pub fn cursor_right(text: &str, cursor: &mut usize) {
+    *cursor = (*cursor + 1).min(end(text));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_multibyte_editing() {
+        let mut text = "héllo".to_string();
+        let mut cursor = end(&text);
+        assert_eq!(cursor, 5);
+
+        cursor = 2;
+        insert_char(&mut text, &mut cursor, 'ü');
+        assert_eq!(text, "héüllo");
+        assert_eq!(cursor, 3);
+
+        delete_char(&mut text, &mut cursor);
+        delete_char(&mut text, &mut cursor);
+        assert_eq!(text, "hllo");
+        assert_eq!(cursor, 1);
+
+        cursor = end(&text);
+        cursor_right(&text, &mut cursor);
+        assert_eq!(cursor, 4);
+        insert_char(&mut text, &mut cursor, '→');
+        assert_eq!(text, "hllo→");
+    }
+}
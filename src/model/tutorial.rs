@@ -0,0 +1,158 @@
+use crate::category::Category;
+use crate::message::{AppearanceMessage, KeybindingsMessage, Message, OutputMessage};
+
+/// One step of the guided tour: a hint shown over a category, finished by
+/// doing what it asks
+pub struct TutorialStep {
+    pub category: Category,
+    /// Status bar key hint to highlight, e.g. "Tab"
+    pub key: &'static str,
+    pub hint: &'static str,
+    /// Whether handling this message completes the step
+    pub done_when: fn(&Message) -> bool,
+}
+
+pub const STEPS: &[TutorialStep] = &[
+    TutorialStep {
+        category: Category::Outputs,
+        key: "Tab",
+        hint: "Press Tab to cycle through your monitors",
+        done_when: |m| matches!(m, Message::Output(OutputMessage::SelectNextOutput | OutputMessage::SelectPrevOutput)),
+    },
+    TutorialStep {
+        category: Category::Outputs,
+        key: "hjkl",
+        hint: "Press h, j, k or l to move the selected monitor",
+        done_when: |m| matches!(m, Message::Output(OutputMessage::MoveOutput { .. })),
+    },
+    TutorialStep {
+        category: Category::Outputs,
+        key: "HJKL",
+        hint: "Press Shift+H, J, K or L to snap it against its neighbours",
+        done_when: |m| {
+            matches!(
+                m,
+                Message::Output(
+                    OutputMessage::SnapLeft { .. }
+                        | OutputMessage::SnapRight { .. }
+                        | OutputMessage::SnapAbove { .. }
+                        | OutputMessage::SnapBelow { .. }
+                )
+            )
+        },
+    },
+    TutorialStep {
+        category: Category::Outputs,
+        key: "m",
+        hint: "Press m to list the monitor's modes, then m again to close the list",
+        done_when: |m| matches!(m, Message::Output(OutputMessage::CloseModeBrowser)),
+    },
+    TutorialStep {
+        category: Category::Keybindings,
+        key: "/",
+        hint: "Press / to search binds, type part of a key or command, then Enter",
+        done_when: |m| matches!(m, Message::StartSearch),
+    },
+    TutorialStep {
+        category: Category::Keybindings,
+        key: "Enter",
+        hint: "Press Enter to edit the selected bind; Esc closes the editor without changes",
+        done_when: |m| matches!(m, Message::Keybindings(KeybindingsMessage::CancelEdit | KeybindingsMessage::ConfirmEdit)),
+    },
+    TutorialStep {
+        category: Category::Appearance,
+        key: "Tab",
+        hint: "Press Tab to expand or collapse a section",
+        done_when: |m| matches!(m, Message::Appearance(AppearanceMessage::ToggleSection)),
+    },
+    TutorialStep {
+        category: Category::Appearance,
+        key: "+/-",
+        hint: "Press + or - to adjust a number, or Space to flip a switch",
+        done_when: |m| {
+            matches!(
+                m,
+                Message::Appearance(
+                    AppearanceMessage::IncrementValue
+                        | AppearanceMessage::DecrementValue
+                        | AppearanceMessage::ToggleAppearanceBool
+                )
+            )
+        },
+    },
+    TutorialStep {
+        category: Category::Appearance,
+        key: "s",
+        hint: "Changes are only staged: press s to save them, or r to throw them away",
+        done_when: |m| matches!(m, Message::Save | Message::Reload),
+    },
+];
+
+/// Progress through the guided tour, shown as an overlay over the normal UI
+#[derive(Debug, Clone, Default)]
+pub struct Tutorial {
+    pub step: usize,
+}
+
+impl Tutorial {
+    /// The step to show, None once the tour is over
+    pub fn current(&self) -> Option<&'static TutorialStep> {
+        STEPS.get(self.step)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.step >= STEPS.len()
+    }
+
+    /// Move to the next step when `message` completes the current one.
+    /// Returns whether it did.
+    pub fn observe(&mut self, message: &Message) -> bool {
+        match self.current() {
+            Some(step) if (step.done_when)(message) => {
+                self.step += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Skip the current step without doing it
+    pub fn skip(&mut self) {
+        self.step = (self.step + 1).min(STEPS.len());
+    }
+
+    /// The category the user has to switch to before the current step
+    /// applies, if they're elsewhere
+    pub fn pending_switch(&self, current: Category) -> Option<Category> {
+        self.current().map(|step| step.category).filter(|&category| category != current)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tutorial_steps() {
+        let mut tutorial = Tutorial::default();
+        assert_eq!(tutorial.current().unwrap().key, "Tab");
+
+        assert!(!tutorial.observe(&Message::Output(OutputMessage::MoveOutput { dx: 10, dy: 0 })));
+        assert!(tutorial.observe(&Message::Output(OutputMessage::SelectNextOutput)));
+        assert!(tutorial.observe(&Message::Output(OutputMessage::MoveOutput { dx: 10, dy: 0 })));
+        assert_eq!(tutorial.pending_switch(Category::Outputs), None);
+
+        tutorial.skip();
+        tutorial.skip();
+        assert_eq!(tutorial.pending_switch(Category::Outputs), Some(Category::Keybindings));
+        assert!(tutorial.observe(&Message::StartSearch));
+
+        while !tutorial.is_finished() {
+            tutorial.skip();
+        }
+        assert!(tutorial.current().is_none());
+        assert!(!tutorial.observe(&Message::Save));
+        tutorial.skip();
+        assert_eq!(tutorial.step, STEPS.len());
+    }
+}
@@ -0,0 +1,295 @@
+//! Values niri writes with a unit: percentages, pixel sizes and durations.
+//!
+//! Each type parses what a user would type ("50%", "1920px", "1.5s") and
+//! renders the spelling niri expects in the config. Plain integers can be
+//! typed as arithmetic ("1920/2").
+
+use std::fmt;
+
+/// Argument of set-column-width and friends: an absolute or relative size,
+/// as a proportion of the screen ("50%", "+10%") or in pixels ("800", "-100").
+/// niri only accepts these as strings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SizeChange {
+    /// Add to the current size instead of replacing it
+    pub relative: bool,
+    pub amount: f64,
+    pub percent: bool,
+}
+
+impl SizeChange {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let value = value.trim();
+        let invalid = || format!("expected a size like 50%, +10%, 800 or -100, got '{value}'");
+        let relative = value.starts_with(['+', '-']);
+        let (number, percent) = match value.strip_suffix('%') {
+            Some(number) => (number, true),
+            None => (value.strip_suffix("px").unwrap_or(value), false),
+        };
+        let amount: f64 = number.parse().map_err(|_| invalid())?;
+        if !amount.is_finite() || (!relative && amount < 0.0) {
+            return Err(invalid());
+        }
+        if !percent && amount.fract() != 0.0 {
+            return Err(format!("pixel sizes must be whole numbers, got '{value}'"));
+        }
+        Ok(Self { relative, amount, percent })
+    }
+}
+
+impl fmt::Display for SizeChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.relative && self.amount >= 0.0 {
+            write!(f, "+")?;
+        }
+        write!(f, "{}", self.amount)?;
+        if self.percent {
+            write!(f, "%")?;
+        }
+        Ok(())
+    }
+}
+
+/// An entry of preset-column-widths and similar lists: written to the
+/// config as `proportion 0.5` or `fixed 1920`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PresetSize {
+    Proportion(f64),
+    Fixed(u32),
+}
+
+impl PresetSize {
+    /// Parse "50%" or "0.5" as a proportion, "1920px" or "1920" as fixed
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let value = value.trim();
+        if let Some(percent) = value.strip_suffix('%') {
+            let percent: f64 = percent
+                .trim()
+                .parse()
+                .map_err(|_| format!("invalid percentage '{value}'"))?;
+            if !(percent > 0.0 && percent <= 100.0) {
+                return Err(format!("percentage must be above 0 and at most 100, got '{value}'"));
+            }
+            return Ok(PresetSize::Proportion(percent / 100.0));
+        }
+        let pixels = value.strip_suffix("px").unwrap_or(value).trim();
+        if let Ok(px) = pixels.parse::<u32>() {
+            return Ok(PresetSize::Fixed(px));
+        }
+        match value.parse::<f64>() {
+            Ok(p) if p > 0.0 && p <= 1.0 => Ok(PresetSize::Proportion(p)),
+            _ => Err(format!("expected a size like 50% or 1920px, got '{value}'")),
+        }
+    }
+
+    /// Node name used for this size in the config
+    pub fn node_name(&self) -> &'static str {
+        match self {
+            PresetSize::Proportion(_) => "proportion",
+            PresetSize::Fixed(_) => "fixed",
+        }
+    }
+}
+
+impl fmt::Display for PresetSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            // Round away float noise such as 0.33333 * 100 = 33.333000000000006
+            PresetSize::Proportion(p) => write!(f, "{}%", (p * 100_000.0).round() / 1000.0),
+            PresetSize::Fixed(px) => write!(f, "{px}px"),
+        }
+    }
+}
+
+/// Parse a duration typed as "150", "150ms" or "1.5s" into milliseconds
+pub fn parse_duration_ms(value: &str) -> Result<u32, String> {
+    let value = value.trim();
+    let invalid = || format!("expected a duration like 150ms or 1.5s, got '{value}'");
+    let (number, scale) = if let Some(ms) = value.strip_suffix("ms") {
+        (ms, 1.0)
+    } else if let Some(s) = value.strip_suffix('s') {
+        (s, 1000.0)
+    } else {
+        (value, 1.0)
+    };
+    let ms = number.trim().parse::<f64>().map_err(|_| invalid())? * scale;
+    if !(0.0..=u32::MAX as f64).contains(&ms) || ms.fract() != 0.0 {
+        return Err(invalid());
+    }
+    Ok(ms as u32)
+}
+
+/// Evaluate an integer typed as simple arithmetic, such as "16+8",
+/// "1920/2" or "-(1080-40)". Supports + - * / and parentheses; division
+/// has to come out even.
+pub fn eval_integer(value: &str) -> Result<i32, String> {
+    let tokens: Vec<char> = value.chars().collect();
+    let mut parser = ExprParser { tokens: &tokens, pos: 0, depth: 0 };
+    let result = parser.sum()?;
+    if parser.peek().is_some() {
+        return Err(format!("unexpected '{}' in '{}'", tokens[parser.pos], value.trim()));
+    }
+    i32::try_from(result).map_err(|_| format!("{result} is out of range"))
+}
+
+/// How deep parentheses and signs may nest before an expression is
+/// refused, so a pasted wall of '(' can't overflow the stack
+const MAX_EXPR_DEPTH: usize = 64;
+
+/// Recursive descent over the characters of an integer expression
+struct ExprParser<'a> {
+    tokens: &'a [char],
+    pos: usize,
+    /// Parentheses and signs currently open
+    depth: usize,
+}
+
+impl ExprParser<'_> {
+    /// The next character that isn't whitespace
+    fn peek(&mut self) -> Option<char> {
+        while self.tokens.get(self.pos).is_some_and(|c| c.is_whitespace()) {
+            self.pos += 1;
+        }
+        self.tokens.get(self.pos).copied()
+    }
+
+    fn sum(&mut self) -> Result<i64, String> {
+        let mut total = self.product()?;
+        while let Some(op @ ('+' | '-')) = self.peek() {
+            self.pos += 1;
+            let rhs = self.product()?;
+            total = if op == '+' { total.checked_add(rhs) } else { total.checked_sub(rhs) }
+                .ok_or("number too large")?;
+        }
+        Ok(total)
+    }
+
+    fn product(&mut self) -> Result<i64, String> {
+        let mut total = self.factor()?;
+        while let Some(op @ ('*' | '/')) = self.peek() {
+            self.pos += 1;
+            let rhs = self.factor()?;
+            total = if op == '*' {
+                total.checked_mul(rhs).ok_or("number too large")?
+            } else if rhs == 0 {
+                return Err("division by zero".to_string());
+            } else if total.checked_rem(rhs).ok_or("number too large")? != 0 {
+                return Err(format!("{total}/{rhs} is not a whole number"));
+            } else {
+                total.checked_div(rhs).ok_or("number too large")?
+            };
+        }
+        Ok(total)
+    }
+
+    fn factor(&mut self) -> Result<i64, String> {
+        if matches!(self.peek(), Some('-' | '+' | '(')) {
+            if self.depth == MAX_EXPR_DEPTH {
+                return Err("expression nested too deeply".to_string());
+            }
+            self.depth += 1;
+            let value = self.nested();
+            self.depth -= 1;
+            return value;
+        }
+        match self.peek() {
+            Some(c) if c.is_ascii_digit() => {
+                let start = self.pos;
+                while self.tokens.get(self.pos).is_some_and(|c| c.is_ascii_digit()) {
+                    self.pos += 1;
+                }
+                let digits: String = self.tokens[start..self.pos].iter().collect();
+                digits.parse().map_err(|_| "number too large".to_string())
+            }
+            Some(c) => Err(format!("expected a number, got '{c}'")),
+            None => Err("expected a number".to_string()),
+        }
+    }
+
+    /// A sign or parenthesized expression, one level deeper
+    fn nested(&mut self) -> Result<i64, String> {
+        match self.peek() {
+            Some('-') => {
+                self.pos += 1;
+                self.factor()?.checked_neg().ok_or_else(|| "number too large".to_string())
+            }
+            Some('+') => {
+                self.pos += 1;
+                self.factor()
+            }
+            _ => {
+                self.pos += 1;
+                let value = self.sum()?;
+                if self.peek() != Some(')') {
+                    return Err("missing ')'".to_string());
+                }
+                self.pos += 1;
+                Ok(value)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_size_change() {
+        assert_eq!(SizeChange::parse("50%").unwrap().to_string(), "50%");
+        assert_eq!(SizeChange::parse("+10%").unwrap().to_string(), "+10%");
+        assert_eq!(SizeChange::parse("-100").unwrap().to_string(), "-100");
+        assert_eq!(SizeChange::parse("800px").unwrap().to_string(), "800");
+        assert_eq!(SizeChange::parse("+0.5%").unwrap().to_string(), "+0.5%");
+        assert!(SizeChange::parse("-5.5").is_err());
+        assert!(SizeChange::parse("wide").is_err());
+        assert!(SizeChange::parse("%").is_err());
+    }
+
+    #[test]
+    fn test_preset_size() {
+        assert_eq!(PresetSize::parse("50%"), Ok(PresetSize::Proportion(0.5)));
+        assert_eq!(PresetSize::parse("0.33333"), Ok(PresetSize::Proportion(0.33333)));
+        assert_eq!(PresetSize::parse("1920px"), Ok(PresetSize::Fixed(1920)));
+        assert_eq!(PresetSize::parse("1280"), Ok(PresetSize::Fixed(1280)));
+        assert_eq!(PresetSize::Proportion(0.33333).to_string(), "33.333%");
+        assert_eq!(PresetSize::Fixed(1920).node_name(), "fixed");
+        assert!(PresetSize::parse("150%").is_err());
+        assert!(PresetSize::parse("-3").is_err());
+    }
+
+    #[test]
+    fn test_parse_duration_ms() {
+        assert_eq!(parse_duration_ms("150"), Ok(150));
+        assert_eq!(parse_duration_ms("150ms"), Ok(150));
+        assert_eq!(parse_duration_ms("1.5s"), Ok(1500));
+        assert_eq!(parse_duration_ms("10000"), Ok(10000));
+        assert!(parse_duration_ms("-1").is_err());
+        assert!(parse_duration_ms("soon").is_err());
+    }
+
+    #[test]
+    fn test_eval_integer() {
+        assert_eq!(eval_integer("16"), Ok(16));
+        assert_eq!(eval_integer("-4"), Ok(-4));
+        assert_eq!(eval_integer("16+8"), Ok(24));
+        assert_eq!(eval_integer("1920/2"), Ok(960));
+        assert_eq!(eval_integer(" 2560 - 1920 / 2 "), Ok(1600));
+        assert_eq!(eval_integer("-(1080-40)*2"), Ok(-2080));
+        assert!(eval_integer("1921/2").is_err());
+        assert!(eval_integer("5/0").is_err());
+        assert!(eval_integer("(1+2").is_err());
+        assert!(eval_integer("3 4").is_err());
+        assert!(eval_integer("").is_err());
+        assert!(eval_integer("99999*99999").is_err());
+
+        // i64 overflow is an error, not a panic
+        assert!(eval_integer("(-9223372036854775807-1)/-1").is_err());
+        assert!(eval_integer("-(-9223372036854775807-1)").is_err());
+
+        // Deep nesting is refused rather than overflowing the stack
+        assert_eq!(eval_integer(&format!("{}1{}", "(".repeat(64), ")".repeat(64))), Ok(1));
+        assert!(eval_integer(&format!("{}1{}", "(".repeat(100_000), ")".repeat(100_000))).is_err());
+        assert!(eval_integer(&format!("{}1", "-".repeat(100_000))).is_err());
+    }
+}
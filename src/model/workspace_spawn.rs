@@ -0,0 +1,178 @@
+use super::binding_template::TemplateField;
+use super::keybindings::{has_unterminated_quote, parse_command_args};
+
+/// The config pieces that open an app on a named workspace: the workspace
+/// declaration, a window rule sending the app there, and optionally a
+/// startup entry launching it
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkspaceSpawn {
+    pub workspace: String,
+    pub command: Vec<String>,
+    /// app-id regular expression for the window rule
+    pub app_id: String,
+    /// Output the workspace lives on, if pinned
+    pub output: Option<String>,
+    pub at_startup: bool,
+}
+
+const WORKSPACE: usize = 0;
+const COMMAND: usize = 1;
+const APP_ID: usize = 2;
+const OUTPUT: usize = 3;
+/// Focus index of the "start at login" switch, after the text fields
+pub const STARTUP_TOGGLE: usize = 4;
+
+/// State for the dialog that builds a [`WorkspaceSpawn`]
+#[derive(Debug, Clone)]
+pub struct WorkspaceSpawnWizard {
+    pub fields: Vec<TemplateField>,
+    pub at_startup: bool,
+    pub focused: usize,
+}
+
+impl WorkspaceSpawnWizard {
+    /// An empty wizard, pinned to `output` when one is given
+    pub fn new(output: Option<&str>) -> Self {
+        Self {
+            fields: vec![
+                TemplateField::new("Workspace", ""),
+                TemplateField::new("Command", ""),
+                TemplateField::new("App ID", ""),
+                TemplateField::new("Output", output.unwrap_or_default()),
+            ],
+            at_startup: true,
+            focused: WORKSPACE,
+        }
+    }
+
+    pub fn focus_next(&mut self) {
+        self.focused = (self.focused + 1) % (STARTUP_TOGGLE + 1);
+    }
+
+    pub fn focus_prev(&mut self) {
+        self.focused = (self.focused + STARTUP_TOGGLE) % (STARTUP_TOGGLE + 1);
+    }
+
+    /// The text field being edited, None while the switch is focused
+    pub fn focused_field(&mut self) -> Option<&mut TemplateField> {
+        self.fields.get_mut(self.focused)
+    }
+
+    /// Type into the focused field; Space flips the switch when it has focus
+    pub fn insert_char(&mut self, c: char) {
+        match self.focused_field() {
+            Some(field) => field.insert_char(c),
+            None if c == ' ' => self.at_startup = !self.at_startup,
+            None => {}
+        }
+    }
+
+    pub fn delete_char(&mut self) {
+        if let Some(field) = self.focused_field() {
+            field.delete_char();
+        }
+    }
+
+    pub fn cursor_left(&mut self) {
+        if let Some(field) = self.focused_field() {
+            field.cursor_left();
+        }
+    }
+
+    pub fn cursor_right(&mut self) {
+        if let Some(field) = self.focused_field() {
+            field.cursor_right();
+        }
+    }
+
+    fn value(&self, index: usize) -> &str {
+        self.fields[index].value.trim()
+    }
+
+    /// The app-id match used when the App ID field is left empty: the
+    /// command's program name, matched exactly
+    pub fn derived_app_id(&self) -> Option<String> {
+        let program = parse_command_args(self.value(COMMAND)).into_iter().next()?;
+        let name = program.rsplit('/').next().unwrap_or(&program).to_string();
+        Some(format!("^{}$", escape_regex(&name)))
+    }
+
+    /// The pieces to write, or what is still missing
+    pub fn plan(&self) -> Result<WorkspaceSpawn, String> {
+        let workspace = self.value(WORKSPACE);
+        if workspace.is_empty() {
+            return Err("Workspace needs a name".to_string());
+        }
+        let command = self.value(COMMAND);
+        if has_unterminated_quote(command) {
+            return Err("Command has an unterminated quote".to_string());
+        }
+        let command = parse_command_args(command);
+        if command.is_empty() {
+            return Err("Command cannot be empty".to_string());
+        }
+        let app_id = match self.value(APP_ID) {
+            "" => self.derived_app_id().unwrap_or_default(),
+            app_id => app_id.to_string(),
+        };
+        let output = Some(self.value(OUTPUT)).filter(|o| !o.is_empty()).map(str::to_string);
+        Ok(WorkspaceSpawn {
+            workspace: workspace.to_string(),
+            command,
+            app_id,
+            output,
+            at_startup: self.at_startup,
+        })
+    }
+}
+
+/// Escape characters that have a meaning in a regular expression
+fn escape_regex(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        if "\\.+*?()|[]{}^$".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_workspace_spawn_plan() {
+        let mut wizard = WorkspaceSpawnWizard::new(Some("DP-1"));
+        assert_eq!(wizard.plan(), Err("Workspace needs a name".to_string()));
+
+        for c in "chat".chars() {
+            wizard.insert_char(c);
+        }
+        wizard.focus_next();
+        for c in "/usr/bin/org.telegram.desktop --startintray".chars() {
+            wizard.insert_char(c);
+        }
+        let plan = wizard.plan().unwrap();
+        assert_eq!(plan.workspace, "chat");
+        assert_eq!(plan.command, vec!["/usr/bin/org.telegram.desktop", "--startintray"]);
+        assert_eq!(plan.app_id, r"^org\.telegram\.desktop$");
+        assert_eq!(plan.output.as_deref(), Some("DP-1"));
+        assert!(plan.at_startup);
+
+        // An explicit app id wins; Space on the switch turns startup off
+        wizard.focus_next();
+        for c in "^telegram".chars() {
+            wizard.insert_char(c);
+        }
+        wizard.focus_prev();
+        wizard.focus_prev();
+        wizard.focus_prev();
+        assert_eq!(wizard.focused, STARTUP_TOGGLE);
+        wizard.insert_char(' ');
+        let plan = wizard.plan().unwrap();
+        assert_eq!(plan.app_id, "^telegram");
+        assert!(!plan.at_startup);
+    }
+}
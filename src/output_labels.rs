@@ -0,0 +1,144 @@
+use anyhow::Result;
+use kdl::KdlDocument;
+use std::collections::BTreeMap;
+
+use crate::config::kdl_value::string;
+use crate::model::TemplateField;
+use crate::state_file;
+
+const FILE: &str = "output-labels.kdl";
+
+/// A friendly name and a freeform note for one output
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OutputLabel {
+    pub label: String,
+    pub note: String,
+}
+
+/// Labels and notes attached to outputs by connector name, kept across
+/// sessions in ~/.local/state/nirikiri/output-labels.kdl
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OutputLabels {
+    pub outputs: BTreeMap<String, OutputLabel>,
+}
+
+impl OutputLabels {
+    /// Load the labels, starting empty when the file doesn't exist
+    pub fn load() -> Result<Self> {
+        Ok(state_file::load(FILE, "output labels", Self::parse)?.unwrap_or_default())
+    }
+
+    /// Parse `output "DP-1" label="..." note="..."` nodes; anything else is ignored
+    pub fn parse(content: &str) -> Result<Self> {
+        let doc = KdlDocument::parse_v1(content)?;
+        let mut labels = Self::default();
+        for node in doc.nodes().iter().filter(|n| n.name().value() == "output") {
+            let Some(name) = node.get(0).and_then(|v| v.as_string()) else {
+                continue;
+            };
+            let text = |key: &str| node.get(key).and_then(|v| v.as_string()).unwrap_or_default().to_string();
+            labels.set(name, &text("label"), &text("note"));
+        }
+        Ok(labels)
+    }
+
+    pub fn to_kdl(&self) -> String {
+        state_file::to_kdl(self.outputs.iter().map(|(name, entry)| {
+            let mut node = state_file::node("output", [string(name.as_str())]);
+            state_file::prop(&mut node, "label", string(entry.label.as_str()));
+            state_file::prop(&mut node, "note", string(entry.note.as_str()));
+            node
+        }))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        state_file::save(FILE, "output labels", &self.to_kdl())
+    }
+
+    pub fn get(&self, output: &str) -> Option<&OutputLabel> {
+        self.outputs.get(output)
+    }
+
+    /// The output's label, None when it has none
+    pub fn label(&self, output: &str) -> Option<&str> {
+        self.get(output).map(|entry| entry.label.as_str()).filter(|label| !label.is_empty())
+    }
+
+    /// Replace the output's label and note; clearing both forgets the output.
+    /// Returns false if nothing changed.
+    pub fn set(&mut self, output: &str, label: &str, note: &str) -> bool {
+        let entry = OutputLabel { label: label.trim().to_string(), note: note.trim().to_string() };
+        if entry == OutputLabel::default() {
+            return self.outputs.remove(output).is_some();
+        }
+        self.outputs.insert(output.to_string(), entry.clone()) != Some(entry)
+    }
+}
+
+const LABEL: usize = 0;
+const NOTE: usize = 1;
+
+/// State for the dialog that edits an output's label and note
+#[derive(Debug, Clone)]
+pub struct OutputLabelEditor {
+    pub output: String,
+    pub fields: Vec<TemplateField>,
+    pub focused: usize,
+}
+
+impl OutputLabelEditor {
+    pub fn new(output: &str, current: Option<&OutputLabel>) -> Self {
+        let current = current.cloned().unwrap_or_default();
+        Self {
+            output: output.to_string(),
+            fields: vec![TemplateField::new("Label", current.label), TemplateField::new("Note", current.note)],
+            focused: LABEL,
+        }
+    }
+
+    pub fn focus_next(&mut self) {
+        self.focused = (self.focused + 1) % self.fields.len();
+    }
+
+    pub fn field_mut(&mut self) -> &mut TemplateField {
+        &mut self.fields[self.focused]
+    }
+
+    pub fn label(&self) -> &str {
+        &self.fields[LABEL].value
+    }
+
+    pub fn note(&self) -> &str {
+        &self.fields[NOTE].value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_labels() {
+        let mut labels =
+            OutputLabels::parse("output \"DP-1\" label=\"Left 27\\\"\" note=\"Desk\"\noutput \"HDMI-A-1\" note=\"\"\nother 1")
+                .unwrap();
+        assert_eq!(labels.label("DP-1"), Some("Left 27\""));
+        assert_eq!(labels.get("DP-1").unwrap().note, "Desk");
+        assert!(labels.get("HDMI-A-1").is_none());
+
+        assert!(labels.set("HDMI-A-1", " TV ", ""));
+        assert!(!labels.set("HDMI-A-1", "TV", ""));
+        assert_eq!(labels.label("HDMI-A-1"), Some("TV"));
+        assert_eq!(OutputLabels::parse(&labels.to_kdl()).unwrap(), labels);
+
+        assert!(labels.set("DP-1", "", ""));
+        assert!(labels.get("DP-1").is_none());
+
+        let mut editor = OutputLabelEditor::new("HDMI-A-1", labels.get("HDMI-A-1"));
+        editor.focus_next();
+        for c in "Living room".chars() {
+            editor.field_mut().insert_char(c);
+        }
+        assert_eq!((editor.label(), editor.note()), ("TV", "Living room"));
+    }
+}
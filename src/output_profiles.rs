@@ -0,0 +1,356 @@
+use anyhow::Result;
+use kdl::{KdlDocument, KdlNode};
+use std::collections::HashMap;
+
+use crate::config::kdl_value::{as_bool, as_float, as_int, boolean, float, int, string};
+use crate::model::{OutputChange, OutputState, OutputTransform, Position, SelectableList, TemplateField};
+use crate::state_file;
+
+const FILE: &str = "output-profiles.kdl";
+
+/// Where one output goes in a profile
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProfileOutput {
+    pub name: String,
+    pub make: String,
+    pub model: String,
+    pub enabled: bool,
+    pub position: Position,
+    /// As niri's config spells it, e.g. `2560x1440@143.912`
+    pub mode: Option<String>,
+    pub scale: f64,
+    pub transform: OutputTransform,
+}
+
+/// Which monitors are plugged in, by connector, make and model, in a
+/// stable order
+pub type Fingerprint = Vec<(String, String, String)>;
+
+/// The fingerprint of the connected outputs
+pub fn fingerprint(outputs: &[OutputState]) -> Fingerprint {
+    let mut print: Fingerprint = outputs
+        .iter()
+        .filter(|o| o.connected)
+        .map(|o| (o.name.clone(), o.make.clone(), o.model.clone()))
+        .collect();
+    print.sort();
+    print
+}
+
+/// A named output arrangement, such as home, office or laptop-only
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputProfile {
+    pub name: String,
+    pub outputs: Vec<ProfileOutput>,
+    /// Apply the profile without asking when its monitors are plugged in
+    pub auto_apply: bool,
+}
+
+impl OutputProfile {
+    /// The monitors the profile was saved with
+    pub fn fingerprint(&self) -> Fingerprint {
+        let mut print: Fingerprint =
+            self.outputs.iter().map(|o| (o.name.clone(), o.make.clone(), o.model.clone())).collect();
+        print.sort();
+        print
+    }
+
+    /// The connected outputs as they would be with the staged changes saved
+    pub fn capture(name: &str, outputs: &[OutputState], pending: &HashMap<String, OutputChange>) -> Self {
+        let outputs = outputs
+            .iter()
+            .filter(|o| o.connected)
+            .map(|output| {
+                let mut output = output.clone();
+                if let Some(change) = pending.get(&output.name) {
+                    output.apply_change(change);
+                }
+                ProfileOutput {
+                    mode: output.current_mode().map(|m| m.config_string()),
+                    name: output.name,
+                    make: output.make,
+                    model: output.model,
+                    enabled: output.enabled,
+                    position: output.position,
+                    scale: output.scale,
+                    transform: output.transform,
+                }
+            })
+            .collect();
+        Self { name: name.trim().to_string(), outputs, auto_apply: false }
+    }
+
+    /// Changes that bring `outputs` to the profile, leaving out fields that
+    /// already match, and a note for each part that can't be applied
+    pub fn changes(&self, outputs: &[OutputState]) -> (HashMap<String, OutputChange>, Vec<String>) {
+        let mut changes = HashMap::new();
+        let mut notes = Vec::new();
+        for wanted in &self.outputs {
+            let Some(output) = outputs.iter().find(|o| o.name == wanted.name && o.connected) else {
+                notes.push(format!("{} is not connected", wanted.name));
+                continue;
+            };
+            let mut change = OutputChange {
+                enabled: Some(wanted.enabled).filter(|&e| e != output.enabled),
+                ..Default::default()
+            };
+            if wanted.enabled {
+                change.position = Some(wanted.position).filter(|&p| p != output.position);
+                change.scale = Some(wanted.scale).filter(|s| (s - output.scale).abs() > 1e-6);
+                change.transform = Some(wanted.transform).filter(|&t| t != output.transform);
+                if let Some(mode) = &wanted.mode {
+                    match output.modes.iter().find(|m| &m.config_string() == mode) {
+                        Some(m) => change.mode = Some(m.clone()).filter(|m| output.current_mode() != Some(m)),
+                        None => notes.push(format!("{} no longer offers {mode}", wanted.name)),
+                    }
+                }
+            }
+            if change != OutputChange::default() {
+                changes.insert(wanted.name.clone(), change);
+            }
+        }
+        (changes, notes)
+    }
+}
+
+/// Saved output profiles, kept across sessions in
+/// ~/.local/state/nirikiri/output-profiles.kdl
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OutputProfiles {
+    pub profiles: Vec<OutputProfile>,
+}
+
+impl OutputProfiles {
+    /// Load the profiles, starting empty when the file doesn't exist
+    pub fn load() -> Result<Self> {
+        Ok(state_file::load(FILE, "output profiles", Self::parse)?.unwrap_or_default())
+    }
+
+    /// Parse `profile "home" { output "DP-1" x=0 y=0 ... }` nodes; anything
+    /// else is ignored
+    pub fn parse(content: &str) -> Result<Self> {
+        let doc = KdlDocument::parse_v1(content)?;
+        let mut profiles = Self::default();
+        for node in doc.nodes().iter().filter(|n| n.name().value() == "profile") {
+            let Some(name) = node.get(0).and_then(|v| v.as_string()) else {
+                continue;
+            };
+            let outputs = node
+                .children()
+                .map(|c| c.nodes())
+                .unwrap_or_default()
+                .iter()
+                .filter(|n| n.name().value() == "output")
+                .filter_map(parse_output)
+                .collect();
+            let auto_apply = node.get("auto-apply").and_then(as_bool).unwrap_or(false);
+            profiles.set(OutputProfile { name: name.to_string(), outputs, auto_apply });
+        }
+        Ok(profiles)
+    }
+
+    pub fn to_kdl(&self) -> String {
+        state_file::to_kdl(self.profiles.iter().map(|profile| {
+            let mut node = state_file::node("profile", [string(profile.name.as_str())]);
+            if profile.auto_apply {
+                state_file::prop(&mut node, "auto-apply", boolean(true));
+            }
+            let mut children = KdlDocument::new();
+            for output in &profile.outputs {
+                let mut child = state_file::node("output", [string(output.name.as_str())]);
+                state_file::prop(&mut child, "make", string(output.make.as_str()));
+                state_file::prop(&mut child, "model", string(output.model.as_str()));
+                state_file::prop(&mut child, "x", int(output.position.x));
+                state_file::prop(&mut child, "y", int(output.position.y));
+                state_file::prop(&mut child, "scale", float(output.scale));
+                state_file::prop(&mut child, "transform", string(output.transform.as_str()));
+                if let Some(mode) = &output.mode {
+                    state_file::prop(&mut child, "mode", string(mode.as_str()));
+                }
+                if !output.enabled {
+                    state_file::prop(&mut child, "enabled", boolean(false));
+                }
+                children.nodes_mut().push(child);
+            }
+            node.set_children(children);
+            node
+        }))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        state_file::save(FILE, "output profiles", &self.to_kdl())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&OutputProfile> {
+        self.profiles.iter().find(|p| p.name == name)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut OutputProfile> {
+        self.profiles.iter_mut().find(|p| p.name == name)
+    }
+
+    /// The first profile saved with exactly the monitors now connected
+    pub fn matching(&self, outputs: &[OutputState]) -> Option<&OutputProfile> {
+        let connected = fingerprint(outputs);
+        if connected.is_empty() {
+            return None;
+        }
+        self.profiles.iter().find(|p| p.fingerprint() == connected)
+    }
+
+    /// Add the profile, replacing one with the same name in place
+    pub fn set(&mut self, profile: OutputProfile) {
+        match self.profiles.iter_mut().find(|p| p.name == profile.name) {
+            Some(existing) => *existing = profile,
+            None => self.profiles.push(profile),
+        }
+    }
+
+    pub fn remove(&mut self, name: &str) -> bool {
+        let before = self.profiles.len();
+        self.profiles.retain(|p| p.name != name);
+        self.profiles.len() != before
+    }
+}
+
+fn parse_output(node: &KdlNode) -> Option<ProfileOutput> {
+    let text = |key: &str| node.get(key).and_then(|v| v.as_string()).unwrap_or_default().to_string();
+    Some(ProfileOutput {
+        name: node.get(0)?.as_string()?.to_string(),
+        make: text("make"),
+        model: text("model"),
+        enabled: node.get("enabled").and_then(as_bool).unwrap_or(true),
+        position: Position::new(node.get("x").and_then(as_int)?, node.get("y").and_then(as_int)?),
+        mode: node.get("mode").and_then(|v| v.as_string()).map(str::to_string),
+        scale: node.get("scale").and_then(as_float).unwrap_or(1.0),
+        transform: OutputTransform::parse(&text("transform")).unwrap_or_default(),
+    })
+}
+
+/// State for the dialog that lists, saves and applies profiles
+#[derive(Debug, Clone)]
+pub struct ProfilePicker {
+    pub list: SelectableList,
+    /// Name for saving the current arrangement, while it is being typed
+    pub naming: Option<TemplateField>,
+}
+
+impl ProfilePicker {
+    /// Start on the profile for the connected monitors, or on the name
+    /// field when there is nothing to pick yet
+    pub fn new(profiles: &OutputProfiles, outputs: &[OutputState]) -> Self {
+        let mut picker = Self { list: SelectableList::default(), naming: None };
+        if profiles.profiles.is_empty() {
+            picker.start_naming(None);
+        }
+        let connected = fingerprint(outputs);
+        if let Some(index) = profiles.profiles.iter().position(|p| p.fingerprint() == connected) {
+            picker.list.selected_index = index;
+        }
+        picker
+    }
+
+    /// Ask for a name, starting from `name` so a profile is easy to update
+    pub fn start_naming(&mut self, name: Option<&str>) {
+        self.naming = Some(TemplateField::new("Name", name.unwrap_or_default()));
+    }
+
+    pub fn selected<'a>(&self, profiles: &'a OutputProfiles) -> Option<&'a OutputProfile> {
+        profiles.profiles.get(self.list.selected_index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{OutputMode, Size};
+
+    fn output(name: &str, x: i32) -> OutputState {
+        let modes = vec![
+            OutputMode { width: 2560, height: 1440, refresh_rate: 59.951, is_preferred: true },
+            OutputMode { width: 1920, height: 1080, refresh_rate: 60.0, is_preferred: false },
+        ];
+        OutputState {
+            name: name.to_string(),
+            modes,
+            current_mode_index: Some(0),
+            scale: 1.0,
+            transform: OutputTransform::Normal,
+            position: Position::new(x, 0),
+            logical_size: Size::new(2560, 1440),
+            physical_size: Size::new(2560, 1440),
+            size_mm: None,
+            enabled: true,
+            vrr: false,
+            connected: true,
+            configured: true,
+            make: "Dell Inc.".to_string(),
+            model: "U2719D".to_string(),
+            source: None,
+            duplicate_sources: Vec::new(),
+            background_color: None,
+            backdrop_color: None,
+            focus_at_startup: false,
+        }
+    }
+
+    #[test]
+    fn test_output_profiles() {
+        let outputs = vec![output("DP-1", 0), output("HDMI-A-1", 2560)];
+        let mut staged = HashMap::new();
+        staged.insert(
+            "HDMI-A-1".to_string(),
+            OutputChange { scale: Some(1.25), transform: Some(OutputTransform::Rotate90), ..Default::default() },
+        );
+        staged.insert("DP-1".to_string(), OutputChange { enabled: Some(false), ..Default::default() });
+        let home = OutputProfile::capture(" home ", &outputs, &staged);
+        assert_eq!(home.name, "home");
+        assert_eq!(home.outputs[1].scale, 1.25);
+        assert_eq!(home.outputs[1].mode.as_deref(), Some("2560x1440@59.951"));
+        assert!(!home.outputs[0].enabled);
+
+        let mut profiles = OutputProfiles::default();
+        profiles.set(home.clone());
+        profiles.set(OutputProfile::capture("office", &outputs, &HashMap::new()));
+        assert_eq!(OutputProfiles::parse(&profiles.to_kdl()).unwrap(), profiles);
+
+        // Applying writes only what differs, skipping what can't be done
+        let (changes, notes) = home.changes(&outputs);
+        assert_eq!(changes["DP-1"], OutputChange { enabled: Some(false), ..Default::default() });
+        assert_eq!(changes["HDMI-A-1"], staged["HDMI-A-1"]);
+        assert!(notes.is_empty());
+        let mut laptop = profiles.get("office").unwrap().clone();
+        laptop.outputs[0].mode = Some("1920x1080@60.000".to_string());
+        laptop.outputs[1].mode = Some("800x600@60.000".to_string());
+        laptop.outputs.push(ProfileOutput { name: "eDP-1".to_string(), ..laptop.outputs[0].clone() });
+        let (changes, notes) = laptop.changes(&outputs);
+        assert_eq!(changes["DP-1"].mode.as_ref().map(|m| m.width), Some(1920));
+        assert!(!changes.contains_key("HDMI-A-1"));
+        assert_eq!(notes, vec!["HDMI-A-1 no longer offers 800x600@60.000", "eDP-1 is not connected"]);
+
+        // A profile matches when exactly its monitors are connected
+        assert_eq!(profiles.matching(&outputs).map(|p| p.name.as_str()), Some("home"));
+        let mut docked = outputs.clone();
+        docked[1].model = "P2419H".to_string();
+        assert!(profiles.matching(&docked).is_none());
+        docked[1].connected = false;
+        assert!(profiles.matching(&docked).is_none());
+        assert!(profiles.matching(&[]).is_none());
+        profiles.profiles[1].auto_apply = true;
+        let reparsed = OutputProfiles::parse(&profiles.to_kdl()).unwrap();
+        assert!(!reparsed.profiles[0].auto_apply);
+        assert!(reparsed.profiles[1].auto_apply);
+        assert_eq!(ProfilePicker::new(&reparsed, &outputs).list.selected_index, 0);
+
+        // Saving under an existing name replaces that profile
+        profiles.set(OutputProfile { name: "home".to_string(), outputs: Vec::new(), auto_apply: false });
+        assert_eq!(profiles.profiles.len(), 2);
+        assert!(profiles.get("home").unwrap().outputs.is_empty());
+        assert!(profiles.remove("office"));
+        assert!(!profiles.remove("office"));
+
+        // Names are escaped, not printed as Rust debug strings
+        profiles.set(OutputProfile { name: "desk \"2\" \\ é".to_string(), outputs: home.outputs.clone(), auto_apply: true });
+        assert_eq!(OutputProfiles::parse(&profiles.to_kdl()).unwrap(), profiles);
+    }
+}
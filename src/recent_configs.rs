@@ -0,0 +1,79 @@
+use anyhow::Result;
+use kdl::KdlDocument;
+use std::path::{Path, PathBuf};
+
+use crate::config::kdl_value::string;
+use crate::state_file;
+
+const FILE: &str = "recent-configs.kdl";
+
+/// How many configs are remembered
+const MAX_CONFIGS: usize = 10;
+
+/// Config files recently opened with --config or the quick-open dialog, most
+/// recent first, kept across sessions in ~/.local/state/nirikiri/recent-configs.kdl
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RecentConfigs {
+    pub paths: Vec<PathBuf>,
+}
+
+impl RecentConfigs {
+    /// Load the list, starting empty when the file doesn't exist
+    pub fn load() -> Result<Self> {
+        Ok(state_file::load(FILE, "recent configs", Self::parse)?.unwrap_or_default())
+    }
+
+    /// Parse `config "/path"` nodes; anything else is ignored
+    pub fn parse(content: &str) -> Result<Self> {
+        let doc = KdlDocument::parse_v1(content)?;
+        let paths = doc
+            .nodes()
+            .iter()
+            .filter(|n| n.name().value() == "config")
+            .filter_map(|n| n.get(0)?.as_string().map(PathBuf::from))
+            .take(MAX_CONFIGS)
+            .collect();
+        Ok(Self { paths })
+    }
+
+    pub fn to_kdl(&self) -> String {
+        state_file::to_kdl(self.paths.iter().map(|p| state_file::node("config", [string(p.display().to_string())])))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        state_file::save(FILE, "recent configs", &self.to_kdl())
+    }
+
+    /// Move `path` to the front, returning false if it already was there
+    pub fn record(&mut self, path: &Path) -> bool {
+        if self.paths.first().is_some_and(|p| p == path) {
+            return false;
+        }
+        self.paths.retain(|p| p != path);
+        self.paths.insert(0, path.to_path_buf());
+        self.paths.truncate(MAX_CONFIGS);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recent_configs() {
+        let mut recent = RecentConfigs::parse("config \"/tmp/test.kdl\"\nconfig \"/home/me/niri.kdl\"\nother 1").unwrap();
+        assert_eq!(recent.paths, vec![PathBuf::from("/tmp/test.kdl"), PathBuf::from("/home/me/niri.kdl")]);
+
+        assert!(recent.record(Path::new("/home/me/niri.kdl")));
+        assert!(!recent.record(Path::new("/home/me/niri.kdl")));
+        assert_eq!(recent.paths[1], PathBuf::from("/tmp/test.kdl"));
+        assert_eq!(RecentConfigs::parse(&recent.to_kdl()).unwrap(), recent);
+
+        for i in 0..20 {
+            recent.record(&PathBuf::from(format!("/tmp/{i}.kdl")));
+        }
+        assert_eq!(recent.paths.len(), MAX_CONFIGS);
+        assert_eq!(recent.paths[0], PathBuf::from("/tmp/19.kdl"));
+    }
+}
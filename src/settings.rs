@@ -0,0 +1,199 @@
+use anyhow::{Context, Result};
+use kdl::KdlDocument;
+use std::path::PathBuf;
+
+use crate::config::kdl_value::{as_bool, as_float};
+use crate::hooks::{Hook, HookEvent};
+use crate::model::BindingTemplate;
+use crate::view::Density;
+
+/// nirikiri's own settings, read from ~/.config/nirikiri/settings.kdl
+#[derive(Debug, Clone, PartialEq)]
+pub struct Settings {
+    /// Commit the niri config after each save when it lives in a git repository
+    pub git_commit: bool,
+    /// How much of an active color the suggested inactive color keeps
+    pub inactive_color_factor: f64,
+    /// Write edits to nirikiri.kdl, included from the niri config, instead
+    /// of the config itself
+    pub managed_fragment: bool,
+    /// Brighter text; see also the --accessible flag
+    pub accessible: bool,
+    /// Compact or spacious lists
+    pub density: Density,
+    /// Language to show the UI in, e.g. "de"; the locale when unset
+    pub language: Option<String>,
+    /// Bind sets with placeholders, staged from the keybindings list
+    pub binding_templates: Vec<BindingTemplate>,
+    /// Commands run on save and reload events
+    pub hooks: Vec<Hook>,
+    /// Save a category's staged changes when leaving it or quitting
+    pub auto_save: bool,
+    /// Send output moves to niri as they happen; P toggles it for the session
+    pub live_preview: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            git_commit: false,
+            inactive_color_factor: 0.6,
+            managed_fragment: false,
+            accessible: false,
+            density: Density::Compact,
+            language: None,
+            binding_templates: Vec::new(),
+            hooks: Vec::new(),
+            auto_save: false,
+            live_preview: false,
+        }
+    }
+}
+
+impl Settings {
+    /// Load settings, falling back to defaults when the file doesn't exist
+    pub fn load() -> Result<Self> {
+        let Some(path) = settings_path() else {
+            return Ok(Self::default());
+        };
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read settings file: {}", path.display()))?;
+        Self::parse(&content)
+            .with_context(|| format!("Failed to parse settings file: {}", path.display()))
+    }
+
+    /// Parse settings from KDL; unknown nodes are ignored
+    pub fn parse(content: &str) -> Result<Self> {
+        let doc = KdlDocument::parse_v1(content)?;
+        let mut settings = Self::default();
+        for node in doc.nodes() {
+            match node.name().value() {
+                // A bare `git-commit` node turns it on
+                "git-commit" => settings.git_commit = node.get(0).is_none_or(|v| as_bool(v) == Some(true)),
+                "density" => {
+                    let name = node.get(0).and_then(|v| v.as_string()).unwrap_or_default();
+                    settings.density = Density::parse(name)
+                        .ok_or_else(|| anyhow::anyhow!("density must be \"compact\" or \"spacious\""))?;
+                }
+                "language" => settings.language = node.get(0).and_then(|v| v.as_string()).map(str::to_string),
+                "accessible" => settings.accessible = node.get(0).is_none_or(|v| as_bool(v) == Some(true)),
+                "auto-save" => settings.auto_save = node.get(0).is_none_or(|v| as_bool(v) == Some(true)),
+                "live-preview" => settings.live_preview = node.get(0).is_none_or(|v| as_bool(v) == Some(true)),
+                "managed-fragment" => {
+                    settings.managed_fragment = node.get(0).is_none_or(|v| as_bool(v) == Some(true))
+                }
+                "binding-template" => settings.binding_templates.push(parse_binding_template(node)?),
+                "hook" => settings.hooks.push(parse_hook(node)?),
+                "inactive-color-factor" => {
+                    let factor = node.get(0).and_then(as_float);
+                    match factor {
+                        Some(f) if (0.0..=1.0).contains(&f) => settings.inactive_color_factor = f,
+                        _ => anyhow::bail!("inactive-color-factor must be a number from 0 to 1"),
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(settings)
+    }
+}
+
+/// `binding-template "Name" var="default" { <bind nodes> }`
+fn parse_binding_template(node: &kdl::KdlNode) -> Result<BindingTemplate> {
+    let name = node
+        .get(0)
+        .and_then(|v| v.as_string())
+        .ok_or_else(|| anyhow::anyhow!("binding-template needs a name, e.g. binding-template \"App launcher\""))?;
+    let binds = node
+        .children()
+        .filter(|c| !c.nodes().is_empty())
+        .ok_or_else(|| anyhow::anyhow!("binding-template \"{name}\" has no binds"))?;
+    let body = binds.nodes().iter().map(|bind| bind.to_string().trim().to_string()).collect::<Vec<_>>().join("\n");
+    let defaults = node
+        .entries()
+        .iter()
+        .filter_map(|e| Some((e.name()?.value().to_string(), e.value().as_string()?.to_string())))
+        .collect();
+    Ok(BindingTemplate { name: name.to_string(), body, defaults })
+}
+
+/// `hook "event" "program" "args"...`
+fn parse_hook(node: &kdl::KdlNode) -> Result<Hook> {
+    let mut args = node.entries().iter().filter(|e| e.name().is_none()).map(|e| e.value().as_string());
+    let event = args
+        .next()
+        .flatten()
+        .and_then(HookEvent::parse)
+        .ok_or_else(|| anyhow::anyhow!("hook needs an event: \"pre-save\", \"post-save\" or \"post-reload\""))?;
+    let command = args
+        .map(|arg| arg.map(str::to_string))
+        .collect::<Option<Vec<_>>>()
+        .filter(|command| !command.is_empty())
+        .ok_or_else(|| anyhow::anyhow!("{} hook needs a command, given as strings", event.name()))?;
+    Ok(Hook { event, command })
+}
+
+fn settings_path() -> Option<PathBuf> {
+    Some(dirs::config_dir()?.join("nirikiri").join("settings.kdl"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_settings() {
+        assert_eq!(Settings::parse("").unwrap(), Settings::default());
+        assert!(Settings::parse("git-commit true").unwrap().git_commit);
+        assert!(Settings::parse("git-commit").unwrap().git_commit);
+        assert!(!Settings::parse("git-commit false").unwrap().git_commit);
+        assert!(Settings::parse("git-commit {").is_err());
+        assert_eq!(Settings::parse("inactive-color-factor 0.4").unwrap().inactive_color_factor, 0.4);
+        assert_eq!(Settings::parse("inactive-color-factor 1").unwrap().inactive_color_factor, 1.0);
+        assert!(Settings::parse("inactive-color-factor 2.5").is_err());
+        assert!(Settings::parse("managed-fragment").unwrap().managed_fragment);
+        assert!(Settings::parse("accessible true").unwrap().accessible);
+        assert_eq!(Settings::parse("density \"spacious\"").unwrap().density, Density::Spacious);
+        assert!(Settings::parse("density \"huge\"").is_err());
+        assert_eq!(Settings::parse("language \"de\"").unwrap().language.as_deref(), Some("de"));
+        assert!(Settings::parse("auto-save").unwrap().auto_save);
+        assert!(!Settings::parse("auto-save false").unwrap().auto_save);
+        assert!(Settings::parse("live-preview").unwrap().live_preview);
+    }
+
+    #[test]
+    fn test_parse_binding_templates() {
+        let settings = Settings::parse(
+            r#"
+            binding-template "App launcher" key="T" {
+                "Mod+{key}" { spawn "{cmd}"; }
+            }
+            "#,
+        )
+        .unwrap();
+        let template = &settings.binding_templates[0];
+        assert_eq!(template.name, "App launcher");
+        assert_eq!(template.defaults, vec![("key".to_string(), "T".to_string())]);
+        assert_eq!(template.variables(), vec!["key", "cmd"]);
+        assert!(Settings::parse("binding-template \"Empty\" {}").is_err());
+        assert!(Settings::parse("binding-template { \"Mod+T\" { spawn \"foot\"; }; }").is_err());
+    }
+
+    #[test]
+    fn test_parse_hooks() {
+        let settings = Settings::parse("hook \"post-save\" \"notify-send\" \"niri config saved\"").unwrap();
+        assert_eq!(
+            settings.hooks,
+            vec![Hook {
+                event: HookEvent::PostSave,
+                command: vec!["notify-send".to_string(), "niri config saved".to_string()],
+            }]
+        );
+        assert!(Settings::parse("hook \"on-quit\" \"true\"").is_err());
+        assert!(Settings::parse("hook \"pre-save\"").is_err());
+        assert!(Settings::parse("hook \"pre-save\" \"sleep\" 1").is_err());
+    }
+}
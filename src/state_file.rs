@@ -0,0 +1,97 @@
+//! Small KDL files nirikiri keeps its own state in, under
+//! ~/.local/state/nirikiri, so the niri config stays as is.
+
+use anyhow::{Context, Result};
+use kdl::{KdlDocument, KdlEntry, KdlNode, KdlValue};
+use std::path::PathBuf;
+
+use crate::config::kdl_format::{ensure_v1_new, format_node};
+
+/// Where the state file called `file` lives, None when the system has
+/// neither a state nor a data directory
+pub fn path(file: &str) -> Option<PathBuf> {
+    let dir = dirs::state_dir().or_else(dirs::data_dir)?;
+    Some(dir.join("nirikiri").join(file))
+}
+
+/// Read a state file and parse it, None when it doesn't exist yet. `what`
+/// names the file in errors, such as "color history".
+pub fn load<T>(file: &str, what: &str, parse: impl FnOnce(&str) -> Result<T>) -> Result<Option<T>> {
+    let Some(path) = path(file) else {
+        return Ok(None);
+    };
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content =
+        std::fs::read_to_string(&path).with_context(|| format!("Failed to read {what}: {}", path.display()))?;
+    parse(&content).map(Some).with_context(|| format!("Failed to parse {what}: {}", path.display()))
+}
+
+/// Write a state file whole. The content goes to a temporary file that is
+/// then renamed over the old one, so a crash never leaves it half written.
+pub fn save(file: &str, what: &str, content: &str) -> Result<()> {
+    let Some(path) = path(file) else {
+        return Ok(());
+    };
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    let temp = path.with_extension("kdl.tmp");
+    std::fs::write(&temp, content).with_context(|| format!("Failed to write {what}: {}", temp.display()))?;
+    std::fs::rename(&temp, &path).with_context(|| format!("Failed to write {what}: {}", path.display()))
+}
+
+/// A node with `args` as its arguments
+pub fn node(name: &str, args: impl IntoIterator<Item = KdlValue>) -> KdlNode {
+    let mut node = KdlNode::new(name);
+    for arg in args {
+        node.push(KdlEntry::new(arg));
+    }
+    node
+}
+
+/// Add a `key=value` property to `node`
+pub fn prop(node: &mut KdlNode, key: &str, value: KdlValue) {
+    node.push(KdlEntry::new_prop(key, value));
+}
+
+/// Print `nodes` as a KDL v1 document, one top-level node per line
+pub fn to_kdl(nodes: impl IntoIterator<Item = KdlNode>) -> String {
+    let mut doc = KdlDocument::new();
+    for mut node in nodes {
+        format_node(&mut node, 0);
+        doc.nodes_mut().push(node);
+    }
+    ensure_v1_new(&mut doc);
+    doc.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::kdl_value::{boolean, int, string};
+
+    #[test]
+    fn test_to_kdl() {
+        let mut output = node("output", [string("DP-1")]);
+        prop(&mut output, "label", string("Left 27\" \\ desk"));
+        prop(&mut output, "x", int(-1920));
+        let mut profile = node("profile", [string("home")]);
+        prop(&mut profile, "auto-apply", boolean(true));
+        let mut children = KdlDocument::new();
+        children.nodes_mut().push(output.clone());
+        profile.set_children(children);
+
+        let kdl = to_kdl([node("color", [string("#ff0000")]), output, profile]);
+        assert_eq!(
+            kdl,
+            "color \"#ff0000\"\n\
+             output \"DP-1\" label=\"Left 27\\\" \\\\ desk\" x=-1920\n\
+             profile \"home\" auto-apply=true {\n    output \"DP-1\" label=\"Left 27\\\" \\\\ desk\" x=-1920\n}\n"
+        );
+        let doc = KdlDocument::parse_v1(&kdl).unwrap();
+        assert_eq!(doc.nodes()[1].get("label").and_then(|v| v.as_string()), Some("Left 27\" \\ desk"));
+        assert_eq!(to_kdl([]), "");
+    }
+}
@@ -0,0 +1,137 @@
+use anyhow::Result;
+use kdl::KdlDocument;
+
+use crate::config::kdl_value::{as_int, int, string};
+use crate::category::Category;
+use crate::state_file;
+
+const FILE: &str = "ui-state.kdl";
+
+/// Outputs left panel width in columns, and how far one step moves it
+const PANEL_WIDTH: (u16, u16) = (18, 60);
+const PANEL_STEP: i32 = 2;
+/// List share of the list/detail split in percent, and one step of it
+const SPLIT_PERCENT: (u16, u16) = (25, 80);
+const SPLIT_STEP: i32 = 5;
+
+/// Pane sizes the user picked, kept across sessions in
+/// ~/.local/state/nirikiri/ui-state.kdl
+#[derive(Debug, Clone, PartialEq)]
+pub struct UiState {
+    /// Width of the output list and info panel left of the canvas
+    pub outputs_panel: u16,
+    /// Percentage of the width the keybindings list takes from its detail panel
+    pub keybindings_split: u16,
+    /// Percentage of the width the appearance list takes from its detail panel
+    pub appearance_split: u16,
+    /// Percentage of the width the general list takes from its detail panel
+    pub general_split: u16,
+}
+
+impl Default for UiState {
+    fn default() -> Self {
+        Self {
+            outputs_panel: 25,
+            keybindings_split: 55,
+            appearance_split: 55,
+            general_split: 55,
+        }
+    }
+}
+
+impl UiState {
+    /// Load the state, starting from the defaults when the file doesn't exist
+    pub fn load() -> Result<Self> {
+        Ok(state_file::load(FILE, "UI state", Self::parse)?.unwrap_or_default())
+    }
+
+    /// Parse `pane "category" size` nodes; unknown or out of range ones are ignored
+    pub fn parse(content: &str) -> Result<Self> {
+        let doc = KdlDocument::parse_v1(content)?;
+        let mut state = Self::default();
+        for node in doc.nodes().iter().filter(|n| n.name().value() == "pane") {
+            let category = node
+                .get(0)
+                .and_then(|v| v.as_string())
+                .and_then(|name| Category::all().iter().copied().find(|c| c.name().eq_ignore_ascii_case(name)));
+            let size = node.get(1).and_then(as_int::<u16>);
+            if let (Some(category), Some(size)) = (category, size) {
+                let (min, max) = bounds(category);
+                if (min..=max).contains(&size) {
+                    *state.pane_mut(category) = size;
+                }
+            }
+        }
+        Ok(state)
+    }
+
+    pub fn to_kdl(&self) -> String {
+        state_file::to_kdl(Category::all().iter().map(|&category| {
+            state_file::node("pane", [string(category.name().to_lowercase()), int(self.pane_size(category))])
+        }))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        state_file::save(FILE, "UI state", &self.to_kdl())
+    }
+
+    /// The outputs panel width, or the list percentage of the other categories
+    pub fn pane_size(&self, category: Category) -> u16 {
+        match category {
+            Category::Outputs => self.outputs_panel,
+            Category::Keybindings => self.keybindings_split,
+            Category::Appearance => self.appearance_split,
+            Category::General => self.general_split,
+        }
+    }
+
+    fn pane_mut(&mut self, category: Category) -> &mut u16 {
+        match category {
+            Category::Outputs => &mut self.outputs_panel,
+            Category::Keybindings => &mut self.keybindings_split,
+            Category::Appearance => &mut self.appearance_split,
+            Category::General => &mut self.general_split,
+        }
+    }
+
+    /// Widen (`steps` > 0) or narrow the category's left pane, within
+    /// limits. Returns false when it was already at the limit.
+    pub fn resize_pane(&mut self, category: Category, steps: i32) -> bool {
+        let (min, max) = bounds(category);
+        let step = match category {
+            Category::Outputs => PANEL_STEP,
+            _ => SPLIT_STEP,
+        };
+        let size = self.pane_mut(category);
+        let resized = (*size as i32 + steps * step).clamp(min as i32, max as i32) as u16;
+        let changed = resized != *size;
+        *size = resized;
+        changed
+    }
+}
+
+fn bounds(category: Category) -> (u16, u16) {
+    match category {
+        Category::Outputs => PANEL_WIDTH,
+        _ => SPLIT_PERCENT,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ui_state() {
+        let mut state = UiState::parse("pane \"keybindings\" 70\npane \"outputs\" 500\npane \"other\" 30").unwrap();
+        assert_eq!(state.keybindings_split, 70);
+        assert_eq!(state.outputs_panel, 25);
+
+        assert!(state.resize_pane(Category::Outputs, -1));
+        assert_eq!(state.pane_size(Category::Outputs), 23);
+        assert!(state.resize_pane(Category::Keybindings, 2));
+        assert_eq!(state.keybindings_split, 80);
+        assert!(!state.resize_pane(Category::Keybindings, 1));
+        assert_eq!(UiState::parse(&state.to_kdl()).unwrap(), state);
+    }
+}
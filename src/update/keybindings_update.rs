@@ -1,21 +1,25 @@
-use crate::message::Message;
+use crate::message::{KeybindingsMessage, Message};
 use crate::model::KeybindingsViewModel;
 
 /// Update keybindings view model based on message
 #[allow(dead_code)] // Available for external use, currently handled in app.rs
 pub fn update_keybindings(view_model: &mut KeybindingsViewModel, message: &Message) {
     match message {
-        Message::SelectNextKeybinding => {
+        Message::Keybindings(KeybindingsMessage::SelectNextKeybinding) => {
             view_model.select_next();
         }
-        Message::SelectPrevKeybinding => {
+        Message::Keybindings(KeybindingsMessage::SelectPrevKeybinding) => {
             view_model.select_prev();
         }
-        Message::SelectKeybinding(idx) => {
+        Message::Keybindings(KeybindingsMessage::PageDownKeybindings) => {
+            view_model.page_down();
+        }
+        Message::Keybindings(KeybindingsMessage::PageUpKeybindings) => {
+            view_model.page_up();
+        }
+        Message::Keybindings(KeybindingsMessage::SelectKeybinding(idx)) => {
             let count = view_model.visible_count();
-            if *idx < count {
-                view_model.selected_index = *idx;
-            }
+            view_model.list.select(*idx, count);
         }
         Message::StartSearch => {
             view_model.search_mode = true;
@@ -1,5 +1,5 @@
-use crate::message::Message;
-use crate::model::{OutputViewModel, Position, Size};
+use crate::message::{Message, OutputMessage};
+use crate::model::{ModeBrowser, OutputViewModel, Position, Size};
 
 /// Get the reference monitor (first other enabled monitor) for snap operations
 fn get_reference_monitor(view_model: &OutputViewModel) -> Option<(Position, Size)> {
@@ -12,101 +12,233 @@ fn get_reference_monitor(view_model: &OutputViewModel) -> Option<(Position, Size
             continue;
         }
         let pos = view_model.get_display_position(&output.name).unwrap_or(output.position);
-        return Some((pos, output.logical_size));
+        let size = view_model.staged_output(&output.name).map_or(output.logical_size, |o| o.logical_size);
+        return Some((pos, size));
     }
     None
 }
 
+/// Point an open mode browser at the selected output's current mode
+fn reset_mode_browser(view_model: &mut OutputViewModel) {
+    let Some(output) = view_model.outputs.get(view_model.list.selected_index) else {
+        return;
+    };
+    if let Some(browser) = view_model.mode_browser.as_mut() {
+        browser.refresh_filter = None;
+        browser.list.selected_index = output.current_mode_index.unwrap_or(0);
+    }
+}
+
 /// Process output-related messages
-pub fn update_output(view_model: &mut OutputViewModel, message: &Message) -> Option<Message> {
+pub fn update_output(view_model: &mut OutputViewModel, message: &OutputMessage) -> Option<Message> {
     match message {
-        Message::SelectNextOutput => {
+        OutputMessage::SelectNextOutput => {
             view_model.select_next();
+            reset_mode_browser(view_model);
             None
         }
-        Message::SelectPrevOutput => {
+        OutputMessage::SelectPrevOutput => {
             view_model.select_prev();
+            reset_mode_browser(view_model);
+            None
+        }
+        OutputMessage::OpenModeBrowser => {
+            view_model.mode_browser = Some(ModeBrowser::default());
+            reset_mode_browser(view_model);
+            None
+        }
+        OutputMessage::CloseModeBrowser => {
+            view_model.mode_browser = None;
+            None
+        }
+        OutputMessage::SelectNextMode => {
+            if let (Some(browser), Some(output)) = (
+                view_model.mode_browser.as_mut(),
+                view_model.outputs.get(view_model.list.selected_index),
+            ) {
+                browser.select_next(output);
+            }
+            None
+        }
+        OutputMessage::StageSelectedMode => {
+            let output = view_model.selected_output()?;
+            let mode = view_model
+                .mode_browser
+                .as_ref()
+                .and_then(|browser| browser.selected_mode_index(output))
+                .and_then(|idx| output.modes.get(idx))
+                .cloned();
+            if let Some(mode) = mode {
+                let name = output.name.clone();
+                view_model.stage_mode(&name, mode);
+                view_model.mode_browser = None;
+            }
             None
         }
-        Message::SelectOutput(idx) => {
-            if *idx < view_model.outputs.len() {
-                view_model.selected_index = *idx;
+        OutputMessage::SelectPrevMode => {
+            if let (Some(browser), Some(output)) = (
+                view_model.mode_browser.as_mut(),
+                view_model.outputs.get(view_model.list.selected_index),
+            ) {
+                browser.select_prev(output);
             }
             None
         }
-        Message::MoveOutput { dx, dy } => {
+        OutputMessage::CycleRefreshFilter => {
+            if let (Some(browser), Some(output)) = (
+                view_model.mode_browser.as_mut(),
+                view_model.outputs.get(view_model.list.selected_index),
+            ) {
+                browser.cycle_refresh_filter(output);
+            }
+            None
+        }
+        OutputMessage::SelectOutput(idx) => {
+            view_model.list.select(*idx, view_model.outputs.len());
+            None
+        }
+        OutputMessage::ToggleOutputPane => {
+            view_model.focused_pane = view_model.focused_pane.toggle();
+            None
+        }
+        OutputMessage::MoveOutput { dx, dy } => {
             if let Some(output) = view_model.selected_output() {
                 let name = output.name.clone();
-                let current_pos = view_model
-                    .pending_changes
-                    .get(&name)
-                    .copied()
-                    .unwrap_or(output.position);
+                let current_pos = view_model.pending_position(&name).unwrap_or(output.position);
 
                 let new_pos = Position::new(current_pos.x + dx, current_pos.y + dy);
                 view_model.apply_pending_change(&name, new_pos);
             }
             None
         }
-        Message::SetPosition { x, y } => {
+        OutputMessage::StepScale(steps) => {
+            if let Some(output) = view_model.selected_output() {
+                let name = output.name.clone();
+                view_model.step_scale(&name, *steps);
+            }
+            None
+        }
+        OutputMessage::StageSuggestedScale => {
+            view_model.stage_suggested_scale();
+            None
+        }
+        OutputMessage::CycleTransform => {
+            if let Some(output) = view_model.selected_output() {
+                let name = output.name.clone();
+                view_model.cycle_transform(&name);
+            }
+            None
+        }
+        OutputMessage::ToggleVrr => {
+            if let Some(output) = view_model.selected_output() {
+                let name = output.name.clone();
+                view_model.toggle_vrr(&name);
+            }
+            None
+        }
+        OutputMessage::ToggleFocusAtStartup => {
+            if let Some(output) = view_model.selected_output() {
+                let name = output.name.clone();
+                view_model.toggle_focus_at_startup(&name);
+            }
+            None
+        }
+        OutputMessage::SetPosition { x, y } => {
             if let Some(output) = view_model.selected_output() {
                 let name = output.name.clone();
                 view_model.apply_pending_change(&name, Position::new(*x, *y));
             }
             None
         }
-        Message::SnapLeft => {
-            if let (Some(output), Some((ref_pos, _ref_size))) =
+        OutputMessage::SnapLeft { axis_lock } => {
+            if let (Some(output), Some((ref_pos, ref_size))) =
                 (view_model.selected_output(), get_reference_monitor(view_model))
             {
                 let name = output.name.clone();
-                let my_size = output.logical_size;
-                // Place to the left of reference, align top edges
+                let current = view_model.get_display_position(&name).unwrap_or(output.position);
+                let my_size = view_model.staged_output(&name).map_or(output.logical_size, |o| o.logical_size);
+                // Place to the left of reference, edges aligned as chosen
                 let new_x = ref_pos.x - my_size.width as i32;
-                let new_y = ref_pos.y;
+                let new_y = if *axis_lock {
+                    current.y
+                } else {
+                    view_model.snap_alignment.beside.place(ref_pos.y, ref_size.height, my_size.height)
+                };
                 view_model.apply_pending_change(&name, Position::new(new_x, new_y));
             }
             None
         }
-        Message::SnapRight => {
+        OutputMessage::SnapRight { axis_lock } => {
             if let (Some(output), Some((ref_pos, ref_size))) =
                 (view_model.selected_output(), get_reference_monitor(view_model))
             {
                 let name = output.name.clone();
-                // Place to the right of reference, align top edges
+                let current = view_model.get_display_position(&name).unwrap_or(output.position);
+                let my_size = view_model.staged_output(&name).map_or(output.logical_size, |o| o.logical_size);
+                // Place to the right of reference, edges aligned as chosen
                 let new_x = ref_pos.x + ref_size.width as i32;
-                let new_y = ref_pos.y;
+                let new_y = if *axis_lock {
+                    current.y
+                } else {
+                    view_model.snap_alignment.beside.place(ref_pos.y, ref_size.height, my_size.height)
+                };
                 view_model.apply_pending_change(&name, Position::new(new_x, new_y));
             }
             None
         }
-        Message::SnapAbove => {
+        OutputMessage::SnapAbove { axis_lock } => {
             if let (Some(output), Some((ref_pos, ref_size))) =
                 (view_model.selected_output(), get_reference_monitor(view_model))
             {
                 let name = output.name.clone();
-                let my_size = output.logical_size;
-                // Center horizontally relative to reference, place above
-                let new_x = ref_pos.x + (ref_size.width as i32 - my_size.width as i32) / 2;
+                let current = view_model.get_display_position(&name).unwrap_or(output.position);
+                let my_size = view_model.staged_output(&name).map_or(output.logical_size, |o| o.logical_size);
+                // Align horizontally as chosen (centered by default), place above
+                let new_x = if *axis_lock {
+                    current.x
+                } else {
+                    view_model.snap_alignment.stacked.place(ref_pos.x, ref_size.width, my_size.width)
+                };
                 let new_y = ref_pos.y - my_size.height as i32;
                 view_model.apply_pending_change(&name, Position::new(new_x, new_y));
             }
             None
         }
-        Message::SnapBelow => {
+        OutputMessage::SnapBelow { axis_lock } => {
             if let (Some(output), Some((ref_pos, ref_size))) =
                 (view_model.selected_output(), get_reference_monitor(view_model))
             {
                 let name = output.name.clone();
-                let my_size = output.logical_size;
-                // Center horizontally relative to reference, place below
-                let new_x = ref_pos.x + (ref_size.width as i32 - my_size.width as i32) / 2;
+                let current = view_model.get_display_position(&name).unwrap_or(output.position);
+                let my_size = view_model.staged_output(&name).map_or(output.logical_size, |o| o.logical_size);
+                // Align horizontally as chosen (centered by default), place below
+                let new_x = if *axis_lock {
+                    current.x
+                } else {
+                    view_model.snap_alignment.stacked.place(ref_pos.x, ref_size.width, my_size.width)
+                };
                 let new_y = ref_pos.y + ref_size.height as i32;
                 view_model.apply_pending_change(&name, Position::new(new_x, new_y));
             }
             None
         }
-        Message::Normalize => {
+        OutputMessage::CycleSnapAlignment { stacked } => {
+            let alignment = &mut view_model.snap_alignment;
+            if *stacked {
+                alignment.stacked = alignment.stacked.next();
+            } else {
+                alignment.beside = alignment.beside.next();
+            }
+            None
+        }
+        OutputMessage::MoveToOrigin => {
+            if let Some(output) = view_model.selected_output() {
+                let name = output.name.clone();
+                view_model.apply_pending_change(&name, Position::new(0, 0));
+            }
+            None
+        }
+        OutputMessage::Normalize => {
             // Find minimum x and y across all enabled outputs
             let mut min_x = i32::MAX;
             let mut min_y = i32::MAX;
@@ -141,6 +273,10 @@ pub fn update_output(view_model: &mut OutputViewModel, message: &Message) -> Opt
             }
             None
         }
+        OutputMessage::Arrange(arrangement) => {
+            view_model.arrange(*arrangement);
+            None
+        }
         _ => None,
     }
 }
@@ -5,6 +5,7 @@ use ratatui::{
     widgets::{Block, Borders, Widget},
 };
 
+use crate::i18n::tr;
 use crate::model::{AppearanceField, AppearanceListItem, AppearanceSection, AppearanceViewModel, ColorValue, FieldValue};
 
 /// Parse a hex color string to a ratatui Color
@@ -56,7 +57,7 @@ impl Widget for AppearanceDetailWidget<'_> {
         let block = Block::default()
             .borders(Borders::ALL)
             .border_style(Style::default().fg(Color::DarkGray))
-            .title(" Details ");
+            .title(format!(" {} ", tr("Details")));
 
         let inner = block.inner(area);
         block.render(area, buf);
@@ -110,6 +111,7 @@ impl AppearanceDetailWidget<'_> {
             AppearanceSection::Border => "Configure window borders that are always visible (unlike focus ring). Enable with 'on', disable with 'off'.",
             AppearanceSection::Shadow => "Configure drop shadows for windows. Enable with 'on'. Shadows are drawn behind windows.",
             AppearanceSection::Struts => "Configure outer gaps (struts) that shrink the usable window area, similar to panel margins.",
+            AppearanceSection::Overview => "The top-level overview block: how far the workspace overview zooms out, the color behind it, and the shadow around each workspace.",
         };
 
         if y < area.y + area.height {
@@ -132,7 +134,7 @@ impl AppearanceDetailWidget<'_> {
         if y < area.y + area.height {
             let field_count = section.fields().len();
             buf.set_string(area.x + 1, y, "Settings:", label_style);
-            buf.set_string(area.x + 11, y, &format!("{field_count}"), value_style);
+            buf.set_string(area.x + 11, y, format!("{field_count}"), value_style);
             y += 1;
         }
 
@@ -168,8 +170,38 @@ impl AppearanceDetailWidget<'_> {
         if y < area.y + area.height {
             buf.set_string(area.x + 1, y, "Section:", label_style);
             buf.set_string(area.x + 10, y, field.section().name(), dim_style);
-            y += 2;
+            y += 1;
+        }
+
+        // Where the field is set in the config
+        if y < area.y + area.height {
+            buf.set_string(area.x + 1, y, "Defined at:", label_style);
+            match self.view_model.field_sources.get(&field) {
+                Some(loc) => buf.set_string(area.x + 13, y, loc.to_string(), dim_style),
+                None => buf.set_string(area.x + 13, y, "(not in config, niri default)", dim_style),
+            }
+            y += 1;
+        }
+
+        // niri's own value for the field, when this one differs
+        if y < area.y + area.height && !self.view_model.is_field_default(field) {
+            let default = self.view_model.defaults.get(field);
+            let release = self
+                .view_model
+                .defaults_release
+                .as_deref()
+                .map(|r| format!(" (niri {r}, x to reset)"))
+                .unwrap_or_default();
+            buf.set_string(area.x + 1, y, "Default:", label_style);
+            buf.set_stringn(
+                area.x + 10,
+                y,
+                format!("{default}{release}"),
+                area.width.saturating_sub(11) as usize,
+                dim_style,
+            );
         }
+        y += 2;
 
         // Current value
         if y < area.y + area.height {
@@ -233,7 +265,7 @@ impl AppearanceDetailWidget<'_> {
                             if let Some(a) = angle {
                                 y += 1;
                                 if y < area.y + area.height {
-                                    buf.set_string(area.x + 3, y, &format!("angle: {a}°"), dim_style);
+                                    buf.set_string(area.x + 3, y, format!("angle: {a}°"), dim_style);
                                 }
                             }
                         }
@@ -292,7 +324,15 @@ impl AppearanceDetailWidget<'_> {
             };
             buf.set_string(area.x + 1, y, "Type:", label_style);
             buf.set_string(area.x + 7, y, type_str, dim_style);
-            y += 2;
+            y += 1;
+            if let Some(bounds) = field.bounds() {
+                if y < area.y + area.height {
+                    buf.set_string(area.x + 1, y, "Range:", label_style);
+                    buf.set_string(area.x + 8, y, bounds.to_string(), dim_style);
+                    y += 1;
+                }
+            }
+            y += 1;
         }
 
         // Description
@@ -312,16 +352,15 @@ impl AppearanceDetailWidget<'_> {
         y += 1;
 
         // Modification status
-        if self.view_model.is_field_modified(field) {
-            if y < area.y + area.height {
-                buf.set_string(
-                    area.x + 1,
-                    y,
-                    "* Modified (unsaved)",
-                    Style::default().fg(Color::Cyan).add_modifier(Modifier::ITALIC),
-                );
-                y += 1;
-            }
+        if let Some(change) = self.view_model.pending_change(field).filter(|_| y < area.y + area.height) {
+            buf.set_stringn(
+                area.x + 1,
+                y,
+                format!("* Modified (unsaved), was {}", change.original),
+                max_width,
+                Style::default().fg(Color::Cyan).add_modifier(Modifier::ITALIC),
+            );
+            y += 1;
         }
 
         y += 1;
@@ -348,7 +387,7 @@ impl AppearanceDetailWidget<'_> {
 }
 
 /// Simple word wrapping for text
-fn wrap_text(text: &str, max_width: usize) -> Vec<String> {
+pub(super) fn wrap_text(text: &str, max_width: usize) -> Vec<String> {
     let mut lines = Vec::new();
     let mut current_line = String::new();
 
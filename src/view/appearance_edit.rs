@@ -5,10 +5,11 @@ use ratatui::{
     widgets::{Block, Borders, Clear, Widget},
 };
 
+use crate::i18n::tr_args;
 use crate::model::{AppearanceEditMode, AppearanceField, ColorEditField};
 
 /// Parse a hex color string to a ratatui Color
-fn parse_hex_color(s: &str) -> Option<Color> {
+pub(super) fn parse_hex_color(s: &str) -> Option<Color> {
     let s = s.trim_start_matches('#');
     match s.len() {
         3 => {
@@ -42,11 +43,51 @@ fn parse_hex_color(s: &str) -> Option<Color> {
 /// Widget for editing an appearance setting in a modal dialog
 pub struct AppearanceEditWidget<'a> {
     edit_mode: &'a AppearanceEditMode,
+    /// KDL the layout block will contain for this field, None if the value is invalid
+    preview: Option<String>,
 }
 
 impl<'a> AppearanceEditWidget<'a> {
     pub fn new(edit_mode: &'a AppearanceEditMode) -> Self {
-        Self { edit_mode }
+        Self {
+            edit_mode,
+            preview: None,
+        }
+    }
+
+    pub fn with_preview(mut self, preview: Option<String>) -> Self {
+        self.preview = preview;
+        self
+    }
+
+    fn preview_lines(&self) -> Vec<&str> {
+        match &self.preview {
+            Some(kdl) => kdl.lines().collect(),
+            None => vec!["(invalid value)"],
+        }
+    }
+
+    /// Draw the KDL preview starting at `y`, stopping before `bottom`
+    fn render_preview(&self, buf: &mut Buffer, inner: Rect, y: &mut u16, bottom: u16) {
+        if *y >= bottom {
+            return;
+        }
+        buf.set_string(inner.x + 1, *y, "In layout:", Style::default().fg(Color::Gray));
+        *y += 1;
+        let style = if self.preview.is_some() {
+            Style::default().fg(Color::Green)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        let max_width = inner.width.saturating_sub(4) as usize;
+        for line in self.preview_lines() {
+            if *y >= bottom {
+                break;
+            }
+            let line: String = line.chars().take(max_width).collect();
+            buf.set_string(inner.x + 3, *y, &line, style);
+            *y += 1;
+        }
     }
 }
 
@@ -64,14 +105,14 @@ impl Widget for AppearanceEditWidget<'_> {
 impl AppearanceEditWidget<'_> {
     fn render_simple_editor(&self, area: Rect, buf: &mut Buffer) {
         let dialog_width = 50.min(area.width.saturating_sub(4));
-        let dialog_height = 10.min(area.height.saturating_sub(2));
+        let dialog_height = (11 + self.preview_lines().len() as u16).min(area.height.saturating_sub(2));
         let dialog_x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
         let dialog_y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
 
         let dialog_area = Rect::new(dialog_x, dialog_y, dialog_width, dialog_height);
         Clear.render(dialog_area, buf);
 
-        let title = format!(" Edit: {} ", self.edit_mode.field.name());
+        let title = format!(" {} ", tr_args("Edit: {name}", &[("name", &self.edit_mode.field.name())]));
         let block = Block::default()
             .borders(Borders::ALL)
             .border_style(Style::default().fg(Color::Cyan))
@@ -106,7 +147,7 @@ impl AppearanceEditWidget<'_> {
         // Input field label
         if y < inner.y + inner.height {
             let type_label = if self.edit_mode.field.is_integer() {
-                "Value (integer):"
+                "Value (integer, or arithmetic like 1920/2):"
             } else {
                 "Value:"
             };
@@ -135,6 +176,10 @@ impl AppearanceEditWidget<'_> {
             y += 2;
         }
 
+        let bottom = (inner.y + inner.height).saturating_sub(2);
+        self.render_preview(buf, inner, &mut y, bottom);
+        y += 1;
+
         // Help text
         if y < inner.y + inner.height {
             buf.set_string(
@@ -151,7 +196,13 @@ impl AppearanceEditWidget<'_> {
 
         // Larger dialog for color editing
         let dialog_width = 60.min(area.width.saturating_sub(4));
-        let dialog_height = if cs.is_gradient { 18 } else { 12 };
+        let palette_height = if cs.palette.is_empty() { 0 } else { 2 };
+        let inactive_height = if self.edit_mode.field.inactive_counterpart().is_some() { 2 } else { 0 };
+        let dialog_height = if cs.is_gradient { 18 } else { 12 }
+            + 1
+            + palette_height
+            + inactive_height
+            + self.preview_lines().len() as u16;
         let dialog_height = dialog_height.min(area.height.saturating_sub(2));
         let dialog_x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
         let dialog_y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
@@ -159,7 +210,7 @@ impl AppearanceEditWidget<'_> {
         let dialog_area = Rect::new(dialog_x, dialog_y, dialog_width, dialog_height);
         Clear.render(dialog_area, buf);
 
-        let title = format!(" Edit: {} ", self.edit_mode.field.name());
+        let title = format!(" {} ", tr_args("Edit: {name}", &[("name", &self.edit_mode.field.name())]));
         let block = Block::default()
             .borders(Borders::ALL)
             .border_style(Style::default().fg(Color::Cyan))
@@ -196,6 +247,11 @@ impl AppearanceEditWidget<'_> {
         buf.set_string(inner.x + 15, y, " Gradient ", gradient_style);
         y += 2;
 
+        if !cs.palette.is_empty() {
+            self.render_palette(buf, inner, y);
+            y += 2;
+        }
+
         if cs.is_gradient {
             // Gradient editing
             self.render_gradient_fields(buf, inner, &mut y, input_width);
@@ -204,6 +260,14 @@ impl AppearanceEditWidget<'_> {
             self.render_solid_field(buf, inner, &mut y, input_width);
         }
 
+        if let Some(inactive) = self.edit_mode.field.inactive_counterpart() {
+            self.render_inactive_suggestion(buf, inner, y, inactive);
+            y += 2;
+        }
+
+        let bottom = (inner.y + inner.height).saturating_sub(2);
+        self.render_preview(buf, inner, &mut y, bottom);
+
         // Help text
         y += 1;
         if y < inner.y + inner.height {
@@ -216,6 +280,63 @@ impl AppearanceEditWidget<'_> {
         }
     }
 
+    /// Quick-pick row: the theme's other colors and recent ones, numbered
+    /// for Alt+1..9
+    fn render_palette(&self, buf: &mut Buffer, inner: Rect, y: u16) {
+        let cs = self.edit_mode.color_state.as_ref().unwrap();
+        let hint_style = Style::default().fg(Color::DarkGray);
+        buf.set_string(inner.x + 1, y, "Pick:", Style::default().fg(Color::Gray));
+        let mut x = inner.x + 7;
+        for (i, color) in cs.palette.iter().enumerate() {
+            if x + 4 > inner.x + inner.width {
+                break;
+            }
+            buf.set_string(x, y, (i + 1).to_string(), hint_style);
+            match parse_hex_color(color) {
+                Some(c) => buf.set_string(x + 1, y, "  ", Style::default().bg(c)),
+                None => {
+                    buf.set_stringn(x + 1, y, color, 2, Style::default().fg(Color::White));
+                }
+            }
+            x += 4;
+        }
+        if x + 8 <= inner.x + inner.width {
+            buf.set_string(x, y, "(Alt+N)", hint_style);
+        }
+    }
+
+    /// The dimmed color Alt+i would stage for the inactive counterpart
+    fn render_inactive_suggestion(&self, buf: &mut Buffer, inner: Rect, y: u16, inactive: AppearanceField) {
+        if y >= inner.y + inner.height {
+            return;
+        }
+        let hint_style = Style::default().fg(Color::DarkGray);
+        let (check, label_style) = if self.edit_mode.suggest_inactive {
+            ("[x]", Style::default().fg(Color::Yellow))
+        } else {
+            ("[ ]", Style::default().fg(Color::Gray))
+        };
+        let label = format!("{check} Also set {}:", inactive.name());
+        buf.set_string(inner.x + 1, y, &label, label_style);
+        let x = inner.x + 2 + label.chars().count() as u16;
+        match self.edit_mode.inactive_suggestion() {
+            Some((_, suggestion)) => {
+                let mut x = x;
+                for color in suggestion.colors() {
+                    if let Some(c) = parse_hex_color(color) {
+                        buf.set_string(x, y, "  ", Style::default().bg(c));
+                    }
+                    buf.set_string(x + 3, y, color, Style::default().fg(Color::White));
+                    x += 4 + color.len() as u16;
+                }
+                buf.set_string(x, y, "(Alt+i)", hint_style);
+            }
+            None => {
+                buf.set_string(x, y, "needs a hex color", hint_style);
+            }
+        }
+    }
+
     fn render_solid_field(&self, buf: &mut Buffer, inner: Rect, y: &mut u16, input_width: usize) {
         let cs = self.edit_mode.color_state.as_ref().unwrap();
         let label_style = Style::default().fg(Color::Gray);
@@ -435,20 +556,16 @@ impl AppearanceEditWidget<'_> {
             return;
         }
 
-        let text_len = text.len();
+        let text_len = text.chars().count();
         let visible_width = inner_width.saturating_sub(1);
 
-        let scroll_offset = if cursor_pos <= visible_width {
-            0
-        } else {
-            cursor_pos - visible_width
-        };
+        let scroll_offset = cursor_pos.saturating_sub(visible_width);
 
         let visible_end = (scroll_offset + visible_width).min(text_len);
-        let visible_text = &text[scroll_offset..visible_end];
+        let visible_text: String = text.chars().skip(scroll_offset).take(visible_width).collect();
 
         let text_style = bg_style.fg(Color::White);
-        buf.set_string(inner_x, y, visible_text, text_style);
+        buf.set_string(inner_x, y, &visible_text, text_style);
 
         if focused {
             let cursor_screen_pos = cursor_pos - scroll_offset;
@@ -461,7 +578,7 @@ impl AppearanceEditWidget<'_> {
             };
 
             let cursor_style = Style::default().bg(Color::Yellow).fg(Color::Black);
-            buf.set_string(cursor_x, y, &cursor_char.to_string(), cursor_style);
+            buf.set_string(cursor_x, y, cursor_char.to_string(), cursor_style);
         }
 
         if scroll_offset > 0 {
@@ -5,7 +5,11 @@ use ratatui::{
     widgets::{Block, Borders, Widget},
 };
 
+use super::Density;
+use crate::i18n::{tr, tr_args};
 use crate::model::{AppearanceField, AppearanceListItem, AppearanceSection, AppearanceViewModel, ColorValue, FieldValue};
+use crate::widgets::table::fit;
+use unicode_width::UnicodeWidthStr;
 
 /// Parse a hex color string to a ratatui Color
 fn parse_hex_color(s: &str) -> Option<Color> {
@@ -48,25 +52,34 @@ fn parse_hex_color(s: &str) -> Option<Color> {
 pub struct AppearanceListWidget<'a> {
     view_model: &'a AppearanceViewModel,
     focused: bool,
+    density: Density,
 }
 
 impl<'a> AppearanceListWidget<'a> {
     pub fn new(view_model: &'a AppearanceViewModel, focused: bool) -> Self {
-        Self { view_model, focused }
+        Self { view_model, focused, density: Density::Compact }
+    }
+
+    pub fn density(mut self, density: Density) -> Self {
+        self.density = density;
+        self
     }
 }
 
 impl Widget for AppearanceListWidget<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let items = self.view_model.visible_items();
-        let count = items.len();
+        let count = self.view_model.iter_visible_items().count();
 
         // Draw border with count
         let modified_count = self.view_model.pending_changes.len();
         let title = if modified_count > 0 {
-            format!(" Appearance ({count}) *{modified_count} modified ")
+            format!(
+                " {} ({count}) *{} ",
+                tr("Appearance"),
+                tr_args("{count} modified", &[("count", &modified_count)])
+            )
         } else {
-            format!(" Appearance ({count}) ")
+            format!(" {} ({count}) ", tr("Appearance"))
         };
 
         let border_style = if self.focused {
@@ -78,6 +91,7 @@ impl Widget for AppearanceListWidget<'_> {
         let block = Block::default()
             .borders(Borders::ALL)
             .border_style(border_style)
+            .padding(self.density.padding())
             .title(title);
 
         let inner = block.inner(area);
@@ -89,31 +103,32 @@ impl Widget for AppearanceListWidget<'_> {
 
         // Calculate visible range
         let visible_height = inner.height as usize;
-        let scroll_offset = self.view_model.scroll_offset;
+        let scroll_offset = self.view_model.list.scroll_offset;
 
         // Render visible items
-        for (i, item) in items
-            .iter()
+        for (i, item) in self
+            .view_model
+            .iter_visible_items()
             .skip(scroll_offset)
             .take(visible_height)
             .enumerate()
         {
             let y = inner.y + i as u16;
-            let is_selected = scroll_offset + i == self.view_model.selected_index;
+            let is_selected = scroll_offset + i == self.view_model.list.selected_index;
 
             match item {
                 AppearanceListItem::SectionHeader(section) => {
-                    self.render_section_header(buf, inner.x, y, inner.width, *section, is_selected);
+                    self.render_section_header(buf, inner.x, y, inner.width, section, is_selected);
                 }
                 AppearanceListItem::Field(field) => {
-                    let is_modified = self.view_model.is_field_modified(*field);
-                    let value = self.view_model.get_field_value(*field);
+                    let is_modified = self.view_model.is_field_modified(field);
+                    let value = self.view_model.get_field_value(field);
                     self.render_field(
                         buf,
                         inner.x,
                         y,
                         inner.width,
-                        *field,
+                        field,
                         &value,
                         is_selected,
                         is_modified,
@@ -181,6 +196,20 @@ impl AppearanceListWidget<'_> {
         buf.set_string(x, y, indicator, style);
         buf.set_string(x + 2, y, collapse_char, style);
         buf.set_string(x + 4, y, name, style);
+        if self.view_model.has_section_changes(section) {
+            let count = self
+                .view_model
+                .pending_changes
+                .keys()
+                .filter(|field| field.section() == section)
+                .count();
+            buf.set_string(
+                x + 5 + name.width() as u16,
+                y,
+                format!("({count} staged)"),
+                Style::default().fg(Color::Cyan),
+            );
+        }
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -209,15 +238,15 @@ impl AppearanceListWidget<'_> {
         let has_color_preview = field.is_color();
         let color_preview_width = if has_color_preview { 4 } else { 0 }; // "██ "
         let available_width = width.saturating_sub(4 + color_preview_width as u16) as usize;
-        let name_width = (available_width * 55 / 100).min(name.len() + 2);
+        let name_width = (available_width * 55 / 100).min(name.width() + 2);
         let value_width = available_width.saturating_sub(name_width);
 
+        // Values the config leaves to niri are dimmed and labelled
+        let inherited = !is_modified && !self.view_model.is_field_explicit(field);
+        let value_width = if inherited { value_width.saturating_sub(10) } else { value_width };
+
         // Truncate name if needed
-        let name_display = if name.len() > name_width {
-            format!("{}...", &name[..name_width.saturating_sub(3)])
-        } else {
-            format!("{name:name_width$}")
-        };
+        let name_display = fit(name, name_width);
 
         // Styles
         let name_style = if is_selected && self.focused {
@@ -236,8 +265,10 @@ impl AppearanceListWidget<'_> {
             Style::default().fg(Color::Yellow)
         } else if is_modified {
             Style::default().fg(Color::Cyan)
-        } else {
+        } else if inherited {
             Style::default().fg(Color::DarkGray)
+        } else {
+            Style::default().fg(Color::Gray)
         };
 
         let indicator_style = if is_modified {
@@ -288,17 +319,13 @@ impl AppearanceListWidget<'_> {
                 // Render color value text
                 let text_x = value_x + 3;
                 let remaining_width = value_width.saturating_sub(3);
-                let value_display = if color_str.len() > remaining_width {
-                    format!("{}...", &color_str[..remaining_width.saturating_sub(3)])
-                } else {
-                    color_str
-                };
+                let value_display = fit(&color_str, remaining_width);
                 buf.set_string(text_x, y, &value_display, value_style);
             }
             FieldValue::Enum(e) => {
                 // Show enum with arrows to indicate it's cyclable
                 let enum_display = format!("◀ {} ▶", e);
-                let display = if enum_display.len() > value_width {
+                let display = if enum_display.width() > value_width {
                     e.to_string()
                 } else {
                     enum_display
@@ -308,13 +335,17 @@ impl AppearanceListWidget<'_> {
             _ => {
                 // Default: just show the value string
                 let value_str = value.to_string();
-                let value_display = if value_str.len() > value_width {
-                    format!("{}...", &value_str[..value_width.saturating_sub(3)])
-                } else {
-                    value_str
-                };
+                let value_display = fit(&value_str, value_width);
                 buf.set_string(value_x, y, &value_display, value_style);
             }
         }
+
+        if inherited {
+            let label = "(default)";
+            let label_x = x + width.saturating_sub(label.width() as u16 + 1);
+            if label_x > value_x + 8 {
+                buf.set_string(label_x, y, label, Style::default().fg(Color::DarkGray));
+            }
+        }
     }
 }
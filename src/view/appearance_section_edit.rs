@@ -0,0 +1,125 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Widget},
+};
+
+use super::appearance_edit::parse_hex_color;
+use crate::i18n::tr_args;
+use crate::model::{SectionEditMode, SectionEditValue};
+
+/// Width of the field name column
+const LABEL_WIDTH: u16 = 20;
+
+/// Form editing every field of one appearance section
+pub struct AppearanceSectionEditWidget<'a> {
+    edit: &'a SectionEditMode,
+}
+
+impl<'a> AppearanceSectionEditWidget<'a> {
+    pub fn new(edit: &'a SectionEditMode) -> Self {
+        Self { edit }
+    }
+}
+
+impl Widget for AppearanceSectionEditWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let dialog_width = 60.min(area.width.saturating_sub(4));
+        let dialog_height = (self.edit.rows.len() as u16 + 7).min(area.height.saturating_sub(2));
+        let dialog_x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
+        let dialog_y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
+
+        let dialog_area = Rect::new(dialog_x, dialog_y, dialog_width, dialog_height);
+        Clear.render(dialog_area, buf);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(format!(" {} ", tr_args("Edit section: {name}", &[("name", &self.edit.section.name())])));
+
+        let inner = block.inner(dialog_area);
+        block.render(dialog_area, buf);
+
+        if inner.height < 4 || inner.width < 40 {
+            return;
+        }
+
+        let label_style = Style::default().fg(Color::Gray);
+        let focused_style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+        let hint_style = Style::default().fg(Color::DarkGray);
+        let value_x = inner.x + 2 + LABEL_WIDTH;
+        let value_width = inner.width.saturating_sub(LABEL_WIDTH + 4) as usize;
+        let bottom = inner.y + inner.height;
+
+        for (y, (i, row)) in (inner.y..).zip(self.edit.rows.iter().enumerate()) {
+            if y >= bottom.saturating_sub(3) {
+                break;
+            }
+            let focused = i == self.edit.focused;
+            let marker = if row.is_modified() { "*" } else { " " };
+            buf.set_string(inner.x, y, marker, Style::default().fg(Color::Yellow));
+            buf.set_stringn(
+                inner.x + 1,
+                y,
+                row.field.name(),
+                LABEL_WIDTH as usize,
+                if focused { focused_style } else { label_style },
+            );
+
+            match &row.value {
+                SectionEditValue::Choice(value) => {
+                    let style = if focused {
+                        Style::default().fg(Color::Black).bg(Color::Yellow)
+                    } else {
+                        Style::default().fg(Color::White)
+                    };
+                    buf.set_stringn(value_x, y, format!("‹ {value} ›"), value_width, style);
+                }
+                SectionEditValue::Text(text) => {
+                    let mut x = value_x;
+                    if row.field.is_color() {
+                        if let Some(color) = parse_hex_color(&text.value) {
+                            buf.set_string(x, y, "  ", Style::default().bg(color));
+                        }
+                        x += 3;
+                    }
+                    let width = value_width.saturating_sub((x - value_x) as usize);
+                    let style = if focused {
+                        Style::default().bg(Color::DarkGray)
+                    } else {
+                        Style::default().fg(Color::White)
+                    };
+                    let shown = if text.value.is_empty() && !focused { "(not set)" } else { text.value.as_str() };
+                    buf.set_stringn(x, y, format!("{shown:width$}"), width, style);
+                    if focused && text.cursor < width {
+                        let under = text.value.chars().nth(text.cursor).unwrap_or(' ');
+                        let cursor_style = Style::default().bg(Color::Yellow).fg(Color::Black);
+                        buf.set_string(x + text.cursor as u16, y, under.to_string(), cursor_style);
+                    }
+                }
+                SectionEditValue::Fixed(value) => {
+                    buf.set_stringn(value_x, y, format!("{value} (Enter on the field to edit)"), value_width, hint_style);
+                }
+            }
+        }
+
+        // Why the form can't be staged yet
+        let status_y = bottom.saturating_sub(2);
+        let status = match self.edit.changed_values() {
+            Ok(changes) if changes.is_empty() => Span::styled("No changes", hint_style),
+            Ok(changes) => Span::styled(format!("{} field(s) changed", changes.len()), Style::default().fg(Color::Green)),
+            Err(e) => Span::styled(format!("✗ {e}"), Style::default().fg(Color::Red)),
+        };
+        buf.set_line(inner.x + 1, status_y, &Line::from(status), inner.width.saturating_sub(2));
+
+        buf.set_stringn(
+            inner.x + 1,
+            bottom - 1,
+            "Tab/↑↓: Field  Space/←→: Change  Enter: Stage all  Esc: Cancel",
+            inner.width.saturating_sub(2) as usize,
+            hint_style,
+        );
+    }
+}
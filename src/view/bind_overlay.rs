@@ -0,0 +1,146 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Widget},
+};
+
+use crate::i18n::tr;
+use crate::model::BindOverlay;
+
+/// Every configured bind grouped by category, like niri's hotkey overlay,
+/// with the prompt for a new combo while one is being rebound
+pub struct BindOverlayWidget<'a> {
+    overlay: &'a BindOverlay,
+    raw_key_names: bool,
+}
+
+impl<'a> BindOverlayWidget<'a> {
+    pub fn new(overlay: &'a BindOverlay, raw_key_names: bool) -> Self {
+        Self { overlay, raw_key_names }
+    }
+}
+
+impl Widget for BindOverlayWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let dialog_width = 90.min(area.width.saturating_sub(4));
+        let dialog_height = area.height.saturating_sub(2);
+        let dialog_x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
+        let dialog_y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
+
+        let dialog_area = Rect::new(dialog_x, dialog_y, dialog_width, dialog_height);
+        Clear.render(dialog_area, buf);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(format!(" {} ({}) ", tr("All Binds"), self.overlay.entries.len()));
+
+        let inner = block.inner(dialog_area);
+        block.render(dialog_area, buf);
+
+        // The capture prompt takes the bottom three rows, the hint one
+        let footer = if self.overlay.capture.is_some() { 4 } else { 1 };
+        if inner.height < footer + 3 || inner.width < 30 {
+            return;
+        }
+
+        let width = inner.width.saturating_sub(2);
+        let combos: Vec<String> = self
+            .overlay
+            .entries
+            .iter()
+            .map(|e| e.binding.display_combo(self.raw_key_names))
+            .collect();
+        let combo_width = combos.iter().map(|c| c.chars().count()).max().unwrap_or(0).min(width as usize / 2);
+
+        // One row per category heading and per bind
+        let mut rows: Vec<Option<usize>> = Vec::new();
+        let mut category = None;
+        for (i, entry) in self.overlay.entries.iter().enumerate() {
+            if category != Some(entry.category) {
+                if category.is_some() {
+                    rows.push(None);
+                }
+                category = Some(entry.category);
+                rows.push(None);
+            }
+            rows.push(Some(i));
+        }
+
+        let list_height = (inner.height - footer - 1) as usize;
+        let selected_row = rows
+            .iter()
+            .position(|r| *r == Some(self.overlay.list.selected_index))
+            .unwrap_or(0);
+        let scroll = selected_row.saturating_sub(list_height.saturating_sub(1));
+
+        let heading_style = Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD);
+        for (row, (index, entry_row)) in rows.iter().enumerate().skip(scroll).take(list_height).enumerate() {
+            let y = inner.y + row as u16;
+            let Some(i) = entry_row else {
+                // A heading is the row before the category's first bind
+                if let Some(Some(next)) = rows.get(index + 1) {
+                    buf.set_stringn(inner.x + 1, y, self.overlay.entries[*next].category, width as usize, heading_style);
+                }
+                continue;
+            };
+            let entry = &self.overlay.entries[*i];
+            let is_selected = *i == self.overlay.list.selected_index;
+            let style = if is_selected {
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+            } else if entry.staged {
+                Style::default().fg(Color::Cyan)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            let marker = if is_selected { "▶ " } else if entry.staged { "◆ " } else { "  " };
+            let line = Line::from(vec![
+                Span::styled(marker, style),
+                Span::styled(format!("{:>combo_width$}  ", combos[*i]), style),
+                Span::styled(entry.binding.action.full_description(), style),
+            ]);
+            buf.set_line(inner.x + 1, y, &line, width);
+        }
+
+        let hint_y = inner.y + inner.height - 1;
+        let Some(capture) = &self.overlay.capture else {
+            buf.set_string(
+                inner.x + 1,
+                hint_y,
+                "j/k: Select  Enter: Rebind  Esc: Close",
+                Style::default().fg(Color::DarkGray),
+            );
+            return;
+        };
+
+        let key_style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+        let text_style = Style::default().fg(Color::White);
+        let prompt_y = hint_y - 3;
+        if let Some(entry) = self.overlay.selected() {
+            let line = Line::from(vec![
+                Span::styled(tr("Press the new combo for "), text_style),
+                Span::styled(entry.binding.action.short_description(), key_style),
+            ]);
+            buf.set_line(inner.x + 1, prompt_y, &line, width);
+        }
+        let modifier = if capture.mod_key { "Mod+ is added" } else { "Mod is off" };
+        buf.set_stringn(
+            inner.x + 1,
+            prompt_y + 1,
+            format!("{modifier}; hold Ctrl, Shift or Alt as needed"),
+            width as usize,
+            Style::default().fg(Color::Cyan),
+        );
+        if let Some(rejected) = &capture.rejected {
+            buf.set_stringn(inner.x + 1, prompt_y + 2, rejected, width as usize, Style::default().fg(Color::Red));
+        }
+        buf.set_string(
+            inner.x + 1,
+            hint_y,
+            "Tab: Toggle Mod  Esc: Cancel",
+            Style::default().fg(Color::DarkGray),
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Widget},
+};
+
+use crate::i18n::tr;
+use crate::model::ConflictResolver;
+
+/// Modal prompt for one clash between an incoming and a configured binding
+pub struct BindingConflictWidget<'a> {
+    resolver: &'a ConflictResolver,
+}
+
+impl<'a> BindingConflictWidget<'a> {
+    pub fn new(resolver: &'a ConflictResolver) -> Self {
+        Self { resolver }
+    }
+}
+
+impl Widget for BindingConflictWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let Some(conflict) = self.resolver.current() else {
+            return;
+        };
+
+        let dialog_width = 64.min(area.width.saturating_sub(4));
+        let dialog_height = 11.min(area.height.saturating_sub(2));
+        let dialog_x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
+        let dialog_y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
+
+        let dialog_area = Rect::new(dialog_x, dialog_y, dialog_width, dialog_height);
+        Clear.render(dialog_area, buf);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(format!(
+                " {} {}/{} ",
+                tr("Clash"),
+                self.resolver.index + 1,
+                self.resolver.conflicts.len()
+            ));
+
+        let inner = block.inner(dialog_area);
+        block.render(dialog_area, buf);
+
+        if inner.height < 8 || inner.width < 30 {
+            return;
+        }
+
+        let label_style = Style::default().fg(Color::Gray);
+        let text_style = Style::default().fg(Color::White);
+        let key_style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+        let width = inner.width.saturating_sub(2);
+
+        let mut y = inner.y;
+        buf.set_stringn(
+            inner.x + 1,
+            y,
+            format!("{} is already bound", conflict.existing.combo()),
+            width as usize,
+            Style::default().fg(Color::Cyan),
+        );
+        y += 1;
+        let rows = [
+            ("Configured", conflict.existing.action.full_description()),
+            (self.resolver.source.as_str(), conflict.incoming.action.full_description()),
+        ];
+        for (label, action) in rows {
+            let line = Line::from(vec![
+                Span::styled(format!("{:<12}", tr(label)), label_style),
+                Span::styled(action, text_style),
+            ]);
+            buf.set_line(inner.x + 1, y, &line, width);
+            y += 1;
+        }
+        y += 1;
+
+        let rebind = match &self.resolver.suggestion {
+            Some(binding) => format!("Bind it to {} instead", binding.combo()),
+            None => "No free combo to rebind to".to_string(),
+        };
+        let options = [
+            ("k", "Keep the configured bind".to_string()),
+            ("r", "Replace it".to_string()),
+            ("b", rebind),
+        ];
+        for (key, label) in options {
+            let line = Line::from(vec![
+                Span::styled(format!("[{key}] "), key_style),
+                Span::styled(label, text_style),
+            ]);
+            buf.set_line(inner.x + 1, y, &line, width);
+            y += 1;
+        }
+
+        buf.set_string(
+            inner.x + 1,
+            inner.y + inner.height - 1,
+            "Esc: Keep the rest",
+            Style::default().fg(Color::DarkGray),
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, Widget},
+};
+
+use crate::i18n::tr;
+use crate::model::{BindingGenerator, Keybinding};
+
+/// Modal dialog previewing the column layout bindings that will be staged
+pub struct BindingGeneratorWidget<'a> {
+    generator: &'a BindingGenerator,
+    existing: &'a [Keybinding],
+}
+
+impl<'a> BindingGeneratorWidget<'a> {
+    pub fn new(generator: &'a BindingGenerator, existing: &'a [Keybinding]) -> Self {
+        Self { generator, existing }
+    }
+}
+
+impl Widget for BindingGeneratorWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let generated = self.generator.generate(self.existing);
+
+        let dialog_width = 60.min(area.width.saturating_sub(4));
+        let dialog_height = (generated.len() as u16 + 8).min(area.height.saturating_sub(2));
+        let dialog_x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
+        let dialog_y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
+
+        let dialog_area = Rect::new(dialog_x, dialog_y, dialog_width, dialog_height);
+        Clear.render(dialog_area, buf);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(format!(" {} ", tr("Column Layout Bindings")));
+
+        let inner = block.inner(dialog_area);
+        block.render(dialog_area, buf);
+
+        if inner.height < 5 || inner.width < 30 {
+            return;
+        }
+
+        let label_style = Style::default().fg(Color::Gray);
+        let focused_style = Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD);
+        let hint_style = Style::default().fg(Color::DarkGray);
+
+        let mut y = inner.y;
+
+        buf.set_string(inner.x + 1, y, "Modifier:", label_style);
+        let modifier_display = format!("< {} >", self.generator.modifier());
+        buf.set_string(inner.x + 11, y, &modifier_display, focused_style);
+        y += 2;
+
+        for generated_binding in &generated {
+            if y >= inner.y + inner.height - 2 {
+                break;
+            }
+            let (marker, style) = if generated_binding.conflicts {
+                ("-", Style::default().fg(Color::DarkGray))
+            } else {
+                ("+", Style::default().fg(Color::Green))
+            };
+            let mut line = format!(
+                "{marker} {:18} {}",
+                generated_binding.binding.combo(),
+                generated_binding.binding.action
+            );
+            if generated_binding.conflicts {
+                line.push_str("  (exists, skipped)");
+            }
+            let max_width = inner.width.saturating_sub(2) as usize;
+            if line.len() > max_width {
+                line.truncate(max_width);
+            }
+            buf.set_string(inner.x + 1, y, &line, style);
+            y += 1;
+        }
+
+        let hint_y = inner.y + inner.height - 1;
+        buf.set_string(
+            inner.x + 1,
+            hint_y,
+            "←/→: Modifier  Enter: Stage  Esc: Cancel",
+            hint_style,
+        );
+    }
+}
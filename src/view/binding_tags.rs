@@ -0,0 +1,64 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, Widget},
+};
+
+use crate::binding_tags::BindingTagEditor;
+use crate::i18n::tr;
+
+/// Modal dialog for the tags on a keybinding
+pub struct BindingTagsWidget<'a> {
+    editor: &'a BindingTagEditor,
+}
+
+impl<'a> BindingTagsWidget<'a> {
+    pub fn new(editor: &'a BindingTagEditor) -> Self {
+        Self { editor }
+    }
+}
+
+impl Widget for BindingTagsWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let dialog_width = 60.min(area.width.saturating_sub(4));
+        let dialog_height = 5.min(area.height.saturating_sub(2));
+        let dialog_x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
+        let dialog_y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
+
+        let dialog_area = Rect::new(dialog_x, dialog_y, dialog_width, dialog_height);
+        Clear.render(dialog_area, buf);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(format!(" {} {} ", tr("Tags"), self.editor.combo));
+
+        let inner = block.inner(dialog_area);
+        block.render(dialog_area, buf);
+
+        if inner.height < 2 || inner.width < 20 {
+            return;
+        }
+
+        let field = &self.editor.field;
+        let value = format!("{}│{}", &field.value[..field.cursor], &field.value[field.cursor..]);
+        let width = inner.width.saturating_sub(2) as usize;
+        // Keep the cursor in view in a long list
+        let skip = value.chars().count().saturating_sub(width);
+        let value: String = value.chars().skip(skip).collect();
+        buf.set_string(
+            inner.x + 1,
+            inner.y,
+            &value,
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+        );
+
+        buf.set_string(
+            inner.x + 1,
+            inner.y + inner.height - 1,
+            "Space or comma between tags  Enter: Save  Esc: Cancel",
+            Style::default().fg(Color::DarkGray),
+        );
+    }
+}
@@ -0,0 +1,106 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, Widget},
+};
+
+use crate::i18n::tr;
+use crate::model::TemplatePrompt;
+
+/// Modal dialog for picking a binding template and filling in its variables
+pub struct BindingTemplateWidget<'a> {
+    prompt: &'a TemplatePrompt,
+}
+
+impl<'a> BindingTemplateWidget<'a> {
+    pub fn new(prompt: &'a TemplatePrompt) -> Self {
+        Self { prompt }
+    }
+}
+
+impl Widget for BindingTemplateWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let preview = self.prompt.render();
+        let preview_lines: Vec<&str> = match &preview {
+            Ok(body) => body.lines().collect(),
+            Err(e) => vec![e.as_str()],
+        };
+
+        let dialog_width = 80.min(area.width.saturating_sub(4));
+        let dialog_height = (self.prompt.fields.len() as u16 + preview_lines.len() as u16 + 7)
+            .min(area.height.saturating_sub(2));
+        let dialog_x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
+        let dialog_y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
+
+        let dialog_area = Rect::new(dialog_x, dialog_y, dialog_width, dialog_height);
+        Clear.render(dialog_area, buf);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(format!(" {} ", tr("Binding Template")));
+
+        let inner = block.inner(dialog_area);
+        block.render(dialog_area, buf);
+
+        if inner.height < 4 || inner.width < 20 {
+            return;
+        }
+
+        let focused = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+        let normal = Style::default().fg(Color::White);
+        let label_style = Style::default().fg(Color::Gray);
+        let hint_y = inner.y + inner.height - 1;
+        let width = inner.width.saturating_sub(2) as usize;
+
+        let mut y = inner.y;
+        let chooser = format!("< {} >", self.prompt.template().name);
+        buf.set_string(inner.x + 1, y, format!("{:<10}", tr("Template")), label_style);
+        buf.set_string(
+            inner.x + 12,
+            y,
+            &chooser,
+            if self.prompt.focused == 0 { focused } else { normal },
+        );
+        y += 1;
+
+        for (i, field) in self.prompt.fields.iter().enumerate() {
+            if y >= hint_y {
+                return;
+            }
+            let is_focused = self.prompt.focused == i + 1;
+            let label: String = format!("{:<10}", field.name).chars().take(10).collect();
+            buf.set_string(inner.x + 1, y, &label, label_style);
+            let value = if is_focused {
+                format!("{}│{}", &field.value[..field.cursor], &field.value[field.cursor..])
+            } else {
+                field.value.clone()
+            };
+            let value: String = value.chars().take(width.saturating_sub(11)).collect();
+            buf.set_string(inner.x + 12, y, &value, if is_focused { focused } else { normal });
+            y += 1;
+        }
+
+        y += 1;
+        let preview_style = match preview {
+            Ok(_) => Style::default().fg(Color::Green),
+            Err(_) => Style::default().fg(Color::Red),
+        };
+        for line in preview_lines {
+            if y >= hint_y {
+                break;
+            }
+            let line: String = line.chars().take(width).collect();
+            buf.set_string(inner.x + 1, y, &line, preview_style);
+            y += 1;
+        }
+
+        buf.set_string(
+            inner.x + 1,
+            hint_y,
+            "Tab: Next field  ←→: Template  Enter: Stage  Esc: Cancel",
+            Style::default().fg(Color::DarkGray),
+        );
+    }
+}
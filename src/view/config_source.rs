@@ -0,0 +1,90 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Widget},
+};
+
+use crate::i18n::tr;
+use crate::model::ConfigDiscovery;
+
+/// Dialog listing where the config was looked for, in order of
+/// precedence, and why the edited one won
+pub struct ConfigSourceWidget<'a> {
+    discovery: &'a ConfigDiscovery,
+}
+
+impl<'a> ConfigSourceWidget<'a> {
+    pub fn new(discovery: &'a ConfigDiscovery) -> Self {
+        Self { discovery }
+    }
+}
+
+impl Widget for ConfigSourceWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let copy = self.discovery.needs_copy();
+        let wanted = self.discovery.candidates.len() as u16 + if copy { 8 } else { 5 };
+        let dialog_width = 80.min(area.width.saturating_sub(4));
+        let dialog_height = wanted.min(area.height.saturating_sub(2));
+        let dialog_x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
+        let dialog_y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
+
+        let dialog_area = Rect::new(dialog_x, dialog_y, dialog_width, dialog_height);
+        Clear.render(dialog_area, buf);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(if copy { Color::Red } else { Color::Cyan }))
+            .title(format!(" {} ", tr("Config Source")));
+
+        let inner = block.inner(dialog_area);
+        block.render(dialog_area, buf);
+
+        if inner.height < 3 || inner.width < 30 {
+            return;
+        }
+
+        let width = inner.width.saturating_sub(2);
+        let hint_y = inner.y + inner.height - 1;
+        let mut y = inner.y;
+        for (i, candidate) in self.discovery.candidates.iter().enumerate() {
+            if y >= hint_y {
+                break;
+            }
+            let chosen = i == self.discovery.chosen;
+            let (status, style) = match (&candidate.path, chosen, candidate.exists) {
+                (None, _, _) => (tr("not set"), Style::default().fg(Color::DarkGray)),
+                (Some(_), true, _) => (tr("edited"), Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+                (Some(_), false, true) => (tr("exists"), Style::default().fg(Color::White)),
+                (Some(_), false, false) => (tr("missing"), Style::default().fg(Color::DarkGray)),
+            };
+            let path = candidate.path.as_ref().map(|p| p.display().to_string()).unwrap_or_default();
+            let line = Line::from(vec![
+                Span::styled(if chosen { "▶ " } else { "  " }, style),
+                Span::styled(format!("{:<18}", candidate.source.label()), style),
+                Span::styled(format!("{path}  "), style),
+                Span::styled(format!("({status})"), Style::default().fg(Color::DarkGray)),
+            ]);
+            buf.set_line(inner.x + 1, y, &line, width);
+            y += 1;
+        }
+
+        y += 1;
+        if y < hint_y {
+            buf.set_stringn(inner.x + 1, y, self.discovery.reason(), width as usize, Style::default().fg(Color::White));
+            y += 1;
+        }
+        if copy {
+            if let (Some(user), true) = (self.discovery.user_path(), y + 2 < hint_y) {
+                let warning = format!("{} {}", self.discovery.path().display(), tr("can't be written"));
+                buf.set_stringn(inner.x + 1, y + 1, warning, width as usize, Style::default().fg(Color::Red));
+                let offer = format!("{} {}", tr("Press c to copy it to"), user.display());
+                buf.set_stringn(inner.x + 1, y + 2, offer, width as usize, Style::default().fg(Color::Yellow));
+            }
+        }
+
+        let hint = if copy { "c: Copy to user config  Esc: Close" } else { "Esc: Close" };
+        buf.set_string(inner.x + 1, hint_y, hint, Style::default().fg(Color::DarkGray));
+    }
+}
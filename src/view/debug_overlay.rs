@@ -0,0 +1,65 @@
+use std::time::Duration;
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    widgets::{Block, Borders, Clear, Widget},
+};
+
+use crate::diagnostics::Diagnostics;
+
+/// Frame time above which a frame is shown as slow; 60 Hz leaves ~16 ms
+const SLOW_FRAME: Duration = Duration::from_millis(16);
+
+/// Small box in the top-right corner with frame, IPC and event loop timings
+pub struct DebugOverlayWidget<'a> {
+    diagnostics: &'a Diagnostics,
+}
+
+impl<'a> DebugOverlayWidget<'a> {
+    pub fn new(diagnostics: &'a Diagnostics) -> Self {
+        Self { diagnostics }
+    }
+}
+
+fn ms(time: Duration) -> String {
+    format!("{:.1}ms", time.as_secs_f64() * 1000.0)
+}
+
+impl Widget for DebugOverlayWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let width = 44.min(area.width);
+        let height = 5.min(area.height);
+        let overlay = Rect::new(area.x + area.width - width, area.y, width, height);
+        Clear.render(overlay, buf);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray))
+            .title(" Debug (F10) ");
+        let inner = block.inner(overlay);
+        block.render(overlay, buf);
+
+        let d = self.diagnostics;
+        let frame = match (d.last_frame(), d.frame_stats()) {
+            (Some(last), Some((avg, max))) => format!("Frame {}  avg {}  max {}", ms(last), ms(avg), ms(max)),
+            _ => "Frame -".to_string(),
+        };
+        let frame_style = if d.last_frame().is_some_and(|last| last > SLOW_FRAME) {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        let ipc = match &d.last_ipc {
+            Some((what, time)) => format!("IPC {what}: {}", ms(*time)),
+            None => "IPC -".to_string(),
+        };
+        let ticks = format!("Ticks {}/s  work {}", d.ticks_per_second(), ms(d.last_tick_work));
+
+        let lines = [(frame, frame_style), (ipc, Style::default().fg(Color::White)), (ticks, Style::default().fg(Color::White))];
+        for (i, (line, style)) in lines.iter().enumerate().take(inner.height as usize) {
+            buf.set_stringn(inner.x, inner.y + i as u16, line, inner.width as usize, *style);
+        }
+    }
+}
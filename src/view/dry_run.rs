@@ -0,0 +1,107 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Widget},
+};
+
+use super::appearance_detail::wrap_text;
+use crate::i18n::{tr, tr_args};
+use crate::model::dry_run::{shell_quote, RUN_TIMEOUT};
+use crate::model::DryRun;
+
+/// Modal showing the argv a spawn binding would execute, and the output
+/// of a test run
+pub struct DryRunWidget<'a> {
+    dry_run: &'a DryRun,
+}
+
+impl<'a> DryRunWidget<'a> {
+    pub fn new(dry_run: &'a DryRun) -> Self {
+        Self { dry_run }
+    }
+}
+
+impl Widget for DryRunWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let dialog_width = 80.min(area.width.saturating_sub(4));
+        let dialog_height = 20.min(area.height.saturating_sub(2));
+        let dialog_x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
+        let dialog_y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
+
+        let dialog_area = Rect::new(dialog_x, dialog_y, dialog_width, dialog_height);
+        Clear.render(dialog_area, buf);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(format!(" {} ", tr_args("Dry run: {combo}", &[("combo", &self.dry_run.combo)])));
+
+        let inner = block.inner(dialog_area);
+        block.render(dialog_area, buf);
+
+        if inner.height < 5 || inner.width < 30 {
+            return;
+        }
+
+        let width = inner.width.saturating_sub(2) as usize;
+        let label_style = Style::default().fg(Color::Gray);
+        let value_style = Style::default().fg(Color::White);
+        let dim_style = Style::default().fg(Color::DarkGray);
+        let hint_y = inner.y + inner.height - 1;
+        let mut lines: Vec<(String, Style)> = Vec::new();
+
+        match &self.dry_run.argv {
+            Ok(argv) => {
+                lines.push((tr("Would execute:").to_string(), label_style));
+                for text in wrap_text(&shell_quote(argv), width.saturating_sub(2)) {
+                    lines.push((format!("  {text}"), Style::default().fg(Color::Green)));
+                }
+                lines.push((String::new(), value_style));
+                lines.push((tr("Arguments:").to_string(), label_style));
+                for (i, arg) in argv.iter().enumerate() {
+                    lines.push((format!("  [{i}] {arg:?}"), value_style));
+                }
+            }
+            Err(e) => {
+                let style = Style::default().fg(Color::Red).add_modifier(Modifier::BOLD);
+                lines.push((format!("{} {e}", tr("Nothing to run:")), style));
+            }
+        }
+
+        if self.dry_run.running.is_some() {
+            lines.push((String::new(), value_style));
+            lines.push((tr("Running…").to_string(), Style::default().fg(Color::Yellow)));
+        } else if let Some(output) = &self.dry_run.output {
+            lines.push((String::new(), value_style));
+            lines.push((tr_args("Test run: {status}", &[("status", &output.status)]), label_style));
+            for (name, text, style) in [
+                ("stdout", &output.stdout, value_style),
+                ("stderr", &output.stderr, Style::default().fg(Color::Red)),
+            ] {
+                if !text.is_empty() {
+                    lines.push((format!("{name}:"), dim_style));
+                    lines.extend(text.lines().map(|line| (format!("  {line}"), style)));
+                }
+            }
+        }
+
+        for (y, (text, style)) in (inner.y..hint_y).zip(&lines) {
+            buf.set_stringn(inner.x + 1, y, text, width, *style);
+        }
+
+        let key_style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+        let mut footer = vec![];
+        if self.dry_run.argv.is_ok() {
+            footer.push(Span::styled("[Enter] ", key_style));
+            footer.push(Span::styled(
+                format!("{}  ", tr_args("Run once (killed after {seconds}s)", &[("seconds", &RUN_TIMEOUT.as_secs())])),
+                dim_style,
+            ));
+        }
+        footer.push(Span::styled("[Esc] ", key_style));
+        footer.push(Span::styled(tr("Close"), dim_style));
+        buf.set_line(inner.x + 1, hint_y, &Line::from(footer), width as u16);
+    }
+}
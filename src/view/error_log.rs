@@ -0,0 +1,111 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, Widget},
+};
+
+use crate::error::ErrorLog;
+use crate::i18n::tr_args;
+
+/// Modal dialog listing recent errors with the selected one's context chain expanded
+pub struct ErrorLogWidget<'a> {
+    log: &'a ErrorLog,
+}
+
+impl<'a> ErrorLogWidget<'a> {
+    pub fn new(log: &'a ErrorLog) -> Self {
+        Self { log }
+    }
+}
+
+impl Widget for ErrorLogWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let dialog_width = 80.min(area.width.saturating_sub(4));
+        let dialog_height = 22.min(area.height.saturating_sub(2));
+        let dialog_x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
+        let dialog_y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
+
+        let dialog_area = Rect::new(dialog_x, dialog_y, dialog_width, dialog_height);
+        Clear.render(dialog_area, buf);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Red))
+            .title(format!(" {} ", tr_args("Errors ({count})", &[("count", &self.log.entries.len())])));
+
+        let inner = block.inner(dialog_area);
+        block.render(dialog_area, buf);
+
+        if inner.height < 6 || inner.width < 30 {
+            return;
+        }
+
+        let hint_style = Style::default().fg(Color::DarkGray);
+        let max_width = inner.width.saturating_sub(2) as usize;
+        let hint_y = inner.y + inner.height - 1;
+
+        if self.log.entries.is_empty() {
+            buf.set_string(inner.x + 1, inner.y, "No errors this session", hint_style);
+            buf.set_string(inner.x + 1, hint_y, "Esc: Close", hint_style);
+            return;
+        }
+
+        // Upper half: history list, newest first
+        let list_height = (inner.height.saturating_sub(2) / 2).max(1) as usize;
+        let scroll = self
+            .log
+            .list
+            .selected_index
+            .saturating_sub(list_height.saturating_sub(1));
+        let mut y = inner.y;
+        for (i, error) in self
+            .log
+            .entries
+            .iter()
+            .enumerate()
+            .skip(scroll)
+            .take(list_height)
+        {
+            let style = if i == self.log.list.selected_index {
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            let line: String = format!("{:10} {}", format!("[{}]", error.kind()), error.context())
+                .chars()
+                .take(max_width)
+                .collect();
+            buf.set_string(inner.x + 1, y, &line, style);
+            y += 1;
+        }
+
+        // Lower half: full context chain of the selected error
+        y = inner.y + list_height as u16 + 1;
+        if let Some(error) = self.log.selected() {
+            buf.set_string(
+                inner.x + 1,
+                y,
+                error.context().chars().take(max_width).collect::<String>(),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            );
+            y += 1;
+            for (depth, cause) in error.chain().iter().enumerate() {
+                if y >= hint_y {
+                    break;
+                }
+                let indent = "  ".repeat(depth + 1);
+                let line: String = format!("{indent}caused by: {cause}")
+                    .chars()
+                    .take(max_width)
+                    .collect();
+                buf.set_string(inner.x + 1, y, &line, Style::default().fg(Color::Gray));
+                y += 1;
+            }
+        }
+
+        buf.set_string(inner.x + 1, hint_y, "↑↓: Select  Esc: Close", hint_style);
+    }
+}
@@ -0,0 +1,78 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Paragraph, Widget, Wrap},
+};
+
+use crate::i18n::tr;
+use crate::model::GeneralViewModel;
+
+/// Details of the selected General setting
+pub struct GeneralDetailWidget<'a> {
+    view_model: &'a GeneralViewModel,
+}
+
+impl<'a> GeneralDetailWidget<'a> {
+    pub fn new(view_model: &'a GeneralViewModel) -> Self {
+        Self { view_model }
+    }
+}
+
+/// What a General block is for
+fn block_description(block: &str) -> &'static str {
+    match block {
+        "xwayland-satellite" => {
+            "X11 apps run through xwayland-satellite, which niri starts on demand. This is the top-level xwayland-satellite block."
+        }
+        "clipboard" => {
+            "The top-level clipboard block. With the primary selection off, selecting text no longer copies it for middle-click paste."
+        }
+        _ => "",
+    }
+}
+
+impl Widget for GeneralDetailWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray))
+            .title(format!(" {} ", tr("Details")));
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if inner.height < 3 || inner.width < 15 {
+            return;
+        }
+        let Some(field) = self.view_model.selected_field() else {
+            return;
+        };
+
+        let label = Style::default().fg(Color::Gray);
+        let mut lines = vec![
+            Line::styled(field.qualified_name(), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Line::raw(""),
+            Line::raw(tr(field.description())),
+            Line::raw(""),
+            Line::styled(
+                format!("{}: {}", tr("Saved"), self.view_model.original_settings.display(field)),
+                label,
+            ),
+        ];
+        if self.view_model.is_field_modified(field) {
+            lines.push(Line::styled(
+                format!("{}: {}", tr("Staged"), self.view_model.settings.display(field)),
+                Style::default().fg(Color::Cyan),
+            ));
+        }
+        if let Some(source) = self.view_model.field_sources.get(&field) {
+            lines.push(Line::styled(format!("{}:{}", source.path.display(), source.line), label));
+        }
+        lines.push(Line::raw(""));
+        lines.push(Line::styled(tr(block_description(field.block())), Style::default().fg(Color::DarkGray)));
+
+        Paragraph::new(lines).wrap(Wrap { trim: false }).render(inner, buf);
+    }
+}
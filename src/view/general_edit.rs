@@ -0,0 +1,59 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, Widget},
+};
+
+use crate::i18n::tr;
+use crate::model::TemplateField;
+
+/// Modal dialog for typing a General text setting
+pub struct GeneralEditWidget<'a> {
+    field: &'a TemplateField,
+}
+
+impl<'a> GeneralEditWidget<'a> {
+    pub fn new(field: &'a TemplateField) -> Self {
+        Self { field }
+    }
+}
+
+impl Widget for GeneralEditWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let dialog_width = 60.min(area.width.saturating_sub(4));
+        let dialog_height = 5.min(area.height.saturating_sub(2));
+        let dialog_x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
+        let dialog_y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
+
+        let dialog_area = Rect::new(dialog_x, dialog_y, dialog_width, dialog_height);
+        Clear.render(dialog_area, buf);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(format!(" {} {} ", tr("Edit"), self.field.name));
+
+        let inner = block.inner(dialog_area);
+        block.render(dialog_area, buf);
+
+        if inner.height < 3 || inner.width < 20 {
+            return;
+        }
+
+        let field = self.field;
+        let value = format!("{}│{}", &field.value[..field.cursor], &field.value[field.cursor..]);
+        // Keep the cursor in view in long paths
+        let width = inner.width.saturating_sub(2) as usize;
+        let skip = value.chars().count().saturating_sub(width);
+        let value: String = value.chars().skip(skip).collect();
+        buf.set_string(inner.x + 1, inner.y, &value, Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+
+        buf.set_string(
+            inner.x + 1,
+            inner.y + inner.height - 1,
+            "Enter: Stage  Esc: Cancel",
+            Style::default().fg(Color::DarkGray),
+        );
+    }
+}
@@ -0,0 +1,128 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Widget},
+};
+use unicode_width::UnicodeWidthStr;
+
+use super::Density;
+use crate::i18n::{tr, tr_args};
+use crate::model::{GeneralField, GeneralViewModel};
+use crate::widgets::table::fit;
+
+/// List of the General settings, grouped under the block they live in
+pub struct GeneralListWidget<'a> {
+    view_model: &'a GeneralViewModel,
+    focused: bool,
+    density: Density,
+}
+
+impl<'a> GeneralListWidget<'a> {
+    pub fn new(view_model: &'a GeneralViewModel, focused: bool) -> Self {
+        Self { view_model, focused, density: Density::Compact }
+    }
+
+    pub fn density(mut self, density: Density) -> Self {
+        self.density = density;
+        self
+    }
+}
+
+impl Widget for GeneralListWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let modified_count = self.view_model.change_summary().len();
+        let title = if modified_count > 0 {
+            format!(" {} *{} ", tr("General"), tr_args("{count} modified", &[("count", &modified_count)]))
+        } else {
+            format!(" {} ", tr("General"))
+        };
+        let border_style = if self.focused {
+            Style::default().fg(Color::Cyan)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(border_style)
+            .padding(self.density.padding())
+            .title(title);
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if inner.height < 1 || inner.width < 10 {
+            return;
+        }
+
+        let header_style = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+        let name_width = GeneralField::all().iter().map(|f| f.name().width()).max().unwrap_or(0) + 2;
+        let value_width = (inner.width as usize).saturating_sub(name_width + 4);
+        let mut y = inner.y;
+        let mut block_name = "";
+        for (index, &field) in GeneralField::all().iter().enumerate() {
+            if field.block() != block_name {
+                block_name = field.block();
+                if y >= inner.y + inner.height {
+                    break;
+                }
+                buf.set_string(inner.x, y, block_name, header_style);
+                y += 1;
+            }
+            if y >= inner.y + inner.height {
+                break;
+            }
+
+            let is_selected = index == self.view_model.list.selected_index;
+            let is_modified = self.view_model.is_field_modified(field);
+            let indicator = match (is_selected, is_modified) {
+                (true, true) => ">*",
+                (true, false) => "> ",
+                (false, true) => " *",
+                (false, false) => "  ",
+            };
+            let style = if is_selected && self.focused {
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+            } else if is_selected {
+                Style::default().fg(Color::White)
+            } else if is_modified {
+                Style::default().fg(Color::Cyan)
+            } else {
+                Style::default().fg(Color::Gray)
+            };
+
+            buf.set_string(inner.x + 1, y, indicator, style);
+            buf.set_string(inner.x + 3, y, fit(field.name(), name_width), style);
+            let value_x = inner.x + 3 + name_width as u16;
+            if field.is_boolean() {
+                let (text, toggle) = if self.view_model.settings.display(field) == "on" {
+                    (" ON ", Style::default().fg(Color::Black).bg(Color::Green))
+                } else {
+                    ("OFF ", Style::default().fg(Color::White).bg(Color::DarkGray))
+                };
+                buf.set_string(value_x, y, text, toggle);
+            } else {
+                buf.set_string(value_x, y, fit(&self.view_model.settings.display(field), value_width), style);
+            }
+            y += 1;
+        }
+
+        // Workspace apps from the wizard, added on the next save
+        let spawns = &self.view_model.workspace_spawns;
+        if spawns.is_empty() || y >= inner.y + inner.height {
+            return;
+        }
+        buf.set_string(inner.x, y, tr("workspace apps"), header_style);
+        y += 1;
+        let width = (inner.width as usize).saturating_sub(3);
+        for spawn in spawns {
+            if y >= inner.y + inner.height {
+                break;
+            }
+            let line = format!("{} → {}", spawn.command.join(" "), spawn.workspace);
+            buf.set_string(inner.x + 1, y, " +", Style::default().fg(Color::Cyan));
+            buf.set_string(inner.x + 3, y, fit(&line, width), Style::default().fg(Color::Cyan));
+            y += 1;
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Widget},
+};
+
+use crate::i18n::tr;
+use crate::model::{CheckStatus, HealthReport};
+
+/// Startup report listing what's wrong and how to fix it
+pub struct HealthReportWidget<'a> {
+    report: &'a HealthReport,
+}
+
+impl<'a> HealthReportWidget<'a> {
+    pub fn new(report: &'a HealthReport) -> Self {
+        Self { report }
+    }
+}
+
+impl Widget for HealthReportWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let hints = self.report.checks.iter().filter(|c| c.hint.is_some()).count() as u16;
+        let wanted = self.report.checks.len() as u16 + hints + 4;
+        let dialog_width = 76.min(area.width.saturating_sub(4));
+        let dialog_height = wanted.min(area.height.saturating_sub(2));
+        let dialog_x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
+        let dialog_y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
+
+        let dialog_area = Rect::new(dialog_x, dialog_y, dialog_width, dialog_height);
+        Clear.render(dialog_area, buf);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(format!(" {} ", tr("Startup Check")));
+
+        let inner = block.inner(dialog_area);
+        block.render(dialog_area, buf);
+
+        if inner.height < 3 || inner.width < 30 {
+            return;
+        }
+
+        let width = inner.width.saturating_sub(2);
+        let bottom = inner.y + inner.height - 1;
+        let mut y = inner.y;
+        for check in &self.report.checks {
+            if y >= bottom {
+                break;
+            }
+            let (mark, color) = match check.status {
+                CheckStatus::Ok => ("✓", Color::Green),
+                CheckStatus::Warning => ("!", Color::Yellow),
+                CheckStatus::Failed => ("✗", Color::Red),
+            };
+            let line = Line::from(vec![
+                Span::styled(format!("{mark} "), Style::default().fg(color).add_modifier(Modifier::BOLD)),
+                Span::styled(format!("{:<20}", tr(check.label)), Style::default().fg(Color::White)),
+                Span::styled(check.detail.as_str(), Style::default().fg(Color::DarkGray)),
+            ]);
+            buf.set_line(inner.x + 1, y, &line, width);
+            y += 1;
+            if let Some(hint) = &check.hint {
+                if y < bottom {
+                    buf.set_stringn(inner.x + 3, y, format!("→ {hint}"), width as usize - 2, Style::default().fg(color));
+                    y += 1;
+                }
+            }
+        }
+
+        let key_style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+        let text_style = Style::default().fg(Color::White);
+        let footer = Line::from(vec![
+            Span::styled("[Enter] ", key_style),
+            Span::styled(format!("{}  ", tr("Continue")), text_style),
+            Span::styled("[q] ", key_style),
+            Span::styled(tr("Quit"), text_style),
+        ]);
+        buf.set_line(inner.x + 1, bottom, &footer, width);
+    }
+}
@@ -5,31 +5,53 @@ use ratatui::{
     widgets::{Block, Borders, Widget},
 };
 
+use crate::i18n::tr;
 use crate::model::{BindingStatus, Keybinding};
 
 /// Widget for displaying details of a selected keybinding
-pub struct KeybindingDetailWidget {
-    binding: Option<Keybinding>,
+pub struct KeybindingDetailWidget<'a> {
+    binding: Option<&'a Keybinding>,
     status: Option<BindingStatus>,
+    raw_key_names: bool,
+    unmodeled: Vec<String>,
+    tags: &'a [String],
 }
 
-impl KeybindingDetailWidget {
+impl<'a> KeybindingDetailWidget<'a> {
     #[allow(dead_code)]
-    pub fn new(binding: Option<Keybinding>) -> Self {
-        Self { binding, status: None }
+    pub fn new(binding: Option<&'a Keybinding>) -> Self {
+        Self { binding, status: None, raw_key_names: false, unmodeled: Vec::new(), tags: &[] }
     }
 
-    pub fn with_status(binding: Option<Keybinding>, status: Option<BindingStatus>) -> Self {
-        Self { binding, status }
+    pub fn with_status(binding: Option<&'a Keybinding>, status: Option<BindingStatus>) -> Self {
+        Self { binding, status, raw_key_names: false, unmodeled: Vec::new(), tags: &[] }
+    }
+
+    /// Show the XKB combo instead of the friendly key label
+    pub fn raw_key_names(mut self, raw: bool) -> Self {
+        self.raw_key_names = raw;
+        self
+    }
+
+    /// Parts of the bind in the config that nirikiri keeps but doesn't edit
+    pub fn unmodeled(mut self, parts: Vec<String>) -> Self {
+        self.unmodeled = parts;
+        self
+    }
+
+    /// The user's tags on the binding
+    pub fn tags(mut self, tags: &'a [String]) -> Self {
+        self.tags = tags;
+        self
     }
 }
 
-impl Widget for KeybindingDetailWidget {
+impl Widget for KeybindingDetailWidget<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let block = Block::default()
             .borders(Borders::ALL)
             .border_style(Style::default().fg(Color::DarkGray))
-            .title(" Details ");
+            .title(format!(" {} ", tr("Details")));
 
         let inner = block.inner(area);
         block.render(area, buf);
@@ -59,8 +81,16 @@ impl Widget for KeybindingDetailWidget {
         // Key combo
         if y < inner.y + inner.height {
             buf.set_string(inner.x + 1, y, "Key Combo:", label_style);
-            buf.set_string(inner.x + 12, y, binding.combo(), value_style);
+            let display = binding.display_combo(self.raw_key_names);
+            let combo = binding.combo();
+            buf.set_string(inner.x + 12, y, &display, value_style);
             y += 1;
+            // Keep the name written in the config visible alongside the label
+            if display != combo && y < inner.y + inner.height {
+                buf.set_string(inner.x + 1, y, "XKB:", dim_style);
+                buf.set_string(inner.x + 12, y, &combo, dim_style);
+                y += 1;
+            }
         }
 
         // Action
@@ -101,6 +131,21 @@ impl Widget for KeybindingDetailWidget {
             y += 1;
         }
 
+        // Holding the key would start the app over and over
+        if let Some(app) = binding.repeat_lint().filter(|_| self.status != Some(BindingStatus::Default)) {
+            let warn_style = Style::default().fg(Color::Yellow);
+            let lines = [
+                format!("⚠ holding it opens a {app} per key repeat"),
+                "  P: repeat off, or set a cooldown (Enter)".to_string(),
+            ];
+            for line in lines {
+                if y < inner.y + inner.height {
+                    buf.set_stringn(inner.x + 3, y, line, inner.width.saturating_sub(4) as usize, warn_style);
+                    y += 1;
+                }
+            }
+        }
+
         // Cooldown property
         if y < inner.y + inner.height {
             if let Some(ms) = binding.properties.cooldown_ms {
@@ -132,6 +177,37 @@ impl Widget for KeybindingDetailWidget {
             y += 1;
         }
 
+        if !self.tags.is_empty() && y < inner.y + inner.height {
+            let tags: Vec<String> = self.tags.iter().map(|tag| format!("#{tag}")).collect();
+            buf.set_string(inner.x + 1, y, "Tags:", label_style);
+            buf.set_stringn(inner.x + 11, y, tags.join(" "), inner.width.saturating_sub(12) as usize, Style::default().fg(Color::Magenta));
+            y += 1;
+        }
+
+        // Where the binding is defined
+        if y < inner.y + inner.height {
+            if let Some(ref loc) = binding.source {
+                buf.set_string(inner.x + 1, y, "Defined at:", label_style);
+                buf.set_string(inner.x + 13, y, loc.to_string(), dim_style);
+                y += 1;
+            }
+        }
+
+        // Structure an edit keeps untouched
+        if !self.unmodeled.is_empty() && y + 1 < inner.y + inner.height {
+            y += 1; // blank line
+            let warn_style = Style::default().fg(Color::Yellow);
+            buf.set_string(inner.x + 1, y, "Kept as is:", warn_style.add_modifier(Modifier::BOLD));
+            y += 1;
+            for part in &self.unmodeled {
+                if y >= inner.y + inner.height {
+                    break;
+                }
+                buf.set_stringn(inner.x + 3, y, part, inner.width.saturating_sub(4) as usize, warn_style);
+                y += 1;
+            }
+        }
+
         // Status (if modified or added)
         if let Some(status) = self.status {
             if status != BindingStatus::Unchanged && y + 1 < inner.y + inner.height {
@@ -139,6 +215,7 @@ impl Widget for KeybindingDetailWidget {
                 let (status_label, status_color) = match status {
                     BindingStatus::Modified => ("* Modified (unsaved)", Color::Cyan),
                     BindingStatus::Added => ("+ New (unsaved)", Color::Green),
+                    BindingStatus::Default => ("niri default, not in config (o: override)", Color::DarkGray),
                     BindingStatus::Unchanged => ("", Color::Gray),
                 };
                 buf.set_string(
@@ -5,24 +5,39 @@ use ratatui::{
     widgets::{Block, Borders, Clear, Widget},
 };
 
+use crate::i18n::tr;
 use crate::model::{ActionType, EditField, EditMode};
 
 /// Widget for editing a keybinding in a modal dialog
 pub struct KeybindingEditWidget<'a> {
     edit_mode: &'a EditMode,
+    /// KDL that saving would write, None while the binding is incomplete
+    preview: Option<String>,
 }
 
 impl<'a> KeybindingEditWidget<'a> {
     pub fn new(edit_mode: &'a EditMode) -> Self {
-        Self { edit_mode }
+        Self {
+            edit_mode,
+            preview: None,
+        }
+    }
+
+    pub fn with_preview(mut self, preview: Option<String>) -> Self {
+        self.preview = preview;
+        self
     }
 }
 
 impl Widget for KeybindingEditWidget<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         // Calculate centered dialog area
+        let preview_lines: Vec<&str> = match &self.preview {
+            Some(kdl) => kdl.lines().collect(),
+            None => vec!["(incomplete)"],
+        };
         let dialog_width = 65.min(area.width.saturating_sub(4));
-        let dialog_height = 16.min(area.height.saturating_sub(2));
+        let dialog_height = (18 + preview_lines.len() as u16).min(area.height.saturating_sub(2));
         let dialog_x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
         let dialog_y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
 
@@ -33,15 +48,15 @@ impl Widget for KeybindingEditWidget<'_> {
 
         // Draw dialog border
         let title = if self.edit_mode.is_new {
-            " Add Keybinding "
+            tr("Add Keybinding")
         } else {
-            " Edit Keybinding "
+            tr("Edit Keybinding")
         };
 
         let block = Block::default()
             .borders(Borders::ALL)
             .border_style(Style::default().fg(Color::Cyan))
-            .title(title);
+            .title(format!(" {title} "));
 
         let inner = block.inner(dialog_area);
         block.render(dialog_area, buf);
@@ -134,7 +149,19 @@ impl Widget for KeybindingEditWidget<'_> {
             is_focused,
             placeholder,
         );
-        y += 2;
+        y += 1;
+
+        // Inline validation error, shown once something has been typed
+        if !self.edit_mode.action_value.trim().is_empty() {
+            if let Err(message) = self.edit_mode.validate_action() {
+                let mut message = format!("✗ {message}");
+                if message.chars().count() > input_width {
+                    message = message.chars().take(input_width).collect();
+                }
+                buf.set_string(inner.x + 1, y, &message, Style::default().fg(Color::Red));
+            }
+        }
+        y += 1;
 
         // Properties section
         buf.set_string(inner.x + 1, y, "Properties:", label_style);
@@ -151,6 +178,34 @@ impl Widget for KeybindingEditWidget<'_> {
         buf.set_string(inner.x + 3, y, repeat_value, style);
         y += 1;
 
+        // Cooldown between repeats
+        let is_focused = self.edit_mode.focused_field == EditField::Cooldown;
+        buf.set_string(inner.x + 3, y, "cooldown:", if is_focused { focused_style } else { value_style });
+        let cooldown_width = 12.min(input_width.saturating_sub(14));
+        self.render_input_field(
+            buf,
+            inner.x + 13,
+            y,
+            cooldown_width,
+            &self.edit_mode.cooldown,
+            self.edit_mode.cooldown_cursor,
+            is_focused,
+            is_focused.then_some("none"),
+        );
+        let note = match self.edit_mode.parsed_cooldown() {
+            Ok(Some(ms)) => (format!("{ms} ms"), hint_style),
+            Ok(None) => ("e.g., 150ms or 1.5s".to_string(), hint_style),
+            Err(_) => ("✗ invalid duration".to_string(), Style::default().fg(Color::Red)),
+        };
+        buf.set_stringn(
+            inner.x + 16 + cooldown_width as u16,
+            y,
+            note.0,
+            input_width.saturating_sub(cooldown_width + 16),
+            note.1,
+        );
+        y += 1;
+
         // Allow when locked toggle
         let is_focused = self.edit_mode.focused_field == EditField::AllowWhenLocked;
         let locked_value = match self.edit_mode.allow_when_locked {
@@ -162,12 +217,33 @@ impl Widget for KeybindingEditWidget<'_> {
         buf.set_string(inner.x + 3, y, locked_value, style);
         y += 2;
 
+        // Read-only preview of the KDL that will be written
+        let bottom = inner.y + inner.height;
+        if y < bottom {
+            buf.set_string(inner.x + 1, y, "Will write:", label_style);
+            y += 1;
+        }
+        let preview_style = if self.preview.is_some() {
+            Style::default().fg(Color::Green)
+        } else {
+            hint_style
+        };
+        for line in &preview_lines {
+            if y + 1 >= bottom {
+                break;
+            }
+            let line: String = line.chars().take(input_width.saturating_sub(2)).collect();
+            buf.set_string(inner.x + 3, y, &line, preview_style);
+            y += 1;
+        }
+        y += 1;
+
         // Help text
         if y < inner.y + inner.height {
             buf.set_string(
                 inner.x + 1,
                 y,
-                "↑↓:Fields  ←→:Cursor  Enter:Save  Esc:Cancel",
+                "↑↓:Fields  ←→:Cursor  Enter:Save  Ctrl+T:Dry run  Esc:Cancel",
                 hint_style,
             );
         }
@@ -232,19 +308,15 @@ impl KeybindingEditWidget<'_> {
         }
 
         // Calculate visible portion of text based on cursor position
-        let text_len = text.len();
+        let text_len = text.chars().count();
         let visible_width = inner_width.saturating_sub(1); // Leave room for cursor at end
 
         // Calculate scroll offset to keep cursor visible
-        let scroll_offset = if cursor_pos <= visible_width {
-            0
-        } else {
-            cursor_pos - visible_width
-        };
+        let scroll_offset = cursor_pos.saturating_sub(visible_width);
 
         // Get the visible portion of text
         let visible_end = (scroll_offset + visible_width).min(text_len);
-        let visible_text = &text[scroll_offset..visible_end];
+        let visible_text: String = text.chars().skip(scroll_offset).take(visible_width).collect();
 
         // Text style
         let text_style = if focused {
@@ -271,7 +343,7 @@ impl KeybindingEditWidget<'_> {
             let cursor_style = Style::default()
                 .bg(Color::Yellow)
                 .fg(Color::Black);
-            buf.set_string(cursor_x, y, &cursor_char.to_string(), cursor_style);
+            buf.set_string(cursor_x, y, cursor_char.to_string(), cursor_style);
         }
 
         // Show scroll indicator if text is scrolled
@@ -5,31 +5,77 @@ use ratatui::{
     widgets::{Block, Borders, Widget},
 };
 
-use crate::model::{BindingStatus, KeybindingsViewModel};
+use super::Density;
+use crate::i18n::{tr, tr_args};
+use crate::model::{BindingStatus, EffectiveBinding, KeybindingColumns, KeybindingsViewModel};
+use crate::widgets::table::{column_widths, fit, render_header, render_row, Column};
 
 /// Widget for displaying the list of keybindings
 pub struct KeybindingsListWidget<'a> {
     view_model: &'a KeybindingsViewModel,
+    /// The view model's filtered bindings, when the caller already has them
+    filtered: Option<&'a [EffectiveBinding<'a>]>,
     focused: bool,
+    density: Density,
 }
 
 impl<'a> KeybindingsListWidget<'a> {
     pub fn new(view_model: &'a KeybindingsViewModel, focused: bool) -> Self {
-        Self { view_model, focused }
+        Self { view_model, filtered: None, focused, density: Density::Compact }
+    }
+
+    /// Draw these filtered bindings instead of filtering again
+    pub fn filtered(mut self, filtered: &'a [EffectiveBinding<'a>]) -> Self {
+        self.filtered = Some(filtered);
+        self
+    }
+
+    pub fn density(mut self, density: Density) -> Self {
+        self.density = density;
+        self
     }
 }
 
 impl Widget for KeybindingsListWidget<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let filtered = self.view_model.filtered_bindings();
+        let owned;
+        let filtered = match self.filtered {
+            Some(filtered) => filtered,
+            None => {
+                owned = self.view_model.filtered_bindings();
+                &owned
+            }
+        };
         let count = filtered.len();
 
-        // Draw border with count
-        let title = if self.view_model.search_query.is_empty() {
-            format!(" Keybindings ({count}) ")
+        // Draw border with count and the mix of action categories
+        let breakdown: Vec<String> = KeybindingsViewModel::category_counts(filtered)
+            .into_iter()
+            .map(|(category, n)| format!("{n} {}", category.to_lowercase()))
+            .collect();
+        let counts = if breakdown.is_empty() {
+            count.to_string()
+        } else {
+            format!("{count}: {}", breakdown.join(", "))
+        };
+        let mut title = if self.view_model.search_query.is_empty() {
+            format!(" {} ({counts}) ", tr("Keybindings"))
         } else {
-            format!(" Keybindings ({}) [/{}] ", count, self.view_model.search_query)
+            // Query first: a long breakdown gets cut off at the border
+            format!(" {} [/{}] ({counts}) ", tr("Keybindings"), self.view_model.search_query)
         };
+        let lints = KeybindingsViewModel::count_repeat_lints(filtered);
+        if lints > 0 {
+            title.push_str(&format!("⚠ {} ", tr_args("{count} repeat", &[("count", &lints)])));
+        }
+        if self.view_model.missing_only {
+            title.push_str(&format!("[{}] ", tr("missing binaries")));
+        }
+        if self.view_model.show_defaults {
+            if let Some(release) = &self.view_model.defaults_release {
+                title.push_str(&format!("+ niri {release} defaults "));
+            }
+        }
 
         let border_style = if self.focused {
             Style::default().fg(Color::Cyan)
@@ -40,18 +86,31 @@ impl Widget for KeybindingsListWidget<'_> {
         let block = Block::default()
             .borders(Borders::ALL)
             .border_style(border_style)
+            .padding(self.density.padding())
             .title(title);
 
         let inner = block.inner(area);
         block.render(area, buf);
 
-        if inner.height < 1 || inner.width < 10 {
+        if inner.height < 2 || inner.width < 10 {
             return;
         }
 
-        // Calculate visible range
-        let visible_height = inner.height as usize;
-        let scroll_offset = self.view_model.scroll_offset;
+        let columns = table_columns(&self.view_model.columns);
+        let widths = column_widths(&columns, inner.width.saturating_sub(3));
+        render_header(
+            buf,
+            inner.x + 2,
+            inner.y,
+            &columns,
+            &widths,
+            Style::default().fg(Color::DarkGray).add_modifier(Modifier::BOLD),
+        );
+
+        // Calculate visible range (below the header)
+        let rows = self.density.binding_rows();
+        let visible_height = (inner.height as usize - 1) / rows;
+        let scroll_offset = self.view_model.list.scroll_offset;
 
         // Render visible items
         for (i, eb) in filtered
@@ -60,45 +119,23 @@ impl Widget for KeybindingsListWidget<'_> {
             .take(visible_height)
             .enumerate()
         {
-            let y = inner.y + i as u16;
-            let is_selected = scroll_offset + i == self.view_model.selected_index;
+            let y = inner.y + 1 + (i * rows) as u16;
+            let is_selected = scroll_offset + i == self.view_model.list.selected_index;
 
             // Status indicator
             let status_char = match eb.status {
                 BindingStatus::Modified => "*",
                 BindingStatus::Added => "+",
+                BindingStatus::Default => "·",
                 BindingStatus::Unchanged => " ",
             };
 
-            // Selection indicator
-            let indicator = if is_selected {
-                format!(">{status_char}")
-            } else {
-                format!(" {status_char}")
-            };
-
-            // Key combo (left-aligned, max width)
-            let combo = eb.binding.combo();
-            let combo_width = 18.min(inner.width as usize - 3);
-            let combo_display = if combo.len() > combo_width {
-                format!("{}...", &combo[..combo_width - 3])
-            } else {
-                format!("{combo:combo_width$}")
-            };
-
-            // Action description (right side)
-            let action_desc = eb.binding.action.short_description();
-            let action_width = inner.width as usize - combo_width - 4;
-            let action_display = if action_desc.len() > action_width {
-                format!("{}...", &action_desc[..action_width.saturating_sub(3)])
-            } else {
-                action_desc
-            };
 
             // Style based on selection and status
             let base_color = match eb.status {
                 BindingStatus::Modified => Color::Cyan,
                 BindingStatus::Added => Color::Green,
+                BindingStatus::Default => Color::DarkGray,
                 BindingStatus::Unchanged => Color::Gray,
             };
 
@@ -120,22 +157,41 @@ impl Widget for KeybindingsListWidget<'_> {
                 Style::default().fg(Color::DarkGray)
             };
 
-            // Render the line
-            buf.set_string(inner.x, y, &indicator, style);
-            buf.set_string(inner.x + 2, y, &combo_display, style);
-            buf.set_string(
-                inner.x + 2 + combo_width as u16 + 1,
-                y,
-                &action_display,
-                action_style,
-            );
+            let combo = eb.binding.display_combo(self.view_model.raw_key_names);
+            let mut action = eb.binding.action.short_description();
+            for tag in self.view_model.tags.tags(&eb.binding.combo()) {
+                action.push_str(&format!(" #{tag}"));
+            }
+            let mut flags = eb.binding.properties.flags();
+            if eb.binding.repeat_lint().is_some() && eb.status != BindingStatus::Default {
+                flags.insert_str(0, "⚠ ");
+            }
+            let mut cells = vec![(combo.as_str(), style), (action.as_str(), action_style)];
+            if self.view_model.columns.flags {
+                cells.push((flags.as_str(), action_style));
+            }
+            if self.view_model.columns.category {
+                cells.push((eb.binding.action.category(), action_style));
+            }
+
+            // Render the line: selection and status indicators, then the cells
+            buf.set_string(inner.x, y, if is_selected { ">" } else { " " }, style);
+            buf.set_string(inner.x + 1, y, status_char, style);
+            render_row(buf, inner.x + 2, y, &widths, &cells);
+
+            // Spacious rows spell out the whole command underneath
+            if rows > 1 {
+                let width = inner.width.saturating_sub(4) as usize;
+                let command = fit(&eb.binding.action.full_description(), width);
+                buf.set_stringn(inner.x + 4, y + 1, command, width, action_style);
+            }
         }
 
         // Show scroll indicators if needed
         if scroll_offset > 0 {
             buf.set_string(
                 inner.x + inner.width - 3,
-                inner.y,
+                inner.y + 1,
                 "▲",
                 Style::default().fg(Color::DarkGray),
             );
@@ -150,3 +206,18 @@ impl Widget for KeybindingsListWidget<'_> {
         }
     }
 }
+
+/// Table columns in display order; optional ones on the right are dropped first
+fn table_columns(options: &KeybindingColumns) -> Vec<Column> {
+    let mut columns = vec![
+        Column::fixed("Combo", options.combo_width, KeybindingColumns::MIN_COMBO_WIDTH),
+        Column::fill("Action", 10),
+    ];
+    if options.flags {
+        columns.push(Column::fixed("Flags", 12, 6));
+    }
+    if options.category {
+        columns.push(Column::fixed("Category", 17, 8));
+    }
+    columns
+}
@@ -0,0 +1,83 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Widget},
+};
+
+use crate::i18n::tr;
+
+/// Modal prompt shown when saving keybindings would leave quit or
+/// close-window without any bind
+pub struct LockoutWarningWidget<'a> {
+    actions: &'a [&'static str],
+}
+
+impl<'a> LockoutWarningWidget<'a> {
+    pub fn new(actions: &'a [&'static str]) -> Self {
+        Self { actions }
+    }
+}
+
+impl Widget for LockoutWarningWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let dialog_width = 64.min(area.width.saturating_sub(4));
+        let dialog_height = 10.min(area.height.saturating_sub(2));
+        let dialog_x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
+        let dialog_y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
+
+        let dialog_area = Rect::new(dialog_x, dialog_y, dialog_width, dialog_height);
+        Clear.render(dialog_area, buf);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Red))
+            .title(format!(" {} ", tr("No Way Out")));
+
+        let inner = block.inner(dialog_area);
+        block.render(dialog_area, buf);
+
+        if inner.height < 7 || inner.width < 30 {
+            return;
+        }
+
+        let text_style = Style::default().fg(Color::White);
+        let key_style = Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD);
+        let width = inner.width.saturating_sub(2);
+
+        let mut y = inner.y;
+        buf.set_stringn(
+            inner.x + 1,
+            y,
+            format!("After this save nothing is bound to {}.", self.actions.join(" or ")),
+            width as usize,
+            Style::default().fg(Color::Cyan),
+        );
+        y += 1;
+        buf.set_stringn(
+            inner.x + 1,
+            y,
+            "Without them a session can't be left from the keyboard.",
+            width as usize,
+            text_style,
+        );
+        y += 2;
+
+        let options = [
+            ("a", "Add fallback binds and save"),
+            ("s", "Save anyway"),
+            ("Esc", "Cancel"),
+        ];
+        for (key, label) in options {
+            let line = Line::from(vec![
+                Span::styled(format!("[{key}] "), key_style),
+                Span::styled(label, text_style),
+            ]);
+            buf.set_line(inner.x + 1, y, &line, width);
+            y += 1;
+        }
+    }
+}
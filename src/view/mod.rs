@@ -1,21 +1,75 @@
 pub mod appearance_detail;
 pub mod appearance_edit;
 pub mod appearance_list;
+pub mod appearance_section_edit;
+pub mod bind_overlay;
+pub mod binding_conflict;
+pub mod binding_generator;
+pub mod binding_tags;
+pub mod binding_template;
+pub mod config_source;
+pub mod debug_overlay;
+pub mod disabled_output_warning;
+pub mod dry_run;
+pub mod error_log;
+pub mod general_detail;
+pub mod general_edit;
+pub mod general_list;
+pub mod health_report;
 pub mod keybinding_detail;
 pub mod keybinding_edit;
 pub mod keybindings_list;
+pub mod lockout_warning;
+pub mod mode_browser;
+pub mod output_colors;
+pub mod output_label;
 pub mod output_list;
+pub mod output_position;
+pub mod output_profiles;
 pub mod output_view;
+pub mod quick_open;
+pub mod save_conflict;
 pub mod status_bar;
 pub mod tab_bar;
+pub mod template_gallery;
+pub mod theme;
+pub mod tutorial;
+pub mod workspace_spawn;
 
 pub use appearance_detail::AppearanceDetailWidget;
 pub use appearance_edit::AppearanceEditWidget;
 pub use appearance_list::AppearanceListWidget;
+pub use appearance_section_edit::AppearanceSectionEditWidget;
+pub use bind_overlay::BindOverlayWidget;
+pub use binding_conflict::BindingConflictWidget;
+pub use binding_generator::BindingGeneratorWidget;
+pub use binding_tags::BindingTagsWidget;
+pub use binding_template::BindingTemplateWidget;
+pub use config_source::ConfigSourceWidget;
+pub use debug_overlay::DebugOverlayWidget;
+pub use disabled_output_warning::DisabledOutputWarningWidget;
+pub use dry_run::DryRunWidget;
+pub use error_log::ErrorLogWidget;
+pub use general_detail::GeneralDetailWidget;
+pub use general_edit::GeneralEditWidget;
+pub use general_list::GeneralListWidget;
+pub use health_report::HealthReportWidget;
 pub use keybinding_detail::KeybindingDetailWidget;
 pub use keybinding_edit::KeybindingEditWidget;
 pub use keybindings_list::KeybindingsListWidget;
+pub use lockout_warning::LockoutWarningWidget;
+pub use mode_browser::ModeBrowserWidget;
+pub use output_colors::OutputColorsWidget;
+pub use output_label::OutputLabelWidget;
 pub use output_list::OutputListWidget;
+pub use output_position::OutputPositionWidget;
+pub use output_profiles::OutputProfilesWidget;
 pub use output_view::OutputInfoWidget;
+pub use quick_open::QuickOpenWidget;
+pub use save_conflict::SaveConflictWidget;
 pub use status_bar::StatusBarWidget;
 pub use tab_bar::TabBarWidget;
+pub use template_gallery::TemplateGalleryWidget;
+pub use theme::{ColorMode, Density};
+pub use tutorial::TutorialWidget;
+pub use workspace_spawn::WorkspaceSpawnWidget;
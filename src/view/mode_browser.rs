@@ -0,0 +1,119 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Widget},
+};
+
+use crate::i18n::tr_args;
+use crate::model::{ModeBrowser, OutputMode, OutputState};
+
+/// Panel listing every mode of the selected output
+pub struct ModeBrowserWidget<'a> {
+    browser: &'a ModeBrowser,
+    output: &'a OutputState,
+    staged: Option<&'a OutputMode>, // Mode picked but not saved yet
+}
+
+impl<'a> ModeBrowserWidget<'a> {
+    pub fn new(browser: &'a ModeBrowser, output: &'a OutputState) -> Self {
+        Self { browser, output, staged: None }
+    }
+
+    pub fn staged(mut self, staged: Option<&'a OutputMode>) -> Self {
+        self.staged = staged;
+        self
+    }
+}
+
+impl Widget for ModeBrowserWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let filter = match self.browser.refresh_filter {
+            Some(hz) => format!("{hz} Hz"),
+            None => "all rates".to_string(),
+        };
+        let block = Block::default()
+            .title(format!(" {} ", tr_args("Modes: {output} ({filter})", &[("output", &self.output.name), ("filter", &filter)])))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan));
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if inner.height < 3 || inner.width < 20 {
+            return;
+        }
+
+        let hint_y = inner.y + inner.height - 1;
+        let list_height = inner.height.saturating_sub(2) as usize;
+        let visible = self.browser.visible_modes(self.output);
+
+        if visible.is_empty() {
+            buf.set_string(
+                inner.x + 1,
+                inner.y,
+                "No modes reported",
+                Style::default().fg(Color::DarkGray),
+            );
+        }
+
+        // Keep the selection in view
+        let scroll = self
+            .browser
+            .list
+            .selected_index
+            .saturating_sub(list_height.saturating_sub(1));
+
+        for (row, (i, (mode_index, mode))) in visible
+            .iter()
+            .enumerate()
+            .skip(scroll)
+            .take(list_height)
+            .enumerate()
+        {
+            let y = inner.y + row as u16;
+            let is_selected = i == self.browser.list.selected_index;
+            let is_current = self.output.current_mode_index == Some(*mode_index);
+            let is_staged = self.staged == Some(*mode);
+
+            let style = if is_selected {
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else if is_staged {
+                Style::default().fg(Color::Cyan)
+            } else if is_current {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default().fg(Color::White)
+            };
+
+            let marker = if is_selected { "▶" } else { " " };
+            let text = format!(
+                "{marker} {:>5}x{:<5} @ {:>7.3} Hz",
+                mode.width, mode.height, mode.refresh_rate
+            );
+            buf.set_string(inner.x + 1, y, &text, style);
+
+            let mut x = inner.x + 1 + text.chars().count() as u16 + 2;
+            if is_current {
+                buf.set_string(x, y, "● current", Style::default().fg(Color::Green));
+                x += 11;
+            }
+            if is_staged {
+                buf.set_string(x, y, "◆ staged", Style::default().fg(Color::Cyan));
+                x += 10;
+            }
+            if mode.is_preferred {
+                buf.set_string(x, y, "★ preferred", Style::default().fg(Color::Magenta));
+            }
+        }
+
+        buf.set_string(
+            inner.x + 1,
+            hint_y,
+            "j/k: Select  Enter: Use mode  f: Filter by rate  Esc: Close",
+            Style::default().fg(Color::DarkGray),
+        );
+    }
+}
@@ -0,0 +1,77 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, Widget},
+};
+
+use super::appearance_edit::parse_hex_color;
+use crate::i18n::tr;
+use crate::model::OutputColorEditor;
+
+/// Modal dialog for an output's background and backdrop colors
+pub struct OutputColorsWidget<'a> {
+    editor: &'a OutputColorEditor,
+}
+
+impl<'a> OutputColorsWidget<'a> {
+    pub fn new(editor: &'a OutputColorEditor) -> Self {
+        Self { editor }
+    }
+}
+
+impl Widget for OutputColorsWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let dialog_width = 56.min(area.width.saturating_sub(4));
+        let dialog_height = (self.editor.fields.len() as u16 + 4).min(area.height.saturating_sub(2));
+        let dialog_x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
+        let dialog_y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
+
+        let dialog_area = Rect::new(dialog_x, dialog_y, dialog_width, dialog_height);
+        Clear.render(dialog_area, buf);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(format!(" {} {} ", tr("Colors"), self.editor.output));
+
+        let inner = block.inner(dialog_area);
+        block.render(dialog_area, buf);
+
+        if inner.height < 3 || inner.width < 24 {
+            return;
+        }
+
+        let focused = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+        let normal = Style::default().fg(Color::White);
+        let label_style = Style::default().fg(Color::Gray);
+        let hint_y = inner.y + inner.height - 1;
+
+        for (i, field) in self.editor.fields.iter().enumerate() {
+            let y = inner.y + i as u16;
+            if y >= hint_y {
+                break;
+            }
+            buf.set_string(inner.x + 1, y, format!("{:<11}", tr(&field.name)), label_style);
+            // Swatch for hex colors; named colors are written as typed
+            if let Some(color) = parse_hex_color(field.value.trim()) {
+                buf.set_string(inner.x + 13, y, "██", Style::default().fg(color));
+            }
+            let (value, style) = if self.editor.focused == i {
+                (format!("{}│{}", &field.value[..field.cursor], &field.value[field.cursor..]), focused)
+            } else if field.value.is_empty() {
+                ("(not set)".to_string(), Style::default().fg(Color::DarkGray))
+            } else {
+                (field.value.clone(), normal)
+            };
+            buf.set_stringn(inner.x + 16, y, &value, inner.width.saturating_sub(17) as usize, style);
+        }
+
+        buf.set_string(
+            inner.x + 1,
+            hint_y,
+            "Tab: Next  Enter: Stage  Empty: Remove  Esc: Cancel",
+            Style::default().fg(Color::DarkGray),
+        );
+    }
+}
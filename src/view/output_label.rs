@@ -0,0 +1,74 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, Widget},
+};
+
+use crate::i18n::tr;
+use crate::output_labels::OutputLabelEditor;
+
+/// Modal dialog for an output's label and note
+pub struct OutputLabelWidget<'a> {
+    editor: &'a OutputLabelEditor,
+}
+
+impl<'a> OutputLabelWidget<'a> {
+    pub fn new(editor: &'a OutputLabelEditor) -> Self {
+        Self { editor }
+    }
+}
+
+impl Widget for OutputLabelWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let dialog_width = 60.min(area.width.saturating_sub(4));
+        let dialog_height = (self.editor.fields.len() as u16 + 4).min(area.height.saturating_sub(2));
+        let dialog_x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
+        let dialog_y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
+
+        let dialog_area = Rect::new(dialog_x, dialog_y, dialog_width, dialog_height);
+        Clear.render(dialog_area, buf);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(format!(" {} {} ", tr("Label"), self.editor.output));
+
+        let inner = block.inner(dialog_area);
+        block.render(dialog_area, buf);
+
+        if inner.height < 3 || inner.width < 20 {
+            return;
+        }
+
+        let focused = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+        let normal = Style::default().fg(Color::White);
+        let label_style = Style::default().fg(Color::Gray);
+        let hint_y = inner.y + inner.height - 1;
+        let width = inner.width.saturating_sub(10) as usize;
+
+        for (i, field) in self.editor.fields.iter().enumerate() {
+            let y = inner.y + i as u16;
+            if y >= hint_y {
+                break;
+            }
+            buf.set_string(inner.x + 1, y, format!("{:<7}", tr(&field.name)), label_style);
+            let (value, style) = if self.editor.focused == i {
+                (format!("{}│{}", &field.value[..field.cursor], &field.value[field.cursor..]), focused)
+            } else {
+                (field.value.clone(), normal)
+            };
+            // Keep the cursor in view in long notes
+            let skip = value.chars().count().saturating_sub(width);
+            let value: String = value.chars().skip(skip).collect();
+            buf.set_string(inner.x + 9, y, &value, style);
+        }
+
+        buf.set_string(
+            inner.x + 1,
+            hint_y,
+            "Tab: Next field  Enter: Save  Esc: Cancel",
+            Style::default().fg(Color::DarkGray),
+        );
+    }
+}
@@ -6,33 +6,56 @@ use ratatui::{
     widgets::{Block, Borders, List, ListItem, ListState, StatefulWidget, Widget},
 };
 
+use super::Density;
+use crate::i18n::tr;
 use crate::model::OutputViewModel;
+use crate::output_labels::OutputLabels;
 
 pub struct OutputListWidget<'a> {
     pub view_model: &'a OutputViewModel,
     pub focused: bool,
+    pub density: Density,
+    pub labels: Option<&'a OutputLabels>,
 }
 
 impl<'a> OutputListWidget<'a> {
     pub fn new(view_model: &'a OutputViewModel, focused: bool) -> Self {
-        Self { view_model, focused }
+        Self { view_model, focused, density: Density::Compact, labels: None }
+    }
+
+    pub fn density(mut self, density: Density) -> Self {
+        self.density = density;
+        self
+    }
+
+    pub fn labels(mut self, labels: &'a OutputLabels) -> Self {
+        self.labels = Some(labels);
+        self
     }
 }
 
 impl<'a> Widget for OutputListWidget<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let items: Vec<ListItem> = self
-            .view_model
-            .outputs
+        let visible = self.view_model.visible_indices();
+        let items: Vec<ListItem> = visible
             .iter()
-            .enumerate()
-            .map(|(idx, output)| {
-                let selected = idx == self.view_model.selected_index;
-                let modified = self.view_model.pending_changes.contains_key(&output.name);
+            .map(|&idx| {
+                let output = &self.view_model.outputs[idx];
+                let selected = idx == self.view_model.list.selected_index;
+                let modified = self.view_model.is_modified(&output.name);
 
                 let prefix = if selected { "> " } else { "  " };
                 let suffix = if modified { " (*)" } else { "" };
                 let enabled_indicator = if output.enabled { "" } else { " [off]" };
+                let duplicates = match output.duplicate_sources.len() {
+                    0 => String::new(),
+                    n => format!(" [{} blocks]", n + 1),
+                };
+                let label = self
+                    .labels
+                    .and_then(|labels| labels.label(&output.name))
+                    .map(|label| format!(" {label}"))
+                    .unwrap_or_default();
 
                 let style = if !output.enabled {
                     Style::default().fg(Color::DarkGray)
@@ -51,7 +74,9 @@ impl<'a> Widget for OutputListWidget<'a> {
                 let line = Line::from(vec![
                     Span::styled(prefix, style),
                     Span::styled(&output.name, style),
+                    Span::styled(label, Style::default().fg(Color::Magenta)),
                     Span::styled(enabled_indicator, Style::default().fg(Color::DarkGray)),
+                    Span::styled(duplicates, Style::default().fg(Color::Yellow)),
                     Span::styled(suffix, Style::default().fg(Color::Cyan)),
                 ]);
                 ListItem::new(line)
@@ -64,15 +89,21 @@ impl<'a> Widget for OutputListWidget<'a> {
             Style::default().fg(Color::DarkGray)
         };
 
+        let title = if self.view_model.search_query.is_empty() && !self.view_model.search_mode {
+            format!(" {} ", tr("Outputs"))
+        } else {
+            format!(" {} [/{}] ({}) ", tr("Outputs"), self.view_model.search_query, visible.len())
+        };
         let list = List::new(items).block(
             Block::default()
-                .title(" Outputs ")
+                .title(title)
                 .borders(Borders::ALL)
-                .border_style(border_style),
+                .border_style(border_style)
+                .padding(self.density.padding()),
         );
 
         let mut state = ListState::default();
-        state.select(Some(self.view_model.selected_index));
+        state.select(visible.iter().position(|&idx| idx == self.view_model.list.selected_index));
 
         StatefulWidget::render(list, area, buf, &mut state);
     }
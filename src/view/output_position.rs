@@ -0,0 +1,77 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, Widget},
+};
+
+use crate::i18n::tr;
+use crate::model::OutputPositionEditor;
+
+/// Modal dialog for typing an output's exact position
+pub struct OutputPositionWidget<'a> {
+    editor: &'a OutputPositionEditor,
+}
+
+impl<'a> OutputPositionWidget<'a> {
+    pub fn new(editor: &'a OutputPositionEditor) -> Self {
+        Self { editor }
+    }
+}
+
+impl Widget for OutputPositionWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let dialog_width = 48.min(area.width.saturating_sub(4));
+        let dialog_height = (self.editor.fields.len() as u16 + 5).min(area.height.saturating_sub(2));
+        let dialog_x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
+        let dialog_y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
+
+        let dialog_area = Rect::new(dialog_x, dialog_y, dialog_width, dialog_height);
+        Clear.render(dialog_area, buf);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(format!(" {} {} ", tr("Position"), self.editor.output));
+
+        let inner = block.inner(dialog_area);
+        block.render(dialog_area, buf);
+
+        if inner.height < 4 || inner.width < 24 {
+            return;
+        }
+
+        let focused = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+        let normal = Style::default().fg(Color::White);
+        let label_style = Style::default().fg(Color::Gray);
+        let hint_y = inner.y + inner.height - 1;
+
+        for (i, field) in self.editor.fields.iter().enumerate() {
+            let y = inner.y + i as u16;
+            buf.set_string(inner.x + 1, y, format!("{}:", field.name), label_style);
+            let (value, style) = if self.editor.focused == i {
+                (format!("{}│{}", &field.value[..field.cursor], &field.value[field.cursor..]), focused)
+            } else {
+                (field.value.clone(), normal)
+            };
+            buf.set_stringn(inner.x + 4, y, &value, inner.width.saturating_sub(5) as usize, style);
+        }
+
+        if let Some(error) = &self.editor.error {
+            buf.set_stringn(
+                inner.x + 1,
+                inner.y + self.editor.fields.len() as u16 + 1,
+                error,
+                inner.width.saturating_sub(2) as usize,
+                Style::default().fg(Color::Red),
+            );
+        }
+
+        buf.set_string(
+            inner.x + 1,
+            hint_y,
+            "Tab: Next  Enter: Stage  Esc: Cancel",
+            Style::default().fg(Color::DarkGray),
+        );
+    }
+}
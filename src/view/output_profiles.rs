@@ -0,0 +1,94 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, Widget},
+};
+
+use crate::i18n::tr;
+use crate::output_profiles::{OutputProfiles, ProfilePicker};
+
+/// Dialog listing saved output profiles, with the name field for saving
+/// the current arrangement
+pub struct OutputProfilesWidget<'a> {
+    picker: &'a ProfilePicker,
+    profiles: &'a OutputProfiles,
+}
+
+impl<'a> OutputProfilesWidget<'a> {
+    pub fn new(picker: &'a ProfilePicker, profiles: &'a OutputProfiles) -> Self {
+        Self { picker, profiles }
+    }
+}
+
+impl Widget for OutputProfilesWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let rows = self.profiles.profiles.len().max(1) as u16;
+        let dialog_width = 70.min(area.width.saturating_sub(4));
+        let dialog_height = (rows + 5).min(area.height.saturating_sub(2));
+        let dialog_x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
+        let dialog_y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
+
+        let dialog_area = Rect::new(dialog_x, dialog_y, dialog_width, dialog_height);
+        Clear.render(dialog_area, buf);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(format!(" {} ", tr("Output Profiles")));
+
+        let inner = block.inner(dialog_area);
+        block.render(dialog_area, buf);
+
+        if inner.height < 4 || inner.width < 30 {
+            return;
+        }
+
+        let width = inner.width.saturating_sub(2) as usize;
+        let hint_y = inner.y + inner.height - 1;
+        let name_y = hint_y - 2;
+        let list_height = (name_y - inner.y) as usize;
+
+        if self.profiles.profiles.is_empty() {
+            buf.set_string(inner.x + 1, inner.y, "No profiles saved yet", Style::default().fg(Color::DarkGray));
+        }
+        let selected = self.picker.list.selected_index;
+        let scroll = selected.saturating_sub(list_height.saturating_sub(1));
+        for (row, (i, profile)) in self.profiles.profiles.iter().enumerate().skip(scroll).take(list_height).enumerate() {
+            let y = inner.y + row as u16;
+            let is_selected = i == selected && self.picker.naming.is_none();
+            let style = if is_selected {
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            let outputs: Vec<String> = profile
+                .outputs
+                .iter()
+                .map(|o| if o.enabled { o.name.clone() } else { format!("{} (off)", o.name) })
+                .collect();
+            let marker = if is_selected { "▶" } else { " " };
+            let auto = if profile.auto_apply { "auto" } else { "" };
+            let text = format!("{marker} {:<16} {auto:<4} {}", profile.name, outputs.join(", "));
+            buf.set_stringn(inner.x + 1, y, text, width, style);
+        }
+
+        let hint = match &self.picker.naming {
+            Some(field) => {
+                let value = format!("{}│{}", &field.value[..field.cursor], &field.value[field.cursor..]);
+                let skip = value.chars().count().saturating_sub(width.saturating_sub(7));
+                let value: String = value.chars().skip(skip).collect();
+                buf.set_string(inner.x + 1, name_y, format!("{:<7}", tr("Name")), Style::default().fg(Color::Gray));
+                buf.set_string(
+                    inner.x + 8,
+                    name_y,
+                    value,
+                    Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                );
+                "Enter: Save current outputs  Esc: Back"
+            }
+            None => "Enter: Apply  s: Save over  n: New  d: Delete  a: Auto  Esc: Close",
+        };
+        buf.set_string(inner.x + 1, hint_y, hint, Style::default().fg(Color::DarkGray));
+    }
+}
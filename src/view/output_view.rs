@@ -6,76 +6,129 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, Widget},
 };
 
-use crate::model::{OutputState, OutputViewModel, Position};
+use super::appearance_edit::parse_hex_color;
+use crate::i18n::tr;
+use crate::model::{OutputChange, OutputState, OutputViewModel, SnapAlignment};
+use crate::output_labels::{OutputLabel, OutputLabels};
 
 /// Info panel showing details about the selected output
 pub struct OutputInfoWidget<'a> {
     pub output: Option<&'a OutputState>,
-    pub pending_position: Option<Position>,
+    pub change: Option<&'a OutputChange>,
+    pub label: Option<&'a OutputLabel>,
+    pub snap_alignment: SnapAlignment,
 }
 
 impl<'a> OutputInfoWidget<'a> {
     pub fn new(view_model: &'a OutputViewModel) -> Self {
         let output = view_model.selected_output();
-        let pending_position = output.and_then(|o| view_model.pending_changes.get(&o.name).copied());
-        Self {
-            output,
-            pending_position,
-        }
+        let change = output.and_then(|o| view_model.pending_changes.get(&o.name));
+        Self { output, change, label: None, snap_alignment: view_model.snap_alignment }
+    }
+
+    pub fn labels(mut self, labels: &'a OutputLabels) -> Self {
+        self.label = self.output.and_then(|o| labels.get(&o.name));
+        self
+    }
+}
+
+/// Value style and suffix for a field, highlighted when it has a staged change
+fn field_spans<'a>(value: String, modified: bool) -> [Span<'a>; 2] {
+    if modified {
+        [
+            Span::styled(value, Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Span::styled(" (modified)", Style::default().fg(Color::Cyan)),
+        ]
+    } else {
+        [Span::styled(value, Style::default().fg(Color::White)), Span::raw("")]
     }
 }
 
 impl<'a> Widget for OutputInfoWidget<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let block = Block::default()
-            .title(" Output Info ")
+            .title(format!(" {} ", tr("Output Info")))
             .borders(Borders::ALL)
             .border_style(Style::default().fg(Color::DarkGray));
 
         let inner = block.inner(area);
         block.render(area, buf);
 
-        if let Some(output) = self.output {
-            let pos = self.pending_position.unwrap_or(output.position);
-            let modified = self.pending_position.is_some();
+        if let Some(current) = self.output {
+            // Show the output as it will be once the staged change is saved
+            let staged = self.change.map(|change| {
+                let mut output = current.clone();
+                output.apply_change(change);
+                output
+            });
+            let output = staged.as_ref().unwrap_or(current);
+            let change = self.change.cloned().unwrap_or_default();
+            let [mode, mode_suffix] = field_spans(output.mode_string(), change.mode.is_some());
+            let [scale, scale_suffix] = field_spans(format!("{}", output.scale), change.scale.is_some());
+            let [transform, transform_suffix] =
+                field_spans(output.transform.as_str().to_string(), change.transform.is_some());
+            let [vrr, vrr_suffix] = field_spans(if output.vrr { "on" } else { "off" }.to_string(), change.vrr.is_some());
+            let [power, power_suffix] =
+                field_spans(if output.enabled { "on" } else { "off" }.to_string(), change.enabled.is_some());
+            let [focus, focus_suffix] = field_spans(
+                if output.focus_at_startup { "yes" } else { "no" }.to_string(),
+                change.focus_at_startup.is_some(),
+            );
+            let [pos, pos_suffix] = field_spans(
+                format!("X={}, Y={}", output.position.x, output.position.y),
+                change.position.is_some(),
+            );
 
-            let lines = vec![
+            let mut lines = vec![
                 Line::from(vec![
                     Span::styled("Name: ", Style::default().fg(Color::Gray)),
                     Span::styled(&output.name, Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
+                    Span::styled(
+                        self.label.map(|l| format!(" {}", l.label)).unwrap_or_default(),
+                        Style::default().fg(Color::Magenta),
+                    ),
                 ]),
+                Line::from(vec![Span::styled("Mode: ", Style::default().fg(Color::Gray)), mode, mode_suffix]),
+                Line::from(vec![Span::styled("Scale: ", Style::default().fg(Color::Gray)), scale, scale_suffix]),
+                Line::from(vec![Span::styled("Transform: ", Style::default().fg(Color::Gray)), transform, transform_suffix]),
+                Line::from(vec![Span::styled("VRR: ", Style::default().fg(Color::Gray)), vrr, vrr_suffix]),
+                Line::from(vec![Span::styled("Power: ", Style::default().fg(Color::Gray)), power, power_suffix]),
+                Line::from(vec![Span::styled("Startup focus: ", Style::default().fg(Color::Gray)), focus, focus_suffix]),
+                Line::from(vec![Span::styled("Position: ", Style::default().fg(Color::Gray)), pos, pos_suffix]),
                 Line::from(vec![
-                    Span::styled("Mode: ", Style::default().fg(Color::Gray)),
-                    Span::styled(output.mode_string(), Style::default().fg(Color::White)),
-                ]),
-                Line::from(vec![
-                    Span::styled("Scale: ", Style::default().fg(Color::Gray)),
-                    Span::styled(format!("{:.1}", output.scale), Style::default().fg(Color::White)),
+                    Span::styled("Snap align: ", Style::default().fg(Color::Gray)),
+                    Span::styled(
+                        format!(
+                            "{} beside, {} above/below",
+                            self.snap_alignment.beside.beside_name(),
+                            self.snap_alignment.stacked.stacked_name()
+                        ),
+                        Style::default().fg(Color::White),
+                    ),
                 ]),
                 Line::from(vec![
-                    Span::styled("Transform: ", Style::default().fg(Color::Gray)),
-                    Span::styled(output.transform.as_str(), Style::default().fg(Color::White)),
+                    Span::styled("Logical Size: ", Style::default().fg(Color::Gray)),
+                    Span::styled(
+                        format!("{}x{}", output.logical_size.width, output.logical_size.height),
+                        Style::default().fg(Color::White),
+                    ),
                 ]),
                 Line::from(vec![
-                    Span::styled("Position: ", Style::default().fg(Color::Gray)),
+                    Span::styled("Physical: ", Style::default().fg(Color::Gray)),
                     Span::styled(
-                        format!("X={}, Y={}", pos.x, pos.y),
-                        if modified {
-                            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
-                        } else {
-                            Style::default().fg(Color::White)
-                        },
+                        format!("{}x{} px", output.physical_size.width, output.physical_size.height),
+                        Style::default().fg(Color::White),
                     ),
-                    if modified {
-                        Span::styled(" (modified)", Style::default().fg(Color::Cyan))
-                    } else {
-                        Span::raw("")
-                    },
                 ]),
                 Line::from(vec![
-                    Span::styled("Logical Size: ", Style::default().fg(Color::Gray)),
+                    Span::styled("DPI: ", Style::default().fg(Color::Gray)),
                     Span::styled(
-                        format!("{}x{}", output.logical_size.width, output.logical_size.height),
+                        match (output.ppi(), output.size_mm) {
+                            (Some(ppi), Some(mm)) => {
+                                format!("{ppi:.0} ppi ({}x{} mm)", mm.width, mm.height)
+                            }
+                            _ => "unknown".to_string(),
+                        },
                         Style::default().fg(Color::White),
                     ),
                 ]),
@@ -86,7 +139,61 @@ impl<'a> Widget for OutputInfoWidget<'a> {
                         Style::default().fg(Color::DarkGray),
                     ),
                 ]),
+                Line::from(vec![
+                    Span::styled("Defined at: ", Style::default().fg(Color::Gray)),
+                    Span::styled(
+                        output
+                            .source
+                            .as_ref()
+                            .map(|loc| loc.to_string())
+                            .unwrap_or_else(|| "(not in config)".to_string()),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ]),
+            ];
+
+            let colors = [
+                ("Background: ", &output.background_color, change.background_color.is_some()),
+                ("Backdrop: ", &output.backdrop_color, change.backdrop_color.is_some()),
             ];
+            for (label, color, modified) in colors {
+                let swatch = color.as_deref().and_then(parse_hex_color);
+                let [value, suffix] = field_spans(color.clone().unwrap_or_else(|| "default".to_string()), modified);
+                lines.push(Line::from(vec![
+                    Span::styled(label, Style::default().fg(Color::Gray)),
+                    Span::styled(if swatch.is_some() { "██ " } else { "" }, Style::default().fg(swatch.unwrap_or_default())),
+                    value,
+                    suffix,
+                ]));
+            }
+
+            // Later blocks niri ignores, until they are merged with M
+            for loc in &output.duplicate_sources {
+                lines.push(Line::from(vec![
+                    Span::styled("Also at: ", Style::default().fg(Color::Gray)),
+                    Span::styled(format!("{loc} (ignored, M: merge)"), Style::default().fg(Color::Yellow)),
+                ]));
+            }
+
+            if output.has_fractional_logical_size() {
+                if let Some((w, h)) = output.exact_logical_size() {
+                    lines.insert(
+                        8,
+                        Line::from(Span::styled(
+                            // The panel is narrow; the scale is shown just above
+                            format!("⚠ Logical {w:.2}x{h:.2}"),
+                            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                        )),
+                    );
+                }
+            }
+
+            if let Some(note) = self.label.map(|l| &l.note).filter(|note| !note.is_empty()) {
+                lines.push(Line::from(vec![
+                    Span::styled("Note: ", Style::default().fg(Color::Gray)),
+                    Span::styled(note, Style::default().fg(Color::Magenta)),
+                ]));
+            }
 
             let paragraph = Paragraph::new(lines);
             paragraph.render(inner, buf);
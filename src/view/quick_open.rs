@@ -0,0 +1,82 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, Widget},
+};
+
+use crate::i18n::tr;
+use crate::model::QuickOpen;
+
+/// Modal list of the live config and recently opened ones
+pub struct QuickOpenWidget<'a> {
+    quick_open: &'a QuickOpen,
+}
+
+impl<'a> QuickOpenWidget<'a> {
+    pub fn new(quick_open: &'a QuickOpen) -> Self {
+        Self { quick_open }
+    }
+}
+
+impl Widget for QuickOpenWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let dialog_width = 80.min(area.width.saturating_sub(4));
+        let dialog_height = (self.quick_open.entries.len() as u16 + 4).min(area.height.saturating_sub(2));
+        let dialog_x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
+        let dialog_y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
+
+        let dialog_area = Rect::new(dialog_x, dialog_y, dialog_width, dialog_height);
+        Clear.render(dialog_area, buf);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(format!(" {} ", tr("Open Config")));
+
+        let inner = block.inner(dialog_area);
+        block.render(dialog_area, buf);
+
+        if inner.height < 2 || inner.width < 20 {
+            return;
+        }
+
+        let hint_y = inner.y + inner.height - 1;
+        let width = inner.width.saturating_sub(2) as usize;
+        for (i, entry) in self.quick_open.entries.iter().enumerate() {
+            let y = inner.y + i as u16;
+            if y >= hint_y {
+                break;
+            }
+            let selected = i == self.quick_open.list.selected_index;
+            let style = if selected {
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+            } else if !entry.exists {
+                Style::default().fg(Color::DarkGray)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            let marker = if selected { "▶ " } else { "  " };
+            let mut tags = Vec::new();
+            if entry.live {
+                tags.push(tr("live"));
+            }
+            if entry.current {
+                tags.push(tr("open"));
+            }
+            if !entry.exists {
+                tags.push(tr("missing"));
+            }
+            let tags = if tags.is_empty() { String::new() } else { format!("  ({})", tags.join(", ")) };
+            let line: String = format!("{marker}{}{tags}", entry.path.display()).chars().take(width).collect();
+            buf.set_string(inner.x + 1, y, &line, style);
+        }
+
+        buf.set_string(
+            inner.x + 1,
+            hint_y,
+            "↑↓: Select  Enter: Open  Esc: Cancel",
+            Style::default().fg(Color::DarkGray),
+        );
+    }
+}
@@ -0,0 +1,91 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Widget},
+};
+
+use crate::category::Category;
+use crate::i18n::tr;
+
+/// Modal prompt shown when the config changed on disk since it was loaded
+pub struct SaveConflictWidget<'a> {
+    category: Category,
+    path: &'a str,
+    staged: usize,
+}
+
+impl<'a> SaveConflictWidget<'a> {
+    pub fn new(category: Category, path: &'a str, staged: usize) -> Self {
+        Self {
+            category,
+            path,
+            staged,
+        }
+    }
+}
+
+impl Widget for SaveConflictWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let dialog_width = 64.min(area.width.saturating_sub(4));
+        let dialog_height = 10.min(area.height.saturating_sub(2));
+        let dialog_x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
+        let dialog_y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
+
+        let dialog_area = Rect::new(dialog_x, dialog_y, dialog_width, dialog_height);
+        Clear.render(dialog_area, buf);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Red))
+            .title(format!(" {} ", tr("Config Changed On Disk")));
+
+        let inner = block.inner(dialog_area);
+        block.render(dialog_area, buf);
+
+        if inner.height < 7 || inner.width < 30 {
+            return;
+        }
+
+        let text_style = Style::default().fg(Color::White);
+        let key_style = Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD);
+        let width = inner.width.saturating_sub(2);
+
+        let mut y = inner.y;
+        buf.set_stringn(inner.x + 1, y, self.path, width as usize, Style::default().fg(Color::Cyan));
+        y += 1;
+        buf.set_stringn(
+            inner.x + 1,
+            y,
+            "was modified by another program since nirikiri loaded it.",
+            width as usize,
+            text_style,
+        );
+        y += 1;
+        buf.set_stringn(
+            inner.x + 1,
+            y,
+            format!("{} has {} staged change(s).", self.category.name(), self.staged),
+            width as usize,
+            text_style,
+        );
+        y += 2;
+
+        let options = [
+            ("o", "Overwrite the file with nirikiri's version"),
+            ("r", "Reload it and re-apply the staged changes"),
+            ("Esc", "Cancel"),
+        ];
+        for (key, label) in options {
+            let line = Line::from(vec![
+                Span::styled(format!("[{key}] "), key_style),
+                Span::styled(label, text_style),
+            ]);
+            buf.set_line(inner.x + 1, y, &line, width);
+            y += 1;
+        }
+    }
+}
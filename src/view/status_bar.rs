@@ -6,10 +6,25 @@ use ratatui::{
     widgets::Widget,
 };
 
+use crate::i18n::tr;
+
 pub struct StatusBarWidget<'a> {
     pub has_changes: bool,
     pub error: Option<String>,
     pub keybinds: &'a [(&'static str, &'static str)],
+    /// Transient status text shown after the keybinds (e.g., load progress)
+    pub notice: Option<String>,
+    /// niri socket in use, shown right-aligned on the second line
+    pub socket: Option<String>,
+    /// Key hint to make stand out, e.g. the one the guided tour asks for
+    pub highlight: Option<&'static str>,
+    /// Staged changes are saved on leaving the category
+    pub auto_save: bool,
+    /// Output moves are previewed on the live session as they happen
+    pub live_preview: bool,
+    /// Problem with the staged changes, shown on the second line unless
+    /// there is an error
+    pub warning: Option<String>,
 }
 
 impl<'a> StatusBarWidget<'a> {
@@ -22,8 +37,44 @@ impl<'a> StatusBarWidget<'a> {
             has_changes,
             error,
             keybinds,
+            notice: None,
+            socket: None,
+            highlight: None,
+            auto_save: false,
+            live_preview: false,
+            warning: None,
         }
     }
+
+    pub fn with_notice(mut self, notice: String) -> Self {
+        self.notice = Some(notice);
+        self
+    }
+
+    pub fn with_socket(mut self, socket: String) -> Self {
+        self.socket = Some(socket);
+        self
+    }
+
+    pub fn highlight(mut self, key: &'static str) -> Self {
+        self.highlight = Some(key);
+        self
+    }
+
+    pub fn auto_save(mut self) -> Self {
+        self.auto_save = true;
+        self
+    }
+
+    pub fn live_preview(mut self) -> Self {
+        self.live_preview = true;
+        self
+    }
+
+    pub fn with_warning(mut self, warning: String) -> Self {
+        self.warning = Some(warning);
+        self
+    }
 }
 
 impl Widget for StatusBarWidget<'_> {
@@ -33,29 +84,59 @@ impl Widget for StatusBarWidget<'_> {
             if i > 0 {
                 spans.push(Span::raw(" "));
             }
-            spans.push(Span::styled(
-                format!("[{key}]"),
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
-            ));
-            spans.push(Span::styled(*action, Style::default().fg(Color::Gray)));
+            let (key_style, action_style) = if self.highlight == Some(*key) {
+                let style = Style::default().fg(Color::Black).bg(Color::Yellow).add_modifier(Modifier::BOLD);
+                (style, style)
+            } else {
+                (
+                    Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                    Style::default().fg(Color::Gray),
+                )
+            };
+            spans.push(Span::styled(format!("[{key}]"), key_style));
+            spans.push(Span::styled(tr(action), action_style));
         }
 
         // Add status indicators
         if self.has_changes {
             spans.push(Span::raw("  "));
             spans.push(Span::styled(
-                "[Modified]",
+                format!("[{}]", tr("Modified")),
                 Style::default().fg(Color::Cyan),
             ));
         }
+        if self.auto_save {
+            spans.push(Span::raw("  "));
+            spans.push(Span::styled(
+                format!("[{}]", tr("Auto-save")),
+                Style::default().fg(Color::Green),
+            ));
+        }
+        if self.live_preview {
+            spans.push(Span::raw("  "));
+            spans.push(Span::styled(
+                format!("[{}]", tr("Live")),
+                Style::default().fg(Color::Green),
+            ));
+        }
+
+        if let Some(notice) = self.notice {
+            spans.push(Span::raw("  "));
+            spans.push(Span::styled(notice, Style::default().fg(Color::Magenta)));
+        }
 
         let help_line = Line::from(spans);
         let y = area.y;
 
         buf.set_line(area.x + 1, y, &help_line, area.width.saturating_sub(2));
 
+        if let Some(socket) = self.socket.as_ref().filter(|_| area.height > 1) {
+            let text = format!("niri: {socket}");
+            let width = text.chars().count() as u16;
+            let x = (area.x + area.width).saturating_sub(width + 1).max(area.x);
+            buf.set_string(x, y + 1, &text, Style::default().fg(Color::DarkGray));
+        }
+
         // Show error if present
         if let Some(error) = &self.error {
             let error_line = Line::from(vec![
@@ -64,10 +145,17 @@ impl Widget for StatusBarWidget<'_> {
                     Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
                 ),
                 Span::styled(error.as_str(), Style::default().fg(Color::Red)),
+                Span::styled("  [!] details", Style::default().fg(Color::DarkGray)),
             ]);
             if area.height > 1 {
                 buf.set_line(area.x + 1, y + 1, &error_line, area.width.saturating_sub(2));
             }
+        } else if let Some(warning) = self.warning.as_ref().filter(|_| area.height > 1) {
+            let warning_line = Line::from(Span::styled(
+                format!("⚠ {warning}"),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ));
+            buf.set_line(area.x + 1, y + 1, &warning_line, area.width.saturating_sub(2));
         }
     }
 }
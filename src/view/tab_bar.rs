@@ -6,15 +6,23 @@ use ratatui::{
 };
 
 use crate::category::Category;
+use crate::i18n::tr;
 
 /// Tab bar showing available settings categories with function key shortcuts
 pub struct TabBarWidget {
     current: Category,
+    /// Tab to draw attention to, e.g. where the guided tour continues
+    highlight: Option<Category>,
 }
 
 impl TabBarWidget {
     pub fn new(current: Category) -> Self {
-        Self { current }
+        Self { current, highlight: None }
+    }
+
+    pub fn highlight(mut self, category: Category) -> Self {
+        self.highlight = Some(category);
+        self
     }
 }
 
@@ -29,11 +37,11 @@ impl Widget for TabBarWidget {
         for category in Category::all() {
             let is_selected = *category == self.current;
             let fkey = category.function_key();
-            let name = category.name();
+            let name = tr(category.name());
 
             // Format: [F1] Outputs
             let tab_text = format!("[F{fkey}] {name}");
-            let tab_width = tab_text.len() as u16;
+            let tab_width = tab_text.chars().count() as u16;
 
             if x + tab_width > area.x + area.width - 1 {
                 break;
@@ -44,6 +52,11 @@ impl Widget for TabBarWidget {
                     .fg(Color::Black)
                     .bg(Color::Cyan)
                     .add_modifier(Modifier::BOLD)
+            } else if self.highlight == Some(*category) {
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
             } else {
                 Style::default().fg(Color::Gray)
             };
@@ -62,5 +75,12 @@ impl Widget for TabBarWidget {
         for x_pos in x..area.x + area.width {
             buf.set_string(x_pos, area.y, "─", border_style);
         }
+
+        // Point new users at the guided tour
+        let tour = format!(" [F12] {} ", tr("Tour"));
+        let tour_width = tour.chars().count() as u16;
+        if x + tour_width + 2 < area.x + area.width {
+            buf.set_string(area.x + area.width - tour_width - 1, area.y, &tour, Style::default().fg(Color::DarkGray));
+        }
     }
 }
@@ -0,0 +1,91 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, Widget},
+};
+
+use crate::i18n::tr;
+use crate::model::TemplateGallery;
+
+/// Modal dialog for choosing a commented template to add to the config
+pub struct TemplateGalleryWidget<'a> {
+    gallery: &'a TemplateGallery,
+}
+
+impl<'a> TemplateGalleryWidget<'a> {
+    pub fn new(gallery: &'a TemplateGallery) -> Self {
+        Self { gallery }
+    }
+}
+
+impl Widget for TemplateGalleryWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let dialog_width = 90.min(area.width.saturating_sub(4));
+        let dialog_height = 18.min(area.height.saturating_sub(2));
+        let dialog_x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
+        let dialog_y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
+
+        let dialog_area = Rect::new(dialog_x, dialog_y, dialog_width, dialog_height);
+        Clear.render(dialog_area, buf);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(format!(" {} ", tr("Add Section From Template")));
+
+        let inner = block.inner(dialog_area);
+        block.render(dialog_area, buf);
+
+        if inner.height < 5 || inner.width < 50 {
+            return;
+        }
+
+        let hint_style = Style::default().fg(Color::DarkGray);
+        let hint_y = inner.y + inner.height - 1;
+
+        // Left column: template titles
+        let list_width: u16 = 24;
+        for (i, template) in self.gallery.templates.iter().enumerate() {
+            let y = inner.y + i as u16;
+            if y >= hint_y {
+                break;
+            }
+            let style = if i == self.gallery.list.selected_index {
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            let marker = if i == self.gallery.list.selected_index { "▶ " } else { "  " };
+            buf.set_string(inner.x + 1, y, format!("{marker}{}", template.title), style);
+        }
+
+        // Right column: the KDL that will be inserted
+        if let Some(template) = self.gallery.selected() {
+            let preview_x = inner.x + list_width + 2;
+            let preview_width = inner.width.saturating_sub(list_width + 3) as usize;
+            for (i, line) in template.kdl.lines().enumerate() {
+                let y = inner.y + i as u16;
+                if y >= hint_y {
+                    break;
+                }
+                let style = if line.trim_start().starts_with("//") {
+                    Style::default().fg(Color::DarkGray)
+                } else {
+                    Style::default().fg(Color::Green)
+                };
+                let line: String = line.chars().take(preview_width).collect();
+                buf.set_string(preview_x, y, &line, style);
+            }
+        }
+
+        buf.set_string(
+            inner.x + 1,
+            hint_y,
+            "↑↓: Select  Enter: Insert and open in editor  Esc: Cancel",
+            hint_style,
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use ratatui::{
+    buffer::Buffer,
+    style::{Color, Modifier},
+    widgets::Padding,
+};
+
+/// How colors are used, applied to the finished frame so widgets can keep
+/// styling with colors
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorMode {
+    #[default]
+    Normal,
+    /// Accessible mode: dim grays are brightened
+    HighContrast,
+    /// NO_COLOR: colors are dropped, backgrounds become reverse video or
+    /// underlines so selections and cursors stay visible
+    Monochrome,
+}
+
+impl ColorMode {
+    /// Pick the mode from accessible mode and the NO_COLOR variable, which
+    /// counts when set to anything but an empty string (no-color.org)
+    pub fn detect(accessible: bool, no_color: Option<&str>) -> Self {
+        match (accessible, no_color) {
+            (_, Some(value)) if !value.is_empty() => ColorMode::Monochrome,
+            (true, _) => ColorMode::HighContrast,
+            _ => ColorMode::Normal,
+        }
+    }
+
+    pub fn apply(self, buf: &mut Buffer) {
+        match self {
+            ColorMode::Normal => {}
+            ColorMode::HighContrast => {
+                for cell in buf.content.iter_mut() {
+                    match cell.fg {
+                        Color::DarkGray => cell.fg = Color::Gray,
+                        Color::Gray => cell.fg = Color::White,
+                        _ => {}
+                    }
+                }
+            }
+            ColorMode::Monochrome => {
+                for cell in buf.content.iter_mut() {
+                    match cell.bg {
+                        Color::Reset | Color::Black => {}
+                        // Focused input fields; the cursor inside them is reversed
+                        Color::DarkGray => cell.modifier.insert(Modifier::UNDERLINED),
+                        _ => cell.modifier.insert(Modifier::REVERSED),
+                    }
+                    cell.fg = Color::Reset;
+                    cell.bg = Color::Reset;
+                }
+            }
+        }
+    }
+}
+
+/// How tightly lists are laid out
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Density {
+    #[default]
+    Compact,
+    /// Padded lists, and keybindings on two lines with the full command
+    Spacious,
+}
+
+impl Density {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "compact" => Some(Density::Compact),
+            "spacious" => Some(Density::Spacious),
+            _ => None,
+        }
+    }
+
+    /// Terminal lines each keybinding takes in the list
+    pub fn binding_rows(self) -> usize {
+        match self {
+            Density::Compact => 1,
+            Density::Spacious => 2,
+        }
+    }
+
+    /// Space between a list's border and its rows
+    pub fn padding(self) -> Padding {
+        match self {
+            Density::Compact => Padding::ZERO,
+            Density::Spacious => Padding::horizontal(1),
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Widget},
+};
+
+use super::appearance_detail::wrap_text;
+use crate::category::Category;
+use crate::i18n::{tr, tr_args};
+use crate::model::tutorial::STEPS;
+use crate::model::Tutorial;
+
+/// Guided tour hint, drawn in the bottom-right corner of the body so the
+/// part of the UI it talks about stays visible
+pub struct TutorialWidget<'a> {
+    tutorial: &'a Tutorial,
+    current: Category,
+}
+
+impl<'a> TutorialWidget<'a> {
+    pub fn new(tutorial: &'a Tutorial, current: Category) -> Self {
+        Self { tutorial, current }
+    }
+}
+
+impl Widget for TutorialWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let Some(step) = self.tutorial.current() else {
+            return;
+        };
+        let width = 52.min(area.width.saturating_sub(2));
+        if width < 24 || area.height < 6 {
+            return;
+        }
+
+        let hint = match self.tutorial.pending_switch(self.current) {
+            Some(category) => tr_args(
+                "Press F{key} to go to {category}",
+                &[("key", &category.function_key()), ("category", &tr(category.name()))],
+            ),
+            None => tr(step.hint).to_string(),
+        };
+        let lines = wrap_text(&hint, width.saturating_sub(4) as usize);
+        let height = (lines.len() as u16 + 4).min(area.height);
+
+        // Sit just above the bottom border of the body
+        let dialog_area = Rect::new(
+            area.x + area.width - width - 1,
+            area.y + area.height - height - 1,
+            width,
+            height,
+        );
+        Clear.render(dialog_area, buf);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(format!(
+                " {} ",
+                tr_args("Tour {step}/{total}", &[("step", &(self.tutorial.step + 1)), ("total", &STEPS.len())])
+            ));
+        let inner = block.inner(dialog_area);
+        block.render(dialog_area, buf);
+
+        let text_style = Style::default().fg(Color::White).add_modifier(Modifier::BOLD);
+        for (y, line) in (inner.y..).zip(&lines) {
+            buf.set_stringn(inner.x + 1, y, line, inner.width.saturating_sub(2) as usize, text_style);
+        }
+
+        let key_style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+        let label_style = Style::default().fg(Color::DarkGray);
+        let footer = Line::from(vec![
+            Span::styled("[F11] ", key_style),
+            Span::styled(format!("{}  ", tr("Skip step")), label_style),
+            Span::styled("[F12] ", key_style),
+            Span::styled(tr("End tour"), label_style),
+        ]);
+        buf.set_line(inner.x + 1, inner.y + inner.height - 1, &footer, inner.width.saturating_sub(2));
+    }
+}
@@ -0,0 +1,113 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, Widget},
+};
+
+use crate::i18n::tr;
+use crate::model::workspace_spawn::STARTUP_TOGGLE;
+use crate::model::WorkspaceSpawnWizard;
+
+/// Modal dialog for opening an app on a named workspace, with a preview of
+/// the nodes it will add
+pub struct WorkspaceSpawnWidget<'a> {
+    wizard: &'a WorkspaceSpawnWizard,
+    /// The KDL to be written, or why there is none yet
+    preview: Result<String, String>,
+}
+
+impl<'a> WorkspaceSpawnWidget<'a> {
+    pub fn new(wizard: &'a WorkspaceSpawnWizard, preview: Result<String, String>) -> Self {
+        Self { wizard, preview }
+    }
+}
+
+impl Widget for WorkspaceSpawnWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let preview_lines: Vec<&str> = match &self.preview {
+            Ok(kdl) => kdl.lines().collect(),
+            Err(e) => vec![e.as_str()],
+        };
+
+        let dialog_width = 80.min(area.width.saturating_sub(4));
+        let dialog_height = (self.wizard.fields.len() as u16 + preview_lines.len() as u16 + 5)
+            .min(area.height.saturating_sub(2));
+        let dialog_x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
+        let dialog_y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
+
+        let dialog_area = Rect::new(dialog_x, dialog_y, dialog_width, dialog_height);
+        Clear.render(dialog_area, buf);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(format!(" {} ", tr("Open App on Workspace")));
+
+        let inner = block.inner(dialog_area);
+        block.render(dialog_area, buf);
+
+        if inner.height < 4 || inner.width < 20 {
+            return;
+        }
+
+        let focused = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+        let normal = Style::default().fg(Color::White);
+        let label_style = Style::default().fg(Color::Gray);
+        let placeholder = Style::default().fg(Color::DarkGray);
+        let hint_y = inner.y + inner.height - 1;
+        let width = inner.width.saturating_sub(13) as usize;
+
+        let mut y = inner.y;
+        for (i, field) in self.wizard.fields.iter().enumerate() {
+            if y >= hint_y {
+                return;
+            }
+            let is_focused = self.wizard.focused == i;
+            buf.set_string(inner.x + 1, y, format!("{:<10}", tr(&field.name)), label_style);
+            let (value, style) = if is_focused {
+                (format!("{}│{}", &field.value[..field.cursor], &field.value[field.cursor..]), focused)
+            } else if field.value.is_empty() {
+                // Show what an empty field falls back to
+                let fallback = match field.name.as_str() {
+                    "App ID" => self.wizard.derived_app_id().unwrap_or_default(),
+                    "Output" => tr("any").to_string(),
+                    _ => String::new(),
+                };
+                (fallback, placeholder)
+            } else {
+                (field.value.clone(), normal)
+            };
+            let value: String = value.chars().take(width).collect();
+            buf.set_string(inner.x + 12, y, &value, style);
+            y += 1;
+        }
+
+        if y < hint_y {
+            let check = if self.wizard.at_startup { "[x]" } else { "[ ]" };
+            let style = if self.wizard.focused == STARTUP_TOGGLE { focused } else { normal };
+            buf.set_string(inner.x + 1, y, format!("{check} {}", tr("Start at login")), style);
+            y += 2;
+        }
+
+        let preview_style = match self.preview {
+            Ok(_) => Style::default().fg(Color::Green),
+            Err(_) => Style::default().fg(Color::Red),
+        };
+        for line in preview_lines {
+            if y >= hint_y {
+                break;
+            }
+            let line: String = line.chars().take(inner.width.saturating_sub(2) as usize).collect();
+            buf.set_string(inner.x + 1, y, &line, preview_style);
+            y += 1;
+        }
+
+        buf.set_string(
+            inner.x + 1,
+            hint_y,
+            "Tab: Next field  Space: Toggle  Enter: Stage  Esc: Cancel",
+            Style::default().fg(Color::DarkGray),
+        );
+    }
+}
@@ -1,3 +1,4 @@
 pub mod monitor_canvas;
+pub mod table;
 
 pub use monitor_canvas::{CanvasViewport, MonitorCanvasWidget};
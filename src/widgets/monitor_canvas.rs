@@ -2,34 +2,111 @@ use ratatui::{
     buffer::Buffer,
     layout::Rect,
     style::{Color, Style},
-    widgets::{Block, Borders, Widget},
+    widgets::{Block, Borders, Clear, Widget},
 };
 
-use crate::model::{OutputViewModel, Position, Size};
+use crate::i18n::tr;
+use crate::model::{AppearanceSettings, OutputState, OutputViewModel, Position, Size};
+use crate::output_labels::OutputLabels;
+
+/// Most columns the column preview splits a monitor into
+const MAX_PREVIEW_COLUMNS: u32 = 6;
+
+/// Cells one pan step scrolls the canvas by (columns, rows)
+const PAN_STEP: (i32, i32) = (8, 4);
+
+/// Narrowest a monitor is drawn when fitting the layout, in cells. Layouts
+/// spread wider than this allows are panned instead.
+const MIN_MONITOR_CELLS: f64 = 8.0;
+
+/// A monitor being dragged with the mouse
+#[derive(Debug, Clone, PartialEq)]
+pub struct CanvasDrag {
+    pub output: String,
+    /// Logical offset of the grabbed point from the monitor's top-left
+    pub grab: (i64, i64),
+    /// Region the view was fitted to when the drag started; kept until the
+    /// drop so the canvas doesn't rescale under the cursor
+    pub fit: (i64, i64, i64, i64),
+}
 
-/// Viewport state for the canvas (zoom only, auto-fits to show all monitors)
+/// Viewport state for the canvas. It auto-fits to show all monitors or, when
+/// `focus_selected` is set, the selected monitor and its surroundings; zoom
+/// and pan are relative to that fit.
 #[derive(Debug, Clone)]
 pub struct CanvasViewport {
     pub scale: f64,
+    /// Cells the view is scrolled right and down from the fitted position
+    pub pan: (i32, i32),
+    pub focus_selected: bool,
+    /// Number of columns the preview inset splits the selected monitor
+    /// into, None while the inset is hidden
+    pub preview_columns: Option<u32>,
+    pub drag: Option<CanvasDrag>,
 }
 
 impl Default for CanvasViewport {
     fn default() -> Self {
-        Self { scale: 1.0 }
+        Self {
+            scale: 1.0,
+            pan: (0, 0),
+            focus_selected: false,
+            preview_columns: None,
+            drag: None,
+        }
     }
 }
 
 impl CanvasViewport {
     pub fn zoom_in(&mut self) {
-        self.scale = (self.scale * 1.2).min(4.0);
+        self.set_scale((self.scale * 1.2).min(4.0));
     }
 
     pub fn zoom_out(&mut self) {
-        self.scale = (self.scale / 1.2).max(0.25);
+        self.set_scale((self.scale / 1.2).max(0.25));
+    }
+
+    /// Zoom, keeping the same logical point at the top-left of a panned view
+    fn set_scale(&mut self, scale: f64) {
+        let factor = scale / self.scale;
+        self.pan = (
+            (self.pan.0 as f64 * factor).round() as i32,
+            (self.pan.1 as f64 * factor).round() as i32,
+        );
+        self.scale = scale;
+    }
+
+    /// Scroll the view by `dx`/`dy` pan steps
+    pub fn pan(&mut self, dx: i32, dy: i32) {
+        self.pan.0 = self.pan.0.saturating_add(dx * PAN_STEP.0);
+        self.pan.1 = self.pan.1.saturating_add(dy * PAN_STEP.1);
     }
 
     pub fn reset(&mut self) {
         self.scale = 1.0;
+        self.pan = (0, 0);
+        self.focus_selected = false;
+    }
+
+    /// Toggle fitting the view around the selected monitor
+    pub fn toggle_focus_selected(&mut self) {
+        self.focus_selected = !self.focus_selected;
+        self.scale = 1.0;
+        self.pan = (0, 0);
+    }
+
+    /// Show or hide the column preview inset
+    pub fn toggle_column_preview(&mut self) {
+        self.preview_columns = match self.preview_columns {
+            Some(_) => None,
+            None => Some(3),
+        };
+    }
+
+    /// Split the preview into more or fewer columns, showing it if hidden
+    pub fn change_preview_columns(&mut self, delta: i32) {
+        let columns = self.preview_columns.unwrap_or(3) as i32 + delta;
+        self.preview_columns = Some(columns.clamp(1, MAX_PREVIEW_COLUMNS as i32) as u32);
     }
 }
 
@@ -37,6 +114,12 @@ pub struct MonitorCanvasWidget<'a> {
     pub view_model: &'a OutputViewModel,
     pub viewport: &'a CanvasViewport,
     pub focused: bool,
+    /// Gaps and struts for the column preview
+    pub appearance: Option<&'a AppearanceSettings>,
+    /// Friendly names shown under the connector names
+    pub labels: Option<&'a OutputLabels>,
+    /// Output being identified on its physical monitor
+    pub identifying: Option<&'a str>,
 }
 
 impl<'a> MonitorCanvasWidget<'a> {
@@ -45,54 +128,192 @@ impl<'a> MonitorCanvasWidget<'a> {
             view_model,
             viewport,
             focused,
+            appearance: None,
+            labels: None,
+            identifying: None,
         }
     }
 
-    /// Get the bounding box of all monitors (min_x, min_y, max_x, max_y)
-    fn get_bounds(&self) -> (i32, i32, i32, i32) {
-        let mut min_x = i32::MAX;
-        let mut min_y = i32::MAX;
-        let mut max_x = i32::MIN;
-        let mut max_y = i32::MIN;
+    pub fn identifying(mut self, name: Option<&'a str>) -> Self {
+        self.identifying = name;
+        self
+    }
+
+    pub fn labels(mut self, labels: &'a OutputLabels) -> Self {
+        self.labels = Some(labels);
+        self
+    }
+
+    pub fn appearance(mut self, appearance: &'a AppearanceSettings) -> Self {
+        self.appearance = Some(appearance);
+        self
+    }
+
+    /// Size a monitor is drawn at: the one its staged mode, scale and
+    /// transform give it. None when it is off or staged to be turned off,
+    /// so it is neither drawn nor grabbable.
+    fn staged_size(&self, output: &OutputState) -> Option<Size> {
+        self.view_model.staged_output(&output.name).filter(|o| o.enabled).map(|o| o.logical_size)
+    }
+
+    /// Get the bounding box of all monitors (min_x, min_y, max_x, max_y).
+    /// Widened to i64 so monitors near the ends of the i32 range don't overflow.
+    fn get_bounds(&self) -> (i64, i64, i64, i64) {
+        let mut min_x = i64::MAX;
+        let mut min_y = i64::MAX;
+        let mut max_x = i64::MIN;
+        let mut max_y = i64::MIN;
 
         for output in &self.view_model.outputs {
-            if !output.enabled {
+            let Some(size) = self.staged_size(output) else {
                 continue;
-            }
+            };
             let pos = self.view_model.get_display_position(&output.name).unwrap_or(output.position);
-            min_x = min_x.min(pos.x);
-            min_y = min_y.min(pos.y);
-            max_x = max_x.max(pos.x + output.logical_size.width as i32);
-            max_y = max_y.max(pos.y + output.logical_size.height as i32);
+            min_x = min_x.min(pos.x as i64);
+            min_y = min_y.min(pos.y as i64);
+            max_x = max_x.max(pos.x as i64 + size.width as i64);
+            max_y = max_y.max(pos.y as i64 + size.height as i64);
         }
 
         (min_x, min_y, max_x, max_y)
     }
 
-    /// Convert logical coordinates to screen coordinates
-    /// Aligns top-left of bounding box to top-left of canvas
-    fn to_screen(&self, pos: Position, canvas_area: Rect) -> (i32, i32) {
-        let (min_x, min_y, _, _) = self.get_bounds();
-        let scale = self.calculate_auto_scale(canvas_area) * self.viewport.scale;
+    /// Get the region the view is fitted to: all monitors, or the selected
+    /// monitor with a quarter of its size as margin on each side
+    fn view_bounds(&self) -> (i64, i64, i64, i64) {
+        if let Some(drag) = &self.viewport.drag {
+            return drag.fit;
+        }
+        if self.viewport.focus_selected {
+            let selected = self.view_model.selected_output();
+            if let Some((output, size)) = selected.and_then(|o| self.staged_size(o).map(|size| (o, size))) {
+                let pos = self.view_model.get_display_position(&output.name).unwrap_or(output.position);
+                let (x, y) = (pos.x as i64, pos.y as i64);
+                let (w, h) = (size.width as i64, size.height as i64);
+                return (x - w / 4, y - h / 4, x + w + w / 4, y + h + h / 4);
+            }
+        }
+        self.get_bounds()
+    }
+
+    /// Canvas columns per logical pixel; a row covers twice as many pixels
+    fn scale(&self, canvas_area: Rect) -> f64 {
+        self.calculate_auto_scale(canvas_area) * self.viewport.scale
+    }
+
+    /// Logical point drawn at the canvas' top-left padding, after panning.
+    /// Zoomed to the selected monitor, the view is centered on it, so it
+    /// stays in the middle however far zooming in or out takes the fit.
+    fn origin(&self, canvas_area: Rect) -> (f64, f64) {
+        let (min_x, min_y, max_x, max_y) = self.view_bounds();
+        let scale = self.scale(canvas_area);
+        let (pan_x, pan_y) = self.viewport.pan;
+        let (x, y) = if self.viewport.focus_selected {
+            let width = canvas_area.width.saturating_sub(2) as f64 / scale;
+            let height = canvas_area.height.saturating_sub(2) as f64 * 2.0 / scale;
+            ((min_x + max_x) as f64 / 2.0 - width / 2.0, (min_y + max_y) as f64 / 2.0 - height / 2.0)
+        } else {
+            (min_x as f64, min_y as f64)
+        };
+        (x + pan_x as f64 / scale, y + pan_y as f64 * 2.0 / scale)
+    }
+
+    /// Logical region currently visible in the canvas (min_x, min_y, max_x, max_y)
+    fn visible_region(&self, canvas_area: Rect) -> (i64, i64, i64, i64) {
+        let (x, y) = self.origin(canvas_area);
+        let scale = self.scale(canvas_area);
+        let width = canvas_area.width.saturating_sub(1) as f64 / scale;
+        let height = canvas_area.height.saturating_sub(1) as f64 * 2.0 / scale;
+        (x as i64, y as i64, (x + width) as i64, (y + height) as i64)
+    }
 
-        // Offset position by bounds minimum, then scale
-        let rel_x = pos.x - min_x;
-        let rel_y = pos.y - min_y;
+    /// Convert logical coordinates to cells relative to the canvas' top-left.
+    /// The result can lie far outside the canvas; callers clip.
+    fn to_screen(&self, x: i64, y: i64, canvas_area: Rect) -> (i64, i64) {
+        let (origin_x, origin_y) = self.origin(canvas_area);
+        let scale = self.scale(canvas_area);
 
-        // Small padding from edge
+        // Small padding from edge; floor so cells left of the origin don't
+        // round towards it
         let padding = 1;
-        let x = padding + (rel_x as f64 * scale) as i32;
-        let y = padding + (rel_y as f64 * scale / 2.0) as i32; // /2 for aspect ratio
+        let x = padding + ((x as f64 - origin_x) * scale).floor() as i64;
+        let y = padding + ((y as f64 - origin_y) * scale / 2.0).floor() as i64; // /2 for aspect ratio
 
         (x, y)
     }
 
+    /// Logical point under a terminal cell of the canvas' inner area
+    fn to_logical(&self, column: u16, row: u16, canvas_area: Rect) -> (i64, i64) {
+        let (origin_x, origin_y) = self.origin(canvas_area);
+        let scale = self.scale(canvas_area);
+        let padding = 1;
+        let dx = column as i64 - canvas_area.x as i64 - padding;
+        let dy = row as i64 - canvas_area.y as i64 - padding;
+        (
+            (origin_x + dx as f64 / scale).floor() as i64,
+            (origin_y + dy as f64 * 2.0 / scale).floor() as i64,
+        )
+    }
+
+    /// The canvas inside the border, for a widget rendered into `area`
+    fn inner_area(area: Rect) -> Rect {
+        Block::default().borders(Borders::ALL).inner(area)
+    }
+
+    /// Start dragging the monitor under a terminal cell, for a widget
+    /// rendered into `area`. Monitors hidden by the search can't be grabbed.
+    /// Returns the monitor's index and the drag to store in the viewport.
+    pub fn grab(&self, area: Rect, column: u16, row: u16) -> Option<(usize, CanvasDrag)> {
+        let canvas_area = Self::inner_area(area);
+        if !canvas_area.contains(ratatui::layout::Position::new(column, row)) {
+            return None;
+        }
+        let (cx, cy) = (
+            column as i64 - canvas_area.x as i64,
+            row as i64 - canvas_area.y as i64,
+        );
+        // Later monitors are drawn on top, so they win
+        let (idx, output) = self.view_model.outputs.iter().enumerate().rev().find(|(_, output)| {
+            let Some(size) = self.staged_size(output).filter(|_| self.view_model.is_visible(output)) else {
+                return false;
+            };
+            let pos = self.view_model.get_display_position(&output.name).unwrap_or(output.position);
+            let (left, top, width, height) = self.monitor_cells(pos, size, canvas_area);
+            (left..left + width).contains(&cx) && (top..top + height).contains(&cy)
+        })?;
+        let pos = self.view_model.get_display_position(&output.name).unwrap_or(output.position);
+        let (x, y) = self.to_logical(column, row, canvas_area);
+        let drag = CanvasDrag {
+            output: output.name.clone(),
+            grab: (x - pos.x as i64, y - pos.y as i64),
+            fit: self.view_bounds(),
+        };
+        Some((idx, drag))
+    }
+
+    /// Where the dragged monitor lands with the cursor at a terminal cell
+    pub fn drag_position(&self, area: Rect, column: u16, row: u16) -> Option<Position> {
+        let drag = self.viewport.drag.as_ref()?;
+        let (x, y) = self.to_logical(column, row, Self::inner_area(area));
+        let clamp = |v: i64| v.clamp(i32::MIN as i64, i32::MAX as i64) as i32;
+        Some(Position::new(clamp(x - drag.grab.0), clamp(y - drag.grab.1)))
+    }
+
+    /// A monitor's cell rectangle relative to the canvas (left, top, width, height)
+    fn monitor_cells(&self, pos: Position, size: Size, canvas_area: Rect) -> (i64, i64, i64, i64) {
+        let (left, top) = self.to_screen(pos.x as i64, pos.y as i64, canvas_area);
+        let scale = self.scale(canvas_area);
+        let width = ((size.width as f64 * scale) as i64).max(1);
+        let height = ((size.height as f64 * scale / 2.0) as i64).max(1); // /2 for char aspect ratio
+        (left, top, width, height)
+    }
+
     fn calculate_auto_scale(&self, area: Rect) -> f64 {
         if self.view_model.outputs.is_empty() {
             return 0.05;
         }
 
-        let (min_x, min_y, max_x, max_y) = self.get_bounds();
+        let (min_x, min_y, max_x, max_y) = self.view_bounds();
 
         let total_width = (max_x - min_x) as f64;
         let total_height = (max_y - min_y) as f64;
@@ -108,7 +329,242 @@ impl<'a> MonitorCanvasWidget<'a> {
         let scale_x = available_width / total_width;
         let scale_y = available_height / total_height;
 
-        scale_x.min(scale_y).min(0.1) // Cap at reasonable scale
+        // Monitors far apart would shrink to specks; keep the narrowest one
+        // legible and let the rest be panned to
+        let narrowest = self
+            .view_model
+            .outputs
+            .iter()
+            .filter_map(|o| self.staged_size(o))
+            .map(|size| size.width.max(1))
+            .min()
+            .unwrap_or(1);
+        let legible = MIN_MONITOR_CELLS.min(available_width) / narrowest as f64;
+
+        scale_x.min(scale_y).max(legible).min(0.1) // Cap at reasonable scale
+    }
+
+    /// Draw a small overview of all monitors in the bottom-right corner,
+    /// outlining the part of the layout that is currently visible
+    fn draw_minimap(&self, buf: &mut Buffer, canvas_area: Rect) {
+        let (min_x, min_y, max_x, max_y) = self.get_bounds();
+        if min_x == i64::MAX {
+            return;
+        }
+
+        // Only useful when part of the layout is off-screen
+        let (vx0, vy0, vx1, vy1) = self.visible_region(canvas_area);
+        if vx0 <= min_x && vy0 <= min_y && vx1 >= max_x && vy1 >= max_y {
+            return;
+        }
+
+        let map_width = (canvas_area.width / 4).clamp(14, 30);
+        let map_height = (canvas_area.height / 3).clamp(6, 10);
+        if canvas_area.width < map_width + 10 || canvas_area.height < map_height + 4 {
+            return;
+        }
+        let map_area = Rect::new(
+            canvas_area.x + canvas_area.width - map_width,
+            canvas_area.y + canvas_area.height - map_height,
+            map_width,
+            map_height,
+        );
+        Clear.render(map_area, buf);
+        let block = Block::default()
+            .title(format!(" {} ", tr("Map")))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray));
+        let map = block.inner(map_area);
+        block.render(map_area, buf);
+        if map.width < 2 || map.height < 2 {
+            return;
+        }
+
+        let total_width = (max_x - min_x).max(1) as f64;
+        let total_height = (max_y - min_y).max(1) as f64;
+        let scale = (map.width as f64 / total_width).min(map.height as f64 * 2.0 / total_height);
+
+        // Map logical coordinates to cells, clamped to the inset
+        let project = |x: i64, y: i64| -> (u16, u16) {
+            let cx = ((x - min_x) as f64 * scale).clamp(0.0, (map.width - 1) as f64) as u16;
+            let cy = ((y - min_y) as f64 * scale / 2.0).clamp(0.0, (map.height - 1) as f64) as u16;
+            (map.x + cx, map.y + cy)
+        };
+
+        for (idx, output) in self.view_model.outputs.iter().enumerate() {
+            let Some(size) = self.staged_size(output) else {
+                continue;
+            };
+            let pos = self.view_model.get_display_position(&output.name).unwrap_or(output.position);
+            let (x, y) = (pos.x as i64, pos.y as i64);
+            let (x0, y0) = project(x, y);
+            let (x1, y1) = project(x + size.width as i64 - 1, y + size.height as i64 - 1);
+            let color = if idx == self.view_model.list.selected_index {
+                Color::Yellow
+            } else if self.view_model.is_modified(&output.name) {
+                Color::Cyan
+            } else {
+                Color::Gray
+            };
+            for y in y0..=y1 {
+                for x in x0..=x1 {
+                    buf[(x, y)].set_char('█').set_fg(color);
+                }
+            }
+        }
+
+        // Outline the visible region
+        let (x0, y0) = project(vx0, vy0);
+        let (x1, y1) = project(vx1, vy1);
+        let outline = Style::default().fg(Color::White).bg(Color::Black);
+        for x in x0..=x1 {
+            buf[(x, y0)].set_char('─').set_style(outline);
+            buf[(x, y1)].set_char('─').set_style(outline);
+        }
+        for y in y0..=y1 {
+            buf[(x0, y)].set_char('│').set_style(outline);
+            buf[(x1, y)].set_char('│').set_style(outline);
+        }
+        buf[(x0, y0)].set_char('┌');
+        buf[(x1, y0)].set_char('┐');
+        buf[(x0, y1)].set_char('└');
+        buf[(x1, y1)].set_char('┘');
+    }
+
+    /// Draw the selected monitor's usable area split into equal columns in
+    /// the bottom-left corner, with gaps and struts to scale, so the effect
+    /// of a gap change on column width is visible at its resolution
+    fn draw_column_preview(&self, buf: &mut Buffer, canvas_area: Rect) {
+        let (Some(columns), Some(appearance)) = (self.viewport.preview_columns, self.appearance) else {
+            return;
+        };
+        let Some(output) = self.view_model.selected_output() else {
+            return;
+        };
+        let Some(size) = self.staged_size(output) else {
+            return;
+        };
+
+        let inset_width = (canvas_area.width / 2).clamp(24, 48);
+        let inset_height = (canvas_area.height / 3).clamp(7, 12);
+        if canvas_area.width < inset_width + 2 || canvas_area.height < inset_height + 2 {
+            return;
+        }
+        let inset_area = Rect::new(
+            canvas_area.x,
+            canvas_area.y + canvas_area.height - inset_height,
+            inset_width,
+            inset_height,
+        );
+        Clear.render(inset_area, buf);
+        let block = Block::default()
+            .title(format!(" {}: {} ", tr("Columns"), output.name))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray));
+        let inner = block.inner(inset_area);
+        block.render(inset_area, buf);
+        if inner.width < 4 || inner.height < 3 {
+            return;
+        }
+
+        // The last row holds the measurements
+        let screen = Rect::new(inner.x, inner.y, inner.width, inner.height - 1);
+        let output_width = size.width as i32;
+        let scale = screen.width as f64 / output_width.max(1) as f64;
+        let to_cell = |x: i32| screen.x + ((x as f64 * scale).round() as u16).min(screen.width);
+
+        let gaps = appearance.gaps;
+        let column_width = appearance.column_width(output_width, columns as i32);
+        let mut x = appearance.struts.left.unwrap_or(0) + gaps;
+        for i in 0..columns {
+            let (x0, x1) = (to_cell(x), to_cell(x + column_width));
+            // Alternate shades so columns stay apart when the gap is under a cell
+            let color = if i % 2 == 0 { Color::Blue } else { Color::Cyan };
+            for cy in screen.y..screen.y + screen.height {
+                for cx in x0..x1 {
+                    buf[(cx, cy)].set_char('█').set_fg(color);
+                }
+            }
+            x += column_width + gaps;
+        }
+
+        let summary = format!("{columns} × {column_width}px  gaps {gaps}  [ ]: columns");
+        buf.set_stringn(inner.x, inner.y + inner.height - 1, summary, inner.width as usize, Style::default().fg(Color::Gray));
+    }
+
+    /// List neighbouring monitors with very different pixel densities along
+    /// the top of the canvas
+    fn draw_dpi_warnings(&self, buf: &mut Buffer, canvas_area: Rect) {
+        let style = Style::default().fg(Color::Yellow).bg(Color::Black);
+        for (i, mismatch) in self.view_model.dpi_mismatches().iter().enumerate() {
+            if i as u16 >= canvas_area.height / 3 {
+                break;
+            }
+            buf.set_stringn(
+                canvas_area.x,
+                canvas_area.y + i as u16,
+                format!("⚠ {} (D)", mismatch.message()),
+                canvas_area.width as usize,
+                style,
+            );
+        }
+    }
+
+    /// Point from the canvas edge towards each monitor that is entirely
+    /// outside it, labelled with the monitor's name
+    fn draw_offscreen_indicators(&self, buf: &mut Buffer, canvas_area: Rect) {
+        let (width, height) = (canvas_area.width as i64, canvas_area.height as i64);
+        if width < 3 || height < 1 {
+            return;
+        }
+        for (idx, output) in self.view_model.outputs.iter().enumerate() {
+            let Some(size) = self.staged_size(output) else {
+                continue;
+            };
+            let pos = self.view_model.get_display_position(&output.name).unwrap_or(output.position);
+            let (left, top, w, h) = self.monitor_cells(pos, size, canvas_area);
+            let horizontal = if left + w <= 0 { -1 } else if left >= width { 1 } else { 0 };
+            let vertical = if top + h <= 0 { -1 } else if top >= height { 1 } else { 0 };
+            let arrow = match (horizontal, vertical) {
+                (0, 0) => continue,
+                (-1, -1) => '↖',
+                (0, -1) => '↑',
+                (1, -1) => '↗',
+                (-1, 0) => '←',
+                (1, 0) => '→',
+                (-1, 1) => '↙',
+                (0, 1) => '↓',
+                _ => '↘',
+            };
+
+            // Arrows on the right edge read "name →", the others "← name"
+            let label = if horizontal == 1 {
+                format!("{} {arrow}", output.name)
+            } else {
+                format!("{arrow} {}", output.name)
+            };
+            let len = (label.chars().count() as i64).min(width);
+            let x = match horizontal {
+                -1 => 0,
+                1 => width - len,
+                _ => (left + w / 2 - len / 2).clamp(0, width - len),
+            };
+            let y = (top + h / 2).clamp(0, height - 1);
+            let color = if idx == self.view_model.list.selected_index {
+                Color::Yellow
+            } else if self.view_model.is_modified(&output.name) {
+                Color::Cyan
+            } else {
+                Color::Gray
+            };
+            buf.set_stringn(
+                canvas_area.x + x as u16,
+                canvas_area.y + y as u16,
+                label,
+                len as usize,
+                Style::default().fg(color).bg(Color::Black),
+            );
+        }
     }
 
     /// Draw a monitor rectangle
@@ -122,15 +578,18 @@ impl<'a> MonitorCanvasWidget<'a> {
         size: Size,
         selected: bool,
         modified: bool,
+        dimmed: bool,
+        flagged: bool,
     ) {
-        let (screen_x, screen_y) = self.to_screen(pos, canvas_area);
-        let scale = self.calculate_auto_scale(canvas_area) * self.viewport.scale;
-
-        let width = ((size.width as f64 * scale) as u16).max(1);
-        let height = ((size.height as f64 * scale / 2.0) as u16).max(1); // /2 for char aspect ratio
+        let (screen_x, screen_y, width, height) = self.monitor_cells(pos, size, canvas_area);
 
         // Determine colors
-        let (border_color, fill_color, text_color) = if selected && self.focused {
+        // Outputs filtered out by the search fade into the background
+        let (border_color, fill_color, text_color) = if self.identifying == Some(name) {
+            (Color::Magenta, Color::Magenta, Color::Black)
+        } else if dimmed {
+            (Color::DarkGray, Color::Black, Color::DarkGray)
+        } else if selected && self.focused {
             (Color::Yellow, Color::DarkGray, Color::Yellow)
         } else if selected {
             (Color::White, Color::DarkGray, Color::White)
@@ -139,44 +598,43 @@ impl<'a> MonitorCanvasWidget<'a> {
         } else {
             (Color::Gray, Color::Black, Color::White)
         };
+        // Overlapping or cut off from the rest of the layout
+        let border_color = if flagged && !dimmed { Color::Red } else { border_color };
 
         // Calculate actual screen positions
-        let left = canvas_area.x as i32 + screen_x;
-        let top = canvas_area.y as i32 + screen_y;
-
-        // Draw the rectangle
-        for dy in 0..height {
-            for dx in 0..width {
-                let x = left + dx as i32;
-                let y = top + dy as i32;
-
-                // Check bounds
-                if x < canvas_area.x as i32
-                    || x >= (canvas_area.x + canvas_area.width) as i32
-                    || y < canvas_area.y as i32
-                    || y >= (canvas_area.y + canvas_area.height) as i32
-                {
-                    continue;
-                }
-
-                let x = x as u16;
-                let y = y as u16;
+        let left = canvas_area.x as i64 + screen_x;
+        let top = canvas_area.y as i64 + screen_y;
+        let (canvas_left, canvas_top) = (canvas_area.x as i64, canvas_area.y as i64);
+        let (canvas_right, canvas_bottom) = (canvas_left + canvas_area.width as i64, canvas_top + canvas_area.height as i64);
+
+        // Draw the part of the rectangle inside the canvas; zoomed in on a
+        // large layout, the rest can be millions of cells
+        for y in top.max(canvas_top)..(top + height).min(canvas_bottom) {
+            for x in left.max(canvas_left)..(left + width).min(canvas_right) {
+                let (dx, dy) = (x - left, y - top);
+                let (x, y) = (x as u16, y as u16);
 
                 let is_border = dy == 0 || dy == height - 1 || dx == 0 || dx == width - 1;
 
                 if is_border {
+                    // The selected monitor gets a double border so it stands out without color
+                    let [top_left, top_right, bottom_left, bottom_right, horizontal, vertical] = if selected {
+                        ['╔', '╗', '╚', '╝', '═', '║']
+                    } else {
+                        ['┌', '┐', '└', '┘', '─', '│']
+                    };
                     let ch = if dy == 0 && dx == 0 {
-                        '┌'
+                        top_left
                     } else if dy == 0 && dx == width - 1 {
-                        '┐'
+                        top_right
                     } else if dy == height - 1 && dx == 0 {
-                        '└'
+                        bottom_left
                     } else if dy == height - 1 && dx == width - 1 {
-                        '┘'
+                        bottom_right
                     } else if dy == 0 || dy == height - 1 {
-                        '─'
+                        horizontal
                     } else {
-                        '│'
+                        vertical
                     };
                     buf[(x, y)].set_char(ch).set_fg(border_color);
                 } else {
@@ -186,16 +644,16 @@ impl<'a> MonitorCanvasWidget<'a> {
         }
 
         // Helper to draw text centered horizontally
-        let draw_text = |buf: &mut Buffer, text: &str, y: i32, color: Color| {
-            let text_x = left + ((width as i32 - text.len() as i32) / 2).max(1);
+        let draw_text = |buf: &mut Buffer, text: &str, y: i64, color: Color| {
+            let text_x = left + ((width - text.chars().count() as i64) / 2).max(1);
             for (i, ch) in text.chars().enumerate() {
-                let x = text_x + i as i32;
-                if x >= canvas_area.x as i32
-                    && x < (canvas_area.x + canvas_area.width) as i32
-                    && y >= canvas_area.y as i32
-                    && y < (canvas_area.y + canvas_area.height) as i32
+                let x = text_x + i as i64;
+                if x >= canvas_left
+                    && x < canvas_right
+                    && y >= canvas_top
+                    && y < canvas_bottom
                     && x > left
-                    && x < left + width as i32 - 1
+                    && x < left + width - 1
                 {
                     buf[(x as u16, y as u16)]
                         .set_char(ch)
@@ -209,19 +667,24 @@ impl<'a> MonitorCanvasWidget<'a> {
         let name_y = if height >= 4 {
             top + 1
         } else {
-            top + (height as i32 / 2)
+            top + height / 2
         };
-        draw_text(buf, name, name_y, text_color);
-
-        // Draw position below name if there's room
-        if height >= 3 {
-            let pos_str = format!("{},{}", pos.x, pos.y);
-            let pos_y = if height >= 4 { name_y + 1 } else { name_y };
-            // Only draw position on separate line if room
+        // Unsaved moves are marked like in the output list
+        let label = if modified { format!("{name} *") } else { name.to_string() };
+        draw_text(buf, &label, name_y, text_color);
+
+        // Draw the label and position below the name if there's room; the
+        // label wins when only one fits
+        let mut next_y = name_y + 1;
+        if let Some(label) = self.labels.and_then(|labels| labels.label(name)) {
             if height >= 4 {
-                draw_text(buf, &pos_str, pos_y, Color::DarkGray);
+                draw_text(buf, label, next_y, Color::Magenta);
+                next_y += 1;
             }
         }
+        if next_y < top + height - 1 {
+            draw_text(buf, &format!("{},{}", pos.x, pos.y), next_y, Color::DarkGray);
+        }
     }
 }
 
@@ -235,11 +698,19 @@ impl<'a> Widget for MonitorCanvasWidget<'a> {
 
         // Get bounds for title
         let (min_x, min_y, max_x, max_y) = self.get_bounds();
-        let title = if min_x != i32::MAX {
+        let mut title = if min_x != i64::MAX {
             format!(" Layout ({min_x},{min_y}) to ({max_x},{max_y}) ")
         } else {
-            " Monitor Layout ".to_string()
+            format!(" {} ", tr("Monitor Layout"))
         };
+        if self.viewport.focus_selected {
+            if let Some(output) = self.view_model.selected_output() {
+                title.push_str(&format!("[zoomed: {}] ", output.name));
+            }
+        }
+        if self.viewport.pan != (0, 0) {
+            title.push_str("[panned, 0: reset] ");
+        }
 
         let block = Block::default()
             .title(title)
@@ -257,24 +728,35 @@ impl<'a> Widget for MonitorCanvasWidget<'a> {
         }
 
         // Draw each monitor
+        let issues = self.view_model.layout_issues(self.appearance);
         for (idx, output) in self.view_model.outputs.iter().enumerate() {
-            if !output.enabled {
+            let Some(size) = self.staged_size(output) else {
                 continue;
-            }
+            };
 
             let pos = self.view_model.get_display_position(&output.name).unwrap_or(output.position);
-            let selected = idx == self.view_model.selected_index;
-            let modified = self.view_model.pending_changes.contains_key(&output.name);
+            let selected = idx == self.view_model.list.selected_index;
+            let modified = self.view_model.is_modified(&output.name);
+            let dimmed = !self.view_model.is_visible(output);
+            let flagged = issues.iter().any(|issue| issue.involves(&output.name));
 
             self.draw_monitor(
                 buf,
                 inner,
                 &output.name,
                 pos,
-                output.logical_size,
+                size,
                 selected,
                 modified,
+                dimmed,
+                flagged,
             );
         }
+
+        self.draw_dpi_warnings(buf, inner);
+        self.draw_minimap(buf, inner);
+        self.draw_column_preview(buf, inner);
+        // Last, so the insets don't hide where a monitor went
+        self.draw_offscreen_indicators(buf, inner);
     }
 }
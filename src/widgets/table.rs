@@ -0,0 +1,102 @@
+use ratatui::{buffer::Buffer, style::Style};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// A column in a text table rendered one row per line
+#[derive(Debug, Clone, Copy)]
+pub struct Column {
+    pub title: &'static str,
+    /// Preferred width; 0 makes the column take whatever space is left
+    pub width: u16,
+    /// Narrowest the column may get before it is dropped
+    pub min_width: u16,
+}
+
+impl Column {
+    pub fn fixed(title: &'static str, width: u16, min_width: u16) -> Self {
+        Self { title, width, min_width: min_width.min(width) }
+    }
+
+    pub fn fill(title: &'static str, min_width: u16) -> Self {
+        Self { title, width: 0, min_width }
+    }
+}
+
+/// Space between columns
+const GAP: u16 = 1;
+
+/// Work out column widths for the available space.
+///
+/// Fixed columns shrink towards their minimum first; if that still doesn't
+/// fit, columns are dropped from the right. The returned widths cover the
+/// columns that are shown, in order.
+pub fn column_widths(columns: &[Column], available: u16) -> Vec<u16> {
+    for shown in (1..=columns.len()).rev() {
+        let columns = &columns[..shown];
+        let gaps = GAP * (shown as u16 - 1);
+        let preferred: u16 = columns.iter().map(|c| c.width.max(c.min_width)).sum::<u16>() + gaps;
+        let minimum: u16 = columns.iter().map(|c| c.min_width).sum::<u16>() + gaps;
+        if minimum > available && shown > 1 {
+            continue;
+        }
+
+        // Share out whatever the fixed columns can't have at their preferred width
+        let mut shortfall = preferred.saturating_sub(available);
+        let mut widths: Vec<u16> = columns
+            .iter()
+            .rev()
+            .map(|c| {
+                let width = c.width.max(c.min_width);
+                let give = shortfall.min(width - c.min_width);
+                shortfall -= give;
+                width - give
+            })
+            .collect();
+        widths.reverse();
+
+        let used: u16 = widths.iter().sum::<u16>() + gaps;
+        if let Some(fill) = columns.iter().position(|c| c.width == 0) {
+            widths[fill] += available.saturating_sub(used);
+        }
+        return widths;
+    }
+    Vec::new()
+}
+
+/// Fit text to exactly `width` terminal columns, truncating with "..." or
+/// padding. Wide characters such as CJK or emoji take two columns.
+pub fn fit(text: &str, width: usize) -> String {
+    let text_width = text.width();
+    if text_width <= width {
+        return format!("{text}{}", " ".repeat(width - text_width));
+    }
+    let budget = width.saturating_sub(3);
+    let mut head = String::new();
+    let mut used = 0;
+    for c in text.chars() {
+        let char_width = c.width().unwrap_or(0);
+        if used + char_width > budget {
+            break;
+        }
+        used += char_width;
+        head.push(c);
+    }
+    // A wide character that didn't fit leaves a column to pad
+    let dots = ".".repeat((width - used).min(3));
+    let pad = width - used - dots.len();
+    format!("{head}{dots}{}", " ".repeat(pad))
+}
+
+/// Render one row of cells at the given column widths
+pub fn render_row(buf: &mut Buffer, x: u16, y: u16, widths: &[u16], cells: &[(&str, Style)]) {
+    let mut x = x;
+    for (width, (text, style)) in widths.iter().zip(cells) {
+        buf.set_stringn(x, y, fit(text, *width as usize), *width as usize, *style);
+        x += width + GAP;
+    }
+}
+
+/// Render the column titles for the shown columns
+pub fn render_header(buf: &mut Buffer, x: u16, y: u16, columns: &[Column], widths: &[u16], style: Style) {
+    let titles: Vec<(&str, Style)> = columns.iter().map(|c| (c.title, style)).collect();
+    render_row(buf, x, y, widths, &titles);
+}